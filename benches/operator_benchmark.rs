@@ -0,0 +1,139 @@
+//! Operator Throughput Benchmarks
+//!
+//! This benchmark suite measures the rows per second of the core operators
+//! on large tables built by the `datagen` module, so the numbers are
+//! reproducible without any CSV files.
+//!
+//! Run benchmarks with:
+//!   cargo bench --bench operator_benchmark
+//!
+//! The table has 1,000,000 rows by default; set `MINI_OLAP_BENCH_ROWS` to
+//! use another size. Criterion reports each result as a throughput in
+//! elements (rows) per second.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use mini_rust_olap::catalog::Catalog;
+use mini_rust_olap::datagen::{generate_table, ColumnSpec, TableSpec};
+use mini_rust_olap::execution::{Operator, TableScan};
+use mini_rust_olap::planner::execute_sql;
+use mini_rust_olap::table::Table;
+
+/// Number of rows in the generated table
+fn bench_rows() -> usize {
+    std::env::var("MINI_OLAP_BENCH_ROWS")
+        .ok()
+        .and_then(|rows| rows.parse().ok())
+        .unwrap_or(1_000_000)
+}
+
+/// The benchmark table: the general-purpose columns plus grouping keys with
+/// 10, 10k, and 1M distinct values
+fn bench_table(rows: usize) -> Table {
+    let categories = |cardinality| ColumnSpec::Categorical {
+        cardinality,
+        zipf_exponent: None,
+    };
+    let spec = TableSpec::benchmark(42)
+        .column("g10", categories(10))
+        .column("g10k", categories(10_000))
+        .column("g1m", categories(1_000_000));
+    generate_table("bench", rows, &spec).expect("valid benchmark spec")
+}
+
+fn bench_catalog(table: &Table) -> Catalog {
+    let mut catalog = Catalog::new();
+    catalog.register_table(table.clone()).unwrap();
+    catalog
+}
+
+/// Run a query and return the number of result rows
+fn run(catalog: &Catalog, sql: &str) -> usize {
+    let (_, batches) = execute_sql(catalog, sql).unwrap();
+    batches.iter().map(|batch| batch.row_count()).sum()
+}
+
+/// Benchmark draining a TableScan over every column
+fn benchmark_scan(c: &mut Criterion) {
+    let rows = bench_rows();
+    let table = bench_table(rows);
+
+    let mut group = c.benchmark_group("datagen_scan");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(rows as u64));
+    group.bench_function("table_scan", |b| {
+        b.iter(|| {
+            let mut scan = TableScan::new(table.clone());
+            scan.open().unwrap();
+            let mut total = 0;
+            while let Some(batch) = scan.next_batch().unwrap() {
+                total += batch.row_count();
+            }
+            black_box(total)
+        });
+    });
+    group.finish();
+}
+
+/// Benchmark Filter at 1%, 10%, 50%, and 100% selectivity
+fn benchmark_filter_selectivity(c: &mut Criterion) {
+    let rows = bench_rows();
+    let catalog = bench_catalog(&bench_table(rows));
+
+    let mut group = c.benchmark_group("datagen_filter");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(rows as u64));
+    // `value` is uniform in 0..=999
+    for percent in [1, 10, 50, 100] {
+        let sql = format!("SELECT id FROM bench WHERE value < {}", percent * 10);
+        group.bench_function(format!("selectivity_{}pct", percent), |b| {
+            b.iter(|| run(black_box(&catalog), black_box(&sql)))
+        });
+    }
+    group.finish();
+}
+
+/// Benchmark GroupBy with 10, 10k, and 1M groups
+fn benchmark_group_by(c: &mut Criterion) {
+    let rows = bench_rows();
+    let catalog = bench_catalog(&bench_table(rows));
+
+    let mut group = c.benchmark_group("datagen_group_by");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(rows as u64));
+    for key in ["g10", "g10k", "g1m"] {
+        let sql = format!(
+            "SELECT {}, COUNT(*), SUM(price) FROM bench GROUP BY {}",
+            key, key
+        );
+        group.bench_function(key, |b| {
+            b.iter(|| run(black_box(&catalog), black_box(&sql)))
+        });
+    }
+    group.finish();
+}
+
+/// Benchmark a full Sort on a Float64 and a String key
+fn benchmark_sort(c: &mut Criterion) {
+    let rows = bench_rows();
+    let catalog = bench_catalog(&bench_table(rows));
+
+    let mut group = c.benchmark_group("datagen_sort");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(rows as u64));
+    for key in ["price", "customer"] {
+        let sql = format!("SELECT id, {} FROM bench ORDER BY {}", key, key);
+        group.bench_function(key, |b| {
+            b.iter(|| run(black_box(&catalog), black_box(&sql)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_scan,
+    benchmark_filter_selectivity,
+    benchmark_group_by,
+    benchmark_sort
+);
+criterion_main!(benches);
@@ -12,10 +12,17 @@
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use mini_rust_olap::catalog::Catalog;
+use mini_rust_olap::column::{Column, IntColumn, StringColumn};
+use mini_rust_olap::execution::{
+    Batch, BinaryComparison, ComparisonOp, Operator, Predicate, TableScan,
+};
 use mini_rust_olap::ingest::load_csv_into_catalog;
 use mini_rust_olap::parser::Parser;
 use mini_rust_olap::planner::Planner;
+use mini_rust_olap::table::Table;
+use mini_rust_olap::types::{DataType, Value};
 use std::path::Path;
+use std::sync::Arc;
 
 /// Execute a SQL query and return the total row count
 fn execute_query(catalog: &Catalog, sql: &str) -> Result<usize, Box<dyn std::error::Error>> {
@@ -213,8 +220,77 @@ fn benchmark_full_query(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark scanning in-memory tables of growing size; batches are slices
+/// of the table's columns, so the time per row should stay flat
+fn benchmark_table_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("table_scan");
+
+    for rows in [100_000, 1_000_000] {
+        let mut ids = IntColumn::new();
+        let mut names = StringColumn::new();
+        for i in 0..rows {
+            ids.push_value(Value::Int64(i)).unwrap();
+            names
+                .push_value(Value::String(format!("name{}", i % 1000)))
+                .unwrap();
+        }
+        let mut table = Table::new("t".to_string());
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+
+        group.bench_function(format!("{}_rows", rows), |b| {
+            b.iter(|| {
+                let mut scan = TableScan::new(table.clone());
+                scan.open().unwrap();
+                let mut total = 0;
+                while let Some(batch) = scan.next_batch().unwrap() {
+                    total += batch.row_count();
+                }
+                black_box(total)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark per-row predicate evaluation, unbound vs bound at open
+fn benchmark_predicate_eval(c: &mut Criterion) {
+    let mut group = c.benchmark_group("predicate_eval");
+
+    let mut column = IntColumn::new();
+    for i in 0..10_000 {
+        column.push_value(Value::Int64(i)).unwrap();
+    }
+    let batch = Batch::new(vec![Arc::new(column)]);
+    let predicate = BinaryComparison::new(0, ComparisonOp::GreaterThan, Value::Float64(5000.5));
+    let bound = predicate.bind(&[DataType::Int64]).unwrap();
+
+    group.bench_function("unbound", |b| {
+        b.iter(|| {
+            (0..batch.row_count())
+                .filter(|&row| predicate.eval(black_box(&batch), row).unwrap())
+                .count()
+        });
+    });
+
+    group.bench_function("bound", |b| {
+        b.iter(|| {
+            (0..batch.row_count())
+                .filter(|&row| bound.eval(black_box(&batch), row).unwrap())
+                .count()
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
+    benchmark_predicate_eval,
+    benchmark_table_scan,
     benchmark_sql_parsing,
     benchmark_full_scan,
     benchmark_filter,
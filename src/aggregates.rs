@@ -9,6 +9,11 @@
 //! - **SUM**: Total of numeric values
 //! - **AVG**: Average of numeric values
 //! - **MIN/MAX**: Range of values
+//! - **STDDEV/VARIANCE**: Spread of numeric values, in sample and population forms
+//! - **MEDIAN/PERCENTILE/APPROX_QUANTILE**: Distribution cut points such as p50/p95/p99
+//!
+//! Any aggregate can be combined with DISTINCT (e.g. `SUM(DISTINCT x)`) by
+//! wrapping it in a [`DistinctAdapter`].
 //!
 //! ## Aggregate Function Design
 //!
@@ -33,7 +38,8 @@
 //! ```
 
 use crate::error::{DatabaseError, Result};
-use crate::types::{DataType, Value};
+use crate::types::{canonical_float_bits, DataType, Value};
+use std::collections::HashSet;
 use std::fmt;
 
 /// A trait that defines the interface for aggregate functions.
@@ -65,7 +71,8 @@ pub trait AggregateFunction: fmt::Debug {
     ///
     /// # Arguments
     ///
-    /// * `value` - The value to add to the aggregate (None for NULL)
+    /// * `value` - The value to add to the aggregate (None or
+    ///   `Some(Value::Null)` for NULL; NULLs are skipped)
     ///
     /// # Returns
     ///
@@ -93,11 +100,18 @@ pub trait AggregateFunction: fmt::Debug {
 
     /// Get the final result of the aggregate.
     ///
-    /// Returns None if no non-NULL values were processed.
+    /// A query without GROUP BY reports this even for an empty input, so
+    /// each aggregate defines its result for no (non-NULL) values:
+    ///
+    /// - COUNT and COUNT(*): `0`
+    /// - SUM: `0` (or `0.0`) of its output type
+    /// - MIN, MAX, AVG, VARIANCE, STDDEV, PERCENTILE and APPROX_QUANTILE:
+    ///   None, shown as NULL
+    /// - DISTINCT variants: the result of the aggregate they wrap
     ///
     /// # Returns
     ///
-    /// The aggregated value, or None if no values were processed
+    /// The aggregated value, or None if there is no value to report
     fn result(&self) -> Option<Value>;
 
     /// Reset the aggregate to its initial state.
@@ -112,6 +126,17 @@ pub trait AggregateFunction: fmt::Debug {
     ///
     /// The DataType that this aggregate produces
     fn data_type(&self) -> DataType;
+
+    /// Clone the aggregate, state included, into a new box.
+    ///
+    /// GroupBy clones a fresh prototype for each group it discovers.
+    fn clone_box(&self) -> Box<dyn AggregateFunction>;
+}
+
+/// Normalises the two spellings of NULL an aggregate can be fed
+/// (`None` and `Some(Value::Null)`) to `None`.
+fn non_null(value: Option<Value>) -> Option<Value> {
+    value.filter(|v| !v.is_null())
 }
 
 // ============================================================================
@@ -139,6 +164,8 @@ pub trait AggregateFunction: fmt::Debug {
 #[derive(Debug, Clone)]
 pub struct CountAggregate {
     count: i64,
+    /// Count NULLs too (COUNT(*))
+    count_nulls: bool,
 }
 
 impl CountAggregate {
@@ -147,20 +174,39 @@ impl CountAggregate {
     /// The data_type parameter is ignored for COUNT since it always
     /// returns Int64, but is kept for API consistency.
     pub fn new(_data_type: DataType) -> Self {
-        CountAggregate { count: 0 }
+        CountAggregate {
+            count: 0,
+            count_nulls: false,
+        }
+    }
+
+    /// Create a COUNT(*) aggregate, which counts every value it is given,
+    /// NULL or not.
+    pub fn rows() -> Self {
+        CountAggregate {
+            count: 0,
+            count_nulls: true,
+        }
     }
 }
 
 impl AggregateFunction for CountAggregate {
     fn update(&mut self, value: Option<Value>) -> Result<()> {
-        if value.is_some() {
+        if self.count_nulls || non_null(value).is_some() {
             self.count += 1;
         }
         Ok(())
     }
 
     fn update_batch(&mut self, values: &[Option<Value>]) -> Result<()> {
-        self.count += values.iter().filter(|v| v.is_some()).count() as i64;
+        self.count += if self.count_nulls {
+            values.len()
+        } else {
+            values
+                .iter()
+                .filter(|v| v.as_ref().is_some_and(|v| !v.is_null()))
+                .count()
+        } as i64;
         Ok(())
     }
 
@@ -175,6 +221,10 @@ impl AggregateFunction for CountAggregate {
     fn data_type(&self) -> DataType {
         DataType::Int64
     }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
 }
 
 // ============================================================================
@@ -186,13 +236,57 @@ impl AggregateFunction for CountAggregate {
 /// SUM works with Int64 and Float64 data types and returns a value
 /// of the same type as the input.
 ///
+/// # Overflow
+///
+/// An Int64 sum is exact or an error: every addition is checked, and the
+/// first one that leaves the Int64 range fails the update with an error
+/// naming the running total and the value, rather than wrapping. The check
+/// is on the running total, so a sum whose final value would fit can still
+/// fail if a prefix of the values overflows. AVG totals in Float64 and
+/// does not overflow.
+///
+/// # Accuracy
+///
+/// A Float64 sum uses compensated (Kahan-Babuska) summation, so the error
+/// no longer grows with the number of values: summing millions of small
+/// values comes within an ulp or two of the exact total.
+///
 /// # Type Safety
 ///
 /// SUM will return an error if applied to String values.
 #[derive(Debug, Clone)]
 pub enum SumAggregate {
     Int64(i64),
-    Float64(f64),
+    Float64(CompensatedSum),
+}
+
+/// A running Float64 sum with Kahan-Babuska (Neumaier) compensation.
+///
+/// Alongside the sum it keeps the low-order bits each addition rounded
+/// away, and adds them back in at the end.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    /// Add a value to the sum.
+    pub fn add(&mut self, value: f64) {
+        let total = self.sum + value;
+        // Whichever operand is smaller lost its low-order bits
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - total) + value;
+        } else {
+            self.compensation += (value - total) + self.sum;
+        }
+        self.sum = total;
+    }
+
+    /// The compensated total.
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
 }
 
 impl SumAggregate {
@@ -208,21 +302,28 @@ impl SumAggregate {
     pub fn new(data_type: DataType) -> Result<Self> {
         match data_type {
             DataType::Int64 => Ok(SumAggregate::Int64(0)),
-            DataType::Float64 => Ok(SumAggregate::Float64(0.0)),
-            DataType::String => Err(DatabaseError::type_error(
-                "SUM cannot be applied to String type".to_string(),
-            )),
+            DataType::Float64 => Ok(SumAggregate::Float64(CompensatedSum::default())),
+            DataType::String | DataType::Boolean | DataType::Date => Err(
+                DatabaseError::type_error(format!("SUM cannot be applied to {} type", data_type)),
+            ),
         }
     }
 }
 
 impl AggregateFunction for SumAggregate {
     fn update(&mut self, value: Option<Value>) -> Result<()> {
-        if let Some(value) = value {
+        if let Some(value) = non_null(value) {
             match (self, value) {
-                (SumAggregate::Int64(sum), Value::Int64(v)) => *sum += v,
-                (SumAggregate::Float64(sum), Value::Float64(v)) => *sum += v,
-                (SumAggregate::Float64(sum), Value::Int64(v)) => *sum += v as f64,
+                (SumAggregate::Int64(sum), Value::Int64(v)) => {
+                    *sum = sum.checked_add(v).ok_or_else(|| {
+                        DatabaseError::execution_error(format!(
+                            "SUM overflowed Int64 adding {} to a running total of {}",
+                            v, sum
+                        ))
+                    })?
+                }
+                (SumAggregate::Float64(sum), Value::Float64(v)) => sum.add(v),
+                (SumAggregate::Float64(sum), Value::Int64(v)) => sum.add(v as f64),
                 (_, other) => {
                     return Err(DatabaseError::type_error(format!(
                         "Incompatible type for SUM: {:?}",
@@ -244,14 +345,14 @@ impl AggregateFunction for SumAggregate {
     fn result(&self) -> Option<Value> {
         match self {
             SumAggregate::Int64(sum) => Some(Value::Int64(*sum)),
-            SumAggregate::Float64(sum) => Some(Value::Float64(*sum)),
+            SumAggregate::Float64(sum) => Some(Value::Float64(sum.value())),
         }
     }
 
     fn reset(&mut self) {
         match self {
             SumAggregate::Int64(sum) => *sum = 0,
-            SumAggregate::Float64(sum) => *sum = 0.0,
+            SumAggregate::Float64(sum) => *sum = CompensatedSum::default(),
         }
     }
 
@@ -261,6 +362,10 @@ impl AggregateFunction for SumAggregate {
             SumAggregate::Float64(_) => DataType::Float64,
         }
     }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
 }
 
 // ============================================================================
@@ -269,13 +374,18 @@ impl AggregateFunction for SumAggregate {
 
 /// Finds the minimum non-NULL value.
 ///
-/// MIN works with all data types (Int64, Float64, String) and
-/// returns a value of the same type as the input.
+/// MIN works with all data types (Int64, Float64, String, Boolean, Date)
+/// and returns a value of the same type as the input; FALSE sorts before TRUE
+/// and dates compare chronologically.
+/// Strings compare lexicographically by their UTF-8 bytes, so uppercase
+/// letters sort before lowercase ones.
 #[derive(Debug, Clone)]
 pub enum MinAggregate {
     Int64(Option<i64>),
     Float64(Option<f64>),
     String(Option<String>),
+    Boolean(Option<bool>),
+    Date(Option<i32>),
 }
 
 impl MinAggregate {
@@ -289,13 +399,15 @@ impl MinAggregate {
             DataType::Int64 => MinAggregate::Int64(None),
             DataType::Float64 => MinAggregate::Float64(None),
             DataType::String => MinAggregate::String(None),
+            DataType::Boolean => MinAggregate::Boolean(None),
+            DataType::Date => MinAggregate::Date(None),
         }
     }
 }
 
 impl AggregateFunction for MinAggregate {
     fn update(&mut self, value: Option<Value>) -> Result<()> {
-        if let Some(value) = value {
+        if let Some(value) = non_null(value) {
             match (self, value) {
                 (MinAggregate::Int64(min), Value::Int64(v)) => {
                     *min = Some(min.map_or(v, |m| m.min(v)))
@@ -303,8 +415,16 @@ impl AggregateFunction for MinAggregate {
                 (MinAggregate::Float64(min), Value::Float64(v)) => {
                     *min = Some(min.map_or(v, |m| m.min(v)))
                 }
+                (MinAggregate::Boolean(min), Value::Boolean(v)) => {
+                    *min = Some(min.map_or(v, |m| m.min(v)))
+                }
+                (MinAggregate::Date(min), Value::Date(v)) => {
+                    *min = Some(min.map_or(v, |m| m.min(v)))
+                }
                 (MinAggregate::String(min), Value::String(v)) => {
-                    *min = Some(min.clone().map_or(v.clone(), |m| if m < v { m } else { v }))
+                    if min.as_ref().is_none_or(|m| v < *m) {
+                        *min = Some(v);
+                    }
                 }
                 (_, other) => {
                     return Err(DatabaseError::type_error(format!(
@@ -329,6 +449,8 @@ impl AggregateFunction for MinAggregate {
             MinAggregate::Int64(min) => min.map(Value::Int64),
             MinAggregate::Float64(min) => min.map(Value::Float64),
             MinAggregate::String(min) => min.clone().map(Value::String),
+            MinAggregate::Boolean(min) => min.map(Value::Boolean),
+            MinAggregate::Date(min) => min.map(Value::Date),
         }
     }
 
@@ -337,6 +459,8 @@ impl AggregateFunction for MinAggregate {
             MinAggregate::Int64(min) => *min = None,
             MinAggregate::Float64(min) => *min = None,
             MinAggregate::String(min) => *min = None,
+            MinAggregate::Boolean(min) => *min = None,
+            MinAggregate::Date(min) => *min = None,
         }
     }
 
@@ -345,8 +469,14 @@ impl AggregateFunction for MinAggregate {
             MinAggregate::Int64(_) => DataType::Int64,
             MinAggregate::Float64(_) => DataType::Float64,
             MinAggregate::String(_) => DataType::String,
+            MinAggregate::Boolean(_) => DataType::Boolean,
+            MinAggregate::Date(_) => DataType::Date,
         }
     }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
 }
 
 // ============================================================================
@@ -355,13 +485,18 @@ impl AggregateFunction for MinAggregate {
 
 /// Finds the maximum non-NULL value.
 ///
-/// MAX works with all data types (Int64, Float64, String) and
-/// returns a value of the same type as the input.
+/// MAX works with all data types (Int64, Float64, String, Boolean, Date)
+/// and returns a value of the same type as the input; FALSE sorts before TRUE
+/// and dates compare chronologically.
+/// Strings compare lexicographically by their UTF-8 bytes, so uppercase
+/// letters sort before lowercase ones.
 #[derive(Debug, Clone)]
 pub enum MaxAggregate {
     Int64(Option<i64>),
     Float64(Option<f64>),
     String(Option<String>),
+    Boolean(Option<bool>),
+    Date(Option<i32>),
 }
 
 impl MaxAggregate {
@@ -375,13 +510,15 @@ impl MaxAggregate {
             DataType::Int64 => MaxAggregate::Int64(None),
             DataType::Float64 => MaxAggregate::Float64(None),
             DataType::String => MaxAggregate::String(None),
+            DataType::Boolean => MaxAggregate::Boolean(None),
+            DataType::Date => MaxAggregate::Date(None),
         }
     }
 }
 
 impl AggregateFunction for MaxAggregate {
     fn update(&mut self, value: Option<Value>) -> Result<()> {
-        if let Some(value) = value {
+        if let Some(value) = non_null(value) {
             match (self, value) {
                 (MaxAggregate::Int64(max), Value::Int64(v)) => {
                     *max = Some(max.map_or(v, |m| m.max(v)))
@@ -389,8 +526,16 @@ impl AggregateFunction for MaxAggregate {
                 (MaxAggregate::Float64(max), Value::Float64(v)) => {
                     *max = Some(max.map_or(v, |m| m.max(v)))
                 }
+                (MaxAggregate::Boolean(max), Value::Boolean(v)) => {
+                    *max = Some(max.map_or(v, |m| m.max(v)))
+                }
+                (MaxAggregate::Date(max), Value::Date(v)) => {
+                    *max = Some(max.map_or(v, |m| m.max(v)))
+                }
                 (MaxAggregate::String(max), Value::String(v)) => {
-                    *max = Some(max.clone().map_or(v.clone(), |m| if m > v { m } else { v }))
+                    if max.as_ref().is_none_or(|m| v > *m) {
+                        *max = Some(v);
+                    }
                 }
                 (_, other) => {
                     return Err(DatabaseError::type_error(format!(
@@ -415,6 +560,8 @@ impl AggregateFunction for MaxAggregate {
             MaxAggregate::Int64(max) => max.map(Value::Int64),
             MaxAggregate::Float64(max) => max.map(Value::Float64),
             MaxAggregate::String(max) => max.clone().map(Value::String),
+            MaxAggregate::Boolean(max) => max.map(Value::Boolean),
+            MaxAggregate::Date(max) => max.map(Value::Date),
         }
     }
 
@@ -423,6 +570,8 @@ impl AggregateFunction for MaxAggregate {
             MaxAggregate::Int64(max) => *max = None,
             MaxAggregate::Float64(max) => *max = None,
             MaxAggregate::String(max) => *max = None,
+            MaxAggregate::Boolean(max) => *max = None,
+            MaxAggregate::Date(max) => *max = None,
         }
     }
 
@@ -431,8 +580,14 @@ impl AggregateFunction for MaxAggregate {
             MaxAggregate::Int64(_) => DataType::Int64,
             MaxAggregate::Float64(_) => DataType::Float64,
             MaxAggregate::String(_) => DataType::String,
+            MaxAggregate::Boolean(_) => DataType::Boolean,
+            MaxAggregate::Date(_) => DataType::Date,
         }
     }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
 }
 
 // ============================================================================
@@ -442,14 +597,16 @@ impl AggregateFunction for MaxAggregate {
 /// Computes the average of non-NULL numeric values.
 ///
 /// AVG works with Int64 and Float64 data types and always returns
-/// Float64 to preserve fractional results.
+/// Float64 to preserve fractional results. The values are totalled with the
+/// same compensated summation as a Float64 SUM, so AVG cannot overflow and
+/// stays accurate over millions of values.
 ///
 /// # Type Safety
 ///
 /// AVG will return an error if applied to String values.
 #[derive(Debug, Clone)]
 pub struct AvgAggregate {
-    sum: f64,
+    sum: CompensatedSum,
     count: i64,
 }
 
@@ -465,24 +622,27 @@ impl AvgAggregate {
     /// A new AvgAggregate instance, or an error if data_type is not numeric
     pub fn new(data_type: DataType) -> Result<Self> {
         match data_type {
-            DataType::Int64 | DataType::Float64 => Ok(AvgAggregate { sum: 0.0, count: 0 }),
-            DataType::String => Err(DatabaseError::type_error(
-                "AVG cannot be applied to String type".to_string(),
-            )),
+            DataType::Int64 | DataType::Float64 => Ok(AvgAggregate {
+                sum: CompensatedSum::default(),
+                count: 0,
+            }),
+            DataType::String | DataType::Boolean | DataType::Date => Err(
+                DatabaseError::type_error(format!("AVG cannot be applied to {} type", data_type)),
+            ),
         }
     }
 }
 
 impl AggregateFunction for AvgAggregate {
     fn update(&mut self, value: Option<Value>) -> Result<()> {
-        if let Some(value) = value {
+        if let Some(value) = non_null(value) {
             match value {
                 Value::Int64(v) => {
-                    self.sum += v as f64;
+                    self.sum.add(v as f64);
                     self.count += 1;
                 }
                 Value::Float64(v) => {
-                    self.sum += v;
+                    self.sum.add(v);
                     self.count += 1;
                 }
                 other => {
@@ -507,18 +667,581 @@ impl AggregateFunction for AvgAggregate {
         if self.count == 0 {
             None
         } else {
-            Some(Value::Float64(self.sum / self.count as f64))
+            Some(Value::Float64(self.sum.value() / self.count as f64))
         }
     }
 
     fn reset(&mut self) {
-        self.sum = 0.0;
+        self.sum = CompensatedSum::default();
         self.count = 0;
     }
 
     fn data_type(&self) -> DataType {
         DataType::Float64
     }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+// ============================================================================
+// VARIANCE AGGREGATES
+// ============================================================================
+
+/// Which variance a [`VarianceAggregate`] or [`StdDevAggregate`] computes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarianceKind {
+    /// Divides by `n - 1`; estimates the variance of a population from a
+    /// sample of it. Undefined (NULL) for fewer than two values.
+    Sample,
+    /// Divides by `n`; the variance of exactly the values seen. Zero for a
+    /// single value.
+    Population,
+}
+
+/// Running count, mean and sum of squared deviations (Welford's algorithm).
+///
+/// Each value updates the state in O(1) without keeping the values, and
+/// avoids the cancellation of the naive `sum(x²) - sum(x)²/n` formula.
+#[derive(Debug, Clone, Default)]
+struct Welford {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Welford {
+    fn add(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Adds a numeric input value, rejecting any other type.
+    fn update(&mut self, function: &str, value: Option<Value>) -> Result<()> {
+        match non_null(value) {
+            None => Ok(()),
+            Some(Value::Int64(v)) => {
+                self.add(v as f64);
+                Ok(())
+            }
+            Some(Value::Float64(v)) => {
+                self.add(v);
+                Ok(())
+            }
+            Some(other) => Err(DatabaseError::type_error(format!(
+                "Incompatible type for {}: {:?}",
+                function, other
+            ))),
+        }
+    }
+
+    fn variance(&self, kind: VarianceKind) -> Option<f64> {
+        match kind {
+            VarianceKind::Sample if self.count > 1 => Some(self.m2 / (self.count - 1) as f64),
+            VarianceKind::Population if self.count > 0 => Some(self.m2 / self.count as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Checks that a variance aggregate's input type is numeric.
+fn check_variance_input(function: &str, data_type: DataType) -> Result<()> {
+    if data_type.is_numeric() {
+        Ok(())
+    } else {
+        Err(DatabaseError::type_error(format!(
+            "{} cannot be applied to {} type",
+            function, data_type
+        )))
+    }
+}
+
+/// Computes the variance of non-NULL numeric values.
+///
+/// Accepts Int64 and Float64 input and returns Float64. Values are folded in
+/// one at a time with Welford's online algorithm, so the aggregate needs a
+/// single pass and constant memory per group.
+///
+/// With [`VarianceKind::Sample`] (SQL `VARIANCE` / `VAR_SAMP`) a group with
+/// a single value has no sample variance and yields NULL; with
+/// [`VarianceKind::Population`] (`VAR_POP`) it yields 0.
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::aggregates::{AggregateFunction, VarianceAggregate, VarianceKind};
+/// use mini_rust_olap::types::{DataType, Value};
+///
+/// let mut var = VarianceAggregate::new(DataType::Int64, VarianceKind::Population)?;
+/// for v in [2, 4, 4, 4, 5, 5, 7, 9] {
+///     var.update(Some(Value::Int64(v)))?;
+/// }
+/// assert_eq!(var.result(), Some(Value::Float64(4.0)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct VarianceAggregate {
+    kind: VarianceKind,
+    state: Welford,
+}
+
+impl VarianceAggregate {
+    /// Create a new VARIANCE aggregate.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type` - Must be Int64 or Float64
+    /// * `kind` - Sample or population variance
+    ///
+    /// # Returns
+    ///
+    /// A new VarianceAggregate, or an error if data_type is not numeric
+    pub fn new(data_type: DataType, kind: VarianceKind) -> Result<Self> {
+        check_variance_input("VARIANCE", data_type)?;
+        Ok(VarianceAggregate {
+            kind,
+            state: Welford::default(),
+        })
+    }
+}
+
+impl AggregateFunction for VarianceAggregate {
+    fn update(&mut self, value: Option<Value>) -> Result<()> {
+        self.state.update("VARIANCE", value)
+    }
+
+    fn result(&self) -> Option<Value> {
+        self.state.variance(self.kind).map(Value::Float64)
+    }
+
+    fn reset(&mut self) {
+        self.state = Welford::default();
+    }
+
+    fn data_type(&self) -> DataType {
+        DataType::Float64
+    }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+/// Computes the standard deviation of non-NULL numeric values.
+///
+/// The square root of [`VarianceAggregate`]'s result, with the same input
+/// types and single-pass state. A single value yields NULL for the sample
+/// standard deviation (SQL `STDDEV` / `STDDEV_SAMP`) and 0 for the
+/// population one (`STDDEV_POP`).
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::aggregates::{AggregateFunction, StdDevAggregate, VarianceKind};
+/// use mini_rust_olap::types::{DataType, Value};
+///
+/// let mut stddev = StdDevAggregate::new(DataType::Int64, VarianceKind::Population)?;
+/// for v in [2, 4, 4, 4, 5, 5, 7, 9] {
+///     stddev.update(Some(Value::Int64(v)))?;
+/// }
+/// assert_eq!(stddev.result(), Some(Value::Float64(2.0)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StdDevAggregate {
+    kind: VarianceKind,
+    state: Welford,
+}
+
+impl StdDevAggregate {
+    /// Create a new STDDEV aggregate.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type` - Must be Int64 or Float64
+    /// * `kind` - Sample or population standard deviation
+    ///
+    /// # Returns
+    ///
+    /// A new StdDevAggregate, or an error if data_type is not numeric
+    pub fn new(data_type: DataType, kind: VarianceKind) -> Result<Self> {
+        check_variance_input("STDDEV", data_type)?;
+        Ok(StdDevAggregate {
+            kind,
+            state: Welford::default(),
+        })
+    }
+}
+
+impl AggregateFunction for StdDevAggregate {
+    fn update(&mut self, value: Option<Value>) -> Result<()> {
+        self.state.update("STDDEV", value)
+    }
+
+    fn result(&self) -> Option<Value> {
+        self.state
+            .variance(self.kind)
+            .map(|variance| Value::Float64(variance.sqrt()))
+    }
+
+    fn reset(&mut self) {
+        self.state = Welford::default();
+    }
+
+    fn data_type(&self) -> DataType {
+        DataType::Float64
+    }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+// ============================================================================
+// QUANTILE AGGREGATES
+// ============================================================================
+
+/// Checks that a quantile lies in [0, 1].
+fn validate_quantile(function: &str, quantile: f64) -> Result<()> {
+    if (0.0..=1.0).contains(&quantile) {
+        Ok(())
+    } else {
+        Err(DatabaseError::type_error(format!(
+            "{} quantile must be in [0, 1], got {}",
+            function, quantile
+        )))
+    }
+}
+
+/// Extracts a numeric input value as f64 for the quantile aggregates.
+fn quantile_input(function: &str, value: Value) -> Result<f64> {
+    match value {
+        Value::Int64(v) => Ok(v as f64),
+        Value::Float64(v) => Ok(v),
+        other => Err(DatabaseError::type_error(format!(
+            "Incompatible type for {}: {:?}",
+            function, other
+        ))),
+    }
+}
+
+/// Computes the quantile of already sorted values with linear interpolation.
+///
+/// The quantile `q` maps to the fractional rank `q * (n - 1)`; the result
+/// interpolates between the two values on either side of that rank.
+fn interpolate_sorted(sorted: &[f64], quantile: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let rank = quantile * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let fraction = rank - lower as f64;
+
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+}
+
+/// Computes an exact quantile of non-NULL numeric values.
+///
+/// PERCENTILE buffers every input value and sorts them when the result is
+/// requested, interpolating linearly between neighbouring values. This is
+/// exact but needs memory proportional to the group size (8 bytes per
+/// value), so prefer [`ApproxQuantileAggregate`] for very large groups.
+///
+/// MEDIAN is the same aggregate with a quantile of 0.5. Quantile 0 gives the
+/// smallest value and quantile 1 the largest.
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::aggregates::{AggregateFunction, PercentileAggregate};
+/// use mini_rust_olap::types::{DataType, Value};
+///
+/// let mut p50 = PercentileAggregate::new(DataType::Int64, 0.5)?;
+/// for v in [1, 2, 3, 4] {
+///     p50.update(Some(Value::Int64(v)))?;
+/// }
+/// assert_eq!(p50.result(), Some(Value::Float64(2.5)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PercentileAggregate {
+    quantile: f64,
+    values: Vec<f64>,
+}
+
+impl PercentileAggregate {
+    /// Create a new PERCENTILE aggregate.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type` - Must be Int64 or Float64
+    /// * `quantile` - The quantile to compute, in [0, 1]
+    ///
+    /// # Returns
+    ///
+    /// A new PercentileAggregate, or an error for non-numeric input or an
+    /// out-of-range quantile
+    pub fn new(data_type: DataType, quantile: f64) -> Result<Self> {
+        if !data_type.is_numeric() {
+            return Err(DatabaseError::type_error(format!(
+                "PERCENTILE cannot be applied to {} type",
+                data_type
+            )));
+        }
+        validate_quantile("PERCENTILE", quantile)?;
+
+        Ok(PercentileAggregate {
+            quantile,
+            values: Vec::new(),
+        })
+    }
+}
+
+impl AggregateFunction for PercentileAggregate {
+    fn update(&mut self, value: Option<Value>) -> Result<()> {
+        if let Some(value) = non_null(value) {
+            self.values.push(quantile_input("PERCENTILE", value)?);
+        }
+        Ok(())
+    }
+
+    fn result(&self) -> Option<Value> {
+        let mut sorted = self.values.clone();
+        sorted.sort_by(f64::total_cmp);
+        interpolate_sorted(&sorted, self.quantile).map(Value::Float64)
+    }
+
+    fn reset(&mut self) {
+        self.values.clear();
+    }
+
+    fn data_type(&self) -> DataType {
+        DataType::Float64
+    }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+/// Number of values kept by [`ApproxQuantileAggregate`]'s reservoir
+pub const APPROX_QUANTILE_RESERVOIR_SIZE: usize = 4096;
+
+/// Computes an approximate quantile using a fixed-size reservoir sample.
+///
+/// The aggregate keeps a uniform random sample of at most
+/// [`APPROX_QUANTILE_RESERVOIR_SIZE`] values (reservoir sampling, "Algorithm
+/// R") and computes the exact quantile of the sample. Memory is therefore
+/// constant (32 KiB) regardless of group size.
+///
+/// # Error Bounds
+///
+/// Groups no larger than the reservoir are answered exactly. Beyond that the
+/// answer's *rank* has a standard error of `sqrt(q * (1 - q) / 4096)`, i.e. at
+/// most about 0.8 percentage points (at the median) and about 0.35 points for
+/// p95. For example p95 over uniformly distributed data typically lands
+/// between p94.3 and p95.7.
+///
+/// The sampler uses a fixed seed, so results are reproducible run to run.
+#[derive(Debug, Clone)]
+pub struct ApproxQuantileAggregate {
+    quantile: f64,
+    reservoir: Vec<f64>,
+    seen: u64,
+    rng_state: u64,
+}
+
+/// Seed for the reservoir sampler (any non-zero value works for xorshift)
+const APPROX_QUANTILE_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+impl ApproxQuantileAggregate {
+    /// Create a new APPROX_QUANTILE aggregate.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type` - Must be Int64 or Float64
+    /// * `quantile` - The quantile to compute, in [0, 1]
+    ///
+    /// # Returns
+    ///
+    /// A new ApproxQuantileAggregate, or an error for non-numeric input or
+    /// an out-of-range quantile
+    pub fn new(data_type: DataType, quantile: f64) -> Result<Self> {
+        if !data_type.is_numeric() {
+            return Err(DatabaseError::type_error(format!(
+                "APPROX_QUANTILE cannot be applied to {} type",
+                data_type
+            )));
+        }
+        validate_quantile("APPROX_QUANTILE", quantile)?;
+
+        Ok(ApproxQuantileAggregate {
+            quantile,
+            reservoir: Vec::new(),
+            seen: 0,
+            rng_state: APPROX_QUANTILE_SEED,
+        })
+    }
+
+    /// Returns the next pseudo-random number (xorshift64).
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        x
+    }
+}
+
+impl AggregateFunction for ApproxQuantileAggregate {
+    fn update(&mut self, value: Option<Value>) -> Result<()> {
+        if let Some(value) = non_null(value) {
+            let v = quantile_input("APPROX_QUANTILE", value)?;
+            self.seen += 1;
+
+            if self.reservoir.len() < APPROX_QUANTILE_RESERVOIR_SIZE {
+                self.reservoir.push(v);
+            } else {
+                // Keep the new value with probability reservoir_size / seen
+                let slot = self.next_random() % self.seen;
+                if (slot as usize) < APPROX_QUANTILE_RESERVOIR_SIZE {
+                    self.reservoir[slot as usize] = v;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn result(&self) -> Option<Value> {
+        let mut sorted = self.reservoir.clone();
+        sorted.sort_by(f64::total_cmp);
+        interpolate_sorted(&sorted, self.quantile).map(Value::Float64)
+    }
+
+    fn reset(&mut self) {
+        self.reservoir.clear();
+        self.seen = 0;
+        self.rng_state = APPROX_QUANTILE_SEED;
+    }
+
+    fn data_type(&self) -> DataType {
+        DataType::Float64
+    }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+// ============================================================================
+// DISTINCT ADAPTER
+// ============================================================================
+
+/// Hashable identity of a value seen by [`DistinctAdapter`].
+///
+/// Floats are keyed by their canonical bits, as GROUP BY keys are, so `0.0`
+/// and `-0.0` count as one value, as do all NaNs whatever their payload.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DistinctKey {
+    Int64(i64),
+    Float64(u64),
+    String(String),
+    Boolean(bool),
+    Date(i32),
+    Null,
+}
+
+impl From<&Value> for DistinctKey {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Int64(v) => DistinctKey::Int64(*v),
+            Value::Float64(v) => DistinctKey::Float64(canonical_float_bits(*v)),
+            Value::String(v) => DistinctKey::String(v.clone()),
+            Value::Boolean(v) => DistinctKey::Boolean(*v),
+            Value::Date(v) => DistinctKey::Date(*v),
+            Value::Null => DistinctKey::Null,
+        }
+    }
+}
+
+/// Applies the DISTINCT modifier to any aggregate function.
+///
+/// The adapter remembers every non-NULL value it has seen and forwards only
+/// the first occurrence of each to the wrapped aggregate, so `SUM(DISTINCT x)`
+/// is a `SumAggregate` that never sees a repeated value. The result and
+/// output type are those of the wrapped aggregate. `reset()` also forgets the
+/// seen values, which keeps groups independent.
+///
+/// The seen set holds one entry per distinct value in the group, so memory
+/// grows with the number of distinct values rather than the number of rows.
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::aggregates::{AggregateFunction, DistinctAdapter, SumAggregate};
+/// use mini_rust_olap::types::{DataType, Value};
+///
+/// let mut sum = DistinctAdapter::new(Box::new(SumAggregate::new(DataType::Int64)?));
+/// for v in [1, 2, 2, 3] {
+///     sum.update(Some(Value::Int64(v)))?;
+/// }
+/// assert_eq!(sum.result(), Some(Value::Int64(6)));
+/// ```
+#[derive(Debug)]
+pub struct DistinctAdapter {
+    inner: Box<dyn AggregateFunction>,
+    seen: HashSet<DistinctKey>,
+}
+
+impl DistinctAdapter {
+    /// Wraps an aggregate so it only sees distinct values.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The aggregate that receives the first occurrence of each value
+    pub fn new(inner: Box<dyn AggregateFunction>) -> Self {
+        DistinctAdapter {
+            inner,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl AggregateFunction for DistinctAdapter {
+    fn update(&mut self, value: Option<Value>) -> Result<()> {
+        match non_null(value) {
+            Some(value) => {
+                if self.seen.insert(DistinctKey::from(&value)) {
+                    self.inner.update(Some(value))?;
+                }
+                Ok(())
+            }
+            None => self.inner.update(None),
+        }
+    }
+
+    fn result(&self) -> Option<Value> {
+        self.inner.result()
+    }
+
+    fn reset(&mut self) {
+        self.seen.clear();
+        self.inner.reset();
+    }
+
+    fn data_type(&self) -> DataType {
+        self.inner.data_type()
+    }
+
+    fn clone_box(&self) -> Box<dyn AggregateFunction> {
+        Box::new(DistinctAdapter {
+            inner: self.inner.clone_box(),
+            seen: self.seen.clone(),
+        })
+    }
 }
 
 // ============================================================================
@@ -605,6 +1328,31 @@ mod tests {
         assert_eq!(count.result(), Some(Value::Int64(3)));
     }
 
+    #[test]
+    fn test_count_skips_null_values() {
+        let mut count = CountAggregate::new(DataType::Int64);
+        count.update(Some(Value::Null)).unwrap();
+        count.update(Some(Value::Int64(1))).unwrap();
+        count
+            .update_batch(&[Some(Value::Null), None, Some(Value::Int64(2))])
+            .unwrap();
+        assert_eq!(count.result(), Some(Value::Int64(2)));
+    }
+
+    #[test]
+    fn test_count_rows_includes_nulls() {
+        let mut count = CountAggregate::rows();
+        count.update(Some(Value::Int64(1))).unwrap();
+        count.update(None).unwrap();
+        count
+            .update_batch(&[Some(Value::Null), Some(Value::Int64(2))])
+            .unwrap();
+        assert_eq!(count.result(), Some(Value::Int64(4)));
+
+        count.reset();
+        assert_eq!(count.result(), Some(Value::Int64(0)));
+    }
+
     #[test]
     fn test_count_data_type() {
         let count = CountAggregate::new(DataType::String);
@@ -708,6 +1456,68 @@ mod tests {
         assert_eq!(sum.result(), Some(Value::Int64(0)));
     }
 
+    #[test]
+    fn test_sum_int64_overflow() {
+        let mut sum = SumAggregate::new(DataType::Int64).unwrap();
+        sum.update(Some(Value::Int64(i64::MAX - 1))).unwrap();
+        sum.update(Some(Value::Int64(1))).unwrap();
+        assert_eq!(sum.result(), Some(Value::Int64(i64::MAX)));
+
+        let err = sum.update(Some(Value::Int64(1))).unwrap_err();
+        assert!(err.to_string().contains("overflowed Int64"), "{}", err);
+        // The failed update leaves the total as it was
+        assert_eq!(sum.result(), Some(Value::Int64(i64::MAX)));
+
+        let mut sum = SumAggregate::new(DataType::Int64).unwrap();
+        sum.update(Some(Value::Int64(i64::MIN))).unwrap();
+        assert!(sum.update(Some(Value::Int64(-1))).is_err());
+        sum.update(Some(Value::Int64(i64::MAX))).unwrap();
+        assert_eq!(sum.result(), Some(Value::Int64(-1)));
+    }
+
+    #[test]
+    fn test_sum_float64_is_compensated() {
+        // Values k / 2^40 are exact in f64 and their exact total can be
+        // computed in integers
+        let mut state: u64 = 12345;
+        let mut exact_numerator: u128 = 0;
+        let mut naive = 0.0;
+        let mut sum = SumAggregate::new(DataType::Float64).unwrap();
+        for _ in 0..10_000_000 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let k = state >> 24;
+            let value = k as f64 / (1u64 << 40) as f64;
+            exact_numerator += k as u128;
+            naive += value;
+            sum.update(Some(Value::Float64(value))).unwrap();
+        }
+        let exact = exact_numerator as f64 / (1u64 << 40) as f64;
+
+        let Some(Value::Float64(compensated)) = sum.result() else {
+            panic!("expected a Float64 sum");
+        };
+        let compensated_error = (compensated - exact).abs();
+        let naive_error = (naive - exact).abs();
+        assert!(
+            compensated_error < naive_error / 100.0,
+            "compensated error {} vs naive error {}",
+            compensated_error,
+            naive_error
+        );
+        assert!(compensated_error <= exact * f64::EPSILON);
+    }
+
+    #[test]
+    fn test_compensated_sum_cancellation() {
+        let mut sum = CompensatedSum::default();
+        for value in [1.0, 1e100, 1.0, -1e100] {
+            sum.add(value);
+        }
+        assert_eq!(sum.value(), 2.0);
+    }
+
     #[test]
     fn test_sum_data_type_int64() {
         let sum = SumAggregate::new(DataType::Int64).unwrap();
@@ -926,6 +1736,29 @@ mod tests {
         assert_eq!(max.result(), Some(Value::String("zebra".to_string())));
     }
 
+    #[test]
+    fn test_min_max_string_lexicographic() {
+        let mut min = MinAggregate::new(DataType::String);
+        let mut max = MaxAggregate::new(DataType::String);
+        for value in [
+            Some(Value::String("bob".to_string())),
+            None,
+            Some(Value::String("Bob".to_string())),
+            Some(Value::Null),
+            Some(Value::String("bobby".to_string())),
+        ] {
+            min.update(value.clone()).unwrap();
+            max.update(value).unwrap();
+        }
+        // Uppercase sorts before lowercase, and a prefix before its extensions
+        assert_eq!(min.result(), Some(Value::String("Bob".to_string())));
+        assert_eq!(max.result(), Some(Value::String("bobby".to_string())));
+
+        // The empty string is a value, not a missing one
+        min.update(Some(Value::String(String::new()))).unwrap();
+        assert_eq!(min.result(), Some(Value::String(String::new())));
+    }
+
     #[test]
     fn test_max_wrong_type() {
         let mut max = MaxAggregate::new(DataType::Int64);
@@ -1065,9 +1898,325 @@ mod tests {
         assert_eq!(avg.result(), None);
     }
 
+    #[test]
+    fn test_avg_is_compensated() {
+        let mut avg = AvgAggregate::new(DataType::Float64).unwrap();
+        for value in [1e16, 1.0, -1e16, 1.0] {
+            avg.update(Some(Value::Float64(value))).unwrap();
+        }
+        // A plain running sum loses the first 1.0 against 1e16
+        assert_eq!(avg.result(), Some(Value::Float64(0.5)));
+    }
+
     #[test]
     fn test_avg_data_type() {
         let avg = AvgAggregate::new(DataType::Int64).unwrap();
         assert_eq!(avg.data_type(), DataType::Float64);
     }
+
+    // ============================================================================
+    // VARIANCE AGGREGATE TESTS
+    // ============================================================================
+
+    fn feed(aggregate: &mut dyn AggregateFunction, values: &[Value]) {
+        for value in values {
+            aggregate.update(Some(value.clone())).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_variance_and_stddev() {
+        let values: Vec<Value> = [2, 4, 4, 4, 5, 5, 7, 9].map(Value::Int64).to_vec();
+
+        let mut var_pop =
+            VarianceAggregate::new(DataType::Int64, VarianceKind::Population).unwrap();
+        let mut var_samp = VarianceAggregate::new(DataType::Int64, VarianceKind::Sample).unwrap();
+        let mut sd_pop = StdDevAggregate::new(DataType::Int64, VarianceKind::Population).unwrap();
+        let mut sd_samp = StdDevAggregate::new(DataType::Int64, VarianceKind::Sample).unwrap();
+        for aggregate in [
+            &mut var_pop as &mut dyn AggregateFunction,
+            &mut var_samp,
+            &mut sd_pop,
+            &mut sd_samp,
+        ] {
+            feed(aggregate, &values);
+            aggregate.update(None).unwrap();
+            aggregate.update(Some(Value::Null)).unwrap();
+            assert_eq!(aggregate.data_type(), DataType::Float64);
+        }
+
+        assert_eq!(var_pop.result(), Some(Value::Float64(4.0)));
+        assert_eq!(var_samp.result(), Some(Value::Float64(32.0 / 7.0)));
+        assert_eq!(sd_pop.result(), Some(Value::Float64(2.0)));
+        assert_eq!(
+            sd_samp.result(),
+            Some(Value::Float64((32.0f64 / 7.0).sqrt()))
+        );
+    }
+
+    #[test]
+    fn test_variance_small_groups() {
+        let mut pop = StdDevAggregate::new(DataType::Float64, VarianceKind::Population).unwrap();
+        let mut samp = StdDevAggregate::new(DataType::Float64, VarianceKind::Sample).unwrap();
+        assert_eq!(pop.result(), None);
+        assert_eq!(samp.result(), None);
+
+        // One value: no spread for the population, undefined for a sample
+        feed(&mut pop, &[Value::Float64(3.5)]);
+        feed(&mut samp, &[Value::Float64(3.5)]);
+        assert_eq!(pop.result(), Some(Value::Float64(0.0)));
+        assert_eq!(samp.result(), None);
+
+        samp.reset();
+        feed(&mut samp, &[Value::Float64(1.0), Value::Float64(3.0)]);
+        assert_eq!(samp.result(), Some(Value::Float64(2.0f64.sqrt())));
+    }
+
+    #[test]
+    fn test_variance_is_stable_for_large_offsets() {
+        // The naive sum-of-squares formula loses every digit here
+        let mut var = VarianceAggregate::new(DataType::Float64, VarianceKind::Sample).unwrap();
+        let values: Vec<Value> = [4.0, 7.0, 13.0, 16.0]
+            .map(|v| Value::Float64(1e9 + v))
+            .to_vec();
+        feed(&mut var, &values);
+        assert_eq!(var.result(), Some(Value::Float64(30.0)));
+    }
+
+    #[test]
+    fn test_variance_type_errors() {
+        for data_type in [DataType::String, DataType::Boolean, DataType::Date] {
+            assert!(VarianceAggregate::new(data_type, VarianceKind::Sample).is_err());
+            assert!(StdDevAggregate::new(data_type, VarianceKind::Population).is_err());
+        }
+        let mut stddev = StdDevAggregate::new(DataType::Int64, VarianceKind::Sample).unwrap();
+        assert!(stddev.update(Some(Value::String("x".to_string()))).is_err());
+    }
+
+    // ============================================================================
+    // QUANTILE AGGREGATE TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_percentile_exact_interpolation() {
+        let mut p50 = PercentileAggregate::new(DataType::Int64, 0.5).unwrap();
+        for v in [4, 1, 3, 2] {
+            p50.update(Some(Value::Int64(v))).unwrap();
+        }
+        assert_eq!(p50.result(), Some(Value::Float64(2.5)));
+
+        let mut p90 = PercentileAggregate::new(DataType::Float64, 0.9).unwrap();
+        for v in 1..=11 {
+            p90.update(Some(Value::Float64(v as f64 * 10.0))).unwrap();
+        }
+        assert_eq!(p90.result(), Some(Value::Float64(100.0)));
+
+        let mut p100 = PercentileAggregate::new(DataType::Int64, 1.0).unwrap();
+        p100.update_batch(&[Some(Value::Int64(7)), None, Some(Value::Int64(9))])
+            .unwrap();
+        assert_eq!(p100.result(), Some(Value::Float64(9.0)));
+    }
+
+    #[test]
+    fn test_percentile_group_sizes_and_extremes() {
+        let percentile = |quantile: f64, values: &[i64]| {
+            let mut p = PercentileAggregate::new(DataType::Int64, quantile).unwrap();
+            for &v in values {
+                p.update(Some(Value::Int64(v))).unwrap();
+            }
+            p.result()
+        };
+
+        // Odd sizes pick the middle value, even sizes average the middle two
+        assert_eq!(percentile(0.5, &[9, 1, 5]), Some(Value::Float64(5.0)));
+        assert_eq!(percentile(0.5, &[9, 1, 5, 2]), Some(Value::Float64(3.5)));
+
+        // A single value is every quantile of its group
+        for q in [0.0, 0.5, 1.0] {
+            assert_eq!(percentile(q, &[42]), Some(Value::Float64(42.0)));
+        }
+
+        // p0 and p1 are the minimum and maximum
+        assert_eq!(percentile(0.0, &[3, -7, 12, 0]), Some(Value::Float64(-7.0)));
+        assert_eq!(percentile(1.0, &[3, -7, 12, 0]), Some(Value::Float64(12.0)));
+    }
+
+    #[test]
+    fn test_percentile_empty_and_reset() {
+        let mut p = PercentileAggregate::new(DataType::Int64, 0.5).unwrap();
+        assert_eq!(p.result(), None);
+
+        p.update(Some(Value::Int64(5))).unwrap();
+        assert_eq!(p.result(), Some(Value::Float64(5.0)));
+
+        p.reset();
+        assert_eq!(p.result(), None);
+        assert_eq!(p.data_type(), DataType::Float64);
+    }
+
+    #[test]
+    fn test_quantile_validation() {
+        assert!(PercentileAggregate::new(DataType::String, 0.5).is_err());
+        assert!(PercentileAggregate::new(DataType::Boolean, 0.5).is_err());
+        assert!(PercentileAggregate::new(DataType::Int64, -0.01).is_err());
+        assert!(PercentileAggregate::new(DataType::Int64, 1.5).is_err());
+        assert!(PercentileAggregate::new(DataType::Int64, f64::NAN).is_err());
+        assert!(ApproxQuantileAggregate::new(DataType::String, 0.5).is_err());
+        assert!(ApproxQuantileAggregate::new(DataType::Float64, -0.1).is_err());
+
+        let mut p = PercentileAggregate::new(DataType::Int64, 0.5).unwrap();
+        assert!(p.update(Some(Value::String("x".to_string()))).is_err());
+    }
+
+    #[test]
+    fn test_approx_quantile_exact_for_small_input() {
+        let mut approx = ApproxQuantileAggregate::new(DataType::Int64, 0.5).unwrap();
+        let mut exact = PercentileAggregate::new(DataType::Int64, 0.5).unwrap();
+        for v in 0..1000 {
+            approx.update(Some(Value::Int64(v))).unwrap();
+            exact.update(Some(Value::Int64(v))).unwrap();
+        }
+        assert_eq!(approx.result(), exact.result());
+    }
+
+    #[test]
+    fn test_approx_quantile_within_tolerance_on_uniform_data() {
+        // 100k values spread uniformly over [0, 100000), in a scrambled order
+        let n: i64 = 100_000;
+        for &q in &[0.5, 0.95, 0.99] {
+            let mut approx = ApproxQuantileAggregate::new(DataType::Int64, q).unwrap();
+            for i in 0..n {
+                approx.update(Some(Value::Int64((i * 7919) % n))).unwrap();
+            }
+
+            let expected = q * (n - 1) as f64;
+            match approx.result() {
+                Some(Value::Float64(result)) => {
+                    // Allow 2% of the range, well beyond the ~0.8% standard error
+                    assert!(
+                        (result - expected).abs() < 0.02 * n as f64,
+                        "q={} expected ~{} got {}",
+                        q,
+                        expected,
+                        result
+                    );
+                }
+                other => panic!("Expected Float64, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_approx_quantile_is_reproducible() {
+        let run = || {
+            let mut approx = ApproxQuantileAggregate::new(DataType::Float64, 0.95).unwrap();
+            for i in 0..50_000 {
+                approx.update(Some(Value::Float64(i as f64))).unwrap();
+            }
+            approx.result()
+        };
+        assert_eq!(run(), run());
+    }
+
+    // ============================================================================
+    // DISTINCT ADAPTER TESTS
+    // ============================================================================
+
+    fn distinct(inner: impl AggregateFunction + 'static) -> DistinctAdapter {
+        DistinctAdapter::new(Box::new(inner))
+    }
+
+    #[test]
+    fn test_null_values_are_skipped() {
+        let mut sum = SumAggregate::new(DataType::Int64).unwrap();
+        let mut avg = AvgAggregate::new(DataType::Int64).unwrap();
+        let mut min = MinAggregate::new(DataType::Int64);
+        for value in [
+            Some(Value::Int64(4)),
+            Some(Value::Null),
+            Some(Value::Int64(2)),
+        ] {
+            sum.update(value.clone()).unwrap();
+            avg.update(value.clone()).unwrap();
+            min.update(value).unwrap();
+        }
+        assert_eq!(sum.result(), Some(Value::Int64(6)));
+        // Divides by the two non-NULL values
+        assert_eq!(avg.result(), Some(Value::Float64(3.0)));
+        assert_eq!(min.result(), Some(Value::Int64(2)));
+    }
+
+    #[test]
+    fn test_distinct_sum_skips_duplicates() {
+        let mut sum = distinct(SumAggregate::new(DataType::Int64).unwrap());
+        for v in [5, 1, 5, 2, 1, 5] {
+            sum.update(Some(Value::Int64(v))).unwrap();
+        }
+        sum.update(None).unwrap();
+        assert_eq!(sum.result(), Some(Value::Int64(8)));
+        assert_eq!(sum.data_type(), DataType::Int64);
+    }
+
+    #[test]
+    fn test_clone_box_copies_state_independently() {
+        let mut sum = distinct(SumAggregate::new(DataType::Int64).unwrap());
+        sum.update(Some(Value::Int64(5))).unwrap();
+
+        let mut copy = sum.clone_box();
+        // The copy remembers 5 was already seen
+        copy.update(Some(Value::Int64(5))).unwrap();
+        copy.update(Some(Value::Int64(2))).unwrap();
+        sum.update(Some(Value::Int64(1))).unwrap();
+
+        assert_eq!(copy.result(), Some(Value::Int64(7)));
+        assert_eq!(sum.result(), Some(Value::Int64(6)));
+    }
+
+    #[test]
+    fn test_distinct_avg_skips_duplicates() {
+        let mut avg = distinct(AvgAggregate::new(DataType::Int64).unwrap());
+        for v in [1, 1, 1, 4] {
+            avg.update(Some(Value::Int64(v))).unwrap();
+        }
+        assert_eq!(avg.result(), Some(Value::Float64(2.5)));
+        assert_eq!(avg.data_type(), DataType::Float64);
+    }
+
+    #[test]
+    fn test_distinct_count_and_min_max() {
+        let mut count = distinct(CountAggregate::new(DataType::String));
+        let mut min = distinct(MinAggregate::new(DataType::String));
+        let mut max = distinct(MaxAggregate::new(DataType::String));
+        for v in ["b", "a", "b", "c", "a"] {
+            let value = Some(Value::String(v.to_string()));
+            count.update(value.clone()).unwrap();
+            min.update(value.clone()).unwrap();
+            max.update(value).unwrap();
+        }
+        assert_eq!(count.result(), Some(Value::Int64(3)));
+        assert_eq!(min.result(), Some(Value::String("a".to_string())));
+        assert_eq!(max.result(), Some(Value::String("c".to_string())));
+    }
+
+    #[test]
+    fn test_distinct_reset_forgets_seen_values() {
+        let mut sum = distinct(SumAggregate::new(DataType::Int64).unwrap());
+        sum.update(Some(Value::Int64(3))).unwrap();
+        sum.update(Some(Value::Int64(3))).unwrap();
+        assert_eq!(sum.result(), Some(Value::Int64(3)));
+
+        sum.reset();
+        sum.update(Some(Value::Int64(3))).unwrap();
+        assert_eq!(sum.result(), Some(Value::Int64(3)));
+    }
+
+    #[test]
+    fn test_distinct_floats_collapse_signed_zeros_and_nans() {
+        let mut count = distinct(CountAggregate::new(DataType::Float64));
+        let other_nan = f64::from_bits(f64::NAN.to_bits() | 1);
+        for v in [0.0, -0.0, 1.5, 1.5, f64::NAN, other_nan] {
+            count.update(Some(Value::Float64(v))).unwrap();
+        }
+        assert_eq!(count.result(), Some(Value::Int64(3)));
+    }
 }
@@ -8,8 +8,20 @@
 //! a collection of tables and their associated metadata.
 
 use crate::error::{DatabaseError, Result};
+use crate::ingest::load_csv;
+use crate::planner::{execute_query, QueryResult};
+use crate::prepared::{prepare, PreparedStatement};
+use crate::table::{IntegrityIssue, IssueKind, TableStats};
+use crate::virtual_table::VirtualTable;
 use crate::Table;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::SystemTime;
+
+/// Tables loaded from CSV files queried by path, keyed by canonical path,
+/// with the file's modification time and length when it was loaded
+type FileTableCache = HashMap<PathBuf, (SystemTime, u64, Arc<Table>)>;
 
 /// Represents the database catalog containing all tables.
 ///
@@ -44,8 +56,15 @@ use std::collections::HashMap;
 /// ```
 #[derive(Clone)]
 pub struct Catalog {
-    /// Mapping from table name to Table object
-    tables: HashMap<String, Table>,
+    /// Mapping from table name to Table object, shared with running scans
+    tables: HashMap<String, Arc<Table>>,
+
+    /// Mapping from table name to closure-backed virtual table
+    virtual_tables: HashMap<String, VirtualTable>,
+
+    /// CSV files queried directly (`FROM 'data.csv'`), shared by clones of
+    /// the catalog; never listed as tables
+    file_tables: Arc<Mutex<FileTableCache>>,
 }
 
 impl Catalog {
@@ -57,6 +76,8 @@ impl Catalog {
     pub fn new() -> Self {
         Self {
             tables: HashMap::new(),
+            virtual_tables: HashMap::new(),
+            file_tables: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -67,15 +88,16 @@ impl Catalog {
     ///
     /// # Arguments
     ///
-    /// * `table` - The table to register
+    /// * `table` - The table to register, owned or already shared
     ///
     /// # Returns
     ///
     /// Returns an error if a table with the same name already exists
-    pub fn register_table(&mut self, table: Table) -> Result<()> {
+    pub fn register_table(&mut self, table: impl Into<Arc<Table>>) -> Result<()> {
+        let table = table.into();
         let table_name = table.name().to_string();
 
-        if self.tables.contains_key(&table_name) {
+        if self.tables.contains_key(&table_name) || self.virtual_tables.contains_key(&table_name) {
             return Err(DatabaseError::catalog_error(format!(
                 "Table '{}' already exists in catalog",
                 table_name
@@ -94,13 +116,115 @@ impl Catalog {
     ///
     /// # Returns
     ///
-    /// A reference to the table, or an error if not found
-    pub fn get_table(&self, name: &str) -> Result<&Table> {
-        self.tables.get(name).ok_or_else(|| {
+    /// A shared handle to the table, or an error if not found. The handle
+    /// stays valid, and unchanged, even if the table is later modified or
+    /// dropped from the catalog.
+    pub fn get_table(&self, name: &str) -> Result<Arc<Table>> {
+        self.tables.get(name).cloned().ok_or_else(|| {
             DatabaseError::catalog_error(format!("Table '{}' not found in catalog", name))
         })
     }
 
+    /// Resolves a table name as written in a query to the name the table is
+    /// registered under.
+    ///
+    /// An exact match wins; otherwise the name matches the one regular or
+    /// virtual table whose name differs from it only by ASCII case, so
+    /// `Users` finds a table registered as `users`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The table name as written
+    ///
+    /// # Returns
+    ///
+    /// The registered name, or an error if no table matches or several
+    /// tables differ from the name only by case
+    pub fn resolve_table_name(&self, name: &str) -> Result<String> {
+        match self.match_table_name(name) {
+            IdentifierMatch::Found(found) => Ok(found.clone()),
+            IdentifierMatch::Missing => Err(DatabaseError::catalog_error(format!(
+                "Table '{}' not found in catalog",
+                name
+            ))),
+            IdentifierMatch::Ambiguous(candidates) => Err(DatabaseError::catalog_error(format!(
+                "Table name '{}' is ambiguous: it matches {}; quote the name to choose one",
+                name,
+                quote_candidates(&candidates)
+            ))),
+        }
+    }
+
+    /// Looks a table name up among the regular and virtual tables the way
+    /// `resolve_table_name` does, telling a missing name from an ambiguous
+    /// one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The table name as written
+    ///
+    /// # Returns
+    ///
+    /// The registered name, or why there is none
+    pub fn match_table_name(&self, name: &str) -> IdentifierMatch<'_> {
+        let names = self.tables.keys().chain(self.virtual_tables.keys());
+        match_identifier(name, names)
+    }
+
+    /// Registers a virtual table in the catalog.
+    ///
+    /// Virtual tables share the table namespace, so the name must not be
+    /// used by a regular table or another virtual table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The virtual table to register
+    ///
+    /// # Returns
+    ///
+    /// Returns an error if a table with the same name already exists
+    pub fn register_virtual_table(&mut self, table: VirtualTable) -> Result<()> {
+        let table_name = table.name().to_string();
+
+        if self.tables.contains_key(&table_name) || self.virtual_tables.contains_key(&table_name) {
+            return Err(DatabaseError::catalog_error(format!(
+                "Table '{}' already exists in catalog",
+                table_name
+            )));
+        }
+
+        self.virtual_tables.insert(table_name, table);
+        Ok(())
+    }
+
+    /// Retrieves a virtual table by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the virtual table to retrieve
+    ///
+    /// # Returns
+    ///
+    /// A reference to the virtual table, or an error if not found
+    pub fn get_virtual_table(&self, name: &str) -> Result<&VirtualTable> {
+        self.virtual_tables.get(name).ok_or_else(|| {
+            DatabaseError::catalog_error(format!("Virtual table '{}' not found in catalog", name))
+        })
+    }
+
+    /// Checks if a virtual table with the given name exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The table name to check
+    ///
+    /// # Returns
+    ///
+    /// true if the virtual table exists, false otherwise
+    pub fn virtual_table_exists(&self, name: &str) -> bool {
+        self.virtual_tables.contains_key(name)
+    }
+
     /// Retrieves a mutable reference to a table by name.
     ///
     /// # Arguments
@@ -109,9 +233,11 @@ impl Catalog {
     ///
     /// # Returns
     ///
-    /// A mutable reference to the table, or an error if not found
+    /// A mutable reference to the table, or an error if not found. If a
+    /// handle from `get_table` or a running scan still shares the table, it
+    /// is copied first so the holders keep seeing the old contents.
     pub fn get_table_mut(&mut self, name: &str) -> Result<&mut Table> {
-        self.tables.get_mut(name).ok_or_else(|| {
+        self.tables.get_mut(name).map(Arc::make_mut).ok_or_else(|| {
             DatabaseError::catalog_error(format!("Table '{}' not found in catalog", name))
         })
     }
@@ -161,15 +287,92 @@ impl Catalog {
     ///
     /// # Returns
     ///
-    /// An error if the table doesn't exist
-    pub fn drop_table(&mut self, name: &str) -> Result<()> {
-        self.tables.remove(name).ok_or_else(|| {
+    /// The removed table, or an error if no table has that name. A virtual
+    /// table is not a `Table` and must be dropped with `drop_virtual_table`.
+    pub fn drop_table(&mut self, name: &str) -> Result<Table> {
+        if self.virtual_tables.contains_key(name) {
+            return Err(DatabaseError::catalog_error(format!(
+                "Cannot drop table '{}': it is a virtual table",
+                name
+            )));
+        }
+        self.tables
+            .remove(name)
+            .map(Arc::unwrap_or_clone)
+            .ok_or_else(|| {
+                DatabaseError::catalog_error(format!(
+                    "Cannot drop table '{}': table not found in catalog",
+                    name
+                ))
+            })
+    }
+
+    /// Drops (removes) a virtual table from the catalog.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the virtual table to drop
+    ///
+    /// # Returns
+    ///
+    /// The removed virtual table, or an error if no virtual table has that name
+    pub fn drop_virtual_table(&mut self, name: &str) -> Result<VirtualTable> {
+        self.virtual_tables.remove(name).ok_or_else(|| {
             DatabaseError::catalog_error(format!(
-                "Cannot drop table '{}': table not found in catalog",
+                "Cannot drop virtual table '{}': table not found in catalog",
                 name
             ))
-        })?;
-        Ok(())
+        })
+    }
+
+    /// Loads a CSV file queried directly by path, as in
+    /// `SELECT * FROM 'data/sales.csv'`, without registering it.
+    ///
+    /// Column types are inferred as by `LOAD`. The table is cached by the
+    /// file's canonical path, and reused while the file's modification time
+    /// and length are unchanged, so repeated queries over the same file
+    /// parse it only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the CSV file, relative to the current directory
+    ///
+    /// # Returns
+    ///
+    /// The table, or an ingestion error naming the file
+    pub fn file_table(&self, path: &str) -> Result<Arc<Table>> {
+        let unreadable = |e: std::io::Error| {
+            DatabaseError::ingestion_error(format!("Cannot read CSV file '{}': {}", path, e))
+        };
+        let canonical = std::fs::canonicalize(path).map_err(unreadable)?;
+        let metadata = std::fs::metadata(&canonical).map_err(unreadable)?;
+        if metadata.is_dir() {
+            return Err(DatabaseError::ingestion_error(format!(
+                "'{}' is a directory, not a CSV file",
+                path
+            )));
+        }
+        let modified = metadata.modified().map_err(unreadable)?;
+
+        // A panic while loading never leaves a half-inserted entry, so a
+        // poisoned cache is used as is
+        let cached = self
+            .file_tables
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&canonical)
+            .filter(|(time, len, _)| *time == modified && *len == metadata.len())
+            .map(|(_, _, table)| Arc::clone(table));
+        if let Some(table) = cached {
+            return Ok(table);
+        }
+
+        let table = Arc::new(load_csv(&canonical, path.to_string())?);
+        self.file_tables
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(canonical, (modified, metadata.len(), Arc::clone(&table)));
+        Ok(table)
     }
 
     /// Returns the number of tables in the catalog.
@@ -188,15 +391,106 @@ impl Catalog {
     /// # Returns
     ///
     /// A reference to the tables HashMap
-    pub fn tables(&self) -> &HashMap<String, Table> {
+    pub fn tables(&self) -> &HashMap<String, Arc<Table>> {
         &self.tables
     }
 
+    /// Runs a SQL query against the tables in this catalog.
+    ///
+    /// Shorthand for [`execute_query`](crate::planner::execute_query).
+    ///
+    /// # Arguments
+    ///
+    /// * `sql` - The SQL text to execute
+    ///
+    /// # Returns
+    ///
+    /// The query's column names, types, and rows
+    pub fn query(&self, sql: &str) -> Result<QueryResult> {
+        execute_query(self, sql)
+    }
+
+    /// Parses a SELECT with `?` or `$n` placeholders for repeated execution.
+    ///
+    /// Shorthand for [`prepare`](crate::prepared::prepare); see the
+    /// [`prepared`](crate::prepared) module for an example.
+    ///
+    /// # Arguments
+    ///
+    /// * `sql` - The SQL text, with placeholders where values go
+    ///
+    /// # Returns
+    ///
+    /// A statement to bind values to with `PreparedStatement::bind`
+    pub fn prepare(&self, sql: &str) -> Result<PreparedStatement> {
+        prepare(self, sql)
+    }
+
+    /// Performs a deep integrity check of every table in the catalog.
+    ///
+    /// Besides each table's own checks (see `Table::verify`), this checks
+    /// that every table is registered under its own name. Virtual tables
+    /// hold no data and are not checked.
+    ///
+    /// # Returns
+    ///
+    /// The issues found, grouped by table in name order; empty if
+    /// everything is consistent
+    pub fn verify(&self) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+        for name in self.list_tables_sorted() {
+            issues.extend(self.verify_table(&name).unwrap_or_default());
+        }
+        issues
+    }
+
+    /// Performs a deep integrity check of one table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the table to check
+    ///
+    /// # Returns
+    ///
+    /// The issues found, or an error if the table doesn't exist
+    pub fn verify_table(&self, name: &str) -> Result<Vec<IntegrityIssue>> {
+        let table = self.get_table(name)?;
+        let mut issues = Vec::new();
+
+        if table.name() != name {
+            issues.push(IntegrityIssue {
+                table: table.name().to_string(),
+                column: None,
+                row: None,
+                kind: IssueKind::NameMismatch {
+                    registered_as: name.to_string(),
+                },
+            });
+        }
+        issues.extend(table.verify());
+
+        Ok(issues)
+    }
+
+    /// Computes summary statistics for a table (see `Table::compute_stats`).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the table to profile
+    ///
+    /// # Returns
+    ///
+    /// The table's statistics, or an error if the table doesn't exist
+    pub fn table_stats(&self, name: &str) -> Result<TableStats> {
+        Ok(self.get_table(name)?.compute_stats())
+    }
+
     /// Clears all tables from the catalog.
     ///
     /// This removes all tables, effectively resetting the catalog to empty.
     pub fn clear(&mut self) {
         self.tables.clear();
+        self.virtual_tables.clear();
     }
 
     /// Renames a table in the catalog.
@@ -225,14 +519,38 @@ impl Catalog {
         }
 
         // Remove the table with old name and reinsert with new name
-        let table = self.tables.remove(old_name).unwrap();
-        // Note: Table doesn't have a set_name method, so we'd need to clone
-        // or implement one. For now, we'll just move it and it keeps its internal name.
-        // In a real implementation, we'd want to update the table's internal name too.
+        let mut table = self.tables.remove(old_name).unwrap();
+        Arc::make_mut(&mut table).set_name(new_name.clone());
         self.tables.insert(new_name, table);
 
         Ok(())
     }
+
+    /// Saves every regular table into a database directory.
+    ///
+    /// Virtual tables are not saved. See [`crate::storage::save_catalog`]
+    /// for the directory layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The database directory, created if it doesn't exist
+    pub fn save_to_dir<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        crate::storage::save_catalog(self, path)
+    }
+
+    /// Loads a catalog from a database directory written by `save_to_dir`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The database directory
+    ///
+    /// # Returns
+    ///
+    /// The restored catalog, or an error if the directory is missing, was
+    /// written by an unsupported format version, or is corrupt
+    pub fn load_from_dir<P: AsRef<Path>>(path: P) -> Result<Catalog> {
+        crate::storage::load_catalog(path)
+    }
 }
 
 impl Default for Catalog {
@@ -254,6 +572,172 @@ impl std::fmt::Display for Catalog {
     }
 }
 
+// ============================================================================
+// SHARED CATALOG
+// ============================================================================
+
+/// A catalog that several threads can query at once.
+///
+/// Clones are cheap handles to the same catalog. Queries run against a
+/// snapshot taken under a brief read lock: the snapshot shares every table
+/// through its `Arc`, so no column data is copied and a long query never
+/// holds up writers. Changes go through [`write`](Self::write) and are seen
+/// by queries started afterwards.
+///
+/// # Example
+///
+/// ```
+/// use mini_rust_olap::catalog::{Catalog, SharedCatalog};
+/// use mini_rust_olap::table::Table;
+///
+/// let shared = SharedCatalog::new(Catalog::new());
+/// shared.write().register_table(Table::new("empty".to_string())).unwrap();
+///
+/// let worker = shared.clone();
+/// let names = std::thread::spawn(move || worker.read().list_tables())
+///     .join()
+///     .unwrap();
+/// assert_eq!(names, ["empty"]);
+/// ```
+#[derive(Clone, Default)]
+pub struct SharedCatalog {
+    inner: Arc<RwLock<Catalog>>,
+}
+
+impl SharedCatalog {
+    /// Wraps a catalog for sharing between threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `catalog` - The catalog to share
+    pub fn new(catalog: Catalog) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(catalog)),
+        }
+    }
+
+    /// Locks the catalog for reading.
+    ///
+    /// Catalog changes validate before they modify anything, so a thread
+    /// that panicked while holding the lock cannot have left the catalog
+    /// half-changed, and a poisoned lock is used as is.
+    pub fn read(&self) -> RwLockReadGuard<'_, Catalog> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Locks the catalog for registering, dropping or changing tables.
+    pub fn write(&self) -> RwLockWriteGuard<'_, Catalog> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Takes a consistent copy of the catalog that shares its tables.
+    ///
+    /// # Returns
+    ///
+    /// A catalog holding the same tables as this one at the moment of the
+    /// call, unaffected by later changes
+    pub fn snapshot(&self) -> Catalog {
+        self.read().clone()
+    }
+
+    /// Runs a SQL query against a snapshot of the catalog.
+    ///
+    /// # Arguments
+    ///
+    /// * `sql` - The SQL text to execute
+    ///
+    /// # Returns
+    ///
+    /// The query's column names, types, and rows
+    pub fn query(&self, sql: &str) -> Result<QueryResult> {
+        self.snapshot().query(sql)
+    }
+
+    /// Retrieves a shared handle to a table by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the table to retrieve
+    ///
+    /// # Returns
+    ///
+    /// The table, or an error if not found
+    pub fn get_table(&self, name: &str) -> Result<Arc<Table>> {
+        self.read().get_table(name)
+    }
+}
+
+impl From<Catalog> for SharedCatalog {
+    fn from(catalog: Catalog) -> Self {
+        Self::new(catalog)
+    }
+}
+
+// ============================================================================
+// IDENTIFIER RESOLUTION
+// ============================================================================
+
+/// The outcome of looking up an identifier among table or column names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierMatch<'a> {
+    /// The identifier names exactly one of the names
+    Found(&'a String),
+    /// No name matches the identifier
+    Missing,
+    /// Several names equal the identifier ignoring case, and none exactly
+    Ambiguous(Vec<&'a String>),
+}
+
+/// Looks up an identifier among names, matching exactly when possible and
+/// otherwise ignoring ASCII case.
+///
+/// Unquoted identifiers reach the planner lowercased while stored names keep
+/// the case they were created with (e.g. a CSV header `UserID`), so an
+/// identifier that matches no name exactly falls back to the names it equals
+/// ignoring case. More than one such name is ambiguous; a quoted identifier
+/// spelling one of them exactly picks it.
+///
+/// # Arguments
+///
+/// * `identifier` - The name as written in the query
+/// * `names` - The names it may refer to
+///
+/// # Returns
+///
+/// The matching name, or why there is none
+pub fn match_identifier<'a>(
+    identifier: &str,
+    names: impl IntoIterator<Item = &'a String>,
+) -> IdentifierMatch<'a> {
+    let mut folded: Vec<&'a String> = Vec::new();
+    for name in names {
+        if name == identifier {
+            return IdentifierMatch::Found(name);
+        }
+        if name.eq_ignore_ascii_case(identifier) {
+            folded.push(name);
+        }
+    }
+
+    match folded.len() {
+        0 => IdentifierMatch::Missing,
+        1 => IdentifierMatch::Found(folded[0]),
+        _ => {
+            folded.sort();
+            IdentifierMatch::Ambiguous(folded)
+        }
+    }
+}
+
+/// Formats the names an ambiguous identifier matched, e.g. `'ID', 'Id'`.
+pub fn quote_candidates(candidates: &[&String]) -> String {
+    candidates
+        .iter()
+        .map(|name| format!("'{}'", name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 // ============================================================================
 // UNIT TESTS
 // ============================================================================
@@ -272,6 +756,29 @@ mod tests {
         assert!(catalog.list_tables().is_empty());
     }
 
+    /// Test resolving table names ignoring case
+    #[test]
+    fn test_resolve_table_name() {
+        let mut catalog = Catalog::new();
+        catalog
+            .register_table(Table::new("Sales".to_string()))
+            .unwrap();
+        catalog
+            .register_table(Table::new("users".to_string()))
+            .unwrap();
+        catalog
+            .register_table(Table::new("USERS".to_string()))
+            .unwrap();
+
+        assert_eq!(catalog.resolve_table_name("sales").unwrap(), "Sales");
+        assert_eq!(catalog.resolve_table_name("USERS").unwrap(), "USERS");
+        assert_eq!(catalog.resolve_table_name("users").unwrap(), "users");
+
+        let err = catalog.resolve_table_name("Users").unwrap_err();
+        assert!(err.to_string().contains("'USERS', 'users'"));
+        assert!(catalog.resolve_table_name("orders").is_err());
+    }
+
     /// Test creating a catalog with Default trait
     #[test]
     fn test_catalog_default() {
@@ -348,6 +855,63 @@ mod tests {
         assert_eq!(table_mut.unwrap().name(), "users");
     }
 
+    /// Test handles from get_table keep their contents when the table changes
+    #[test]
+    fn test_table_handles_are_snapshots() {
+        let mut catalog = Catalog::new();
+        let mut table = Table::new("numbers".to_string());
+        let mut column = IntColumn::new();
+        column.push_value(Value::Int64(1)).unwrap();
+        table.add_column("n".to_string(), Box::new(column)).unwrap();
+        catalog.register_table(table).unwrap();
+
+        // Handles share the table rather than copying it
+        let before = catalog.get_table("numbers").unwrap();
+        assert!(Arc::ptr_eq(&before, &catalog.get_table("numbers").unwrap()));
+
+        catalog
+            .get_table_mut("numbers")
+            .unwrap()
+            .insert_rows(vec![vec![Value::Int64(2)]])
+            .unwrap();
+        assert_eq!(before.row_count(), 1);
+        assert_eq!(catalog.get_table("numbers").unwrap().row_count(), 2);
+
+        let dropped = catalog.drop_table("numbers").unwrap();
+        assert_eq!(dropped.row_count(), 2);
+        assert_eq!(before.row_count(), 1);
+    }
+
+    /// Test SharedCatalog queries see changes made through write()
+    #[test]
+    fn test_shared_catalog_write_then_query() {
+        let shared = SharedCatalog::new(Catalog::new());
+        let mut table = Table::new("numbers".to_string());
+        table
+            .add_column("n".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        shared.write().register_table(table).unwrap();
+
+        let snapshot = shared.snapshot();
+        shared
+            .write()
+            .get_table_mut("numbers")
+            .unwrap()
+            .insert_rows(vec![vec![Value::Int64(7)], vec![Value::Int64(8)]])
+            .unwrap();
+
+        assert_eq!(
+            shared
+                .query("SELECT SUM(n) FROM numbers")
+                .unwrap()
+                .rows()
+                .next(),
+            Some(vec![Value::Int64(15)])
+        );
+        assert_eq!(snapshot.get_table("numbers").unwrap().row_count(), 0);
+        assert!(shared.get_table("missing").is_err());
+    }
+
     /// Test table_exists for existing table
     #[test]
     fn test_table_exists_true() {
@@ -425,7 +989,8 @@ mod tests {
         catalog.register_table(table).unwrap();
         assert_eq!(catalog.table_count(), 1);
 
-        assert!(catalog.drop_table("users").is_ok());
+        let dropped = catalog.drop_table("users").unwrap();
+        assert_eq!(dropped.name(), "users");
         assert_eq!(catalog.table_count(), 0);
         assert!(!catalog.table_exists("users"));
     }
@@ -437,7 +1002,7 @@ mod tests {
 
         let result = catalog.drop_table("nonexistent");
         assert!(result.is_err());
-        let error_msg = format!("{}", result.unwrap_err());
+        let error_msg = format!("{}", result.err().unwrap());
         assert!(error_msg.contains("not found"));
     }
 
@@ -517,6 +1082,7 @@ mod tests {
         assert!(result.is_ok());
         assert!(!catalog.table_exists("old_name"));
         assert!(catalog.table_exists("new_name"));
+        assert_eq!(catalog.get_table("new_name").unwrap().name(), "new_name");
     }
 
     /// Test rename_table with non-existent old name
@@ -670,4 +1236,87 @@ mod tests {
         assert!(cloned.table_exists("users"));
         assert!(cloned.table_exists("products"));
     }
+
+    /// Test virtual table registration shares the table namespace
+    #[test]
+    fn test_register_virtual_table() {
+        use crate::types::{DataType, Value};
+        use crate::virtual_table::VirtualTable;
+
+        let numbers = || {
+            VirtualTable::new(
+                "numbers".to_string(),
+                vec![("n".to_string(), DataType::Int64)],
+                || Box::new((0..3).map(|n| vec![Value::Int64(n)])),
+            )
+            .unwrap()
+        };
+
+        let mut catalog = Catalog::new();
+        catalog.register_virtual_table(numbers()).unwrap();
+        assert!(catalog.virtual_table_exists("numbers"));
+        assert!(!catalog.table_exists("numbers"));
+        assert_eq!(
+            catalog.get_virtual_table("numbers").unwrap().rows().count(),
+            3
+        );
+
+        // Names collide with both kinds of table
+        assert!(catalog.register_virtual_table(numbers()).is_err());
+        assert!(catalog
+            .register_table(Table::new("numbers".to_string()))
+            .is_err());
+
+        // Only drop_virtual_table removes a virtual table
+        assert!(catalog.drop_table("numbers").is_err());
+        assert!(catalog.virtual_table_exists("numbers"));
+        catalog.drop_virtual_table("numbers").unwrap();
+        assert!(!catalog.virtual_table_exists("numbers"));
+        assert!(catalog.get_virtual_table("numbers").is_err());
+    }
+
+    /// Test verify reports a table registered under another name
+    #[test]
+    fn test_verify_detects_name_mismatch() {
+        let mut catalog = Catalog::new();
+        catalog
+            .register_table(Table::new("users".to_string()))
+            .unwrap();
+        assert!(catalog.verify().is_empty());
+
+        // Bypass register_table to break the invariant
+        catalog.tables.insert(
+            "people".to_string(),
+            Arc::new(Table::new("persons".to_string())),
+        );
+
+        let issues = catalog.verify();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].table, "persons");
+        assert_eq!(
+            issues[0].kind,
+            IssueKind::NameMismatch {
+                registered_as: "people".to_string()
+            }
+        );
+        assert!(catalog.verify_table("missing").is_err());
+    }
+
+    /// Test table_stats looks the table up by name
+    #[test]
+    fn test_table_stats() {
+        let mut catalog = Catalog::new();
+        let mut table = Table::new("numbers".to_string());
+        let mut column = IntColumn::new();
+        for value in [3, 1, 3] {
+            column.push_value(Value::Int64(value)).unwrap();
+        }
+        table.add_column("n".to_string(), Box::new(column)).unwrap();
+        catalog.register_table(table).unwrap();
+
+        let stats = catalog.table_stats("numbers").unwrap();
+        assert_eq!(stats.row_count, 3);
+        assert_eq!(stats.column("n").unwrap().max, Some(Value::Int64(3)));
+        assert!(catalog.table_stats("missing").is_err());
+    }
 }
@@ -27,8 +27,32 @@
 //! ## Architecture
 //!
 //! The `Column` trait defines a common interface for all column types.
-//! Concrete implementations (`IntColumn`, `FloatColumn`, `StringColumn`)
-//! store data in typed `Vec` structures for optimal performance.
+//! Concrete implementations (`IntColumn`, `FloatColumn`, `StringColumn`,
+//! `BoolColumn`, `DateColumn`) store data in typed `Vec` structures for
+//! optimal performance; `BoolColumn` packs its values one bit per row.
+//! `DictStringColumn` is an alternative String representation that stores
+//! each distinct value once and a `u32` code per row. `RleColumn` (runs of
+//! equal values) and `DeltaColumn` (differences between Int64 values) are
+//! compressed representations that [`compress_column`] chooses between when
+//! one saves enough memory. A `ColumnSlice` is a
+//! read-only window onto a row range of another column, so batches can share
+//! a table's storage instead of copying it, and a `FilteredColumn` is a view
+//! of selected rows of another column, so a filter can pass its input on
+//! without copying the rows that survive.
+//!
+//! ## Zone Maps
+//!
+//! A `ZoneMap` records the minimum and maximum of every block of
+//! [`ZONE_MAP_BLOCK_SIZE`] rows of an Int64, Float64, or Date column. Tables
+//! keep one per such column, so a scan with a range predicate can skip the
+//! blocks whose range cannot satisfy it.
+//!
+//! ## NULL Values
+//!
+//! Every column is nullable. NULLs are tracked in a validity bitmap kept
+//! alongside the typed `Vec`, so a column without NULLs pays nothing beyond
+//! an empty bitmap. The typed `Vec` holds a placeholder (zero or the empty
+//! string) at each NULL position, and `get`/`slice` return `Value::Null` there.
 //!
 //! ## Usage Example
 //!
@@ -54,6 +78,10 @@
 
 use crate::error::{DatabaseError, Result};
 use crate::types::{DataType, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
 
 // ============================================================================
 // COLUMN TRAIT
@@ -77,6 +105,8 @@ use crate::types::{DataType, Value};
 /// - **Owned Values**: Methods return `Value` instead of `&Value` for simplicity
 /// - **Type Safety**: Push operations check type compatibility at runtime
 /// - **Zero-Cost Abstraction**: Static dispatch, no runtime overhead
+/// - **Thread Safety**: Columns are `Send + Sync`, so a table can be scanned
+///   by several worker threads at once (see `ParallelScan`)
 ///
 /// # Example
 ///
@@ -88,7 +118,7 @@ use crate::types::{DataType, Value};
 /// col.push_value(Value::Int64(42)).unwrap();
 /// assert_eq!(col.get(0).unwrap(), Value::Int64(42));
 /// ```
-pub trait Column {
+pub trait Column: Send + Sync {
     /// Returns the data type of this column
     ///
     /// This is used for schema validation and query planning.
@@ -160,6 +190,40 @@ pub trait Column {
     /// ```
     fn push_value(&mut self, value: Value) -> Result<()>;
 
+    /// Adds a NULL to the end of the column
+    ///
+    /// Pushing `Value::Null` through [`Column::push_value`] does the same.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// col.push_null();
+    /// assert_eq!(col.get(0).unwrap(), Value::Null);
+    /// ```
+    fn push_null(&mut self);
+
+    /// Returns the number of NULLs in the column
+    ///
+    /// The default implementation reads back every row; implementations
+    /// that track NULLs directly should override it.
+    fn null_count(&self) -> usize {
+        (0..self.len())
+            .filter(|&row| matches!(self.get(row), Ok(Value::Null)))
+            .count()
+    }
+
+    /// Returns true if the row at `index` is NULL
+    ///
+    /// The default implementation reads the value back; implementations
+    /// that track NULLs directly should override it. Rows past the end are
+    /// reported as not NULL.
+    fn is_null(&self, index: usize) -> bool {
+        matches!(self.get(index), Ok(Value::Null))
+    }
+
     /// Retrieves a value by index
     ///
     /// Returns an owned copy of the value at the specified index.
@@ -215,6 +279,114 @@ pub trait Column {
     /// ```
     fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value>;
 
+    /// Iterates over the values in a range of rows
+    ///
+    /// Yields the same values as `slice(Some(range))` without collecting
+    /// them into a vector. The default implementation does collect through
+    /// `slice`; the typed columns override it to read their storage
+    /// directly.
+    ///
+    /// # Arguments
+    /// * `range` - The rows to read
+    ///
+    /// # Panics
+    /// Panics if the range runs past the end of the column, as `slice` does
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// for v in 0..5 {
+    ///     col.push_value(Value::Int64(v))?;
+    /// }
+    ///
+    /// let middle: Vec<Value> = col.iter_range(1..4).collect();
+    /// assert_eq!(middle, col.slice(Some(1..4)));
+    /// # Ok::<(), mini_rust_olap::error::DatabaseError>(())
+    /// ```
+    fn iter_range(&self, range: Range<usize>) -> Box<dyn Iterator<Item = Value> + '_> {
+        Box::new(self.slice(Some(range)).into_iter())
+    }
+
+    /// Appends a range of rows to another column of the same type
+    ///
+    /// The default implementation pushes each value through
+    /// [`Column::push_value`]. When `target` has the same representation,
+    /// the typed columns instead extend its storage with the whole range at
+    /// once, NULLs included.
+    ///
+    /// # Arguments
+    /// * `range` - The rows to copy
+    /// * `target` - The column to append them to
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::ColumnError` if the range runs past the end of
+    /// the column and `DatabaseError::TypeError` if `target` holds another
+    /// type; `target` is left unchanged in both cases
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// for v in 0..5 {
+    ///     col.push_value(Value::Int64(v))?;
+    /// }
+    ///
+    /// let mut tail = IntColumn::new();
+    /// col.copy_range_into(3..5, &mut tail)?;
+    /// assert_eq!(tail.as_vec(), &[3, 4]);
+    /// # Ok::<(), mini_rust_olap::error::DatabaseError>(())
+    /// ```
+    fn copy_range_into(&self, range: Range<usize>, target: &mut dyn Column) -> Result<()> {
+        check_copy_range(self.data_type(), self.len(), &range, target)?;
+        push_values(target, self.iter_range(range))
+    }
+
+    /// Appends every row of another column of the same type
+    ///
+    /// The default implementation pushes each value through
+    /// [`Column::push_value`]. `IntColumn`, `FloatColumn` and `StringColumn`
+    /// share the chunks of a column of their own type instead of copying
+    /// them, so appending a large column costs little more than its NULL
+    /// bitmap.
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::TypeError` if `other` holds another type;
+    /// the column is left unchanged
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// col.push_value(Value::Int64(1))?;
+    /// let mut more = IntColumn::new();
+    /// more.push_value(Value::Int64(2))?;
+    /// more.push_null();
+    ///
+    /// col.append_column(&more)?;
+    /// assert_eq!(col.slice(None), vec![Value::Int64(1), Value::Int64(2), Value::Null]);
+    /// # Ok::<(), mini_rust_olap::error::DatabaseError>(())
+    /// ```
+    fn append_column(&mut self, other: &dyn Column) -> Result<()> {
+        if other.data_type() != self.data_type() {
+            return Err(DatabaseError::type_error(format!(
+                "Cannot append {} rows to a {} column",
+                other.data_type(),
+                self.data_type()
+            )));
+        }
+        for value in other.iter_range(0..other.len()) {
+            self.push_value(value)?;
+        }
+        Ok(())
+    }
+
     /// Clears all values from the column
     ///
     /// This removes all data but preserves the column's schema/type.
@@ -234,6 +406,398 @@ pub trait Column {
     /// # Ok::<(), mini_rust_olap::error::DatabaseError>(())
     /// ```
     fn clear(&mut self);
+
+    /// Returns the approximate heap memory held by the column, in bytes
+    ///
+    /// Counts allocated capacity (values, string contents, dictionaries and
+    /// the NULL bitmap), not the column struct itself. Useful for comparing
+    /// representations of the same data.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    ///
+    /// let col = IntColumn::with_capacity(100);
+    /// assert_eq!(col.memory_size(), 800);
+    /// ```
+    fn memory_size(&self) -> usize;
+
+    /// Returns a copy of the column in the same representation
+    ///
+    /// The default implementation copies the values into a new column from
+    /// [`create_column`]; representations that `create_column` does not
+    /// produce (such as `DictStringColumn`) override it to keep their
+    /// encoding.
+    fn clone_column(&self) -> Box<dyn Column> {
+        let mut column = create_column(self.data_type());
+        // Values read back from a column always fit its type
+        let _ = self.copy_range_into(0..self.len(), column.as_mut());
+        column
+    }
+
+    /// Returns the column as `Any`, so it can be downcast to its concrete type
+    ///
+    /// Vectorized code uses this to reach the typed storage behind an
+    /// `Arc<dyn Column>` and work on the raw slice instead of `Value`s.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// col.push_value(Value::Int64(7))?;
+    /// let column: &dyn Column = &col;
+    ///
+    /// let ints = column.as_any().downcast_ref::<IntColumn>().unwrap();
+    /// assert_eq!(ints.as_vec(), &[7]);
+    /// # Ok::<(), mini_rust_olap::error::DatabaseError>(())
+    /// ```
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the column as mutable `Any`, so it can be downcast to its
+    /// concrete type
+    ///
+    /// [`Column::copy_range_into`] uses this to reach the typed storage of
+    /// its target.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Checks the column's internal consistency
+    ///
+    /// The default implementation reads back every row and checks that it
+    /// is present and either NULL or of the column's type. Representations with extra
+    /// internal structure should extend this with their own invariants.
+    /// Problems are reported, never panicked on.
+    ///
+    /// # Returns
+    /// One `ColumnIssue` per problem found; empty if the column is consistent
+    fn verify(&self) -> Vec<ColumnIssue> {
+        let data_type = self.data_type();
+        let mut issues = Vec::new();
+
+        for row in 0..self.len() {
+            match self.get(row) {
+                Ok(Value::Null) => {}
+                Ok(value) if value.data_type() == Some(data_type) => {}
+                Ok(value) => issues.push(ColumnIssue {
+                    row: Some(row),
+                    detail: format!(
+                        "holds a {} value but the column is {}",
+                        value.type_name(),
+                        data_type
+                    ),
+                }),
+                Err(e) => issues.push(ColumnIssue {
+                    row: Some(row),
+                    detail: format!("cannot be read: {}", e),
+                }),
+            }
+        }
+
+        issues
+    }
+}
+
+/// A consistency problem found inside a single column by [`Column::verify`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnIssue {
+    /// The affected row, if the problem is specific to one row
+    pub row: Option<usize>,
+    /// What is wrong
+    pub detail: String,
+}
+
+/// Checks that rows `range` of a column with `len` rows of `data_type` can
+/// be appended to `target`, for [`Column::copy_range_into`]
+fn check_copy_range(
+    data_type: DataType,
+    len: usize,
+    range: &Range<usize>,
+    target: &dyn Column,
+) -> Result<()> {
+    if range.start > range.end || range.end > len {
+        return Err(DatabaseError::column_error(format!(
+            "Range {}..{} out of bounds (len: {})",
+            range.start, range.end, len
+        )));
+    }
+    if target.data_type() != data_type {
+        return Err(DatabaseError::type_error(format!(
+            "Cannot copy {} rows into a {} column",
+            data_type,
+            target.data_type()
+        )));
+    }
+    Ok(())
+}
+
+/// Pushes every value into `target`, one at a time
+fn push_values(target: &mut dyn Column, values: impl Iterator<Item = Value>) -> Result<()> {
+    for value in values {
+        target.push_value(value)?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// NULL BITMAP
+// ============================================================================
+
+/// Tracks which rows of a column are NULL
+///
+/// One bit per row, set for NULL. Words are only allocated once the first
+/// NULL is pushed, so columns without NULLs carry an empty vector.
+#[derive(Debug, Clone, Default)]
+struct NullBitmap {
+    /// Bit `i % 64` of word `i / 64` is set when row `i` is NULL
+    words: Vec<u64>,
+    /// Number of set bits
+    null_count: usize,
+}
+
+impl NullBitmap {
+    /// Marks row `index` as NULL
+    fn set_null(&mut self, index: usize) {
+        let word = index / 64;
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (index % 64);
+        self.null_count += 1;
+    }
+
+    /// Returns true if row `index` is NULL
+    fn is_null(&self, index: usize) -> bool {
+        self.words
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+
+    fn null_count(&self) -> usize {
+        self.null_count
+    }
+
+    /// Marks the rows of `source` in `range` that are NULL, placing the
+    /// first at row `at`
+    fn copy_range(&mut self, source: &NullBitmap, range: Range<usize>, at: usize) {
+        if source.null_count == 0 {
+            return;
+        }
+        for (offset, row) in range.enumerate() {
+            if source.is_null(row) {
+                self.set_null(at + offset);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.words.clear();
+        self.null_count = 0;
+    }
+
+    /// Heap bytes held by the bitmap
+    fn memory_size(&self) -> usize {
+        self.words.capacity() * std::mem::size_of::<u64>()
+    }
+}
+
+// ============================================================================
+// CHUNKED STORAGE
+// ============================================================================
+
+/// Most values one chunk of an `IntColumn`, `FloatColumn` or `StringColumn`
+/// holds
+pub const CHUNK_SIZE: usize = 65_536;
+
+/// The values of a typed column, split into chunks of at most
+/// [`CHUNK_SIZE`] values
+///
+/// Chunks are reference-counted, so cloning a column or appending one to
+/// another shares them instead of copying values; a shared chunk is only
+/// copied when it is written to. Chunks shared by an append may be shorter
+/// than `CHUNK_SIZE`, so each chunk's first row is kept alongside it.
+#[derive(Debug, Clone)]
+struct Chunks<T> {
+    chunks: Vec<Arc<Vec<T>>>,
+    /// Row at which each chunk starts
+    starts: Vec<usize>,
+    /// Total number of values
+    len: usize,
+}
+
+impl<T: Clone> Chunks<T> {
+    fn new() -> Self {
+        Self {
+            chunks: Vec::new(),
+            starts: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Reserves room for `capacity` values in the first chunk, up to a chunk
+    fn with_capacity(capacity: usize) -> Self {
+        let mut chunks = Self::new();
+        if capacity > 0 {
+            chunks
+                .chunks
+                .push(Arc::new(Vec::with_capacity(capacity.min(CHUNK_SIZE))));
+            chunks.starts.push(0);
+        }
+        chunks
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Maps a row to the chunk holding it and its offset in that chunk
+    fn locate(&self, index: usize) -> (usize, usize) {
+        let chunk = match self.starts.last() {
+            // Reads and writes mostly land in the last chunk
+            Some(&start) if index >= start => self.starts.len() - 1,
+            _ => self.starts.partition_point(|&start| start <= index) - 1,
+        };
+        (chunk, index - self.starts[chunk])
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        let (chunk, offset) = self.locate(index);
+        self.chunks[chunk].get(offset)
+    }
+
+    /// Returns the last chunk for appending, starting a new one if it is full
+    fn last_mut(&mut self) -> &mut Vec<T> {
+        if self
+            .chunks
+            .last()
+            .is_none_or(|chunk| chunk.len() >= CHUNK_SIZE)
+        {
+            self.chunks.push(Arc::new(Vec::new()));
+            self.starts.push(self.len);
+        }
+        let last = self.chunks.last_mut().expect("a chunk was just ensured");
+        Arc::make_mut(last)
+    }
+
+    fn push(&mut self, value: T) {
+        self.last_mut().push(value);
+        self.len += 1;
+    }
+
+    fn extend_from_slice(&mut self, mut values: &[T]) {
+        while !values.is_empty() {
+            let last = self.last_mut();
+            let (head, rest) = values.split_at((CHUNK_SIZE - last.len()).min(values.len()));
+            last.extend_from_slice(head);
+            self.len += head.len();
+            values = rest;
+        }
+    }
+
+    /// Appends every value of `other`, sharing its chunks where doing so
+    /// keeps chunks from fragmenting: a chunk that fits in the room left in
+    /// the last chunk is copied there instead
+    fn append(&mut self, other: &Chunks<T>) {
+        if self.chunks.last().is_some_and(|chunk| chunk.is_empty()) {
+            self.chunks.pop();
+            self.starts.pop();
+        }
+        for chunk in &other.chunks {
+            let room = self
+                .chunks
+                .last()
+                .map_or(0, |last| CHUNK_SIZE.saturating_sub(last.len()));
+            if chunk.len() <= room {
+                self.extend_from_slice(chunk);
+            } else if !chunk.is_empty() {
+                self.chunks.push(Arc::clone(chunk));
+                self.starts.push(self.len);
+                self.len += chunk.len();
+            }
+        }
+    }
+
+    /// The chunks in order, as slices
+    fn slices(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.chunks.iter().map(|chunk| chunk.as_slice())
+    }
+
+    /// The parts of the chunks covering rows `range`, in order
+    ///
+    /// # Panics
+    /// Panics if the range runs past the end of the values
+    fn slices_in(&self, range: Range<usize>) -> impl Iterator<Item = &[T]> + '_ {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range {}..{} out of bounds (len: {})",
+            range.start,
+            range.end,
+            self.len
+        );
+        let first = if range.is_empty() {
+            self.chunks.len()
+        } else {
+            self.locate(range.start).0
+        };
+        self.chunks[first..]
+            .iter()
+            .zip(&self.starts[first..])
+            .map_while(move |(chunk, &start)| {
+                (start < range.end).then(|| {
+                    &chunk[range.start.saturating_sub(start)..(range.end - start).min(chunk.len())]
+                })
+            })
+    }
+
+    /// The values in rows `range`, in order
+    fn iter_in(&self, range: Range<usize>) -> impl Iterator<Item = &T> + '_ {
+        self.slices_in(range).flatten()
+    }
+
+    /// The values in the selected rows, in order
+    ///
+    /// # Panics
+    /// Panics if a selected row is past the end of the values
+    fn iter_selected<'a>(&'a self, rows: RowSelection<'a>) -> impl Iterator<Item = &'a T> + 'a {
+        let (range, listed) = match rows {
+            RowSelection::Range(range) => (range, None),
+            RowSelection::Rows(rows) => (0..0, Some(rows.iter())),
+        };
+        self.iter_in(range)
+            .chain(listed.into_iter().flatten().map(move |&row| {
+                self.get(row as usize)
+                    .unwrap_or_else(|| panic!("row {} out of bounds (len: {})", row, self.len))
+            }))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.slices().flatten()
+    }
+
+    fn to_vec(&self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len);
+        for slice in self.slices() {
+            values.extend_from_slice(slice);
+        }
+        values
+    }
+
+    fn clear(&mut self) {
+        self.chunks.clear();
+        self.starts.clear();
+        self.len = 0;
+    }
+
+    /// Heap bytes allocated for the values themselves
+    fn capacity_bytes(&self) -> usize {
+        self.chunks
+            .iter()
+            .map(|chunk| chunk.capacity() * std::mem::size_of::<T>())
+            .sum()
+    }
 }
 
 // ============================================================================
@@ -243,16 +807,17 @@ pub trait Column {
 /// A column that stores 64-bit integers
 ///
 /// This is the most efficient column type for integer data.
-/// Internally uses `Vec<i64>` for storage.
+/// Internally stores the values in chunks of up to [`CHUNK_SIZE`] `i64`s.
 ///
 /// # Memory Layout
 ///
 /// ```text
 /// IntColumn {
-///     data: [i64, i64, i64, ...]
+///     data: [[i64, i64, ...], [i64, i64, ...], ...]
 /// }
 /// ```
-/// Each value takes exactly 8 bytes of memory.
+/// Each value takes exactly 8 bytes of memory. Chunks are shared, not
+/// copied, when the column is cloned or appended to another.
 ///
 /// # Example
 ///
@@ -267,8 +832,10 @@ pub trait Column {
 /// ```
 #[derive(Debug, Clone)]
 pub struct IntColumn {
-    /// The underlying vector storing the integer values
-    data: Vec<i64>,
+    /// The values, in chunks of up to [`CHUNK_SIZE`]
+    data: Chunks<i64>,
+    /// Which rows are NULL
+    nulls: NullBitmap,
 }
 
 impl IntColumn {
@@ -285,7 +852,10 @@ impl IntColumn {
     /// assert_eq!(col.len(), 0);
     /// ```
     pub fn new() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: Chunks::new(),
+            nulls: NullBitmap::default(),
+        }
     }
 
     /// Creates a new `IntColumn` with pre-allocated capacity
@@ -309,17 +879,20 @@ impl IntColumn {
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            data: Vec::with_capacity(capacity),
+            data: Chunks::with_capacity(capacity),
+            nulls: NullBitmap::default(),
         }
     }
 
-    /// Returns a reference to the underlying data vector
+    /// Returns a copy of the column's values in one vector
     ///
-    /// This provides access to the raw `Vec<i64>` for advanced operations.
-    /// Be careful when using this method, as it bypasses type safety.
+    /// This provides the raw `i64`s for advanced operations. Be careful when
+    /// using this method, as it bypasses type safety and NULL tracking: NULL
+    /// rows appear as `0`. It copies every chunk; prefer
+    /// [`IntColumn::chunks`] or [`IntColumn::values_in`] on large columns.
     ///
     /// # Returns
-    /// A reference to the `Vec<i64>` containing all values
+    /// A `Vec<i64>` containing all values
     ///
     /// # Example
     /// ```rust
@@ -332,8 +905,85 @@ impl IntColumn {
     /// let vec = col.as_vec();
     /// assert_eq!(vec, &[42, 43]);
     /// ```
-    pub fn as_vec(&self) -> &[i64] {
-        &self.data
+    pub fn as_vec(&self) -> Vec<i64> {
+        self.data.to_vec()
+    }
+
+    /// Returns a copy of the column's values, without boxing each in a `Value`
+    ///
+    /// NULL rows hold `0`; check [`Column::is_null`] or use
+    /// [`IntColumn::iter`] to tell them apart.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// col.push_value(Value::Int64(42)).unwrap();
+    /// col.push_value(Value::Int64(43)).unwrap();
+    /// assert_eq!(col.values(), &[42, 43]);
+    /// ```
+    pub fn values(&self) -> Vec<i64> {
+        self.data.to_vec()
+    }
+
+    /// Returns the column's chunks as slices, in row order
+    ///
+    /// Every chunk holds at most [`CHUNK_SIZE`] values; NULL rows hold `0`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn, CHUNK_SIZE};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// for v in 0..CHUNK_SIZE as i64 + 1 {
+    ///     col.push_value(Value::Int64(v)).unwrap();
+    /// }
+    /// let sizes: Vec<usize> = col.chunks().map(|chunk| chunk.len()).collect();
+    /// assert_eq!(sizes, vec![CHUNK_SIZE, 1]);
+    /// ```
+    pub fn chunks(&self) -> impl Iterator<Item = &[i64]> + '_ {
+        self.data.slices()
+    }
+
+    /// Iterates over the values in rows `rows` (a range or a
+    /// [`RowSelection`]), reading across chunk boundaries without copying
+    ///
+    /// NULL rows hold `0`, as in [`IntColumn::values`].
+    ///
+    /// # Panics
+    /// Panics if a row is past the end of the column
+    pub fn values_in<'a>(
+        &'a self,
+        rows: impl Into<RowSelection<'a>>,
+    ) -> impl Iterator<Item = &'a i64> + 'a {
+        self.data.iter_selected(rows.into())
+    }
+
+    /// Iterates over the column's values, with None for NULL rows
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = IntColumn::new();
+    /// col.push_value(Value::Int64(40)).unwrap();
+    /// col.push_null();
+    /// col.push_value(Value::Int64(2)).unwrap();
+    ///
+    /// // Sum the non-NULL values without going through SQL
+    /// let total: i64 = col.iter().flatten().sum();
+    /// assert_eq!(total, 42);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Option<i64>> + '_ {
+        let nulls = &self.nulls;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(index, v)| (!nulls.is_null(index)).then_some(*v))
     }
 }
 
@@ -358,51 +1008,124 @@ impl Column for IntColumn {
                 self.data.push(v);
                 Ok(())
             }
+            Value::Null => {
+                self.push_null();
+                Ok(())
+            }
             _ => Err(DatabaseError::type_error(format!(
-                "Cannot push {:?} into IntColumn",
-                value.data_type()
+                "Cannot push {} into IntColumn",
+                value.type_name()
             ))),
         }
     }
 
-    fn get(&self, index: usize) -> Result<Value> {
-        self.data
-            .get(index)
-            .map(|v| Value::Int64(*v))
-            .ok_or_else(|| {
-                DatabaseError::column_error(format!(
-                    "Index {} out of bounds (len: {})",
-                    index,
-                    self.len()
-                ))
-            })
+    fn push_null(&mut self) {
+        self.nulls.set_null(self.data.len());
+        self.data.push(0);
     }
 
-    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
-        let data = &self.data;
-        let range = range.unwrap_or(0..data.len());
-        data[range].iter().map(|v| Value::Int64(*v)).collect()
+    fn null_count(&self) -> usize {
+        self.nulls.null_count()
     }
 
-    fn clear(&mut self) {
-        self.data.clear();
+    fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
     }
-}
 
-// ============================================================================
-// FLOAT COLUMN IMPLEMENTATION
-// ============================================================================
+    fn get(&self, index: usize) -> Result<Value> {
+        match self.data.get(index) {
+            Some(_) if self.nulls.is_null(index) => Ok(Value::Null),
+            Some(v) => Ok(Value::Int64(*v)),
+            None => Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.len()
+            ))),
+        }
+    }
+
+    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+        self.iter_range(range.unwrap_or(0..self.data.len()))
+            .collect()
+    }
+
+    fn iter_range(&self, range: Range<usize>) -> Box<dyn Iterator<Item = Value> + '_> {
+        let start = range.start;
+        Box::new(
+            self.data
+                .iter_in(range)
+                .enumerate()
+                .map(move |(offset, v)| {
+                    if self.nulls.is_null(start + offset) {
+                        Value::Null
+                    } else {
+                        Value::Int64(*v)
+                    }
+                }),
+        )
+    }
+
+    fn copy_range_into(&self, range: Range<usize>, target: &mut dyn Column) -> Result<()> {
+        check_copy_range(self.data_type(), self.len(), &range, target)?;
+        if let Some(target) = target.as_any_mut().downcast_mut::<IntColumn>() {
+            target
+                .nulls
+                .copy_range(&self.nulls, range.clone(), target.data.len());
+            for values in self.data.slices_in(range) {
+                target.data.extend_from_slice(values);
+            }
+            return Ok(());
+        }
+        push_values(target, self.iter_range(range))
+    }
+
+    fn append_column(&mut self, other: &dyn Column) -> Result<()> {
+        if let Some(other) = other.as_any().downcast_ref::<IntColumn>() {
+            self.nulls
+                .copy_range(&other.nulls, 0..other.len(), self.data.len());
+            self.data.append(&other.data);
+            return Ok(());
+        }
+        other.copy_range_into(0..other.len(), self)
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.nulls.clear();
+    }
+
+    fn memory_size(&self) -> usize {
+        self.data.capacity_bytes() + self.nulls.memory_size()
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        // Shares the chunks; each is copied only when next written to
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// FLOAT COLUMN IMPLEMENTATION
+// ============================================================================
 
 /// A column that stores 64-bit floating point numbers
 ///
-/// Uses `Vec<f64>` for storage. Suitable for decimal numbers, percentages,
-/// and any data requiring fractional precision.
+/// Stores the values in chunks of up to [`CHUNK_SIZE`] `f64`s. Suitable for
+/// decimal numbers, percentages, and any data requiring fractional precision.
 ///
 /// # Memory Layout
 ///
 /// ```text
 /// FloatColumn {
-///     data: [f64, f64, f64, ...]
+///     data: [[f64, f64, ...], [f64, f64, ...], ...]
 /// }
 /// ```
 /// Each value takes exactly 8 bytes of memory.
@@ -420,8 +1143,10 @@ impl Column for IntColumn {
 /// ```
 #[derive(Debug, Clone)]
 pub struct FloatColumn {
-    /// The underlying vector storing the floating point values
-    data: Vec<f64>,
+    /// The values, in chunks of up to [`CHUNK_SIZE`]
+    data: Chunks<f64>,
+    /// Which rows are NULL
+    nulls: NullBitmap,
 }
 
 impl FloatColumn {
@@ -438,7 +1163,10 @@ impl FloatColumn {
     /// assert_eq!(col.len(), 0);
     /// ```
     pub fn new() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: Chunks::new(),
+            nulls: NullBitmap::default(),
+        }
     }
 
     /// Creates a new `FloatColumn` with pre-allocated capacity
@@ -450,16 +1178,64 @@ impl FloatColumn {
     /// A new `FloatColumn` with the specified capacity
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            data: Vec::with_capacity(capacity),
+            data: Chunks::with_capacity(capacity),
+            nulls: NullBitmap::default(),
         }
     }
 
-    /// Returns a reference to the underlying data vector
+    /// Returns a copy of the column's values in one vector
     ///
     /// # Returns
-    /// A reference to the `Vec<f64>` containing all values
-    pub fn as_vec(&self) -> &[f64] {
-        &self.data
+    /// A `Vec<f64>` containing all values
+    pub fn as_vec(&self) -> Vec<f64> {
+        self.data.to_vec()
+    }
+
+    /// Returns a copy of the column's values, without boxing each in a `Value`
+    ///
+    /// NULL rows hold `0.0`; check [`Column::is_null`] or use
+    /// [`FloatColumn::iter`] to tell them apart.
+    pub fn values(&self) -> Vec<f64> {
+        self.data.to_vec()
+    }
+
+    /// Returns the column's chunks as slices, in row order
+    ///
+    /// Every chunk holds at most [`CHUNK_SIZE`] values; NULL rows hold `0.0`.
+    pub fn chunks(&self) -> impl Iterator<Item = &[f64]> + '_ {
+        self.data.slices()
+    }
+
+    /// Iterates over the values in rows `rows` (a range or a
+    /// [`RowSelection`]), reading across chunk boundaries without copying
+    ///
+    /// # Panics
+    /// Panics if a row is past the end of the column
+    pub fn values_in<'a>(
+        &'a self,
+        rows: impl Into<RowSelection<'a>>,
+    ) -> impl Iterator<Item = &'a f64> + 'a {
+        self.data.iter_selected(rows.into())
+    }
+
+    /// Iterates over the column's values, with None for NULL rows
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, FloatColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = FloatColumn::new();
+    /// col.push_value(Value::Float64(1.5)).unwrap();
+    /// col.push_null();
+    /// assert_eq!(col.iter().collect::<Vec<_>>(), vec![Some(1.5), None]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Option<f64>> + '_ {
+        let nulls = &self.nulls;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(index, v)| (!nulls.is_null(index)).then_some(*v))
     }
 }
 
@@ -484,34 +1260,107 @@ impl Column for FloatColumn {
                 self.data.push(v);
                 Ok(())
             }
+            Value::Null => {
+                self.push_null();
+                Ok(())
+            }
             _ => Err(DatabaseError::type_error(format!(
-                "Cannot push {:?} into FloatColumn",
-                value.data_type()
+                "Cannot push {} into FloatColumn",
+                value.type_name()
             ))),
         }
     }
 
+    fn push_null(&mut self) {
+        self.nulls.set_null(self.data.len());
+        self.data.push(0.0);
+    }
+
+    fn null_count(&self) -> usize {
+        self.nulls.null_count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
+    }
+
     fn get(&self, index: usize) -> Result<Value> {
-        self.data
-            .get(index)
-            .map(|v| Value::Float64(*v))
-            .ok_or_else(|| {
-                DatabaseError::column_error(format!(
-                    "Index {} out of bounds (len: {})",
-                    index,
-                    self.len()
-                ))
-            })
+        match self.data.get(index) {
+            Some(_) if self.nulls.is_null(index) => Ok(Value::Null),
+            Some(v) => Ok(Value::Float64(*v)),
+            None => Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.len()
+            ))),
+        }
     }
 
     fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
-        let data = &self.data;
-        let range = range.unwrap_or(0..data.len());
-        data[range].iter().map(|v| Value::Float64(*v)).collect()
+        self.iter_range(range.unwrap_or(0..self.data.len()))
+            .collect()
+    }
+
+    fn iter_range(&self, range: Range<usize>) -> Box<dyn Iterator<Item = Value> + '_> {
+        let start = range.start;
+        Box::new(
+            self.data
+                .iter_in(range)
+                .enumerate()
+                .map(move |(offset, v)| {
+                    if self.nulls.is_null(start + offset) {
+                        Value::Null
+                    } else {
+                        Value::Float64(*v)
+                    }
+                }),
+        )
+    }
+
+    fn copy_range_into(&self, range: Range<usize>, target: &mut dyn Column) -> Result<()> {
+        check_copy_range(self.data_type(), self.len(), &range, target)?;
+        if let Some(target) = target.as_any_mut().downcast_mut::<FloatColumn>() {
+            target
+                .nulls
+                .copy_range(&self.nulls, range.clone(), target.data.len());
+            for values in self.data.slices_in(range) {
+                target.data.extend_from_slice(values);
+            }
+            return Ok(());
+        }
+        push_values(target, self.iter_range(range))
+    }
+
+    fn append_column(&mut self, other: &dyn Column) -> Result<()> {
+        if let Some(other) = other.as_any().downcast_ref::<FloatColumn>() {
+            self.nulls
+                .copy_range(&other.nulls, 0..other.len(), self.data.len());
+            self.data.append(&other.data);
+            return Ok(());
+        }
+        other.copy_range_into(0..other.len(), self)
     }
 
     fn clear(&mut self) {
         self.data.clear();
+        self.nulls.clear();
+    }
+
+    fn memory_size(&self) -> usize {
+        self.data.capacity_bytes() + self.nulls.memory_size()
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        // Shares the chunks; each is copied only when next written to
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
     }
 }
 
@@ -521,14 +1370,15 @@ impl Column for FloatColumn {
 
 /// A column that stores UTF-8 strings
 ///
-/// Uses `Vec<String>` for storage. Each string is heap-allocated.
-/// This is the most flexible column type but has higher memory overhead.
+/// Stores the values in chunks of up to [`CHUNK_SIZE`] `String`s. Each string
+/// is heap-allocated. This is the most flexible column type but has higher
+/// memory overhead.
 ///
 /// # Memory Layout
 ///
 /// ```text
 /// StringColumn {
-///     data: [String, String, String, ...]
+///     data: [[String, String, ...], [String, String, ...], ...]
 /// }
 /// ```
 /// Each entry is a pointer to heap-allocated string data.
@@ -546,8 +1396,10 @@ impl Column for FloatColumn {
 /// ```
 #[derive(Debug, Clone)]
 pub struct StringColumn {
-    /// The underlying vector storing the string values
-    data: Vec<String>,
+    /// The values, in chunks of up to [`CHUNK_SIZE`]
+    data: Chunks<String>,
+    /// Which rows are NULL
+    nulls: NullBitmap,
 }
 
 impl StringColumn {
@@ -564,7 +1416,10 @@ impl StringColumn {
     /// assert_eq!(col.len(), 0);
     /// ```
     pub fn new() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: Chunks::new(),
+            nulls: NullBitmap::default(),
+        }
     }
 
     /// Creates a new `StringColumn` with pre-allocated capacity
@@ -576,16 +1431,66 @@ impl StringColumn {
     /// A new `StringColumn` with the specified capacity
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            data: Vec::with_capacity(capacity),
+            data: Chunks::with_capacity(capacity),
+            nulls: NullBitmap::default(),
         }
     }
 
-    /// Returns a reference to the underlying data vector
+    /// Returns a copy of the column's values in one vector
     ///
     /// # Returns
-    /// A reference to the `Vec<String>` containing all values
-    pub fn as_vec(&self) -> &[String] {
-        &self.data
+    /// A `Vec<String>` containing all values
+    pub fn as_vec(&self) -> Vec<String> {
+        self.data.to_vec()
+    }
+
+    /// Returns a copy of the column's values, without wrapping each in a
+    /// `Value`
+    ///
+    /// NULL rows hold an empty string; check [`Column::is_null`] or use
+    /// [`StringColumn::iter`] to tell them apart.
+    pub fn values(&self) -> Vec<String> {
+        self.data.to_vec()
+    }
+
+    /// Returns the column's chunks as slices, in row order
+    ///
+    /// Every chunk holds at most [`CHUNK_SIZE`] values; NULL rows hold an
+    /// empty string.
+    pub fn chunks(&self) -> impl Iterator<Item = &[String]> + '_ {
+        self.data.slices()
+    }
+
+    /// Iterates over the values in rows `rows` (a range or a
+    /// [`RowSelection`]), reading across chunk boundaries without cloning
+    ///
+    /// # Panics
+    /// Panics if a row is past the end of the column
+    pub fn values_in<'a>(
+        &'a self,
+        rows: impl Into<RowSelection<'a>>,
+    ) -> impl Iterator<Item = &'a String> + 'a {
+        self.data.iter_selected(rows.into())
+    }
+
+    /// Iterates over the column's values, with None for NULL rows
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, StringColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = StringColumn::new();
+    /// col.push_value(Value::String("a".to_string())).unwrap();
+    /// col.push_null();
+    /// assert_eq!(col.iter().collect::<Vec<_>>(), vec![Some("a"), None]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        let nulls = &self.nulls;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(index, v)| (!nulls.is_null(index)).then_some(v.as_str()))
     }
 }
 
@@ -604,43 +1509,1890 @@ impl Column for StringColumn {
         self.data.len()
     }
 
-    fn push_value(&mut self, value: Value) -> Result<()> {
-        match value {
-            Value::String(v) => {
-                self.data.push(v);
-                Ok(())
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        match value {
+            Value::String(v) => {
+                self.data.push(v);
+                Ok(())
+            }
+            Value::Null => {
+                self.push_null();
+                Ok(())
+            }
+            _ => Err(DatabaseError::type_error(format!(
+                "Cannot push {} into StringColumn",
+                value.type_name()
+            ))),
+        }
+    }
+
+    fn push_null(&mut self) {
+        self.nulls.set_null(self.data.len());
+        self.data.push(String::new());
+    }
+
+    fn null_count(&self) -> usize {
+        self.nulls.null_count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        match self.data.get(index) {
+            Some(_) if self.nulls.is_null(index) => Ok(Value::Null),
+            Some(v) => Ok(Value::String(v.clone())),
+            None => Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.len()
+            ))),
+        }
+    }
+
+    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+        self.iter_range(range.unwrap_or(0..self.data.len()))
+            .collect()
+    }
+
+    fn iter_range(&self, range: Range<usize>) -> Box<dyn Iterator<Item = Value> + '_> {
+        let start = range.start;
+        Box::new(
+            self.data
+                .iter_in(range)
+                .enumerate()
+                .map(move |(offset, v)| {
+                    if self.nulls.is_null(start + offset) {
+                        Value::Null
+                    } else {
+                        Value::String(v.clone())
+                    }
+                }),
+        )
+    }
+
+    fn copy_range_into(&self, range: Range<usize>, target: &mut dyn Column) -> Result<()> {
+        check_copy_range(self.data_type(), self.len(), &range, target)?;
+        if let Some(target) = target.as_any_mut().downcast_mut::<StringColumn>() {
+            target
+                .nulls
+                .copy_range(&self.nulls, range.clone(), target.data.len());
+            for values in self.data.slices_in(range) {
+                target.data.extend_from_slice(values);
+            }
+            return Ok(());
+        }
+        push_values(target, self.iter_range(range))
+    }
+
+    fn append_column(&mut self, other: &dyn Column) -> Result<()> {
+        if let Some(other) = other.as_any().downcast_ref::<StringColumn>() {
+            self.nulls
+                .copy_range(&other.nulls, 0..other.len(), self.data.len());
+            self.data.append(&other.data);
+            return Ok(());
+        }
+        other.copy_range_into(0..other.len(), self)
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.nulls.clear();
+    }
+
+    fn memory_size(&self) -> usize {
+        self.data.capacity_bytes()
+            + self.data.iter().map(String::capacity).sum::<usize>()
+            + self.nulls.memory_size()
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        // Shares the chunks; each is copied only when next written to
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// DICTIONARY-ENCODED STRING COLUMN IMPLEMENTATION
+// ============================================================================
+
+/// The distinct values of a `DictStringColumn`, in first-seen order
+#[derive(Debug, Clone, Default)]
+struct StringDictionary {
+    /// Value of each code
+    values: Vec<String>,
+    /// Code of each value
+    codes: HashMap<String, u32>,
+}
+
+/// A String column that stores each distinct value once
+///
+/// Rows hold a `u32` code into a dictionary of distinct values, so a
+/// low-cardinality column (country, status, category) costs four bytes per
+/// row instead of a heap `String` per row. Its data type is
+/// `DataType::String`, and it reads back exactly like a `StringColumn`.
+///
+/// Columns built from one another with [`DictStringColumn::gather`] share
+/// the dictionary, which lets operators compare codes instead of strings
+/// (see [`DictStringColumn::shares_dictionary`]). Pushing a value that is
+/// not yet in a shared dictionary gives the column its own copy first.
+///
+/// # Memory Layout
+///
+/// ```text
+/// DictStringColumn {
+///     codes:      [0, 1, 0, 0, 2, ...]
+///     dictionary: ["DE", "FR", "US"]
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, DictStringColumn};
+/// use mini_rust_olap::types::Value;
+///
+/// let mut col = DictStringColumn::new();
+/// for country in ["US", "DE", "US", "US"] {
+///     col.push_value(Value::String(country.to_string())).unwrap();
+/// }
+/// assert_eq!(col.codes(), &[0, 1, 0, 0]);
+/// assert_eq!(col.dictionary(), &["US".to_string(), "DE".to_string()]);
+/// assert_eq!(col.get(2).unwrap(), Value::String("US".to_string()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DictStringColumn {
+    /// One code per row; NULL rows hold code 0
+    codes: Vec<u32>,
+    /// The distinct values, shared with columns gathered from this one
+    dictionary: Arc<StringDictionary>,
+    /// Which rows are NULL
+    nulls: NullBitmap,
+}
+
+impl DictStringColumn {
+    /// Creates a new empty `DictStringColumn`
+    ///
+    /// # Returns
+    /// A new `DictStringColumn` with no values and an empty dictionary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the code of every row
+    ///
+    /// NULL rows appear as `0`; check [`Column::is_null`] for them.
+    pub fn codes(&self) -> &[u32] {
+        &self.codes
+    }
+
+    /// Returns the distinct values, indexed by code
+    pub fn dictionary(&self) -> &[String] {
+        &self.dictionary.values
+    }
+
+    /// Returns the code of a value, or `None` if it is not in the dictionary
+    ///
+    /// # Arguments
+    /// * `value` - The string to look up
+    pub fn lookup(&self, value: &str) -> Option<u32> {
+        self.dictionary.codes.get(value).copied()
+    }
+
+    /// Returns true if both columns use the same dictionary, so equal codes
+    /// mean equal strings
+    ///
+    /// # Arguments
+    /// * `other` - The column to compare with
+    pub fn shares_dictionary(&self, other: &DictStringColumn) -> bool {
+        Arc::ptr_eq(&self.dictionary, &other.dictionary)
+    }
+
+    /// Builds a column from the given rows of this one, sharing its
+    /// dictionary
+    ///
+    /// # Arguments
+    /// * `rows` - Row indices to copy, in output order; each must be in bounds
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, DictStringColumn};
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let mut col = DictStringColumn::new();
+    /// for status in ["open", "closed", "open"] {
+    ///     col.push_value(Value::String(status.to_string())).unwrap();
+    /// }
+    /// let tail = col.gather(1..3);
+    /// assert_eq!(tail.len(), 2);
+    /// assert!(tail.shares_dictionary(&col));
+    /// ```
+    pub fn gather(&self, rows: impl IntoIterator<Item = usize>) -> DictStringColumn {
+        let mut gathered = DictStringColumn {
+            codes: Vec::new(),
+            dictionary: Arc::clone(&self.dictionary),
+            nulls: NullBitmap::default(),
+        };
+        for row in rows {
+            if self.nulls.is_null(row) {
+                gathered.nulls.set_null(gathered.codes.len());
+            }
+            gathered.codes.push(self.codes[row]);
+        }
+        gathered
+    }
+}
+
+impl Column for DictStringColumn {
+    fn data_type(&self) -> DataType {
+        DataType::String
+    }
+
+    fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        match value {
+            Value::String(v) => {
+                let code = match self.dictionary.codes.get(&v) {
+                    Some(&code) => code,
+                    None => {
+                        let dictionary = Arc::make_mut(&mut self.dictionary);
+                        let code = u32::try_from(dictionary.values.len()).map_err(|_| {
+                            DatabaseError::column_error(
+                                "DictStringColumn cannot hold more than 2^32 distinct values"
+                                    .to_string(),
+                            )
+                        })?;
+                        dictionary.values.push(v.clone());
+                        dictionary.codes.insert(v, code);
+                        code
+                    }
+                };
+                self.codes.push(code);
+                Ok(())
+            }
+            Value::Null => {
+                self.push_null();
+                Ok(())
+            }
+            _ => Err(DatabaseError::type_error(format!(
+                "Cannot push {} into DictStringColumn",
+                value.type_name()
+            ))),
+        }
+    }
+
+    fn push_null(&mut self) {
+        self.nulls.set_null(self.codes.len());
+        self.codes.push(0);
+    }
+
+    fn null_count(&self) -> usize {
+        self.nulls.null_count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        match self.codes.get(index) {
+            Some(_) if self.nulls.is_null(index) => Ok(Value::Null),
+            Some(&code) => self
+                .dictionary
+                .values
+                .get(code as usize)
+                .map(|v| Value::String(v.clone()))
+                .ok_or_else(|| {
+                    DatabaseError::column_error(format!(
+                        "Row {} has code {} outside the dictionary of {} values",
+                        index,
+                        code,
+                        self.dictionary.values.len()
+                    ))
+                }),
+            None => Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.len()
+            ))),
+        }
+    }
+
+    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+        let range = range.unwrap_or(0..self.codes.len());
+        range
+            .map(|row| self.get(row).unwrap_or(Value::Null))
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.codes.clear();
+        self.dictionary = Arc::default();
+        self.nulls.clear();
+    }
+
+    fn memory_size(&self) -> usize {
+        // Each distinct string is stored twice: in `values` and as a key
+        let strings: usize = self.dictionary.values.iter().map(String::capacity).sum();
+        self.codes.capacity() * std::mem::size_of::<u32>()
+            + self.dictionary.values.capacity() * std::mem::size_of::<String>()
+            + self.dictionary.codes.capacity()
+                * (std::mem::size_of::<String>() + std::mem::size_of::<u32>())
+            + 2 * strings
+            + self.nulls.memory_size()
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// BOOL COLUMN IMPLEMENTATION
+// ============================================================================
+
+/// A column that stores booleans, packed one bit per row
+///
+/// # Memory Layout
+///
+/// ```text
+/// BoolColumn {
+///     bits: [u64, u64, ...]   // bit i % 64 of word i / 64 is row i
+///     len: usize
+/// }
+/// ```
+/// Each value takes a single bit, so 64 rows share one word.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, BoolColumn};
+/// use mini_rust_olap::types::Value;
+///
+/// let mut col = BoolColumn::new();
+/// col.push_value(Value::Boolean(true)).unwrap();
+/// col.push_value(Value::Boolean(false)).unwrap();
+/// assert_eq!(col.get(0).unwrap(), Value::Boolean(true));
+/// assert_eq!(col.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BoolColumn {
+    /// The packed values; NULL rows hold a clear bit
+    bits: Vec<u64>,
+    /// Number of rows
+    len: usize,
+    /// Which rows are NULL
+    nulls: NullBitmap,
+}
+
+impl BoolColumn {
+    /// Creates a new empty `BoolColumn`
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, BoolColumn};
+    ///
+    /// let col = BoolColumn::new();
+    /// assert_eq!(col.len(), 0);
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a bit, growing the packed words as needed
+    fn push_bit(&mut self, value: bool) {
+        if self.len.is_multiple_of(64) {
+            self.bits.push(0);
+        }
+        if value {
+            self.bits[self.len / 64] |= 1 << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    /// Returns the bit stored for row `index`, which must be in bounds
+    fn bit(&self, index: usize) -> bool {
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn value_at(&self, index: usize) -> Value {
+        if self.nulls.is_null(index) {
+            Value::Null
+        } else {
+            Value::Boolean(self.bit(index))
+        }
+    }
+}
+
+impl Column for BoolColumn {
+    fn data_type(&self) -> DataType {
+        DataType::Boolean
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        match value {
+            Value::Boolean(v) => {
+                self.push_bit(v);
+                Ok(())
+            }
+            Value::Null => {
+                self.push_null();
+                Ok(())
+            }
+            _ => Err(DatabaseError::type_error(format!(
+                "Cannot push {} into BoolColumn",
+                value.type_name()
+            ))),
+        }
+    }
+
+    fn push_null(&mut self) {
+        self.nulls.set_null(self.len);
+        self.push_bit(false);
+    }
+
+    fn null_count(&self) -> usize {
+        self.nulls.null_count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        if index < self.len {
+            Ok(self.value_at(index))
+        } else {
+            Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.len()
+            )))
+        }
+    }
+
+    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+        let range = range.unwrap_or(0..self.len);
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range end index {} out of range for column of length {}",
+            range.end,
+            self.len
+        );
+        range.map(|index| self.value_at(index)).collect()
+    }
+
+    fn clear(&mut self) {
+        self.bits.clear();
+        self.len = 0;
+        self.nulls.clear();
+    }
+
+    fn memory_size(&self) -> usize {
+        self.bits.capacity() * std::mem::size_of::<u64>() + self.nulls.memory_size()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// DATE COLUMN IMPLEMENTATION
+// ============================================================================
+
+/// A column that stores calendar dates
+///
+/// Dates are kept as days since 1970-01-01, so comparing and sorting them
+/// is plain integer work.
+///
+/// # Memory Layout
+///
+/// ```text
+/// DateColumn {
+///     data: [i32, i32, i32, ...]
+/// }
+/// ```
+/// Each value takes exactly 4 bytes of memory.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, DateColumn};
+/// use mini_rust_olap::types::{parse_date, Value};
+///
+/// let mut col = DateColumn::new();
+/// col.push_value(Value::Date(parse_date("2024-01-01").unwrap())).unwrap();
+/// assert_eq!(col.get(0).unwrap().to_string(), "2024-01-01");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DateColumn {
+    /// Days since 1970-01-01 for each row
+    data: Vec<i32>,
+    /// Which rows are NULL
+    nulls: NullBitmap,
+}
+
+impl DateColumn {
+    /// Creates a new empty `DateColumn`
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, DateColumn};
+    ///
+    /// let col = DateColumn::new();
+    /// assert_eq!(col.len(), 0);
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the stored day numbers; NULL rows appear as `0`
+    pub fn as_vec(&self) -> &[i32] {
+        &self.data
+    }
+}
+
+impl Column for DateColumn {
+    fn data_type(&self) -> DataType {
+        DataType::Date
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        match value {
+            Value::Date(v) => {
+                self.data.push(v);
+                Ok(())
+            }
+            Value::Null => {
+                self.push_null();
+                Ok(())
+            }
+            _ => Err(DatabaseError::type_error(format!(
+                "Cannot push {} into DateColumn",
+                value.type_name()
+            ))),
+        }
+    }
+
+    fn push_null(&mut self) {
+        self.nulls.set_null(self.data.len());
+        self.data.push(0);
+    }
+
+    fn null_count(&self) -> usize {
+        self.nulls.null_count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        match self.data.get(index) {
+            Some(_) if self.nulls.is_null(index) => Ok(Value::Null),
+            Some(v) => Ok(Value::Date(*v)),
+            None => Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.len()
+            ))),
+        }
+    }
+
+    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+        self.iter_range(range.unwrap_or(0..self.data.len()))
+            .collect()
+    }
+
+    fn iter_range(&self, range: Range<usize>) -> Box<dyn Iterator<Item = Value> + '_> {
+        let start = range.start;
+        Box::new(self.data[range].iter().enumerate().map(move |(offset, v)| {
+            if self.nulls.is_null(start + offset) {
+                Value::Null
+            } else {
+                Value::Date(*v)
+            }
+        }))
+    }
+
+    fn copy_range_into(&self, range: Range<usize>, target: &mut dyn Column) -> Result<()> {
+        check_copy_range(self.data_type(), self.len(), &range, target)?;
+        if let Some(target) = target.as_any_mut().downcast_mut::<DateColumn>() {
+            target
+                .nulls
+                .copy_range(&self.nulls, range.clone(), target.data.len());
+            target.data.extend_from_slice(&self.data[range]);
+            return Ok(());
+        }
+        push_values(target, self.iter_range(range))
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.nulls.clear();
+    }
+
+    fn memory_size(&self) -> usize {
+        self.data.capacity() * std::mem::size_of::<i32>() + self.nulls.memory_size()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// RUN-LENGTH ENCODED COLUMN IMPLEMENTATION
+// ============================================================================
+
+/// A column that stores each run of equal consecutive values once
+///
+/// Rows are grouped into runs of equal values (NULL included), and each run
+/// is stored as its value and the row it ends before, so a sorted or
+/// low-cardinality column with long runs costs a few bytes per run instead
+/// of a value per row. It holds any data type and reads back exactly like
+/// the column it was built from; `get` finds a row's run with a binary
+/// search.
+///
+/// # Memory Layout
+///
+/// ```text
+/// Values: [DE, DE, DE, FR, FR, NULL, US, US, US, US]
+///
+/// RleColumn {
+///     values:   [DE, FR, NULL, US]
+///     run_ends: [3,  5,  6,    10]
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, RleColumn};
+/// use mini_rust_olap::types::{DataType, Value};
+///
+/// let mut col = RleColumn::new(DataType::Int64);
+/// for v in [7, 7, 7, 9] {
+///     col.push_value(Value::Int64(v)).unwrap();
+/// }
+/// assert_eq!(col.len(), 4);
+/// assert_eq!(col.run_count(), 2);
+/// assert_eq!(col.get(2).unwrap(), Value::Int64(7));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RleColumn {
+    /// The type of every non-NULL value
+    data_type: DataType,
+    /// The value of each run
+    values: Vec<Value>,
+    /// The row after each run's last row; increasing
+    run_ends: Vec<usize>,
+}
+
+impl RleColumn {
+    /// Creates a new empty `RleColumn` holding values of `data_type`
+    ///
+    /// # Arguments
+    /// * `data_type` - The column's data type
+    pub fn new(data_type: DataType) -> Self {
+        RleColumn {
+            data_type,
+            values: Vec::new(),
+            run_ends: Vec::new(),
+        }
+    }
+
+    /// Builds a run-length encoded copy of a column
+    ///
+    /// # Arguments
+    /// * `column` - The column to encode, in any representation
+    pub fn encode(column: &dyn Column) -> Result<Self> {
+        let mut encoded = RleColumn::new(column.data_type());
+        for row in 0..column.len() {
+            encoded.push_value(column.get(row)?)?;
+        }
+        encoded.values.shrink_to_fit();
+        encoded.run_ends.shrink_to_fit();
+        Ok(encoded)
+    }
+
+    /// Returns the number of runs
+    pub fn run_count(&self) -> usize {
+        self.run_ends.len()
+    }
+
+    /// Returns the index of the run holding `row`, which must be in bounds
+    fn run_of(&self, row: usize) -> usize {
+        self.run_ends.partition_point(|&end| end <= row)
+    }
+}
+
+/// Whether two values are identical, telling apart float values that
+/// compare equal (0.0 and -0.0) and matching NaN with itself, so encoding
+/// never changes a value.
+fn same_value(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Float64(a), Value::Float64(b)) => a.to_bits() == b.to_bits(),
+        _ => a == b,
+    }
+}
+
+impl Column for RleColumn {
+    fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    fn len(&self) -> usize {
+        self.run_ends.last().copied().unwrap_or(0)
+    }
+
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        if let Some(data_type) = value.data_type() {
+            if data_type != self.data_type {
+                return Err(DatabaseError::type_error(format!(
+                    "Cannot push {} into RleColumn of {}",
+                    value.type_name(),
+                    self.data_type
+                )));
+            }
+        }
+
+        match (self.values.last(), self.run_ends.last_mut()) {
+            (Some(last), Some(end)) if same_value(last, &value) => *end += 1,
+            _ => {
+                let end = self.len() + 1;
+                self.values.push(value);
+                self.run_ends.push(end);
+            }
+        }
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        // NULL fits every type
+        let _ = self.push_value(Value::Null);
+    }
+
+    fn null_count(&self) -> usize {
+        let mut start = 0;
+        let mut count = 0;
+        for (value, &end) in self.values.iter().zip(&self.run_ends) {
+            if value.is_null() {
+                count += end - start;
+            }
+            start = end;
+        }
+        count
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        index < self.len() && self.values[self.run_of(index)].is_null()
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        if index >= self.len() {
+            return Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.len()
+            )));
+        }
+        Ok(self.values[self.run_of(index)].clone())
+    }
+
+    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+        let range = range.unwrap_or(0..self.len());
+        let end = range.end.min(self.len());
+        let mut values = Vec::with_capacity(end.saturating_sub(range.start));
+        let mut row = range.start;
+        let mut run = self.run_of(row);
+        while row < end {
+            let run_end = self.run_ends[run].min(end);
+            values.extend(std::iter::repeat_n(&self.values[run], run_end - row).cloned());
+            row = run_end;
+            run += 1;
+        }
+        values
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.run_ends.clear();
+    }
+
+    fn memory_size(&self) -> usize {
+        let strings: usize = self
+            .values
+            .iter()
+            .map(|value| match value {
+                Value::String(s) => s.capacity(),
+                _ => 0,
+            })
+            .sum();
+        self.values.capacity() * std::mem::size_of::<Value>()
+            + strings
+            + self.run_ends.capacity() * std::mem::size_of::<usize>()
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// DELTA-ENCODED INT COLUMN IMPLEMENTATION
+// ============================================================================
+
+/// Rows per block of a [`DeltaColumn`]; reading a row decodes at most this
+/// many deltas
+pub const DELTA_BLOCK_SIZE: usize = 64;
+
+/// An Int64 column that stores each value as its difference from the
+/// previous one
+///
+/// Differences are zigzag-encoded, so small steps in either direction stay
+/// small, and written as variable-length integers of 7 bits per byte. Ids,
+/// timestamps and other increasing columns with small steps then take one
+/// or two bytes per row instead of eight. The first value of every block of
+/// [`DELTA_BLOCK_SIZE`] rows is stored whole, with the position of the
+/// block's deltas, so `get` decodes at most one block. NULL rows repeat the
+/// previous value and are marked in a bitmap.
+///
+/// # Memory Layout
+///
+/// ```text
+/// Values: [1000, 1001, 1003, 1004, | 1010, 1011, ...]
+///
+/// DeltaColumn {
+///     block_starts:  [1000, 1010, ...]
+///     block_offsets: [0, 3, ...]
+///     deltas:        [2, 4, 2, | 2, ...]   (zigzag: +1 -> 2, +2 -> 4)
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, DeltaColumn, IntColumn};
+/// use mini_rust_olap::types::Value;
+///
+/// let mut ids = IntColumn::new();
+/// for id in 1_000_000..1_010_000 {
+///     ids.push_value(Value::Int64(id)).unwrap();
+/// }
+/// let encoded = DeltaColumn::encode(&ids).unwrap();
+/// assert_eq!(encoded.get(1234).unwrap(), Value::Int64(1_001_234));
+/// assert!(encoded.memory_size() * 4 < ids.memory_size());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeltaColumn {
+    /// Value of the first row of each block
+    block_starts: Vec<i64>,
+    /// Position in `deltas` of each block's second row
+    block_offsets: Vec<usize>,
+    /// Zigzag varint difference of each row that does not start a block
+    deltas: Vec<u8>,
+    /// The last value pushed, which the next difference is taken from
+    last: i64,
+    /// Number of rows
+    len: usize,
+    /// Which rows are NULL
+    nulls: NullBitmap,
+}
+
+impl DeltaColumn {
+    /// Creates a new empty `DeltaColumn`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a delta-encoded copy of an Int64 column
+    ///
+    /// # Arguments
+    /// * `column` - The column to encode, in any representation
+    ///
+    /// # Returns
+    /// An error if the column is not Int64
+    pub fn encode(column: &dyn Column) -> Result<Self> {
+        let mut encoded = DeltaColumn::new();
+        for row in 0..column.len() {
+            encoded.push_value(column.get(row)?)?;
+        }
+        encoded.block_starts.shrink_to_fit();
+        encoded.block_offsets.shrink_to_fit();
+        encoded.deltas.shrink_to_fit();
+        Ok(encoded)
+    }
+
+    /// Appends a value, NULL or not
+    fn push_raw(&mut self, value: i64) {
+        if self.len.is_multiple_of(DELTA_BLOCK_SIZE) {
+            self.block_starts.push(value);
+            self.block_offsets.push(self.deltas.len());
+        } else {
+            let delta = value.wrapping_sub(self.last);
+            let mut zigzag = ((delta << 1) ^ (delta >> 63)) as u64;
+            while zigzag >= 0x80 {
+                self.deltas.push(zigzag as u8 | 0x80);
+                zigzag >>= 7;
+            }
+            self.deltas.push(zigzag as u8);
+        }
+        self.last = value;
+        self.len += 1;
+    }
+
+    /// Calls `visit` with each row of `range` and its stored value, decoding
+    /// from the start of the block holding the first row.
+    fn decode(&self, range: Range<usize>, mut visit: impl FnMut(usize, i64)) {
+        let range = range.start..range.end.min(self.len);
+        if range.is_empty() {
+            return;
+        }
+
+        let block = range.start / DELTA_BLOCK_SIZE;
+        let mut row = block * DELTA_BLOCK_SIZE;
+        let mut value = self.block_starts[block];
+        let mut position = self.block_offsets[block];
+        loop {
+            if row >= range.start {
+                visit(row, value);
+            }
+            row += 1;
+            if row >= range.end {
+                return;
+            }
+            if row.is_multiple_of(DELTA_BLOCK_SIZE) {
+                value = self.block_starts[row / DELTA_BLOCK_SIZE];
+                continue;
+            }
+
+            let mut zigzag = 0u64;
+            let mut shift = 0;
+            loop {
+                let byte = self.deltas[position];
+                position += 1;
+                zigzag |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+            }
+            let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+            value = value.wrapping_add(delta);
+        }
+    }
+}
+
+impl Column for DeltaColumn {
+    fn data_type(&self) -> DataType {
+        DataType::Int64
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        match value {
+            Value::Int64(v) => {
+                self.push_raw(v);
+                Ok(())
+            }
+            Value::Null => {
+                self.push_null();
+                Ok(())
+            }
+            _ => Err(DatabaseError::type_error(format!(
+                "Cannot push {} into DeltaColumn",
+                value.type_name()
+            ))),
+        }
+    }
+
+    fn push_null(&mut self) {
+        self.nulls.set_null(self.len);
+        self.push_raw(self.last);
+    }
+
+    fn null_count(&self) -> usize {
+        self.nulls.null_count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        self.nulls.is_null(index)
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        if index >= self.len {
+            return Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index, self.len
+            )));
+        }
+        if self.nulls.is_null(index) {
+            return Ok(Value::Null);
+        }
+        let mut value = 0;
+        self.decode(index..index + 1, |_, v| value = v);
+        Ok(Value::Int64(value))
+    }
+
+    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+        let range = range.unwrap_or(0..self.len);
+        let mut values = Vec::with_capacity(range.len());
+        self.decode(range, |row, v| {
+            values.push(if self.nulls.is_null(row) {
+                Value::Null
+            } else {
+                Value::Int64(v)
+            })
+        });
+        values
+    }
+
+    fn clear(&mut self) {
+        *self = DeltaColumn::new();
+    }
+
+    fn memory_size(&self) -> usize {
+        self.block_starts.capacity() * std::mem::size_of::<i64>()
+            + self.block_offsets.capacity() * std::mem::size_of::<usize>()
+            + self.deltas.capacity()
+            + self.nulls.memory_size()
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// COMPRESSION
+// ============================================================================
+
+/// A compressed representation chosen by [`compress_column`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// [`RleColumn`]: one value per run of equal values
+    RunLength,
+    /// [`DeltaColumn`]: variable-length differences between Int64 values
+    Delta,
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encoding::RunLength => write!(f, "run-length"),
+            Encoding::Delta => write!(f, "delta"),
+        }
+    }
+}
+
+/// An encoding replaces a column only if it needs at most this percentage
+/// of the column's memory, so a marginal saving does not cost slower reads
+pub const COMPRESSION_THRESHOLD_PERCENT: usize = 75;
+
+/// Picks the smallest compressed encoding of a column, if it saves enough
+///
+/// Run-length encoding is tried for every type, once counting runs shows it
+/// could win; delta encoding is tried for Int64 columns. A candidate wins
+/// if it needs at most [`COMPRESSION_THRESHOLD_PERCENT`] of the memory the
+/// column uses now. Columns that are already compressed are left alone.
+///
+/// # Arguments
+/// * `column` - The column to analyze
+///
+/// # Returns
+/// The chosen encoding and the encoded column, or `None` if no encoding
+/// saves enough
+///
+/// # Example
+/// ```rust
+/// use mini_rust_olap::column::{compress_column, Column, Encoding, StringColumn};
+/// use mini_rust_olap::types::Value;
+///
+/// let mut status = StringColumn::new();
+/// for row in 0..1000 {
+///     let value = if row < 600 { "open" } else { "closed" };
+///     status.push_value(Value::String(value.to_string())).unwrap();
+/// }
+/// let (encoding, compressed) = compress_column(&status).unwrap().unwrap();
+/// assert_eq!(encoding, Encoding::RunLength);
+/// assert_eq!(compressed.slice(None), status.slice(None));
+/// ```
+pub fn compress_column(column: &dyn Column) -> Result<Option<(Encoding, Box<dyn Column>)>> {
+    let any = column.as_any();
+    if any.is::<RleColumn>() || any.is::<DeltaColumn>() || column.is_empty() {
+        return Ok(None);
+    }
+    let limit = column.memory_size() * COMPRESSION_THRESHOLD_PERCENT / 100;
+
+    let mut best: Option<(Encoding, Box<dyn Column>)> = None;
+    let mut consider = |encoding: Encoding, candidate: Box<dyn Column>| {
+        let size = candidate.memory_size();
+        let smallest = best
+            .as_ref()
+            .map_or(limit, |(_, b)| b.memory_size().min(limit));
+        if size <= smallest {
+            best = Some((encoding, candidate));
+        }
+    };
+
+    // Counting runs is cheap; only build the runs if they can fit
+    let mut runs = 0;
+    let mut previous: Option<Value> = None;
+    for row in 0..column.len() {
+        let value = column.get(row)?;
+        if !previous.as_ref().is_some_and(|p| same_value(p, &value)) {
+            runs += 1;
+        }
+        previous = Some(value);
+    }
+    if runs * (std::mem::size_of::<Value>() + std::mem::size_of::<usize>()) <= limit {
+        consider(Encoding::RunLength, Box::new(RleColumn::encode(column)?));
+    }
+
+    if column.data_type() == DataType::Int64 {
+        consider(Encoding::Delta, Box::new(DeltaColumn::encode(column)?));
+    }
+
+    Ok(best)
+}
+
+// ============================================================================
+// COLUMN SLICES
+// ============================================================================
+
+/// A window onto rows `offset..offset + len` of a shared column
+///
+/// Creating a slice copies nothing: it holds an `Arc` to the column and a
+/// row range, so scanning a table in batches costs the same however large
+/// the table is. Reads go straight to the underlying column. Writing to a
+/// slice (push, clear) first gives it its own copy of the sliced rows.
+///
+/// Vectorized code that downcasts to a concrete column type should look
+/// through slices with [`resolve_slice`].
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, ColumnSlice, IntColumn};
+/// use mini_rust_olap::types::Value;
+/// use std::sync::Arc;
+///
+/// let mut col = IntColumn::new();
+/// for v in 0..10 {
+///     col.push_value(Value::Int64(v)).unwrap();
+/// }
+/// let slice = ColumnSlice::new(Arc::new(col), 3..6).unwrap();
+/// assert_eq!(slice.len(), 3);
+/// assert_eq!(slice.get(0).unwrap(), Value::Int64(3));
+/// ```
+#[derive(Clone)]
+pub struct ColumnSlice {
+    /// The column holding the data; never itself a `ColumnSlice`
+    column: Arc<dyn Column>,
+    /// First row of the window
+    offset: usize,
+    /// Number of rows in the window
+    len: usize,
+}
+
+impl ColumnSlice {
+    /// Creates a slice of `column` covering `range`
+    ///
+    /// Slicing a slice yields a slice of the original column, so views do
+    /// not nest.
+    ///
+    /// # Arguments
+    /// * `column` - The column to look into
+    /// * `range` - The rows to cover, relative to `column`
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::ColumnError` if the range is reversed or runs
+    /// past the end of the column
+    pub fn new(column: Arc<dyn Column>, range: Range<usize>) -> Result<Self> {
+        if range.start > range.end || range.end > column.len() {
+            return Err(DatabaseError::column_error(format!(
+                "Slice {}..{} out of bounds (len: {})",
+                range.start,
+                range.end,
+                column.len()
+            )));
+        }
+
+        if let Some(slice) = column.as_any().downcast_ref::<ColumnSlice>() {
+            return Ok(ColumnSlice {
+                column: Arc::clone(&slice.column),
+                offset: slice.offset + range.start,
+                len: range.len(),
+            });
+        }
+
+        Ok(ColumnSlice {
+            column,
+            offset: range.start,
+            len: range.len(),
+        })
+    }
+
+    /// Returns the column the slice looks into
+    pub fn column(&self) -> &Arc<dyn Column> {
+        &self.column
+    }
+
+    /// Returns the rows of [`ColumnSlice::column`] the slice covers
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.len
+    }
+
+    /// Replaces the window with an owned copy of its rows, before a write
+    fn make_owned(&mut self) {
+        if self.offset != 0 || self.len != self.column.len() || Arc::strong_count(&self.column) > 1
+        {
+            self.column = Arc::from(self.clone_column());
+            self.offset = 0;
+        }
+    }
+}
+
+/// Returns a column covering `range` of `column`, sharing its storage
+///
+/// The whole range gives back `column` itself; any other range gives a
+/// [`ColumnSlice`], or a narrower [`FilteredColumn`] when `column` is one.
+///
+/// # Arguments
+/// * `column` - The column to slice
+/// * `range` - The rows to keep
+///
+/// # Errors
+/// Returns `DatabaseError::ColumnError` if the range is out of bounds
+pub fn slice_column(column: &Arc<dyn Column>, range: Range<usize>) -> Result<Arc<dyn Column>> {
+    if range == (0..column.len()) {
+        return Ok(Arc::clone(column));
+    }
+    if let Some(view) = column.as_any().downcast_ref::<FilteredColumn>() {
+        let rows = view.rows.get(range.clone()).ok_or_else(|| {
+            DatabaseError::column_error(format!(
+                "Slice {}..{} out of bounds (len: {})",
+                range.start,
+                range.end,
+                view.len()
+            ))
+        })?;
+        return Ok(Arc::new(FilteredColumn {
+            column: Arc::clone(&view.column),
+            rows: Arc::new(rows.to_vec()),
+        }));
+    }
+    Ok(Arc::new(ColumnSlice::new(Arc::clone(column), range)?))
+}
+
+/// The rows of a column that a [`ColumnSlice`] or a [`FilteredColumn`]
+/// reads, as returned by [`resolve_slice`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowSelection<'a> {
+    /// Consecutive rows
+    Range(Range<usize>),
+    /// Listed rows, in the order they are read
+    Rows(&'a [u32]),
+}
+
+impl RowSelection<'_> {
+    /// Number of rows selected
+    pub fn len(&self) -> usize {
+        match self {
+            RowSelection::Range(range) => range.len(),
+            RowSelection::Rows(rows) => rows.len(),
+        }
+    }
+
+    /// Whether no rows are selected
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The row of the underlying column that row `index` of the selection
+    /// reads
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than [`RowSelection::len`]
+    pub fn row(&self, index: usize) -> usize {
+        match self {
+            RowSelection::Range(range) => {
+                assert!(index < range.len(), "index {} out of bounds", index);
+                range.start + index
+            }
+            RowSelection::Rows(rows) => rows[index] as usize,
+        }
+    }
+
+    /// The selected rows of the underlying column, in order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        let (range, listed) = match self {
+            RowSelection::Range(range) => (range.clone(), None),
+            RowSelection::Rows(rows) => (0..0, Some(rows.iter())),
+        };
+        range.chain(listed.into_iter().flatten().map(|&row| row as usize))
+    }
+}
+
+impl From<Range<usize>> for RowSelection<'_> {
+    fn from(range: Range<usize>) -> Self {
+        RowSelection::Range(range)
+    }
+}
+
+impl<'a> From<&'a [u32]> for RowSelection<'a> {
+    fn from(rows: &'a [u32]) -> Self {
+        RowSelection::Rows(rows)
+    }
+}
+
+/// Looks through a [`ColumnSlice`] or a [`FilteredColumn`] to the column
+/// holding the data
+///
+/// Returns the underlying column and the rows of it that `column` reads:
+/// a range for a slice, the row list for a filtered view. Any other column
+/// is returned as is with its full range. Row `i` of `column` is row
+/// `rows.row(i)` of the returned column.
+///
+/// # Example
+/// ```rust
+/// use mini_rust_olap::column::{
+///     resolve_slice, Column, ColumnSlice, FilteredColumn, IntColumn, RowSelection,
+/// };
+/// use mini_rust_olap::types::Value;
+/// use std::sync::Arc;
+///
+/// let mut col = IntColumn::new();
+/// for v in 0..10 {
+///     col.push_value(Value::Int64(v * 10)).unwrap();
+/// }
+/// let col: Arc<dyn Column> = Arc::new(col);
+///
+/// let slice = ColumnSlice::new(Arc::clone(&col), 4..7).unwrap();
+/// let (base, rows) = resolve_slice(&slice);
+/// assert_eq!(rows, RowSelection::Range(4..7));
+/// let ints = base.as_any().downcast_ref::<IntColumn>().unwrap();
+/// assert_eq!(ints.values_in(rows).copied().collect::<Vec<_>>(), [40, 50, 60]);
+///
+/// let view = FilteredColumn::new(col, Arc::new(vec![1, 8])).unwrap();
+/// let (base, rows) = resolve_slice(&view);
+/// let ints = base.as_any().downcast_ref::<IntColumn>().unwrap();
+/// assert_eq!(ints.values_in(rows).copied().collect::<Vec<_>>(), [10, 80]);
+/// ```
+pub fn resolve_slice(column: &dyn Column) -> (&dyn Column, RowSelection<'_>) {
+    let any = column.as_any();
+    if let Some(slice) = any.downcast_ref::<ColumnSlice>() {
+        (slice.column.as_ref(), RowSelection::Range(slice.range()))
+    } else if let Some(view) = any.downcast_ref::<FilteredColumn>() {
+        (
+            view.column.as_ref(),
+            RowSelection::Rows(view.rows.as_slice()),
+        )
+    } else {
+        (column, RowSelection::Range(0..column.len()))
+    }
+}
+
+impl Column for ColumnSlice {
+    fn data_type(&self) -> DataType {
+        self.column.data_type()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        self.make_owned();
+        let column = Arc::get_mut(&mut self.column).expect("owned after make_owned");
+        column.push_value(value)?;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        self.make_owned();
+        Arc::get_mut(&mut self.column)
+            .expect("owned after make_owned")
+            .push_null();
+        self.len += 1;
+    }
+
+    fn null_count(&self) -> usize {
+        if self.column.null_count() == 0 {
+            return 0;
+        }
+        self.range().filter(|&row| self.column.is_null(row)).count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        index < self.len && self.column.is_null(self.offset + index)
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        if index >= self.len {
+            return Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index, self.len
+            )));
+        }
+        self.column.get(self.offset + index)
+    }
+
+    fn slice(&self, range: Option<Range<usize>>) -> Vec<Value> {
+        self.iter_range(range.unwrap_or(0..self.len)).collect()
+    }
+
+    fn iter_range(&self, range: Range<usize>) -> Box<dyn Iterator<Item = Value> + '_> {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range end index {} out of range for column of length {}",
+            range.end,
+            self.len
+        );
+        self.column
+            .iter_range(self.offset + range.start..self.offset + range.end)
+    }
+
+    fn copy_range_into(&self, range: Range<usize>, target: &mut dyn Column) -> Result<()> {
+        check_copy_range(self.data_type(), self.len, &range, target)?;
+        self.column
+            .copy_range_into(self.offset + range.start..self.offset + range.end, target)
+    }
+
+    fn clear(&mut self) {
+        self.column = Arc::from(create_column(self.column.data_type()));
+        self.offset = 0;
+        self.len = 0;
+    }
+
+    fn memory_size(&self) -> usize {
+        // The rows belong to the shared column
+        0
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        if let Some(dict) = self.column.as_any().downcast_ref::<DictStringColumn>() {
+            return Box::new(dict.gather(self.range()));
+        }
+        let mut column = create_column(self.data_type());
+        // Values read back from a column always fit its type
+        let _ = self.copy_range_into(0..self.len, column.as_mut());
+        column
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// FILTERED VIEWS
+// ============================================================================
+
+/// A read-only view of selected rows of another column
+///
+/// Row `i` of the view is row `rows[i]` of the underlying column. A filter
+/// builds one per column of its input, all sharing one row list, so the
+/// surviving rows are not copied until something needs them packed
+/// together; [`Column::clone_column`] compacts a view into a column of its
+/// own. Writes first replace the view with such a copy.
+///
+/// Vectorized code that downcasts to a concrete column type does not see
+/// through a view and should fall back to [`Column::get`] or
+/// [`Column::iter_range`], or compact the view first.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, FilteredColumn, IntColumn};
+/// use mini_rust_olap::types::Value;
+/// use std::sync::Arc;
+///
+/// let mut col = IntColumn::new();
+/// for v in 0..10 {
+///     col.push_value(Value::Int64(v * 10)).unwrap();
+/// }
+/// let view = FilteredColumn::new(Arc::new(col), Arc::new(vec![1, 4, 9])).unwrap();
+/// assert_eq!(view.len(), 3);
+/// assert_eq!(view.get(1).unwrap(), Value::Int64(40));
+/// ```
+#[derive(Clone)]
+pub struct FilteredColumn {
+    /// The column holding the data
+    column: Arc<dyn Column>,
+    /// The rows of `column` in the view, in view order
+    rows: Arc<Vec<u32>>,
+}
+
+impl FilteredColumn {
+    /// Creates a view of `rows` of `column`
+    ///
+    /// # Arguments
+    /// * `column` - The column to look into
+    /// * `rows` - The rows to show, as indices into `column`; they may be
+    ///   shared with the views of the other columns of a batch
+    ///
+    /// # Errors
+    /// Returns `DatabaseError::ColumnError` if a row is past the end of the
+    /// column
+    pub fn new(column: Arc<dyn Column>, rows: Arc<Vec<u32>>) -> Result<Self> {
+        if let Some(&row) = rows.iter().find(|&&row| row as usize >= column.len()) {
+            return Err(DatabaseError::column_error(format!(
+                "Filtered row {} out of bounds (len: {})",
+                row,
+                column.len()
+            )));
+        }
+        Ok(FilteredColumn { column, rows })
+    }
+
+    /// Returns the column the view looks into
+    pub fn column(&self) -> &Arc<dyn Column> {
+        &self.column
+    }
+
+    /// Returns the rows of [`FilteredColumn::column`] the view shows
+    pub fn rows(&self) -> &Arc<Vec<u32>> {
+        &self.rows
+    }
+
+    /// Replaces the view with an owned copy of its rows, before a write
+    fn make_owned(&mut self) {
+        let column = self.clone_column();
+        self.rows = Arc::new((0..column.len() as u32).collect());
+        self.column = Arc::from(column);
+    }
+
+    /// Appends a row to the owned copy made by `make_owned`
+    fn push_row(&mut self) {
+        let row = self.rows.len() as u32;
+        Arc::make_mut(&mut self.rows).push(row);
+    }
+}
+
+impl Column for FilteredColumn {
+    fn data_type(&self) -> DataType {
+        self.column.data_type()
+    }
+
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn push_value(&mut self, value: Value) -> Result<()> {
+        self.make_owned();
+        let column = Arc::get_mut(&mut self.column).expect("owned after make_owned");
+        column.push_value(value)?;
+        self.push_row();
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        self.make_owned();
+        Arc::get_mut(&mut self.column)
+            .expect("owned after make_owned")
+            .push_null();
+        self.push_row();
+    }
+
+    fn null_count(&self) -> usize {
+        if self.column.null_count() == 0 {
+            return 0;
+        }
+        self.rows
+            .iter()
+            .filter(|&&row| self.column.is_null(row as usize))
+            .count()
+    }
+
+    fn is_null(&self, index: usize) -> bool {
+        self.rows
+            .get(index)
+            .is_some_and(|&row| self.column.is_null(row as usize))
+    }
+
+    fn get(&self, index: usize) -> Result<Value> {
+        match self.rows.get(index) {
+            Some(&row) => self.column.get(row as usize),
+            None => Err(DatabaseError::column_error(format!(
+                "Index {} out of bounds (len: {})",
+                index,
+                self.rows.len()
+            ))),
+        }
+    }
+
+    fn slice(&self, range: Option<Range<usize>>) -> Vec<Value> {
+        self.iter_range(range.unwrap_or(0..self.rows.len()))
+            .collect()
+    }
+
+    fn iter_range(&self, range: Range<usize>) -> Box<dyn Iterator<Item = Value> + '_> {
+        Box::new(self.rows[range].iter().map(|&row| {
+            self.column
+                .get(row as usize)
+                .expect("filtered rows are within the column")
+        }))
+    }
+
+    fn copy_range_into(&self, range: Range<usize>, target: &mut dyn Column) -> Result<()> {
+        check_copy_range(self.data_type(), self.rows.len(), &range, target)?;
+        // Copy each run of consecutive rows in one call
+        let rows = &self.rows[range];
+        let mut start = 0;
+        while start < rows.len() {
+            let first = rows[start] as usize;
+            let run = rows[start..]
+                .iter()
+                .enumerate()
+                .take_while(|&(offset, &row)| row as usize == first + offset)
+                .count();
+            self.column.copy_range_into(first..first + run, target)?;
+            start += run;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.column = Arc::from(create_column(self.column.data_type()));
+        self.rows = Arc::new(Vec::new());
+    }
+
+    fn memory_size(&self) -> usize {
+        // The values belong to the shared column, and the row list is shared
+        // by the views of the batch's other columns
+        0
+    }
+
+    fn clone_column(&self) -> Box<dyn Column> {
+        if let Some(dict) = self.column.as_any().downcast_ref::<DictStringColumn>() {
+            return Box::new(dict.gather(self.rows.iter().map(|&row| row as usize)));
+        }
+        let mut column = create_column(self.data_type());
+        // Values read back from a column always fit its type
+        let _ = self.copy_range_into(0..self.rows.len(), column.as_mut());
+        column
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// ============================================================================
+// ZONE MAPS
+// ============================================================================
+
+/// Number of rows summarised by each zone of a [`ZoneMap`]
+pub const ZONE_MAP_BLOCK_SIZE: usize = 1024;
+
+/// Summary of the values in one block of rows.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Zone {
+    /// Every row in the block is NULL
+    AllNull,
+    /// The non-NULL rows lie within `min..=max`
+    Range { min: Value, max: Value },
+    /// The block holds a NaN, which has no place in the order, so the block
+    /// cannot be ruled out by its range
+    Unordered,
+}
+
+impl Zone {
+    /// Fold one more row into the zone.
+    fn include(&mut self, value: Value) {
+        match (&mut *self, value) {
+            (_, Value::Null) | (Zone::Unordered, _) => {}
+            (_, Value::Float64(v)) if v.is_nan() => *self = Zone::Unordered,
+            (Zone::AllNull, value) => {
+                *self = Zone::Range {
+                    min: value.clone(),
+                    max: value,
+                }
+            }
+            (Zone::Range { min, max }, value) => {
+                if ordered_before(&value, min) {
+                    *min = value;
+                } else if ordered_before(max, &value) {
+                    *max = value;
+                }
+            }
+        }
+    }
+}
+
+/// Whether `a` sorts before `b`; both are non-NULL values of one zone-mapped
+/// type.
+fn ordered_before(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Int64(a), Value::Int64(b)) => a < b,
+        (Value::Float64(a), Value::Float64(b)) => a < b,
+        (Value::Date(a), Value::Date(b)) => a < b,
+        _ => false,
+    }
+}
+
+/// Per-block minimum and maximum of a column, used to skip blocks in scans.
+///
+/// Zone `i` covers rows `i * block_size .. (i + 1) * block_size`; the last
+/// zone may cover fewer rows. Only appends can be folded in with
+/// [`ZoneMap::extend`]; after any other change the map must be rebuilt.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::column::{Column, IntColumn, Zone, ZoneMap};
+/// use mini_rust_olap::types::Value;
+///
+/// let mut ids = IntColumn::new();
+/// for id in 0..5 {
+///     ids.push_value(Value::Int64(id)).unwrap();
+/// }
+///
+/// let zones = ZoneMap::with_block_size(&ids, 2);
+/// assert_eq!(zones.zones().len(), 3);
+/// assert_eq!(
+///     zones.zones()[1],
+///     Zone::Range { min: Value::Int64(2), max: Value::Int64(3) }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneMap {
+    block_size: usize,
+    zones: Vec<Zone>,
+    /// Number of rows folded into the zones so far
+    rows: usize,
+}
+
+impl ZoneMap {
+    /// Whether columns of a type get zone maps.
+    pub fn supports(data_type: DataType) -> bool {
+        matches!(
+            data_type,
+            DataType::Int64 | DataType::Float64 | DataType::Date
+        )
+    }
+
+    /// Build the zone map of a column with the default block size.
+    pub fn build(column: &dyn Column) -> Self {
+        Self::with_block_size(column, ZONE_MAP_BLOCK_SIZE)
+    }
+
+    /// Build the zone map of a column with a custom block size.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to summarise
+    /// * `block_size` - Rows per zone (must be > 0)
+    pub fn with_block_size(column: &dyn Column, block_size: usize) -> Self {
+        if block_size == 0 {
+            panic!("Zone map block size must be greater than 0");
+        }
+        let mut map = ZoneMap {
+            block_size,
+            zones: Vec::new(),
+            rows: 0,
+        };
+        map.extend(column);
+        map
+    }
+
+    /// Fold in the rows appended to the column since the map was last
+    /// built or extended.
+    pub fn extend(&mut self, column: &dyn Column) {
+        for row in self.rows..column.len() {
+            if row % self.block_size == 0 {
+                self.zones.push(Zone::AllNull);
             }
-            _ => Err(DatabaseError::type_error(format!(
-                "Cannot push {:?} into StringColumn",
-                value.data_type()
-            ))),
+            // Rows below len() always exist
+            let value = column.get(row).unwrap_or(Value::Null);
+            self.zones
+                .last_mut()
+                .expect("a zone was pushed for the block")
+                .include(value);
         }
+        self.rows = self.rows.max(column.len());
     }
 
-    fn get(&self, index: usize) -> Result<Value> {
-        self.data
-            .get(index)
-            .map(|v| Value::String(v.clone()))
-            .ok_or_else(|| {
-                DatabaseError::column_error(format!(
-                    "Index {} out of bounds (len: {})",
-                    index,
-                    self.len()
-                ))
-            })
+    /// Rows per zone.
+    pub fn block_size(&self) -> usize {
+        self.block_size
     }
 
-    fn slice(&self, range: Option<std::ops::Range<usize>>) -> Vec<Value> {
-        let data = &self.data;
-        let range = range.unwrap_or(0..data.len());
-        data[range]
-            .iter()
-            .map(|v| Value::String(v.clone()))
-            .collect()
+    /// The zones, in row order.
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
     }
 
-    fn clear(&mut self) {
-        self.data.clear();
+    /// Number of rows the map covers.
+    pub fn row_count(&self) -> usize {
+        self.rows
     }
 }
 
@@ -673,6 +3425,8 @@ pub fn create_column(data_type: DataType) -> Box<dyn Column> {
         DataType::Int64 => Box::new(IntColumn::new()),
         DataType::Float64 => Box::new(FloatColumn::new()),
         DataType::String => Box::new(StringColumn::new()),
+        DataType::Boolean => Box::new(BoolColumn::new()),
+        DataType::Date => Box::new(DateColumn::new()),
     }
 }
 
@@ -788,6 +3542,187 @@ mod tests {
         assert_eq!(data, &[1, 2]);
     }
 
+    #[test]
+    fn test_typed_values_and_iter() {
+        let mut ints = IntColumn::new();
+        ints.push_value(Value::Int64(5)).unwrap();
+        ints.push_null();
+        assert_eq!(ints.values(), &[5, 0]);
+        assert_eq!(ints.iter().collect::<Vec<_>>(), vec![Some(5), None]);
+
+        let mut floats = FloatColumn::new();
+        floats.push_null();
+        floats.push_value(Value::Float64(2.5)).unwrap();
+        assert_eq!(floats.values(), &[0.0, 2.5]);
+        assert_eq!(floats.iter().flatten().sum::<f64>(), 2.5);
+
+        let mut strings = StringColumn::new();
+        strings.push_value(Value::String("x".to_string())).unwrap();
+        strings.push_null();
+        assert_eq!(strings.values().len(), 2);
+        assert_eq!(strings.iter().collect::<Vec<_>>(), vec![Some("x"), None]);
+    }
+
+    /// An `IntColumn` holding `0..len`, with NULL at each row in `nulls`
+    fn chunked_ints(len: usize, nulls: &[usize]) -> IntColumn {
+        let mut col = IntColumn::new();
+        for row in 0..len {
+            if nulls.contains(&row) {
+                col.push_null();
+            } else {
+                col.push_value(Value::Int64(row as i64)).unwrap();
+            }
+        }
+        col
+    }
+
+    #[test]
+    fn test_get_at_chunk_boundaries() {
+        let col = chunked_ints(2 * CHUNK_SIZE + 3, &[CHUNK_SIZE]);
+
+        let sizes: Vec<usize> = col.chunks().map(<[i64]>::len).collect();
+        assert_eq!(sizes, vec![CHUNK_SIZE, CHUNK_SIZE, 3]);
+        assert_eq!(col.len(), 2 * CHUNK_SIZE + 3);
+        assert_eq!(col.null_count(), 1);
+
+        assert_eq!(col.get(0).unwrap(), Value::Int64(0));
+        assert_eq!(
+            col.get(CHUNK_SIZE - 1).unwrap(),
+            Value::Int64(CHUNK_SIZE as i64 - 1)
+        );
+        assert_eq!(col.get(CHUNK_SIZE).unwrap(), Value::Null);
+        assert_eq!(
+            col.get(CHUNK_SIZE + 1).unwrap(),
+            Value::Int64(CHUNK_SIZE as i64 + 1)
+        );
+        assert_eq!(
+            col.get(2 * CHUNK_SIZE).unwrap(),
+            Value::Int64(2 * CHUNK_SIZE as i64)
+        );
+        assert_eq!(
+            col.get(2 * CHUNK_SIZE + 2).unwrap(),
+            Value::Int64(2 * CHUNK_SIZE as i64 + 2)
+        );
+        assert!(col.get(2 * CHUNK_SIZE + 3).is_err());
+        assert!(col.verify().is_empty());
+    }
+
+    #[test]
+    fn test_ranges_across_chunks() {
+        let col = chunked_ints(CHUNK_SIZE + 4, &[CHUNK_SIZE + 1]);
+        let start = CHUNK_SIZE as i64;
+        let range = CHUNK_SIZE - 2..CHUNK_SIZE + 3;
+        let expected = vec![
+            Value::Int64(start - 2),
+            Value::Int64(start - 1),
+            Value::Int64(start),
+            Value::Null,
+            Value::Int64(start + 2),
+        ];
+
+        assert_eq!(col.slice(Some(range.clone())), expected);
+        assert_eq!(col.iter_range(range.clone()).collect::<Vec<_>>(), expected);
+        assert_eq!(
+            col.values_in(range.clone()).copied().collect::<Vec<_>>(),
+            vec![start - 2, start - 1, start, 0, start + 2]
+        );
+        assert_eq!(col.values_in(5..5).count(), 0);
+
+        let mut copy = IntColumn::new();
+        col.copy_range_into(range.clone(), &mut copy).unwrap();
+        assert_eq!(copy.slice(None), expected);
+        assert_eq!(copy.null_count(), 1);
+
+        // Copies into a column spill into a new chunk once the last is full
+        let mut target = chunked_ints(CHUNK_SIZE - 1, &[]);
+        col.copy_range_into(range, &mut target).unwrap();
+        let sizes: Vec<usize> = target.chunks().map(<[i64]>::len).collect();
+        assert_eq!(sizes, vec![CHUNK_SIZE, 4]);
+        assert_eq!(target.slice(Some(CHUNK_SIZE - 1..CHUNK_SIZE + 4)), expected);
+
+        let mut strings = StringColumn::new();
+        for row in 0..CHUNK_SIZE + 1 {
+            strings.push_value(Value::String(row.to_string())).unwrap();
+        }
+        assert_eq!(
+            strings.slice(Some(CHUNK_SIZE - 1..CHUNK_SIZE + 1)),
+            vec![
+                Value::String((CHUNK_SIZE - 1).to_string()),
+                Value::String(CHUNK_SIZE.to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_append_column_shares_chunks() {
+        let source = chunked_ints(2 * CHUNK_SIZE + 5, &[1, CHUNK_SIZE + 7]);
+        let mut col = chunked_ints(3, &[0]);
+
+        col.append_column(&source).unwrap();
+        assert_eq!(col.len(), 2 * CHUNK_SIZE + 8);
+        assert_eq!(col.null_count(), 3);
+        assert_eq!(col.get(0).unwrap(), Value::Null);
+        assert_eq!(col.get(3).unwrap(), Value::Int64(0));
+        assert_eq!(col.get(4).unwrap(), Value::Null);
+        assert_eq!(col.get(CHUNK_SIZE + 10).unwrap(), Value::Null);
+        assert_eq!(
+            col.get(2 * CHUNK_SIZE + 7).unwrap(),
+            Value::Int64(2 * CHUNK_SIZE as i64 + 4)
+        );
+
+        // The source's chunks are shared, not copied
+        let shared: Vec<&[i64]> = source.chunks().collect();
+        let appended: Vec<&[i64]> = col.chunks().collect();
+        assert_eq!(appended.len(), 4);
+        assert_eq!(appended[0].len(), 3);
+        for (appended, shared) in appended[1..].iter().zip(&shared) {
+            assert!(std::ptr::eq(*appended, *shared));
+        }
+        assert_eq!(col.values_in(2 * CHUNK_SIZE + 3..col.len()).count(), 5);
+
+        // Writing to the appended column leaves the source alone
+        col.push_value(Value::Int64(-1)).unwrap();
+        assert_eq!(source.len(), 2 * CHUNK_SIZE + 5);
+        assert_eq!(col.get(2 * CHUNK_SIZE + 8).unwrap(), Value::Int64(-1));
+
+        // A short column is copied into the last chunk rather than shared
+        let chunk_count = col.chunks().count();
+        col.append_column(&chunked_ints(2, &[])).unwrap();
+        assert_eq!(col.chunks().count(), chunk_count);
+
+        let mut floats = FloatColumn::new();
+        let mut more = FloatColumn::new();
+        more.push_null();
+        more.push_value(Value::Float64(1.5)).unwrap();
+        floats.append_column(&more).unwrap();
+        assert_eq!(floats.slice(None), vec![Value::Null, Value::Float64(1.5)]);
+
+        // Other representations fall back to pushing each value
+        let mut dict = DictStringColumn::new();
+        let mut strings = StringColumn::new();
+        strings.push_value(Value::String("a".to_string())).unwrap();
+        dict.append_column(&strings).unwrap();
+        assert_eq!(dict.get(0).unwrap(), Value::String("a".to_string()));
+
+        assert!(col.append_column(&floats).is_err());
+        assert!(dict.append_column(&floats).is_err());
+        assert_eq!(col.len(), 2 * CHUNK_SIZE + 11);
+    }
+
+    #[test]
+    fn test_clone_column_shares_chunks() {
+        let col = chunked_ints(CHUNK_SIZE + 1, &[]);
+        let mut copy = col.clone_column();
+        copy.push_value(Value::Int64(7)).unwrap();
+
+        let copy = copy.as_any().downcast_ref::<IntColumn>().unwrap();
+        let (original, copied): (Vec<&[i64]>, Vec<&[i64]>) =
+            (col.chunks().collect(), copy.chunks().collect());
+        assert!(std::ptr::eq(original[0], copied[0]));
+        assert_eq!(original[1].len(), 1);
+        assert_eq!(copied[1].len(), 2);
+    }
+
     #[test]
     fn test_int_column_default() {
         let col: IntColumn = Default::default();
@@ -804,6 +3739,49 @@ mod tests {
         assert_eq!(col2.as_vec(), col1.as_vec());
     }
 
+    #[test]
+    fn test_int_column_nulls() {
+        let mut col = IntColumn::new();
+        col.push_value(Value::Int64(1)).unwrap();
+        col.push_null();
+        col.push_value(Value::Null).unwrap();
+        col.push_value(Value::Int64(4)).unwrap();
+
+        assert_eq!(col.len(), 4);
+        assert_eq!(col.null_count(), 2);
+        assert_eq!(col.get(0).unwrap(), Value::Int64(1));
+        assert_eq!(col.get(1).unwrap(), Value::Null);
+        assert_eq!(col.get(2).unwrap(), Value::Null);
+        assert_eq!(
+            col.slice(Some(1..4)),
+            vec![Value::Null, Value::Null, Value::Int64(4)]
+        );
+        // NULL rows hold a placeholder in the raw data
+        assert_eq!(col.as_vec(), &[1, 0, 0, 4]);
+        assert!(col.verify().is_empty());
+
+        col.clear();
+        assert_eq!(col.null_count(), 0);
+        col.push_value(Value::Int64(7)).unwrap();
+        assert_eq!(col.get(0).unwrap(), Value::Int64(7));
+    }
+
+    #[test]
+    fn test_null_bitmap_spans_words() {
+        let mut col = IntColumn::new();
+        for i in 0..200 {
+            if i % 65 == 0 {
+                col.push_null();
+            } else {
+                col.push_value(Value::Int64(i)).unwrap();
+            }
+        }
+        assert_eq!(col.null_count(), 4);
+        for i in 0..200 {
+            assert_eq!(col.get(i).unwrap().is_null(), i % 65 == 0, "row {}", i);
+        }
+    }
+
     // ============================================================================
     // FLOAT COLUMN TESTS
     // ============================================================================
@@ -946,6 +3924,22 @@ mod tests {
         assert_eq!(col.len(), 0);
     }
 
+    #[test]
+    fn test_string_and_float_column_nulls() {
+        let mut strings = StringColumn::new();
+        strings.push_null();
+        strings.push_value(Value::String(String::new())).unwrap();
+        assert_eq!(strings.get(0).unwrap(), Value::Null);
+        // An empty string is a value, not NULL
+        assert_eq!(strings.get(1).unwrap(), Value::String(String::new()));
+        assert_eq!(strings.null_count(), 1);
+
+        let mut floats = FloatColumn::new();
+        floats.push_value(Value::Float64(1.5)).unwrap();
+        floats.push_null();
+        assert_eq!(floats.slice(None), vec![Value::Float64(1.5), Value::Null]);
+    }
+
     #[test]
     fn test_string_column_empty_string() {
         let mut col = StringColumn::new();
@@ -956,6 +3950,576 @@ mod tests {
         assert_eq!(v, Value::String("".to_string()));
     }
 
+    // ============================================================================
+    // DICTIONARY STRING COLUMN TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_dict_string_column_basics() {
+        let mut col = DictStringColumn::new();
+        for value in ["open", "closed", "open"] {
+            col.push_value(Value::String(value.to_string())).unwrap();
+        }
+        col.push_null();
+
+        assert_eq!(col.data_type(), DataType::String);
+        assert_eq!(col.codes(), &[0, 1, 0, 0]);
+        assert_eq!(
+            col.dictionary(),
+            &["open".to_string(), "closed".to_string()]
+        );
+        assert_eq!(col.lookup("closed"), Some(1));
+        assert_eq!(col.lookup("pending"), None);
+        assert_eq!(col.get(3).unwrap(), Value::Null);
+        assert_eq!(col.null_count(), 1);
+        assert_eq!(
+            col.slice(Some(1..3)),
+            vec![Value::String("closed".into()), Value::String("open".into())]
+        );
+        assert!(col.push_value(Value::Int64(1)).is_err());
+        assert!(col.get(4).is_err());
+        assert!(col.verify().is_empty());
+    }
+
+    #[test]
+    fn test_dict_string_column_gather_shares_dictionary() {
+        let mut col = DictStringColumn::new();
+        for value in ["a", "b", "a"] {
+            col.push_value(Value::String(value.to_string())).unwrap();
+        }
+        col.push_null();
+
+        let mut gathered = col.gather([3, 1]);
+        assert!(gathered.shares_dictionary(&col));
+        assert_eq!(
+            gathered.slice(None),
+            vec![Value::Null, Value::String("b".into())]
+        );
+
+        // A new value copies the dictionary instead of changing the original
+        gathered.push_value(Value::String("c".into())).unwrap();
+        assert!(!gathered.shares_dictionary(&col));
+        assert_eq!(col.dictionary().len(), 2);
+        assert_eq!(gathered.get(2).unwrap(), Value::String("c".into()));
+
+        let copy = col.clone_column();
+        assert!(copy
+            .as_any()
+            .downcast_ref::<DictStringColumn>()
+            .unwrap()
+            .shares_dictionary(&col));
+    }
+
+    #[test]
+    fn test_dict_string_column_is_smaller_on_repetitive_data() {
+        let countries = ["Germany", "France", "United States", "Japan", "Brazil"];
+        let mut plain = StringColumn::new();
+        let mut dict = DictStringColumn::new();
+        for row in 0..100_000 {
+            let value = Value::String(countries[row % countries.len()].to_string());
+            plain.push_value(value.clone()).unwrap();
+            dict.push_value(value).unwrap();
+        }
+
+        assert_eq!(dict.dictionary().len(), 5);
+        assert_eq!(dict.slice(None), plain.slice(None));
+        assert!(
+            dict.memory_size() * 5 < plain.memory_size(),
+            "dictionary {} bytes vs plain {} bytes",
+            dict.memory_size(),
+            plain.memory_size()
+        );
+    }
+
+    // ============================================================================
+    // COMPRESSED COLUMN TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_rle_column_round_trip() {
+        let values = vec![
+            Value::String("DE".into()),
+            Value::String("DE".into()),
+            Value::String("DE".into()),
+            Value::String("FR".into()),
+            Value::Null,
+            Value::Null,
+            Value::String("US".into()),
+        ];
+        let mut plain = StringColumn::new();
+        for value in &values {
+            plain.push_value(value.clone()).unwrap();
+        }
+
+        let col = RleColumn::encode(&plain).unwrap();
+        assert_eq!(col.data_type(), DataType::String);
+        assert_eq!((col.len(), col.run_count()), (7, 4));
+        assert_eq!(col.slice(None), values);
+        assert_eq!(col.slice(Some(2..5)), values[2..5].to_vec());
+        assert_eq!(col.slice(Some(3..3)), Vec::<Value>::new());
+        for (row, value) in values.iter().enumerate() {
+            assert_eq!(&col.get(row).unwrap(), value);
+            assert_eq!(col.is_null(row), value.is_null());
+        }
+        assert_eq!(col.null_count(), 2);
+        assert!(col.get(7).is_err());
+        assert!(!col.is_null(7));
+
+        let mut col = col;
+        assert!(col.push_value(Value::Int64(1)).is_err());
+        col.push_value(Value::String("US".into())).unwrap();
+        assert_eq!((col.len(), col.run_count()), (8, 4));
+        assert!(col.verify().is_empty());
+    }
+
+    #[test]
+    fn test_rle_column_keeps_float_bits() {
+        let mut col = RleColumn::new(DataType::Float64);
+        for v in [0.0, -0.0, f64::NAN, f64::NAN] {
+            col.push_value(Value::Float64(v)).unwrap();
+        }
+        // 0.0 and -0.0 compare equal but are different values
+        assert_eq!(col.run_count(), 3);
+        match col.get(1).unwrap() {
+            Value::Float64(v) => assert!(v == 0.0 && v.is_sign_negative()),
+            other => panic!("expected a float, got {:?}", other),
+        }
+        assert!(matches!(col.get(3).unwrap(), Value::Float64(v) if v.is_nan()));
+    }
+
+    #[test]
+    fn test_delta_column_round_trip() {
+        // Steps up and down, large jumps and NULLs across several blocks
+        let mut values = Vec::new();
+        for row in 0..(3 * DELTA_BLOCK_SIZE as i64 + 5) {
+            values.push(match row % 17 {
+                0 => Value::Null,
+                5 => Value::Int64(i64::MIN),
+                6 => Value::Int64(i64::MAX),
+                n => Value::Int64(1_700_000_000 + row * 3 - n),
+            });
+        }
+        let mut plain = IntColumn::new();
+        for value in &values {
+            plain.push_value(value.clone()).unwrap();
+        }
+
+        let col = DeltaColumn::encode(&plain).unwrap();
+        assert_eq!(col.data_type(), DataType::Int64);
+        assert_eq!(col.len(), values.len());
+        assert_eq!(col.slice(None), values);
+        for range in [0..1, 60..70, 64..128, 190..values.len()] {
+            assert_eq!(col.slice(Some(range.clone())), values[range].to_vec());
+        }
+        for (row, value) in values.iter().enumerate() {
+            assert_eq!(&col.get(row).unwrap(), value, "row {}", row);
+        }
+        assert_eq!(col.null_count(), plain.null_count());
+        assert!(col.get(values.len()).is_err());
+        assert_eq!(col.clone_column().slice(None), values);
+
+        let mut col = col;
+        assert!(col.push_value(Value::Float64(1.0)).is_err());
+        assert!(DeltaColumn::encode(&FloatColumn::new()).is_ok());
+        let mut floats = FloatColumn::new();
+        floats.push_value(Value::Float64(1.0)).unwrap();
+        assert!(DeltaColumn::encode(&floats).is_err());
+        col.clear();
+        assert!(col.is_empty());
+        assert_eq!(col.memory_size(), 0);
+    }
+
+    #[test]
+    fn test_compress_column_picks_the_smallest_encoding() {
+        // Increasing ids: delta
+        let mut ids = IntColumn::new();
+        for id in 0..10_000 {
+            ids.push_value(Value::Int64(1_000_000 + id)).unwrap();
+        }
+        let (encoding, compressed) = compress_column(&ids).unwrap().unwrap();
+        assert_eq!(encoding, Encoding::Delta);
+        assert!(compressed.memory_size() * 4 < ids.memory_size());
+        assert_eq!(compressed.slice(None), ids.slice(None));
+
+        // Long runs of a few values: run-length, even for Int64
+        let mut codes = IntColumn::new();
+        for row in 0..10_000 {
+            codes.push_value(Value::Int64(row / 2_500)).unwrap();
+        }
+        let (encoding, compressed) = compress_column(&codes).unwrap().unwrap();
+        assert_eq!(encoding, Encoding::RunLength);
+        assert_eq!(compressed.slice(None), codes.slice(None));
+
+        // Scattered floats: nothing saves enough
+        let mut noise = FloatColumn::new();
+        for row in 0..10_000 {
+            noise
+                .push_value(Value::Float64((row as f64 * 7.31).sin()))
+                .unwrap();
+        }
+        assert!(compress_column(&noise).unwrap().is_none());
+
+        // Compressed and empty columns are left alone
+        assert!(compress_column(compressed.as_ref()).unwrap().is_none());
+        assert!(compress_column(&IntColumn::new()).unwrap().is_none());
+        assert_eq!(Encoding::RunLength.to_string(), "run-length");
+        assert_eq!(Encoding::Delta.to_string(), "delta");
+    }
+
+    // ============================================================================
+    // BOOL COLUMN TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_bool_column_packs_across_words() {
+        let mut col = BoolColumn::new();
+        for i in 0..130 {
+            col.push_value(Value::Boolean(i % 3 == 0)).unwrap();
+        }
+
+        assert_eq!(col.len(), 130);
+        assert_eq!(col.data_type(), DataType::Boolean);
+        for i in 0..130 {
+            assert_eq!(col.get(i).unwrap(), Value::Boolean(i % 3 == 0));
+        }
+        assert!(col.get(130).is_err());
+    }
+
+    #[test]
+    fn test_bool_column_nulls_and_slice() {
+        let mut col = BoolColumn::new();
+        col.push_value(Value::Boolean(true)).unwrap();
+        col.push_null();
+        col.push_value(Value::Boolean(false)).unwrap();
+
+        assert_eq!(col.null_count(), 1);
+        assert_eq!(
+            col.slice(Some(1..3)),
+            vec![Value::Null, Value::Boolean(false)]
+        );
+        assert!(col.push_value(Value::Int64(1)).is_err());
+
+        col.clear();
+        assert!(col.is_empty());
+        assert_eq!(col.null_count(), 0);
+    }
+
+    // ============================================================================
+    // DATE COLUMN TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_date_column_basic() {
+        let mut col = DateColumn::new();
+        col.push_value(Value::Date(19_723)).unwrap();
+        col.push_null();
+        col.push_value(Value::Date(-1)).unwrap();
+
+        assert_eq!(col.len(), 3);
+        assert_eq!(col.data_type(), DataType::Date);
+        assert_eq!(col.get(0).unwrap(), Value::Date(19_723));
+        assert_eq!(col.get(1).unwrap(), Value::Null);
+        assert_eq!(col.null_count(), 1);
+        assert_eq!(col.as_vec(), &[19_723, 0, -1]);
+        assert!(col.push_value(Value::String("2024-01-01".into())).is_err());
+        assert!(col.get(3).is_err());
+    }
+
+    // ============================================================================
+    // COLUMN SLICE TESTS
+    // ============================================================================
+
+    fn shared_ints(values: &[Option<i64>]) -> Arc<dyn Column> {
+        let mut col = IntColumn::new();
+        for value in values {
+            col.push_value(value.map_or(Value::Null, Value::Int64))
+                .unwrap();
+        }
+        Arc::new(col)
+    }
+
+    #[test]
+    fn test_column_slice_reads_its_window() {
+        let base = shared_ints(&[Some(0), Some(1), None, Some(3), Some(4), None]);
+        let slice = ColumnSlice::new(Arc::clone(&base), 1..5).unwrap();
+
+        assert_eq!(slice.len(), 4);
+        assert_eq!(slice.data_type(), DataType::Int64);
+        assert_eq!(slice.get(0).unwrap(), Value::Int64(1));
+        assert_eq!(slice.get(1).unwrap(), Value::Null);
+        assert!(slice.get(4).is_err());
+        assert!(slice.is_null(1) && !slice.is_null(0) && !slice.is_null(4));
+        assert_eq!(slice.null_count(), 1);
+        assert_eq!(
+            slice.slice(Some(2..4)),
+            vec![Value::Int64(3), Value::Int64(4)]
+        );
+        assert_eq!(slice.memory_size(), 0);
+        assert!(slice.verify().is_empty());
+
+        assert!(ColumnSlice::new(Arc::clone(&base), 4..7).is_err());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 3..2;
+        assert!(ColumnSlice::new(Arc::clone(&base), reversed).is_err());
+    }
+
+    #[test]
+    fn test_column_slice_of_slice_points_at_the_base_column() {
+        let base = shared_ints(&[Some(0), Some(1), Some(2), Some(3), Some(4)]);
+        let outer: Arc<dyn Column> = Arc::new(ColumnSlice::new(Arc::clone(&base), 1..5).unwrap());
+        let inner = ColumnSlice::new(outer, 2..4).unwrap();
+
+        assert!(Arc::ptr_eq(inner.column(), &base));
+        assert_eq!(inner.range(), 3..5);
+        let (column, rows) = resolve_slice(&inner);
+        assert_eq!(rows, RowSelection::Range(3..5));
+        assert_eq!(
+            column
+                .as_any()
+                .downcast_ref::<IntColumn>()
+                .unwrap()
+                .values_in(rows)
+                .collect::<Vec<_>>(),
+            [&3, &4]
+        );
+
+        // The whole range needs no slice at all
+        assert!(Arc::ptr_eq(&slice_column(&base, 0..5).unwrap(), &base));
+        assert_eq!(slice_column(&base, 1..3).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_column_slice_copies_before_writing() {
+        let base = shared_ints(&[Some(0), Some(1), Some(2)]);
+        let mut slice = ColumnSlice::new(Arc::clone(&base), 1..2).unwrap();
+
+        slice.push_value(Value::Int64(9)).unwrap();
+        slice.push_null();
+        assert_eq!(
+            slice.slice(None),
+            vec![Value::Int64(1), Value::Int64(9), Value::Null]
+        );
+        assert!(slice.push_value(Value::Boolean(true)).is_err());
+
+        // The shared column is untouched
+        assert_eq!(base.len(), 3);
+        assert_eq!(base.get(1).unwrap(), Value::Int64(1));
+
+        slice.clear();
+        assert!(slice.is_empty());
+        assert_eq!(base.len(), 3);
+    }
+
+    #[test]
+    fn test_column_slice_clone_keeps_dictionary_encoding() {
+        let mut dict = DictStringColumn::new();
+        for status in ["open", "closed", "open", "open"] {
+            dict.push_value(Value::String(status.to_string())).unwrap();
+        }
+        let dict: Arc<dyn Column> = Arc::new(dict);
+        let slice = ColumnSlice::new(Arc::clone(&dict), 1..3).unwrap();
+
+        let copy = slice.clone_column();
+        let copy = copy.as_any().downcast_ref::<DictStringColumn>().unwrap();
+        assert_eq!(copy.slice(None), slice.slice(None));
+        assert!(copy.shares_dictionary(dict.as_any().downcast_ref::<DictStringColumn>().unwrap()));
+    }
+
+    #[test]
+    fn test_filtered_column_reads_its_rows() {
+        let base = shared_ints(&[Some(0), Some(1), None, Some(3), Some(4), None]);
+        let view = FilteredColumn::new(Arc::clone(&base), Arc::new(vec![0, 2, 3, 4])).unwrap();
+
+        assert_eq!(view.len(), 4);
+        assert_eq!(view.data_type(), DataType::Int64);
+        assert_eq!(view.get(1).unwrap(), Value::Null);
+        assert_eq!(view.get(2).unwrap(), Value::Int64(3));
+        assert!(view.get(4).is_err());
+        assert!(view.is_null(1) && !view.is_null(0) && !view.is_null(4));
+        assert_eq!(view.null_count(), 1);
+        assert_eq!(
+            view.slice(None),
+            vec![
+                Value::Int64(0),
+                Value::Null,
+                Value::Int64(3),
+                Value::Int64(4)
+            ]
+        );
+
+        // Runs of consecutive rows are copied a run at a time
+        let mut copy = IntColumn::new();
+        view.copy_range_into(1..4, &mut copy).unwrap();
+        assert_eq!(
+            copy.slice(None),
+            vec![Value::Null, Value::Int64(3), Value::Int64(4)]
+        );
+        assert_eq!(view.clone_column().slice(None), view.slice(None));
+        assert!(view.verify().is_empty());
+
+        assert!(FilteredColumn::new(Arc::clone(&base), Arc::new(vec![1, 6])).is_err());
+    }
+
+    #[test]
+    fn test_filtered_column_slices_and_writes() {
+        let base = shared_ints(&[Some(0), Some(1), Some(2), Some(3)]);
+        let view: Arc<dyn Column> =
+            Arc::new(FilteredColumn::new(Arc::clone(&base), Arc::new(vec![3, 1, 0])).unwrap());
+
+        // Slicing a view narrows its rows rather than nesting
+        let sliced = slice_column(&view, 1..3).unwrap();
+        let sliced = sliced.as_any().downcast_ref::<FilteredColumn>().unwrap();
+        assert!(Arc::ptr_eq(sliced.column(), &base));
+        assert_eq!(sliced.rows().as_slice(), [1, 0]);
+        assert!(slice_column(&view, 2..4).is_err());
+
+        let mut owned = sliced.clone();
+        owned.push_value(Value::Int64(9)).unwrap();
+        owned.push_null();
+        assert_eq!(
+            owned.slice(None),
+            vec![
+                Value::Int64(1),
+                Value::Int64(0),
+                Value::Int64(9),
+                Value::Null
+            ]
+        );
+        assert_eq!(base.len(), 4);
+        assert_eq!(sliced.len(), 2);
+    }
+
+    #[test]
+    fn test_filtered_column_clone_keeps_dictionary_encoding() {
+        let mut dict = DictStringColumn::new();
+        for status in ["open", "closed", "open", "open"] {
+            dict.push_value(Value::String(status.to_string())).unwrap();
+        }
+        let dict: Arc<dyn Column> = Arc::new(dict);
+        let view = FilteredColumn::new(Arc::clone(&dict), Arc::new(vec![3, 1])).unwrap();
+
+        let copy = view.clone_column();
+        let copy = copy.as_any().downcast_ref::<DictStringColumn>().unwrap();
+        assert_eq!(copy.slice(None), view.slice(None));
+        assert!(copy.shares_dictionary(dict.as_any().downcast_ref::<DictStringColumn>().unwrap()));
+    }
+
+    // ============================================================================
+    // RANGE ACCESS TESTS
+    // ============================================================================
+
+    /// One column of each representation, every one with NULLs
+    fn columns_of_every_kind() -> Vec<Arc<dyn Column>> {
+        let rows = 0..150i64;
+        let value = |row: i64, value: Value| if row % 7 == 3 { Value::Null } else { value };
+        let mut columns: Vec<Box<dyn Column>> = vec![
+            Box::new(IntColumn::new()),
+            Box::new(FloatColumn::new()),
+            Box::new(StringColumn::new()),
+            Box::new(DictStringColumn::new()),
+            Box::new(BoolColumn::new()),
+            Box::new(DateColumn::new()),
+        ];
+        for row in rows {
+            let values = [
+                Value::Int64(row * 3),
+                Value::Float64(row as f64 / 4.0),
+                Value::String(format!("s{}", row)),
+                Value::String(format!("d{}", row % 5)),
+                Value::Boolean(row % 3 == 0),
+                Value::Date(19_000 + row as i32),
+            ];
+            for (column, v) in columns.iter_mut().zip(values) {
+                column.push_value(value(row, v)).unwrap();
+            }
+        }
+
+        let mut columns: Vec<Arc<dyn Column>> = columns.into_iter().map(Arc::from).collect();
+        let ints = Arc::clone(&columns[0]);
+        columns.push(Arc::new(RleColumn::encode(columns[3].as_ref()).unwrap()));
+        columns.push(Arc::new(DeltaColumn::encode(ints.as_ref()).unwrap()));
+        columns.push(Arc::new(ColumnSlice::new(ints, 10..140).unwrap()));
+        columns
+    }
+
+    #[test]
+    fn test_iter_range_matches_slice() {
+        for column in columns_of_every_kind() {
+            let len = column.len();
+            for range in [0..0, 0..len, 5..9, 64..130, len - 1..len, len..len] {
+                assert_eq!(
+                    column.iter_range(range.clone()).collect::<Vec<_>>(),
+                    column.slice(Some(range.clone())),
+                    "{} {:?}",
+                    column.data_type(),
+                    range
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_copy_range_into_matches_slice() {
+        for column in columns_of_every_kind() {
+            let len = column.len();
+            for range in [0..0, 0..len, 3..4, 5..70, 100..len, len..len] {
+                // Into a column that already holds a row, so NULLs must
+                // land at the right offset
+                let mut target = create_column(column.data_type());
+                target.push_value(column.get(1).unwrap()).unwrap();
+                column
+                    .copy_range_into(range.clone(), target.as_mut())
+                    .unwrap();
+
+                let mut expected = vec![column.get(1).unwrap()];
+                expected.extend(column.slice(Some(range.clone())));
+                assert_eq!(
+                    target.slice(None),
+                    expected,
+                    "{} {:?}",
+                    column.data_type(),
+                    range
+                );
+                assert_eq!(
+                    target.null_count(),
+                    expected.iter().filter(|v| v.is_null()).count()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_copy_range_into_another_representation() {
+        let mut plain = StringColumn::new();
+        for status in ["open", "closed", "open"] {
+            plain.push_value(Value::String(status.to_string())).unwrap();
+        }
+        plain.push_null();
+
+        let mut dict = DictStringColumn::new();
+        plain.copy_range_into(1..4, &mut dict).unwrap();
+        assert_eq!(dict.slice(None), plain.slice(Some(1..4)));
+        assert_eq!(dict.null_count(), 1);
+    }
+
+    #[test]
+    fn test_copy_range_into_rejects_bad_ranges_and_types() {
+        let ints = shared_ints(&[Some(1), None, Some(3)]);
+
+        let mut target = IntColumn::new();
+        assert!(ints.copy_range_into(2..4, &mut target).is_err());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 2..1;
+        assert!(ints.copy_range_into(reversed, &mut target).is_err());
+        let mut floats = FloatColumn::new();
+        assert!(ints.copy_range_into(0..1, &mut floats).is_err());
+        assert!(target.is_empty() && floats.is_empty());
+
+        let slice = ColumnSlice::new(ints, 1..3).unwrap();
+        assert!(slice.copy_range_into(1..3, &mut target).is_err());
+        assert!(target.is_empty());
+    }
+
     // ============================================================================
     // FACTORY FUNCTION TESTS
     // ============================================================================
@@ -978,6 +4542,18 @@ mod tests {
         assert_eq!(col.data_type(), DataType::String);
     }
 
+    #[test]
+    fn test_create_column_boolean() {
+        let col = create_column(DataType::Boolean);
+        assert_eq!(col.data_type(), DataType::Boolean);
+    }
+
+    #[test]
+    fn test_create_column_date() {
+        let col = create_column(DataType::Date);
+        assert_eq!(col.data_type(), DataType::Date);
+    }
+
     // ============================================================================
     // TRAIT OBJECT TESTS
     // ============================================================================
@@ -1081,4 +4657,56 @@ mod tests {
         assert_eq!(values[0], Value::Int64(10));
         assert_eq!(values[79], Value::Int64(89));
     }
+
+    #[test]
+    fn test_zone_map_blocks() {
+        let mut col = FloatColumn::new();
+        for value in [
+            Value::Float64(3.0),
+            Value::Null,
+            Value::Float64(-1.0),
+            Value::Null,
+            Value::Null,
+            Value::Float64(f64::NAN),
+            Value::Float64(7.0),
+        ] {
+            col.push_value(value).unwrap();
+        }
+
+        let mut zones = ZoneMap::with_block_size(&col, 3);
+        assert_eq!(zones.row_count(), 7);
+        assert_eq!(
+            zones.zones(),
+            &[
+                Zone::Range {
+                    min: Value::Float64(-1.0),
+                    max: Value::Float64(3.0)
+                },
+                Zone::Unordered,
+                Zone::Range {
+                    min: Value::Float64(7.0),
+                    max: Value::Float64(7.0)
+                },
+            ]
+        );
+
+        // Appends fold into the last block, then start new ones
+        col.push_value(Value::Float64(9.5)).unwrap();
+        col.push_value(Value::Float64(2.0)).unwrap();
+        col.push_value(Value::Null).unwrap();
+        zones.extend(&col);
+        assert_eq!(zones, ZoneMap::with_block_size(&col, 3));
+        assert_eq!(
+            zones.zones()[2],
+            Zone::Range {
+                min: Value::Float64(2.0),
+                max: Value::Float64(9.5)
+            }
+        );
+        assert_eq!(zones.zones()[3], Zone::AllNull);
+
+        assert!(ZoneMap::supports(DataType::Date));
+        assert!(!ZoneMap::supports(DataType::String));
+        assert_eq!(ZoneMap::build(&IntColumn::new()).zones(), &[]);
+    }
 }
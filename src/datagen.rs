@@ -0,0 +1,458 @@
+//! # Data Generation Module
+//!
+//! This module builds large tables in memory from a short description of
+//! their columns, so benchmarks and experiments do not need CSV files.
+//!
+//! ## Determinism
+//!
+//! Values come from a SplitMix64 generator seeded by the spec, so the same
+//! spec and row count always produce the same table, on every platform and
+//! build. Each column draws from its own stream, derived from the seed and the
+//! column's position, so adding a column does not change the values of the
+//! columns before it. SplitMix64 is implemented here instead of pulling in a
+//! random number crate because its output is fixed forever.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mini_rust_olap::datagen::{generate_table, ColumnSpec, TableSpec};
+//!
+//! let spec = TableSpec::new(7)
+//!     .column("id", ColumnSpec::Sequence { start: 1 })
+//!     .column("price", ColumnSpec::UniformFloat { min: 0.0, max: 100.0 })
+//!     .column(
+//!         "region",
+//!         ColumnSpec::Categorical {
+//!             cardinality: 5,
+//!             zipf_exponent: None,
+//!         },
+//!     );
+//!
+//! let table = generate_table("sales", 1000, &spec).unwrap();
+//! assert_eq!(table.row_count(), 1000);
+//! assert_eq!(table.column_names(), vec!["id", "price", "region"]);
+//! ```
+
+use crate::column::{Column, FloatColumn, IntColumn, StringColumn};
+use crate::error::{DatabaseError, Result};
+use crate::table::Table;
+use crate::types::Value;
+
+// ============================================================================
+// COLUMN AND TABLE SPECS
+// ============================================================================
+
+/// How the values of one generated column are produced.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnSpec {
+    /// Int64 values `start, start + 1, start + 2, ...`
+    Sequence { start: i64 },
+    /// Int64 values drawn uniformly from `min..=max`
+    UniformInt { min: i64, max: i64 },
+    /// Float64 values drawn uniformly from `min..max`
+    UniformFloat { min: f64, max: f64 },
+    /// String values `<column>_<k>` with `k` in `0..cardinality`
+    ///
+    /// Values are uniform unless `zipf_exponent` is set, in which case value
+    /// `k` is drawn with probability proportional to `1 / (k + 1)^exponent`,
+    /// so `<column>_0` is the most frequent.
+    Categorical {
+        cardinality: usize,
+        zipf_exponent: Option<f64>,
+    },
+}
+
+/// The columns of a generated table and the seed they are drawn with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSpec {
+    seed: u64,
+    columns: Vec<(String, ColumnSpec)>,
+}
+
+impl TableSpec {
+    /// Create a spec with no columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for the random columns; equal seeds give equal tables
+    pub fn new(seed: u64) -> Self {
+        TableSpec {
+            seed,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Add a column after the existing ones.
+    pub fn column(mut self, name: &str, spec: ColumnSpec) -> Self {
+        self.columns.push((name.to_string(), spec));
+        self
+    }
+
+    /// The columns of the spec, in order.
+    pub fn columns(&self) -> &[(String, ColumnSpec)] {
+        &self.columns
+    }
+
+    /// The seed of the spec.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// A general-purpose spec for experiments, used by the REPL's GENERATE.
+    ///
+    /// The columns are:
+    ///
+    /// * `id` - a sequence from 1
+    /// * `value` - uniform Int64 in `0..=999`, for selectivity sweeps
+    /// * `price` - uniform Float64 in `0..1000`
+    /// * `category` - 10 uniform categories
+    /// * `customer` - 10,000 zipfian categories (exponent 1.1)
+    pub fn benchmark(seed: u64) -> Self {
+        TableSpec::new(seed)
+            .column("id", ColumnSpec::Sequence { start: 1 })
+            .column("value", ColumnSpec::UniformInt { min: 0, max: 999 })
+            .column(
+                "price",
+                ColumnSpec::UniformFloat {
+                    min: 0.0,
+                    max: 1000.0,
+                },
+            )
+            .column(
+                "category",
+                ColumnSpec::Categorical {
+                    cardinality: 10,
+                    zipf_exponent: None,
+                },
+            )
+            .column(
+                "customer",
+                ColumnSpec::Categorical {
+                    cardinality: 10_000,
+                    zipf_exponent: Some(1.1),
+                },
+            )
+    }
+}
+
+// ============================================================================
+// GENERATION
+// ============================================================================
+
+/// Generate a table from a spec.
+///
+/// # Arguments
+///
+/// * `name` - Name of the new table
+/// * `rows` - Number of rows to generate
+/// * `spec` - The columns to generate
+///
+/// # Returns
+///
+/// The table, or an error if the spec has no columns, repeats a column name,
+/// or describes an empty range, zero categories, or a non-positive zipf
+/// exponent
+pub fn generate_table(name: &str, rows: usize, spec: &TableSpec) -> Result<Table> {
+    if spec.columns.is_empty() {
+        return Err(DatabaseError::table_error(format!(
+            "Cannot generate table '{}': the spec has no columns",
+            name
+        )));
+    }
+
+    let mut table = Table::new(name.to_string());
+    for (position, (column_name, column_spec)) in spec.columns.iter().enumerate() {
+        validate_column_spec(column_name, column_spec)?;
+        let mut rng = SplitMix64::new(spec.seed ^ (position as u64).wrapping_mul(GOLDEN_GAMMA));
+        let column = generate_column(column_name, column_spec, rows, &mut rng)?;
+        table.add_column(column_name.clone(), column)?;
+    }
+    Ok(table)
+}
+
+/// Check that a column spec describes at least one possible value.
+fn validate_column_spec(name: &str, spec: &ColumnSpec) -> Result<()> {
+    let problem = match spec {
+        ColumnSpec::Sequence { .. } => None,
+        ColumnSpec::UniformInt { min, max } if min > max => {
+            Some(format!("min {} is greater than max {}", min, max))
+        }
+        ColumnSpec::UniformFloat { min, max } if min >= max || !(max - min).is_finite() => {
+            Some(format!("range {}..{} is empty or not finite", min, max))
+        }
+        ColumnSpec::Categorical { cardinality: 0, .. } => {
+            Some("cardinality must be at least 1".to_string())
+        }
+        ColumnSpec::Categorical {
+            zipf_exponent: Some(exponent),
+            ..
+        } if !exponent.is_finite() || *exponent <= 0.0 => {
+            Some(format!("zipf exponent must be positive, got {}", exponent))
+        }
+        _ => None,
+    };
+    match problem {
+        Some(problem) => Err(DatabaseError::table_error(format!(
+            "Invalid spec for column '{}': {}",
+            name, problem
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Generate the values of one column.
+fn generate_column(
+    name: &str,
+    spec: &ColumnSpec,
+    rows: usize,
+    rng: &mut SplitMix64,
+) -> Result<Box<dyn Column>> {
+    Ok(match spec {
+        ColumnSpec::Sequence { start } => {
+            let mut column = IntColumn::with_capacity(rows);
+            for i in 0..rows as i64 {
+                let value = start.checked_add(i).ok_or_else(|| {
+                    DatabaseError::table_error(format!(
+                        "Sequence column '{}' overflows Int64",
+                        name
+                    ))
+                })?;
+                column.push_value(Value::Int64(value))?;
+            }
+            Box::new(column)
+        }
+        ColumnSpec::UniformInt { min, max } => {
+            // The span is at most 2^64, which needs one bit more than a u64
+            let span = (*max as i128 - *min as i128 + 1) as u128;
+            let mut column = IntColumn::with_capacity(rows);
+            for _ in 0..rows {
+                let offset = if span > u64::MAX as u128 {
+                    rng.next_u64() as i128
+                } else {
+                    rng.below(span as u64) as i128
+                };
+                column.push_value(Value::Int64((*min as i128 + offset) as i64))?;
+            }
+            Box::new(column)
+        }
+        ColumnSpec::UniformFloat { min, max } => {
+            let mut column = FloatColumn::with_capacity(rows);
+            for _ in 0..rows {
+                column.push_value(Value::Float64(min + rng.next_f64() * (max - min)))?;
+            }
+            Box::new(column)
+        }
+        ColumnSpec::Categorical {
+            cardinality,
+            zipf_exponent,
+        } => {
+            let labels: Vec<String> = (0..*cardinality)
+                .map(|k| format!("{}_{}", name, k))
+                .collect();
+            let zipf = zipf_exponent.map(|exponent| zipf_cdf(*cardinality, exponent));
+
+            let mut column = StringColumn::with_capacity(rows);
+            for _ in 0..rows {
+                let k = match &zipf {
+                    Some(cdf) => {
+                        let u = rng.next_f64();
+                        cdf.partition_point(|&p| p <= u).min(cardinality - 1)
+                    }
+                    None => rng.below(*cardinality as u64) as usize,
+                };
+                column.push_value(Value::String(labels[k].clone()))?;
+            }
+            Box::new(column)
+        }
+    })
+}
+
+/// Cumulative probabilities of a zipf distribution over `0..cardinality`.
+fn zipf_cdf(cardinality: usize, exponent: f64) -> Vec<f64> {
+    let mut cdf: Vec<f64> = (1..=cardinality)
+        .map(|rank| 1.0 / (rank as f64).powf(exponent))
+        .collect();
+    let mut total = 0.0;
+    for weight in cdf.iter_mut() {
+        total += *weight;
+        *weight = total;
+    }
+    for p in cdf.iter_mut() {
+        *p /= total;
+    }
+    cdf
+}
+
+// ============================================================================
+// RANDOM NUMBERS
+// ============================================================================
+
+/// The SplitMix64 increment, also used to spread column positions apart
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The SplitMix64 generator (Steele, Lea and Flood, 2014).
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A float in `[0, 1)` with 53 random bits.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// An integer in `0..bound` by multiply-shift; the bias is at most
+    /// `bound / 2^64`, far below what a benchmark can notice.
+    fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn values(table: &Table, column: &str) -> Vec<Value> {
+        (0..table.row_count())
+            .map(|row| table.get_value(column, row).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_table() {
+        let first = generate_table("a", 500, &TableSpec::benchmark(1)).unwrap();
+        let second = generate_table("b", 500, &TableSpec::benchmark(1)).unwrap();
+        let other_seed = generate_table("c", 500, &TableSpec::benchmark(2)).unwrap();
+
+        for column in first.column_names() {
+            assert_eq!(values(&first, &column), values(&second, &column));
+        }
+        assert_ne!(values(&first, "price"), values(&other_seed, "price"));
+        // A sequence does not depend on the seed
+        assert_eq!(values(&first, "id"), values(&other_seed, "id"));
+
+        // Adding a column leaves the earlier ones unchanged
+        let extended = TableSpec::benchmark(1).column("extra", ColumnSpec::Sequence { start: 0 });
+        let extended = generate_table("d", 500, &extended).unwrap();
+        assert_eq!(values(&first, "customer"), values(&extended, "customer"));
+    }
+
+    #[test]
+    fn test_column_specs() {
+        let spec = TableSpec::new(3)
+            .column("seq", ColumnSpec::Sequence { start: -2 })
+            .column("int", ColumnSpec::UniformInt { min: 5, max: 7 })
+            .column(
+                "wide",
+                ColumnSpec::UniformInt {
+                    min: i64::MIN,
+                    max: i64::MAX,
+                },
+            )
+            .column(
+                "float",
+                ColumnSpec::UniformFloat {
+                    min: -1.0,
+                    max: 1.0,
+                },
+            )
+            .column(
+                "cat",
+                ColumnSpec::Categorical {
+                    cardinality: 4,
+                    zipf_exponent: None,
+                },
+            );
+        let table = generate_table("t", 2000, &spec).unwrap();
+
+        assert_eq!(table.row_count(), 2000);
+        assert_eq!(
+            &values(&table, "seq")[..3],
+            &[Value::Int64(-2), Value::Int64(-1), Value::Int64(0)]
+        );
+
+        let ints: Vec<Value> = values(&table, "int");
+        for v in 5..=7 {
+            assert!(ints.contains(&Value::Int64(v)));
+        }
+        assert!(ints.iter().all(|v| matches!(v, Value::Int64(5..=7))));
+
+        assert!(values(&table, "float")
+            .iter()
+            .all(|v| matches!(v, Value::Float64(f) if (-1.0..1.0).contains(f))));
+
+        let mut categories: Vec<String> = values(&table, "cat")
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        categories.sort();
+        categories.dedup();
+        assert_eq!(categories, vec!["cat_0", "cat_1", "cat_2", "cat_3"]);
+    }
+
+    #[test]
+    fn test_zipfian_categories_are_skewed() {
+        let spec = TableSpec::new(11).column(
+            "key",
+            ColumnSpec::Categorical {
+                cardinality: 100,
+                zipf_exponent: Some(1.5),
+            },
+        );
+        let table = generate_table("t", 10_000, &spec).unwrap();
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for value in values(&table, "key") {
+            *counts.entry(value.to_string()).or_default() += 1;
+        }
+        // With exponent 1.5 the top key holds about 40% of the rows; a
+        // uniform draw would give it about 1%
+        let top = counts["key_0"];
+        assert!(top > 3000 && top < 5000, "{}", top);
+        assert!(counts.values().all(|&count| count <= top));
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        let invalid = [
+            ColumnSpec::UniformInt { min: 2, max: 1 },
+            ColumnSpec::UniformFloat { min: 1.0, max: 1.0 },
+            ColumnSpec::UniformFloat {
+                min: 0.0,
+                max: f64::NAN,
+            },
+            ColumnSpec::Categorical {
+                cardinality: 0,
+                zipf_exponent: None,
+            },
+            ColumnSpec::Categorical {
+                cardinality: 3,
+                zipf_exponent: Some(0.0),
+            },
+            ColumnSpec::Sequence { start: i64::MAX },
+        ];
+        for spec in invalid {
+            let result = generate_table("t", 2, &TableSpec::new(0).column("c", spec.clone()));
+            assert!(result.is_err(), "{:?}", spec);
+        }
+
+        assert!(generate_table("t", 2, &TableSpec::new(0)).is_err());
+        let duplicate = TableSpec::new(0)
+            .column("c", ColumnSpec::Sequence { start: 0 })
+            .column("c", ColumnSpec::Sequence { start: 0 });
+        assert!(generate_table("t", 2, &duplicate).is_err());
+    }
+}
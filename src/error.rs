@@ -16,8 +16,10 @@
 //! 1. **Column Errors**: Column operations
 //! 2. **Table Errors**: Table operations
 //! 3. **Catalog Errors**: Metadata management
-//! 4. **Ingestion Errors**: CSV parsing and data loading
-//! 5. **Execution Errors**: Query execution problems
+//! 4. **Ingestion Errors**: CSV parsing and data loading; a bad row of a
+//!    CSV file is an [`IngestError`] that records where it is and what it holds
+//! 5. **Execution Errors**: Query execution problems; an operator's
+//!    [`ExecutionError`] is wrapped as is, keeping its kind and fields
 //! 6. **Parser Errors**: SQL syntax and parsing issues
 //! 7. **Type Errors**: Data type and conversion issues
 //!
@@ -33,7 +35,10 @@
 //! }
 //! ```
 
+use crate::execution::ExecutionError;
+use crate::types::DataType;
 use std::io;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// A type alias for `Result<T, DatabaseError>`
@@ -103,6 +108,21 @@ pub enum DatabaseError {
     #[error("Ingestion error: {0}")]
     IngestionError(String),
 
+    /// A row of a CSV file that could not be loaded
+    ///
+    /// Unlike `IngestionError`, the position and contents of the bad row are
+    /// kept as fields, so callers can report or match on them.
+    #[error("Ingestion error: {0}")]
+    IngestError(IngestError),
+
+    /// Errors while planning a parsed query
+    ///
+    /// Planning errors occur when a syntactically valid query cannot be turned
+    /// into an execution plan: unknown tables or columns, invalid aggregates,
+    /// or unsupported combinations of clauses.
+    #[error("Planning error: {0}")]
+    PlanningError(String),
+
     /// Errors during query execution
     ///
     /// Query execution errors occur when the physical operators fail.
@@ -110,6 +130,13 @@ pub enum DatabaseError {
     #[error("Execution error: {0}")]
     ExecutionError(String),
 
+    /// An error raised by a physical operator while a plan runs
+    ///
+    /// Unlike `ExecutionError`, the operator's error is kept as it was
+    /// raised, so callers can match on its kind and fields.
+    #[error("Execution error: {0}")]
+    Execution(#[from] ExecutionError),
+
     /// Errors during SQL parsing
     ///
     /// Parser errors occur when the input SQL doesn't match the expected syntax.
@@ -117,6 +144,13 @@ pub enum DatabaseError {
     #[error("Parser error: {0}")]
     ParserError(String),
 
+    /// Errors when changing a session setting
+    ///
+    /// Settings errors occur when a well-formed `SET <name> = <value>` names
+    /// an unknown setting or gives a value the setting does not accept.
+    #[error("Settings error: {0}")]
+    SettingsError(String),
+
     /// Errors related to data types and conversions
     ///
     /// Type errors occur when attempting operations between incompatible types,
@@ -137,6 +171,62 @@ pub enum DatabaseError {
     GenericError(String),
 }
 
+/// A row of a CSV file that could not be loaded.
+///
+/// # Example
+/// ```rust
+/// use mini_rust_olap::error::{IngestError, IngestErrorKind};
+/// use mini_rust_olap::types::DataType;
+///
+/// let err = IngestError {
+///     path: "sales.csv".into(),
+///     line: 7,
+///     kind: IngestErrorKind::InvalidValue {
+///         column: "amount".to_string(),
+///         value: "n/a".to_string(),
+///         expected: DataType::Float64,
+///     },
+/// };
+/// assert_eq!(
+///     err.to_string(),
+///     "'sales.csv' line 7: column 'amount' expects Float64, found 'n/a'"
+/// );
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("'{}' line {line}: {kind}", path.display())]
+pub struct IngestError {
+    /// The file being loaded
+    pub path: PathBuf,
+    /// 1-based line of the file on which the row starts (a header is line 1)
+    pub line: usize,
+    /// What is wrong with the row
+    pub kind: IngestErrorKind,
+}
+
+/// What is wrong with a row of a CSV file.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum IngestErrorKind {
+    /// A field does not parse as its column's type
+    #[error("column '{column}' expects {expected}, found '{value}'")]
+    InvalidValue {
+        /// The column's name
+        column: String,
+        /// The field's raw text
+        value: String,
+        /// The column's type
+        expected: DataType,
+    },
+
+    /// The row has a different number of fields than the file has columns
+    #[error("expected {expected} fields, found {found}")]
+    FieldCount {
+        /// The number of columns
+        expected: usize,
+        /// The number of fields in the row
+        found: usize,
+    },
+}
+
 // ============================================================================
 // IMPLEMENTATIONS FOR CREATING SPECIFIC ERRORS
 // ============================================================================
@@ -208,6 +298,18 @@ impl DatabaseError {
         Self::ExecutionError(msg.into())
     }
 
+    /// Creates a planning error with a descriptive message
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::error::DatabaseError;
+    ///
+    /// let err = DatabaseError::planning_error("Table 'users' not found");
+    /// ```
+    pub fn planning_error(msg: impl Into<String>) -> Self {
+        Self::PlanningError(msg.into())
+    }
+
     /// Creates a parser error with a descriptive message
     ///
     /// # Example
@@ -220,6 +322,18 @@ impl DatabaseError {
         Self::ParserError(msg.into())
     }
 
+    /// Creates a settings error with a descriptive message
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::error::DatabaseError;
+    ///
+    /// let err = DatabaseError::settings_error("Unknown setting 'parallelism'");
+    /// ```
+    pub fn settings_error(msg: impl Into<String>) -> Self {
+        Self::SettingsError(msg.into())
+    }
+
     /// Creates a type error with a descriptive message
     ///
     /// # Example
@@ -231,6 +345,41 @@ impl DatabaseError {
     pub fn type_error(msg: impl Into<String>) -> Self {
         Self::TypeError(msg.into())
     }
+
+    /// Prefixes the error's message with context, keeping its variant
+    ///
+    /// # Arguments
+    /// * `context` - Where the error happened, e.g. which statement failed
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::error::DatabaseError;
+    ///
+    /// let err = DatabaseError::parser_error("Unexpected token").with_context("Statement 2");
+    /// assert_eq!(err.to_string(), "Parser error: Statement 2: Unexpected token");
+    /// ```
+    pub fn with_context(self, context: impl std::fmt::Display) -> Self {
+        let prefix = |msg: String| format!("{}: {}", context, msg);
+        match self {
+            Self::ColumnError(msg) => Self::ColumnError(prefix(msg)),
+            Self::TableError(msg) => Self::TableError(prefix(msg)),
+            Self::CatalogError(msg) => Self::CatalogError(prefix(msg)),
+            Self::IngestionError(msg) => Self::IngestionError(prefix(msg)),
+            // The context has no field to live in, so the row's details
+            // become part of the message
+            Self::IngestError(err) => Self::IngestionError(prefix(err.to_string())),
+            Self::Execution(err) => Self::ExecutionError(prefix(err.to_string())),
+            Self::PlanningError(msg) => Self::PlanningError(prefix(msg)),
+            Self::ExecutionError(msg) => Self::ExecutionError(prefix(msg)),
+            Self::ParserError(msg) => Self::ParserError(prefix(msg)),
+            Self::SettingsError(msg) => Self::SettingsError(prefix(msg)),
+            Self::TypeError(msg) => Self::TypeError(prefix(msg)),
+            Self::IoError(err) => {
+                Self::IoError(io::Error::new(err.kind(), prefix(err.to_string())))
+            }
+            Self::GenericError(msg) => Self::GenericError(prefix(msg)),
+        }
+    }
 }
 
 // ============================================================================
@@ -280,6 +429,9 @@ mod tests {
         let par_err = DatabaseError::parser_error("Syntax error");
         assert_eq!(par_err.to_string(), "Parser error: Syntax error");
 
+        let set_err = DatabaseError::settings_error("Unknown setting");
+        assert_eq!(set_err.to_string(), "Settings error: Unknown setting");
+
         let typ_err = DatabaseError::type_error("Type mismatch");
         assert_eq!(typ_err.to_string(), "Type error: Type mismatch");
     }
@@ -321,6 +473,64 @@ mod tests {
         assert!(db_err.to_string().contains("file not found"));
     }
 
+    /// Tests that context is prefixed without changing the variant
+    #[test]
+    fn test_with_context() {
+        let err = DatabaseError::catalog_error("Table 'x' not found").with_context("Statement 2");
+        assert!(matches!(err, DatabaseError::CatalogError(_)));
+        assert_eq!(
+            err.to_string(),
+            "Catalog error: Statement 2: Table 'x' not found"
+        );
+
+        let err = DatabaseError::from(ExecutionError::ColumnNotFound("age".to_string()))
+            .with_context("Statement 3");
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Statement 3: Column 'age' not found in batch"
+        );
+
+        let err = DatabaseError::from(io::Error::new(io::ErrorKind::NotFound, "missing"))
+            .with_context("Statement 1");
+        match err {
+            DatabaseError::IoError(e) => {
+                assert_eq!(e.kind(), io::ErrorKind::NotFound);
+                assert_eq!(e.to_string(), "Statement 1: missing");
+            }
+            other => panic!("Expected IoError, got {:?}", other),
+        }
+    }
+
+    /// Tests that a bad CSV row keeps its details as fields
+    #[test]
+    fn test_ingest_error() {
+        let err = DatabaseError::IngestError(IngestError {
+            path: PathBuf::from("data/sales.csv"),
+            line: 12,
+            kind: IngestErrorKind::FieldCount {
+                expected: 3,
+                found: 2,
+            },
+        });
+        assert_eq!(
+            err.to_string(),
+            "Ingestion error: 'data/sales.csv' line 12: expected 3 fields, found 2"
+        );
+        match &err {
+            DatabaseError::IngestError(IngestError { line, kind, .. }) => {
+                assert_eq!(*line, 12);
+                assert!(matches!(kind, IngestErrorKind::FieldCount { found: 2, .. }));
+            }
+            other => panic!("Expected IngestError, got {:?}", other),
+        }
+
+        // Context turns it into a plain message
+        assert_eq!(
+            err.with_context("Statement 1").to_string(),
+            "Ingestion error: Statement 1: 'data/sales.csv' line 12: expected 3 fields, found 2"
+        );
+    }
+
     /// Tests error propagation with the ? operator
     #[test]
     fn test_error_propagation() {
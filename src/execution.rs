@@ -5,13 +5,22 @@
 //! `Operator` trait for implementing query operators like Scan, Filter,
 //! Project, and GroupBy.
 
-use crate::column::Column;
-use crate::table::Table;
-use crate::types::{DataType, SortDirection, Value};
-use std::collections::HashMap;
+use crate::column::{
+    resolve_slice, slice_column, Column, ColumnSlice, DateColumn, DictStringColumn, FilteredColumn,
+    FloatColumn, IntColumn, RowSelection, StringColumn, Zone,
+};
+use crate::format::FormatOptions;
+use crate::table::{ColumnSet, Table};
+use crate::types::{
+    canonical_float_bits, parse_boolean, parse_date, DataType, SortDirection, Value,
+};
+use crate::virtual_table::{RowIterator, VirtualTable};
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Error type for execution operations
 #[derive(Debug)]
@@ -32,6 +41,44 @@ pub enum ExecutionError {
     InvalidRowIndex { index: usize, count: usize },
     /// IO error during execution
     IoError(std::io::Error),
+    /// The query's cancellation token was cancelled or its deadline passed
+    Cancelled,
+    /// An operator needed more memory than the query's [`MemoryTracker`]
+    /// allows
+    ResourceExhausted {
+        /// The operator that asked for the memory, as EXPLAIN ANALYZE
+        /// describes it
+        operator: String,
+        /// Bytes the query would have held with the request granted
+        requested: usize,
+        /// The tracker's limit, in bytes
+        limit: usize,
+    },
+    /// A column holds a different type than required
+    TypeMismatch {
+        /// The type required
+        expected: DataType,
+        /// The type found
+        actual: DataType,
+        /// The column, when the mismatch is in one
+        column: Option<String>,
+    },
+    /// An aggregate function failed on a value of its input column
+    AggregateError {
+        /// The aggregated input column
+        column: String,
+        /// Why the aggregate failed
+        source: Box<crate::error::DatabaseError>,
+    },
+    /// An input of a blocking operator failed while it was being read
+    ChildOperatorError(Box<ExecutionError>),
+    /// A value could not be read from or written to a column
+    ColumnConversion(String),
+    /// A scan of a table with columns was given an empty column list
+    EmptyProjection {
+        /// The scanned table
+        table: String,
+    },
     /// Custom error message
     Custom(String),
 }
@@ -71,6 +118,54 @@ impl fmt::Display for ExecutionError {
             ExecutionError::IoError(err) => {
                 write!(f, "IO error during execution: {}", err)
             }
+            ExecutionError::Cancelled => {
+                write!(f, "Query cancelled")
+            }
+            ExecutionError::ResourceExhausted {
+                operator,
+                requested,
+                limit,
+            } => {
+                write!(
+                    f,
+                    "{} exceeded the query's memory limit: {} bytes needed, limit is {} bytes",
+                    operator, requested, limit
+                )
+            }
+            ExecutionError::TypeMismatch {
+                expected,
+                actual,
+                column: Some(column),
+            } => {
+                write!(
+                    f,
+                    "column '{}' is {}, expected {}",
+                    column, actual, expected
+                )
+            }
+            ExecutionError::TypeMismatch {
+                expected,
+                actual,
+                column: None,
+            } => {
+                write!(f, "expected {}, found {}", expected, actual)
+            }
+            ExecutionError::AggregateError { column, source } => {
+                write!(f, "aggregate over column '{}' failed: {}", column, source)
+            }
+            ExecutionError::ChildOperatorError(err) => {
+                write!(f, "{}", err)
+            }
+            ExecutionError::ColumnConversion(msg) => {
+                write!(f, "{}", msg)
+            }
+            ExecutionError::EmptyProjection { table } => {
+                write!(
+                    f,
+                    "A scan of table '{}' must select at least one column; scan without a column list to read them all",
+                    table
+                )
+            }
             ExecutionError::Custom(msg) => {
                 write!(f, "{}", msg)
             }
@@ -80,21 +175,268 @@ impl fmt::Display for ExecutionError {
 
 impl std::error::Error for ExecutionError {}
 
+impl ExecutionError {
+    /// Wraps an error returned by an operator's input.
+    ///
+    /// Cancellation and the memory limit apply to the whole query rather
+    /// than to the input, so their errors pass through unwrapped.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error the input returned
+    pub fn from_child(err: ExecutionError) -> Self {
+        match err {
+            ExecutionError::Cancelled | ExecutionError::ResourceExhausted { .. } => err,
+            err => ExecutionError::ChildOperatorError(Box::new(err)),
+        }
+    }
+
+    /// The error at the bottom of any `ChildOperatorError` wrappers, i.e.
+    /// the one raised by the operator that actually failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mini_rust_olap::execution::ExecutionError;
+    ///
+    /// let err = ExecutionError::from_child(ExecutionError::from_child(
+    ///     ExecutionError::ColumnNotFound("age".to_string()),
+    /// ));
+    /// assert!(matches!(err.root_cause(), ExecutionError::ColumnNotFound(name) if name == "age"));
+    /// ```
+    pub fn root_cause(&self) -> &ExecutionError {
+        match self {
+            ExecutionError::ChildOperatorError(err) => err.root_cause(),
+            err => err,
+        }
+    }
+}
+
 impl From<std::io::Error> for ExecutionError {
     fn from(err: std::io::Error) -> Self {
         ExecutionError::IoError(err)
     }
 }
 
+/// Keeps the structure of errors that have an execution counterpart: an
+/// execution error that was wrapped is unwrapped again, and the column and
+/// type errors columns raise for values they cannot hold become
+/// `ColumnConversion`. Anything else keeps only its message.
 impl From<crate::error::DatabaseError> for ExecutionError {
     fn from(err: crate::error::DatabaseError) -> Self {
-        ExecutionError::Custom(err.to_string())
+        use crate::error::DatabaseError;
+        match err {
+            DatabaseError::Execution(err) => err,
+            DatabaseError::ColumnError(msg) | DatabaseError::TypeError(msg) => {
+                ExecutionError::ColumnConversion(msg)
+            }
+            DatabaseError::IoError(err) => ExecutionError::IoError(err),
+            err => ExecutionError::Custom(err.to_string()),
+        }
     }
 }
 
 /// Result type for execution operations
 pub type Result<T> = std::result::Result<T, ExecutionError>;
 
+// ============================================================================
+// SCHEMA
+// ============================================================================
+
+/// A named, typed column in a [`Schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// Column name
+    pub name: String,
+    /// Type of every value in the column
+    pub data_type: DataType,
+}
+
+impl Field {
+    /// Create a new field.
+    pub fn new(name: impl Into<String>, data_type: DataType) -> Self {
+        Field {
+            name: name.into(),
+            data_type,
+        }
+    }
+}
+
+/// The ordered columns an operator produces.
+///
+/// Field `i` describes column `i` of every batch the operator returns, so
+/// names and types can never disagree with the column order. Names are
+/// unique: [`Schema::new`] rejects duplicates.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::execution::{Field, Schema};
+/// use mini_rust_olap::types::DataType;
+///
+/// let schema = Schema::new(vec![
+///     Field::new("name", DataType::String),
+///     Field::new("age", DataType::Int64),
+/// ])
+/// .unwrap();
+/// assert_eq!(schema.column_names(), vec!["name", "age"]);
+/// assert_eq!(schema.index_of("age"), Some(1));
+/// assert_eq!(schema.data_type("age"), Some(DataType::Int64));
+///
+/// // Two columns cannot share a name
+/// let duplicate = Schema::new(vec![
+///     Field::new("age", DataType::Int64),
+///     Field::new("age", DataType::Float64),
+/// ]);
+/// assert!(duplicate.is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Schema {
+    fields: Vec<Field>,
+}
+
+impl Schema {
+    /// Create a schema from fields in column order.
+    ///
+    /// # Returns
+    ///
+    /// An error naming the first column that appears twice.
+    pub fn new(fields: Vec<Field>) -> Result<Self> {
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|other| other.name == field.name) {
+                return Err(ExecutionError::Custom(format!(
+                    "Duplicate column name: {}",
+                    field.name
+                )));
+            }
+        }
+        Ok(Schema { fields })
+    }
+
+    /// Create a schema from (name, type) pairs in column order.
+    pub fn from_columns(columns: &[(String, DataType)]) -> Result<Self> {
+        Schema::new(
+            columns
+                .iter()
+                .map(|(name, data_type)| Field::new(name.clone(), *data_type))
+                .collect(),
+        )
+    }
+
+    /// Returns the fields in column order.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Returns the field of column `index`.
+    pub fn field(&self, index: usize) -> Option<&Field> {
+        self.fields.get(index)
+    }
+
+    /// Returns the number of columns.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns true if the schema has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the position of the column called `name`.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field.name == name)
+    }
+
+    /// Returns the type of the column called `name`.
+    pub fn data_type(&self, name: &str) -> Option<DataType> {
+        self.index_of(name)
+            .map(|index| self.fields[index].data_type)
+    }
+
+    /// Returns the column names in order.
+    pub fn column_names(&self) -> Vec<String> {
+        self.fields.iter().map(|field| field.name.clone()).collect()
+    }
+
+    /// Returns the column types in order.
+    pub fn data_types(&self) -> Vec<DataType> {
+        self.fields.iter().map(|field| field.data_type).collect()
+    }
+
+    /// Keep the columns at `indices`, in that order.
+    ///
+    /// # Returns
+    ///
+    /// An error if an index is out of range or selected twice.
+    pub fn select(&self, indices: &[usize]) -> Result<Schema> {
+        let fields = indices
+            .iter()
+            .map(|&index| {
+                self.fields
+                    .get(index)
+                    .cloned()
+                    .ok_or(ExecutionError::InvalidColumnIndex {
+                        index,
+                        count: self.fields.len(),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Schema::new(fields)
+    }
+
+    /// Keep the columns at `indices`, in that order, renamed to `aliases`.
+    ///
+    /// # Returns
+    ///
+    /// An error if an index is out of range, the number of aliases differs
+    /// from the number of indices, or two columns end up with one name.
+    pub fn project(&self, indices: &[usize], aliases: &[String]) -> Result<Schema> {
+        check_alias_count(indices.len(), aliases.len())?;
+        let fields = indices
+            .iter()
+            .zip(aliases)
+            .map(|(&index, alias)| {
+                let field = self
+                    .fields
+                    .get(index)
+                    .ok_or(ExecutionError::InvalidColumnIndex {
+                        index,
+                        count: self.fields.len(),
+                    })?;
+                Ok(Field::new(alias.clone(), field.data_type))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Schema::new(fields)
+    }
+}
+
+/// Fails unless there is one alias per projected column.
+fn check_alias_count(columns: usize, aliases: usize) -> Result<()> {
+    if aliases != columns {
+        return Err(ExecutionError::Custom(format!(
+            "Alias count mismatch: expected {}, got {}",
+            columns, aliases
+        )));
+    }
+    Ok(())
+}
+
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| format!("{}: {}", field.name, field.data_type))
+            .collect();
+        write!(f, "({})", fields.join(", "))
+    }
+}
+
+// ============================================================================
+// BATCH
+// ============================================================================
+
 /// A batch of rows in columnar format for vectorized execution.
 ///
 /// A Batch holds data in columnar format, which allows for efficient
@@ -126,6 +468,8 @@ pub type Result<T> = std::result::Result<T, ExecutionError>;
 #[derive(Clone)]
 pub struct Batch {
     columns: Vec<Arc<dyn Column>>,
+    /// Names and types of the columns, when the producer attached them
+    schema: Option<Arc<Schema>>,
 }
 
 impl Batch {
@@ -133,30 +477,122 @@ impl Batch {
     ///
     /// # Panics
     ///
-    /// Panics if columns have different lengths or if the batch is empty.
+    /// Panics if columns have different lengths or if the batch is empty;
+    /// operators use [`Batch::try_new`], which returns those as errors.
     ///
     /// # Arguments
     ///
     /// * `columns` - Vector of columns with identical row counts
     pub fn new(columns: Vec<Arc<dyn Column>>) -> Self {
-        if columns.is_empty() {
-            panic!("Cannot create a batch with no columns");
-        }
+        Self::try_new(columns).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        let row_count = columns[0].len();
+    /// Create a new Batch from a vector of columns, or fail if there are
+    /// none or their lengths differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - Vector of columns with identical row counts
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::execution::Batch;
+    /// use std::sync::Arc;
+    ///
+    /// assert!(Batch::try_new(Vec::new()).is_err());
+    ///
+    /// let mut ids = IntColumn::new();
+    /// ids.push_value(mini_rust_olap::types::Value::Int64(1)).unwrap();
+    /// let batch = Batch::try_new(vec![Arc::new(ids)]).unwrap();
+    /// assert_eq!(batch.row_count(), 1);
+    /// ```
+    pub fn try_new(columns: Vec<Arc<dyn Column>>) -> Result<Self> {
+        let Some(first) = columns.first() else {
+            return Err(ExecutionError::SchemaMismatch(
+                "Cannot create a batch with no columns".to_string(),
+            ));
+        };
 
+        let row_count = first.len();
         for (i, col) in columns.iter().enumerate() {
             if col.len() != row_count {
-                panic!(
+                return Err(ExecutionError::SchemaMismatch(format!(
                     "Column {} has {} rows, but column 0 has {} rows",
                     i,
                     col.len(),
                     row_count
-                );
+                )));
+            }
+        }
+
+        Ok(Batch {
+            columns,
+            schema: None,
+        })
+    }
+
+    /// Attach the names and types of this batch's columns.
+    ///
+    /// Operators attach their output schema to every batch they return, so
+    /// consumers can look columns up with [`Batch::column_by_name`].
+    ///
+    /// # Returns
+    ///
+    /// An error if the schema has a different number of columns than the
+    /// batch or a field's type differs from its column's.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::execution::{Batch, Field, Schema};
+    /// use mini_rust_olap::types::{DataType, Value};
+    /// use std::sync::Arc;
+    ///
+    /// let mut ages = IntColumn::new();
+    /// ages.push_value(Value::Int64(42)).unwrap();
+    /// let schema = Schema::new(vec![Field::new("age", DataType::Int64)]).unwrap();
+    ///
+    /// let batch = Batch::new(vec![Arc::new(ages)])
+    ///     .with_schema(Arc::new(schema))
+    ///     .unwrap();
+    /// let age = batch.column_by_name("age").unwrap();
+    /// assert_eq!(age.get(0).unwrap(), Value::Int64(42));
+    /// ```
+    pub fn with_schema(mut self, schema: Arc<Schema>) -> Result<Batch> {
+        if schema.len() != self.columns.len() {
+            return Err(ExecutionError::SchemaMismatch(format!(
+                "schema has {} columns, but the batch has {}",
+                schema.len(),
+                self.columns.len()
+            )));
+        }
+        for (field, column) in schema.fields().iter().zip(&self.columns) {
+            if field.data_type != column.data_type() {
+                return Err(ExecutionError::TypeMismatch {
+                    expected: field.data_type,
+                    actual: column.data_type(),
+                    column: Some(field.name.clone()),
+                });
             }
         }
+        self.schema = Some(schema);
+        Ok(self)
+    }
+
+    /// Returns the schema attached with [`Batch::with_schema`], if any.
+    pub fn schema(&self) -> Option<&Schema> {
+        self.schema.as_deref()
+    }
 
-        Batch { columns }
+    /// Get a column by name.
+    ///
+    /// Returns None if the batch has no schema or no column called `name`.
+    pub fn column_by_name(&self, name: &str) -> Option<&Arc<dyn Column>> {
+        let index = self.schema.as_ref()?.index_of(name)?;
+        self.columns.get(index)
     }
 
     /// Create an empty batch with the given schema.
@@ -167,6 +603,7 @@ impl Batch {
         // Create a batch with no columns (special case for empty result)
         Batch {
             columns: Vec::new(),
+            schema: None,
         }
     }
 
@@ -209,6 +646,43 @@ impl Batch {
         &self.columns
     }
 
+    /// Get a column as its concrete type, such as `IntColumn`.
+    ///
+    /// Returns None if the index is out of range or the column is stored as
+    /// another type. Columns read by a scan are often a
+    /// [`ColumnSlice`](crate::column::ColumnSlice) of the table's column,
+    /// and filtered columns a [`FilteredColumn`] view of it; use
+    /// [`resolve_slice`] to reach the data behind either.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The column index
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::execution::Batch;
+    /// use mini_rust_olap::types::Value;
+    /// use std::sync::Arc;
+    ///
+    /// let mut amounts = IntColumn::new();
+    /// for amount in [10, 20, 12] {
+    ///     amounts.push_value(Value::Int64(amount)).unwrap();
+    /// }
+    /// let batch = Batch::new(vec![Arc::new(amounts)]);
+    ///
+    /// // Sum a column without SQL or a `Value` per cell
+    /// let total: i64 = batch
+    ///     .column_as::<IntColumn>(0)
+    ///     .map(|column| column.iter().flatten().sum())
+    ///     .unwrap_or(0);
+    /// assert_eq!(total, 42);
+    /// ```
+    pub fn column_as<T: Column + 'static>(&self, index: usize) -> Option<&T> {
+        self.columns.get(index)?.as_any().downcast_ref::<T>()
+    }
+
     /// Get the value at a specific row and column.
     ///
     /// # Arguments
@@ -234,21 +708,75 @@ impl Batch {
             });
         }
 
-        column
-            .get(row_index)
-            .map_err(|e| ExecutionError::Custom(e.to_string()))
+        Ok(column.get(row_index)?)
     }
 
     /// Get the value at a specific row and column as a string.
     ///
-    /// This is a convenience method that always returns the value as a string.
+    /// This is a convenience method that always returns the value as a
+    /// string, rendered with the default [`FormatOptions`].
     pub fn get_as_string(&self, row_index: usize, column_index: usize) -> Result<String> {
         let value = self.get(row_index, column_index)?;
-        Ok(value.to_string())
+        Ok(value.format(&FormatOptions::default()))
+    }
+
+    /// Convert the rows to a JSON array with one object per row.
+    ///
+    /// Each object maps a name from `names` to the value in the matching
+    /// column, encoded with [`Value::to_json`]: numbers, strings and
+    /// booleans as themselves, dates as `YYYY-MM-DD`, and NULL, NaN and
+    /// infinities as `null`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `names` does not have one entry per column.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn};
+    /// use mini_rust_olap::execution::Batch;
+    /// use mini_rust_olap::types::Value;
+    /// use std::sync::Arc;
+    ///
+    /// let mut ages = IntColumn::new();
+    /// ages.push_value(Value::Int64(42)).unwrap();
+    /// ages.push_null();
+    ///
+    /// let batch = Batch::new(vec![Arc::new(ages)]);
+    /// assert_eq!(
+    ///     batch.to_json_rows(&["age".to_string()]).to_string(),
+    ///     r#"[{"age":42},{"age":null}]"#
+    /// );
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json_rows(&self, names: &[String]) -> serde_json::Value {
+        assert_eq!(
+            names.len(),
+            self.columns.len(),
+            "to_json_rows needs one name per column"
+        );
+        let rows = (0..self.row_count())
+            .map(|row| {
+                let object = names
+                    .iter()
+                    .zip(&self.columns)
+                    .map(|(name, column)| {
+                        let value = column.get(row).expect("row is within the batch");
+                        (name.clone(), value.to_json())
+                    })
+                    .collect();
+                serde_json::Value::Object(object)
+            })
+            .collect();
+        serde_json::Value::Array(rows)
     }
 
     /// Select specific columns to create a new batch.
     ///
+    /// The result keeps the matching part of this batch's schema, unless
+    /// an index is selected twice, which would repeat a column name.
+    ///
     /// # Arguments
     ///
     /// * `column_indices` - Indices of columns to select
@@ -265,27 +793,169 @@ impl Batch {
             selected_columns.push(self.columns[index].clone());
         }
 
-        Ok(Batch::new(selected_columns))
+        let mut batch = Batch::try_new(selected_columns)?;
+        batch.schema = self
+            .schema
+            .as_ref()
+            .and_then(|schema| schema.select(column_indices).ok())
+            .map(Arc::new);
+        Ok(batch)
     }
 
     /// Project columns to create a new batch with renamed columns.
     ///
+    /// The aliases name the result's columns whether or not this batch has
+    /// a schema.
+    ///
     /// # Arguments
     ///
     /// * `column_indices` - Indices of columns to select
-    /// * `_aliases` - New names for the selected columns (not yet implemented)
-    pub fn project(&self, column_indices: &[usize], _aliases: &[String]) -> Result<Batch> {
-        // For now, just select - renaming will be handled at the schema level
-        self.select(column_indices)
+    /// * `aliases` - New names for the selected columns, or empty to keep
+    ///   the names in this batch's schema
+    ///
+    /// # Returns
+    ///
+    /// An error if an index is out of range, the number of aliases differs
+    /// from the number of indices, or two aliases are the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, IntColumn, StringColumn};
+    /// use mini_rust_olap::execution::Batch;
+    /// use mini_rust_olap::types::Value;
+    /// use std::sync::Arc;
+    ///
+    /// let mut ids = IntColumn::new();
+    /// ids.push_value(Value::Int64(7)).unwrap();
+    /// let mut names = StringColumn::new();
+    /// names.push_value(Value::String("Ada".to_string())).unwrap();
+    /// let batch = Batch::new(vec![Arc::new(ids), Arc::new(names)]);
+    ///
+    /// let renamed = batch
+    ///     .project(&[1, 0], &["who".to_string(), "user_id".to_string()])
+    ///     .unwrap();
+    /// assert_eq!(renamed.schema().unwrap().column_names(), ["who", "user_id"]);
+    ///
+    /// // Two columns cannot share a name
+    /// assert!(batch.project(&[0, 1], &["x".to_string(), "x".to_string()]).is_err());
+    /// ```
+    pub fn project(&self, column_indices: &[usize], aliases: &[String]) -> Result<Batch> {
+        let selected = self.select(column_indices)?;
+        if aliases.is_empty() {
+            return Ok(selected);
+        }
+
+        let schema = match &self.schema {
+            Some(schema) => schema.project(column_indices, aliases)?,
+            None => {
+                check_alias_count(column_indices.len(), aliases.len())?;
+                Schema::new(
+                    aliases
+                        .iter()
+                        .zip(selected.columns())
+                        .map(|(alias, column)| Field::new(alias.clone(), column.data_type()))
+                        .collect(),
+                )?
+            }
+        };
+        selected.with_schema(Arc::new(schema))
+    }
+
+    /// Rename one column of a batch that has a schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The column's current name
+    /// * `new` - Its new name
+    ///
+    /// # Returns
+    ///
+    /// An error if the batch has no schema, has no column `old`, or already
+    /// has a different column called `new`.
+    pub fn rename(self, old: &str, new: impl Into<String>) -> Result<Batch> {
+        let schema = self
+            .schema
+            .as_deref()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+        let index = schema
+            .index_of(old)
+            .ok_or_else(|| ExecutionError::ColumnNotFound(old.to_string()))?;
+
+        let mut fields = schema.fields().to_vec();
+        fields[index].name = new.into();
+        let schema = Arc::new(Schema::new(fields)?);
+        self.with_schema(schema)
+    }
+
+    /// Append a named column to a batch that has a schema.
+    ///
+    /// Together with [`Batch::with_schema`] for the first columns, this
+    /// builds a batch column by column.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The new column's name
+    /// * `column` - Its data, with one value per row of the batch
+    ///
+    /// # Returns
+    ///
+    /// An error if the batch has no schema, the column has a different
+    /// number of rows, or the batch already has a column called `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_rust_olap::column::{Column, FloatColumn, IntColumn};
+    /// use mini_rust_olap::execution::{Batch, Field, Schema};
+    /// use mini_rust_olap::types::{DataType, Value};
+    /// use std::sync::Arc;
+    ///
+    /// let mut ids = IntColumn::new();
+    /// ids.push_value(Value::Int64(1)).unwrap();
+    /// let mut scores = FloatColumn::new();
+    /// scores.push_value(Value::Float64(0.5)).unwrap();
+    ///
+    /// let schema = Schema::new(vec![Field::new("id", DataType::Int64)]).unwrap();
+    /// let batch = Batch::new(vec![Arc::new(ids)])
+    ///     .with_schema(Arc::new(schema))
+    ///     .unwrap()
+    ///     .with_column("score", Arc::new(scores))
+    ///     .unwrap();
+    /// assert_eq!(batch.column_by_name("score").unwrap().get(0).unwrap(), Value::Float64(0.5));
+    /// ```
+    pub fn with_column(self, name: impl Into<String>, column: Arc<dyn Column>) -> Result<Batch> {
+        let schema = self
+            .schema
+            .as_deref()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+        if column.len() != self.row_count() {
+            return Err(ExecutionError::SchemaMismatch(format!(
+                "column has {} rows, but the batch has {}",
+                column.len(),
+                self.row_count()
+            )));
+        }
+
+        let mut fields = schema.fields().to_vec();
+        fields.push(Field::new(name, column.data_type()));
+        let schema = Arc::new(Schema::new(fields)?);
+
+        let mut columns = self.columns;
+        columns.push(column);
+        Batch::try_new(columns)?.with_schema(schema)
     }
 
     /// Skip rows from the beginning of the batch.
     ///
+    /// The result shares this batch's column data (see `ColumnSlice`).
+    /// Skipping every row gives a zero-row batch with the same columns.
+    ///
     /// # Arguments
     ///
     /// * `skip_count` - Number of rows to skip
     pub fn skip_rows(&self, skip_count: usize) -> Result<Batch> {
-        if skip_count >= self.row_count() {
+        if skip_count > self.row_count() {
             return Err(ExecutionError::Custom(format!(
                 "Cannot skip {} rows from batch with only {} rows",
                 skip_count,
@@ -293,40 +963,13 @@ impl Batch {
             )));
         }
 
-        let mut new_columns = Vec::new();
-        for col in &self.columns {
-            let data_type = col.data_type();
-            let new_col: Arc<dyn Column> = match data_type {
-                DataType::Int64 => {
-                    let mut int_col = crate::column::IntColumn::new();
-                    for row_idx in skip_count..col.len() {
-                        int_col.push_value(col.get(row_idx)?)?;
-                    }
-                    Arc::new(int_col)
-                }
-                DataType::Float64 => {
-                    let mut float_col = crate::column::FloatColumn::new();
-                    for row_idx in skip_count..col.len() {
-                        float_col.push_value(col.get(row_idx)?)?;
-                    }
-                    Arc::new(float_col)
-                }
-                DataType::String => {
-                    let mut string_col = crate::column::StringColumn::new();
-                    for row_idx in skip_count..col.len() {
-                        string_col.push_value(col.get(row_idx)?)?;
-                    }
-                    Arc::new(string_col)
-                }
-            };
-            new_columns.push(new_col);
-        }
-
-        Ok(Batch::new(new_columns))
+        self.slice_rows(skip_count..self.row_count())
     }
 
     /// Take only the first N rows from the batch.
     ///
+    /// The result shares this batch's column data (see `ColumnSlice`).
+    ///
     /// # Arguments
     ///
     /// * `take_count` - Number of rows to take
@@ -339,40 +982,179 @@ impl Batch {
             )));
         }
 
-        let mut new_columns = Vec::new();
-        for col in &self.columns {
-            let data_type = col.data_type();
-            let new_col: Arc<dyn Column> = match data_type {
-                DataType::Int64 => {
-                    let mut int_col = crate::column::IntColumn::new();
-                    for row_idx in 0..take_count {
-                        int_col.push_value(col.get(row_idx)?)?;
-                    }
-                    Arc::new(int_col)
-                }
-                DataType::Float64 => {
-                    let mut float_col = crate::column::FloatColumn::new();
-                    for row_idx in 0..take_count {
-                        float_col.push_value(col.get(row_idx)?)?;
-                    }
-                    Arc::new(float_col)
-                }
-                DataType::String => {
-                    let mut string_col = crate::column::StringColumn::new();
-                    for row_idx in 0..take_count {
-                        string_col.push_value(col.get(row_idx)?)?;
-                    }
-                    Arc::new(string_col)
-                }
-            };
-            new_columns.push(new_col);
-        }
+        self.slice_rows(0..take_count)
+    }
 
-        Ok(Batch::new(new_columns))
+    /// Keep the rows in `range` without copying any column data.
+    fn slice_rows(&self, range: std::ops::Range<usize>) -> Result<Batch> {
+        let columns = self
+            .columns
+            .iter()
+            .map(|col| slice_column(col, range.clone()))
+            .collect::<crate::error::Result<Vec<_>>>()?;
+        let mut batch = Batch::try_new(columns)?;
+        batch.schema = self.schema.clone();
+        Ok(batch)
     }
-}
 
-impl fmt::Debug for Batch {
+    /// Keep only the rows selected by a predicate.
+    ///
+    /// No values are copied: each column of the result is a
+    /// [`FilteredColumn`] view of the selected rows, and the columns that
+    /// look into the same rows share one row list. Filtering a view or a
+    /// slice selects from the column behind it, so views never nest.
+    /// Dictionary-encoded columns are gathered instead, which copies only
+    /// their codes and keeps the encoding. [`Batch::materialize`] packs
+    /// the rows into columns of their own.
+    ///
+    /// # Arguments
+    ///
+    /// * `selection` - One flag per row, as returned by `Predicate::eval_batch`
+    pub fn filter_rows(&self, selection: &[bool]) -> Result<Batch> {
+        if selection.len() != self.row_count() {
+            return Err(ExecutionError::Custom(format!(
+                "Selection has {} entries for a batch of {} rows",
+                selection.len(),
+                self.row_count()
+            )));
+        }
+
+        let selected: Vec<usize> = selection
+            .iter()
+            .enumerate()
+            .filter(|(_, &keep)| keep)
+            .map(|(row, _)| row)
+            .collect();
+        // The row lists built so far, by the rows they select from
+        let mut row_lists: Vec<(SelectedFrom, Arc<Vec<u32>>)> = Vec::new();
+
+        let mut new_columns = Vec::with_capacity(self.columns.len());
+        for col in &self.columns {
+            let (base, rows) = resolve_slice(col.as_ref());
+            if let Some(dict) = base.as_any().downcast_ref::<DictStringColumn>() {
+                count_copied_values(selected.len());
+                let rows = selected.iter().map(|&row| rows.row(row));
+                new_columns.push(Arc::new(dict.gather(rows)) as Arc<dyn Column>);
+                continue;
+            }
+
+            let any = col.as_any();
+            let (base, from) = if let Some(view) = any.downcast_ref::<FilteredColumn>() {
+                (view.column(), SelectedFrom::View(view.rows()))
+            } else if let Some(slice) = any.downcast_ref::<ColumnSlice>() {
+                (slice.column(), SelectedFrom::Offset(slice.range().start))
+            } else {
+                (col, SelectedFrom::Offset(0))
+            };
+            // Row lists hold u32 indices; a longer column is copied
+            if u32::try_from(base.len()).is_err() {
+                count_copied_values(selected.len());
+                let mut filtered = crate::column::create_column(col.data_type());
+                for rows in selected_runs(selection) {
+                    col.copy_range_into(rows, filtered.as_mut())?;
+                }
+                new_columns.push(filtered.into());
+                continue;
+            }
+
+            let rows = match row_lists.iter().find(|(other, _)| other.same_rows(&from)) {
+                Some((_, rows)) => Arc::clone(rows),
+                None => {
+                    let rows: Vec<u32> = match &from {
+                        SelectedFrom::View(rows) => selected.iter().map(|&row| rows[row]).collect(),
+                        SelectedFrom::Offset(offset) => {
+                            selected.iter().map(|&row| (offset + row) as u32).collect()
+                        }
+                    };
+                    let rows = Arc::new(rows);
+                    row_lists.push((from, Arc::clone(&rows)));
+                    rows
+                }
+            };
+            new_columns.push(Arc::new(FilteredColumn::new(Arc::clone(base), rows)?));
+        }
+
+        let mut batch = Batch::try_new(new_columns)?;
+        batch.schema = self.schema.clone();
+        Ok(batch)
+    }
+
+    /// Pack the rows of any [`FilteredColumn`] views into columns of their
+    /// own.
+    ///
+    /// A view keeps the whole column it looks into alive, so operators that
+    /// hold on to rows (Sort, the build side of a hash join) and query
+    /// results materialize what they keep. Other columns are shared as
+    /// they are.
+    pub fn materialize(self) -> Batch {
+        let columns = self
+            .columns
+            .into_iter()
+            .map(
+                |column| match column.as_any().downcast_ref::<FilteredColumn>() {
+                    Some(view) => {
+                        count_copied_values(view.len());
+                        Arc::from(view.clone_column())
+                    }
+                    None => column,
+                },
+            )
+            .collect();
+        Batch {
+            columns,
+            schema: self.schema,
+        }
+    }
+}
+
+/// The rows a column of a batch being filtered selects from: a view's row
+/// list, or the rows of a column from an offset on
+enum SelectedFrom<'a> {
+    View(&'a Arc<Vec<u32>>),
+    Offset(usize),
+}
+
+impl SelectedFrom<'_> {
+    /// Whether filtering both picks the same rows of their base columns
+    fn same_rows(&self, other: &SelectedFrom) -> bool {
+        match (self, other) {
+            (SelectedFrom::View(a), SelectedFrom::View(b)) => Arc::ptr_eq(a, b),
+            (SelectedFrom::Offset(a), SelectedFrom::Offset(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Values this thread copied into new columns while filtering or
+    /// materializing batches
+    static COPIED_VALUES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Records that `values` values were copied into new columns; tests read
+/// the count to check what a plan copies
+#[cfg(test)]
+fn count_copied_values(values: usize) {
+    COPIED_VALUES.with(|copied| copied.set(copied.get() + values));
+}
+
+#[cfg(not(test))]
+fn count_copied_values(_values: usize) {}
+
+/// Splits a selection into the ranges of consecutive selected rows, so they
+/// can be copied a range at a time.
+fn selected_runs(selection: &[bool]) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+    let mut row = 0;
+    std::iter::from_fn(move || {
+        let start = row + selection[row..].iter().position(|&keep| keep)?;
+        let len = selection[start..].iter().take_while(|&&keep| keep).count();
+        row = start + len;
+        Some(start..row)
+    })
+}
+
+impl fmt::Debug for Batch {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Batch")
             .field("row_count", &self.row_count())
@@ -381,6 +1163,227 @@ impl fmt::Debug for Batch {
     }
 }
 
+// ============================================================================
+// CANCELLATION
+// ============================================================================
+
+/// A flag that stops a running query, shared between the thread running the
+/// plan and whoever may cancel it (a Ctrl+C handler, a watchdog, ...).
+///
+/// Clones share the flag. Operators check the token between batches and
+/// fail with [`ExecutionError::Cancelled`] once it is set or its deadline,
+/// if any, has passed. A token is attached to a whole plan with
+/// [`Operator::set_cancellation`].
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::execution::{CancellationToken, ExecutionError};
+/// use std::time::Duration;
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+/// assert!(token.check().is_ok());
+///
+/// // From another thread, or a signal handler
+/// handle.cancel();
+/// assert!(matches!(token.check(), Err(ExecutionError::Cancelled)));
+///
+/// // A deadline cancels without anyone calling cancel()
+/// let timed = CancellationToken::new().with_timeout(Duration::ZERO);
+/// assert!(timed.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    /// Set once the query should stop
+    cancelled: Arc<AtomicBool>,
+    /// When the query stops on its own, if it has a timeout
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// Create a token that is not cancelled and has no deadline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the token once `timeout` has elapsed from now.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    /// Cancel the token and every clone of it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, AtomicOrdering::Relaxed);
+    }
+
+    /// Clear a cancellation, so the token (and its clones) can be reused
+    /// for the next query.
+    pub fn reset(&self) {
+        self.cancelled.store(false, AtomicOrdering::Relaxed);
+    }
+
+    /// Whether the token was cancelled or its deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(AtomicOrdering::Relaxed) || self.timed_out()
+    }
+
+    /// Whether the token's deadline has passed.
+    pub fn timed_out(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Fail with [`ExecutionError::Cancelled`] if the token is cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(ExecutionError::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// MEMORY ACCOUNTING
+// ============================================================================
+
+/// Counts the bytes a query's operators hold at once, and its peak.
+///
+/// Operators that buffer rows or groups across batches ([`Sort`],
+/// [`GroupBy`] and [`HashJoin`]) report their buffers as they grow and
+/// release them when closed. With a limit set, a request that would take
+/// the total over it fails with [`ExecutionError::ResourceExhausted`]
+/// instead of growing the process until it runs out of memory. The sizes
+/// are estimates, so the limit is a soft one. A tracker is attached to a
+/// whole plan with [`Operator::set_memory_tracker`].
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::execution::{ExecutionError, MemoryTracker};
+///
+/// let tracker = MemoryTracker::with_limit(1000);
+/// tracker.try_grow("Sort", 600).unwrap();
+/// tracker.shrink(600);
+/// tracker.try_grow("Sort", 800).unwrap();
+/// assert_eq!(tracker.peak(), 800);
+///
+/// let err = tracker.try_grow("GroupBy", 300).unwrap_err();
+/// assert!(matches!(err, ExecutionError::ResourceExhausted { requested: 1100, .. }));
+/// assert_eq!(tracker.used(), 800);
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryTracker {
+    /// Most bytes the query may hold, if limited
+    limit: Option<usize>,
+    /// Bytes held now
+    used: AtomicUsize,
+    /// Most bytes held at once
+    peak: AtomicUsize,
+}
+
+impl MemoryTracker {
+    /// Create a tracker without a limit, which only records the peak.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a tracker that refuses to let the query hold more than
+    /// `bytes`.
+    pub fn with_limit(bytes: usize) -> Self {
+        MemoryTracker {
+            limit: Some(bytes),
+            ..Self::default()
+        }
+    }
+
+    /// The limit, in bytes, if there is one.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Bytes held now.
+    pub fn used(&self) -> usize {
+        self.used.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Most bytes held at once since the tracker was created.
+    pub fn peak(&self) -> usize {
+        self.peak.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Count `bytes` more as held by `operator`, or fail with
+    /// [`ExecutionError::ResourceExhausted`], counting nothing, if that
+    /// would exceed the limit.
+    pub fn try_grow(&self, operator: &str, bytes: usize) -> Result<()> {
+        let used = self.used.fetch_add(bytes, AtomicOrdering::Relaxed) + bytes;
+        if let Some(limit) = self.limit.filter(|&limit| used > limit) {
+            self.used.fetch_sub(bytes, AtomicOrdering::Relaxed);
+            return Err(ExecutionError::ResourceExhausted {
+                operator: operator.to_string(),
+                requested: used,
+                limit,
+            });
+        }
+        self.peak.fetch_max(used, AtomicOrdering::Relaxed);
+        Ok(())
+    }
+
+    /// Count `bytes` as released.
+    pub fn shrink(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, AtomicOrdering::Relaxed);
+    }
+}
+
+/// The bytes one operator has counted against its query's
+/// [`MemoryTracker`], released when the operator closes or is dropped.
+#[derive(Debug, Default)]
+struct MemoryReservation {
+    tracker: Option<Arc<MemoryTracker>>,
+    bytes: usize,
+}
+
+impl MemoryReservation {
+    /// Count against `tracker` from now on.
+    fn set_tracker(&mut self, tracker: &Arc<MemoryTracker>) {
+        self.release();
+        self.tracker = Some(Arc::clone(tracker));
+    }
+
+    /// Hold `bytes` in total, growing or shrinking what is held.
+    fn resize(&mut self, operator: &str, bytes: usize) -> Result<()> {
+        if bytes > self.bytes {
+            if let Some(tracker) = &self.tracker {
+                tracker.try_grow(operator, bytes - self.bytes)?;
+            }
+        } else if let Some(tracker) = &self.tracker {
+            tracker.shrink(self.bytes - bytes);
+        }
+        self.bytes = bytes;
+        Ok(())
+    }
+
+    /// Hold `bytes` more.
+    fn grow(&mut self, operator: &str, bytes: usize) -> Result<()> {
+        self.resize(operator, self.bytes + bytes)
+    }
+
+    /// Hold nothing.
+    fn release(&mut self) {
+        if let Some(tracker) = &self.tracker {
+            tracker.shrink(self.bytes);
+        }
+        self.bytes = 0;
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
 /// Base trait for all query operators.
 ///
 /// Every query operator (Scan, Filter, Project, GroupBy, etc.) implements
@@ -393,15 +1396,29 @@ impl fmt::Debug for Batch {
 /// Operators can be chained together to form a query execution plan.
 /// For example: Scan → Filter → Project
 ///
+/// # Lifecycle
+///
+/// An operator moves through the states of [`OperatorState`]:
+///
+/// ```text
+/// NotOpen --open()--> Open --close()--> Closed --open()--> Open ...
+/// ```
+///
+/// `open()` on an open operator is an error, and `next_batch()` on one
+/// that is not open returns [`ExecutionError::OperatorNotOpen`]. `close()`
+/// may be called in any state. `open()` after `close()` starts over: the
+/// operator reopens its inputs and discards every position, buffer and
+/// cache left from the previous run, so each open/consume/close pass
+/// returns the same batches as the first. This is what lets a plan be
+/// run again, such as a prepared statement executed twice.
+///
 /// # Example
 ///
 /// ```rust
 /// use mini_rust_olap::execution::Operator;
-/// use mini_rust_olap::execution::ExecutionError;
 /// use mini_rust_olap::execution::Result;
+/// use mini_rust_olap::execution::{Field, Schema};
 /// use mini_rust_olap::types::DataType;
-/// use std::collections::HashMap;
-/// use std::sync::Arc;
 ///
 /// struct MyOperator {
 ///     // operator state
@@ -423,14 +1440,9 @@ impl fmt::Debug for Batch {
 ///         Ok(())
 ///     }
 ///
-///     fn schema(&self) -> Result<HashMap<String, DataType>> {
-///         // Return output schema
-///         Err(ExecutionError::Custom("Not implemented".into()))
-///     }
-///
-///     fn column_names(&self) -> Result<Vec<String>> {
-///         // Return column names in order
-///         Err(ExecutionError::Custom("Not implemented".into()))
+///     fn schema(&self) -> Result<Schema> {
+///         // Return the output columns in order
+///         Schema::new(vec![Field::new("id", DataType::Int64)])
 ///     }
 /// }
 /// ```
@@ -439,7 +1451,8 @@ pub trait Operator {
     ///
     /// Must be called before `next_batch()`. This is where operators
     /// typically open file handles, allocate buffers, or initialize
-    /// child operators.
+    /// child operators. After `close()`, it resets the operator to the
+    /// state of a fresh one (see the [lifecycle](Operator#lifecycle)).
     fn open(&mut self) -> Result<()>;
 
     /// Get the next batch of data from the operator.
@@ -462,32 +1475,87 @@ pub trait Operator {
     /// Release resources and cleanup.
     ///
     /// Must be called after processing is complete. Operators should
-    /// close file handles, free memory, and close child operators.
+    /// close file handles, free memory, and close child operators. Closing
+    /// an operator that is not open does nothing harmful.
     fn close(&mut self) -> Result<()>;
 
     /// Get the schema of the output data.
     ///
-    /// Returns the schema that will be produced by this operator.
-    /// The schema should be valid after `open()` is called.
-    fn schema(&self) -> Result<HashMap<String, DataType>>;
+    /// Returns the columns this operator produces, in the order they
+    /// appear in its batches. The schema should be valid after `open()`
+    /// is called.
+    fn schema(&self) -> Result<Schema>;
 
     /// Get column names in order.
     ///
     /// Returns a vector of column names in the order they appear in the output.
-    /// This is important for operators like Project that need to preserve column order.
-    fn column_names(&self) -> Result<Vec<String>>;
+    /// The names always come from `schema()`, so the two cannot disagree.
+    fn column_names(&self) -> Result<Vec<String>> {
+        Ok(self.schema()?.column_names())
+    }
 
     /// Check if the operator is currently open.
     fn is_open(&self) -> bool {
         false
     }
+
+    /// Stop this operator, and every operator below it, once `token` is
+    /// cancelled.
+    ///
+    /// Scans, filters, sorts, and groupings check the token between
+    /// batches; other operators only pass it on to their inputs. The
+    /// default does nothing, for operators that never run long.
+    fn set_cancellation(&mut self, _token: &CancellationToken) {}
+
+    /// Count the memory this operator, and every operator below it,
+    /// buffers against `tracker`.
+    ///
+    /// Sorts, groupings and hash joins report the rows and groups they hold
+    /// across batches; the default passes the tracker on to the operator's
+    /// inputs.
+    fn set_memory_tracker(&mut self, tracker: &Arc<MemoryTracker>) {
+        for child in self.children_mut() {
+            child.set_memory_tracker(tracker);
+        }
+    }
+
+    /// A one-line description of the operator for EXPLAIN ANALYZE, such as
+    /// `Filter(age > 30)`.
+    ///
+    /// The default is the operator's type name. Descriptions that name
+    /// columns read them from the schemas, so they are complete once the
+    /// operator has been opened.
+    fn describe(&self) -> String {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
+
+    /// The operators this one reads from, in order.
+    fn children(&self) -> Vec<&dyn Operator> {
+        Vec::new()
+    }
+
+    /// Mutable access to the operators this one reads from, so a plan can
+    /// be rebuilt with each of them wrapped (see [`ProfiledOperator`]).
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        Vec::new()
+    }
+
+    /// Runtime statistics, for operators that collect them.
+    fn stats(&self) -> Option<OperatorStats> {
+        None
+    }
 }
 
-/// State tracking for operator lifecycle
+/// State tracking for operator lifecycle; see [`Operator`] for the
+/// transitions between states
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OperatorState {
+    /// Created and never opened
     NotOpen,
+    /// Opened and producing batches
     Open,
+    /// Closed; `open()` starts a fresh run
     Closed,
 }
 
@@ -497,6 +1565,11 @@ pub enum OperatorState {
 /// it in columnar batches. It supports column pruning, which means it only
 /// reads the columns that are needed for the query.
 ///
+/// The table is held through an `Arc`, so scanning a catalog table shares
+/// it rather than copying its columns. open() takes a [`Table::snapshot`]
+/// of the columns and every batch is read from it, so the scan returns the
+/// rows the table had when it was opened, with every column the same length.
+///
 /// # Example
 ///
 /// ```ignore
@@ -517,16 +1590,20 @@ pub enum OperatorState {
 /// ```
 pub struct TableScan {
     /// The table to scan data from
-    table: Table,
+    table: Arc<Table>,
 
-    /// Indices of columns to read (column pruning)
-    /// If empty, read all columns
+    /// Indices of columns to read (column pruning); empty only for a
+    /// table without columns
     column_indices: Vec<usize>,
 
+    /// First row of the scanned range
+    start_row: usize,
+
     /// Current row position in the table
     current_row: usize,
 
-    /// Total number of rows in the table
+    /// End of the scanned range: the table's row count unless a row range
+    /// was given
     total_rows: usize,
 
     /// Number of rows to return per batch
@@ -535,8 +1612,25 @@ pub struct TableScan {
     /// Operator state
     state: OperatorState,
 
-    /// Cached output schema
-    output_schema: Option<HashMap<String, DataType>>,
+    /// Cached output schema, attached to every batch
+    output_schema: Option<Arc<Schema>>,
+
+    /// Name and type of each selected column, resolved once in open()
+    /// so next_batch() never has to look up the table's column list
+    resolved_columns: Vec<(String, DataType)>,
+
+    /// Comparison pushed down by the planner, over the selected columns,
+    /// used to skip blocks through the table's zone maps
+    pushed_predicate: Option<BinaryComparison>,
+
+    /// Number of zone map blocks skipped since open()
+    blocks_skipped: usize,
+
+    /// Checked before each batch is read
+    cancellation: CancellationToken,
+
+    /// The table's columns as of open()
+    snapshot: Option<Arc<ColumnSet>>,
 }
 
 impl TableScan {
@@ -544,7 +1638,8 @@ impl TableScan {
     ///
     /// # Arguments
     ///
-    /// * `table` - The table to scan
+    /// * `table` - The table to scan, either owned or an `Arc` shared with
+    ///   the catalog
     ///
     /// # Example
     ///
@@ -554,18 +1649,25 @@ impl TableScan {
     /// let table = Table::new("users".to_string());
     /// let scan = TableScan::new(table);
     /// ```
-    pub fn new(table: Table) -> Self {
+    pub fn new(table: impl Into<Arc<Table>>) -> Self {
+        let table = table.into();
         let column_count = table.column_count();
         let total_rows = table.row_count();
 
         TableScan {
             table,
             column_indices: (0..column_count).collect(),
+            start_row: 0,
             current_row: 0,
             total_rows,
             batch_size: 1024, // Default batch size
             state: OperatorState::NotOpen,
             output_schema: None,
+            resolved_columns: Vec::new(),
+            pushed_predicate: None,
+            blocks_skipped: 0,
+            cancellation: CancellationToken::default(),
+            snapshot: None,
         }
     }
 
@@ -576,9 +1678,17 @@ impl TableScan {
     ///
     /// # Arguments
     ///
-    /// * `table` - The table to scan
+    /// * `table` - The table to scan, either owned or an `Arc` shared with
+    ///   the catalog
     /// * `column_indices` - Indices of columns to read
     ///
+    /// # Errors
+    ///
+    /// `open()` fails with [`ExecutionError::EmptyProjection`] if
+    /// `column_indices` is empty while the table has columns, and with
+    /// [`ExecutionError::InvalidColumnIndex`] if it names a column the table
+    /// does not have; [`TableScan::new`] reads every column.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -588,17 +1698,24 @@ impl TableScan {
     /// // Only read columns 0 and 2 (e.g., id and age)
     /// let scan = TableScan::with_columns(table, vec![0, 2]);
     /// ```
-    pub fn with_columns(table: Table, column_indices: Vec<usize>) -> Self {
+    pub fn with_columns(table: impl Into<Arc<Table>>, column_indices: Vec<usize>) -> Self {
+        let table = table.into();
         let total_rows = table.row_count();
 
         TableScan {
             table,
             column_indices,
+            start_row: 0,
             current_row: 0,
             total_rows,
             batch_size: 1024,
             state: OperatorState::NotOpen,
             output_schema: None,
+            resolved_columns: Vec::new(),
+            pushed_predicate: None,
+            blocks_skipped: 0,
+            cancellation: CancellationToken::default(),
+            snapshot: None,
         }
     }
 
@@ -617,34 +1734,234 @@ impl TableScan {
         self.batch_size = batch_size;
         self
     }
-}
 
-impl Operator for TableScan {
-    fn open(&mut self) -> Result<()> {
-        if self.state == OperatorState::Open {
-            return Err(ExecutionError::OperatorAlreadyOpen);
+    /// Scan only the `count` rows starting at row `start`.
+    ///
+    /// The scan starts at `start` instead of reading and discarding the rows
+    /// before it, and stops after `count` rows or at the end of the table,
+    /// whichever comes first. A range starting past the end of the table
+    /// returns no rows. The planner uses this for LIMIT/OFFSET over a plain
+    /// scan; callers can use it to read a large table page by page.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Index of the first row to return
+    /// * `count` - Maximum number of rows to return
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_rust_olap::column::{Column, IntColumn};
+    /// # use mini_rust_olap::execution::{Operator, TableScan};
+    /// # use mini_rust_olap::table::Table;
+    /// # use mini_rust_olap::types::Value;
+    /// let mut column = IntColumn::new();
+    /// for n in 0..100 {
+    ///     column.push_value(Value::Int64(n)).unwrap();
+    /// }
+    /// let mut table = Table::new("numbers".to_string());
+    /// table.add_column("n".to_string(), Box::new(column)).unwrap();
+    ///
+    /// // The third page of 10 rows
+    /// let mut scan = TableScan::new(table).with_row_range(20, 10);
+    /// assert_eq!(scan.row_range(), 20..30);
+    /// scan.open().unwrap();
+    /// let page = scan.next_batch().unwrap().unwrap();
+    /// assert_eq!(page.row_count(), 10);
+    /// assert_eq!(page.get(0, 0).unwrap(), Value::Int64(20));
+    /// assert!(scan.next_batch().unwrap().is_none());
+    /// ```
+    pub fn with_row_range(mut self, start: usize, count: usize) -> Self {
+        let row_count = self.table.row_count();
+        self.start_row = start.min(row_count);
+        self.current_row = self.start_row;
+        self.total_rows = start.saturating_add(count).min(row_count);
+        self
+    }
+
+    /// The range of table rows this scan reads.
+    pub fn row_range(&self) -> std::ops::Range<usize> {
+        self.start_row..self.total_rows
+    }
+
+    /// Skip blocks of rows that cannot satisfy a comparison.
+    ///
+    /// The scan consults the zone map of the compared column and skips every
+    /// block whose min/max range rules the comparison out. Blocks are only
+    /// skipped whole, so the batches it does return still contain rows that
+    /// fail the comparison; a Filter above the scan must still apply it.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A comparison whose column index refers to the
+    ///   selected columns, like a predicate of a Filter over this scan
+    pub fn with_pushed_predicate(mut self, predicate: BinaryComparison) -> Self {
+        self.pushed_predicate = Some(predicate);
+        self
+    }
+
+    /// Number of zone map blocks skipped since the scan was opened.
+    pub fn blocks_skipped(&self) -> usize {
+        self.blocks_skipped
+    }
+
+    /// Move past the blocks starting at the current row that the pushed
+    /// predicate rules out.
+    fn skip_excluded_blocks(&mut self) {
+        let Some(predicate) = &self.pushed_predicate else {
+            return;
+        };
+        let Some((_, data_type)) = self.resolved_columns.get(predicate.column_index) else {
+            return;
+        };
+        let Some(zone_map) = self.snapshot.as_ref().and_then(|snapshot| {
+            snapshot.zone_map(*self.column_indices.get(predicate.column_index)?)
+        }) else {
+            return;
+        };
+
+        let block_size = zone_map.block_size();
+        while self.current_row < self.total_rows {
+            let block = self.current_row / block_size;
+            match zone_map.zones().get(block) {
+                Some(zone)
+                    if !zone_may_match(zone, *data_type, &predicate.op, &predicate.value) =>
+                {
+                    self.current_row = ((block + 1) * block_size).min(self.total_rows);
+                    self.blocks_skipped += 1;
+                }
+                _ => break,
+            }
         }
+    }
+}
+
+/// Whether any row summarised by a zone could satisfy `column op literal`.
+///
+/// The bounds are compared with the same type-specialised comparison the
+/// Filter applies to rows, so a block is only ruled out if the Filter would
+/// reject every row in it. Comparisons that cannot be resolved never rule a
+/// block out; the Filter reports them.
+fn zone_may_match(zone: &Zone, column_type: DataType, op: &ComparisonOp, literal: &Value) -> bool {
+    let (min, max) = match zone {
+        // Comparisons against NULL are never true
+        Zone::AllNull => return false,
+        Zone::Unordered => return true,
+        Zone::Range { min, max } => (min, max),
+    };
+    range_may_match(min, max, column_type, op, literal).unwrap_or(true)
+}
 
-        // Build output schema
-        let mut schema = HashMap::new();
-        let column_names = self.table.column_names();
+/// Whether a value in `min..=max` could satisfy `column op literal`.
+fn range_may_match(
+    min: &Value,
+    max: &Value,
+    column_type: DataType,
+    op: &ComparisonOp,
+    literal: &Value,
+) -> Result<bool> {
+    let test = |op: ComparisonOp, bound: &Value| {
+        Comparator::resolve(column_type, &op, literal)?.matches(bound)
+    };
+    Ok(match op {
+        ComparisonOp::Equal => {
+            test(ComparisonOp::LessThanOrEqual, min)?
+                && test(ComparisonOp::GreaterThanOrEqual, max)?
+        }
+        // Only a block holding nothing but the literal is ruled out
+        ComparisonOp::NotEqual => {
+            !(test(ComparisonOp::Equal, min)? && test(ComparisonOp::Equal, max)?)
+        }
+        ComparisonOp::LessThan => test(ComparisonOp::LessThan, min)?,
+        ComparisonOp::LessThanOrEqual => test(ComparisonOp::LessThanOrEqual, min)?,
+        ComparisonOp::GreaterThan => test(ComparisonOp::GreaterThan, max)?,
+        ComparisonOp::GreaterThanOrEqual => test(ComparisonOp::GreaterThanOrEqual, max)?,
+    })
+}
 
-        for &col_idx in &self.column_indices {
-            if col_idx >= column_names.len() {
-                return Err(ExecutionError::InvalidColumnIndex {
+/// Resolve the name and type of each selected table column.
+///
+/// A scan of a table with columns must select at least one of them: a batch
+/// cannot be built without columns, and reading every column is what
+/// `TableScan::new` is for.
+fn resolve_scan_columns(
+    table: &Table,
+    column_indices: &[usize],
+) -> Result<Vec<(String, DataType)>> {
+    if column_indices.is_empty() && table.column_count() > 0 {
+        return Err(no_scan_columns(table.name()));
+    }
+    let column_names = table.column_names();
+    column_indices
+        .iter()
+        .map(|&col_idx| {
+            let col_name = column_names
+                .get(col_idx)
+                .ok_or(ExecutionError::InvalidColumnIndex {
                     index: col_idx,
                     count: column_names.len(),
-                });
-            }
-            let col_name = &column_names[col_idx];
-            let data_type = self
-                .table
+                })?;
+            let data_type = table
                 .get_column_type(col_name)
-                .map_err(|e| ExecutionError::Custom(e.to_string()))?;
-            schema.insert(col_name.clone(), data_type);
+                .map_err(|_| ExecutionError::ColumnNotFound(col_name.clone()))?;
+            Ok((col_name.clone(), data_type))
+        })
+        .collect()
+}
+
+/// The error for a scan of `table` that selects no columns.
+fn no_scan_columns(table: &str) -> ExecutionError {
+    ExecutionError::EmptyProjection {
+        table: table.to_string(),
+    }
+}
+
+/// Build a batch of the rows `start_row..end_row` of the selected columns
+/// of a table snapshot.
+///
+/// The batch's columns are slices of the table's columns, so no values are
+/// copied however large the table is.
+fn scan_table_rows(
+    snapshot: &ColumnSet,
+    column_indices: &[usize],
+    start_row: usize,
+    end_row: usize,
+) -> Result<Batch> {
+    let mut batch_columns = Vec::with_capacity(column_indices.len());
+
+    for &index in column_indices {
+        let column = snapshot
+            .column(index)
+            .ok_or(ExecutionError::InvalidColumnIndex {
+                index,
+                count: snapshot.len(),
+            })?;
+        batch_columns.push(slice_column(column, start_row..end_row)?);
+    }
+
+    Batch::try_new(batch_columns)
+}
+
+impl Operator for TableScan {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.cancellation = token.clone();
+    }
+
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
         }
 
-        self.output_schema = Some(schema);
+        // Resolve the selected columns and build the output schema
+        let resolved_columns = resolve_scan_columns(&self.table, &self.column_indices)?;
+
+        self.output_schema = Some(Arc::new(Schema::from_columns(&resolved_columns)?));
+        self.resolved_columns = resolved_columns;
+        let snapshot = self.table.snapshot();
+        self.total_rows = self.total_rows.min(snapshot.row_count());
+        self.snapshot = Some(snapshot);
+        self.current_row = self.start_row;
+        self.blocks_skipped = 0;
         self.state = OperatorState::Open;
 
         Ok(())
@@ -655,6 +1972,9 @@ impl Operator for TableScan {
             return Err(ExecutionError::OperatorNotOpen);
         }
 
+        self.cancellation.check()?;
+        self.skip_excluded_blocks();
+
         // Check if we've read all rows
         if self.current_row >= self.total_rows {
             return Ok(None);
@@ -664,43 +1984,21 @@ impl Operator for TableScan {
         let remaining_rows = self.total_rows - self.current_row;
         let batch_rows = self.batch_size.min(remaining_rows);
 
-        // Build the batch columns
-        let mut batch_columns = Vec::new();
-
-        for &col_idx in &self.column_indices {
-            let column_names = self.table.column_names();
-            let col_name = &column_names[col_idx];
-
-            // Get the column and slice it for this batch
-            let column = self
-                .table
-                .get_column(col_name)
-                .map_err(|e| ExecutionError::Custom(e.to_string()))?;
-
-            let start_row = self.current_row;
-            let end_row = start_row + batch_rows;
-
-            // Get the sliced values and convert them back into a Column
-            let values = column.slice(Some(start_row..end_row));
-
-            // Create a new column of the appropriate type
-            let data_type = self
-                .table
-                .get_column_type(col_name)
-                .map_err(|e| ExecutionError::Custom(e.to_string()))?;
-
-            let mut batch_column = crate::column::create_column(data_type);
-            for value in values {
-                batch_column
-                    .push_value(value)
-                    .map_err(|e| ExecutionError::Custom(e.to_string()))?;
-            }
-
-            batch_columns.push(batch_column.into());
-        }
-
-        // Create the batch
-        let batch = Batch::new(batch_columns);
+        let schema = self
+            .output_schema
+            .clone()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+        let snapshot = self
+            .snapshot
+            .as_ref()
+            .ok_or(ExecutionError::OperatorNotOpen)?;
+        let batch = scan_table_rows(
+            snapshot,
+            &self.column_indices,
+            self.current_row,
+            self.current_row + batch_rows,
+        )?
+        .with_schema(schema)?;
 
         // Advance the row position
         self.current_row += batch_rows;
@@ -709,40 +2007,250 @@ impl Operator for TableScan {
     }
 
     fn close(&mut self) -> Result<()> {
+        self.snapshot = None;
         self.state = OperatorState::Closed;
         Ok(())
     }
 
-    fn schema(&self) -> Result<HashMap<String, DataType>> {
+    fn schema(&self) -> Result<Schema> {
         self.output_schema
-            .clone()
+            .as_deref()
+            .cloned()
             .ok_or(ExecutionError::SchemaNotFound)
     }
 
-    fn column_names(&self) -> Result<Vec<String>> {
-        if self.column_indices.is_empty() {
-            // No column pruning, return all column names in order
-            Ok(self.table.column_names())
-        } else {
-            // Return selected column names in the specified order
-            let all_names = self.table.column_names();
-            let mut selected_names = Vec::new();
-            for &index in &self.column_indices {
-                if index >= all_names.len() {
-                    return Err(ExecutionError::InvalidColumnIndex {
-                        index,
-                        count: all_names.len(),
-                    });
-                }
-                selected_names.push(all_names[index].clone());
-            }
-            Ok(selected_names)
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
+
+    fn describe(&self) -> String {
+        let names = self.table.column_names();
+        let columns: Vec<String> = self
+            .column_indices
+            .iter()
+            .map(|&index| column_label(&names, index))
+            .collect();
+        let mut description = format!("TableScan({}: {}", self.table.name(), columns.join(", "));
+        if self.start_row > 0 || self.total_rows < self.table.row_count() {
+            description.push_str(&format!(", rows {}..{}", self.start_row, self.total_rows));
+        }
+        description.push(')');
+        description
+    }
+}
+
+/// VirtualScan reads rows from a closure-backed virtual table.
+///
+/// Each `open()` calls the table's factory for a fresh snapshot, and
+/// `next_batch()` pulls up to `batch_size` rows from the resulting iterator,
+/// converting them into columns lazily. The total row count is unknown until
+/// the iterator is exhausted.
+///
+/// # Example
+///
+/// ```rust
+/// # use mini_rust_olap::execution::{Operator, VirtualScan};
+/// # use mini_rust_olap::types::{DataType, Value};
+/// # use mini_rust_olap::virtual_table::VirtualTable;
+/// let table = VirtualTable::new(
+///     "numbers".to_string(),
+///     vec![("n".to_string(), DataType::Int64)],
+///     || Box::new((0..10).map(|n| vec![Value::Int64(n)])),
+/// )
+/// .unwrap();
+///
+/// let mut scan = VirtualScan::new(table).with_batch_size(4);
+/// scan.open().unwrap();
+/// let batch = scan.next_batch().unwrap().unwrap();
+/// assert_eq!(batch.row_count(), 4);
+/// scan.close().unwrap();
+/// ```
+pub struct VirtualScan {
+    /// The virtual table to scan
+    table: VirtualTable,
+
+    /// Indices of columns to output (column pruning)
+    column_indices: Vec<usize>,
+
+    /// Number of rows to return per batch
+    batch_size: usize,
+
+    /// Row iterator for the current snapshot, created in open()
+    rows: Option<RowIterator>,
+
+    /// Schema of the selected columns, resolved in open()
+    output_schema: Option<Arc<Schema>>,
+
+    /// Operator state
+    state: OperatorState,
+}
+
+impl VirtualScan {
+    /// Create a new VirtualScan that outputs all columns of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The virtual table to scan
+    pub fn new(table: VirtualTable) -> Self {
+        let column_count = table.column_count();
+        Self::with_columns(table, (0..column_count).collect())
+    }
+
+    /// Create a new VirtualScan that outputs only specific columns.
+    ///
+    /// Rows are still produced in full by the factory; the other values
+    /// are dropped before they are converted into columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The virtual table to scan
+    /// * `column_indices` - Indices of columns to output
+    pub fn with_columns(table: VirtualTable, column_indices: Vec<usize>) -> Self {
+        VirtualScan {
+            table,
+            column_indices,
+            batch_size: 1024,
+            rows: None,
+            output_schema: None,
+            state: OperatorState::NotOpen,
+        }
+    }
+
+    /// Set the batch size for this scan.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - Number of rows per batch (must be > 0)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        if batch_size == 0 {
+            panic!("Batch size must be greater than 0");
+        }
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// The selected (name, type) pairs, in output order.
+    fn selected_columns(&self) -> Result<Vec<(String, DataType)>> {
+        if self.column_indices.is_empty() && self.table.column_count() > 0 {
+            return Err(no_scan_columns(self.table.name()));
+        }
+        let columns = self.table.columns();
+        self.column_indices
+            .iter()
+            .map(|&index| {
+                columns
+                    .get(index)
+                    .cloned()
+                    .ok_or(ExecutionError::InvalidColumnIndex {
+                        index,
+                        count: columns.len(),
+                    })
+            })
+            .collect()
+    }
+}
+
+impl Operator for VirtualScan {
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
+        }
+
+        // Validate the pruned columns before pulling any rows
+        let schema = Schema::from_columns(&self.selected_columns()?)?;
+        self.output_schema = Some(Arc::new(schema));
+
+        self.rows = Some(self.table.rows());
+        self.state = OperatorState::Open;
+        Ok(())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::OperatorNotOpen);
+        }
+
+        let rows = self.rows.as_mut().ok_or(ExecutionError::OperatorNotOpen)?;
+        let expected_width = self.table.column_count();
+
+        let mut batch_columns: Vec<Box<dyn Column>> = self
+            .column_indices
+            .iter()
+            .map(|&index| crate::column::create_column(self.table.columns()[index].1))
+            .collect();
+        let mut row_count = 0;
+
+        while row_count < self.batch_size {
+            let Some(row) = rows.next() else {
+                break;
+            };
+            if row.len() != expected_width {
+                return Err(ExecutionError::SchemaMismatch(format!(
+                    "virtual table '{}' produced a row with {} values, expected {}",
+                    self.table.name(),
+                    row.len(),
+                    expected_width
+                )));
+            }
+
+            for (column, &index) in batch_columns.iter_mut().zip(&self.column_indices) {
+                let value = &row[index];
+                column.push_value(value.clone()).map_err(|e| {
+                    let (name, data_type) = &self.table.columns()[index];
+                    match value.data_type() {
+                        Some(actual) => ExecutionError::TypeMismatch {
+                            expected: *data_type,
+                            actual,
+                            column: Some(format!("{}.{}", self.table.name(), name)),
+                        },
+                        None => e.into(),
+                    }
+                })?;
+            }
+            row_count += 1;
+        }
+
+        if row_count == 0 {
+            return Ok(None);
+        }
+
+        let schema = self
+            .output_schema
+            .clone()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+        let batch = Batch::try_new(batch_columns.into_iter().map(Arc::from).collect())?;
+        Ok(Some(batch.with_schema(schema)?))
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.rows = None;
+        self.state = OperatorState::Closed;
+        Ok(())
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::SchemaNotFound);
         }
+        self.output_schema
+            .as_deref()
+            .cloned()
+            .ok_or(ExecutionError::SchemaNotFound)
     }
 
     fn is_open(&self) -> bool {
         self.state == OperatorState::Open
     }
+
+    fn describe(&self) -> String {
+        let names = self.table.column_names();
+        let columns: Vec<String> = self
+            .column_indices
+            .iter()
+            .map(|&index| column_label(&names, index))
+            .collect();
+        format!("VirtualScan({}: {})", self.table.name(), columns.join(", "))
+    }
 }
 
 // ============================================================================
@@ -778,6 +2286,54 @@ pub trait Predicate: Send + Sync + std::fmt::Debug {
     ///
     /// `Ok(true)` if the row matches the predicate, `Ok(false)` otherwise
     fn eval(&self, batch: &Batch, row_index: usize) -> Result<bool>;
+
+    /// Resolve the predicate against the input column types.
+    ///
+    /// Called once by `Filter::open`. Implementations do their type dispatch
+    /// here so that `eval` does not have to repeat it for every row.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_types` - Data types of the input columns, in order
+    ///
+    /// # Returns
+    ///
+    /// A predicate equivalent to `self` specialised for those types, or an
+    /// error if the predicate cannot be evaluated against them
+    fn bind(&self, column_types: &[DataType]) -> Result<Arc<dyn Predicate>>;
+
+    /// Format the predicate as SQL for EXPLAIN ANALYZE, e.g. `age > 30`.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_names` - Names of the input columns, in order
+    fn describe(&self, _column_names: &[String]) -> String {
+        "<predicate>".to_string()
+    }
+
+    /// Evaluate the predicate on every row of a batch at once.
+    ///
+    /// The default implementation calls `eval` row by row. Predicates that
+    /// can work on the typed column storage directly override it so that no
+    /// `Value` is built per cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - The batch to evaluate
+    ///
+    /// # Returns
+    ///
+    /// One flag per row, `true` where the row matches the predicate
+    fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+        eval_each_row(self, batch)
+    }
+}
+
+/// Row-by-row fallback shared by `Predicate::eval_batch` and its overrides.
+fn eval_each_row<P: Predicate + ?Sized>(predicate: &P, batch: &Batch) -> Result<Vec<bool>> {
+    (0..batch.row_count())
+        .map(|row| predicate.eval(batch, row))
+        .collect()
 }
 
 /// Comparison operators for predicates
@@ -791,6 +2347,20 @@ pub enum ComparisonOp {
     GreaterThanOrEqual,
 }
 
+impl ComparisonOp {
+    /// The comparison as a plain function over one operand type.
+    fn test_fn<T: PartialOrd + ?Sized>(&self) -> fn(&T, &T) -> bool {
+        match self {
+            ComparisonOp::Equal => |a, b| a == b,
+            ComparisonOp::NotEqual => |a, b| a != b,
+            ComparisonOp::LessThan => |a, b| a < b,
+            ComparisonOp::LessThanOrEqual => |a, b| a <= b,
+            ComparisonOp::GreaterThan => |a, b| a > b,
+            ComparisonOp::GreaterThanOrEqual => |a, b| a >= b,
+        }
+    }
+}
+
 impl std::fmt::Display for ComparisonOp {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -826,6 +2396,8 @@ pub struct BinaryComparison {
     op: ComparisonOp,
     /// The constant value to compare against
     value: Value,
+    /// Type-specialised comparison, set once the predicate is bound
+    comparator: Option<Comparator>,
 }
 
 impl BinaryComparison {
@@ -841,146 +2413,606 @@ impl BinaryComparison {
             column_index,
             op,
             value,
+            comparator: None,
         }
     }
+
+    /// The index of the compared column.
+    pub fn column_index(&self) -> usize {
+        self.column_index
+    }
+
+    /// The comparison operator.
+    pub fn op(&self) -> &ComparisonOp {
+        &self.op
+    }
+
+    /// The constant the column is compared against.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
 }
 
 impl Predicate for BinaryComparison {
     fn eval(&self, batch: &Batch, row_index: usize) -> Result<bool> {
         let actual = batch.get(row_index, self.column_index)?;
 
-        match (&self.op, &actual, &self.value) {
-            // Equal
-            (ComparisonOp::Equal, Value::Int64(a), Value::Int64(b)) => Ok(a == b),
-            (ComparisonOp::Equal, Value::Float64(a), Value::Float64(b)) => Ok(a == b),
-            (ComparisonOp::Equal, Value::String(a), Value::String(b)) => Ok(a == b),
+        match (&self.comparator, actual.data_type()) {
+            (Some(comparator), _) => comparator.matches(&actual),
+            // Unbound: resolve from the value itself (slow path, used when
+            // the predicate is evaluated outside a Filter)
+            (None, Some(actual_type)) => {
+                Comparator::resolve(actual_type, &self.op, &self.value)?.matches(&actual)
+            }
+            // Comparisons against NULL are never true
+            (None, None) => Ok(false),
+        }
+    }
+
+    fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+        if let Some(comparator) = &self.comparator {
+            let column = batch.column(self.column_index)?;
+            if let Some(selection) = comparator.matches_column(column.as_ref()) {
+                return Ok(selection);
+            }
+        }
+        eval_each_row(self, batch)
+    }
+
+    fn bind(&self, column_types: &[DataType]) -> Result<Arc<dyn Predicate>> {
+        let column_type =
+            column_types
+                .get(self.column_index)
+                .ok_or(ExecutionError::InvalidColumnIndex {
+                    index: self.column_index,
+                    count: column_types.len(),
+                })?;
+
+        Ok(Arc::new(BinaryComparison {
+            comparator: Some(Comparator::resolve(*column_type, &self.op, &self.value)?),
+            ..self.clone()
+        }))
+    }
+
+    fn describe(&self, column_names: &[String]) -> String {
+        format!(
+            "{} {} {}",
+            column_label(column_names, self.column_index),
+            self.op,
+            sql_literal(&self.value)
+        )
+    }
+}
 
-            // NotEqual
-            (ComparisonOp::NotEqual, Value::Int64(a), Value::Int64(b)) => Ok(a != b),
-            (ComparisonOp::NotEqual, Value::Float64(a), Value::Float64(b)) => Ok(a != b),
-            (ComparisonOp::NotEqual, Value::String(a), Value::String(b)) => Ok(a != b),
+/// A comparison specialised to one (column type, literal type) pair.
+///
+/// Resolved once by `BinaryComparison::bind`, so evaluating a row is a single
+/// type check followed by a call through a monomorphic function pointer.
+/// Int64 and Float64 are compared numerically in both directions; String and
+/// Boolean only compare with their own type, and any other pair is rejected
+/// when resolving. Following
+/// SQL, a comparison with NULL on either side is never true.
+#[derive(Debug, Clone)]
+enum Comparator {
+    /// Any column against a NULL literal; matches nothing
+    Null,
+    /// Int64 column against an Int64 literal
+    Int64 {
+        test: fn(&i64, &i64) -> bool,
+        literal: i64,
+    },
+    /// Int64 column against a Float64 literal (column widened to f64)
+    Int64AsFloat64 {
+        test: fn(&f64, &f64) -> bool,
+        literal: f64,
+    },
+    /// Float64 column against a Float64 or Int64 literal
+    Float64 {
+        test: fn(&f64, &f64) -> bool,
+        literal: f64,
+    },
+    /// String column against a String literal
+    String {
+        test: fn(&str, &str) -> bool,
+        literal: String,
+    },
+    /// Boolean column against a Boolean literal (FALSE sorts before TRUE)
+    Boolean {
+        test: fn(&bool, &bool) -> bool,
+        literal: bool,
+    },
+    /// Date column against a Date literal or an ISO `YYYY-MM-DD` string
+    Date {
+        test: fn(&i32, &i32) -> bool,
+        literal: i32,
+    },
+}
 
-            // LessThan
-            (ComparisonOp::LessThan, Value::Int64(a), Value::Int64(b)) => Ok(a < b),
-            (ComparisonOp::LessThan, Value::Float64(a), Value::Float64(b)) => Ok(a < b),
-            // LessThan with type conversion (Int64 <-> Float64)
-            (ComparisonOp::LessThan, Value::Float64(a), Value::Int64(b)) => Ok(*a < (*b as f64)),
-            (ComparisonOp::LessThan, Value::Int64(a), Value::Float64(b)) => Ok((*a as f64) < *b),
+impl Comparator {
+    /// Pick the comparator for a column type and literal, pre-converting the
+    /// literal so no per-row coercion is needed.
+    fn resolve(column_type: DataType, op: &ComparisonOp, literal: &Value) -> Result<Self> {
+        match (column_type, literal) {
+            (_, Value::Null) => Ok(Comparator::Null),
+            (DataType::Int64, Value::Int64(b)) => Ok(Comparator::Int64 {
+                test: op.test_fn(),
+                literal: *b,
+            }),
+            (DataType::Int64, Value::Float64(b)) => Ok(Comparator::Int64AsFloat64 {
+                test: op.test_fn(),
+                literal: *b,
+            }),
+            (DataType::Float64, Value::Float64(b)) => Ok(Comparator::Float64 {
+                test: op.test_fn(),
+                literal: *b,
+            }),
+            (DataType::Float64, Value::Int64(b)) => Ok(Comparator::Float64 {
+                test: op.test_fn(),
+                literal: *b as f64,
+            }),
+            (DataType::String, Value::String(b)) => Ok(Comparator::String {
+                test: op.test_fn(),
+                literal: b.clone(),
+            }),
+            (DataType::Boolean, Value::Boolean(b)) => Ok(Comparator::Boolean {
+                test: op.test_fn(),
+                literal: *b,
+            }),
+            (DataType::Date, Value::Date(b)) => Ok(Comparator::Date {
+                test: op.test_fn(),
+                literal: *b,
+            }),
+            (DataType::Date, Value::String(b)) => match parse_date(b) {
+                Some(days) => Ok(Comparator::Date {
+                    test: op.test_fn(),
+                    literal: days,
+                }),
+                None => Err(ExecutionError::SchemaMismatch(format!(
+                    "cannot compare Date column with '{}': expected a YYYY-MM-DD date",
+                    b
+                ))),
+            },
+            _ => Err(ExecutionError::SchemaMismatch(format!(
+                "cannot compare {} column with {} literal {} using '{}'",
+                column_type,
+                literal.type_name(),
+                literal,
+                op
+            ))),
+        }
+    }
 
-            // LessThanOrEqual
-            (ComparisonOp::LessThanOrEqual, Value::Int64(a), Value::Int64(b)) => Ok(a <= b),
-            (ComparisonOp::LessThanOrEqual, Value::Float64(a), Value::Float64(b)) => Ok(a <= b),
-            // LessThanOrEqual with type conversion (Int64 <-> Float64)
-            (ComparisonOp::LessThanOrEqual, Value::Float64(a), Value::Int64(b)) => {
-                Ok(*a <= (*b as f64))
+    /// Apply the comparison to a single column value.
+    fn matches(&self, actual: &Value) -> Result<bool> {
+        match (self, actual) {
+            (Comparator::Null, _) | (_, Value::Null) => Ok(false),
+            (Comparator::Int64 { test, literal }, Value::Int64(a)) => Ok(test(a, literal)),
+            (Comparator::Int64AsFloat64 { test, literal }, Value::Int64(a)) => {
+                Ok(test(&(*a as f64), literal))
             }
-            (ComparisonOp::LessThanOrEqual, Value::Int64(a), Value::Float64(b)) => {
-                Ok((*a as f64) <= *b)
+            (Comparator::Float64 { test, literal }, Value::Float64(a)) => Ok(test(a, literal)),
+            (Comparator::String { test, literal }, Value::String(a)) => {
+                Ok(test(a.as_str(), literal.as_str()))
             }
+            (Comparator::Boolean { test, literal }, Value::Boolean(a)) => Ok(test(a, literal)),
+            (Comparator::Date { test, literal }, Value::Date(a)) => Ok(test(a, literal)),
+            _ => Err(ExecutionError::SchemaMismatch(format!(
+                "comparison bound to a different column type than value {}",
+                actual
+            ))),
+        }
+    }
 
-            // GreaterThan
-            (ComparisonOp::GreaterThan, Value::Int64(a), Value::Int64(b)) => Ok(a > b),
-            (ComparisonOp::GreaterThan, Value::Float64(a), Value::Float64(b)) => Ok(a > b),
-            // GreaterThan with type conversion (Int64 <-> Float64)
-            (ComparisonOp::GreaterThan, Value::Float64(a), Value::Int64(b)) => Ok(*a > (*b as f64)),
-            (ComparisonOp::GreaterThan, Value::Int64(a), Value::Float64(b)) => Ok((*a as f64) > *b),
-
-            // GreaterThanOrEqual
-            (ComparisonOp::GreaterThanOrEqual, Value::Int64(a), Value::Int64(b)) => Ok(a >= b),
-            (ComparisonOp::GreaterThanOrEqual, Value::Float64(a), Value::Float64(b)) => Ok(a >= b),
-            // GreaterThanOrEqual with type conversion (Int64 <-> Float64)
-            (ComparisonOp::GreaterThanOrEqual, Value::Float64(a), Value::Int64(b)) => {
-                Ok(*a >= (*b as f64))
+    /// Apply the comparison to a whole column, reading the typed storage
+    /// directly instead of extracting a `Value` per row. NULL rows never
+    /// match.
+    ///
+    /// Returns `None` when the column has no vectorized path (Boolean
+    /// columns, or a column that is not one of the built-in types); callers
+    /// then fall back to `matches`.
+    fn matches_column(&self, column: &dyn Column) -> Option<Vec<bool>> {
+        // A slice or filtered view is read as its rows of the underlying
+        // typed storage
+        let (column, rows) = resolve_slice(column);
+        let any = column.as_any();
+        let mut selection: Vec<bool> = match self {
+            Comparator::Null => return Some(vec![false; rows.len()]),
+            Comparator::Int64 { test, literal } => any
+                .downcast_ref::<IntColumn>()?
+                .values_in(rows.clone())
+                .map(|a| test(a, literal))
+                .collect(),
+            Comparator::Int64AsFloat64 { test, literal } => any
+                .downcast_ref::<IntColumn>()?
+                .values_in(rows.clone())
+                .map(|a| test(&(*a as f64), literal))
+                .collect(),
+            Comparator::Float64 { test, literal } => any
+                .downcast_ref::<FloatColumn>()?
+                .values_in(rows.clone())
+                .map(|a| test(a, literal))
+                .collect(),
+            Comparator::String { test, literal } => {
+                if let Some(dict) = any.downcast_ref::<DictStringColumn>() {
+                    // Test each distinct value once, then look rows up by code
+                    let by_code: Vec<bool> = dict
+                        .dictionary()
+                        .iter()
+                        .map(|a| test(a.as_str(), literal.as_str()))
+                        .collect();
+                    let codes = dict.codes();
+                    rows.iter()
+                        .map(|row| by_code.get(codes[row] as usize).copied().unwrap_or(false))
+                        .collect()
+                } else {
+                    any.downcast_ref::<StringColumn>()?
+                        .values_in(rows.clone())
+                        .map(|a| test(a.as_str(), literal.as_str()))
+                        .collect()
+                }
             }
-            (ComparisonOp::GreaterThanOrEqual, Value::Int64(a), Value::Float64(b)) => {
-                Ok((*a as f64) >= *b)
+            Comparator::Date { test, literal } => {
+                let dates = any.downcast_ref::<DateColumn>()?.as_vec();
+                rows.iter().map(|row| test(&dates[row], literal)).collect()
             }
+            Comparator::Boolean { .. } => return None,
+        };
 
-            // Type mismatch - for now, return false
-            _ => Ok(false),
+        // NULL rows hold a placeholder in the typed storage; clear them
+        if column.null_count() > 0 {
+            for (row, selected) in rows.iter().zip(selection.iter_mut()) {
+                if *selected && column.is_null(row) {
+                    *selected = false;
+                }
+            }
         }
+        Some(selection)
     }
 }
 
-/// Logical AND predicate: both sub-predicates must be true.
+/// List membership predicate: the column value equals one of the constants.
+///
+/// Members are matched with the same coercions as `=` in `BinaryComparison`,
+/// so an Int64 column matches Float64 members numerically and vice versa.
+/// A String member against a numeric column (or the reverse) is an error
+/// when the predicate is bound. As in SQL, a NULL column value satisfies
+/// neither IN nor NOT IN, and NOT IN is never true when the list contains
+/// NULL.
 ///
 /// # Example
 ///
 /// ```rust
-/// # use mini_rust_olap::execution::And;
-/// # use mini_rust_olap::execution::BinaryComparison;
-/// # use mini_rust_olap::execution::ComparisonOp;
+/// # use mini_rust_olap::execution::InList;
 /// # use mini_rust_olap::types::Value;
-/// use std::sync::Arc;
+/// // Create: category IN ('Laptop', 'Phone')
+/// let predicate = InList::new(
+///     1,
+///     vec![Value::String("Laptop".into()), Value::String("Phone".into())],
+/// );
 ///
-/// // Create: age > 25 AND age < 50
-/// let pred1 = BinaryComparison::new(2, ComparisonOp::GreaterThan, Value::Float64(25.0));
-/// let pred2 = BinaryComparison::new(2, ComparisonOp::LessThan, Value::Float64(50.0));
-/// let predicate = And::new(Arc::new(pred1), Arc::new(pred2));
+/// // Create: age NOT IN (25, 30)
+/// let predicate = InList::new(2, vec![Value::Int64(25), Value::Int64(30)]).negated();
 /// ```
-#[derive(Debug)]
-pub struct And {
-    /// Left sub-predicate
-    left: Arc<dyn Predicate>,
-    /// Right sub-predicate
-    right: Arc<dyn Predicate>,
+#[derive(Debug, Clone)]
+pub struct InList {
+    /// The column index to test
+    column_index: usize,
+    /// The candidate values
+    values: Vec<Value>,
+    /// True for NOT IN
+    negated: bool,
+    /// One equality comparator per value, set once the predicate is bound
+    comparators: Option<Vec<Comparator>>,
 }
 
-impl And {
-    /// Create a new AND predicate.
+impl InList {
+    /// Create a new IN predicate.
     ///
     /// # Arguments
     ///
-    /// * `left` - The left sub-predicate
-    /// * `right` - The right sub-predicate
-    pub fn new(left: Arc<dyn Predicate>, right: Arc<dyn Predicate>) -> Self {
-        Self { left, right }
+    /// * `column_index` - Index of the column to test
+    /// * `values` - The values the column is compared against
+    pub fn new(column_index: usize, values: Vec<Value>) -> Self {
+        Self {
+            column_index,
+            values,
+            negated: false,
+            comparators: None,
+        }
+    }
+
+    /// Turn the predicate into NOT IN.
+    pub fn negated(mut self) -> Self {
+        self.negated = !self.negated;
+        self
+    }
+
+    fn resolve(&self, column_type: DataType) -> Result<Vec<Comparator>> {
+        self.values
+            .iter()
+            .map(|value| Comparator::resolve(column_type, &ComparisonOp::Equal, value))
+            .collect()
+    }
+
+    fn any_matches(comparators: &[Comparator], actual: &Value) -> Result<bool> {
+        for comparator in comparators {
+            if comparator.matches(actual)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 }
 
-impl Predicate for And {
+impl Predicate for InList {
     fn eval(&self, batch: &Batch, row_index: usize) -> Result<bool> {
-        let left_result = self.left.eval(batch, row_index)?;
-        if !left_result {
-            return Ok(false); // Short-circuit
+        let actual = batch.get(row_index, self.column_index)?;
+        let Some(actual_type) = actual.data_type() else {
+            return Ok(false);
+        };
+
+        let found = match &self.comparators {
+            Some(comparators) => Self::any_matches(comparators, &actual)?,
+            // Unbound: resolve from the value itself (slow path)
+            None => Self::any_matches(&self.resolve(actual_type)?, &actual)?,
+        };
+
+        if !found && self.negated && self.values.iter().any(Value::is_null) {
+            return Ok(false);
         }
-        self.right.eval(batch, row_index)
+        Ok(found != self.negated)
+    }
+
+    fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+        let Some(comparators) = &self.comparators else {
+            return eval_each_row(self, batch);
+        };
+        let column = batch.column(self.column_index)?;
+
+        let mut found = vec![false; column.len()];
+        for comparator in comparators {
+            let Some(matches) = comparator.matches_column(column.as_ref()) else {
+                return eval_each_row(self, batch);
+            };
+            for (found, matched) in found.iter_mut().zip(matches) {
+                *found |= matched;
+            }
+        }
+
+        if self.negated {
+            let list_has_null = self.values.iter().any(Value::is_null);
+            for (row, found) in found.iter_mut().enumerate() {
+                *found = !*found && !list_has_null && !column.is_null(row);
+            }
+        }
+        Ok(found)
+    }
+
+    fn bind(&self, column_types: &[DataType]) -> Result<Arc<dyn Predicate>> {
+        let column_type =
+            column_types
+                .get(self.column_index)
+                .ok_or(ExecutionError::InvalidColumnIndex {
+                    index: self.column_index,
+                    count: column_types.len(),
+                })?;
+
+        Ok(Arc::new(InList {
+            comparators: Some(self.resolve(*column_type)?),
+            ..self.clone()
+        }))
+    }
+
+    fn describe(&self, column_names: &[String]) -> String {
+        let values: Vec<String> = self.values.iter().map(sql_literal).collect();
+        format!(
+            "{} {}IN ({})",
+            column_label(column_names, self.column_index),
+            if self.negated { "NOT " } else { "" },
+            values.join(", ")
+        )
     }
 }
 
-/// Logical OR predicate: at least one sub-predicate must be true.
+/// NULL test predicate: the column value is NULL (or, negated, is not).
+///
+/// The column is asked through `Column::is_null`, so no `Value` is built per
+/// row, and a batch whose column has no NULLs is answered without looking at
+/// any row.
 ///
 /// # Example
 ///
 /// ```rust
-/// # use mini_rust_olap::execution::Or;
-/// # use mini_rust_olap::execution::BinaryComparison;
-/// # use mini_rust_olap::execution::ComparisonOp;
-/// # use mini_rust_olap::types::Value;
-/// use std::sync::Arc;
+/// # use mini_rust_olap::execution::IsNullPredicate;
+/// // Create: email IS NULL
+/// let predicate = IsNullPredicate::new(3);
 ///
-/// // Create: age < 25 OR age > 65
-/// let pred1 = BinaryComparison::new(2, ComparisonOp::LessThan, Value::Float64(25.0));
-/// let pred2 = BinaryComparison::new(2, ComparisonOp::GreaterThan, Value::Float64(65.0));
-/// let predicate = Or::new(Arc::new(pred1), Arc::new(pred2));
+/// // Create: email IS NOT NULL
+/// let predicate = IsNullPredicate::new(3).negated();
 /// ```
-#[derive(Debug)]
-pub struct Or {
-    /// Left sub-predicate
-    left: Arc<dyn Predicate>,
-    /// Right sub-predicate
-    right: Arc<dyn Predicate>,
+#[derive(Debug, Clone)]
+pub struct IsNullPredicate {
+    /// The column index to test
+    column_index: usize,
+    /// True for IS NOT NULL
+    negated: bool,
 }
 
-impl Or {
-    /// Create a new OR predicate.
+impl IsNullPredicate {
+    /// Create a new IS NULL predicate.
     ///
     /// # Arguments
     ///
-    /// * `left` - The left sub-predicate
-    /// * `right` - The right sub-predicate
-    pub fn new(left: Arc<dyn Predicate>, right: Arc<dyn Predicate>) -> Self {
-        Self { left, right }
+    /// * `column_index` - Index of the column to test
+    pub fn new(column_index: usize) -> Self {
+        Self {
+            column_index,
+            negated: false,
+        }
+    }
+
+    /// Turn the predicate into IS NOT NULL.
+    pub fn negated(mut self) -> Self {
+        self.negated = !self.negated;
+        self
+    }
+}
+
+impl Predicate for IsNullPredicate {
+    fn eval(&self, batch: &Batch, row_index: usize) -> Result<bool> {
+        let column = batch.column(self.column_index)?;
+        Ok(column.is_null(row_index) != self.negated)
+    }
+
+    fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+        let column = batch.column(self.column_index)?;
+        if column.null_count() == 0 {
+            return Ok(vec![self.negated; column.len()]);
+        }
+        Ok((0..column.len())
+            .map(|row| column.is_null(row) != self.negated)
+            .collect())
+    }
+
+    fn bind(&self, column_types: &[DataType]) -> Result<Arc<dyn Predicate>> {
+        if self.column_index >= column_types.len() {
+            return Err(ExecutionError::InvalidColumnIndex {
+                index: self.column_index,
+                count: column_types.len(),
+            });
+        }
+        Ok(Arc::new(self.clone()))
+    }
+
+    fn describe(&self, column_names: &[String]) -> String {
+        format!(
+            "{} IS {}NULL",
+            column_label(column_names, self.column_index),
+            if self.negated { "NOT " } else { "" }
+        )
+    }
+}
+
+/// Logical AND predicate: both sub-predicates must be true.
+///
+/// # Example
+///
+/// ```rust
+/// # use mini_rust_olap::execution::And;
+/// # use mini_rust_olap::execution::BinaryComparison;
+/// # use mini_rust_olap::execution::ComparisonOp;
+/// # use mini_rust_olap::types::Value;
+/// use std::sync::Arc;
+///
+/// // Create: age > 25 AND age < 50
+/// let pred1 = BinaryComparison::new(2, ComparisonOp::GreaterThan, Value::Float64(25.0));
+/// let pred2 = BinaryComparison::new(2, ComparisonOp::LessThan, Value::Float64(50.0));
+/// let predicate = And::new(Arc::new(pred1), Arc::new(pred2));
+/// ```
+#[derive(Debug)]
+pub struct And {
+    /// Left sub-predicate
+    left: Arc<dyn Predicate>,
+    /// Right sub-predicate
+    right: Arc<dyn Predicate>,
+}
+
+impl And {
+    /// Create a new AND predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - The left sub-predicate
+    /// * `right` - The right sub-predicate
+    pub fn new(left: Arc<dyn Predicate>, right: Arc<dyn Predicate>) -> Self {
+        Self { left, right }
+    }
+}
+
+impl Predicate for And {
+    fn eval(&self, batch: &Batch, row_index: usize) -> Result<bool> {
+        let left_result = self.left.eval(batch, row_index)?;
+        if !left_result {
+            return Ok(false); // Short-circuit
+        }
+        self.right.eval(batch, row_index)
+    }
+
+    fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+        let mut selection = self.left.eval_batch(batch)?;
+        if !selection.contains(&true) {
+            return Ok(selection);
+        }
+
+        match self.right.eval_batch(batch) {
+            Ok(right) => {
+                for (selected, matched) in selection.iter_mut().zip(right) {
+                    *selected &= matched;
+                }
+            }
+            // `eval` never reaches the right side on rows the left side
+            // rejected, so an error raised on one of those must not fail
+            // the batch: retry row by row on the surviving rows only
+            Err(_) => {
+                for (row, selected) in selection.iter_mut().enumerate() {
+                    if *selected {
+                        *selected = self.right.eval(batch, row)?;
+                    }
+                }
+            }
+        }
+        Ok(selection)
+    }
+
+    fn bind(&self, column_types: &[DataType]) -> Result<Arc<dyn Predicate>> {
+        Ok(Arc::new(And::new(
+            self.left.bind(column_types)?,
+            self.right.bind(column_types)?,
+        )))
+    }
+
+    fn describe(&self, column_names: &[String]) -> String {
+        format!(
+            "{} AND {}",
+            self.left.describe(column_names),
+            self.right.describe(column_names)
+        )
+    }
+}
+
+/// Logical OR predicate: at least one sub-predicate must be true.
+///
+/// # Example
+///
+/// ```rust
+/// # use mini_rust_olap::execution::Or;
+/// # use mini_rust_olap::execution::BinaryComparison;
+/// # use mini_rust_olap::execution::ComparisonOp;
+/// # use mini_rust_olap::types::Value;
+/// use std::sync::Arc;
+///
+/// // Create: age < 25 OR age > 65
+/// let pred1 = BinaryComparison::new(2, ComparisonOp::LessThan, Value::Float64(25.0));
+/// let pred2 = BinaryComparison::new(2, ComparisonOp::GreaterThan, Value::Float64(65.0));
+/// let predicate = Or::new(Arc::new(pred1), Arc::new(pred2));
+/// ```
+#[derive(Debug)]
+pub struct Or {
+    /// Left sub-predicate
+    left: Arc<dyn Predicate>,
+    /// Right sub-predicate
+    right: Arc<dyn Predicate>,
+}
+
+impl Or {
+    /// Create a new OR predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - The left sub-predicate
+    /// * `right` - The right sub-predicate
+    pub fn new(left: Arc<dyn Predicate>, right: Arc<dyn Predicate>) -> Self {
+        Self { left, right }
     }
 }
 
@@ -992,13 +3024,53 @@ impl Predicate for Or {
         }
         self.right.eval(batch, row_index)
     }
+
+    fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+        let mut selection = self.left.eval_batch(batch)?;
+        if !selection.contains(&false) {
+            return Ok(selection);
+        }
+
+        match self.right.eval_batch(batch) {
+            Ok(right) => {
+                for (selected, matched) in selection.iter_mut().zip(right) {
+                    *selected |= matched;
+                }
+            }
+            // As in `And`: only rows the left side rejected reach the right
+            Err(_) => {
+                for (row, selected) in selection.iter_mut().enumerate() {
+                    if !*selected {
+                        *selected = self.right.eval(batch, row)?;
+                    }
+                }
+            }
+        }
+        Ok(selection)
+    }
+
+    fn bind(&self, column_types: &[DataType]) -> Result<Arc<dyn Predicate>> {
+        Ok(Arc::new(Or::new(
+            self.left.bind(column_types)?,
+            self.right.bind(column_types)?,
+        )))
+    }
+
+    fn describe(&self, column_names: &[String]) -> String {
+        format!(
+            "({} OR {})",
+            self.left.describe(column_names),
+            self.right.describe(column_names)
+        )
+    }
 }
 
 /// Filter operator that filters rows based on a predicate.
 ///
 /// Filter reads batches from its child operator and returns only the rows
-/// that match the predicate. It evaluates the predicate on each row and
-/// includes the row in the output if the predicate evaluates to true.
+/// that match the predicate. It evaluates the predicate over a whole batch
+/// at a time with `Predicate::eval_batch` and copies the selected rows into
+/// the output; a batch whose rows all match is passed through as-is.
 ///
 /// # Example
 ///
@@ -1008,21 +3080,30 @@ impl Predicate for Or {
 /// # use mini_rust_olap::execution::BinaryComparison;
 /// # use mini_rust_olap::execution::ComparisonOp;
 /// # use mini_rust_olap::execution::Operator;
+/// # use mini_rust_olap::column::{Column, FloatColumn, IntColumn};
 /// # use mini_rust_olap::table::Table;
 /// # use mini_rust_olap::types::Value;
 /// use std::sync::Arc;
 ///
-/// let table = Table::new("users".to_string());
-/// // ... add columns to table ...
+/// let mut table = Table::new("users".to_string());
+/// let mut ids = IntColumn::new();
+/// let mut ages = FloatColumn::new();
+/// for (id, age) in [(1, 25.0), (2, 35.0)] {
+///     ids.push_value(Value::Int64(id)).unwrap();
+///     ages.push_value(Value::Float64(age)).unwrap();
+/// }
+/// table.add_column("id".to_string(), Box::new(ids)).unwrap();
+/// table.add_column("age".to_string(), Box::new(ages)).unwrap();
 ///
 /// let scan = TableScan::new(table);
 /// let predicate = BinaryComparison::new(
-///     2, // age column
+///     1, // age column
 ///     ComparisonOp::GreaterThan,
 ///     Value::Float64(30.0)
 /// );
 /// let mut filter = Filter::new(Box::new(scan), Arc::new(predicate));
 ///
+/// // Binding the predicate at open checks it against the column types
 /// filter.open().unwrap();
 /// while let Some(batch) = filter.next_batch().unwrap() {
 ///     // Process filtered batches
@@ -1036,11 +3117,17 @@ pub struct Filter {
     /// The predicate to evaluate on each row
     predicate: Arc<dyn Predicate>,
 
+    /// The predicate bound to the child's column types at open
+    bound_predicate: Option<Arc<dyn Predicate>>,
+
     /// Operator state
     state: OperatorState,
 
     /// Cached output schema
-    output_schema: Option<HashMap<String, DataType>>,
+    output_schema: Option<Schema>,
+
+    /// Checked before each batch is filtered
+    cancellation: CancellationToken,
 }
 
 impl Filter {
@@ -1054,13 +3141,20 @@ impl Filter {
         Filter {
             child,
             predicate,
+            bound_predicate: None,
             state: OperatorState::NotOpen,
             output_schema: None,
+            cancellation: CancellationToken::default(),
         }
     }
 }
 
 impl Operator for Filter {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.cancellation = token.clone();
+        self.child.set_cancellation(token);
+    }
+
     fn open(&mut self) -> Result<()> {
         if self.state == OperatorState::Open {
             return Err(ExecutionError::OperatorAlreadyOpen);
@@ -1070,7 +3164,11 @@ impl Operator for Filter {
         self.child.open()?;
 
         // Cache the child's schema as our output schema
-        self.output_schema = Some(self.child.schema()?);
+        let schema = self.child.schema()?;
+
+        // Resolve the predicate's comparisons against the input types once
+        self.bound_predicate = Some(self.predicate.bind(&schema.data_types())?);
+        self.output_schema = Some(schema);
 
         self.state = OperatorState::Open;
         Ok(())
@@ -1081,6 +3179,8 @@ impl Operator for Filter {
             return Err(ExecutionError::OperatorNotOpen);
         }
 
+        self.cancellation.check()?;
+
         // Get next batch from child
         let batch = match self.child.next_batch()? {
             Some(b) => b,
@@ -1092,46 +3192,25 @@ impl Operator for Filter {
             return Ok(Some(batch));
         }
 
-        // Evaluate predicate on each row and collect matching rows
-        let mut matching_row_indices = Vec::new();
-        for row_idx in 0..batch.row_count() {
-            if self.predicate.eval(&batch, row_idx)? {
-                matching_row_indices.push(row_idx);
-            }
-        }
+        // Evaluate the predicate over the whole batch at once
+        let predicate = self
+            .bound_predicate
+            .as_ref()
+            .ok_or(ExecutionError::OperatorNotOpen)?;
+        let selection = predicate.eval_batch(&batch)?;
+        let matching_rows = selection.iter().filter(|&&selected| selected).count();
 
         // If no rows match, continue to next batch
-        if matching_row_indices.is_empty() {
+        if matching_rows == 0 {
             return self.next_batch(); // Recursively get next batch
         }
 
-        // Create a new batch with only the matching rows
-        let mut filtered_columns = Vec::new();
-        let column_count = batch.column_count();
-
-        for col_idx in 0..column_count {
-            let original_column = batch.column(col_idx)?;
-            let original_values = original_column.slice(None);
-
-            // Filter the values
-            let filtered_values: Vec<Value> = matching_row_indices
-                .iter()
-                .map(|&row_idx| original_values[row_idx].clone())
-                .collect();
-
-            // Create a new column with filtered values
-            let data_type = original_column.data_type();
-            let mut filtered_column = crate::column::create_column(data_type);
-            for value in filtered_values {
-                filtered_column
-                    .push_value(value)
-                    .map_err(|e| ExecutionError::Custom(e.to_string()))?;
-            }
-
-            filtered_columns.push(filtered_column.into());
+        // Every row matched: pass the batch through without copying
+        if matching_rows == batch.row_count() {
+            return Ok(Some(batch));
         }
 
-        Ok(Some(Batch::new(filtered_columns)))
+        batch.filter_rows(&selection).map(Some)
     }
 
     fn close(&mut self) -> Result<()> {
@@ -1140,20 +3219,31 @@ impl Operator for Filter {
         Ok(())
     }
 
-    fn schema(&self) -> Result<HashMap<String, DataType>> {
+    fn schema(&self) -> Result<Schema> {
+        // Filter preserves column names and order from child
         self.output_schema
             .clone()
             .ok_or(ExecutionError::SchemaNotFound)
     }
 
-    fn column_names(&self) -> Result<Vec<String>> {
-        // Filter preserves column names and order from child
-        self.child.column_names()
-    }
-
     fn is_open(&self) -> bool {
         self.state == OperatorState::Open
     }
+
+    fn describe(&self) -> String {
+        format!(
+            "Filter({})",
+            self.predicate.describe(&output_names(self.child.as_ref()))
+        )
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.child]
+    }
 }
 
 /// The Project operator selects a subset of columns from its input.
@@ -1190,8 +3280,8 @@ pub struct Project {
     /// Operator state
     state: OperatorState,
 
-    /// Cached output schema
-    output_schema: Option<HashMap<String, DataType>>,
+    /// Output schema, resolved in open() and attached to every batch
+    output_schema: Option<Arc<Schema>>,
 }
 
 impl Project {
@@ -1224,7 +3314,8 @@ impl Project {
     ///
     /// # Arguments
     ///
-    /// * `aliases` - Names for the projected columns (must match length of column_indices)
+    /// * `aliases` - Names for the projected columns (must match length of column_indices,
+    ///   otherwise open() fails)
     ///
     /// # Example
     ///
@@ -1241,9 +3332,23 @@ impl Project {
         self.aliases = Some(aliases);
         self
     }
+
+    /// Resolves the output schema from the child's schema and the aliases,
+    /// validating indices, alias count, and name uniqueness.
+    fn resolve_output_schema(&self) -> Result<Schema> {
+        let child_schema = self.child.schema()?;
+        match &self.aliases {
+            Some(aliases) => child_schema.project(&self.column_indices, aliases),
+            None => child_schema.select(&self.column_indices),
+        }
+    }
 }
 
 impl Operator for Project {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.child.set_cancellation(token);
+    }
+
     fn open(&mut self) -> Result<()> {
         if self.state == OperatorState::Open {
             return Err(ExecutionError::OperatorAlreadyOpen);
@@ -1253,49 +3358,7 @@ impl Operator for Project {
         self.child.open()?;
 
         // Build the output schema
-        let child_schema = self.child.schema()?;
-        let child_column_names = self.child.column_names()?;
-        let mut output_schema = HashMap::new();
-
-        for (i, &index) in self.column_indices.iter().enumerate() {
-            // Validate column index
-            if index >= child_column_names.len() {
-                return Err(ExecutionError::InvalidColumnIndex {
-                    index,
-                    count: child_column_names.len(),
-                });
-            }
-
-            let original_name = &child_column_names[index];
-            let data_type = child_schema[original_name];
-
-            // Determine output column name (use alias if provided)
-            let output_name = match &self.aliases {
-                Some(aliases) => {
-                    if i >= aliases.len() {
-                        return Err(ExecutionError::Custom(format!(
-                            "Not enough aliases provided: expected {}, got {}",
-                            self.column_indices.len(),
-                            aliases.len()
-                        )));
-                    }
-                    aliases[i].clone()
-                }
-                None => original_name.clone(),
-            };
-
-            // Check for duplicate column names
-            if output_schema.contains_key(&output_name) {
-                return Err(ExecutionError::Custom(format!(
-                    "Duplicate column name: {}",
-                    output_name
-                )));
-            }
-
-            output_schema.insert(output_name, data_type);
-        }
-
-        self.output_schema = Some(output_schema);
+        self.output_schema = Some(Arc::new(self.resolve_output_schema()?));
         self.state = OperatorState::Open;
         Ok(())
     }
@@ -1311,15 +3374,14 @@ impl Operator for Project {
             None => return Ok(None), // No more data
         };
 
-        // Project the selected columns
-        let mut projected_columns = Vec::new();
-
-        for &index in &self.column_indices {
-            let column = batch.column(index)?;
-            projected_columns.push(column);
-        }
-
-        Ok(Some(Batch::new(projected_columns)))
+        // Project the selected columns under the names resolved in open()
+        let schema = self
+            .output_schema
+            .clone()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+        Ok(Some(
+            batch.select(&self.column_indices)?.with_schema(schema)?,
+        ))
     }
 
     fn close(&mut self) -> Result<()> {
@@ -1328,284 +3390,248 @@ impl Operator for Project {
         Ok(())
     }
 
-    fn schema(&self) -> Result<HashMap<String, DataType>> {
+    fn schema(&self) -> Result<Schema> {
         self.output_schema
-            .clone()
+            .as_deref()
+            .cloned()
             .ok_or(ExecutionError::SchemaNotFound)
     }
 
-    fn column_names(&self) -> Result<Vec<String>> {
-        // Rebuild column names from output schema to preserve order
-        let child_column_names = self.child.column_names()?;
-        let mut output_names = Vec::new();
-
-        for (i, &index) in self.column_indices.iter().enumerate() {
-            let original_name = &child_column_names[index];
-
-            // Use alias if provided, otherwise use original name
-            let output_name = match &self.aliases {
-                Some(aliases) => {
-                    if i < aliases.len() {
-                        aliases[i].clone()
-                    } else {
-                        original_name.clone()
-                    }
-                }
-                None => original_name.clone(),
-            };
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
 
-            output_names.push(output_name);
-        }
+    fn describe(&self) -> String {
+        let names = self.column_names().unwrap_or_else(|_| {
+            let input = output_names(self.child.as_ref());
+            self.column_indices
+                .iter()
+                .map(|&index| column_label(&input, index))
+                .collect()
+        });
+        format!("Project({})", names.join(", "))
+    }
 
-        Ok(output_names)
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.child.as_ref()]
     }
 
-    fn is_open(&self) -> bool {
-        self.state == OperatorState::Open
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.child]
     }
 }
 
 // ============================================================================
-// GROUP BY OPERATOR
+// PARALLEL SCAN OPERATOR
 // ============================================================================
 
-/// A key for grouping rows in a GroupBy operation.
+/// A scan that reads a table on several worker threads, optionally filtering
+/// each batch before handing it on.
 ///
-/// The key is a vector of values representing the group by columns.
-/// It implements Hash and Eq for use as a HashMap key.
-#[derive(Debug, Clone)]
-struct GroupKey(Vec<Option<Value>>);
-
-impl PartialEq for GroupKey {
-    fn eq(&self, other: &Self) -> bool {
-        if self.0.len() != other.0.len() {
-            return false;
-        }
-        for (a, b) in self.0.iter().zip(other.0.iter()) {
-            match (a, b) {
-                (None, None) => continue,
-                (None, Some(_)) | (Some(_), None) => return false,
-                (Some(va), Some(vb)) => {
-                    // Compare using string representation
-                    if va.to_string() != vb.to_string() {
-                        return false;
-                    }
-                }
-            }
-        }
-        true
-    }
-}
-
-impl Eq for GroupKey {}
-
-impl Hash for GroupKey {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        for value in &self.0 {
-            match value {
-                None => 0.hash(state),
-                Some(v) => {
-                    // Hash based on value type and value
-                    match v {
-                        Value::Int64(i) => (1, i).hash(state),
-                        Value::Float64(f) => (2, f.to_bits()).hash(state),
-                        Value::String(s) => (3, s).hash(state),
-                    }
-                }
-            }
-        }
-    }
-}
-
-/// GroupBy operator for grouping rows and computing aggregates.
+/// Workers claim batch-sized row ranges from a shared counter, build the
+/// batch, apply the predicate (if any) and send the surviving rows through a
+/// bounded channel; `next_batch` returns them in whatever order they arrive.
+/// The output therefore holds the same rows as `Filter` over a `TableScan`,
+/// but not in table order, so it must only be used where row order does not
+/// matter (aggregates, or a plan that sorts afterwards).
 ///
-/// GroupBy reads all rows from a child operator, groups them by specified
-/// columns, and computes aggregates for each group. The output contains
-/// one row per group with the group by keys followed by the aggregate results.
+/// Closing the operator early (e.g. after a LIMIT is satisfied) makes the
+/// workers stop at their next send.
 ///
 /// # Example
 ///
 /// ```ignore
-/// use mini_rust_olap::execution::{Operator, TableScan, GroupBy};
-/// use mini_rust_olap::aggregates::CountAggregate;
-/// use mini_rust_olap::types::DataType;
+/// use mini_rust_olap::execution::{BinaryComparison, ComparisonOp, ParallelScan};
 ///
-/// let scan = Box::new(TableScan::new(table).unwrap());
-/// let group_by = Box::new(GroupBy::new(
-///     scan,
-///     vec![0],  // Group by first column
-///     vec![1],  // Aggregate second column
-///     vec![Box::new(CountAggregate::new(DataType::Int64))]
-/// ));
+/// // age > 30 over columns 0 and 2, on four threads
+/// let predicate = BinaryComparison::new(1, ComparisonOp::GreaterThan, Value::Int64(30));
+/// let mut scan = ParallelScan::new(Arc::new(table), vec![0, 2], 4)
+///     .with_predicate(Arc::new(predicate));
+/// scan.open()?;
+/// while let Some(batch) = scan.next_batch()? {
+///     // Process batches in arrival order
+/// }
+/// scan.close()?;
 /// ```
-pub struct GroupBy {
-    /// The child operator to read data from
-    child: Box<dyn Operator>,
+pub struct ParallelScan {
+    /// The table to scan, shared with the workers
+    table: Arc<Table>,
 
-    /// Indices of columns to group by
-    group_by_columns: Vec<usize>,
+    /// Indices of columns to read
+    column_indices: Vec<usize>,
 
-    /// Indices of columns to aggregate
-    aggregate_columns: Vec<usize>,
+    /// Number of worker threads
+    parallelism: usize,
 
-    /// Aggregates to compute for each group
-    aggregates: Vec<Box<dyn crate::aggregates::AggregateFunction>>,
+    /// Number of rows each worker reads at a time
+    batch_size: usize,
+
+    /// Optional predicate applied by the workers, over the selected columns
+    predicate: Option<Arc<dyn Predicate>>,
 
     /// Operator state
     state: OperatorState,
 
-    /// Output schema will include group by columns followed by aggregates
-    output_schema: Option<HashMap<String, DataType>>,
+    /// Schema of the selected columns, resolved in open() and attached by
+    /// the workers to every batch
+    output_schema: Option<Arc<Schema>>,
 
-    /// Column names in output order
-    output_column_names: Option<Vec<String>>,
+    /// Checked by each worker before it claims rows, and before each batch
+    /// is returned
+    cancellation: CancellationToken,
 
-    /// Cache of grouped data (computed during open())
-    grouped_data: Option<HashMap<GroupKey, Vec<Vec<Option<Value>>>>>,
+    /// Batches (or the first error) sent by the workers
+    receiver: Option<std::sync::mpsc::Receiver<Result<Batch>>>,
 
-    /// Whether results have been returned
-    results_returned: bool,
+    /// Worker threads, joined in close()
+    workers: Vec<std::thread::JoinHandle<()>>,
 }
 
-impl GroupBy {
-    /// Create a new GroupBy operator.
+impl ParallelScan {
+    /// Create a parallel scan over selected columns of a table.
     ///
     /// # Arguments
     ///
-    /// * `child` - The child operator to read data from
-    /// * `group_by_columns` - Indices of columns to group by
-    /// * `aggregates` - Vector of aggregate functions to compute for each group
-    ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// use mini_rust_olap::execution::{Operator, TableScan, GroupBy};
-    /// use mini_rust_olap::aggregates::CountAggregate;
-    /// use mini_rust_olap::types::DataType;
-    ///
-    /// let scan = Box::new(TableScan::new(table).unwrap());
-    /// let group_by = Box::new(GroupBy::new(
-    ///     scan,
-    ///     vec![0, 1],  // Group by first two columns
-    ///     vec![2],     // Aggregate third column
-    ///     vec![Box::new(CountAggregate::new(DataType::Int64))]
-    /// ));
-    /// ```
-    pub fn new(
-        child: Box<dyn Operator>,
-        group_by_columns: Vec<usize>,
-        aggregate_columns: Vec<usize>,
-        aggregates: Vec<Box<dyn crate::aggregates::AggregateFunction>>,
-    ) -> Self {
-        GroupBy {
-            child,
-            group_by_columns,
-            aggregate_columns,
-            aggregates,
+    /// * `table` - The table to scan
+    /// * `column_indices` - Indices of columns to read
+    /// * `parallelism` - Number of worker threads (must be > 0)
+    pub fn new(table: Arc<Table>, column_indices: Vec<usize>, parallelism: usize) -> Self {
+        if parallelism == 0 {
+            panic!("Parallelism must be greater than 0");
+        }
+        ParallelScan {
+            table,
+            column_indices,
+            parallelism,
+            batch_size: 1024,
+            predicate: None,
             state: OperatorState::NotOpen,
             output_schema: None,
-            output_column_names: None,
-            grouped_data: None,
-            results_returned: false,
+            cancellation: CancellationToken::default(),
+            receiver: None,
+            workers: Vec::new(),
         }
     }
-}
-
-impl Operator for GroupBy {
-    fn open(&mut self) -> Result<()> {
-        if self.state == OperatorState::Open {
-            return Err(ExecutionError::OperatorAlreadyOpen);
-        }
-
-        // Open the child operator
-        self.child.open()?;
 
-        // Get child schema and column names
-        let child_schema = self.child.schema()?;
-        let child_column_names = self.child.column_names()?;
-        let child_column_count = child_column_names.len();
+    /// Filter rows with a predicate on the worker threads.
+    ///
+    /// Column indices in the predicate refer to the scan's output columns,
+    /// as they would for a `Filter` placed above it.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The condition rows must satisfy
+    pub fn with_predicate(mut self, predicate: Arc<dyn Predicate>) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
 
-        // Validate group by column indices
-        for &index in &self.group_by_columns {
-            if index >= child_column_count {
-                return Err(ExecutionError::InvalidColumnIndex {
-                    index,
-                    count: child_column_count,
-                });
-            }
+    /// Set the number of rows each worker reads at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size` - Number of rows per batch (must be > 0)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        if batch_size == 0 {
+            panic!("Batch size must be greater than 0");
         }
+        self.batch_size = batch_size;
+        self
+    }
 
-        // Validate aggregate column indices
-        for &index in &self.aggregate_columns {
-            if index >= child_column_count {
-                return Err(ExecutionError::InvalidColumnIndex {
-                    index,
-                    count: child_column_count,
-                });
+    /// Wait for the workers to finish, reporting the first one that panicked.
+    fn join_workers(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() && result.is_ok() {
+                result = Err(ExecutionError::Custom(
+                    "parallel scan worker panicked".to_string(),
+                ));
             }
         }
+        result
+    }
+}
 
-        // Validate that aggregate_columns length matches aggregates length
-        if self.aggregate_columns.len() != self.aggregates.len() {
-            return Err(ExecutionError::Custom(format!(
-                "aggregate_columns length ({}) must match aggregates length ({})",
-                self.aggregate_columns.len(),
-                self.aggregates.len()
-            )));
-        }
-
-        // Build output schema and column names
-        let mut output_schema = HashMap::new();
-        let mut output_column_names = Vec::new();
-
-        // Add group by columns to output
-        for &index in &self.group_by_columns {
-            let name = child_column_names[index].clone();
-            let data_type = child_schema[&name];
-            output_schema.insert(name.clone(), data_type);
-            output_column_names.push(name);
-        }
+impl Operator for ParallelScan {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.cancellation = token.clone();
+    }
 
-        // Add aggregates to output
-        for (i, agg) in self.aggregates.iter().enumerate() {
-            let name = format!("agg_{}", i);
-            let data_type = agg.data_type();
-            output_schema.insert(name.clone(), data_type);
-            output_column_names.push(name);
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
         }
 
-        self.output_schema = Some(output_schema);
-        self.output_column_names = Some(output_column_names);
-
-        // Read all data and group it
-        let mut grouped_data: HashMap<GroupKey, Vec<Vec<Option<Value>>>> = HashMap::new();
-
-        while let Some(batch) = self.child.next_batch()? {
-            let row_count = batch.row_count();
-            let col_count = batch.column_count();
+        let resolved_columns = resolve_scan_columns(&self.table, &self.column_indices)?;
+        let schema = Arc::new(Schema::from_columns(&resolved_columns)?);
+        let predicate = match &self.predicate {
+            Some(predicate) => Some(predicate.bind(&schema.data_types())?),
+            None => None,
+        };
 
-            for row_index in 0..row_count {
-                // Build group key
-                let mut key_values = Vec::new();
-                for &col_index in &self.group_by_columns {
-                    let value = batch.get(row_index, col_index)?;
-                    key_values.push(Some(value));
+        // Two batches of slack per worker keeps them busy without letting a
+        // slow consumer buffer the whole table
+        let (sender, receiver) = std::sync::mpsc::sync_channel(self.parallelism * 2);
+        let next_row = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let column_indices: Arc<[usize]> = self.column_indices.clone().into();
+        let snapshot = self.table.snapshot();
+        let total_rows = snapshot.row_count();
+
+        for _ in 0..self.parallelism {
+            let snapshot = Arc::clone(&snapshot);
+            let column_indices = Arc::clone(&column_indices);
+            let schema = Arc::clone(&schema);
+            let predicate = predicate.clone();
+            let next_row = Arc::clone(&next_row);
+            let sender = sender.clone();
+            let batch_size = self.batch_size;
+            let cancellation = self.cancellation.clone();
+
+            self.workers.push(std::thread::spawn(move || loop {
+                if let Err(err) = cancellation.check() {
+                    // The consumer only reads the first error
+                    let _ = sender.send(Err(err));
+                    return;
                 }
-                let key = GroupKey(key_values);
-
-                // Get all values for this row
-                let mut row_values = Vec::new();
-                for col_index in 0..col_count {
-                    let value = batch.get(row_index, col_index)?;
-                    row_values.push(Some(value));
+                let start_row =
+                    next_row.fetch_add(batch_size, std::sync::atomic::Ordering::Relaxed);
+                if start_row >= total_rows {
+                    return;
                 }
+                let end_row = (start_row + batch_size).min(total_rows);
+
+                let batch = scan_table_rows(&snapshot, &column_indices, start_row, end_row)
+                    .and_then(|batch| batch.with_schema(Arc::clone(&schema)))
+                    .and_then(|batch| match &predicate {
+                        Some(predicate) => {
+                            let selection = predicate.eval_batch(&batch)?;
+                            if selection.iter().all(|&selected| selected) {
+                                Ok(Some(batch))
+                            } else if selection.iter().any(|&selected| selected) {
+                                batch.filter_rows(&selection).map(Some)
+                            } else {
+                                Ok(None)
+                            }
+                        }
+                        None => Ok(Some(batch)),
+                    });
 
-                // Add row to its group
-                grouped_data.entry(key).or_default().push(row_values);
-            }
+                let stop = batch.is_err();
+                if let Some(message) = batch.transpose() {
+                    // A send only fails once the operator has been closed
+                    if sender.send(message).is_err() {
+                        return;
+                    }
+                }
+                if stop {
+                    return;
+                }
+            }));
         }
 
-        self.grouped_data = Some(grouped_data);
+        self.output_schema = Some(schema);
+        self.receiver = Some(receiver);
         self.state = OperatorState::Open;
         Ok(())
     }
@@ -1615,377 +3641,867 @@ impl Operator for GroupBy {
             return Err(ExecutionError::OperatorNotOpen);
         }
 
-        if self.results_returned {
+        let Some(receiver) = &self.receiver else {
             return Ok(None);
+        };
+        self.cancellation.check()?;
+        match receiver.recv() {
+            Ok(batch) => batch.map(Some),
+            // Every worker has finished and hung up
+            Err(_) => {
+                self.receiver = None;
+                self.join_workers()?;
+                Ok(None)
+            }
         }
+    }
 
-        self.results_returned = true;
-
-        let grouped_data = self.grouped_data.as_ref().unwrap();
+    fn close(&mut self) -> Result<()> {
+        // Dropping the receiver makes any worker still running stop
+        self.receiver = None;
+        self.state = OperatorState::Closed;
+        self.join_workers()
+    }
 
-        // If no data, return empty batch
-        if grouped_data.is_empty() {
-            return Ok(None);
+    fn schema(&self) -> Result<Schema> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::SchemaNotFound);
         }
+        self.output_schema
+            .as_deref()
+            .cloned()
+            .ok_or(ExecutionError::SchemaNotFound)
+    }
 
-        // Prepare output columns
-        let group_by_col_count = self.group_by_columns.len();
-        let agg_col_count = self.aggregates.len();
-        let mut output_columns: Vec<Vec<Option<Value>>> =
-            vec![Vec::new(); group_by_col_count + agg_col_count];
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
 
-        // Process each group
-        for (key, rows) in grouped_data {
-            // Add group by values
-            for (col_index, value) in key.0.iter().enumerate() {
-                output_columns[col_index].push(value.clone());
-            }
+    fn describe(&self) -> String {
+        let names = self.table.column_names();
+        let columns: Vec<String> = self
+            .column_indices
+            .iter()
+            .map(|&index| column_label(&names, index))
+            .collect();
+        let mut description = format!(
+            "ParallelScan({}: {}, {} threads",
+            self.table.name(),
+            columns.join(", "),
+            self.parallelism
+        );
+        if let Some(predicate) = &self.predicate {
+            description.push_str(&format!(", WHERE {}", predicate.describe(&columns)));
+        }
+        description.push(')');
+        description
+    }
+}
 
-            // Compute aggregates
-            for (agg_index, agg) in self.aggregates.iter_mut().enumerate() {
-                agg.reset();
-                let agg_col_index = self.aggregate_columns[agg_index];
+// ============================================================================
+// EXPRESSION EVALUATION AND COMPUTE OPERATOR
+// ============================================================================
 
-                // Add all rows in this group to the aggregate
-                for row in rows {
-                    if let Some(value) = &row[agg_col_index] {
-                        agg.update(Some(value.clone()))?;
-                    }
-                }
+/// Arithmetic operators for computed expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
 
-                let result = agg.result();
-                let output_index = group_by_col_count + agg_index;
-                output_columns[output_index].push(result);
-            }
+impl std::fmt::Display for ArithmeticOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticOp::Add => write!(f, "+"),
+            ArithmeticOp::Subtract => write!(f, "-"),
+            ArithmeticOp::Multiply => write!(f, "*"),
+            ArithmeticOp::Divide => write!(f, "/"),
         }
+    }
+}
 
-        // Convert output columns to actual column types
-        let mut final_columns = Vec::new();
-        let child_schema = self.child.schema()?;
-        let child_column_names = self.child.column_names()?;
-
-        // Group by columns
-        for (i, &col_index) in self.group_by_columns.iter().enumerate() {
-            let col_name = &child_column_names[col_index];
-            let data_type = &child_schema[col_name];
-            let values = &output_columns[i];
-
-            let column: Arc<dyn Column> = match data_type {
-                DataType::Int64 => {
-                    let mut int_col = crate::column::IntColumn::new();
-                    for value in values {
-                        if let Some(Value::Int64(v)) = value {
-                            int_col.push_value(Value::Int64(*v))?;
-                        } else {
-                            int_col.push_value(Value::Int64(0))?;
-                        }
-                    }
-                    Arc::new(int_col)
-                }
-                DataType::Float64 => {
-                    let mut float_col = crate::column::FloatColumn::new();
-                    for value in values {
-                        if let Some(Value::Float64(v)) = value {
-                            float_col.push_value(Value::Float64(*v))?;
-                        } else {
-                            float_col.push_value(Value::Float64(0.0))?;
-                        }
-                    }
-                    Arc::new(float_col)
-                }
-                DataType::String => {
-                    let mut string_col = crate::column::StringColumn::new();
-                    for value in values {
-                        if let Some(Value::String(v)) = value {
-                            string_col.push_value(Value::String(v.clone()))?;
-                        } else {
-                            string_col.push_value(Value::String(String::new()))?;
-                        }
-                    }
-                    Arc::new(string_col)
-                }
-            };
-            final_columns.push(column);
-        }
+/// Built-in scalar functions, applied to one value per row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarFunction {
+    /// `UPPER(s)`: the string in upper case
+    Upper,
+    /// `LOWER(s)`: the string in lower case
+    Lower,
+    /// `LENGTH(s)`: the number of characters in the string, as Int64
+    Length,
+    /// `TRIM(s)`: the string without leading and trailing whitespace
+    Trim,
+}
 
-        // Aggregate columns
-        for (i, agg) in self.aggregates.iter().enumerate() {
-            let data_type = agg.data_type();
-            let values = &output_columns[group_by_col_count + i];
-
-            let column: Arc<dyn Column> = match data_type {
-                DataType::Int64 => {
-                    let mut int_col = crate::column::IntColumn::new();
-                    for value in values {
-                        if let Some(Value::Int64(v)) = value {
-                            int_col.push_value(Value::Int64(*v))?;
-                        } else {
-                            int_col.push_value(Value::Int64(0))?;
-                        }
-                    }
-                    Arc::new(int_col)
-                }
-                DataType::Float64 => {
-                    let mut float_col = crate::column::FloatColumn::new();
-                    for value in values {
-                        if let Some(Value::Float64(v)) = value {
-                            float_col.push_value(Value::Float64(*v))?;
-                        } else {
-                            float_col.push_value(Value::Float64(0.0))?;
-                        }
-                    }
-                    Arc::new(float_col)
-                }
-                DataType::String => {
-                    let mut string_col = crate::column::StringColumn::new();
-                    for value in values {
-                        if let Some(Value::String(v)) = value {
-                            string_col.push_value(Value::String(v.clone()))?;
-                        } else {
-                            string_col.push_value(Value::String(String::new()))?;
-                        }
-                    }
-                    Arc::new(string_col)
-                }
-            };
-            final_columns.push(column);
-        }
+impl ScalarFunction {
+    /// Every supported function, in the order error messages list them.
+    pub const ALL: [ScalarFunction; 4] = [
+        ScalarFunction::Upper,
+        ScalarFunction::Lower,
+        ScalarFunction::Length,
+        ScalarFunction::Trim,
+    ];
 
-        Ok(Some(Batch::new(final_columns)))
+    /// Look up a function by name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|function| function.name().eq_ignore_ascii_case(name))
     }
 
-    fn close(&mut self) -> Result<()> {
-        self.state = OperatorState::Closed;
-        self.child.close()?;
-        self.grouped_data = None;
-        self.results_returned = false;
-        Ok(())
+    /// The function's SQL name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScalarFunction::Upper => "UPPER",
+            ScalarFunction::Lower => "LOWER",
+            ScalarFunction::Length => "LENGTH",
+            ScalarFunction::Trim => "TRIM",
+        }
     }
 
-    fn schema(&self) -> Result<HashMap<String, DataType>> {
-        self.output_schema
-            .clone()
-            .ok_or(ExecutionError::SchemaNotFound)
+    /// The type the function returns for arguments of the given types.
+    ///
+    /// # Returns
+    ///
+    /// The result type, or an error if the function is given the wrong
+    /// number of arguments or an argument that is not a String
+    pub fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types {
+            [DataType::String] => Ok(match self {
+                ScalarFunction::Length => DataType::Int64,
+                ScalarFunction::Upper | ScalarFunction::Lower | ScalarFunction::Trim => {
+                    DataType::String
+                }
+            }),
+            [other] => Err(ExecutionError::SchemaMismatch(format!(
+                "{} expects a String argument, got {}",
+                self, other
+            ))),
+            _ => Err(ExecutionError::Custom(format!(
+                "{} takes 1 argument, {} given",
+                self,
+                arg_types.len()
+            ))),
+        }
     }
 
-    fn column_names(&self) -> Result<Vec<String>> {
-        self.output_column_names
-            .clone()
-            .ok_or(ExecutionError::Custom(
-                "Column names not initialized".to_string(),
-            ))
+    /// Apply the function to one row's arguments; NULL gives NULL.
+    pub fn apply(&self, args: &[Value]) -> Result<Value> {
+        let text = match args {
+            [Value::Null] => return Ok(Value::Null),
+            [Value::String(text)] => text,
+            [other] => {
+                return Err(ExecutionError::SchemaMismatch(format!(
+                    "{} expects a String argument, got {}",
+                    self,
+                    other.type_name()
+                )))
+            }
+            _ => {
+                return Err(ExecutionError::Custom(format!(
+                    "{} takes 1 argument, {} given",
+                    self,
+                    args.len()
+                )))
+            }
+        };
+        Ok(match self {
+            ScalarFunction::Upper => Value::String(text.to_uppercase()),
+            ScalarFunction::Lower => Value::String(text.to_lowercase()),
+            ScalarFunction::Length => Value::Int64(text.chars().count() as i64),
+            ScalarFunction::Trim => Value::String(text.trim().to_string()),
+        })
     }
+}
 
-    fn is_open(&self) -> bool {
-        self.state == OperatorState::Open
+impl std::fmt::Display for ScalarFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
     }
 }
 
-// ============================================================================
-// SORT OPERATOR (ORDER BY)
-// ============================================================================
-
-/// Sort operator for ORDER BY clause.
+/// A scalar expression over the columns of a batch, such as `salary / 12`.
 ///
-/// Sort operator reads all data from the child operator and sorts it
-/// according to the specified columns and directions. Since sorting
-/// requires all data, this operator reads all rows in open().
-pub struct Sort {
-    /// The child operator to read data from
-    child: Box<dyn Operator>,
-
-    /// Indices of columns to sort by
-    sort_columns: Vec<usize>,
+/// Columns are referenced by index in the input batch; the planner
+/// translates parsed expressions into this form.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarExpression {
+    /// The value of an input column
+    Column(usize),
+    /// A constant
+    Literal(Value),
+    /// Arithmetic negation, `-operand`
+    Negate(Box<ScalarExpression>),
+    /// A binary arithmetic operation
+    Arithmetic {
+        left: Box<ScalarExpression>,
+        op: ArithmeticOp,
+        right: Box<ScalarExpression>,
+    },
+    /// Explicit type conversion, `CAST(expr AS target)`
+    Cast {
+        expr: Box<ScalarExpression>,
+        target: DataType,
+    },
+    /// A scalar function call, such as `UPPER(name)`
+    Function {
+        function: ScalarFunction,
+        args: Vec<ScalarExpression>,
+    },
+    /// `CASE WHEN ... THEN ... ELSE ... END`: the result of the first branch
+    /// whose condition holds, else `else_expr`, else NULL
+    Case {
+        branches: Vec<CaseBranch>,
+        else_expr: Option<Box<ScalarExpression>>,
+    },
+}
 
-    /// Sort direction for each column
-    sort_directions: Vec<SortDirection>,
+/// One `WHEN condition THEN result` branch of a CASE expression.
+#[derive(Debug, Clone)]
+pub struct CaseBranch {
+    /// The rows the branch applies to; a NULL comparison never holds
+    pub condition: Arc<dyn Predicate>,
+    /// The value for those rows
+    pub result: ScalarExpression,
+}
 
-    /// Operator state
-    state: OperatorState,
+impl PartialEq for CaseBranch {
+    /// Predicates cannot be compared, so branches are equal only when they
+    /// share the same condition
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(Arc::as_ptr(&self.condition), Arc::as_ptr(&other.condition))
+            && self.result == other.result
+    }
+}
 
-    /// Sorted data after sorting
-    sorted_data: Option<Batch>,
+/// The common type of two CASE results, widening Int64 to Float64.
+fn unify_case_types(left: DataType, right: DataType) -> Result<DataType> {
+    match (left, right) {
+        (a, b) if a == b => Ok(a),
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            Ok(DataType::Float64)
+        }
+        _ => Err(ExecutionError::SchemaMismatch(format!(
+            "CASE branches return {} and {}, which have no common type",
+            left, right
+        ))),
+    }
+}
 
-    /// Current position in sorted data
-    current_row: usize,
+impl ScalarExpression {
+    /// Format the expression as SQL over the named input columns.
+    fn describe(&self, column_names: &[String]) -> String {
+        match self {
+            ScalarExpression::Column(index) => column_label(column_names, *index),
+            ScalarExpression::Literal(value) => sql_literal(value),
+            ScalarExpression::Negate(operand) => format!("-{}", operand.describe(column_names)),
+            ScalarExpression::Arithmetic { left, op, right } => {
+                let operand = |expr: &ScalarExpression| match expr {
+                    ScalarExpression::Arithmetic { .. } => {
+                        format!("({})", expr.describe(column_names))
+                    }
+                    _ => expr.describe(column_names),
+                };
+                format!("{} {} {}", operand(left), op, operand(right))
+            }
+            ScalarExpression::Cast { expr, target } => {
+                format!("CAST({} AS {})", expr.describe(column_names), target)
+            }
+            ScalarExpression::Function { function, args } => {
+                let args: Vec<String> = args.iter().map(|arg| arg.describe(column_names)).collect();
+                format!("{}({})", function, args.join(", "))
+            }
+            ScalarExpression::Case {
+                branches,
+                else_expr,
+            } => {
+                let mut text = "CASE".to_string();
+                for branch in branches {
+                    text.push_str(&format!(
+                        " WHEN {} THEN {}",
+                        branch.condition.describe(column_names),
+                        branch.result.describe(column_names)
+                    ));
+                }
+                if let Some(else_expr) = else_expr {
+                    text.push_str(&format!(" ELSE {}", else_expr.describe(column_names)));
+                }
+                text + " END"
+            }
+        }
+    }
+}
 
-    /// Batch size for output
-    batch_size: usize,
+/// Evaluates a `ScalarExpression` against batches.
+///
+/// Int64 arithmetic stays Int64 and is checked, so overflow is an error
+/// rather than wrapping; if either operand is Float64 the operation is done
+/// in Float64. Integer division truncates. Division by zero, and any
+/// operation with a NULL operand, yields NULL.
+///
+/// A cast converts between Int64 and Float64, renders any value as a
+/// String, and parses a String into any other type; a value that does not
+/// convert (such as `'abc'` cast to Int64) is an error naming that value.
+///
+/// Functions take String arguments; see [`ScalarFunction`].
+///
+/// A CASE expression evaluates only the result of the branch each row takes.
+/// Its type is the common type of its results, where Int64 and Float64 mix
+/// as Float64; results of other differing types are an error.
+///
+/// # Example
+///
+/// ```rust
+/// # use mini_rust_olap::execution::{ArithmeticOp, ExpressionEvaluator, ScalarExpression};
+/// # use mini_rust_olap::types::{DataType, Value};
+/// // salary / 12
+/// let evaluator = ExpressionEvaluator::new(ScalarExpression::Arithmetic {
+///     left: Box::new(ScalarExpression::Column(0)),
+///     op: ArithmeticOp::Divide,
+///     right: Box::new(ScalarExpression::Literal(Value::Int64(12))),
+/// });
+/// assert_eq!(evaluator.data_type(&[DataType::Float64]).unwrap(), DataType::Float64);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpressionEvaluator {
+    expression: ScalarExpression,
 }
 
-impl Sort {
-    /// Create a new Sort operator.
+impl ExpressionEvaluator {
+    /// Create an evaluator for an expression.
+    pub fn new(expression: ScalarExpression) -> Self {
+        ExpressionEvaluator { expression }
+    }
+
+    /// Determine the type the expression produces.
     ///
     /// # Arguments
     ///
-    /// * `child` - The child operator to read data from
-    /// * `sort_columns` - Indices of columns to sort by
-    /// * `sort_directions` - Sort direction for each column (true = ascending, false = descending)
-    ///
-    /// # Example
+    /// * `column_types` - Data types of the input columns, in order
     ///
-    /// ```rust
-    /// # use mini_rust_olap::execution::Sort;
-    /// # use mini_rust_olap::execution::TableScan;
-    /// # use mini_rust_olap::execution::Operator;
-    /// # use mini_rust_olap::types::SortDirection;
-    /// # use mini_rust_olap::table::Table;
-    /// use std::sync::Arc;
+    /// # Returns
     ///
-    /// let table = Table::new("users".to_string());
-    /// // ... add columns to table ...
+    /// The result type, or an error if the expression references a missing
+    /// column or applies arithmetic to a non-numeric value
+    pub fn data_type(&self, column_types: &[DataType]) -> Result<DataType> {
+        Self::expression_type(&self.expression, column_types)
+    }
+
+    fn expression_type(expr: &ScalarExpression, column_types: &[DataType]) -> Result<DataType> {
+        match expr {
+            ScalarExpression::Column(index) => {
+                column_types
+                    .get(*index)
+                    .copied()
+                    .ok_or(ExecutionError::InvalidColumnIndex {
+                        index: *index,
+                        count: column_types.len(),
+                    })
+            }
+            ScalarExpression::Literal(value) => value.data_type().ok_or_else(|| {
+                ExecutionError::Custom("NULL literal has no type in an expression".to_string())
+            }),
+            ScalarExpression::Negate(operand) => {
+                match Self::expression_type(operand, column_types)? {
+                    numeric if numeric.is_numeric() => Ok(numeric),
+                    other => Err(ExecutionError::SchemaMismatch(format!(
+                        "cannot negate a {}",
+                        other
+                    ))),
+                }
+            }
+            ScalarExpression::Arithmetic { left, op, right } => {
+                let left_type = Self::expression_type(left, column_types)?;
+                let right_type = Self::expression_type(right, column_types)?;
+                match (left_type, right_type) {
+                    (DataType::Int64, DataType::Int64) => Ok(DataType::Int64),
+                    (a, b) if a.is_numeric() && b.is_numeric() => Ok(DataType::Float64),
+                    _ => Err(ExecutionError::SchemaMismatch(format!(
+                        "cannot apply '{}' to {} and {}",
+                        op, left_type, right_type
+                    ))),
+                }
+            }
+            ScalarExpression::Cast { expr, target } => {
+                let source = Self::expression_type(expr, column_types)?;
+                if can_cast(source, *target) {
+                    Ok(*target)
+                } else {
+                    Err(ExecutionError::SchemaMismatch(format!(
+                        "cannot cast {} to {}",
+                        source, target
+                    )))
+                }
+            }
+            ScalarExpression::Function { function, args } => {
+                let arg_types = args
+                    .iter()
+                    .map(|arg| Self::expression_type(arg, column_types))
+                    .collect::<Result<Vec<_>>>()?;
+                function.return_type(&arg_types)
+            }
+            ScalarExpression::Case {
+                branches,
+                else_expr,
+            } => {
+                // Binding checks each condition can be evaluated on the input
+                for branch in branches {
+                    branch.condition.bind(column_types)?;
+                }
+                let results = branches
+                    .iter()
+                    .map(|branch| &branch.result)
+                    .chain(else_expr.as_deref());
+                let mut data_type: Option<DataType> = None;
+                for result in results {
+                    let result_type = Self::expression_type(result, column_types)?;
+                    data_type = Some(match data_type {
+                        Some(data_type) => unify_case_types(data_type, result_type)?,
+                        None => result_type,
+                    });
+                }
+                data_type.ok_or_else(|| {
+                    ExecutionError::Custom("CASE needs at least one WHEN branch".to_string())
+                })
+            }
+        }
+    }
+
+    /// Evaluate the expression for every row of a batch.
     ///
-    /// let scan = TableScan::new(table);
-    /// let mut sort = Sort::new(
-    ///     Box::new(scan),
-    ///     vec![0, 1],
-    ///     vec![SortDirection::Ascending, SortDirection::Descending],
-    /// );
+    /// # Returns
     ///
-    /// sort.open().unwrap();
-    /// while let Some(batch) = sort.next_batch().unwrap() {
-    ///     // Process sorted batches
-    /// }
-    /// sort.close().unwrap();
-    /// ```
-    pub fn new(
-        child: Box<dyn Operator>,
-        sort_columns: Vec<usize>,
-        sort_directions: Vec<SortDirection>,
-    ) -> Self {
-        Sort {
-            child,
-            sort_columns,
-            sort_directions,
-            state: OperatorState::NotOpen,
-            sorted_data: None,
-            current_row: 0,
-            batch_size: 1024,
+    /// A new column with one value per row
+    pub fn evaluate(&self, batch: &Batch) -> Result<Arc<dyn Column>> {
+        let column_types: Vec<DataType> =
+            batch.columns().iter().map(|col| col.data_type()).collect();
+        let data_type = self.data_type(&column_types)?;
+
+        let mut column = crate::column::create_column(data_type);
+        for value in Self::evaluate_values(&self.expression, batch)? {
+            column.push_value(value)?;
         }
+        Ok(column.into())
     }
 
-    /// Set the batch size for output.
-    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
-        self.batch_size = batch_size;
-        self
+    /// Evaluate the expression for a single row.
+    pub fn evaluate_row(&self, batch: &Batch, row_index: usize) -> Result<Value> {
+        Self::evaluate_value(&self.expression, batch, row_index)
     }
-}
 
-impl Operator for Sort {
-    fn open(&mut self) -> Result<()> {
-        if self.state == OperatorState::Open {
-            return Err(ExecutionError::OperatorAlreadyOpen);
+    fn evaluate_values(expr: &ScalarExpression, batch: &Batch) -> Result<Vec<Value>> {
+        match expr {
+            ScalarExpression::Column(index) => Ok(batch.column(*index)?.slice(None)),
+            ScalarExpression::Literal(value) => Ok(vec![value.clone(); batch.row_count()]),
+            ScalarExpression::Negate(operand) => Self::evaluate_values(operand, batch)?
+                .iter()
+                .map(negate)
+                .collect(),
+            ScalarExpression::Arithmetic { left, op, right } => {
+                let left = Self::evaluate_values(left, batch)?;
+                let right = Self::evaluate_values(right, batch)?;
+                left.iter()
+                    .zip(&right)
+                    .map(|(a, b)| apply_arithmetic(*op, a, b))
+                    .collect()
+            }
+            ScalarExpression::Cast { expr, target } => Self::evaluate_values(expr, batch)?
+                .iter()
+                .map(|value| cast_value(value, *target))
+                .collect(),
+            ScalarExpression::Function { function, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| Self::evaluate_values(arg, batch))
+                    .collect::<Result<Vec<_>>>()?;
+                (0..batch.row_count())
+                    .map(|row| {
+                        let row_args: Vec<Value> =
+                            args.iter().map(|values| values[row].clone()).collect();
+                        function.apply(&row_args)
+                    })
+                    .collect()
+            }
+            ScalarExpression::Case {
+                branches,
+                else_expr,
+            } => {
+                let column_types: Vec<DataType> =
+                    batch.columns().iter().map(|col| col.data_type()).collect();
+                let data_type = Self::expression_type(expr, &column_types)?;
+                let matches = branches
+                    .iter()
+                    .map(|branch| branch.condition.bind(&column_types)?.eval_batch(batch))
+                    .collect::<Result<Vec<_>>>()?;
+                (0..batch.row_count())
+                    .map(|row| {
+                        let result = branches
+                            .iter()
+                            .zip(&matches)
+                            .find(|(_, matched)| matched[row])
+                            .map(|(branch, _)| &branch.result)
+                            .or(else_expr.as_deref());
+                        match result {
+                            Some(result) => {
+                                cast_value(&Self::evaluate_value(result, batch, row)?, data_type)
+                            }
+                            None => Ok(Value::Null),
+                        }
+                    })
+                    .collect()
+            }
         }
+    }
 
-        // Open child operator
-        self.child.open()?;
+    fn evaluate_value(expr: &ScalarExpression, batch: &Batch, row_index: usize) -> Result<Value> {
+        match expr {
+            ScalarExpression::Column(index) => batch.get(row_index, *index),
+            ScalarExpression::Literal(value) => Ok(value.clone()),
+            ScalarExpression::Negate(operand) => {
+                negate(&Self::evaluate_value(operand, batch, row_index)?)
+            }
+            ScalarExpression::Arithmetic { left, op, right } => apply_arithmetic(
+                *op,
+                &Self::evaluate_value(left, batch, row_index)?,
+                &Self::evaluate_value(right, batch, row_index)?,
+            ),
+            ScalarExpression::Cast { expr, target } => {
+                cast_value(&Self::evaluate_value(expr, batch, row_index)?, *target)
+            }
+            ScalarExpression::Function { function, args } => function.apply(
+                &args
+                    .iter()
+                    .map(|arg| Self::evaluate_value(arg, batch, row_index))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            ScalarExpression::Case {
+                branches,
+                else_expr,
+            } => {
+                let column_types: Vec<DataType> =
+                    batch.columns().iter().map(|col| col.data_type()).collect();
+                let data_type = Self::expression_type(expr, &column_types)?;
+                for branch in branches {
+                    if branch
+                        .condition
+                        .bind(&column_types)?
+                        .eval(batch, row_index)?
+                    {
+                        let value = Self::evaluate_value(&branch.result, batch, row_index)?;
+                        return cast_value(&value, data_type);
+                    }
+                }
+                match else_expr {
+                    Some(else_expr) => cast_value(
+                        &Self::evaluate_value(else_expr, batch, row_index)?,
+                        data_type,
+                    ),
+                    None => Ok(Value::Null),
+                }
+            }
+        }
+    }
+}
 
-        // Read all data from child
-        let mut all_rows: Vec<Vec<Value>> = Vec::new();
-        let mut all_batches: Vec<Batch> = Vec::new();
+/// Negate a numeric value; NULL stays NULL.
+fn negate(value: &Value) -> Result<Value> {
+    match value {
+        Value::Null => Ok(Value::Null),
+        Value::Int64(v) => v
+            .checked_neg()
+            .map(Value::Int64)
+            .ok_or_else(|| ExecutionError::Custom(format!("integer overflow evaluating -{}", v))),
+        Value::Float64(v) => Ok(Value::Float64(-v)),
+        Value::String(_) | Value::Boolean(_) | Value::Date(_) => Err(
+            ExecutionError::SchemaMismatch(format!("cannot negate a {}", value.type_name())),
+        ),
+    }
+}
 
-        while let Some(batch) = self.child.next_batch()? {
-            all_batches.push(batch);
+/// Apply an arithmetic operator to two values.
+fn apply_arithmetic(op: ArithmeticOp, left: &Value, right: &Value) -> Result<Value> {
+    match (left, right) {
+        (Value::Null, _) | (_, Value::Null) => Ok(Value::Null),
+        (Value::Int64(a), Value::Int64(b)) => {
+            let result = match op {
+                ArithmeticOp::Add => a.checked_add(*b),
+                ArithmeticOp::Subtract => a.checked_sub(*b),
+                ArithmeticOp::Multiply => a.checked_mul(*b),
+                ArithmeticOp::Divide if *b == 0 => return Ok(Value::Null),
+                ArithmeticOp::Divide => a.checked_div(*b),
+            };
+            result.map(Value::Int64).ok_or_else(|| {
+                ExecutionError::Custom(format!("integer overflow evaluating {} {} {}", a, op, b))
+            })
         }
+        _ => match (as_f64(left), as_f64(right)) {
+            (Some(a), Some(b)) => Ok(match op {
+                ArithmeticOp::Add => Value::Float64(a + b),
+                ArithmeticOp::Subtract => Value::Float64(a - b),
+                ArithmeticOp::Multiply => Value::Float64(a * b),
+                ArithmeticOp::Divide if b == 0.0 => Value::Null,
+                ArithmeticOp::Divide => Value::Float64(a / b),
+            }),
+            _ => Err(ExecutionError::SchemaMismatch(format!(
+                "cannot apply '{}' to {} and {}",
+                op,
+                left.type_name(),
+                right.type_name()
+            ))),
+        },
+    }
+}
 
-        // Flatten all batches into rows
-        for batch in &all_batches {
-            for row_idx in 0..batch.row_count() {
-                let mut row = Vec::new();
-                for col_idx in 0..batch.column_count() {
-                    row.push(batch.get(row_idx, col_idx)?);
-                }
-                all_rows.push(row);
+/// Whether values of one type can be cast to another.
+fn can_cast(source: DataType, target: DataType) -> bool {
+    source == target
+        || target == DataType::String
+        || source == DataType::String
+        || (source.is_numeric() && target.is_numeric())
+}
+
+/// Convert a value to the target type; NULL stays NULL.
+///
+/// Float64 to Int64 rounds to the nearest integer and fails for NaN or a
+/// value out of range. Strings are trimmed before parsing and fail with an
+/// error naming the value if they do not parse.
+fn cast_value(value: &Value, target: DataType) -> Result<Value> {
+    let fail = || {
+        ExecutionError::Custom(format!(
+            "cannot cast {} '{}' to {}",
+            value.type_name(),
+            value,
+            target
+        ))
+    };
+    match (value, target) {
+        (Value::Null, _) => Ok(Value::Null),
+        (_, DataType::String) => Ok(Value::String(value.to_string())),
+        (Value::Int64(v), DataType::Int64) => Ok(Value::Int64(*v)),
+        (Value::Int64(v), DataType::Float64) => Ok(Value::Float64(*v as f64)),
+        (Value::Float64(v), DataType::Float64) => Ok(Value::Float64(*v)),
+        (Value::Float64(v), DataType::Int64) => {
+            let rounded = v.round();
+            // i64::MAX as f64 rounds up to 2^63, which is already out of range
+            if rounded.is_nan() || rounded < i64::MIN as f64 || rounded >= i64::MAX as f64 {
+                Err(fail())
+            } else {
+                Ok(Value::Int64(rounded as i64))
+            }
+        }
+        (Value::Boolean(v), DataType::Boolean) => Ok(Value::Boolean(*v)),
+        (Value::Date(days), DataType::Date) => Ok(Value::Date(*days)),
+        (Value::String(text), _) => {
+            let text = text.trim();
+            match target {
+                DataType::Int64 => text.parse().map(Value::Int64).map_err(|_| fail()),
+                DataType::Float64 => text.parse().map(Value::Float64).map_err(|_| fail()),
+                DataType::Boolean => parse_boolean(text).map(Value::Boolean).ok_or_else(fail),
+                DataType::Date => parse_date(text).map(Value::Date).ok_or_else(fail),
+                DataType::String => unreachable!("handled above"),
             }
         }
+        _ => Err(ExecutionError::SchemaMismatch(format!(
+            "cannot cast {} to {}",
+            value.type_name(),
+            target
+        ))),
+    }
+}
+
+/// A numeric value widened to f64, or None for non-numeric values and NULL.
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int64(v) => Some(*v as f64),
+        Value::Float64(v) => Some(*v),
+        Value::String(_) | Value::Boolean(_) | Value::Date(_) | Value::Null => None,
+    }
+}
+
+/// Comparison predicate between two computed expressions.
+///
+/// Used for conditions such as `salary - bonus > 1000` that are not a plain
+/// column compared with a constant. Int64 and Float64 operands compare
+/// numerically, Strings and Booleans only with their own type, and a NULL on
+/// either side is never true.
+#[derive(Debug, Clone)]
+pub struct ExpressionComparison {
+    left: ExpressionEvaluator,
+    op: ComparisonOp,
+    right: ExpressionEvaluator,
+}
+
+impl ExpressionComparison {
+    /// Create a new expression comparison predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - The expression on the left of the operator
+    /// * `op` - The comparison operator to use
+    /// * `right` - The expression on the right of the operator
+    pub fn new(left: ScalarExpression, op: ComparisonOp, right: ScalarExpression) -> Self {
+        ExpressionComparison {
+            left: ExpressionEvaluator::new(left),
+            op,
+            right: ExpressionEvaluator::new(right),
+        }
+    }
+}
 
-        // Sort the rows
-        all_rows.sort_by(|row_a, row_b| {
-            for (col_idx, direction) in self.sort_columns.iter().zip(self.sort_directions.iter()) {
-                let val_a = &row_a[*col_idx];
-                let val_b = &row_b[*col_idx];
-
-                let cmp = match (val_a, val_b) {
-                    (Value::Int64(a), Value::Int64(b)) => a.cmp(b),
-                    (Value::Float64(a), Value::Float64(b)) => {
-                        // Use total_cmp for float comparison to handle NaN properly
-                        a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
-                    }
-                    (Value::String(a), Value::String(b)) => a.cmp(b),
-                    (a, b) => {
-                        // Different types - compare data types as fallback
-                        a.data_type().cmp(&b.data_type())
-                    }
-                };
+impl Predicate for ExpressionComparison {
+    fn eval(&self, batch: &Batch, row_index: usize) -> Result<bool> {
+        let left = self.left.evaluate_row(batch, row_index)?;
+        let right = self.right.evaluate_row(batch, row_index)?;
+        match (&left, &right) {
+            (Value::Null, _) | (_, Value::Null) => Ok(false),
+            (Value::Int64(a), Value::Int64(b)) => Ok(self.op.test_fn()(a, b)),
+            (Value::String(a), Value::String(b)) => Ok(self.op.test_fn()(a.as_str(), b.as_str())),
+            (Value::Boolean(a), Value::Boolean(b)) => Ok(self.op.test_fn()(a, b)),
+            (Value::Date(a), Value::Date(b)) => Ok(self.op.test_fn()(a, b)),
+            _ => match (as_f64(&left), as_f64(&right)) {
+                (Some(a), Some(b)) => Ok(self.op.test_fn()(&a, &b)),
+                _ => Err(ExecutionError::SchemaMismatch(format!(
+                    "cannot compare {} with {} using '{}'",
+                    left.type_name(),
+                    right.type_name(),
+                    self.op
+                ))),
+            },
+        }
+    }
 
-                if cmp != std::cmp::Ordering::Equal {
-                    // Reverse if descending
-                    return if *direction == SortDirection::Descending {
-                        cmp.reverse()
-                    } else {
-                        cmp
-                    };
+    fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+        // Two columns encoded with the same dictionary are equal exactly
+        // when their codes are
+        if let (ScalarExpression::Column(left), ScalarExpression::Column(right)) =
+            (&self.left.expression, &self.right.expression)
+        {
+            let (left, right) = (batch.column(*left)?, batch.column(*right)?);
+            let ((left, left_rows), (right, right_rows)) =
+                (resolve_slice(left.as_ref()), resolve_slice(right.as_ref()));
+            let dictionaries = (
+                left.as_any().downcast_ref::<DictStringColumn>(),
+                right.as_any().downcast_ref::<DictStringColumn>(),
+            );
+            if let (Some(l), Some(r)) = dictionaries {
+                let test = match self.op {
+                    ComparisonOp::Equal if l.shares_dictionary(r) => Some(true),
+                    ComparisonOp::NotEqual if l.shares_dictionary(r) => Some(false),
+                    _ => None,
+                };
+                if let Some(equal) = test {
+                    return Ok(left_rows
+                        .iter()
+                        .zip(right_rows.iter())
+                        .map(|(lrow, rrow)| {
+                            !l.is_null(lrow)
+                                && !r.is_null(rrow)
+                                && (l.codes()[lrow] == r.codes()[rrow]) == equal
+                        })
+                        .collect());
                 }
             }
+        }
+        eval_each_row(self, batch)
+    }
+
+    fn bind(&self, column_types: &[DataType]) -> Result<Arc<dyn Predicate>> {
+        let left_type = self.left.data_type(column_types)?;
+        let right_type = self.right.data_type(column_types)?;
+        let comparable =
+            left_type == right_type || (left_type.is_numeric() && right_type.is_numeric());
+        if !comparable {
+            return Err(ExecutionError::SchemaMismatch(format!(
+                "cannot compare {} with {} using '{}'",
+                left_type, right_type, self.op
+            )));
+        }
+        Ok(Arc::new(self.clone()))
+    }
 
-            std::cmp::Ordering::Equal
-        });
+    fn describe(&self, column_names: &[String]) -> String {
+        format!(
+            "{} {} {}",
+            self.left.expression.describe(column_names),
+            self.op,
+            self.right.expression.describe(column_names)
+        )
+    }
+}
 
-        // Convert sorted rows back to columns
-        if all_rows.is_empty() {
-            self.sorted_data = Some(Batch::empty());
-        } else {
-            let col_count = all_rows[0].len();
-            let mut columns: Vec<Vec<Value>> = vec![Vec::new(); col_count];
+/// The Compute operator appends computed expression columns to its input.
+///
+/// Each output batch holds the child's columns followed by one column per
+/// expression, so a Project above it can pick computed and plain columns
+/// alike.
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::execution::{Compute, ExpressionEvaluator, ScalarExpression};
+///
+/// // Append salary * 2 as a new column named "salary * 2"
+/// let compute = Compute::new(scan, vec![("salary * 2".to_string(), evaluator)]);
+/// ```
+pub struct Compute {
+    /// The child operator to read data from
+    child: Box<dyn Operator>,
 
-            for row in &all_rows {
-                for (col_idx, value) in row.iter().enumerate() {
-                    columns[col_idx].push(value.clone());
-                }
-            }
+    /// Output names and evaluators of the appended columns
+    expressions: Vec<(String, ExpressionEvaluator)>,
 
-            // Convert to actual column types
-            let schema = self.child.schema()?;
-            let column_names = self.child.column_names()?;
-            let mut final_columns: Vec<Arc<dyn Column>> = Vec::new();
+    /// Operator state
+    state: OperatorState,
 
-            for (i, col_name) in column_names.iter().enumerate() {
-                let data_type = &schema[col_name];
-                let values = &columns[i];
+    /// Output schema, resolved in open() and attached to every batch
+    output_schema: Option<Arc<Schema>>,
+}
 
-                let column: Arc<dyn Column> = match data_type {
-                    DataType::Int64 => {
-                        let mut int_col = crate::column::IntColumn::new();
-                        for value in values {
-                            int_col.push_value(value.clone())?;
-                        }
-                        Arc::new(int_col)
-                    }
-                    DataType::Float64 => {
-                        let mut float_col = crate::column::FloatColumn::new();
-                        for value in values {
-                            float_col.push_value(value.clone())?;
-                        }
-                        Arc::new(float_col)
-                    }
-                    DataType::String => {
-                        let mut string_col = crate::column::StringColumn::new();
-                        for value in values {
-                            string_col.push_value(value.clone())?;
-                        }
-                        Arc::new(string_col)
-                    }
-                };
+impl Compute {
+    /// Create a new Compute operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - The child operator to read data from
+    /// * `expressions` - Name and evaluator for each column to append
+    pub fn new(child: Box<dyn Operator>, expressions: Vec<(String, ExpressionEvaluator)>) -> Self {
+        Compute {
+            child,
+            expressions,
+            state: OperatorState::NotOpen,
+            output_schema: None,
+        }
+    }
+}
 
-                final_columns.push(column);
-            }
+impl Operator for Compute {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.child.set_cancellation(token);
+    }
 
-            self.sorted_data = Some(Batch::new(final_columns));
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
         }
 
-        self.current_row = 0;
-        self.state = OperatorState::Open;
+        self.child.open()?;
+
+        // Type each expression against the child's columns once
+        let schema = self.child.schema()?;
+        let column_types = schema.data_types();
+        let mut fields = schema.fields().to_vec();
+        for (name, evaluator) in &self.expressions {
+            fields.push(Field::new(
+                name.clone(),
+                evaluator.data_type(&column_types)?,
+            ));
+        }
 
+        // Rejects an expression named like an input column or another one
+        self.output_schema = Some(Arc::new(Schema::new(fields)?));
+        self.state = OperatorState::Open;
         Ok(())
     }
 
@@ -1994,1217 +4510,6630 @@ impl Operator for Sort {
             return Err(ExecutionError::OperatorNotOpen);
         }
 
-        let sorted_data = self.sorted_data.as_ref().unwrap();
-        let total_rows = sorted_data.row_count();
-
-        if self.current_row >= total_rows {
-            return Ok(None);
-        }
-
-        let end_row = std::cmp::min(self.current_row + self.batch_size, total_rows);
-        let _batch_size = end_row - self.current_row;
-
-        // Create a batch with the rows for this batch
-        let mut batch_columns: Vec<Arc<dyn Column>> = Vec::new();
-        let column_count = sorted_data.column_count();
-
-        for col_idx in 0..column_count {
-            let original_column = sorted_data.column(col_idx)?;
-
-            // Extract rows for this batch
-            let batch_data: Vec<Value> = (self.current_row..end_row)
-                .map(|row_idx| {
-                    original_column
-                        .get(row_idx)
-                        .map_err(|e| ExecutionError::Custom(e.to_string()))
-                })
-                .collect::<Result<Vec<_>>>()?;
-
-            let data_type = original_column.data_type();
-            let batch_column: Arc<dyn Column> = match data_type {
-                DataType::Int64 => {
-                    let mut int_col = crate::column::IntColumn::new();
-                    for value in batch_data {
-                        int_col.push_value(value)?;
-                    }
-                    Arc::new(int_col)
-                }
-                DataType::Float64 => {
-                    let mut float_col = crate::column::FloatColumn::new();
-                    for value in batch_data {
-                        float_col.push_value(value)?;
-                    }
-                    Arc::new(float_col)
-                }
-                DataType::String => {
-                    let mut string_col = crate::column::StringColumn::new();
-                    for value in batch_data {
-                        string_col.push_value(value)?;
-                    }
-                    Arc::new(string_col)
-                }
-            };
+        let batch = match self.child.next_batch()? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
 
-            batch_columns.push(batch_column);
+        let mut columns = batch.columns().to_vec();
+        for (_, evaluator) in &self.expressions {
+            columns.push(evaluator.evaluate(&batch)?);
         }
-
-        self.current_row = end_row;
-
-        Ok(Some(Batch::new(batch_columns)))
+        let schema = self
+            .output_schema
+            .clone()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+        Ok(Some(Batch::try_new(columns)?.with_schema(schema)?))
     }
 
     fn close(&mut self) -> Result<()> {
         self.state = OperatorState::Closed;
-        self.child.close()
-    }
-
-    fn schema(&self) -> Result<std::collections::HashMap<String, DataType>> {
-        self.child.schema()
+        self.child.close()?;
+        Ok(())
     }
 
-    fn column_names(&self) -> Result<Vec<String>> {
-        self.child.column_names()
+    fn schema(&self) -> Result<Schema> {
+        self.output_schema
+            .as_deref()
+            .cloned()
+            .ok_or(ExecutionError::SchemaNotFound)
     }
 
     fn is_open(&self) -> bool {
         self.state == OperatorState::Open
     }
-}
 
-// ============================================================================
-// LIMIT OPERATOR (LIMIT/OFFSET)
-// ============================================================================
+    fn describe(&self) -> String {
+        let names: Vec<&str> = self
+            .expressions
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        format!("Compute({})", names.join(", "))
+    }
 
-/// Limit operator for LIMIT/OFFSET clause.
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.child]
+    }
+}
+
+// ============================================================================
+// GROUP BY OPERATOR
+// ============================================================================
+
+/// A key for grouping rows in a GroupBy operation.
 ///
-/// Limit operator skips a specified number of rows (OFFSET) and then
-/// returns up to a specified number of rows (LIMIT). This operator can
-/// stop reading from the child operator once it has read enough rows.
-pub struct Limit {
+/// The key is a vector of values representing the group by columns.
+/// It implements Hash and Eq for use as a HashMap key. Values are equal
+/// only when they have the same type and value; neither allocates.
+///
+/// Floats are compared and hashed by their [canonical bits](canonical_float_bits),
+/// so `0.0` and `-0.0` share a group, as do all NaNs whatever their payload.
+/// Each group keeps the key value of its first row.
+#[derive(Debug, Clone)]
+struct GroupKey(Vec<Option<Value>>);
+
+impl PartialEq for GroupKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(a, b)| match (a, b) {
+                    (None, None) => true,
+                    (Some(va), Some(vb)) => group_values_equal(va, vb),
+                    (None, Some(_)) | (Some(_), None) => false,
+                })
+    }
+}
+
+/// Typed equality for group key values: an Int64 never equals a Float64 or
+/// a String that prints the same, and floats are equal when their canonical
+/// bits are.
+fn group_values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Int64(a), Value::Int64(b)) => a == b,
+        (Value::Float64(a), Value::Float64(b)) => {
+            canonical_float_bits(*a) == canonical_float_bits(*b)
+        }
+        (Value::String(a), Value::String(b)) => a == b,
+        (Value::Boolean(a), Value::Boolean(b)) => a == b,
+        (Value::Date(a), Value::Date(b)) => a == b,
+        (Value::Null, Value::Null) => true,
+        _ => false,
+    }
+}
+
+impl Eq for GroupKey {}
+
+impl Hash for GroupKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in &self.0 {
+            match value {
+                None => 0.hash(state),
+                Some(v) => {
+                    // Hash based on value type and value
+                    match v {
+                        Value::Int64(i) => (1, i).hash(state),
+                        Value::Float64(f) => (2, canonical_float_bits(*f)).hash(state),
+                        Value::String(s) => (3, s).hash(state),
+                        Value::Null => 4.hash(state),
+                        Value::Boolean(b) => (5, b).hash(state),
+                        Value::Date(d) => (6, d).hash(state),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Bytes counted for each aggregate state of a group; the states are trait
+/// objects of unknown size, so this is a flat estimate
+const AGGREGATE_STATE_BYTES: usize = 64;
+
+/// Approximate bytes one group occupies in a [`GroupTable`]: its key, which
+/// is stored twice, its position, and its aggregate states.
+fn group_memory_size(key: &GroupKey, aggregates: usize) -> usize {
+    let key_bytes: usize = key
+        .0
+        .iter()
+        .map(|value| {
+            std::mem::size_of::<Option<Value>>()
+                + match value {
+                    Some(Value::String(s)) => s.len(),
+                    _ => 0,
+                }
+        })
+        .sum();
+    2 * key_bytes + std::mem::size_of::<usize>() + aggregates * AGGREGATE_STATE_BYTES
+}
+
+/// Aggregate state of each group, kept in the order the groups were first
+/// seen.
+///
+/// A hash map from key to position finds a row's group; the groups live in
+/// a Vec, so output order depends only on the input order and never on
+/// hashing.
+struct GroupTable {
+    /// Position in `groups` of each key
+    positions: HashMap<GroupKey, usize>,
+
+    /// Each group's key and aggregate states, in first-seen order
+    groups: Vec<(GroupKey, Vec<Box<dyn crate::aggregates::AggregateFunction>>)>,
+
+    /// Approximate bytes held by the groups, see [`group_memory_size`]
+    bytes: usize,
+}
+
+impl GroupTable {
+    fn new() -> Self {
+        GroupTable {
+            positions: HashMap::new(),
+            groups: Vec::new(),
+            bytes: 0,
+        }
+    }
+
+    /// The aggregate states of `key`'s group, created by `init` the first
+    /// time the key is seen.
+    fn states_mut(
+        &mut self,
+        key: GroupKey,
+        init: impl FnOnce() -> Vec<Box<dyn crate::aggregates::AggregateFunction>>,
+    ) -> &mut Vec<Box<dyn crate::aggregates::AggregateFunction>> {
+        let position = match self.positions.get(&key) {
+            Some(&position) => position,
+            None => {
+                let states = init();
+                self.bytes += group_memory_size(&key, states.len());
+                self.positions.insert(key.clone(), self.groups.len());
+                self.groups.push((key, states));
+                self.groups.len() - 1
+            }
+        };
+        &mut self.groups[position].1
+    }
+
+    /// Approximate bytes held by the groups.
+    fn memory_size(&self) -> usize {
+        self.bytes
+    }
+
+    fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Groups in first-seen order.
+    fn iter(
+        &self,
+    ) -> impl Iterator<Item = &(GroupKey, Vec<Box<dyn crate::aggregates::AggregateFunction>>)> {
+        self.groups.iter()
+    }
+}
+
+/// Fold one input row into a group's aggregate states.
+///
+/// A failing update is reported as an `AggregateError` naming its column
+/// in `input`.
+fn update_group_states(
+    states: &mut [Box<dyn crate::aggregates::AggregateFunction>],
+    batch: &Batch,
+    row_index: usize,
+    aggregate_columns: &[usize],
+    input: &Schema,
+) -> Result<()> {
+    // NULLs are passed as None so COUNT(*) can still count them
+    for (state, &col_index) in states.iter_mut().zip(aggregate_columns) {
+        let value = batch.get(row_index, col_index)?;
+        state
+            .update((!value.is_null()).then_some(value))
+            .map_err(|e| ExecutionError::AggregateError {
+                column: input.fields()[col_index].name.clone(),
+                source: Box::new(e),
+            })?;
+    }
+    Ok(())
+}
+
+/// GroupBy operator for grouping rows and computing aggregates.
+///
+/// GroupBy reads all rows from a child operator, groups them by specified
+/// columns, and computes aggregates for each group. The output contains
+/// one row per group with the group by keys followed by the aggregate results.
+///
+/// Rows are folded into per-group aggregate state as they stream in, so
+/// memory grows with the number of groups rather than the number of rows.
+///
+/// Groups are output in the order their first row arrived, so the same
+/// input always gives the same output, and a LIMIT without ORDER BY keeps
+/// the earliest groups.
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::execution::{Operator, TableScan, GroupBy};
+/// use mini_rust_olap::aggregates::CountAggregate;
+/// use mini_rust_olap::types::DataType;
+///
+/// let scan = Box::new(TableScan::new(table).unwrap());
+/// let group_by = Box::new(GroupBy::new(
+///     scan,
+///     vec![0],  // Group by first column
+///     vec![1],  // Aggregate second column
+///     vec![Box::new(CountAggregate::new(DataType::Int64))]
+/// ));
+/// ```
+pub struct GroupBy {
     /// The child operator to read data from
     child: Box<dyn Operator>,
 
-    /// Maximum number of rows to return
-    limit: usize,
-
-    /// Number of rows to skip
-    offset: usize,
+    /// Indices of columns to group by
+    group_by_columns: Vec<usize>,
 
-    /// Number of rows already returned
-    rows_returned: usize,
+    /// Indices of columns to aggregate
+    aggregate_columns: Vec<usize>,
 
-    /// Number of rows already skipped
-    rows_skipped: usize,
+    /// Aggregates to compute, cloned as fresh state for each group
+    aggregates: Vec<Box<dyn crate::aggregates::AggregateFunction>>,
 
     /// Operator state
     state: OperatorState,
+
+    /// Output schema: the group by columns followed by the aggregates
+    output_schema: Option<Arc<Schema>>,
+
+    /// Data type of each group by column, resolved in open()
+    group_by_types: Vec<DataType>,
+
+    /// Aggregate state of each group (computed during open())
+    grouped_data: Option<GroupTable>,
+
+    /// Whether results have been returned
+    results_returned: bool,
+
+    /// Checked after each input batch is aggregated
+    cancellation: CancellationToken,
+
+    /// The groups' memory, counted after each input batch is aggregated
+    memory: MemoryReservation,
 }
 
-impl Limit {
-    /// Create a new Limit operator.
+impl GroupBy {
+    /// Create a new GroupBy operator.
     ///
     /// # Arguments
     ///
     /// * `child` - The child operator to read data from
-    /// * `limit` - Maximum number of rows to return (if None, return all rows after offset)
-    /// * `offset` - Number of rows to skip
+    /// * `group_by_columns` - Indices of columns to group by
+    /// * `aggregates` - Vector of aggregate functions to compute for each group
     ///
     /// # Example
     ///
-    /// ```rust
-    /// # use mini_rust_olap::execution::Limit;
-    /// # use mini_rust_olap::execution::TableScan;
-    /// # use mini_rust_olap::execution::Operator;
-    /// # use mini_rust_olap::table::Table;
-    /// use std::sync::Arc;
-    ///
-    /// let table = Table::new("users".to_string());
-    /// // ... add columns to table ...
-    ///
-    /// let scan = TableScan::new(table);
-    /// let mut limit = Limit::new(Box::new(scan), Some(5), 10);
+    /// ```ignore
+    /// use mini_rust_olap::execution::{Operator, TableScan, GroupBy};
+    /// use mini_rust_olap::aggregates::CountAggregate;
+    /// use mini_rust_olap::types::DataType;
     ///
-    /// limit.open().unwrap();
-    /// while let Some(batch) = limit.next_batch().unwrap() {
-    ///     // Process limited batches
-    /// }
-    /// limit.close().unwrap();
+    /// let scan = Box::new(TableScan::new(table).unwrap());
+    /// let group_by = Box::new(GroupBy::new(
+    ///     scan,
+    ///     vec![0, 1],  // Group by first two columns
+    ///     vec![2],     // Aggregate third column
+    ///     vec![Box::new(CountAggregate::new(DataType::Int64))]
+    /// ));
     /// ```
-    pub fn new(child: Box<dyn Operator>, limit: Option<usize>, offset: usize) -> Self {
-        Limit {
+    pub fn new(
+        child: Box<dyn Operator>,
+        group_by_columns: Vec<usize>,
+        aggregate_columns: Vec<usize>,
+        aggregates: Vec<Box<dyn crate::aggregates::AggregateFunction>>,
+    ) -> Self {
+        GroupBy {
             child,
-            limit: limit.unwrap_or(usize::MAX),
-            offset,
-            rows_returned: 0,
-            rows_skipped: 0,
+            group_by_columns,
+            aggregate_columns,
+            aggregates,
             state: OperatorState::NotOpen,
+            output_schema: None,
+            group_by_types: Vec::new(),
+            grouped_data: None,
+            results_returned: false,
+            cancellation: CancellationToken::default(),
+            memory: MemoryReservation::default(),
         }
     }
 }
 
-impl Operator for Limit {
+impl Operator for GroupBy {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.cancellation = token.clone();
+        self.child.set_cancellation(token);
+    }
+
+    fn set_memory_tracker(&mut self, tracker: &Arc<MemoryTracker>) {
+        self.memory.set_tracker(tracker);
+        self.child.set_memory_tracker(tracker);
+    }
+
     fn open(&mut self) -> Result<()> {
         if self.state == OperatorState::Open {
             return Err(ExecutionError::OperatorAlreadyOpen);
         }
 
+        // Open the child operator
         self.child.open()?;
-        self.rows_returned = 0;
-        self.rows_skipped = 0;
-        self.state = OperatorState::Open;
 
-        Ok(())
-    }
+        // Get child schema
+        let child_schema = self.child.schema()?;
+        let child_column_count = child_schema.len();
 
-    fn next_batch(&mut self) -> Result<Option<Batch>> {
-        if self.state != OperatorState::Open {
-            return Err(ExecutionError::OperatorNotOpen);
+        // Validate group by column indices
+        for &index in &self.group_by_columns {
+            if index >= child_column_count {
+                return Err(ExecutionError::InvalidColumnIndex {
+                    index,
+                    count: child_column_count,
+                });
+            }
         }
 
-        // If we've already returned enough rows, stop
-        if self.rows_returned >= self.limit {
-            return Ok(None);
+        // Validate aggregate column indices
+        for &index in &self.aggregate_columns {
+            if index >= child_column_count {
+                return Err(ExecutionError::InvalidColumnIndex {
+                    index,
+                    count: child_column_count,
+                });
+            }
         }
 
-        // Get next batch from child
-        let mut batch = match self.child.next_batch()? {
-            Some(b) => b,
-            None => return Ok(None),
-        };
+        // Validate that aggregate_columns length matches aggregates length
+        if self.aggregate_columns.len() != self.aggregates.len() {
+            return Err(ExecutionError::Custom(format!(
+                "aggregate_columns length ({}) must match aggregates length ({})",
+                self.aggregate_columns.len(),
+                self.aggregates.len()
+            )));
+        }
 
-        // Apply offset if we haven't skipped enough rows yet
-        if self.rows_skipped < self.offset {
-            let batch_row_count = batch.row_count();
+        // Build the output schema
+        let mut fields = Vec::with_capacity(self.group_by_columns.len() + self.aggregates.len());
 
-            if self.rows_skipped + batch_row_count <= self.offset {
-                // Entire batch should be skipped
-                self.rows_skipped += batch_row_count;
-                return self.next_batch();
-            } else {
-                // Need to skip part of this batch
-                let skip_count = self.offset - self.rows_skipped;
-                batch = batch.skip_rows(skip_count)?;
-                self.rows_skipped += skip_count;
-            }
+        // Add group by columns to output
+        for &index in &self.group_by_columns {
+            fields.push(child_schema.fields()[index].clone());
         }
+        self.group_by_types = fields.iter().map(|field| field.data_type).collect();
 
-        // Apply limit if this batch would exceed our limit
-        let remaining_limit = self.limit - self.rows_returned;
-        if batch.row_count() > remaining_limit {
-            batch = batch.take_rows(remaining_limit)?;
+        // Add aggregates to output
+        for (i, agg) in self.aggregates.iter().enumerate() {
+            fields.push(Field::new(format!("agg_{}", i), agg.data_type()));
         }
 
-        self.rows_returned += batch.row_count();
+        self.output_schema = Some(Arc::new(Schema::new(fields)?));
+
+        // Read all data, folding each row into its group's aggregates
+        let mut grouped_data = GroupTable::new();
+        let operator = self.describe();
+        self.memory.release();
+
+        while let Some(batch) = self
+            .child
+            .next_batch()
+            .map_err(ExecutionError::from_child)?
+        {
+            self.cancellation.check()?;
+            self.memory.resize(&operator, grouped_data.memory_size())?;
+
+            // When every key column is dictionary-encoded, group the batch's
+            // rows by their codes first so each distinct key is built and
+            // hashed as strings once per batch rather than once per row
+            let key_columns = self
+                .group_by_columns
+                .iter()
+                .map(|&col_index| batch.column(col_index))
+                .collect::<Result<Vec<_>>>()?;
+            // Each key is the dictionary column and the batch's rows in it
+            let dictionary_keys: Option<Vec<(&DictStringColumn, RowSelection)>> = key_columns
+                .iter()
+                .map(|column| {
+                    let (column, rows) = resolve_slice(column.as_ref());
+                    let dict = column.as_any().downcast_ref::<DictStringColumn>()?;
+                    Some((dict, rows))
+                })
+                .collect();
+            if let Some(dictionary_keys) = dictionary_keys.filter(|keys| !keys.is_empty()) {
+                // Codes are listed in first-seen order, like the groups
+                let mut code_positions: HashMap<Vec<Option<u32>>, usize> = HashMap::new();
+                let mut rows_by_codes: Vec<(Vec<Option<u32>>, Vec<usize>)> = Vec::new();
+                for row_index in 0..batch.row_count() {
+                    let codes: Vec<Option<u32>> = dictionary_keys
+                        .iter()
+                        .map(|(column, rows)| {
+                            let row = rows.row(row_index);
+                            (!column.is_null(row)).then(|| column.codes()[row])
+                        })
+                        .collect();
+                    let position = *code_positions.entry(codes.clone()).or_insert_with(|| {
+                        rows_by_codes.push((codes, Vec::new()));
+                        rows_by_codes.len() - 1
+                    });
+                    rows_by_codes[position].1.push(row_index);
+                }
 
-        if batch.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(batch))
+                for (codes, rows) in rows_by_codes {
+                    let key_values = dictionary_keys
+                        .iter()
+                        .zip(codes)
+                        .map(|((column, _), code)| {
+                            Some(match code {
+                                Some(code) => {
+                                    Value::String(column.dictionary()[code as usize].clone())
+                                }
+                                None => Value::Null,
+                            })
+                        })
+                        .collect();
+
+                    let states = grouped_data.states_mut(GroupKey(key_values), || {
+                        self.aggregates.iter().map(|agg| agg.clone_box()).collect()
+                    });
+                    for row_index in rows {
+                        update_group_states(
+                            states,
+                            &batch,
+                            row_index,
+                            &self.aggregate_columns,
+                            &child_schema,
+                        )?;
+                    }
+                }
+                continue;
+            }
+
+            for row_index in 0..batch.row_count() {
+                // Build group key
+                let mut key_values = Vec::new();
+                for &col_index in &self.group_by_columns {
+                    let value = batch.get(row_index, col_index)?;
+                    key_values.push(Some(value));
+                }
+
+                let states = grouped_data.states_mut(GroupKey(key_values), || {
+                    self.aggregates.iter().map(|agg| agg.clone_box()).collect()
+                });
+                update_group_states(
+                    states,
+                    &batch,
+                    row_index,
+                    &self.aggregate_columns,
+                    &child_schema,
+                )?;
+            }
         }
+
+        self.memory.resize(&operator, grouped_data.memory_size())?;
+        self.grouped_data = Some(grouped_data);
+        self.results_returned = false;
+        self.state = OperatorState::Open;
+        Ok(())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::OperatorNotOpen);
+        }
+
+        if self.results_returned {
+            return Ok(None);
+        }
+
+        self.results_returned = true;
+
+        let grouped_data = self.grouped_data.as_ref().unwrap();
+
+        // With GROUP BY, no input means no groups and no rows
+        if grouped_data.is_empty() && !self.group_by_columns.is_empty() {
+            return Ok(None);
+        }
+
+        // Prepare output columns
+        let group_by_col_count = self.group_by_columns.len();
+        let agg_col_count = self.aggregates.len();
+        let mut output_columns: Vec<Vec<Option<Value>>> =
+            vec![Vec::new(); group_by_col_count + agg_col_count];
+
+        // Process each group, in first-seen order
+        for (key, states) in grouped_data.iter() {
+            // Add group by values
+            for (col_index, value) in key.0.iter().enumerate() {
+                output_columns[col_index].push(value.clone());
+            }
+
+            // Add aggregate results
+            for (agg_index, state) in states.iter().enumerate() {
+                output_columns[group_by_col_count + agg_index].push(state.result());
+            }
+        }
+
+        // Without GROUP BY the whole input is one group even when it is
+        // empty, so each aggregate gives its result for no rows (COUNT 0,
+        // the others mostly NULL); the prototypes have never been updated
+        if grouped_data.is_empty() {
+            for (agg_index, agg) in self.aggregates.iter().enumerate() {
+                output_columns[agg_index].push(agg.result());
+            }
+        }
+
+        // Convert output columns to actual column types; missing values
+        // (NULL keys, aggregates over no non-NULL input) become NULLs, and
+        // a value of any other type than the column's is an error
+        let schema = self
+            .output_schema
+            .clone()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+        let mut final_columns = Vec::new();
+        for (field, values) in schema.fields().iter().zip(output_columns) {
+            let mut column = crate::column::create_column(field.data_type);
+            for value in values {
+                let value = value.unwrap_or(Value::Null);
+                match value.data_type() {
+                    Some(actual) if actual != field.data_type => {
+                        return Err(ExecutionError::TypeMismatch {
+                            expected: field.data_type,
+                            actual,
+                            column: Some(field.name.clone()),
+                        });
+                    }
+                    _ => column.push_value(value)?,
+                }
+            }
+            final_columns.push(Arc::from(column));
+        }
+
+        Ok(Some(Batch::try_new(final_columns)?.with_schema(schema)?))
     }
 
     fn close(&mut self) -> Result<()> {
         self.state = OperatorState::Closed;
-        self.child.close()
+        self.child.close()?;
+        self.grouped_data = None;
+        self.memory.release();
+        self.results_returned = false;
+        Ok(())
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        self.output_schema
+            .as_deref()
+            .cloned()
+            .ok_or(ExecutionError::SchemaNotFound)
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
+
+    fn describe(&self) -> String {
+        let input = output_names(self.child.as_ref());
+        let keys: Vec<String> = self
+            .group_by_columns
+            .iter()
+            .map(|&index| column_label(&input, index))
+            .collect();
+        let aggregates = match self.aggregates.len() {
+            1 => "1 aggregate".to_string(),
+            count => format!("{} aggregates", count),
+        };
+        if keys.is_empty() {
+            format!("GroupBy({})", aggregates)
+        } else {
+            format!("GroupBy({}; {})", keys.join(", "), aggregates)
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.child]
+    }
+}
+
+// ============================================================================
+// SORT OPERATOR (ORDER BY)
+// ============================================================================
+
+/// Sort operator for ORDER BY clause.
+///
+/// Sort operator reads all data from the child operator and sorts it
+/// according to the specified columns and directions. Since sorting
+/// requires all data, this operator reads all rows in open().
+///
+/// Input batches are buffered as they arrive. To sort them, the ORDER BY
+/// columns are extracted once into typed key arrays (integers, floats, or
+/// strings borrowed from the input columns), a list of row positions is
+/// sorted by comparing those keys, and the output columns are gathered in
+/// the sorted order. No values are cloned while comparing.
+///
+/// The sort is stable: rows that tie on every sort column come out in the
+/// order the child produced them.
+///
+/// With a memory limit (see `with_memory_limit`) the sort runs externally:
+/// whenever the buffered rows outgrow the budget they are sorted and
+/// spilled to a temporary file as a run, and `next_batch` merges the
+/// spilled runs with the rows still in memory. The output is the same as
+/// an in-memory sort, including the input order of rows that tie.
+pub struct Sort {
+    /// The child operator to read data from
+    child: Box<dyn Operator>,
+
+    /// Indices of columns to sort by
+    sort_columns: Vec<usize>,
+
+    /// Sort direction for each column
+    sort_directions: Vec<SortDirection>,
+
+    /// Operator state
+    state: OperatorState,
+
+    /// Sorted data after sorting
+    sorted_data: Option<Batch>,
+
+    /// Current position in sorted data
+    current_row: usize,
+
+    /// Batch size for output
+    batch_size: usize,
+
+    /// Bytes of buffered rows allowed before a run is spilled, if any
+    memory_limit: Option<usize>,
+
+    /// Directory for spilled runs; the system temporary directory if None
+    spill_dir: Option<std::path::PathBuf>,
+
+    /// Merge over the spilled runs, when the input outgrew the budget
+    merge: Option<RunMerge>,
+
+    /// Schema of the output columns, resolved in open()
+    output_schema: Option<Arc<Schema>>,
+
+    /// Checked after each input batch is buffered and before each output
+    /// batch
+    cancellation: CancellationToken,
+
+    /// The rows buffered in memory, as measured for the memory limit
+    memory: MemoryReservation,
+}
+
+impl Sort {
+    /// Create a new Sort operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - The child operator to read data from
+    /// * `sort_columns` - Indices of columns to sort by
+    /// * `sort_directions` - Sort direction for each column (true = ascending, false = descending)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_rust_olap::execution::Sort;
+    /// # use mini_rust_olap::execution::TableScan;
+    /// # use mini_rust_olap::execution::Operator;
+    /// # use mini_rust_olap::types::SortDirection;
+    /// # use mini_rust_olap::table::Table;
+    /// use std::sync::Arc;
+    ///
+    /// let table = Table::new("users".to_string());
+    /// // ... add columns to table ...
+    ///
+    /// let scan = TableScan::new(table);
+    /// let mut sort = Sort::new(
+    ///     Box::new(scan),
+    ///     vec![0, 1],
+    ///     vec![SortDirection::Ascending, SortDirection::Descending],
+    /// );
+    ///
+    /// sort.open().unwrap();
+    /// while let Some(batch) = sort.next_batch().unwrap() {
+    ///     // Process sorted batches
+    /// }
+    /// sort.close().unwrap();
+    /// ```
+    pub fn new(
+        child: Box<dyn Operator>,
+        sort_columns: Vec<usize>,
+        sort_directions: Vec<SortDirection>,
+    ) -> Self {
+        Sort {
+            child,
+            sort_columns,
+            sort_directions,
+            state: OperatorState::NotOpen,
+            sorted_data: None,
+            current_row: 0,
+            batch_size: 1024,
+            memory_limit: None,
+            spill_dir: None,
+            merge: None,
+            output_schema: None,
+            cancellation: CancellationToken::default(),
+            memory: MemoryReservation::default(),
+        }
+    }
+
+    /// Set the batch size for output.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Limit the memory used to buffer input rows.
+    ///
+    /// Rows are measured as their `Value`s plus any string contents; once
+    /// the buffered rows exceed `bytes` they are sorted and spilled to disk
+    /// as one run. The spill files are deleted when the operator is closed
+    /// or dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Budget for buffered rows, in bytes
+    pub fn with_memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Write spilled runs to `dir` instead of the system temporary directory.
+    pub fn with_spill_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.spill_dir = Some(dir.into());
+        self
+    }
+
+    /// Number of runs spilled to disk by the last open(), 0 if the input
+    /// fit in memory.
+    pub fn spilled_runs(&self) -> usize {
+        self.merge.as_ref().map_or(0, |merge| merge.spilled_runs)
+    }
+
+    /// Order the rows of the buffered batches by the sort columns, keeping
+    /// ties in input order.
+    ///
+    /// Returns the `(batch, row)` position of each row in sorted order.
+    fn sorted_positions(&self, batches: &[Batch]) -> Result<Vec<(usize, usize)>> {
+        let positions: Vec<(usize, usize)> = batches
+            .iter()
+            .enumerate()
+            .flat_map(|(b, batch)| (0..batch.row_count()).map(move |row| (b, row)))
+            .collect();
+        let keys = self
+            .sort_columns
+            .iter()
+            .map(|&column| SortKeyColumn::build(batches, column))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut order: Vec<usize> = (0..positions.len()).collect();
+        sort_cancellable(&mut order, &self.cancellation, |&a, &b| {
+            for (key, direction) in keys.iter().zip(&self.sort_directions) {
+                let cmp = key.compare(a, b);
+                if cmp != std::cmp::Ordering::Equal {
+                    return if *direction == SortDirection::Descending {
+                        cmp.reverse()
+                    } else {
+                        cmp
+                    };
+                }
+            }
+            std::cmp::Ordering::Equal
+        })?;
+        Ok(order.into_iter().map(|index| positions[index]).collect())
+    }
+
+    /// Write sorted rows to a new spill file.
+    fn spill_run(&self, rows: &[Vec<Value>]) -> Result<SpilledRun> {
+        let file = match &self.spill_dir {
+            Some(dir) => tempfile::NamedTempFile::new_in(dir)?,
+            None => tempfile::NamedTempFile::new()?,
+        };
+        let mut writer = std::io::BufWriter::new(file.reopen()?);
+        for row in rows {
+            for value in row {
+                write_spilled_value(&mut writer, value)?;
+            }
+        }
+        std::io::Write::flush(&mut writer)?;
+        Ok(SpilledRun {
+            reader: std::io::BufReader::new(file.reopen()?),
+            _file: file,
+        })
+    }
+}
+
+/// Rows sorted, or merged, between cancellation checks in
+/// [`sort_cancellable`]
+const SORT_CANCEL_CHECK_INTERVAL: usize = 1 << 16;
+
+/// Stable-sort `items` with `compare`, stopping with
+/// [`ExecutionError::Cancelled`] once `token` is cancelled.
+///
+/// A single `sort_by` over millions of rows can run for many seconds and
+/// cannot be interrupted, so the items are sorted in chunks of
+/// [`SORT_CANCEL_CHECK_INTERVAL`], like the runs of a spilling [`Sort`],
+/// and the sorted chunks merged pairwise. The token is checked before each
+/// chunk and every [`SORT_CANCEL_CHECK_INTERVAL`] rows merged. A cancelled
+/// sort leaves `items` in some order.
+fn sort_cancellable<T: Clone>(
+    items: &mut [T],
+    token: &CancellationToken,
+    mut compare: impl FnMut(&T, &T) -> std::cmp::Ordering,
+) -> Result<()> {
+    for chunk in items.chunks_mut(SORT_CANCEL_CHECK_INTERVAL) {
+        token.check()?;
+        chunk.sort_by(&mut compare);
+    }
+
+    let len = items.len();
+    let mut merged = Vec::with_capacity(len.min(2 * SORT_CANCEL_CHECK_INTERVAL));
+    let mut width = SORT_CANCEL_CHECK_INTERVAL;
+    while width < len {
+        for start in (0..len).step_by(2 * width) {
+            let (middle, end) = ((start + width).min(len), (start + 2 * width).min(len));
+            let (left, right) = items[start..end].split_at(middle - start);
+            let (mut l, mut r) = (0, 0);
+            merged.clear();
+            while l < left.len() && r < right.len() {
+                if merged.len() % SORT_CANCEL_CHECK_INTERVAL == 0 {
+                    token.check()?;
+                }
+                // Ties take the left run's row first, keeping the sort stable
+                if compare(&right[r], &left[l]) == std::cmp::Ordering::Less {
+                    merged.push(right[r].clone());
+                    r += 1;
+                } else {
+                    merged.push(left[l].clone());
+                    l += 1;
+                }
+            }
+            merged.extend_from_slice(&left[l..]);
+            merged.extend_from_slice(&right[r..]);
+            items[start..end].clone_from_slice(&merged);
+        }
+        width *= 2;
+    }
+    Ok(())
+}
+
+/// Compare two rows in ORDER BY order on the given columns.
+fn compare_rows(
+    sort_columns: &[usize],
+    sort_directions: &[SortDirection],
+    row_a: &[Value],
+    row_b: &[Value],
+) -> std::cmp::Ordering {
+    for (col_idx, direction) in sort_columns.iter().zip(sort_directions.iter()) {
+        let cmp = compare_sort_values(&row_a[*col_idx], &row_b[*col_idx]);
+
+        if cmp != std::cmp::Ordering::Equal {
+            // Reverse if descending
+            return if *direction == SortDirection::Descending {
+                cmp.reverse()
+            } else {
+                cmp
+            };
+        }
+    }
+
+    std::cmp::Ordering::Equal
+}
+
+/// Approximate bytes a buffered batch occupies, counted against a Sort's
+/// memory limit: each row as a `Vec<Value>`, plus the contents of its
+/// strings.
+fn batch_memory_size(batch: &Batch) -> usize {
+    let row_size =
+        std::mem::size_of::<Vec<Value>>() + batch.column_count() * std::mem::size_of::<Value>();
+    let string_bytes: usize = batch
+        .columns()
+        .iter()
+        .filter(|column| column.data_type() == DataType::String)
+        .map(|column| {
+            let (base, rows) = resolve_slice(column.as_ref());
+            match base.as_any().downcast_ref::<StringColumn>() {
+                Some(strings) => strings.values_in(rows).map(String::len).sum::<usize>(),
+                None => (0..column.len())
+                    .map(|row| match column.get(row) {
+                        Ok(Value::String(s)) => s.len(),
+                        _ => 0,
+                    })
+                    .sum(),
+            }
+        })
+        .sum();
+    batch.row_count() * row_size + string_bytes
+}
+
+/// One ORDER BY column of the rows buffered by a [`Sort`], indexed by the
+/// row's position across all buffered batches.
+///
+/// Keys are extracted once before sorting, so each comparison reads a
+/// plain integer, float, or string slice. `None` is NULL, which sorts
+/// first, as in [`compare_sort_values`].
+enum SortKeyColumn<'a> {
+    /// Int64, Date, and Boolean columns
+    Int64(Vec<Option<i64>>),
+    /// Float64 columns
+    Float64(Vec<Option<f64>>),
+    /// String columns, borrowed from the input where the column stores them
+    String(Vec<Option<std::borrow::Cow<'a, str>>>),
+}
+
+impl<'a> SortKeyColumn<'a> {
+    /// Extract column `column` of every batch, in order.
+    fn build(batches: &'a [Batch], column: usize) -> Result<Self> {
+        let columns = batches
+            .iter()
+            .map(|batch| {
+                batch
+                    .columns()
+                    .get(column)
+                    .map(|column| resolve_slice(column.as_ref()))
+                    .ok_or(ExecutionError::InvalidColumnIndex {
+                        index: column,
+                        count: batch.column_count(),
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let Some(data_type) = columns.first().map(|(base, _)| base.data_type()) else {
+            return Ok(SortKeyColumn::Int64(Vec::new()));
+        };
+
+        Ok(match data_type {
+            DataType::Float64 => {
+                let mut keys = Vec::new();
+                for (base, rows) in columns {
+                    match base.as_any().downcast_ref::<FloatColumn>() {
+                        Some(floats) => keys.extend(
+                            rows.iter()
+                                .zip(floats.values_in(rows.clone()))
+                                .map(|(row, v)| (!base.is_null(row)).then_some(*v)),
+                        ),
+                        None => {
+                            for row in rows.iter() {
+                                keys.push(match base.get(row)? {
+                                    Value::Float64(v) => Some(v),
+                                    _ => None,
+                                });
+                            }
+                        }
+                    }
+                }
+                SortKeyColumn::Float64(keys)
+            }
+            DataType::String => {
+                let mut keys = Vec::new();
+                for (base, rows) in columns {
+                    let any = base.as_any();
+                    if let Some(strings) = any.downcast_ref::<StringColumn>() {
+                        keys.extend(
+                            rows.iter()
+                                .zip(strings.values_in(rows.clone()))
+                                .map(|(row, v)| (!base.is_null(row)).then_some(v.as_str().into())),
+                        );
+                    } else if let Some(dict) = any.downcast_ref::<DictStringColumn>() {
+                        let dictionary = dict.dictionary();
+                        let codes = dict.codes();
+                        keys.extend(rows.iter().map(|row| {
+                            (!base.is_null(row))
+                                .then(|| dictionary[codes[row] as usize].as_str().into())
+                        }));
+                    } else {
+                        for row in rows.iter() {
+                            keys.push(match base.get(row)? {
+                                Value::String(s) => Some(s.into()),
+                                _ => None,
+                            });
+                        }
+                    }
+                }
+                SortKeyColumn::String(keys)
+            }
+            DataType::Int64 | DataType::Date | DataType::Boolean => {
+                let mut keys = Vec::new();
+                for (base, rows) in columns {
+                    let any = base.as_any();
+                    if let Some(ints) = any.downcast_ref::<IntColumn>() {
+                        keys.extend(
+                            rows.iter()
+                                .zip(ints.values_in(rows.clone()))
+                                .map(|(row, v)| (!base.is_null(row)).then_some(*v)),
+                        );
+                    } else if let Some(dates) = any.downcast_ref::<DateColumn>() {
+                        keys.extend(
+                            rows.iter().map(|row| {
+                                (!base.is_null(row)).then_some(dates.as_vec()[row] as i64)
+                            }),
+                        );
+                    } else {
+                        for row in rows.iter() {
+                            keys.push(match base.get(row)? {
+                                Value::Int64(v) => Some(v),
+                                Value::Date(v) => Some(v as i64),
+                                Value::Boolean(v) => Some(v as i64),
+                                _ => None,
+                            });
+                        }
+                    }
+                }
+                SortKeyColumn::Int64(keys)
+            }
+        })
+    }
+
+    /// Compare the keys of rows `a` and `b` in ascending order.
+    fn compare(&self, a: usize, b: usize) -> std::cmp::Ordering {
+        match self {
+            SortKeyColumn::Int64(keys) => keys[a].cmp(&keys[b]),
+            SortKeyColumn::Float64(keys) => match (keys[a], keys[b]) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            },
+            SortKeyColumn::String(keys) => keys[a].cmp(&keys[b]),
+        }
+    }
+}
+
+/// Move the values of column `column` out of the buffered batches, in the
+/// order of `positions`.
+///
+/// Each position must appear at most once; a value is taken, not cloned.
+fn gather_values(
+    batches: &[Batch],
+    column: usize,
+    positions: &[(usize, usize)],
+) -> Result<Vec<Value>> {
+    let mut values = batches
+        .iter()
+        .map(|batch| Ok(column_values(batch.column(column)?.as_ref())))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(positions
+        .iter()
+        .map(|&(b, row)| std::mem::replace(&mut values[b][row], Value::Null))
+        .collect())
+}
+
+/// The buffered rows in the order of `positions`, as row vectors for a
+/// spill file or merge.
+fn gather_rows(
+    batches: &[Batch],
+    column_count: usize,
+    positions: &[(usize, usize)],
+) -> Result<Vec<Vec<Value>>> {
+    let mut columns = (0..column_count)
+        .map(|column| Ok(gather_values(batches, column, positions)?.into_iter()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((0..positions.len())
+        .map(|_| columns.iter_mut().filter_map(Iterator::next).collect())
+        .collect())
+}
+
+/// Copy a column's values out, reading Int64, Float64, and String columns
+/// (or slices of them) through their typed accessors rather than calling
+/// `get` for every row.
+fn column_values(column: &dyn Column) -> Vec<Value> {
+    let (base, rows) = resolve_slice(column);
+    let any = base.as_any();
+    let typed = |row: usize, value: Value| {
+        if base.is_null(row) {
+            Value::Null
+        } else {
+            value
+        }
+    };
+    if let Some(ints) = any.downcast_ref::<IntColumn>() {
+        return rows
+            .iter()
+            .zip(ints.values_in(rows.clone()))
+            .map(|(row, v)| typed(row, Value::Int64(*v)))
+            .collect();
+    }
+    if let Some(floats) = any.downcast_ref::<FloatColumn>() {
+        return rows
+            .iter()
+            .zip(floats.values_in(rows.clone()))
+            .map(|(row, v)| typed(row, Value::Float64(*v)))
+            .collect();
+    }
+    if let Some(strings) = any.downcast_ref::<StringColumn>() {
+        return rows
+            .iter()
+            .zip(strings.values_in(rows.clone()))
+            .map(|(row, v)| typed(row, Value::String(v.clone())))
+            .collect();
+    }
+    column.slice(None)
+}
+
+/// Build a column of the given type from values.
+fn column_from_values(data_type: DataType, values: Vec<Value>) -> Result<Arc<dyn Column>> {
+    let mut column = crate::column::create_column(data_type);
+    for value in values {
+        column.push_value(value)?;
+    }
+    Ok(Arc::from(column))
+}
+
+// Spilled values are written as a one-byte tag followed by the payload in
+// little-endian order; strings are prefixed with their length in bytes.
+const SPILL_NULL: u8 = 0;
+const SPILL_INT64: u8 = 1;
+const SPILL_FLOAT64: u8 = 2;
+const SPILL_STRING: u8 = 3;
+const SPILL_BOOLEAN: u8 = 4;
+const SPILL_DATE: u8 = 5;
+
+/// Encode one value into a spill file.
+fn write_spilled_value(writer: &mut impl std::io::Write, value: &Value) -> std::io::Result<()> {
+    match value {
+        Value::Null => writer.write_all(&[SPILL_NULL]),
+        Value::Int64(v) => {
+            writer.write_all(&[SPILL_INT64])?;
+            writer.write_all(&v.to_le_bytes())
+        }
+        Value::Float64(v) => {
+            writer.write_all(&[SPILL_FLOAT64])?;
+            writer.write_all(&v.to_bits().to_le_bytes())
+        }
+        Value::String(s) => {
+            writer.write_all(&[SPILL_STRING])?;
+            writer.write_all(&(s.len() as u64).to_le_bytes())?;
+            writer.write_all(s.as_bytes())
+        }
+        Value::Boolean(v) => writer.write_all(&[SPILL_BOOLEAN, *v as u8]),
+        Value::Date(v) => {
+            writer.write_all(&[SPILL_DATE])?;
+            writer.write_all(&v.to_le_bytes())
+        }
+    }
+}
+
+/// Decode one value from a spill file, or None at the end of the file.
+fn read_spilled_value(reader: &mut impl std::io::Read) -> std::io::Result<Option<Value>> {
+    let mut tag = [0u8; 1];
+    if reader.read(&mut tag)? == 0 {
+        return Ok(None);
+    }
+    let mut word = [0u8; 8];
+    let value = match tag[0] {
+        SPILL_NULL => Value::Null,
+        SPILL_INT64 => {
+            reader.read_exact(&mut word)?;
+            Value::Int64(i64::from_le_bytes(word))
+        }
+        SPILL_FLOAT64 => {
+            reader.read_exact(&mut word)?;
+            Value::Float64(f64::from_bits(u64::from_le_bytes(word)))
+        }
+        SPILL_STRING => {
+            reader.read_exact(&mut word)?;
+            let mut bytes = vec![0u8; u64::from_le_bytes(word) as usize];
+            reader.read_exact(&mut bytes)?;
+            Value::String(
+                String::from_utf8(bytes)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?,
+            )
+        }
+        SPILL_BOOLEAN => {
+            let mut byte = [0u8; 1];
+            reader.read_exact(&mut byte)?;
+            Value::Boolean(byte[0] != 0)
+        }
+        SPILL_DATE => {
+            let mut days = [0u8; 4];
+            reader.read_exact(&mut days)?;
+            Value::Date(i32::from_le_bytes(days))
+        }
+        tag => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Unknown value tag {} in sort spill file", tag),
+            ))
+        }
+    };
+    Ok(Some(value))
+}
+
+/// A sorted run written to a temporary file, which is deleted on drop.
+struct SpilledRun {
+    /// Reads the run back, row by row
+    reader: std::io::BufReader<std::fs::File>,
+
+    /// Keeps the file alive until the run is dropped
+    _file: tempfile::NamedTempFile,
+}
+
+impl SpilledRun {
+    /// Read the next row of `column_count` values, or None after the last.
+    fn next_row(&mut self, column_count: usize) -> Result<Option<Vec<Value>>> {
+        let mut row = Vec::with_capacity(column_count);
+        for _ in 0..column_count {
+            match read_spilled_value(&mut self.reader)? {
+                Some(value) => row.push(value),
+                None if row.is_empty() => return Ok(None),
+                None => {
+                    return Err(ExecutionError::Custom(
+                        "Sort spill file ends partway through a row".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(Some(row))
+    }
+}
+
+/// One source of sorted rows in a [`RunMerge`].
+enum MergeRun {
+    /// A run spilled to disk
+    Spilled(SpilledRun),
+    /// The rows still in memory when the input ended
+    Memory(std::vec::IntoIter<Vec<Value>>),
+}
+
+/// The next row of one run, as held in the merge heap.
+///
+/// Rows are ordered by their sort key and then by run, so rows that tie
+/// come out in input order: earlier runs hold earlier rows.
+struct MergeEntry {
+    row: Vec<Value>,
+    run: usize,
+    sort_columns: Arc<[usize]>,
+    sort_directions: Arc<[SortDirection]>,
+}
+
+impl PartialEq for MergeEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for MergeEntry {}
+
+impl PartialOrd for MergeEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MergeEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        compare_rows(
+            &self.sort_columns,
+            &self.sort_directions,
+            &self.row,
+            &other.row,
+        )
+        .then(self.run.cmp(&other.run))
+    }
+}
+
+/// A k-way merge over sorted runs, yielding rows in sorted order.
+struct RunMerge {
+    runs: Vec<MergeRun>,
+    heap: BinaryHeap<std::cmp::Reverse<MergeEntry>>,
+    column_count: usize,
+    spilled_runs: usize,
+    sort_columns: Arc<[usize]>,
+    sort_directions: Arc<[SortDirection]>,
+}
+
+impl RunMerge {
+    fn new(
+        runs: Vec<MergeRun>,
+        column_count: usize,
+        sort_columns: &[usize],
+        sort_directions: &[SortDirection],
+    ) -> Result<Self> {
+        let spilled_runs = runs
+            .iter()
+            .filter(|run| matches!(run, MergeRun::Spilled(_)))
+            .count();
+        let mut merge = RunMerge {
+            runs,
+            heap: BinaryHeap::new(),
+            column_count,
+            spilled_runs,
+            sort_columns: sort_columns.into(),
+            sort_directions: sort_directions.into(),
+        };
+        for run in 0..merge.runs.len() {
+            merge.advance(run)?;
+        }
+        Ok(merge)
+    }
+
+    /// Push the next row of a run onto the heap, if it has one.
+    fn advance(&mut self, run: usize) -> Result<()> {
+        let row = match &mut self.runs[run] {
+            MergeRun::Spilled(spilled) => spilled.next_row(self.column_count)?,
+            MergeRun::Memory(rows) => rows.next(),
+        };
+        if let Some(row) = row {
+            self.heap.push(std::cmp::Reverse(MergeEntry {
+                row,
+                run,
+                sort_columns: Arc::clone(&self.sort_columns),
+                sort_directions: Arc::clone(&self.sort_directions),
+            }));
+        }
+        Ok(())
+    }
+
+    /// The next row in sorted order, or None when every run is exhausted.
+    fn next_row(&mut self) -> Result<Option<Vec<Value>>> {
+        match self.heap.pop() {
+            Some(std::cmp::Reverse(entry)) => {
+                self.advance(entry.run)?;
+                Ok(Some(entry.row))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Compare two values in ascending ORDER BY order.
+///
+/// Values of different types (including NULL, which has no type and
+/// therefore sorts first) are ordered by their data type.
+fn compare_sort_values(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a, b) {
+        (Value::Int64(a), Value::Int64(b)) => a.cmp(b),
+        (Value::Float64(a), Value::Float64(b)) => {
+            // Use total_cmp for float comparison to handle NaN properly
+            a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+        }
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+        (Value::Date(a), Value::Date(b)) => a.cmp(b),
+        (a, b) => a.data_type().cmp(&b.data_type()),
+    }
+}
+
+impl Operator for Sort {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.cancellation = token.clone();
+        self.child.set_cancellation(token);
+    }
+
+    fn set_memory_tracker(&mut self, tracker: &Arc<MemoryTracker>) {
+        self.memory.set_tracker(tracker);
+        self.child.set_memory_tracker(tracker);
+    }
+
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
+        }
+
+        // Open child operator
+        self.child.open()?;
+
+        // Resolve the output column types once, before buffering any rows
+        let schema = Arc::new(self.child.schema()?);
+        let column_types = schema.data_types();
+
+        // Read all data from child, spilling sorted runs whenever the
+        // buffered batches outgrow the memory limit
+        let mut batches: Vec<Batch> = Vec::new();
+        let mut buffered_bytes = 0;
+        let mut runs: Vec<MergeRun> = Vec::new();
+        self.merge = None;
+        self.sorted_data = None;
+        let operator = self.describe();
+        self.memory.release();
+
+        while let Some(batch) = self
+            .child
+            .next_batch()
+            .map_err(ExecutionError::from_child)?
+        {
+            self.cancellation.check()?;
+            let batch = batch.materialize();
+            let batch_bytes = batch_memory_size(&batch);
+            self.memory.grow(&operator, batch_bytes)?;
+            buffered_bytes += batch_bytes;
+            batches.push(batch);
+
+            if self
+                .memory_limit
+                .is_some_and(|limit| buffered_bytes > limit)
+            {
+                let positions = self.sorted_positions(&batches)?;
+                let rows = gather_rows(&batches, column_types.len(), &positions)?;
+                runs.push(MergeRun::Spilled(self.spill_run(&rows)?));
+                batches.clear();
+                buffered_bytes = 0;
+                self.memory.release();
+            }
+        }
+
+        // Sort the rows
+        let positions = self.sorted_positions(&batches)?;
+
+        self.output_schema = Some(Arc::clone(&schema));
+
+        // Merge spilled runs with the rows left in memory as rows are read
+        if !runs.is_empty() {
+            let rows = gather_rows(&batches, column_types.len(), &positions)?;
+            runs.push(MergeRun::Memory(rows.into_iter()));
+            self.merge = Some(RunMerge::new(
+                runs,
+                column_types.len(),
+                &self.sort_columns,
+                &self.sort_directions,
+            )?);
+            self.current_row = 0;
+            self.state = OperatorState::Open;
+            return Ok(());
+        }
+
+        // Gather the output columns in sorted order
+        if positions.is_empty() {
+            self.sorted_data = Some(Batch::empty());
+        } else {
+            let mut final_columns: Vec<Arc<dyn Column>> = Vec::new();
+            for (column, data_type) in column_types.iter().enumerate() {
+                self.cancellation.check()?;
+                let values = gather_values(&batches, column, &positions)?;
+                final_columns.push(column_from_values(*data_type, values)?);
+            }
+            self.sorted_data = Some(Batch::try_new(final_columns)?.with_schema(schema)?);
+        }
+
+        self.current_row = 0;
+        self.state = OperatorState::Open;
+
+        Ok(())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::OperatorNotOpen);
+        }
+        self.cancellation.check()?;
+        let schema = self
+            .output_schema
+            .clone()
+            .ok_or(ExecutionError::SchemaNotFound)?;
+
+        if let Some(merge) = &mut self.merge {
+            let mut columns: Vec<Vec<Value>> = vec![Vec::new(); schema.len()];
+            let mut rows = 0;
+            while rows < self.batch_size {
+                let Some(row) = merge.next_row()? else {
+                    break;
+                };
+                for (column, value) in columns.iter_mut().zip(row) {
+                    column.push(value);
+                }
+                rows += 1;
+            }
+            if rows == 0 {
+                return Ok(None);
+            }
+
+            let columns = schema
+                .fields()
+                .iter()
+                .zip(columns)
+                .map(|(field, values)| column_from_values(field.data_type, values))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Some(Batch::try_new(columns)?.with_schema(schema)?));
+        }
+
+        let sorted_data = self.sorted_data.as_ref().unwrap();
+        let total_rows = sorted_data.row_count();
+
+        if self.current_row >= total_rows {
+            return Ok(None);
+        }
+
+        // Output batches are slices of the sorted columns, so no values are
+        // copied
+        let end_row = std::cmp::min(self.current_row + self.batch_size, total_rows);
+        let batch = sorted_data.slice_rows(self.current_row..end_row)?;
+        self.current_row = end_row;
+
+        Ok(Some(batch))
+    }
+
+    fn close(&mut self) -> Result<()> {
+        // Dropping the merge deletes the spill files
+        self.merge = None;
+        self.sorted_data = None;
+        self.memory.release();
+        self.state = OperatorState::Closed;
+        self.child.close()
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        self.child.schema()
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Sort({})",
+            describe_sort_keys(
+                &output_names(self.child.as_ref()),
+                &self.sort_columns,
+                &self.sort_directions
+            )
+        )
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.child]
+    }
+}
+
+// ============================================================================
+// TOP-N OPERATOR (ORDER BY ... LIMIT k)
+// ============================================================================
+
+/// A buffered row in the [`TopN`] heap.
+///
+/// Rows are ordered by their sort key and then by arrival, so the heap's
+/// maximum is always the row that would be emitted last.
+struct TopNEntry {
+    /// Values of the sort columns, in ORDER BY order
+    key: Vec<Value>,
+
+    /// Position of the row in the child's output, used to break ties
+    sequence: usize,
+
+    /// The full row
+    row: Vec<Value>,
+
+    /// Sort direction for each key value
+    directions: Arc<[SortDirection]>,
+}
+
+impl TopNEntry {
+    /// Compare this entry's key against another sort key, ignoring arrival order.
+    fn compare_key(&self, key: &[Value]) -> std::cmp::Ordering {
+        for ((ours, theirs), direction) in self.key.iter().zip(key).zip(self.directions.iter()) {
+            let cmp = compare_sort_values(ours, theirs);
+            if cmp != std::cmp::Ordering::Equal {
+                return if *direction == SortDirection::Descending {
+                    cmp.reverse()
+                } else {
+                    cmp
+                };
+            }
+        }
+        std::cmp::Ordering::Equal
+    }
+}
+
+impl PartialEq for TopNEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for TopNEntry {}
+
+impl PartialOrd for TopNEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TopNEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.compare_key(&other.key)
+            .then(self.sequence.cmp(&other.sequence))
+    }
+}
+
+/// Top-N operator for `ORDER BY ... LIMIT k`.
+///
+/// Produces the same rows as a [`Sort`] followed by a [`Limit`] of `k`,
+/// but keeps only `k` rows in memory: the child is streamed through a
+/// bounded max-heap whose top is the current worst of the best `k` rows.
+/// Rows that tie on every sort key keep their input order, as with Sort.
+pub struct TopN {
+    /// The child operator to read data from
+    child: Box<dyn Operator>,
+
+    /// Indices of columns to sort by
+    sort_columns: Vec<usize>,
+
+    /// Sort direction for each column
+    sort_directions: Vec<SortDirection>,
+
+    /// Number of rows to keep
+    limit: usize,
+
+    /// Operator state
+    state: OperatorState,
+
+    /// The best `limit` rows, in sorted order
+    sorted_data: Option<Batch>,
+
+    /// Current position in sorted data
+    current_row: usize,
+
+    /// Batch size for output
+    batch_size: usize,
+}
+
+impl TopN {
+    /// Create a new TopN operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - The child operator to read data from
+    /// * `sort_columns` - Indices of columns to sort by
+    /// * `sort_directions` - Sort direction for each column
+    /// * `limit` - Number of rows to return
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_rust_olap::execution::{Operator, TableScan, TopN};
+    /// # use mini_rust_olap::types::SortDirection;
+    /// # use mini_rust_olap::table::Table;
+    /// let table = Table::new("users".to_string());
+    /// // ... add columns to table ...
+    ///
+    /// let scan = TableScan::new(table);
+    /// let mut top = TopN::new(Box::new(scan), vec![0], vec![SortDirection::Descending], 10);
+    ///
+    /// top.open().unwrap();
+    /// while let Some(batch) = top.next_batch().unwrap() {
+    ///     // At most 10 rows, largest first
+    /// }
+    /// top.close().unwrap();
+    /// ```
+    pub fn new(
+        child: Box<dyn Operator>,
+        sort_columns: Vec<usize>,
+        sort_directions: Vec<SortDirection>,
+        limit: usize,
+    ) -> Self {
+        TopN {
+            child,
+            sort_columns,
+            sort_directions,
+            limit,
+            state: OperatorState::NotOpen,
+            sorted_data: None,
+            current_row: 0,
+            batch_size: 1024,
+        }
+    }
+
+    /// Set the batch size for output.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl Operator for TopN {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.child.set_cancellation(token);
+    }
+
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
+        }
+
+        self.child.open()?;
+
+        let schema = Arc::new(self.child.schema()?);
+
+        let directions: Arc<[SortDirection]> = self.sort_directions.clone().into();
+        let mut heap: BinaryHeap<TopNEntry> = BinaryHeap::with_capacity(self.limit + 1);
+        let mut sequence = 0;
+
+        while let Some(batch) = self
+            .child
+            .next_batch()
+            .map_err(ExecutionError::from_child)?
+        {
+            if self.limit == 0 {
+                // Drain the child without keeping anything
+                continue;
+            }
+            for row_idx in 0..batch.row_count() {
+                let key = self
+                    .sort_columns
+                    .iter()
+                    .map(|&col_idx| batch.get(row_idx, col_idx))
+                    .collect::<Result<Vec<_>>>()?;
+                sequence += 1;
+
+                // Later rows lose ties, so a full heap only admits a
+                // strictly better key
+                if heap.len() == self.limit {
+                    let worst = heap.peek().expect("heap is full");
+                    if worst.compare_key(&key) != std::cmp::Ordering::Greater {
+                        continue;
+                    }
+                    heap.pop();
+                }
+
+                let row = (0..batch.column_count())
+                    .map(|col_idx| batch.get(row_idx, col_idx))
+                    .collect::<Result<Vec<_>>>()?;
+                heap.push(TopNEntry {
+                    key,
+                    sequence,
+                    row,
+                    directions: Arc::clone(&directions),
+                });
+            }
+        }
+
+        let rows = heap.into_sorted_vec();
+        self.sorted_data = Some(if rows.is_empty() {
+            Batch::empty()
+        } else {
+            let mut columns = Vec::with_capacity(schema.len());
+            for (col_idx, field) in schema.fields().iter().enumerate() {
+                let mut column = crate::column::create_column(field.data_type);
+                for entry in &rows {
+                    column.push_value(entry.row[col_idx].clone())?;
+                }
+                columns.push(Arc::from(column));
+            }
+            Batch::try_new(columns)?.with_schema(schema)?
+        });
+
+        self.current_row = 0;
+        self.state = OperatorState::Open;
+
+        Ok(())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::OperatorNotOpen);
+        }
+
+        let sorted_data = self.sorted_data.as_ref().unwrap();
+        if self.current_row >= sorted_data.row_count() {
+            return Ok(None);
+        }
+
+        let end_row = std::cmp::min(self.current_row + self.batch_size, sorted_data.row_count());
+        let batch = sorted_data
+            .skip_rows(self.current_row)?
+            .take_rows(end_row - self.current_row)?;
+        self.current_row = end_row;
+
+        Ok(Some(batch))
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.state = OperatorState::Closed;
+        self.sorted_data = None;
+        self.child.close()
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        self.child.schema()
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "TopN({}; {})",
+            self.limit,
+            describe_sort_keys(
+                &output_names(self.child.as_ref()),
+                &self.sort_columns,
+                &self.sort_directions
+            )
+        )
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.child]
+    }
+}
+
+// ============================================================================
+// LIMIT OPERATOR (LIMIT/OFFSET)
+// ============================================================================
+
+/// Limit operator for LIMIT/OFFSET clause.
+///
+/// Limit operator skips a specified number of rows (OFFSET) and then
+/// returns up to a specified number of rows (LIMIT). This operator can
+/// stop reading from the child operator once it has read enough rows.
+pub struct Limit {
+    /// The child operator to read data from
+    child: Box<dyn Operator>,
+
+    /// Maximum number of rows to return
+    limit: usize,
+
+    /// Number of rows to skip
+    offset: usize,
+
+    /// Number of rows already returned
+    rows_returned: usize,
+
+    /// Number of rows already skipped
+    rows_skipped: usize,
+
+    /// Operator state
+    state: OperatorState,
+}
+
+impl Limit {
+    /// Create a new Limit operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - The child operator to read data from
+    /// * `limit` - Maximum number of rows to return (if None, return all rows after offset)
+    /// * `offset` - Number of rows to skip
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_rust_olap::execution::Limit;
+    /// # use mini_rust_olap::execution::TableScan;
+    /// # use mini_rust_olap::execution::Operator;
+    /// # use mini_rust_olap::table::Table;
+    /// use std::sync::Arc;
+    ///
+    /// let table = Table::new("users".to_string());
+    /// // ... add columns to table ...
+    ///
+    /// let scan = TableScan::new(table);
+    /// let mut limit = Limit::new(Box::new(scan), Some(5), 10);
+    ///
+    /// limit.open().unwrap();
+    /// while let Some(batch) = limit.next_batch().unwrap() {
+    ///     // Process limited batches
+    /// }
+    /// limit.close().unwrap();
+    /// ```
+    pub fn new(child: Box<dyn Operator>, limit: Option<usize>, offset: usize) -> Self {
+        Limit {
+            child,
+            limit: limit.unwrap_or(usize::MAX),
+            offset,
+            rows_returned: 0,
+            rows_skipped: 0,
+            state: OperatorState::NotOpen,
+        }
+    }
+}
+
+impl Operator for Limit {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.child.set_cancellation(token);
+    }
+
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
+        }
+
+        self.child.open()?;
+        self.rows_returned = 0;
+        self.rows_skipped = 0;
+        self.state = OperatorState::Open;
+
+        Ok(())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::OperatorNotOpen);
+        }
+
+        // A batch left empty by the offset (or an empty batch from the
+        // child) says nothing about the rows still to come, so keep pulling
+        // until there is a row to return or the child is exhausted
+        loop {
+            // If we've already returned enough rows, stop
+            if self.rows_returned >= self.limit {
+                return Ok(None);
+            }
+
+            // Get next batch from child
+            let mut batch = match self.child.next_batch()? {
+                Some(b) => b,
+                None => return Ok(None),
+            };
+
+            // Apply offset if we haven't skipped enough rows yet
+            if self.rows_skipped < self.offset {
+                let skip_count = (self.offset - self.rows_skipped).min(batch.row_count());
+                batch = batch.skip_rows(skip_count)?;
+                self.rows_skipped += skip_count;
+            }
+
+            // Apply limit if this batch would exceed our limit
+            let remaining_limit = self.limit - self.rows_returned;
+            if batch.row_count() > remaining_limit {
+                batch = batch.take_rows(remaining_limit)?;
+            }
+
+            if !batch.is_empty() {
+                self.rows_returned += batch.row_count();
+                return Ok(Some(batch));
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.state = OperatorState::Closed;
+        self.child.close()
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        self.child.schema()
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
+
+    fn describe(&self) -> String {
+        match (self.limit, self.offset) {
+            (usize::MAX, offset) => format!("Limit(OFFSET {})", offset),
+            (limit, 0) => format!("Limit({})", limit),
+            (limit, offset) => format!("Limit({} OFFSET {})", limit, offset),
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.child]
+    }
+}
+
+// ============================================================================
+// HASH JOIN OPERATOR (INNER JOIN)
+// ============================================================================
+
+/// Which input of a [`HashJoin`] the hash table is built over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinSide {
+    /// Build over the left input, probe with the right
+    Left,
+    /// Build over the right input, probe with the left
+    Right,
+}
+
+/// A join key value that can be hashed.
+///
+/// When one key column is Int64 and the other Float64, both sides are keyed
+/// as floats so `1` matches `1.0`. `-0.0` is folded into `0.0`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum JoinKey {
+    Int64(i64),
+    Float64(u64),
+    String(String),
+    Boolean(bool),
+    Date(i32),
+}
+
+impl JoinKey {
+    /// Returns the key for a value, or None for NULL (which never matches).
+    fn from_value(value: Value, as_float: bool) -> Option<Self> {
+        let float_key = |v: f64| JoinKey::Float64(if v == 0.0 { 0.0f64 } else { v }.to_bits());
+        match value {
+            Value::Int64(v) if as_float => Some(float_key(v as f64)),
+            Value::Int64(v) => Some(JoinKey::Int64(v)),
+            Value::Float64(v) => Some(float_key(v)),
+            Value::String(v) => Some(JoinKey::String(v)),
+            Value::Boolean(v) => Some(JoinKey::Boolean(v)),
+            Value::Date(v) => Some(JoinKey::Date(v)),
+            Value::Null => None,
+        }
+    }
+}
+
+/// HashJoin operator for equi-joins (SQL inner `JOIN ... ON a = b`).
+///
+/// During `open()` the build side is read completely and every row is
+/// indexed by its key in a hash table; `next_batch()` then streams the probe
+/// side one batch at a time and emits one output row per matching pair.
+/// Planners should build over the smaller input, which is the right one by
+/// default. Rows whose key is NULL never match.
+///
+/// The output columns are the left input's columns followed by the right
+/// input's, whichever side is built. The two inputs must not share a column
+/// name, so callers joining tables with overlapping schemas should qualify
+/// the names first (e.g. with `Project::with_aliases`).
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::execution::{HashJoin, JoinSide, Operator, TableScan};
+///
+/// // users(id, name) JOIN orders(user_id, amount) ON users.id = orders.user_id
+/// let mut join = HashJoin::new(
+///     Box::new(TableScan::new(users)),
+///     Box::new(TableScan::new(orders)),
+///     0, // users.id
+///     0, // orders.user_id
+/// )
+/// .with_build_side(JoinSide::Left); // fewer users than orders
+///
+/// join.open()?;
+/// while let Some(batch) = join.next_batch()? {
+///     // columns: id, name, user_id, amount
+/// }
+/// join.close()?;
+/// ```
+pub struct HashJoin {
+    /// The left input
+    left: Box<dyn Operator>,
+
+    /// The right input
+    right: Box<dyn Operator>,
+
+    /// Index of the join key in the left input
+    left_key: usize,
+
+    /// Index of the join key in the right input
+    right_key: usize,
+
+    /// The input the hash table is built over
+    build_side: JoinSide,
+
+    /// Whether keys are compared as floats (Int64 joined with Float64)
+    keys_as_float: bool,
+
+    /// Batches read from the build side during open()
+    build_batches: Vec<Batch>,
+
+    /// Build-side (batch, row) positions for each key
+    hash_table: HashMap<JoinKey, Vec<(usize, usize)>>,
+
+    /// Operator state
+    state: OperatorState,
+
+    /// Output schema, the left columns then the right, resolved in open()
+    output_schema: Option<Arc<Schema>>,
+
+    /// The build side's batches and hash table, as counted in memory
+    memory: MemoryReservation,
+}
+
+impl HashJoin {
+    /// Create a new HashJoin operator that builds over the right input.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - The left input
+    /// * `right` - The right input
+    /// * `left_key` - Index of the join key column in the left input
+    /// * `right_key` - Index of the join key column in the right input
+    pub fn new(
+        left: Box<dyn Operator>,
+        right: Box<dyn Operator>,
+        left_key: usize,
+        right_key: usize,
+    ) -> Self {
+        HashJoin {
+            left,
+            right,
+            left_key,
+            right_key,
+            build_side: JoinSide::Right,
+            keys_as_float: false,
+            build_batches: Vec::new(),
+            hash_table: HashMap::new(),
+            state: OperatorState::NotOpen,
+            output_schema: None,
+            memory: MemoryReservation::default(),
+        }
+    }
+
+    /// Choose which input the hash table is built over.
+    pub fn with_build_side(mut self, build_side: JoinSide) -> Self {
+        self.build_side = build_side;
+        self
+    }
+
+    /// Returns the schema of an input and its key column.
+    fn input_schema(input: &dyn Operator, key: usize) -> Result<(Schema, Field)> {
+        let schema = input.schema()?;
+        let key_field = schema
+            .field(key)
+            .cloned()
+            .ok_or(ExecutionError::InvalidColumnIndex {
+                index: key,
+                count: schema.len(),
+            })?;
+        Ok((schema, key_field))
+    }
+
+    /// Joins one probe batch against the hash table.
+    fn probe(&self, probe: &Batch) -> Result<Option<Batch>> {
+        let (probe_key, probe_is_left) = match self.build_side {
+            JoinSide::Right => (self.left_key, true),
+            JoinSide::Left => (self.right_key, false),
+        };
+
+        // (probe row, build batch, build row) for every matching pair
+        let mut matches = Vec::new();
+        for row in 0..probe.row_count() {
+            let Some(key) = JoinKey::from_value(probe.get(row, probe_key)?, self.keys_as_float)
+            else {
+                continue;
+            };
+            if let Some(positions) = self.hash_table.get(&key) {
+                matches.extend(
+                    positions
+                        .iter()
+                        .map(|&(batch, build_row)| (row, batch, build_row)),
+                );
+            }
+        }
+        if matches.is_empty() {
+            return Ok(None);
+        }
+
+        let schema = self
+            .output_schema
+            .clone()
+            .ok_or(ExecutionError::OperatorNotOpen)?;
+        let left_width = self.left.schema()?.len();
+
+        let mut columns = Vec::with_capacity(schema.len());
+        for (index, field) in schema.fields().iter().enumerate() {
+            let from_left = index < left_width;
+            let input_index = if from_left { index } else { index - left_width };
+
+            let mut column = crate::column::create_column(field.data_type);
+            for &(probe_row, batch, build_row) in &matches {
+                let value = if from_left == probe_is_left {
+                    probe.get(probe_row, input_index)?
+                } else {
+                    self.build_batches[batch].get(build_row, input_index)?
+                };
+                column.push_value(value)?;
+            }
+            columns.push(Arc::from(column));
+        }
+
+        Ok(Some(Batch::try_new(columns)?.with_schema(schema)?))
+    }
+}
+
+impl Operator for HashJoin {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.left.set_cancellation(token);
+        self.right.set_cancellation(token);
+    }
+
+    fn set_memory_tracker(&mut self, tracker: &Arc<MemoryTracker>) {
+        self.memory.set_tracker(tracker);
+        self.left.set_memory_tracker(tracker);
+        self.right.set_memory_tracker(tracker);
+    }
+
+    fn open(&mut self) -> Result<()> {
+        if self.state == OperatorState::Open {
+            return Err(ExecutionError::OperatorAlreadyOpen);
+        }
+
+        self.left.open()?;
+        self.right.open()?;
+
+        let (left_schema, left_key) = Self::input_schema(self.left.as_ref(), self.left_key)?;
+        let (right_schema, right_key) = Self::input_schema(self.right.as_ref(), self.right_key)?;
+
+        if left_key.data_type != right_key.data_type
+            && !(left_key.data_type.is_numeric() && right_key.data_type.is_numeric())
+        {
+            return Err(ExecutionError::SchemaMismatch(format!(
+                "cannot join {} column '{}' with {} column '{}'",
+                left_key.data_type, left_key.name, right_key.data_type, right_key.name
+            )));
+        }
+        self.keys_as_float = left_key.data_type != right_key.data_type;
+
+        let mut fields = left_schema.fields().to_vec();
+        for field in right_schema.fields() {
+            if left_schema.index_of(&field.name).is_some() {
+                return Err(ExecutionError::SchemaMismatch(format!(
+                    "column '{}' appears on both sides of the join",
+                    field.name
+                )));
+            }
+            fields.push(field.clone());
+        }
+        self.output_schema = Some(Arc::new(Schema::new(fields)?));
+
+        // Build the hash table over the whole build side, counting each
+        // batch and its row positions
+        let operator = self.describe();
+        let (build, build_key) = match self.build_side {
+            JoinSide::Left => (&mut self.left, self.left_key),
+            JoinSide::Right => (&mut self.right, self.right_key),
+        };
+        self.build_batches.clear();
+        self.hash_table.clear();
+        self.memory.release();
+        while let Some(batch) = build.next_batch().map_err(ExecutionError::from_child)? {
+            let batch = batch.materialize();
+            self.memory.grow(
+                &operator,
+                batch_memory_size(&batch)
+                    + batch.row_count() * std::mem::size_of::<(usize, usize)>(),
+            )?;
+            let batch_index = self.build_batches.len();
+            for row in 0..batch.row_count() {
+                if let Some(key) =
+                    JoinKey::from_value(batch.get(row, build_key)?, self.keys_as_float)
+                {
+                    self.hash_table
+                        .entry(key)
+                        .or_default()
+                        .push((batch_index, row));
+                }
+            }
+            self.build_batches.push(batch);
+        }
+
+        self.state = OperatorState::Open;
+        Ok(())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        if self.state != OperatorState::Open {
+            return Err(ExecutionError::OperatorNotOpen);
+        }
+
+        loop {
+            let probe_batch = match self.build_side {
+                JoinSide::Right => self.left.next_batch()?,
+                JoinSide::Left => self.right.next_batch()?,
+            };
+            let Some(probe_batch) = probe_batch else {
+                return Ok(None);
+            };
+
+            // Skip probe batches without a single match
+            if let Some(batch) = self.probe(&probe_batch)? {
+                return Ok(Some(batch));
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.state = OperatorState::Closed;
+        self.build_batches.clear();
+        self.hash_table.clear();
+        self.memory.release();
+        self.left.close()?;
+        self.right.close()
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        self.output_schema
+            .as_deref()
+            .cloned()
+            .ok_or(ExecutionError::SchemaNotFound)
+    }
+
+    fn is_open(&self) -> bool {
+        self.state == OperatorState::Open
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "HashJoin({} = {})",
+            column_label(&output_names(self.left.as_ref()), self.left_key),
+            column_label(&output_names(self.right.as_ref()), self.right_key)
+        )
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.left, &mut self.right]
+    }
+}
+
+// ============================================================================
+// PROFILING (EXPLAIN ANALYZE)
+// ============================================================================
+
+/// Runtime statistics collected by a [`ProfiledOperator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperatorStats {
+    /// Batches the operator returned
+    pub batches: usize,
+    /// Rows the operator returned
+    pub rows: usize,
+    /// Wall time spent in open(), next_batch() and close(), including the
+    /// time its inputs took
+    pub elapsed: Duration,
+}
+
+/// Wraps an operator to count the batches and rows it returns and the time
+/// it takes, for EXPLAIN ANALYZE.
+///
+/// The wrapper is transparent: it returns the inner operator's batches
+/// unchanged and delegates its schema, description and inputs, so a
+/// profiled plan produces exactly the results of the plain one.
+///
+/// # Example
+///
+/// ```rust
+/// # use mini_rust_olap::column::{Column, IntColumn};
+/// # use mini_rust_olap::execution::{render_profile, Operator, ProfiledOperator, TableScan};
+/// # use mini_rust_olap::table::Table;
+/// # use mini_rust_olap::types::Value;
+/// let mut ids = IntColumn::new();
+/// for id in 0..10 {
+///     ids.push_value(Value::Int64(id)).unwrap();
+/// }
+/// let mut table = Table::new("t".to_string());
+/// table.add_column("id".to_string(), Box::new(ids)).unwrap();
+///
+/// let mut plan = ProfiledOperator::wrap_plan(Box::new(TableScan::new(table)));
+/// plan.open().unwrap();
+/// while plan.next_batch().unwrap().is_some() {}
+/// plan.close().unwrap();
+///
+/// assert_eq!(plan.stats().unwrap().rows, 10);
+/// assert!(render_profile(plan.as_ref()).starts_with("TableScan(t: id) rows=10 "));
+/// ```
+pub struct ProfiledOperator {
+    /// The operator being measured
+    inner: Box<dyn Operator>,
+    /// What it has returned so far
+    stats: OperatorStats,
+}
+
+impl ProfiledOperator {
+    /// Wrap a single operator, leaving its inputs as they are.
+    pub fn new(inner: Box<dyn Operator>) -> Self {
+        ProfiledOperator {
+            inner,
+            stats: OperatorStats::default(),
+        }
+    }
+
+    /// Wrap every operator of a plan, from the leaves up.
+    pub fn wrap_plan(mut plan: Box<dyn Operator>) -> Box<dyn Operator> {
+        for child in plan.children_mut() {
+            let inner = std::mem::replace(child, Box::new(Detached));
+            *child = Self::wrap_plan(inner);
+        }
+        Box::new(ProfiledOperator::new(plan))
+    }
+
+    /// Run `f` on the inner operator, adding its duration to the stats.
+    fn timed<T>(&mut self, f: impl FnOnce(&mut dyn Operator) -> T) -> T {
+        let start = Instant::now();
+        let result = f(self.inner.as_mut());
+        self.stats.elapsed += start.elapsed();
+        result
+    }
+}
+
+impl Operator for ProfiledOperator {
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.inner.set_cancellation(token);
+    }
+
+    fn set_memory_tracker(&mut self, tracker: &Arc<MemoryTracker>) {
+        self.inner.set_memory_tracker(tracker);
+    }
+
+    fn open(&mut self) -> Result<()> {
+        self.stats = OperatorStats::default();
+        self.timed(|inner| inner.open())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        let batch = self.timed(|inner| inner.next_batch())?;
+        if let Some(batch) = &batch {
+            self.stats.batches += 1;
+            self.stats.rows += batch.row_count();
+        }
+        Ok(batch)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.timed(|inner| inner.close())
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        self.inner.schema()
+    }
+
+    fn column_names(&self) -> Result<Vec<String>> {
+        self.inner.column_names()
+    }
+
+    fn is_open(&self) -> bool {
+        self.inner.is_open()
+    }
+
+    fn describe(&self) -> String {
+        self.inner.describe()
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        self.inner.children()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        self.inner.children_mut()
+    }
+
+    fn stats(&self) -> Option<OperatorStats> {
+        Some(self.stats)
+    }
+}
+
+/// Stands in for an input while [`ProfiledOperator::wrap_plan`] moves it
+/// into its wrapper.
+struct Detached;
+
+impl Operator for Detached {
+    fn open(&mut self) -> Result<()> {
+        Err(ExecutionError::Custom(
+            "operator was detached from its plan".to_string(),
+        ))
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        Err(ExecutionError::OperatorNotOpen)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        Err(ExecutionError::SchemaNotFound)
+    }
+}
+
+/// The plan of a derived table, `FROM (SELECT ...) alias`, read by the
+/// query around it.
+///
+/// The planner opens the inner plan to learn its output columns, so the
+/// first `open()` here finds it open already and leaves it be. Once closed,
+/// opening runs the inner plan again like any other input.
+pub(crate) struct DerivedTable {
+    /// The alias, or empty for a derived table without one
+    name: String,
+    input: Box<dyn Operator>,
+    /// Whether the input is still open from planning
+    planned_open: bool,
+}
+
+impl DerivedTable {
+    /// Wrap an inner plan that has already been opened.
+    pub(crate) fn opened(name: String, input: Box<dyn Operator>) -> Self {
+        DerivedTable {
+            name,
+            input,
+            planned_open: true,
+        }
+    }
+}
+
+impl Operator for DerivedTable {
+    fn open(&mut self) -> Result<()> {
+        if std::mem::take(&mut self.planned_open) {
+            return Ok(());
+        }
+        self.input.open()
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        self.input.next_batch()
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.planned_open = false;
+        self.input.close()
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        self.input.schema()
+    }
+
+    fn is_open(&self) -> bool {
+        self.input.is_open()
+    }
+
+    fn set_cancellation(&mut self, token: &CancellationToken) {
+        self.input.set_cancellation(token);
+    }
+
+    fn describe(&self) -> String {
+        if self.name.is_empty() {
+            "DerivedTable".to_string()
+        } else {
+            format!("DerivedTable({})", self.name)
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Operator> {
+        vec![self.input.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Box<dyn Operator>> {
+        vec![&mut self.input]
+    }
+}
+
+/// Render a plan as an indented tree, one operator per line, annotated with
+/// the statistics of profiled operators.
+///
+/// Each profiled line reads `rows=<returned>`, then `input=<rows>` (the
+/// rows its inputs returned) for operators with inputs, then `time=` (the
+/// operator's own time, excluding its inputs'). Comparing rows with input
+/// shows how selective each operator is.
+pub fn render_profile(plan: &dyn Operator) -> String {
+    fn render(operator: &dyn Operator, depth: usize, out: &mut String) {
+        let children = operator.children();
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(&operator.describe());
+        if let Some(stats) = operator.stats() {
+            let child_stats: Vec<OperatorStats> =
+                children.iter().filter_map(|child| child.stats()).collect();
+            out.push_str(&format!(" rows={}", stats.rows));
+            if !children.is_empty() {
+                let input: usize = child_stats.iter().map(|child| child.rows).sum();
+                out.push_str(&format!(" input={}", input));
+            }
+            let inputs_elapsed: Duration = child_stats.iter().map(|child| child.elapsed).sum();
+            let own = stats.elapsed.saturating_sub(inputs_elapsed);
+            out.push_str(&format!(" time={:.3}ms", own.as_secs_f64() * 1000.0));
+        }
+        out.push('\n');
+        for child in children {
+            render(child, depth + 1, out);
+        }
+    }
+
+    let mut out = String::new();
+    render(plan, 0, &mut out);
+    out
+}
+
+/// The names of an operator's output columns, or none if its schema is not
+/// resolved yet.
+fn output_names(operator: &dyn Operator) -> Vec<String> {
+    operator.column_names().unwrap_or_default()
+}
+
+/// Sort keys as in ORDER BY, e.g. `age DESC, name`.
+fn describe_sort_keys(names: &[String], columns: &[usize], directions: &[SortDirection]) -> String {
+    let keys: Vec<String> = columns
+        .iter()
+        .zip(directions)
+        .map(|(&index, direction)| match direction {
+            SortDirection::Ascending => column_label(names, index),
+            SortDirection::Descending => format!("{} DESC", column_label(names, index)),
+        })
+        .collect();
+    keys.join(", ")
+}
+
+/// The name of column `index` among `names`, or `#index` if it is unknown.
+fn column_label(names: &[String], index: usize) -> String {
+    names
+        .get(index)
+        .cloned()
+        .unwrap_or_else(|| format!("#{}", index))
+}
+
+/// A value as it would be written in SQL, with strings quoted.
+fn sql_literal(value: &Value) -> String {
+    match value {
+        Value::String(text) => format!("'{}'", text),
+        Value::Date(_) => format!("'{}'", value),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregates::*;
+    use crate::column::{FloatColumn, IntColumn, StringColumn};
+    use crate::types::{DataType, Value};
+
+    #[test]
+    fn test_batch_creation() {
+        let mut col1 = IntColumn::new();
+        col1.push_value(Value::Int64(1)).unwrap();
+        col1.push_value(Value::Int64(2)).unwrap();
+        col1.push_value(Value::Int64(3)).unwrap();
+
+        let mut col2 = FloatColumn::new();
+        col2.push_value(Value::Float64(1.0)).unwrap();
+        col2.push_value(Value::Float64(2.0)).unwrap();
+        col2.push_value(Value::Float64(3.0)).unwrap();
+
+        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+
+        assert_eq!(batch.row_count(), 3);
+        assert_eq!(batch.column_count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot create a batch with no columns")]
+    fn test_batch_empty_columns() {
+        let _batch = Batch::new(vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 1 has 5 rows, but column 0 has 3 rows")]
+    fn test_batch_mismatched_lengths() {
+        let mut col1 = IntColumn::new();
+        col1.push_value(Value::Int64(1)).unwrap();
+        col1.push_value(Value::Int64(2)).unwrap();
+        col1.push_value(Value::Int64(3)).unwrap();
+
+        let mut col2 = IntColumn::new();
+        col2.push_value(Value::Int64(1)).unwrap();
+        col2.push_value(Value::Int64(2)).unwrap();
+        col2.push_value(Value::Int64(3)).unwrap();
+        col2.push_value(Value::Int64(4)).unwrap();
+        col2.push_value(Value::Int64(5)).unwrap();
+
+        let _batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+    }
+
+    #[test]
+    fn test_batch_try_new_reports_errors() {
+        assert!(matches!(
+            Batch::try_new(vec![]),
+            Err(ExecutionError::SchemaMismatch(msg)) if msg.contains("no columns")
+        ));
+
+        let mut short = IntColumn::new();
+        short.push_value(Value::Int64(1)).unwrap();
+        let mut long = IntColumn::new();
+        long.push_value(Value::Int64(1)).unwrap();
+        long.push_value(Value::Int64(2)).unwrap();
+        let err = Batch::try_new(vec![Arc::new(short.clone()), Arc::new(long)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema mismatch: Column 1 has 2 rows, but column 0 has 1 rows"
+        );
+
+        let batch = Batch::try_new(vec![Arc::new(short)]).unwrap();
+        assert_eq!(batch.row_count(), 1);
+    }
+
+    #[test]
+    fn test_batch_column_access() {
+        let mut col1 = IntColumn::new();
+        col1.push_value(Value::Int64(1)).unwrap();
+        col1.push_value(Value::Int64(2)).unwrap();
+        col1.push_value(Value::Int64(3)).unwrap();
+
+        let mut col2 = StringColumn::new();
+        col2.push_value(Value::String("a".to_string())).unwrap();
+        col2.push_value(Value::String("b".to_string())).unwrap();
+        col2.push_value(Value::String("c".to_string())).unwrap();
+
+        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+
+        let result = batch.column(0);
+        assert!(result.is_ok());
+
+        let result = batch.column(2);
+        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ExecutionError::InvalidColumnIndex { index: 2, count: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_batch_column_as() {
+        let mut ints = IntColumn::new();
+        ints.push_value(Value::Int64(7)).unwrap();
+        let mut strings = StringColumn::new();
+        strings.push_value(Value::String("a".to_string())).unwrap();
+        let batch = Batch::new(vec![Arc::new(ints), Arc::new(strings)]);
+
+        assert_eq!(batch.column_as::<IntColumn>(0).unwrap().values(), &[7]);
+        assert!(batch.column_as::<FloatColumn>(0).is_none());
+        assert_eq!(
+            batch.column_as::<StringColumn>(1).unwrap().values(),
+            &["a".to_string()]
+        );
+        assert!(batch.column_as::<IntColumn>(2).is_none());
+    }
+
+    #[test]
+    fn test_schema_lookups_and_duplicates() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64),
+            Field::new("name", DataType::String),
+            Field::new("age", DataType::Float64),
+        ])
+        .unwrap();
+        assert_eq!(schema.len(), 3);
+        assert_eq!(schema.column_names(), vec!["id", "name", "age"]);
+        assert_eq!(
+            schema.data_types(),
+            vec![DataType::Int64, DataType::String, DataType::Float64]
+        );
+        assert_eq!(schema.index_of("age"), Some(2));
+        assert_eq!(schema.data_type("name"), Some(DataType::String));
+        assert_eq!(schema.data_type("missing"), None);
+        assert_eq!(
+            schema.to_string(),
+            "(id: Int64, name: String, age: Float64)"
+        );
+
+        let selected = schema.select(&[2, 0]).unwrap();
+        assert_eq!(selected.column_names(), vec!["age", "id"]);
+        assert!(matches!(
+            schema.select(&[3]),
+            Err(ExecutionError::InvalidColumnIndex { index: 3, count: 3 })
+        ));
+
+        // Names must be unique, including when a column is selected twice
+        let duplicate = Schema::from_columns(&[
+            ("id".to_string(), DataType::Int64),
+            ("id".to_string(), DataType::String),
+        ]);
+        assert_eq!(
+            duplicate.unwrap_err().to_string(),
+            "Duplicate column name: id"
+        );
+        assert!(schema.select(&[0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_batch_with_schema_and_column_by_name() {
+        let mut ids = IntColumn::new();
+        let mut names = StringColumn::new();
+        for (id, name) in [(1, "a"), (2, "b"), (3, "c")] {
+            ids.push_value(Value::Int64(id)).unwrap();
+            names.push_value(Value::String(name.to_string())).unwrap();
+        }
+        let batch = Batch::new(vec![Arc::new(ids), Arc::new(names)]);
+        assert!(batch.schema().is_none());
+        assert!(batch.column_by_name("id").is_none());
+
+        // The schema must describe the batch's columns exactly
+        let too_short = Schema::new(vec![Field::new("id", DataType::Int64)]).unwrap();
+        assert!(matches!(
+            batch.clone().with_schema(Arc::new(too_short)),
+            Err(ExecutionError::SchemaMismatch(_))
+        ));
+        let wrong_type = Schema::new(vec![
+            Field::new("id", DataType::Float64),
+            Field::new("name", DataType::String),
+        ])
+        .unwrap();
+        match batch.clone().with_schema(Arc::new(wrong_type)) {
+            Err(ExecutionError::TypeMismatch {
+                expected,
+                actual,
+                column,
+            }) => {
+                assert_eq!((expected, actual), (DataType::Float64, DataType::Int64));
+                assert_eq!(column.as_deref(), Some("id"));
+            }
+            other => panic!("expected a type mismatch, got {:?}", other),
+        }
+
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64),
+            Field::new("name", DataType::String),
+        ])
+        .unwrap();
+        let batch = batch.with_schema(Arc::new(schema.clone())).unwrap();
+        assert_eq!(batch.schema(), Some(&schema));
+        let name = batch.column_by_name("name").unwrap();
+        assert_eq!(name.get(1).unwrap(), Value::String("b".to_string()));
+        assert!(batch.column_by_name("age").is_none());
+
+        // Row operations keep the schema; selections keep the selected part
+        let filtered = batch.filter_rows(&[true, false, true]).unwrap();
+        assert_eq!(filtered.schema(), Some(&schema));
+        assert_eq!(
+            filtered.column_by_name("id").unwrap().get(1).unwrap(),
+            Value::Int64(3)
+        );
+        assert_eq!(batch.skip_rows(1).unwrap().schema(), Some(&schema));
+        assert_eq!(batch.take_rows(2).unwrap().schema(), Some(&schema));
+        let selected = batch.select(&[1, 0]).unwrap();
+        assert_eq!(
+            selected.schema().unwrap().column_names(),
+            vec!["name", "id"]
+        );
+        assert!(batch.select(&[0, 0]).unwrap().schema().is_none());
+
+        // Projecting with aliases renames the columns
+        let renamed = batch
+            .project(&[1, 0], &["label".to_string(), "key".to_string()])
+            .unwrap();
+        assert_eq!(
+            renamed.column_by_name("key").unwrap().get(0).unwrap(),
+            Value::Int64(1)
+        );
+        assert!(renamed.column_by_name("id").is_none());
+        assert!(batch
+            .project(&[0], &[])
+            .unwrap()
+            .column_by_name("id")
+            .is_some());
+        assert!(batch.project(&[0, 1], &["x".to_string()]).is_err());
+    }
+
+    fn id_name_batch() -> Batch {
+        let mut ids = IntColumn::new();
+        let mut names = StringColumn::new();
+        for (id, name) in [(1, "a"), (2, "b")] {
+            ids.push_value(Value::Int64(id)).unwrap();
+            names.push_value(Value::String(name.to_string())).unwrap();
+        }
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64),
+            Field::new("name", DataType::String),
+        ])
+        .unwrap();
+        Batch::new(vec![Arc::new(ids), Arc::new(names)])
+            .with_schema(Arc::new(schema))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_batch_project_aliases_round_trip() {
+        let batch = id_name_batch();
+
+        // Renaming and renaming back gives the original schema
+        let aliased = batch
+            .project(&[0, 1], &["key".to_string(), "label".to_string()])
+            .unwrap();
+        assert_eq!(aliased.schema().unwrap().column_names(), ["key", "label"]);
+        let restored = aliased
+            .project(&[0, 1], &["id".to_string(), "name".to_string()])
+            .unwrap();
+        assert_eq!(restored.schema(), batch.schema());
+        assert_eq!(restored.get(1, 1).unwrap(), Value::String("b".to_string()));
+
+        // Aliases may repeat a column under two names, but not one name twice
+        let twice = batch
+            .project(&[0, 0], &["a".to_string(), "b".to_string()])
+            .unwrap();
+        assert_eq!(twice.schema().unwrap().column_names(), ["a", "b"]);
+        assert!(matches!(
+            batch.project(&[0, 1], &["x".to_string(), "x".to_string()]),
+            Err(ExecutionError::Custom(msg)) if msg == "Duplicate column name: x"
+        ));
+
+        // Without a schema the aliases still name the result
+        let unnamed = Batch::new(batch.columns().to_vec());
+        let named = unnamed.project(&[1], &["name".to_string()]).unwrap();
+        assert_eq!(
+            named.schema().unwrap().fields(),
+            [Field::new("name", DataType::String)]
+        );
+        assert!(unnamed
+            .project(&[0, 1], &["x".to_string(), "x".to_string()])
+            .is_err());
+    }
+
+    #[test]
+    fn test_batch_rename() {
+        let batch = id_name_batch();
+
+        let renamed = batch.clone().rename("name", "label").unwrap();
+        assert_eq!(renamed.schema().unwrap().column_names(), ["id", "label"]);
+        assert_eq!(
+            renamed.column_by_name("label").unwrap().get(0).unwrap(),
+            Value::String("a".to_string())
+        );
+        // Renaming a column to its own name is allowed
+        assert!(batch.clone().rename("id", "id").is_ok());
+
+        assert!(matches!(
+            batch.clone().rename("age", "years"),
+            Err(ExecutionError::ColumnNotFound(name)) if name == "age"
+        ));
+        assert!(matches!(
+            batch.clone().rename("name", "id"),
+            Err(ExecutionError::Custom(msg)) if msg == "Duplicate column name: id"
+        ));
+        assert!(matches!(
+            Batch::new(batch.columns().to_vec()).rename("id", "key"),
+            Err(ExecutionError::SchemaNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_batch_with_column() {
+        let batch = id_name_batch();
+
+        let mut scores = FloatColumn::new();
+        scores.push_value(Value::Float64(0.5)).unwrap();
+        scores.push_null();
+        let scores: Arc<dyn Column> = Arc::new(scores);
+
+        let extended = batch.clone().with_column("score", scores.clone()).unwrap();
+        assert_eq!(extended.column_count(), 3);
+        assert_eq!(
+            extended.schema().unwrap().column_names(),
+            ["id", "name", "score"]
+        );
+        assert_eq!(
+            extended.schema().unwrap().data_type("score"),
+            Some(DataType::Float64)
+        );
+        assert_eq!(extended.get(1, 2).unwrap(), Value::Null);
+
+        assert!(matches!(
+            batch.clone().with_column("id", scores.clone()),
+            Err(ExecutionError::Custom(msg)) if msg == "Duplicate column name: id"
+        ));
+
+        let mut short = FloatColumn::new();
+        short.push_value(Value::Float64(1.0)).unwrap();
+        assert!(matches!(
+            batch.clone().with_column("short", Arc::new(short)),
+            Err(ExecutionError::SchemaMismatch(_))
+        ));
+        assert!(matches!(
+            Batch::new(batch.columns().to_vec()).with_column("score", scores),
+            Err(ExecutionError::SchemaNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_column_values_reads_slices_and_nulls() {
+        let mut ints = IntColumn::new();
+        let mut floats = FloatColumn::new();
+        let mut strings = StringColumn::new();
+        let mut dates = DateColumn::new();
+        for i in 0..6 {
+            if i % 3 == 0 {
+                ints.push_null();
+                floats.push_null();
+                strings.push_null();
+                dates.push_null();
+            } else {
+                ints.push_value(Value::Int64(i)).unwrap();
+                floats.push_value(Value::Float64(i as f64)).unwrap();
+                strings.push_value(Value::String(i.to_string())).unwrap();
+                dates.push_value(Value::Date(i as i32)).unwrap();
+            }
+        }
+        let columns: Vec<Arc<dyn Column>> = vec![
+            Arc::new(ints),
+            Arc::new(floats),
+            Arc::new(strings),
+            Arc::new(dates),
+        ];
+
+        for column in &columns {
+            // The typed path must agree with `get`, whole and sliced
+            let sliced = slice_column(column, 2..5).unwrap();
+            for column in [column, &sliced] {
+                let expected: Vec<Value> = (0..column.len())
+                    .map(|row| column.get(row).unwrap())
+                    .collect();
+                assert_eq!(column_values(column.as_ref()), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_get_value() {
+        let mut col1 = IntColumn::new();
+        col1.push_value(Value::Int64(10)).unwrap();
+        col1.push_value(Value::Int64(20)).unwrap();
+        col1.push_value(Value::Int64(30)).unwrap();
+
+        let mut col2 = StringColumn::new();
+        col2.push_value(Value::String("x".to_string())).unwrap();
+        col2.push_value(Value::String("y".to_string())).unwrap();
+        col2.push_value(Value::String("z".to_string())).unwrap();
+
+        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+
+        // Valid access - returns Value enum
+        let result = batch.get(1, 0);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Value::Int64(20));
+
+        let result = batch.get(2, 1);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Value::String("z".to_string()));
+
+        // Test get_as_string
+        let result = batch.get_as_string(1, 0);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "20");
+
+        // Invalid row index
+        let result = batch.get(5, 0);
+        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ExecutionError::InvalidRowIndex { index: 5, count: 3 })
+        ));
+
+        // Invalid column index
+        let result = batch.get(0, 5);
+        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ExecutionError::InvalidColumnIndex { index: 5, count: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_batch_get_as_string() {
+        let mut col1 = StringColumn::new();
+        col1.push_value(Value::String("hello".to_string())).unwrap();
+        col1.push_value(Value::String("world".to_string())).unwrap();
+        col1.push_value(Value::String("test".to_string())).unwrap();
+
+        let batch = Batch::new(vec![Arc::new(col1)]);
+
+        let result = batch.get_as_string(1, 0);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "world");
+    }
+
+    #[test]
+    fn test_batch_select() {
+        let mut col1 = IntColumn::new();
+        col1.push_value(Value::Int64(1)).unwrap();
+        col1.push_value(Value::Int64(2)).unwrap();
+        col1.push_value(Value::Int64(3)).unwrap();
+
+        let mut col2 = StringColumn::new();
+        col2.push_value(Value::String("a".to_string())).unwrap();
+        col2.push_value(Value::String("b".to_string())).unwrap();
+        col2.push_value(Value::String("c".to_string())).unwrap();
+
+        let mut col3 = FloatColumn::new();
+        col3.push_value(Value::Float64(1.0)).unwrap();
+        col3.push_value(Value::Float64(2.0)).unwrap();
+        col3.push_value(Value::Float64(3.0)).unwrap();
+
+        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2), Arc::new(col3)]);
+
+        let selected = batch.select(&[0, 2]).unwrap();
+
+        assert_eq!(selected.row_count(), 3);
+        assert_eq!(selected.column_count(), 2);
+
+        let val = selected.get(0, 0).unwrap();
+        assert_eq!(val, Value::Int64(1));
+
+        let val = selected.get(1, 1).unwrap();
+        assert_eq!(val, Value::Float64(2.0));
+    }
+
+    #[test]
+    fn test_batch_select_invalid_index() {
+        let mut col1 = IntColumn::new();
+        col1.push_value(Value::Int64(1)).unwrap();
+        col1.push_value(Value::Int64(2)).unwrap();
+        col1.push_value(Value::Int64(3)).unwrap();
+
+        let batch = Batch::new(vec![Arc::new(col1)]);
+
+        let result = batch.select(&[0, 5]);
+        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ExecutionError::InvalidColumnIndex { index: 5, count: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_batch_empty() {
+        let batch = Batch::empty();
+
+        assert!(batch.is_empty());
+        assert_eq!(batch.row_count(), 0);
+    }
+
+    #[test]
+    fn test_execution_error_display() {
+        let err = ExecutionError::OperatorNotOpen;
+        assert_eq!(
+            err.to_string(),
+            "Operator must be opened before calling this operation"
+        );
+
+        let err = ExecutionError::InvalidColumnIndex { index: 5, count: 3 };
+        assert_eq!(
+            err.to_string(),
+            "Invalid column index 5 (only 3 columns available)"
+        );
+
+        let err = ExecutionError::ColumnNotFound("age".to_string());
+        assert_eq!(err.to_string(), "Column 'age' not found in batch");
+    }
+
+    #[test]
+    fn test_io_error_conversion() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+        let exec_err: ExecutionError = io_err.into();
+
+        assert!(matches!(exec_err, ExecutionError::IoError(_)));
+        assert_eq!(
+            exec_err.to_string(),
+            "IO error during execution: file not found"
+        );
+    }
+
+    #[test]
+    fn test_batch_debug() {
+        let mut col1 = IntColumn::new();
+        col1.push_value(Value::Int64(1)).unwrap();
+        col1.push_value(Value::Int64(2)).unwrap();
+        col1.push_value(Value::Int64(3)).unwrap();
+
+        let mut col2 = StringColumn::new();
+        col2.push_value(Value::String("a".to_string())).unwrap();
+        col2.push_value(Value::String("b".to_string())).unwrap();
+        col2.push_value(Value::String("c".to_string())).unwrap();
+
+        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+
+        let debug_str = format!("{:?}", batch);
+        assert!(debug_str.contains("Batch"));
+        assert!(debug_str.contains("row_count: 3"));
+        assert!(debug_str.contains("column_count: 2"));
+    }
+
+    // Simple mock operator for testing
+    struct MockOperator {
+        state: OperatorState,
+    }
+
+    impl Operator for MockOperator {
+        fn open(&mut self) -> Result<()> {
+            self.state = OperatorState::Open;
+            Ok(())
+        }
+
+        fn next_batch(&mut self) -> Result<Option<Batch>> {
+            if self.state != OperatorState::Open {
+                return Err(ExecutionError::OperatorNotOpen);
+            }
+            Ok(None)
+        }
+
+        fn close(&mut self) -> Result<()> {
+            self.state = OperatorState::Closed;
+            Ok(())
+        }
+
+        fn schema(&self) -> Result<Schema> {
+            Err(ExecutionError::Custom(
+                "Mock operator has no schema".to_string(),
+            ))
+        }
+
+        fn is_open(&self) -> bool {
+            self.state == OperatorState::Open
+        }
+    }
+
+    #[test]
+    fn test_operator_lifecycle() {
+        let mut op = MockOperator {
+            state: OperatorState::NotOpen,
+        };
+
+        assert!(!op.is_open());
+
+        // Open
+        assert!(op.open().is_ok());
+        assert!(op.is_open());
+
+        // Get batch
+        assert!(op.next_batch().is_ok());
+
+        // Close
+        assert!(op.close().is_ok());
+        assert!(!op.is_open());
+    }
+
+    #[test]
+    fn test_operator_not_open_error() {
+        let mut op = MockOperator {
+            state: OperatorState::NotOpen,
+        };
+
+        let result = op.next_batch();
+        assert!(result.is_err());
+        assert!(matches!(result, Err(ExecutionError::OperatorNotOpen)));
+    }
+
+    // TableScan Operator Tests
+    #[test]
+    fn test_table_scan_create() {
+        let table = create_test_table();
+        let scan = TableScan::new(table.clone());
+
+        assert_eq!(scan.total_rows, 5);
+        assert_eq!(scan.column_indices.len(), 3); // All columns
+        assert_eq!(scan.batch_size, 1024);
+    }
+
+    #[test]
+    fn test_table_scan_skips_blocks_with_zone_maps() {
+        let mut ids = IntColumn::with_capacity(1_000_000);
+        for id in 0..1_000_000 {
+            ids.push_value(Value::Int64(id)).unwrap();
+        }
+        let mut table = Table::new("events".to_string());
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        let blocks = table.zone_map("id").unwrap().zones().len();
+        assert_eq!(blocks, 977);
+
+        let predicate = BinaryComparison::new(0, ComparisonOp::GreaterThan, Value::Int64(999_000));
+        let mut scan = TableScan::new(table).with_pushed_predicate(predicate.clone());
+        let mut visited = 0;
+        let mut matched = 0;
+        scan.open().unwrap();
+        while let Some(batch) = scan.next_batch().unwrap() {
+            visited += 1;
+            matched += predicate
+                .eval_batch(&batch)
+                .unwrap()
+                .iter()
+                .filter(|&&keep| keep)
+                .count();
+        }
+        assert_eq!(matched, 999);
+        assert_eq!(scan.blocks_skipped(), 975);
+        assert_eq!(visited, 2);
+
+        // Reopening starts the count again
+        scan.close().unwrap();
+        let mut scan = TableScan::new(scan.table.clone()).with_pushed_predicate(
+            BinaryComparison::new(0, ComparisonOp::LessThan, Value::Float64(-0.5)),
+        );
+        scan.open().unwrap();
+        assert!(scan.next_batch().unwrap().is_none());
+        assert_eq!(scan.blocks_skipped(), blocks);
+    }
+
+    #[test]
+    fn test_zone_may_match() {
+        let range = Zone::Range {
+            min: Value::Int64(10),
+            max: Value::Int64(20),
+        };
+        let int = |op, literal| zone_may_match(&range, DataType::Int64, &op, &literal);
+
+        assert!(int(ComparisonOp::Equal, Value::Int64(15)));
+        assert!(!int(ComparisonOp::Equal, Value::Int64(21)));
+        assert!(int(ComparisonOp::GreaterThan, Value::Int64(19)));
+        assert!(!int(ComparisonOp::GreaterThan, Value::Float64(20.5)));
+        assert!(int(ComparisonOp::GreaterThanOrEqual, Value::Int64(20)));
+        assert!(!int(ComparisonOp::LessThan, Value::Int64(10)));
+        assert!(int(ComparisonOp::LessThanOrEqual, Value::Int64(10)));
+        assert!(int(ComparisonOp::NotEqual, Value::Int64(10)));
+        // A comparison the Filter would reject with an error is left to it
+        assert!(int(ComparisonOp::Equal, Value::String("x".into())));
+
+        let single = Zone::Range {
+            min: Value::Int64(7),
+            max: Value::Int64(7),
+        };
+        for (op, expected) in [(ComparisonOp::NotEqual, false), (ComparisonOp::Equal, true)] {
+            assert_eq!(
+                zone_may_match(&single, DataType::Int64, &op, &Value::Int64(7)),
+                expected
+            );
+        }
+
+        for op in [ComparisonOp::Equal, ComparisonOp::NotEqual] {
+            assert!(!zone_may_match(
+                &Zone::AllNull,
+                DataType::Int64,
+                &op,
+                &Value::Int64(1)
+            ));
+            assert!(zone_may_match(
+                &Zone::Unordered,
+                DataType::Float64,
+                &op,
+                &Value::Float64(1.0)
+            ));
+        }
+
+        let dates = Zone::Range {
+            min: Value::Date(parse_date("2024-01-01").unwrap()),
+            max: Value::Date(parse_date("2024-01-31").unwrap()),
+        };
+        let after = Value::String("2024-02-01".to_string());
+        assert!(!zone_may_match(
+            &dates,
+            DataType::Date,
+            &ComparisonOp::GreaterThanOrEqual,
+            &after
+        ));
+        assert!(zone_may_match(
+            &dates,
+            DataType::Date,
+            &ComparisonOp::LessThan,
+            &after
+        ));
+    }
+
+    #[test]
+    fn test_table_scan_with_columns() {
+        let table = create_test_table();
+        let scan = TableScan::with_columns(table.clone(), vec![0, 2]);
+
+        assert_eq!(scan.column_indices, vec![0, 2]);
+        assert_eq!(scan.total_rows, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch size must be greater than 0")]
+    fn test_table_scan_invalid_batch_size() {
+        let table = create_test_table();
+        let _scan = TableScan::new(table).with_batch_size(0);
+    }
+
+    #[test]
+    fn test_table_scan_lifecycle() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table);
+
+        assert!(!scan.is_open());
+
+        // Open
+        assert!(scan.open().is_ok());
+        assert!(scan.is_open());
+
+        // Get batches
+        let batch1 = scan.next_batch();
+        assert!(batch1.is_ok());
+        assert!(batch1.unwrap().is_some());
+
+        // Should return None after exhausting data
+        while scan.next_batch().unwrap().is_some() {
+            // Keep consuming batches
+        }
+
+        // Close
+        assert!(scan.close().is_ok());
+        assert!(!scan.is_open());
+    }
+
+    #[test]
+    fn test_table_scan_resolved_columns_match_table() {
+        let table = create_test_table();
+
+        // Collect the batches a scan produces, in a pruned and reordered layout
+        let scan_values = |table: Table| {
+            let mut scan = TableScan::with_columns(table, vec![2, 0]).with_batch_size(2);
+            scan.open().unwrap();
+            assert_eq!(
+                scan.column_names().unwrap(),
+                vec!["age".to_string(), "id".to_string()]
+            );
+
+            let mut values = Vec::new();
+            while let Some(batch) = scan.next_batch().unwrap() {
+                for row in 0..batch.row_count() {
+                    values.push((batch.get(row, 0).unwrap(), batch.get(row, 1).unwrap()));
+                }
+            }
+            scan.close().unwrap();
+            values
+        };
+
+        let first = scan_values(table.clone());
+        let second = scan_values(table.clone());
+        assert_eq!(first, second);
+
+        // The cached columns must still read straight from the table
+        let expected: Vec<(Value, Value)> = (0..table.row_count())
+            .map(|row| {
+                (
+                    table.get_value("age", row).unwrap(),
+                    table.get_value("id", row).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(first, expected);
+    }
+
+    #[test]
+    fn test_table_scan_single_batch() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
+
+        scan.open().unwrap();
+
+        let batch = scan.next_batch().unwrap();
+        assert!(batch.is_some());
+
+        let batch = batch.unwrap();
+        assert_eq!(batch.row_count(), 5);
+        assert_eq!(batch.column_count(), 3);
+
+        // Verify data
+        let val = batch.get(0, 0).unwrap();
+        assert_eq!(val, Value::Int64(1));
+
+        let val = batch.get(1, 1).unwrap();
+        assert_eq!(val, Value::String("Bob".to_string()));
+
+        let val = batch.get(2, 2).unwrap();
+        assert_eq!(val, Value::Float64(35.0));
+
+        // Next batch should be None
+        let batch = scan.next_batch().unwrap();
+        assert!(batch.is_none());
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_table_scan_multiple_batches() {
+        let table = create_test_large_table(2500); // 2500 rows
+        let mut scan = TableScan::new(table).with_batch_size(1000);
+
+        scan.open().unwrap();
+
+        let mut total_rows = 0;
+        let mut batch_count = 0;
+
+        loop {
+            let batch = scan.next_batch().unwrap();
+            if let Some(batch) = batch {
+                batch_count += 1;
+                total_rows += batch.row_count();
+            } else {
+                break;
+            }
+        }
+
+        assert_eq!(batch_count, 3); // 1000 + 1000 + 500 = 2500
+        assert_eq!(total_rows, 2500);
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_table_scan_row_range() {
+        let table = create_test_large_table(2500);
+
+        // A range ending on a batch boundary ends with a full batch
+        let mut scan = TableScan::new(table.clone())
+            .with_batch_size(1000)
+            .with_row_range(1000, 1000);
+        assert_eq!(scan.row_range(), 1000..2000);
+        scan.open().unwrap();
+        let batch = scan.next_batch().unwrap().unwrap();
+        assert_eq!(batch.row_count(), 1000);
+        assert_eq!(batch.get(0, 0).unwrap(), Value::Int64(1000));
+        assert!(scan.next_batch().unwrap().is_none());
+        scan.close().unwrap();
+
+        // A range running past the end stops at the last row
+        let scan = TableScan::new(table.clone()).with_row_range(2400, 1000);
+        assert_eq!(scan.row_range(), 2400..2500);
+
+        // A range starting past the end is empty
+        let mut scan = TableScan::new(table).with_row_range(3000, 10);
+        assert_eq!(scan.row_range(), 2500..2500);
+        scan.open().unwrap();
+        assert!(scan.next_batch().unwrap().is_none());
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_table_scan_column_pruning() {
+        let table = create_test_table();
+        let mut scan = TableScan::with_columns(table, vec![0, 2]); // Only id and age
+
+        scan.open().unwrap();
+
+        let batch = scan.next_batch().unwrap();
+        assert!(batch.is_some());
+
+        let batch = batch.unwrap();
+        assert_eq!(batch.column_count(), 2);
+        assert_eq!(batch.row_count(), 5);
+
+        // Verify we have the right columns
+        let val = batch.get(0, 0).unwrap();
+        assert_eq!(val, Value::Int64(1)); // id
+
+        let val = batch.get(0, 1).unwrap();
+        assert_eq!(val, Value::Float64(25.0)); // age
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_table_scan_schema() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table);
+
+        // Schema not available before open
+        assert!(scan.schema().is_err());
+
+        scan.open().unwrap();
+
+        let schema = scan.schema().unwrap();
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("id", DataType::Int64),
+                Field::new("name", DataType::String),
+                Field::new("age", DataType::Float64),
+            ]
+        );
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_table_scan_pruned_schema() {
+        let table = create_test_table();
+        let mut scan = TableScan::with_columns(table, vec![0, 1]);
+
+        scan.open().unwrap();
+
+        let schema = scan.schema().unwrap();
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("id", DataType::Int64),
+                Field::new("name", DataType::String),
+            ]
+        );
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_table_scan_invalid_column_index() {
+        let table = create_test_table();
+        let mut scan = TableScan::with_columns(table, vec![0, 5]); // Column 5 doesn't exist
+
+        let result = scan.open();
+        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ExecutionError::InvalidColumnIndex { index: 5, count: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_table_scan_with_no_columns_is_rejected() {
+        let mut scan = TableScan::with_columns(create_test_table(), vec![]);
+        assert!(matches!(
+            scan.open(),
+            Err(ExecutionError::EmptyProjection { table }) if table == "test"
+        ));
+        assert!(!scan.is_open());
+        assert!(scan.next_batch().is_err());
+
+        // A table without columns has nothing to select and no rows
+        let mut scan = TableScan::with_columns(Table::new("empty".to_string()), vec![]);
+        scan.open().unwrap();
+        assert!(scan.next_batch().unwrap().is_none());
+
+        let mut parallel = ParallelScan::new(Arc::new(create_test_table()), vec![], 2);
+        assert!(matches!(
+            parallel.open(),
+            Err(ExecutionError::EmptyProjection { .. })
+        ));
+    }
+
+    #[test]
+    fn test_table_scan_next_batch_not_open() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table);
+
+        let result = scan.next_batch();
+        assert!(result.is_err());
+        assert!(matches!(result, Err(ExecutionError::OperatorNotOpen)));
+    }
+
+    #[test]
+    fn test_table_scan_double_open() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table);
+
+        scan.open().unwrap();
+        let result = scan.open();
+        assert!(result.is_err());
+        assert!(matches!(result, Err(ExecutionError::OperatorAlreadyOpen)));
+    }
+
+    #[test]
+    fn test_table_scan_empty_table() {
+        let table = Table::new("empty".to_string());
+        let mut scan = TableScan::new(table);
+
+        scan.open().unwrap();
+
+        let batch = scan.next_batch().unwrap();
+        assert!(batch.is_none());
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_table_scan_single_row_table() {
+        let mut table = Table::new("single_row".to_string());
+
+        let mut id_col = IntColumn::new();
+        id_col.push_value(Value::Int64(42)).unwrap();
+        table
+            .add_column("id".to_string(), Box::new(id_col))
+            .unwrap();
+
+        let mut scan = TableScan::new(table);
+
+        scan.open().unwrap();
+
+        let batch = scan.next_batch().unwrap();
+        assert!(batch.is_some());
+
+        let batch = batch.unwrap();
+        assert_eq!(batch.row_count(), 1);
+        assert_eq!(batch.column_count(), 1);
+
+        let val = batch.get(0, 0).unwrap();
+        assert_eq!(val, Value::Int64(42));
+
+        let batch = scan.next_batch().unwrap();
+        assert!(batch.is_none());
+
+        scan.close().unwrap();
+    }
+
+    // ParallelScan Tests
+
+    /// A Table with the same rows as `scored_table`.
+    fn scored_rows_table(rows: usize) -> Table {
+        let mut table = Table::new("scored".to_string());
+        let mut ids = IntColumn::new();
+        let mut buckets = IntColumn::new();
+        let mut scores = FloatColumn::new();
+        for row in scored_table(rows).rows() {
+            let mut row = row.into_iter();
+            ids.push_value(row.next().unwrap()).unwrap();
+            buckets.push_value(row.next().unwrap()).unwrap();
+            scores.push_value(row.next().unwrap()).unwrap();
+        }
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table
+            .add_column("bucket".to_string(), Box::new(buckets))
+            .unwrap();
+        table
+            .add_column("score".to_string(), Box::new(scores))
+            .unwrap();
+        table
+    }
+
+    #[test]
+    fn test_parallel_scan_matches_serial_filter() {
+        let table = scored_rows_table(50_000);
+        let predicate: Arc<dyn Predicate> = Arc::new(BinaryComparison::new(
+            1,
+            ComparisonOp::GreaterThan,
+            Value::Int64(50),
+        ));
+
+        let mut serial = Filter::new(
+            Box::new(TableScan::with_columns(table.clone(), vec![0, 1])),
+            Arc::clone(&predicate),
+        );
+        let mut parallel = ParallelScan::new(Arc::new(table), vec![0, 1], 4)
+            .with_batch_size(700)
+            .with_predicate(predicate);
+
+        let expected = drain_rows(&mut serial);
+        let mut actual = drain_rows(&mut parallel);
+        actual.sort_by(|a, b| compare_sort_values(&a[0], &b[0]));
+        assert!(!expected.is_empty());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_parallel_scan_stops_when_closed_early() {
+        let mut scan = ParallelScan::new(Arc::new(scored_rows_table(20_000)), vec![0, 2], 3)
+            .with_batch_size(100);
+
+        scan.open().unwrap();
+        assert_eq!(scan.column_names().unwrap(), vec!["id", "score"]);
+        assert_eq!(scan.next_batch().unwrap().unwrap().row_count(), 100);
+        scan.close().unwrap();
+
+        // Predicates are checked against the column types at open
+        let mut mismatched = ParallelScan::new(Arc::new(scored_rows_table(10)), vec![0], 2)
+            .with_predicate(Arc::new(BinaryComparison::new(
+                0,
+                ComparisonOp::Equal,
+                Value::String("x".to_string()),
+            )));
+        assert!(mismatched.open().is_err());
+    }
+
+    // TopN Tests
+    fn scored_table(rows: usize) -> VirtualTable {
+        VirtualTable::new(
+            "scored".to_string(),
+            vec![
+                ("id".to_string(), DataType::Int64),
+                ("bucket".to_string(), DataType::Int64),
+                ("score".to_string(), DataType::Float64),
+            ],
+            move || {
+                Box::new((0..rows as i64).map(|id| {
+                    let bucket = if id % 13 == 0 {
+                        Value::Null
+                    } else {
+                        Value::Int64(id % 97)
+                    };
+                    let score = Value::Float64(((id * 7919) % 1000) as f64 / 10.0);
+                    vec![Value::Int64(id), bucket, score]
+                }))
+            },
+        )
+        .unwrap()
+    }
+
+    fn drain_rows(operator: &mut dyn Operator) -> Vec<Vec<Value>> {
+        operator.open().unwrap();
+        let mut rows = Vec::new();
+        while let Some(batch) = operator.next_batch().unwrap() {
+            for row in 0..batch.row_count() {
+                rows.push(
+                    (0..batch.column_count())
+                        .map(|col| batch.get(row, col).unwrap())
+                        .collect(),
+                );
+            }
+        }
+        operator.close().unwrap();
+        rows
+    }
+
+    // Lifecycle Tests
+
+    /// One open/consume/close pass, returning the rows of each batch
+    fn run_pass(operator: &mut dyn Operator) -> Vec<Vec<Vec<Value>>> {
+        operator.open().unwrap();
+        let mut batches = Vec::new();
+        while let Some(batch) = operator.next_batch().unwrap() {
+            batches.push(
+                (0..batch.row_count())
+                    .map(|row| {
+                        (0..batch.column_count())
+                            .map(|col| batch.get(row, col).unwrap())
+                            .collect()
+                    })
+                    .collect(),
+            );
+        }
+        operator.close().unwrap();
+        batches
+    }
+
+    #[test]
+    fn test_every_operator_can_be_reopened() {
+        let users = Arc::new(create_test_table());
+        let orders = Arc::new(create_orders_table());
+        let scan = || -> Box<dyn Operator> {
+            Box::new(TableScan::new(Arc::clone(&users)).with_batch_size(2))
+        };
+        let older = || -> Arc<dyn Predicate> {
+            Arc::new(BinaryComparison::new(
+                2,
+                ComparisonOp::GreaterThan,
+                Value::Float64(30.0),
+            ))
+        };
+        let spill_dir = tempfile::tempdir().unwrap();
+
+        let operators: Vec<(&str, Box<dyn Operator>)> = vec![
+            ("TableScan", scan()),
+            (
+                "TableScan with a row range",
+                Box::new(
+                    TableScan::with_columns(Arc::clone(&users), vec![0, 2])
+                        .with_batch_size(2)
+                        .with_row_range(1, 3),
+                ),
+            ),
+            (
+                "TableScan with a pushed predicate",
+                Box::new(
+                    TableScan::new(Arc::new(create_test_large_table(5000))).with_pushed_predicate(
+                        BinaryComparison::new(0, ComparisonOp::GreaterThan, Value::Int64(4000)),
+                    ),
+                ),
+            ),
+            (
+                "VirtualScan",
+                Box::new(VirtualScan::new(scored_table(50)).with_batch_size(16)),
+            ),
+            (
+                "ParallelScan",
+                Box::new(ParallelScan::new(Arc::clone(&users), vec![0, 1], 2).with_batch_size(2)),
+            ),
+            ("Filter", Box::new(Filter::new(scan(), older()))),
+            ("Project", Box::new(Project::new(scan(), vec![1, 0]))),
+            (
+                "Compute",
+                Box::new(Compute::new(
+                    scan(),
+                    vec![(
+                        "double".to_string(),
+                        arithmetic(
+                            ScalarExpression::Column(0),
+                            ArithmeticOp::Multiply,
+                            ScalarExpression::Literal(Value::Int64(2)),
+                        ),
+                    )],
+                )),
+            ),
+            (
+                "GroupBy",
+                Box::new(GroupBy::new(
+                    Box::new(VirtualScan::new(scored_table(200))),
+                    vec![1],
+                    vec![2],
+                    vec![Box::new(SumAggregate::new(DataType::Float64).unwrap())],
+                )),
+            ),
+            (
+                "Sort",
+                Box::new(Sort::new(scan(), vec![2], vec![SortDirection::Descending])),
+            ),
+            (
+                "Sort with spilled runs",
+                Box::new(
+                    Sort::new(
+                        Box::new(VirtualScan::new(scored_table(5000))),
+                        vec![2, 0],
+                        vec![SortDirection::Ascending, SortDirection::Ascending],
+                    )
+                    .with_memory_limit(16 * 1024)
+                    .with_spill_dir(spill_dir.path()),
+                ),
+            ),
+            (
+                "TopN",
+                Box::new(TopN::new(
+                    scan(),
+                    vec![2],
+                    vec![SortDirection::Descending],
+                    3,
+                )),
+            ),
+            ("Limit", Box::new(Limit::new(scan(), Some(2), 1))),
+            (
+                "HashJoin",
+                Box::new(HashJoin::new(
+                    scan(),
+                    Box::new(TableScan::new(Arc::clone(&orders))),
+                    0,
+                    0,
+                )),
+            ),
+            (
+                "ProfiledOperator",
+                ProfiledOperator::wrap_plan(Box::new(Filter::new(scan(), older()))),
+            ),
+        ];
+
+        for (name, mut operator) in operators {
+            let mut first = run_pass(operator.as_mut());
+            let mut second = run_pass(operator.as_mut());
+            assert!(!first.is_empty(), "{} returned nothing", name);
+            if name == "ParallelScan" {
+                // Workers deliver batches in no particular order
+                first.sort_by_key(|batch| format!("{:?}", batch));
+                second.sort_by_key(|batch| format!("{:?}", batch));
+            }
+            assert_eq!(first, second, "{} changed when reopened", name);
+
+            // A third pass without consuming everything, then a full one
+            operator.open().unwrap();
+            operator.next_batch().unwrap();
+            operator.close().unwrap();
+            let mut third = run_pass(operator.as_mut());
+            if name == "ParallelScan" {
+                third.sort_by_key(|batch| format!("{:?}", batch));
+            }
+            assert_eq!(first, third, "{} changed after a partial pass", name);
+        }
+    }
+
+    #[test]
+    fn test_open_twice_and_next_batch_before_open_are_errors() {
+        let mut scan = TableScan::new(create_test_table());
+        assert!(matches!(
+            scan.next_batch(),
+            Err(ExecutionError::OperatorNotOpen)
+        ));
+        scan.open().unwrap();
+        assert!(matches!(
+            scan.open(),
+            Err(ExecutionError::OperatorAlreadyOpen)
+        ));
+        scan.close().unwrap();
+        assert!(matches!(
+            scan.next_batch(),
+            Err(ExecutionError::OperatorNotOpen)
+        ));
+        // Closing twice is harmless
+        scan.close().unwrap();
+    }
+
+    /// A table of `rows` rows with `groups` distinct Int64 keys
+    fn generated_groups(rows: usize, groups: i64) -> Table {
+        use crate::datagen::{generate_table, ColumnSpec, TableSpec};
+        let spec = TableSpec::new(3)
+            .column(
+                "key",
+                ColumnSpec::UniformInt {
+                    min: 0,
+                    max: groups - 1,
+                },
+            )
+            .column("amount", ColumnSpec::UniformFloat { min: 0.0, max: 1.0 });
+        generate_table("generated", rows, &spec).unwrap()
+    }
+
+    fn grouped_sum(table: Table, tracker: &Arc<MemoryTracker>) -> GroupBy {
+        let mut group_by = GroupBy::new(
+            Box::new(TableScan::new(table)),
+            vec![0],
+            vec![1],
+            vec![Box::new(SumAggregate::new(DataType::Float64).unwrap())],
+        );
+        group_by.set_memory_tracker(tracker);
+        group_by
+    }
+
+    #[test]
+    fn test_group_by_over_memory_limit_is_resource_exhausted() {
+        let tracker = Arc::new(MemoryTracker::with_limit(4 * 1024));
+        let mut group_by = grouped_sum(generated_groups(20_000, 5_000), &tracker);
+        match group_by.open() {
+            Err(ExecutionError::ResourceExhausted {
+                operator,
+                requested,
+                limit,
+            }) => {
+                assert_eq!(operator, "GroupBy(key; 1 aggregate)");
+                assert!(requested > limit);
+                assert_eq!(limit, 4 * 1024);
+            }
+            other => panic!("expected ResourceExhausted, got {:?}", other.err()),
+        }
+        group_by.close().unwrap();
+        assert_eq!(tracker.used(), 0);
+
+        // The error names the operator and survives being passed up a plan
+        let err = ExecutionError::from_child(ExecutionError::ResourceExhausted {
+            operator: "Sort(id)".to_string(),
+            requested: 10,
+            limit: 5,
+        });
+        assert!(matches!(err, ExecutionError::ResourceExhausted { .. }));
+        assert!(err.to_string().contains("Sort(id)"), "{}", err);
+    }
+
+    #[test]
+    fn test_group_by_reports_peak_memory() {
+        let tracker = Arc::new(MemoryTracker::with_limit(64 * 1024 * 1024));
+        let mut group_by = grouped_sum(generated_groups(20_000, 5_000), &tracker);
+        let groups: usize = run_pass(&mut group_by).iter().map(Vec::len).sum();
+        assert!(groups > 4_000, "{}", groups);
+
+        // At least a key and an aggregate state per group, and nothing
+        // held once closed
+        let peak = tracker.peak();
+        assert!(peak >= groups * AGGREGATE_STATE_BYTES, "{}", peak);
+        assert!(peak < 16 * 1024 * 1024, "{}", peak);
+        assert_eq!(tracker.used(), 0);
+
+        // A reopened GroupBy counts its groups again from nothing
+        run_pass(&mut group_by);
+        assert_eq!(tracker.peak(), peak);
+    }
+
+    #[test]
+    fn test_sort_and_hash_join_count_buffered_rows() {
+        let users = Arc::new(create_test_table());
+        let orders = Arc::new(create_orders_table());
+        let operators: Vec<Box<dyn Operator>> = vec![
+            Box::new(Sort::new(
+                Box::new(TableScan::new(Arc::clone(&users))),
+                vec![2],
+                vec![SortDirection::Descending],
+            )),
+            Box::new(HashJoin::new(
+                Box::new(TableScan::new(Arc::clone(&users))),
+                Box::new(TableScan::new(Arc::clone(&orders))),
+                0,
+                0,
+            )),
+            // Trackers reach the operators below pass-through ones
+            Box::new(Limit::new(
+                Box::new(Sort::new(
+                    Box::new(TableScan::new(Arc::clone(&users))),
+                    vec![0],
+                    vec![SortDirection::Ascending],
+                )),
+                Some(1),
+                0,
+            )),
+        ];
+        for mut operator in operators {
+            let tracker = Arc::new(MemoryTracker::new());
+            operator.set_memory_tracker(&tracker);
+            operator.open().unwrap();
+            assert!(tracker.used() > 0, "{}", operator.describe());
+            operator.close().unwrap();
+            assert!(tracker.peak() > 0);
+            assert_eq!(tracker.used(), 0);
+        }
+
+        let tracker = Arc::new(MemoryTracker::with_limit(16));
+        let mut join = HashJoin::new(
+            Box::new(TableScan::new(Arc::clone(&users))),
+            Box::new(TableScan::new(Arc::clone(&orders))),
+            0,
+            0,
+        );
+        join.set_memory_tracker(&tracker);
+        assert!(matches!(
+            join.open(),
+            Err(ExecutionError::ResourceExhausted { operator, .. }) if operator.starts_with("HashJoin(")
+        ));
     }
 
-    fn schema(&self) -> Result<std::collections::HashMap<String, DataType>> {
-        self.child.schema()
-    }
+    #[test]
+    fn test_external_sort_matches_in_memory_sort() {
+        const ROWS: usize = 100_000;
+        let spill_dir = tempfile::tempdir().unwrap();
+        let keys = vec![1, 2];
+        let directions = vec![SortDirection::Descending, SortDirection::Ascending];
+
+        let mut external = Sort::new(
+            Box::new(VirtualScan::new(scored_table(ROWS))),
+            keys.clone(),
+            directions.clone(),
+        )
+        .with_memory_limit(64 * 1024)
+        .with_spill_dir(spill_dir.path());
+        let mut in_memory = Sort::new(
+            Box::new(VirtualScan::new(scored_table(ROWS))),
+            keys,
+            directions,
+        );
 
-    fn column_names(&self) -> Result<Vec<String>> {
-        self.child.column_names()
-    }
+        external.open().unwrap();
+        assert!(external.spilled_runs() > 1);
+        assert!(std::fs::read_dir(spill_dir.path()).unwrap().count() > 1);
+        let mut rows = Vec::new();
+        while let Some(batch) = external.next_batch().unwrap() {
+            assert!(batch.row_count() <= 1024);
+            for row in 0..batch.row_count() {
+                rows.push(
+                    (0..batch.column_count())
+                        .map(|col| batch.get(row, col).unwrap())
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+        external.close().unwrap();
 
-    fn is_open(&self) -> bool {
-        self.state == OperatorState::Open
+        // Every row comes back, in exactly the in-memory order (ties included)
+        assert_eq!(rows.len(), ROWS);
+        assert_eq!(rows, drain_rows(&mut in_memory));
+
+        // Closing deletes the spill files
+        assert_eq!(std::fs::read_dir(spill_dir.path()).unwrap().count(), 0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::aggregates::*;
-    use crate::column::{FloatColumn, IntColumn, StringColumn};
-    use crate::types::{DataType, Value};
+    #[test]
+    fn test_external_sort_cleans_up_on_drop() {
+        let spill_dir = tempfile::tempdir().unwrap();
+        let mut sort = Sort::new(
+            Box::new(TableScan::new(scored_rows_table(5000))),
+            vec![2],
+            vec![SortDirection::Ascending],
+        )
+        .with_memory_limit(4 * 1024)
+        .with_spill_dir(spill_dir.path())
+        .with_batch_size(100);
+
+        sort.open().unwrap();
+        let first = sort.next_batch().unwrap().unwrap();
+        assert_eq!(first.row_count(), 100);
+        assert!(sort.spilled_runs() > 0);
+
+        drop(sort);
+        assert_eq!(std::fs::read_dir(spill_dir.path()).unwrap().count(), 0);
+    }
 
     #[test]
-    fn test_batch_creation() {
-        let mut col1 = IntColumn::new();
-        col1.push_value(Value::Int64(1)).unwrap();
-        col1.push_value(Value::Int64(2)).unwrap();
-        col1.push_value(Value::Int64(3)).unwrap();
+    fn test_spilled_values_round_trip() {
+        let values = vec![
+            Value::Null,
+            Value::Int64(i64::MIN),
+            Value::Float64(-0.0),
+            Value::Float64(f64::NAN),
+            Value::String(String::new()),
+            Value::String("naïve, \"quoted\"\n".to_string()),
+            Value::Boolean(true),
+            Value::Date(-719_162),
+        ];
+        let mut bytes = Vec::new();
+        for value in &values {
+            write_spilled_value(&mut bytes, value).unwrap();
+        }
 
-        let mut col2 = FloatColumn::new();
-        col2.push_value(Value::Float64(1.0)).unwrap();
-        col2.push_value(Value::Float64(2.0)).unwrap();
-        col2.push_value(Value::Float64(3.0)).unwrap();
+        let mut reader = bytes.as_slice();
+        for value in &values {
+            let read = read_spilled_value(&mut reader).unwrap().unwrap();
+            match (value, &read) {
+                (Value::Float64(a), Value::Float64(b)) => assert_eq!(a.to_bits(), b.to_bits()),
+                _ => assert_eq!(&read, value),
+            }
+        }
+        assert!(read_spilled_value(&mut reader).unwrap().is_none());
+    }
 
-        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+    /// Rows of (id, word, group): short random words with many repeats and
+    /// a group that is NULL for every eleventh row.
+    fn random_words_table(rows: usize) -> VirtualTable {
+        VirtualTable::new(
+            "words".to_string(),
+            vec![
+                ("id".to_string(), DataType::Int64),
+                ("word".to_string(), DataType::String),
+                ("group".to_string(), DataType::Int64),
+            ],
+            move || {
+                let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+                Box::new((0..rows as i64).map(move |id| {
+                    state = state
+                        .wrapping_mul(6_364_136_223_846_793_005)
+                        .wrapping_add(1_442_695_040_888_963_407);
+                    let bits = state >> 33;
+                    let word: String = (0..1 + bits % 4)
+                        .map(|i| (b'a' + ((bits >> (4 * i + 2)) % 6) as u8) as char)
+                        .collect();
+                    let group = if id % 11 == 0 {
+                        Value::Null
+                    } else {
+                        Value::Int64((bits >> 20) as i64 % 5)
+                    };
+                    vec![Value::Int64(id), Value::String(word), group]
+                }))
+            },
+        )
+        .unwrap()
+    }
 
-        assert_eq!(batch.row_count(), 3);
-        assert_eq!(batch.column_count(), 2);
+    /// The rows of `table` sorted the way Sort used to: as row vectors,
+    /// compared value by value with a stable sort.
+    fn sort_rows_by_value(
+        table: VirtualTable,
+        sort_columns: &[usize],
+        sort_directions: &[SortDirection],
+    ) -> Vec<Vec<Value>> {
+        let mut rows = drain_rows(&mut VirtualScan::new(table));
+        rows.sort_by(|a, b| compare_rows(sort_columns, sort_directions, a, b));
+        rows
     }
 
     #[test]
-    #[should_panic(expected = "Cannot create a batch with no columns")]
-    fn test_batch_empty_columns() {
-        let _batch = Batch::new(vec![]);
+    fn test_sort_on_random_strings_matches_value_comparison() {
+        const ROWS: usize = 200_000;
+        for (keys, directions) in [
+            (vec![1], vec![SortDirection::Ascending]),
+            (vec![1], vec![SortDirection::Descending]),
+            (
+                vec![2, 1],
+                vec![SortDirection::Descending, SortDirection::Ascending],
+            ),
+        ] {
+            let mut sort = Sort::new(
+                Box::new(VirtualScan::new(random_words_table(ROWS))),
+                keys.clone(),
+                directions.clone(),
+            );
+            let rows = drain_rows(&mut sort);
+            assert_eq!(rows.len(), ROWS);
+            assert_eq!(
+                rows,
+                sort_rows_by_value(random_words_table(ROWS), &keys, &directions)
+            );
+
+            // Rows with equal keys keep their input (id) order
+            for pair in rows.windows(2) {
+                let key =
+                    |row: &Vec<Value>| keys.iter().map(|&k| row[k].clone()).collect::<Vec<_>>();
+                if key(&pair[0]) == key(&pair[1]) {
+                    assert_eq!(
+                        compare_sort_values(&pair[0][0], &pair[1][0]),
+                        std::cmp::Ordering::Less
+                    );
+                }
+            }
+        }
     }
 
+    /// Times Sort against the row-vector sort it replaced: buffer each row
+    /// as a `Vec<Value>`, sort the rows, and copy them back into columns.
+    /// Run with
+    /// `cargo test --release --lib -- --ignored bench_sort_strings --nocapture`.
     #[test]
-    #[should_panic(expected = "Column 1 has 5 rows, but column 0 has 3 rows")]
-    fn test_batch_mismatched_lengths() {
-        let mut col1 = IntColumn::new();
-        col1.push_value(Value::Int64(1)).unwrap();
-        col1.push_value(Value::Int64(2)).unwrap();
-        col1.push_value(Value::Int64(3)).unwrap();
-
-        let mut col2 = IntColumn::new();
-        col2.push_value(Value::Int64(1)).unwrap();
-        col2.push_value(Value::Int64(2)).unwrap();
-        col2.push_value(Value::Int64(3)).unwrap();
-        col2.push_value(Value::Int64(4)).unwrap();
-        col2.push_value(Value::Int64(5)).unwrap();
+    #[ignore]
+    fn bench_sort_strings_against_row_sort() {
+        const ROWS: usize = 1_000_000;
+        let keys = [1, 0];
+        let directions = [SortDirection::Ascending, SortDirection::Descending];
+        let types = [DataType::Int64, DataType::String, DataType::Int64];
+
+        let start = std::time::Instant::now();
+        let mut rows = drain_rows(&mut VirtualScan::new(random_words_table(ROWS)));
+        rows.sort_by(|a, b| compare_rows(&keys, &directions, a, b));
+        let columns: Vec<_> = types
+            .iter()
+            .enumerate()
+            .map(|(col, data_type)| {
+                column_from_values(
+                    *data_type,
+                    rows.iter().map(|row| row[col].clone()).collect(),
+                )
+                .unwrap()
+            })
+            .collect();
+        let row_sort_time = start.elapsed();
+        assert_eq!(columns[0].len(), ROWS);
+
+        let start = std::time::Instant::now();
+        let mut sort = Sort::new(
+            Box::new(VirtualScan::new(random_words_table(ROWS))),
+            keys.to_vec(),
+            directions.to_vec(),
+        );
+        sort.open().unwrap();
+        let mut sorted_rows = 0;
+        while let Some(batch) = sort.next_batch().unwrap() {
+            sorted_rows += batch.row_count();
+        }
+        sort.close().unwrap();
+        let sort_time = start.elapsed();
+        assert_eq!(sorted_rows, ROWS);
 
-        let _batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+        println!(
+            "{} rows: row vectors {:?}, Sort {:?}",
+            ROWS, row_sort_time, sort_time
+        );
+        assert_eq!(drain_rows(&mut sort), rows);
     }
 
     #[test]
-    fn test_batch_column_access() {
-        let mut col1 = IntColumn::new();
-        col1.push_value(Value::Int64(1)).unwrap();
-        col1.push_value(Value::Int64(2)).unwrap();
-        col1.push_value(Value::Int64(3)).unwrap();
+    fn test_top_n_matches_sort_and_limit_on_large_input() {
+        const ROWS: usize = 1_000_000;
+        let keys = vec![1, 2];
+        let directions = vec![SortDirection::Descending, SortDirection::Ascending];
+
+        let mut top = TopN::new(
+            Box::new(VirtualScan::new(scored_table(ROWS))),
+            keys.clone(),
+            directions.clone(),
+            10,
+        );
+        let mut naive = Limit::new(
+            Box::new(Sort::new(
+                Box::new(VirtualScan::new(scored_table(ROWS))),
+                keys,
+                directions,
+            )),
+            Some(10),
+            0,
+        );
 
-        let mut col2 = StringColumn::new();
-        col2.push_value(Value::String("a".to_string())).unwrap();
-        col2.push_value(Value::String("b".to_string())).unwrap();
-        col2.push_value(Value::String("c".to_string())).unwrap();
+        let top_rows = drain_rows(&mut top);
+        assert_eq!(top_rows.len(), 10);
+        assert_eq!(top_rows, drain_rows(&mut naive));
+        assert!(top_rows.iter().all(|row| row[1] == Value::Int64(96)));
+    }
 
-        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+    #[test]
+    fn test_top_n_keeps_input_order_for_ties() {
+        // Every bucket value repeats, so ties must resolve as a stable sort would
+        for limit in [0, 1, 5, 30, 50] {
+            let mut top = TopN::new(
+                Box::new(VirtualScan::new(scored_table(40)).with_batch_size(7)),
+                vec![1],
+                vec![SortDirection::Ascending],
+                limit,
+            )
+            .with_batch_size(4);
+            let mut naive = Limit::new(
+                Box::new(Sort::new(
+                    Box::new(VirtualScan::new(scored_table(40))),
+                    vec![1],
+                    vec![SortDirection::Ascending],
+                )),
+                Some(limit),
+                0,
+            );
+
+            let top_rows = drain_rows(&mut top);
+            assert_eq!(top_rows.len(), limit.min(40));
+            assert_eq!(top_rows, drain_rows(&mut naive), "limit {}", limit);
+        }
+    }
 
-        let result = batch.column(0);
-        assert!(result.is_ok());
+    // VirtualScan Tests
+    fn virtual_pairs_table(rows: Vec<Vec<Value>>) -> VirtualTable {
+        VirtualTable::new(
+            "pairs".to_string(),
+            vec![
+                ("n".to_string(), DataType::Int64),
+                ("label".to_string(), DataType::String),
+            ],
+            move || Box::new(rows.clone().into_iter()),
+        )
+        .unwrap()
+    }
 
-        let result = batch.column(2);
-        assert!(result.is_err());
-        assert!(matches!(
-            result,
-            Err(ExecutionError::InvalidColumnIndex { index: 2, count: 2 })
-        ));
+    fn pair_rows(count: i64) -> Vec<Vec<Value>> {
+        (0..count)
+            .map(|n| vec![Value::Int64(n), Value::String(format!("row{}", n))])
+            .collect()
     }
 
     #[test]
-    fn test_batch_get_value() {
-        let mut col1 = IntColumn::new();
-        col1.push_value(Value::Int64(10)).unwrap();
-        col1.push_value(Value::Int64(20)).unwrap();
-        col1.push_value(Value::Int64(30)).unwrap();
+    fn test_virtual_scan_batches_rows_lazily() {
+        let mut scan = VirtualScan::new(virtual_pairs_table(pair_rows(5))).with_batch_size(2);
+        assert!(matches!(scan.schema(), Err(ExecutionError::SchemaNotFound)));
 
-        let mut col2 = StringColumn::new();
-        col2.push_value(Value::String("x".to_string())).unwrap();
-        col2.push_value(Value::String("y".to_string())).unwrap();
-        col2.push_value(Value::String("z".to_string())).unwrap();
+        scan.open().unwrap();
+        assert_eq!(scan.column_names().unwrap(), vec!["n", "label"]);
+        assert_eq!(
+            scan.schema().unwrap().data_type("label"),
+            Some(DataType::String)
+        );
 
-        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+        let sizes: Vec<usize> = std::iter::from_fn(|| scan.next_batch().unwrap())
+            .map(|batch| batch.row_count())
+            .collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        scan.close().unwrap();
 
-        // Valid access - returns Value enum
-        let result = batch.get(1, 0);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), Value::Int64(20));
+        // Re-opening takes a fresh snapshot from the factory
+        scan.open().unwrap();
+        let batch = scan.next_batch().unwrap().unwrap();
+        assert_eq!(batch.get(1, 1).unwrap(), Value::String("row1".to_string()));
+    }
 
-        let result = batch.get(2, 1);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), Value::String("z".to_string()));
+    #[test]
+    fn test_virtual_scan_with_columns() {
+        let mut scan = VirtualScan::with_columns(virtual_pairs_table(pair_rows(3)), vec![1]);
+        scan.open().unwrap();
+        let batch = scan.next_batch().unwrap().unwrap();
+        assert_eq!(batch.column_count(), 1);
+        assert_eq!(batch.get(2, 0).unwrap(), Value::String("row2".to_string()));
 
-        // Test get_as_string
-        let result = batch.get_as_string(1, 0);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "20");
+        let mut bad = VirtualScan::with_columns(virtual_pairs_table(vec![]), vec![5]);
+        assert!(matches!(
+            bad.open(),
+            Err(ExecutionError::InvalidColumnIndex { index: 5, count: 2 })
+        ));
 
-        // Invalid row index
-        let result = batch.get(5, 0);
-        assert!(result.is_err());
+        let mut empty = VirtualScan::with_columns(virtual_pairs_table(pair_rows(3)), vec![]);
         assert!(matches!(
-            result,
-            Err(ExecutionError::InvalidRowIndex { index: 5, count: 3 })
+            empty.open(),
+            Err(ExecutionError::EmptyProjection { .. })
         ));
+    }
 
-        // Invalid column index
-        let result = batch.get(0, 5);
-        assert!(result.is_err());
+    #[test]
+    fn test_virtual_scan_rejects_malformed_rows() {
+        let short_row = vec![vec![Value::Int64(1)]];
+        let mut scan = VirtualScan::new(virtual_pairs_table(short_row));
+        scan.open().unwrap();
         assert!(matches!(
-            result,
-            Err(ExecutionError::InvalidColumnIndex { index: 5, count: 2 })
+            scan.next_batch(),
+            Err(ExecutionError::SchemaMismatch(_))
         ));
+
+        // The error names the table and column holding the bad value
+        let wrong_type = vec![vec![Value::String("x".to_string()), Value::Int64(1)]];
+        let mut scan = VirtualScan::new(virtual_pairs_table(wrong_type));
+        scan.open().unwrap();
+        match scan.next_batch() {
+            Err(ExecutionError::TypeMismatch {
+                expected,
+                actual,
+                column,
+            }) => {
+                assert_eq!((expected, actual), (DataType::Int64, DataType::String));
+                assert_eq!(column.as_deref(), Some("pairs.n"));
+            }
+            other => panic!("expected a type mismatch, got {:?}", other),
+        }
     }
 
+    // Predicate and Filter Operator Tests
     #[test]
-    fn test_batch_get_as_string() {
-        let mut col1 = StringColumn::new();
-        col1.push_value(Value::String("hello".to_string())).unwrap();
-        col1.push_value(Value::String("world".to_string())).unwrap();
-        col1.push_value(Value::String("test".to_string())).unwrap();
+    fn test_binary_comparison_equal() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
 
-        let batch = Batch::new(vec![Arc::new(col1)]);
+        scan.open().unwrap();
 
-        let result = batch.get_as_string(1, 0);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "world");
+        let batch = scan.next_batch().unwrap().unwrap();
+
+        // Test equals with integer
+        let pred = BinaryComparison::new(0, ComparisonOp::Equal, Value::Int64(1));
+        assert!(pred.eval(&batch, 0).unwrap()); // row 0, id=1
+        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, id=2
+
+        // Test equals with string
+        let pred =
+            BinaryComparison::new(1, ComparisonOp::Equal, Value::String("Alice".to_string()));
+        assert!(pred.eval(&batch, 0).unwrap()); // row 0, name=Alice
+        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, name=Bob
+
+        scan.close().unwrap();
     }
 
     #[test]
-    fn test_batch_select() {
-        let mut col1 = IntColumn::new();
-        col1.push_value(Value::Int64(1)).unwrap();
-        col1.push_value(Value::Int64(2)).unwrap();
-        col1.push_value(Value::Int64(3)).unwrap();
+    fn test_binary_comparison_not_equal() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
 
-        let mut col2 = StringColumn::new();
-        col2.push_value(Value::String("a".to_string())).unwrap();
-        col2.push_value(Value::String("b".to_string())).unwrap();
-        col2.push_value(Value::String("c".to_string())).unwrap();
+        scan.open().unwrap();
 
-        let mut col3 = FloatColumn::new();
-        col3.push_value(Value::Float64(1.0)).unwrap();
-        col3.push_value(Value::Float64(2.0)).unwrap();
-        col3.push_value(Value::Float64(3.0)).unwrap();
+        let batch = scan.next_batch().unwrap().unwrap();
+
+        // Test not equals with float
+        let pred = BinaryComparison::new(2, ComparisonOp::NotEqual, Value::Float64(25.0));
+        assert!(!pred.eval(&batch, 0).unwrap()); // row 0, age=25.0
+        assert!(pred.eval(&batch, 1).unwrap()); // row 1, age=30.0
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_binary_comparison_less_than() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
+
+        scan.open().unwrap();
+
+        let batch = scan.next_batch().unwrap().unwrap();
+
+        // Test less than with integer
+        let pred = BinaryComparison::new(0, ComparisonOp::LessThan, Value::Int64(3));
+        assert!(pred.eval(&batch, 0).unwrap()); // row 0, id=1
+        assert!(pred.eval(&batch, 1).unwrap()); // row 1, id=2
+        assert!(!pred.eval(&batch, 2).unwrap()); // row 2, id=3
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_binary_comparison_greater_than() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
+
+        scan.open().unwrap();
+
+        let batch = scan.next_batch().unwrap().unwrap();
+
+        // Test greater than with float
+        let pred = BinaryComparison::new(2, ComparisonOp::GreaterThan, Value::Float64(30.0));
+        assert!(!pred.eval(&batch, 0).unwrap()); // row 0, age=25.0
+        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, age=30.0
+        assert!(pred.eval(&batch, 2).unwrap()); // row 2, age=35.0
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_binary_comparison_less_than_or_equal() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
+
+        scan.open().unwrap();
+
+        let batch = scan.next_batch().unwrap().unwrap();
+
+        // Test less than or equal with integer
+        let pred = BinaryComparison::new(0, ComparisonOp::LessThanOrEqual, Value::Int64(2));
+        assert!(pred.eval(&batch, 0).unwrap()); // row 0, id=1
+        assert!(pred.eval(&batch, 1).unwrap()); // row 1, id=2
+        assert!(!pred.eval(&batch, 2).unwrap()); // row 2, id=3
+
+        scan.close().unwrap();
+    }
+
+    #[test]
+    fn test_binary_comparison_greater_than_or_equal() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
 
-        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2), Arc::new(col3)]);
+        scan.open().unwrap();
 
-        let selected = batch.select(&[0, 2]).unwrap();
+        let batch = scan.next_batch().unwrap().unwrap();
 
-        assert_eq!(selected.row_count(), 3);
-        assert_eq!(selected.column_count(), 2);
+        // Test greater than or equal with float
+        let pred = BinaryComparison::new(2, ComparisonOp::GreaterThanOrEqual, Value::Float64(35.0));
+        assert!(!pred.eval(&batch, 0).unwrap()); // row 0, age=25.0
+        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, age=30.0
+        assert!(pred.eval(&batch, 2).unwrap()); // row 2, age=35.0
+        assert!(pred.eval(&batch, 3).unwrap()); // row 3, age=40.0
 
-        let val = selected.get(0, 0).unwrap();
-        assert_eq!(val, Value::Int64(1));
+        scan.close().unwrap();
+    }
 
-        let val = selected.get(1, 1).unwrap();
-        assert_eq!(val, Value::Float64(2.0));
+    #[test]
+    fn test_comparison_op_display() {
+        assert_eq!(format!("{}", ComparisonOp::Equal), "=");
+        assert_eq!(format!("{}", ComparisonOp::NotEqual), "!=");
+        assert_eq!(format!("{}", ComparisonOp::LessThan), "<");
+        assert_eq!(format!("{}", ComparisonOp::LessThanOrEqual), "<=");
+        assert_eq!(format!("{}", ComparisonOp::GreaterThan), ">");
+        assert_eq!(format!("{}", ComparisonOp::GreaterThanOrEqual), ">=");
     }
 
     #[test]
-    fn test_batch_select_invalid_index() {
-        let mut col1 = IntColumn::new();
-        col1.push_value(Value::Int64(1)).unwrap();
-        col1.push_value(Value::Int64(2)).unwrap();
-        col1.push_value(Value::Int64(3)).unwrap();
+    fn test_and_predicate() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
 
-        let batch = Batch::new(vec![Arc::new(col1)]);
+        scan.open().unwrap();
 
-        let result = batch.select(&[0, 5]);
-        assert!(result.is_err());
-        assert!(matches!(
-            result,
-            Err(ExecutionError::InvalidColumnIndex { index: 5, count: 1 })
+        let batch = scan.next_batch().unwrap().unwrap();
+
+        // Test: id > 1 AND id < 4
+        let pred1 = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThan,
+            Value::Int64(1),
         ));
-    }
+        let pred2 = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::LessThan,
+            Value::Int64(4),
+        ));
+        let and_pred = And::new(pred1, pred2);
 
-    #[test]
-    fn test_batch_empty() {
-        let batch = Batch::empty();
+        assert!(!and_pred.eval(&batch, 0).unwrap()); // id=1 (not > 1)
+        assert!(and_pred.eval(&batch, 1).unwrap()); // id=2 (1 < 2 < 4)
+        assert!(and_pred.eval(&batch, 2).unwrap()); // id=3 (1 < 3 < 4)
+        assert!(!and_pred.eval(&batch, 3).unwrap()); // id=4 (not < 4)
 
-        assert!(batch.is_empty());
-        assert_eq!(batch.row_count(), 0);
+        scan.close().unwrap();
     }
 
     #[test]
-    fn test_execution_error_display() {
-        let err = ExecutionError::OperatorNotOpen;
-        assert_eq!(
-            err.to_string(),
-            "Operator must be opened before calling this operation"
-        );
+    fn test_or_predicate() {
+        let table = create_test_table();
+        let mut scan = TableScan::new(table).with_batch_size(10);
 
-        let err = ExecutionError::InvalidColumnIndex { index: 5, count: 3 };
-        assert_eq!(
-            err.to_string(),
-            "Invalid column index 5 (only 3 columns available)"
-        );
+        scan.open().unwrap();
 
-        let err = ExecutionError::ColumnNotFound("age".to_string());
-        assert_eq!(err.to_string(), "Column 'age' not found in batch");
-    }
+        let batch = scan.next_batch().unwrap().unwrap();
 
-    #[test]
-    fn test_io_error_conversion() {
-        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
-        let exec_err: ExecutionError = io_err.into();
+        // Test: age < 30 OR age > 40
+        let pred1 = Arc::new(BinaryComparison::new(
+            2,
+            ComparisonOp::LessThan,
+            Value::Float64(30.0),
+        ));
+        let pred2 = Arc::new(BinaryComparison::new(
+            2,
+            ComparisonOp::GreaterThan,
+            Value::Float64(40.0),
+        ));
+        let or_pred = Or::new(pred1, pred2);
 
-        assert!(matches!(exec_err, ExecutionError::IoError(_)));
-        assert_eq!(
-            exec_err.to_string(),
-            "IO error during execution: file not found"
-        );
+        assert!(or_pred.eval(&batch, 0).unwrap()); // age=25.0 (< 30)
+        assert!(!or_pred.eval(&batch, 1).unwrap()); // age=30.0 (not < 30 and not > 40)
+        assert!(!or_pred.eval(&batch, 2).unwrap()); // age=35.0 (not < 30 and not > 40)
+        assert!(!or_pred.eval(&batch, 3).unwrap()); // age=40.0 (not < 30 and not > 40)
+        assert!(or_pred.eval(&batch, 4).unwrap()); // age=45.0 (> 40)
+
+        scan.close().unwrap();
     }
 
     #[test]
-    fn test_batch_debug() {
-        let mut col1 = IntColumn::new();
-        col1.push_value(Value::Int64(1)).unwrap();
-        col1.push_value(Value::Int64(2)).unwrap();
-        col1.push_value(Value::Int64(3)).unwrap();
-
-        let mut col2 = StringColumn::new();
-        col2.push_value(Value::String("a".to_string())).unwrap();
-        col2.push_value(Value::String("b".to_string())).unwrap();
-        col2.push_value(Value::String("c".to_string())).unwrap();
-
-        let batch = Batch::new(vec![Arc::new(col1), Arc::new(col2)]);
+    fn test_filter_simple() {
+        let table = create_test_table();
+        let scan = Box::new(TableScan::new(table).with_batch_size(10));
 
-        let debug_str = format!("{:?}", batch);
-        assert!(debug_str.contains("Batch"));
-        assert!(debug_str.contains("row_count: 3"));
-        assert!(debug_str.contains("column_count: 2"));
-    }
+        // Filter: age > 30
+        let predicate = Arc::new(BinaryComparison::new(
+            2,
+            ComparisonOp::GreaterThan,
+            Value::Float64(30.0),
+        ));
 
-    // Simple mock operator for testing
-    struct MockOperator {
-        state: OperatorState,
-    }
+        let mut filter = Filter::new(scan, predicate);
 
-    impl Operator for MockOperator {
-        fn open(&mut self) -> Result<()> {
-            self.state = OperatorState::Open;
-            Ok(())
-        }
+        filter.open().unwrap();
 
-        fn next_batch(&mut self) -> Result<Option<Batch>> {
-            if self.state != OperatorState::Open {
-                return Err(ExecutionError::OperatorNotOpen);
-            }
-            Ok(None)
-        }
+        let batch = filter.next_batch().unwrap();
+        assert!(batch.is_some());
 
-        fn close(&mut self) -> Result<()> {
-            self.state = OperatorState::Closed;
-            Ok(())
-        }
+        let batch = batch.unwrap();
+        // Should only have rows with age > 30: rows 2, 3, 4 (ages 35, 40, 45)
+        assert_eq!(batch.row_count(), 3);
+        assert_eq!(batch.column_count(), 3);
 
-        fn schema(&self) -> Result<HashMap<String, DataType>> {
-            Err(ExecutionError::Custom(
-                "Mock operator has no schema".to_string(),
-            ))
-        }
+        // Verify first row (id=3, name=Charlie, age=35.0)
+        let val = batch.get(0, 0).unwrap();
+        assert_eq!(val, Value::Int64(3));
 
-        fn column_names(&self) -> Result<Vec<String>> {
-            Err(ExecutionError::Custom(
-                "Mock operator has no column names".to_string(),
-            ))
-        }
+        // Verify last row (id=5, name=Eve, age=45.0)
+        let val = batch.get(2, 0).unwrap();
+        assert_eq!(val, Value::Int64(5));
 
-        fn is_open(&self) -> bool {
-            self.state == OperatorState::Open
-        }
+        filter.close().unwrap();
     }
 
     #[test]
-    fn test_operator_lifecycle() {
-        let mut op = MockOperator {
-            state: OperatorState::NotOpen,
-        };
-
-        assert!(!op.is_open());
+    fn test_filter_with_and() {
+        let table = create_test_table();
+        let scan = Box::new(TableScan::new(table).with_batch_size(10));
 
-        // Open
-        assert!(op.open().is_ok());
-        assert!(op.is_open());
+        // Filter: id >= 2 AND id <= 4
+        let pred1 = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThanOrEqual,
+            Value::Int64(2),
+        ));
+        let pred2 = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::LessThanOrEqual,
+            Value::Int64(4),
+        ));
+        let predicate = Arc::new(And::new(pred1, pred2));
 
-        // Get batch
-        assert!(op.next_batch().is_ok());
+        let mut filter = Filter::new(scan, predicate);
 
-        // Close
-        assert!(op.close().is_ok());
-        assert!(!op.is_open());
-    }
+        filter.open().unwrap();
 
-    #[test]
-    fn test_operator_not_open_error() {
-        let mut op = MockOperator {
-            state: OperatorState::NotOpen,
-        };
+        let batch = filter.next_batch().unwrap().unwrap();
+        // Should have rows 1, 2, 3 (ids 2, 3, 4)
+        assert_eq!(batch.row_count(), 3);
 
-        let result = op.next_batch();
-        assert!(result.is_err());
-        assert!(matches!(result, Err(ExecutionError::OperatorNotOpen)));
+        filter.close().unwrap();
     }
 
-    // TableScan Operator Tests
     #[test]
-    fn test_table_scan_create() {
+    fn test_filter_with_or() {
         let table = create_test_table();
-        let scan = TableScan::new(table.clone());
+        let scan = Box::new(TableScan::new(table).with_batch_size(10));
 
-        assert_eq!(scan.total_rows, 5);
-        assert_eq!(scan.column_indices.len(), 3); // All columns
-        assert_eq!(scan.batch_size, 1024);
-    }
+        // Filter: name = "Alice" OR name = "Eve"
+        let pred1 = Arc::new(BinaryComparison::new(
+            1,
+            ComparisonOp::Equal,
+            Value::String("Alice".to_string()),
+        ));
+        let pred2 = Arc::new(BinaryComparison::new(
+            1,
+            ComparisonOp::Equal,
+            Value::String("Eve".to_string()),
+        ));
+        let predicate = Arc::new(Or::new(pred1, pred2));
+
+        let mut filter = Filter::new(scan, predicate);
+
+        filter.open().unwrap();
+
+        let batch = filter.next_batch().unwrap().unwrap();
+        // Should have rows 0 and 4 (Alice and Eve)
+        assert_eq!(batch.row_count(), 2);
 
-    #[test]
-    fn test_table_scan_with_columns() {
-        let table = create_test_table();
-        let scan = TableScan::with_columns(table.clone(), vec![0, 2]);
+        let val = batch.get(0, 1).unwrap();
+        assert_eq!(val, Value::String("Alice".to_string()));
 
-        assert_eq!(scan.column_indices, vec![0, 2]);
-        assert_eq!(scan.total_rows, 5);
-    }
+        let val = batch.get(1, 1).unwrap();
+        assert_eq!(val, Value::String("Eve".to_string()));
 
-    #[test]
-    #[should_panic(expected = "Batch size must be greater than 0")]
-    fn test_table_scan_invalid_batch_size() {
-        let table = create_test_table();
-        let _scan = TableScan::new(table).with_batch_size(0);
+        filter.close().unwrap();
     }
 
     #[test]
-    fn test_table_scan_lifecycle() {
+    fn test_filter_empty_result() {
         let table = create_test_table();
-        let mut scan = TableScan::new(table);
+        let scan = Box::new(TableScan::new(table).with_batch_size(10));
 
-        assert!(!scan.is_open());
+        // Filter: id > 100 (no rows match)
+        let predicate = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThan,
+            Value::Int64(100),
+        ));
 
-        // Open
-        assert!(scan.open().is_ok());
-        assert!(scan.is_open());
+        let mut filter = Filter::new(scan, predicate);
 
-        // Get batches
-        let batch1 = scan.next_batch();
-        assert!(batch1.is_ok());
-        assert!(batch1.unwrap().is_some());
+        filter.open().unwrap();
 
-        // Should return None after exhausting data
-        while scan.next_batch().unwrap().is_some() {
-            // Keep consuming batches
-        }
+        let batch = filter.next_batch().unwrap();
+        // Should return None immediately since no rows match
+        assert!(batch.is_none());
 
-        // Close
-        assert!(scan.close().is_ok());
-        assert!(!scan.is_open());
+        filter.close().unwrap();
     }
 
     #[test]
-    fn test_table_scan_single_batch() {
+    fn test_filter_all_rows_pass() {
         let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
-
-        scan.open().unwrap();
-
-        let batch = scan.next_batch().unwrap();
-        assert!(batch.is_some());
-
-        let batch = batch.unwrap();
-        assert_eq!(batch.row_count(), 5);
-        assert_eq!(batch.column_count(), 3);
+        let scan = Box::new(TableScan::new(table).with_batch_size(10));
 
-        // Verify data
-        let val = batch.get(0, 0).unwrap();
-        assert_eq!(val, Value::Int64(1));
+        // Filter: id >= 1 (all rows pass)
+        let predicate = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThanOrEqual,
+            Value::Int64(1),
+        ));
 
-        let val = batch.get(1, 1).unwrap();
-        assert_eq!(val, Value::String("Bob".to_string()));
+        let mut filter = Filter::new(scan, predicate);
 
-        let val = batch.get(2, 2).unwrap();
-        assert_eq!(val, Value::Float64(35.0));
+        filter.open().unwrap();
 
-        // Next batch should be None
-        let batch = scan.next_batch().unwrap();
-        assert!(batch.is_none());
+        let batch = filter.next_batch().unwrap().unwrap();
+        // Should have all 5 rows
+        assert_eq!(batch.row_count(), 5);
 
-        scan.close().unwrap();
+        filter.close().unwrap();
     }
 
     #[test]
-    fn test_table_scan_multiple_batches() {
-        let table = create_test_large_table(2500); // 2500 rows
-        let mut scan = TableScan::new(table).with_batch_size(1000);
+    fn test_filter_multiple_batches() {
+        let table = create_test_large_table(2500);
+        let scan = Box::new(TableScan::new(table).with_batch_size(1000));
 
-        scan.open().unwrap();
+        // Filter: id >= 1000 (only second and third batches)
+        let predicate = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThanOrEqual,
+            Value::Int64(1000),
+        ));
 
-        let mut total_rows = 0;
-        let mut batch_count = 0;
+        let mut filter = Filter::new(scan, predicate);
 
-        loop {
-            let batch = scan.next_batch().unwrap();
-            if let Some(batch) = batch {
-                batch_count += 1;
-                total_rows += batch.row_count();
-            } else {
-                break;
-            }
+        filter.open().unwrap();
+
+        let mut total_rows = 0;
+        while let Some(batch) = filter.next_batch().unwrap() {
+            total_rows += batch.row_count();
         }
 
-        assert_eq!(batch_count, 3); // 1000 + 1000 + 500 = 2500
-        assert_eq!(total_rows, 2500);
+        // Should have 1500 rows (ids 1000-2499)
+        assert_eq!(total_rows, 1500);
 
-        scan.close().unwrap();
+        filter.close().unwrap();
     }
 
     #[test]
-    fn test_table_scan_column_pruning() {
+    fn test_filter_schema() {
         let table = create_test_table();
-        let mut scan = TableScan::with_columns(table, vec![0, 2]); // Only id and age
+        let scan = Box::new(TableScan::new(table));
 
-        scan.open().unwrap();
+        let predicate = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThan,
+            Value::Int64(0),
+        ));
 
-        let batch = scan.next_batch().unwrap();
-        assert!(batch.is_some());
+        let mut filter = Filter::new(scan, predicate);
 
-        let batch = batch.unwrap();
-        assert_eq!(batch.column_count(), 2);
-        assert_eq!(batch.row_count(), 5);
+        // Schema not available before open
+        assert!(filter.schema().is_err());
 
-        // Verify we have the right columns
-        let val = batch.get(0, 0).unwrap();
-        assert_eq!(val, Value::Int64(1)); // id
+        filter.open().unwrap();
 
-        let val = batch.get(0, 1).unwrap();
-        assert_eq!(val, Value::Float64(25.0)); // age
+        // Schema should match child's schema
+        let schema = filter.schema().unwrap();
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("id", DataType::Int64),
+                Field::new("name", DataType::String),
+                Field::new("age", DataType::Float64),
+            ]
+        );
 
-        scan.close().unwrap();
+        filter.close().unwrap();
     }
 
     #[test]
-    fn test_table_scan_schema() {
+    fn test_filter_lifecycle() {
         let table = create_test_table();
-        let mut scan = TableScan::new(table);
-
-        // Schema not available before open
-        assert!(scan.schema().is_err());
-
-        scan.open().unwrap();
+        let scan = Box::new(TableScan::new(table));
 
-        let schema = scan.schema().unwrap();
-        assert_eq!(schema.len(), 3);
-        assert_eq!(schema.get("id"), Some(&DataType::Int64));
-        assert_eq!(schema.get("name"), Some(&DataType::String));
-        assert_eq!(schema.get("age"), Some(&DataType::Float64));
+        let predicate = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThan,
+            Value::Int64(2),
+        ));
 
-        scan.close().unwrap();
-    }
+        let mut filter = Filter::new(scan, predicate);
 
-    #[test]
-    fn test_table_scan_pruned_schema() {
-        let table = create_test_table();
-        let mut scan = TableScan::with_columns(table, vec![0, 1]);
+        assert!(!filter.is_open());
 
-        scan.open().unwrap();
+        filter.open().unwrap();
+        assert!(filter.is_open());
 
-        let schema = scan.schema().unwrap();
-        assert_eq!(schema.len(), 2);
-        assert_eq!(schema.get("id"), Some(&DataType::Int64));
-        assert_eq!(schema.get("name"), Some(&DataType::String));
-        assert_eq!(schema.get("age"), None);
+        // Get batches
+        while filter.next_batch().unwrap().is_some() {
+            // Keep consuming
+        }
 
-        scan.close().unwrap();
+        filter.close().unwrap();
+        assert!(!filter.is_open());
     }
 
     #[test]
-    fn test_table_scan_invalid_column_index() {
+    fn test_filter_next_batch_not_open() {
         let table = create_test_table();
-        let mut scan = TableScan::with_columns(table, vec![0, 5]); // Column 5 doesn't exist
+        let scan = Box::new(TableScan::new(table));
 
-        let result = scan.open();
-        assert!(result.is_err());
-        assert!(matches!(
-            result,
-            Err(ExecutionError::InvalidColumnIndex { index: 5, count: 3 })
+        let predicate = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThan,
+            Value::Int64(0),
         ));
-    }
 
-    #[test]
-    fn test_table_scan_next_batch_not_open() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table);
+        let mut filter = Filter::new(scan, predicate);
 
-        let result = scan.next_batch();
+        let result = filter.next_batch();
         assert!(result.is_err());
         assert!(matches!(result, Err(ExecutionError::OperatorNotOpen)));
     }
 
-    #[test]
-    fn test_table_scan_double_open() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table);
-
-        scan.open().unwrap();
-        let result = scan.open();
-        assert!(result.is_err());
-        assert!(matches!(result, Err(ExecutionError::OperatorAlreadyOpen)));
+    /// The per-row comparison as it was before comparators were bound at
+    /// open, kept as a reference for the differential tests below.
+    fn legacy_compare(op: &ComparisonOp, actual: &Value, literal: &Value) -> bool {
+        use ComparisonOp::*;
+        match (op, actual, literal) {
+            (Equal, Value::Int64(a), Value::Int64(b)) => a == b,
+            (Equal, Value::Float64(a), Value::Float64(b)) => a == b,
+            (Equal, Value::String(a), Value::String(b)) => a == b,
+            (NotEqual, Value::Int64(a), Value::Int64(b)) => a != b,
+            (NotEqual, Value::Float64(a), Value::Float64(b)) => a != b,
+            (NotEqual, Value::String(a), Value::String(b)) => a != b,
+            (LessThan, Value::Int64(a), Value::Int64(b)) => a < b,
+            (LessThan, Value::Float64(a), Value::Float64(b)) => a < b,
+            (LessThan, Value::Float64(a), Value::Int64(b)) => *a < (*b as f64),
+            (LessThan, Value::Int64(a), Value::Float64(b)) => (*a as f64) < *b,
+            (LessThanOrEqual, Value::Int64(a), Value::Int64(b)) => a <= b,
+            (LessThanOrEqual, Value::Float64(a), Value::Float64(b)) => a <= b,
+            (LessThanOrEqual, Value::Float64(a), Value::Int64(b)) => *a <= (*b as f64),
+            (LessThanOrEqual, Value::Int64(a), Value::Float64(b)) => (*a as f64) <= *b,
+            (GreaterThan, Value::Int64(a), Value::Int64(b)) => a > b,
+            (GreaterThan, Value::Float64(a), Value::Float64(b)) => a > b,
+            (GreaterThan, Value::Float64(a), Value::Int64(b)) => *a > (*b as f64),
+            (GreaterThan, Value::Int64(a), Value::Float64(b)) => (*a as f64) > *b,
+            (GreaterThanOrEqual, Value::Int64(a), Value::Int64(b)) => a >= b,
+            (GreaterThanOrEqual, Value::Float64(a), Value::Float64(b)) => a >= b,
+            (GreaterThanOrEqual, Value::Float64(a), Value::Int64(b)) => *a >= (*b as f64),
+            (GreaterThanOrEqual, Value::Int64(a), Value::Float64(b)) => (*a as f64) >= *b,
+            _ => false,
+        }
     }
 
-    #[test]
-    fn test_table_scan_empty_table() {
-        let table = Table::new("empty".to_string());
-        let mut scan = TableScan::new(table);
-
-        scan.open().unwrap();
-
-        let batch = scan.next_batch().unwrap();
-        assert!(batch.is_none());
+    fn all_comparison_ops() -> Vec<ComparisonOp> {
+        vec![
+            ComparisonOp::Equal,
+            ComparisonOp::NotEqual,
+            ComparisonOp::LessThan,
+            ComparisonOp::LessThanOrEqual,
+            ComparisonOp::GreaterThan,
+            ComparisonOp::GreaterThanOrEqual,
+        ]
+    }
 
-        scan.close().unwrap();
+    fn single_column_batch(data_type: DataType, values: &[Value]) -> Batch {
+        let mut column = crate::column::create_column(data_type);
+        for value in values {
+            column.push_value(value.clone()).unwrap();
+        }
+        Batch::new(vec![column.into()])
     }
 
     #[test]
-    fn test_table_scan_single_row_table() {
-        let mut table = Table::new("single_row".to_string());
+    fn test_bound_comparison_matches_legacy_semantics() {
+        let ints: Vec<Value> = [-3, 0, 1, 2, 7, i64::MAX]
+            .into_iter()
+            .map(Value::Int64)
+            .collect();
+        let floats: Vec<Value> = [-3.0, 0.0, 1.5, 2.0, 7.25, f64::NAN]
+            .into_iter()
+            .map(Value::Float64)
+            .collect();
+        let strings: Vec<Value> = ["", "Alice", "Bob", "alice"]
+            .into_iter()
+            .map(|s| Value::String(s.to_string()))
+            .collect();
+
+        // Every (column type, literal type, operator) combination the old
+        // evaluator handled. It silently returned `false` for mixed
+        // Int64/Float64 equality and for ordering on strings, which are
+        // now supported and so are not compared here.
+        let pairs = [
+            (DataType::Int64, &ints[..], &ints[..], true, true),
+            (DataType::Float64, &floats[..], &floats[..], true, true),
+            (DataType::String, &strings[..], &strings[..], true, false),
+            (DataType::Int64, &ints[..], &floats[..], false, true),
+            (DataType::Float64, &floats[..], &ints[..], false, true),
+        ];
+
+        for (data_type, column_values, literals, equality, ordering) in pairs {
+            let batch = single_column_batch(data_type, column_values);
+            for op in all_comparison_ops() {
+                let is_equality = matches!(op, ComparisonOp::Equal | ComparisonOp::NotEqual);
+                if (is_equality && !equality) || (!is_equality && !ordering) {
+                    continue;
+                }
+                for literal in literals {
+                    let bound = BinaryComparison::new(0, op.clone(), literal.clone())
+                        .bind(&[data_type])
+                        .unwrap();
+                    for (row, actual) in column_values.iter().enumerate() {
+                        assert_eq!(
+                            bound.eval(&batch, row).unwrap(),
+                            legacy_compare(&op, actual, literal),
+                            "{} {} {}",
+                            actual,
+                            op,
+                            literal
+                        );
+                    }
+                }
+            }
+        }
+    }
 
-        let mut id_col = IntColumn::new();
-        id_col.push_value(Value::Int64(42)).unwrap();
-        table
-            .add_column("id".to_string(), Box::new(id_col))
+    #[test]
+    fn test_bound_comparison_mixed_numeric_equality() {
+        // Equality now coerces like the ordering operators do
+        let batch = single_column_batch(DataType::Int64, &[Value::Int64(2), Value::Int64(3)]);
+        let bound = BinaryComparison::new(0, ComparisonOp::Equal, Value::Float64(2.0))
+            .bind(&[DataType::Int64])
             .unwrap();
+        assert!(bound.eval(&batch, 0).unwrap());
+        assert!(!bound.eval(&batch, 1).unwrap());
 
-        let mut scan = TableScan::new(table);
-
-        scan.open().unwrap();
+        let batch = single_column_batch(DataType::Float64, &[Value::Float64(2.5)]);
+        let bound = BinaryComparison::new(0, ComparisonOp::NotEqual, Value::Int64(2))
+            .bind(&[DataType::Float64])
+            .unwrap();
+        assert!(bound.eval(&batch, 0).unwrap());
+    }
 
-        let batch = scan.next_batch().unwrap();
-        assert!(batch.is_some());
+    #[test]
+    fn test_bound_comparison_orders_strings() {
+        let names = ["Alice", "Bob", "Charlie"].map(|s| Value::String(s.to_string()));
+        let batch = single_column_batch(DataType::String, &names);
+        let bound =
+            BinaryComparison::new(0, ComparisonOp::LessThan, Value::String("Bob".to_string()))
+                .bind(&[DataType::String])
+                .unwrap();
 
-        let batch = batch.unwrap();
-        assert_eq!(batch.row_count(), 1);
-        assert_eq!(batch.column_count(), 1);
+        assert!(bound.eval(&batch, 0).unwrap());
+        assert!(!bound.eval(&batch, 1).unwrap());
+        assert!(!bound.eval(&batch, 2).unwrap());
+    }
 
-        let val = batch.get(0, 0).unwrap();
-        assert_eq!(val, Value::Int64(42));
+    #[test]
+    fn test_bound_comparison_on_booleans() {
+        let batch = single_column_batch(
+            DataType::Boolean,
+            &[Value::Boolean(true), Value::Null, Value::Boolean(false)],
+        );
+        let unbound = BinaryComparison::new(0, ComparisonOp::Equal, Value::Boolean(true));
+        let bound = unbound.bind(&[DataType::Boolean]).unwrap();
+        for predicate in [&unbound as &dyn Predicate, bound.as_ref()] {
+            assert!(predicate.eval(&batch, 0).unwrap());
+            assert!(!predicate.eval(&batch, 1).unwrap());
+            assert!(!predicate.eval(&batch, 2).unwrap());
+        }
 
-        let batch = scan.next_batch().unwrap();
-        assert!(batch.is_none());
+        // FALSE orders before TRUE
+        let bound = BinaryComparison::new(0, ComparisonOp::LessThan, Value::Boolean(true))
+            .bind(&[DataType::Boolean])
+            .unwrap();
+        assert!(bound.eval(&batch, 2).unwrap());
 
-        scan.close().unwrap();
+        // Booleans only compare with Booleans
+        assert!(
+            BinaryComparison::new(0, ComparisonOp::Equal, Value::Int64(1))
+                .bind(&[DataType::Boolean])
+                .is_err()
+        );
     }
 
-    // Predicate and Filter Operator Tests
     #[test]
-    fn test_binary_comparison_equal() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
-
-        scan.open().unwrap();
+    fn test_bound_comparison_on_dates() {
+        let batch = single_column_batch(
+            DataType::Date,
+            &[
+                Value::Date(19_722),
+                Value::Null,
+                Value::Date(19_723),
+                Value::Date(19_724),
+            ],
+        );
 
-        let batch = scan.next_batch().unwrap().unwrap();
+        // An ISO string literal is read as a date
+        let unbound = BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThanOrEqual,
+            Value::String("2024-01-01".to_string()),
+        );
+        let bound = unbound.bind(&[DataType::Date]).unwrap();
+        assert_eq!(
+            bound.eval_batch(&batch).unwrap(),
+            vec![false, false, true, true]
+        );
+        for row in 0..4 {
+            assert_eq!(
+                unbound.eval(&batch, row).unwrap(),
+                bound.eval(&batch, row).unwrap()
+            );
+        }
 
-        // Test equals with integer
-        let pred = BinaryComparison::new(0, ComparisonOp::Equal, Value::Int64(1));
-        assert!(pred.eval(&batch, 0).unwrap()); // row 0, id=1
-        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, id=2
+        assert!(BinaryComparison::new(
+            0,
+            ComparisonOp::Equal,
+            Value::String("January 1st".to_string())
+        )
+        .bind(&[DataType::Date])
+        .is_err());
+        assert!(
+            BinaryComparison::new(0, ComparisonOp::Equal, Value::Int64(19_723))
+                .bind(&[DataType::Date])
+                .is_err()
+        );
+    }
 
-        // Test equals with string
-        let pred =
-            BinaryComparison::new(1, ComparisonOp::Equal, Value::String("Alice".to_string()));
-        assert!(pred.eval(&batch, 0).unwrap()); // row 0, name=Alice
-        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, name=Bob
+    /// Evaluates `predicate` both ways and checks the results agree.
+    fn assert_batch_matches_rows(predicate: &dyn Predicate, batch: &Batch) -> Vec<bool> {
+        let start = std::time::Instant::now();
+        let vectorized = predicate.eval_batch(batch).unwrap();
+        let vectorized_time = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let row_by_row: Vec<bool> = (0..batch.row_count())
+            .map(|row| predicate.eval(batch, row).unwrap())
+            .collect();
+        println!(
+            "{} rows: eval_batch {:?}, row by row {:?}",
+            batch.row_count(),
+            vectorized_time,
+            start.elapsed()
+        );
 
-        scan.close().unwrap();
+        assert_eq!(vectorized, row_by_row);
+        vectorized
     }
 
     #[test]
-    fn test_binary_comparison_not_equal() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
-
-        scan.open().unwrap();
+    fn test_eval_batch_matches_row_by_row_on_large_batch() {
+        const ROWS: usize = 1_000_000;
+        let mut ids = IntColumn::new();
+        let mut scores = FloatColumn::new();
+        let mut tags = StringColumn::new();
+        for row in 0..ROWS as i64 {
+            if row % 7 == 0 {
+                ids.push_null();
+            } else {
+                ids.push_value(Value::Int64(row % 1000)).unwrap();
+            }
+            scores
+                .push_value(Value::Float64(row as f64 / 10.0))
+                .unwrap();
+            if row % 11 == 0 {
+                tags.push_null();
+            } else {
+                tags.push_value(Value::String(format!("t{}", row % 5)))
+                    .unwrap();
+            }
+        }
+        let batch = Batch::new(vec![Arc::new(ids), Arc::new(scores), Arc::new(tags)]);
+        let types = [DataType::Int64, DataType::Float64, DataType::String];
 
-        let batch = scan.next_batch().unwrap().unwrap();
+        let id_below = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::LessThan,
+            Value::Int64(500),
+        ));
+        let tag_is = Arc::new(BinaryComparison::new(
+            2,
+            ComparisonOp::Equal,
+            Value::String("t3".to_string()),
+        ));
+        let predicates: Vec<Arc<dyn Predicate>> = vec![
+            id_below.clone(),
+            // Int64 column against a Float64 literal; NULL literal
+            Arc::new(BinaryComparison::new(
+                0,
+                ComparisonOp::GreaterThanOrEqual,
+                Value::Float64(250.5),
+            )),
+            Arc::new(BinaryComparison::new(0, ComparisonOp::Equal, Value::Null)),
+            Arc::new(BinaryComparison::new(
+                1,
+                ComparisonOp::NotEqual,
+                Value::Int64(42),
+            )),
+            tag_is.clone(),
+            Arc::new(InList::new(0, vec![Value::Int64(1), Value::Float64(999.0)])),
+            Arc::new(InList::new(2, vec![Value::String("t0".into())]).negated()),
+            Arc::new(InList::new(0, vec![Value::Int64(1), Value::Null]).negated()),
+            Arc::new(And::new(id_below.clone(), tag_is.clone())),
+            Arc::new(Or::new(id_below.clone(), tag_is.clone())),
+        ];
+
+        for predicate in predicates {
+            let bound = predicate.bind(&types).unwrap();
+            assert_batch_matches_rows(bound.as_ref(), &batch);
+        }
 
-        // Test not equals with float
-        let pred = BinaryComparison::new(2, ComparisonOp::NotEqual, Value::Float64(25.0));
-        assert!(!pred.eval(&batch, 0).unwrap()); // row 0, age=25.0
-        assert!(pred.eval(&batch, 1).unwrap()); // row 1, age=30.0
+        // Unbound predicates take the row-by-row fallback
+        let selection = assert_batch_matches_rows(&And::new(id_below, tag_is), &batch);
+        assert!(selection.contains(&true));
+    }
 
-        scan.close().unwrap();
+    #[test]
+    fn test_and_batch_does_not_fail_on_rows_the_left_side_rejects() {
+        let batch =
+            single_column_batch(DataType::Int64, &[Value::Int64(2), Value::Int64(i64::MAX)]);
+        // x < 10 AND x * 2 > 3: the multiplication overflows on row 1 only
+        let predicate = And::new(
+            Arc::new(BinaryComparison::new(
+                0,
+                ComparisonOp::LessThan,
+                Value::Int64(10),
+            )),
+            Arc::new(ExpressionComparison::new(
+                ScalarExpression::Arithmetic {
+                    left: Box::new(ScalarExpression::Column(0)),
+                    op: ArithmeticOp::Multiply,
+                    right: Box::new(ScalarExpression::Literal(Value::Int64(2))),
+                },
+                ComparisonOp::GreaterThan,
+                ScalarExpression::Literal(Value::Int64(3)),
+            )),
+        )
+        .bind(&[DataType::Int64])
+        .unwrap();
+
+        assert_eq!(predicate.eval_batch(&batch).unwrap(), vec![true, false]);
     }
 
     #[test]
-    fn test_binary_comparison_less_than() {
+    fn test_filter_passes_fully_matching_batch_through() {
         let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
-
-        scan.open().unwrap();
+        let scan = Box::new(TableScan::new(table));
+        let predicate = Arc::new(BinaryComparison::new(
+            0,
+            ComparisonOp::GreaterThan,
+            Value::Int64(0),
+        ));
+        let mut filter = Filter::new(scan, predicate);
+        filter.open().unwrap();
 
-        let batch = scan.next_batch().unwrap().unwrap();
+        let batch = filter.next_batch().unwrap().unwrap();
+        assert_eq!(batch.row_count(), 5);
+        assert!(filter.next_batch().unwrap().is_none());
+        filter.close().unwrap();
+    }
 
-        // Test less than with integer
-        let pred = BinaryComparison::new(0, ComparisonOp::LessThan, Value::Int64(3));
-        assert!(pred.eval(&batch, 0).unwrap()); // row 0, id=1
-        assert!(pred.eval(&batch, 1).unwrap()); // row 1, id=2
-        assert!(!pred.eval(&batch, 2).unwrap()); // row 2, id=3
+    /// 20 rows of `id`, `grp` (NULL for every 7th row), `score` (NULL for
+    /// every 5th row) and `name`, scanned 4 rows at a time
+    fn nullable_scan() -> Box<dyn Operator> {
+        let mut table = Table::new("nullable".to_string());
+        let mut id = IntColumn::new();
+        let mut grp = IntColumn::new();
+        let mut score = FloatColumn::new();
+        let mut name = StringColumn::new();
+        for i in 1..=20i64 {
+            id.push_value(Value::Int64(i)).unwrap();
+            grp.push_value(if i % 7 == 0 {
+                Value::Null
+            } else {
+                Value::Int64(i % 3)
+            })
+            .unwrap();
+            score
+                .push_value(if i % 5 == 0 {
+                    Value::Null
+                } else {
+                    Value::Float64(i as f64 / 2.0)
+                })
+                .unwrap();
+            name.push_value(Value::String(format!("n{}", i))).unwrap();
+        }
+        table.add_column("id".to_string(), Box::new(id)).unwrap();
+        table.add_column("grp".to_string(), Box::new(grp)).unwrap();
+        table
+            .add_column("score".to_string(), Box::new(score))
+            .unwrap();
+        table
+            .add_column("name".to_string(), Box::new(name))
+            .unwrap();
+        Box::new(TableScan::new(table).with_batch_size(4))
+    }
+
+    /// `id > 2` followed by `score IS NOT NULL` over [`nullable_scan`]
+    fn two_filters() -> Box<dyn Operator> {
+        let first = Filter::new(
+            nullable_scan(),
+            Arc::new(BinaryComparison::new(
+                0,
+                ComparisonOp::GreaterThan,
+                Value::Int64(2),
+            )),
+        );
+        Box::new(Filter::new(
+            Box::new(first),
+            Arc::new(IsNullPredicate::new(2).negated()),
+        ))
+    }
 
-        scan.close().unwrap();
+    /// The rows [`two_filters`] should return, filtered one row at a time
+    fn two_filters_expected() -> Vec<Vec<Value>> {
+        drain_rows(nullable_scan().as_mut())
+            .into_iter()
+            .filter(|row| matches!(row[0], Value::Int64(id) if id > 2) && !row[2].is_null())
+            .collect()
     }
 
     #[test]
-    fn test_binary_comparison_greater_than() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
+    fn test_filter_views_share_rows_and_never_nest() {
+        let scan_batch = |scan: &mut dyn Operator| {
+            scan.open().unwrap();
+            scan.next_batch().unwrap().unwrap()
+        };
+        let batch = scan_batch(nullable_scan().as_mut());
+        let once = batch.filter_rows(&[true, false, true, true]).unwrap();
+        let twice = once.filter_rows(&[false, true, true]).unwrap();
+
+        let views: Vec<&FilteredColumn> = twice
+            .columns
+            .iter()
+            .map(|col| col.as_any().downcast_ref::<FilteredColumn>().unwrap())
+            .collect();
+        for (view, scanned) in views.iter().zip(&batch.columns) {
+            // A view of a view looks into the table's column itself
+            let (base, _) = resolve_slice(scanned.as_ref());
+            assert!(std::ptr::addr_eq(Arc::as_ptr(view.column()), base));
+            assert!(Arc::ptr_eq(view.rows(), views[0].rows()));
+        }
+        assert_eq!(views[0].rows().as_slice(), &[2, 3]);
+        assert_eq!(twice.get(0, 0).unwrap(), Value::Int64(3));
+        assert_eq!(twice.get(1, 3).unwrap(), Value::String("n4".to_string()));
+
+        // Slicing a filtered batch and filtering again still does not nest
+        let sliced = once
+            .take_rows(2)
+            .unwrap()
+            .filter_rows(&[false, true])
+            .unwrap();
+        let view = sliced.columns[0]
+            .as_any()
+            .downcast_ref::<FilteredColumn>()
+            .unwrap();
+        let (base, _) = resolve_slice(batch.columns[0].as_ref());
+        assert!(std::ptr::addr_eq(Arc::as_ptr(view.column()), base));
+        assert_eq!(view.rows().as_slice(), &[2]);
+
+        // Materializing packs the rows and keeps the values
+        let packed = twice.clone().materialize();
+        assert!(packed
+            .columns
+            .iter()
+            .all(|col| col.as_any().downcast_ref::<FilteredColumn>().is_none()));
+        for row in 0..2 {
+            for col in 0..4 {
+                assert_eq!(packed.get(row, col).unwrap(), twice.get(row, col).unwrap());
+            }
+        }
+    }
 
-        scan.open().unwrap();
+    #[test]
+    fn test_chained_filters_match_row_at_a_time_filtering() {
+        let expected = two_filters_expected();
+        // Rows 3..=20 without the multiples of 5
+        assert_eq!(expected.len(), 14);
+        assert_eq!(drain_rows(two_filters().as_mut()), expected);
+
+        // Filter -> Limit, with an offset that crosses batches
+        let mut limit = Limit::new(two_filters(), Some(6), 5);
+        assert_eq!(drain_rows(&mut limit), expected[5..11].to_vec());
+
+        // Filter -> Project
+        let mut project = Project::new(two_filters(), vec![3, 1]);
+        let projected: Vec<Vec<Value>> = expected
+            .iter()
+            .map(|row| vec![row[3].clone(), row[1].clone()])
+            .collect();
+        assert_eq!(drain_rows(&mut project), projected);
+
+        // Filter -> Sort
+        let mut sort = Sort::new(two_filters(), vec![2], vec![SortDirection::Descending]);
+        let mut sorted = expected.clone();
+        sorted.reverse();
+        assert_eq!(drain_rows(&mut sort), sorted);
+
+        // Filter -> GroupBy, with a NULL group
+        let mut group_by = GroupBy::new(
+            two_filters(),
+            vec![1],
+            vec![2, 0],
+            vec![
+                Box::new(SumAggregate::new(DataType::Float64).unwrap()),
+                Box::new(CountAggregate::new(DataType::Int64)),
+            ],
+        );
+        let mut groups = drain_rows(&mut group_by);
+        groups.sort_by_key(|row| format!("{:?}", row[0]));
+        let mut expected_groups: Vec<Vec<Value>> = Vec::new();
+        for row in &expected {
+            let score = as_f64(&row[2]).unwrap();
+            match expected_groups.iter_mut().find(|group| group[0] == row[1]) {
+                Some(group) => {
+                    group[1] = Value::Float64(as_f64(&group[1]).unwrap() + score);
+                    group[2] = Value::Int64(as_f64(&group[2]).unwrap() as i64 + 1);
+                }
+                None => expected_groups.push(vec![
+                    row[1].clone(),
+                    Value::Float64(score),
+                    Value::Int64(1),
+                ]),
+            }
+        }
+        expected_groups.sort_by_key(|row| format!("{:?}", row[0]));
+        assert_eq!(expected_groups.len(), 4);
+        assert_eq!(groups, expected_groups);
+
+        // Filtered inputs on both sides of a join: labels for the even keys
+        let mut lookup = Table::new("lookup".to_string());
+        let mut key = IntColumn::new();
+        let mut label = StringColumn::new();
+        for i in 1..=20i64 {
+            key.push_value(Value::Int64(i)).unwrap();
+            label
+                .push_value(if i % 2 == 0 {
+                    Value::String(format!("even {}", i))
+                } else {
+                    Value::Null
+                })
+                .unwrap();
+        }
+        lookup.add_column("key".to_string(), Box::new(key)).unwrap();
+        lookup
+            .add_column("label".to_string(), Box::new(label))
+            .unwrap();
+        let labels = Filter::new(
+            Box::new(TableScan::new(lookup).with_batch_size(3)),
+            Arc::new(IsNullPredicate::new(1).negated()),
+        );
+        let mut join = HashJoin::new(two_filters(), Box::new(labels), 0, 0);
+        let mut joined = drain_rows(&mut join);
+        joined.sort_by_key(|row| as_f64(&row[0]).map(|id| id as i64));
+        let labelled: Vec<Vec<Value>> = expected
+            .iter()
+            .filter(|row| matches!(row[0], Value::Int64(id) if id % 2 == 0))
+            .map(|row| {
+                let mut row = row.clone();
+                let id = row[0].clone();
+                row.push(id.clone());
+                row.push(Value::String(format!("even {}", id)));
+                row
+            })
+            .collect();
+        assert_eq!(labelled.len(), 7);
+        assert_eq!(joined, labelled);
+    }
 
-        let batch = scan.next_batch().unwrap().unwrap();
+    #[test]
+    fn test_chained_filters_keep_the_typed_paths() {
+        let mut plan = two_filters();
+        plan.open().unwrap();
+        let mut batches = Vec::new();
+        while let Some(batch) = plan.next_batch().unwrap() {
+            batches.push(batch);
+        }
+        plan.close().unwrap();
 
-        // Test greater than with float
-        let pred = BinaryComparison::new(2, ComparisonOp::GreaterThan, Value::Float64(30.0));
-        assert!(!pred.eval(&batch, 0).unwrap()); // row 0, age=25.0
-        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, age=30.0
-        assert!(pred.eval(&batch, 2).unwrap()); // row 2, age=35.0
+        let literals = [
+            Value::Int64(10),
+            Value::Int64(1),
+            Value::Float64(4.0),
+            Value::String("n15".to_string()),
+        ];
+        for batch in &batches {
+            let packed = batch.clone().materialize();
+            for (index, literal) in literals.iter().enumerate() {
+                let column = batch.column(index).unwrap();
+                assert!(column.as_any().is::<FilteredColumn>());
+                let comparator =
+                    Comparator::resolve(column.data_type(), &ComparisonOp::GreaterThan, literal)
+                        .unwrap();
+                // The view is read through its base column's typed storage
+                let selection = comparator.matches_column(column.as_ref()).unwrap();
+                let expected: Vec<bool> = (0..column.len())
+                    .map(|row| comparator.matches(&column.get(row).unwrap()).unwrap())
+                    .collect();
+                assert_eq!(selection, expected);
+                assert_eq!(column_values(column.as_ref()), column.slice(None));
+            }
+            assert_eq!(batch_memory_size(batch), batch_memory_size(&packed));
+        }
 
-        scan.close().unwrap();
+        let packed: Vec<Batch> = batches.iter().cloned().map(Batch::materialize).collect();
+        for column in 0..4 {
+            let (views, copies) = (
+                SortKeyColumn::build(&batches, column).unwrap(),
+                SortKeyColumn::build(&packed, column).unwrap(),
+            );
+            let rows = packed.iter().map(Batch::row_count).sum();
+            for (a, b) in (0..rows).zip(1..rows) {
+                assert_eq!(
+                    views.compare(a, b),
+                    copies.compare(a, b),
+                    "column {}",
+                    column
+                );
+            }
+        }
     }
 
     #[test]
-    fn test_binary_comparison_less_than_or_equal() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
+    fn test_stacked_filters_copy_no_values() {
+        // `units` and `tens` are the last two digits of `id`, so each filter
+        // below drops one row in ten from every batch
+        let rows = 1_000_000;
+        let mut table = Table::new("digits".to_string());
+        let mut id = IntColumn::new();
+        let mut units = IntColumn::new();
+        let mut tens = IntColumn::new();
+        for i in 0..rows as i64 {
+            id.push_value(Value::Int64(i)).unwrap();
+            units.push_value(Value::Int64(i % 10)).unwrap();
+            tens.push_value(Value::Int64(i / 10 % 10)).unwrap();
+        }
+        table.add_column("id".to_string(), Box::new(id)).unwrap();
+        table
+            .add_column("units".to_string(), Box::new(units))
+            .unwrap();
+        table
+            .add_column("tens".to_string(), Box::new(tens))
+            .unwrap();
+        let nonzero = |column| {
+            Arc::new(BinaryComparison::new(
+                column,
+                ComparisonOp::NotEqual,
+                Value::Int64(0),
+            ))
+        };
+        let first = Filter::new(Box::new(TableScan::new(table)), nonzero(1));
+        let mut filter = Filter::new(Box::new(first), nonzero(2));
+        let copied = || COPIED_VALUES.with(|copied| copied.get());
 
-        scan.open().unwrap();
+        let before = copied();
+        filter.open().unwrap();
+        let mut batches = Vec::new();
+        while let Some(batch) = filter.next_batch().unwrap() {
+            batches.push(batch);
+        }
+        filter.close().unwrap();
+        let kept: usize = batches.iter().map(Batch::row_count).sum();
+        assert_eq!(kept, rows * 81 / 100);
+        // Copying what each filter keeps would cost (900,000 + 810,000) x 3
+        assert_eq!(copied() - before, 0);
+
+        let before = copied();
+        let packed: Vec<Batch> = batches.into_iter().map(Batch::materialize).collect();
+        assert_eq!(copied() - before, kept * 3);
+        assert_eq!(packed.iter().map(Batch::row_count).sum::<usize>(), kept);
+        assert_eq!(packed[0].get(0, 0).unwrap(), Value::Int64(11));
+        let last = packed.last().unwrap();
+        assert_eq!(
+            last.get(last.row_count() - 1, 0).unwrap(),
+            Value::Int64(rows as i64 - 1)
+        );
+    }
 
-        let batch = scan.next_batch().unwrap().unwrap();
+    /// The same strings as a plain and as a dictionary-encoded column.
+    fn plain_and_dictionary_columns(values: &[Option<&str>]) -> (Arc<dyn Column>, Arc<dyn Column>) {
+        let mut plain = StringColumn::new();
+        let mut dict = DictStringColumn::new();
+        for value in values {
+            let value = value.map_or(Value::Null, |v| Value::String(v.to_string()));
+            plain.push_value(value.clone()).unwrap();
+            dict.push_value(value).unwrap();
+        }
+        (Arc::new(plain), Arc::new(dict))
+    }
 
-        // Test less than or equal with integer
-        let pred = BinaryComparison::new(0, ComparisonOp::LessThanOrEqual, Value::Int64(2));
-        assert!(pred.eval(&batch, 0).unwrap()); // row 0, id=1
-        assert!(pred.eval(&batch, 1).unwrap()); // row 1, id=2
-        assert!(!pred.eval(&batch, 2).unwrap()); // row 2, id=3
+    #[test]
+    fn test_dictionary_columns_filter_like_plain_strings() {
+        let values = [Some("b"), Some("a"), None, Some("c"), Some("a"), Some("b")];
+        let (plain, dict) = plain_and_dictionary_columns(&values);
+        let plain = Batch::new(vec![plain]);
+        let dict = Batch::new(vec![dict]);
 
-        scan.close().unwrap();
+        for op in [
+            ComparisonOp::Equal,
+            ComparisonOp::NotEqual,
+            ComparisonOp::LessThan,
+            ComparisonOp::GreaterThanOrEqual,
+        ] {
+            for literal in ["a", "b", "z"] {
+                let predicate =
+                    BinaryComparison::new(0, op.clone(), Value::String(literal.to_string()))
+                        .bind(&[DataType::String])
+                        .unwrap();
+                assert_eq!(
+                    predicate.eval_batch(&dict).unwrap(),
+                    predicate.eval_batch(&plain).unwrap(),
+                    "{} {}",
+                    op,
+                    literal
+                );
+            }
+        }
+
+        // Filtering keeps the encoding and the shared dictionary
+        let filtered = dict
+            .filter_rows(&[true, false, true, true, false, false])
+            .unwrap();
+        let column = filtered.column(0).unwrap();
+        let filtered_dict = column.as_any().downcast_ref::<DictStringColumn>().unwrap();
+        let original = dict.column(0).unwrap();
+        assert!(filtered_dict.shares_dictionary(
+            original
+                .as_any()
+                .downcast_ref::<DictStringColumn>()
+                .unwrap()
+        ));
+        assert_eq!(
+            filtered_dict.slice(None),
+            vec![
+                Value::String("b".into()),
+                Value::Null,
+                Value::String("c".into())
+            ]
+        );
     }
 
     #[test]
-    fn test_binary_comparison_greater_than_or_equal() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
-
-        scan.open().unwrap();
+    fn test_dictionary_column_equality_compares_codes() {
+        let (_, dict) = plain_and_dictionary_columns(&[Some("x"), Some("y"), None, Some("x")]);
+        let other = dict
+            .as_any()
+            .downcast_ref::<DictStringColumn>()
+            .unwrap()
+            .gather([3, 1, 1, 0]);
+        let batch = Batch::new(vec![dict, Arc::new(other)]);
+
+        for (op, expected) in [
+            (ComparisonOp::Equal, vec![true, true, false, true]),
+            (ComparisonOp::NotEqual, vec![false, false, false, false]),
+        ] {
+            let predicate = ExpressionComparison::new(
+                ScalarExpression::Column(0),
+                op,
+                ScalarExpression::Column(1),
+            );
+            assert_eq!(predicate.eval_batch(&batch).unwrap(), expected);
+            assert_eq!(eval_each_row(&predicate, &batch).unwrap(), expected);
+        }
+    }
 
-        let batch = scan.next_batch().unwrap().unwrap();
+    #[test]
+    fn test_group_by_dictionary_keys_matches_plain_keys() {
+        let rows = 3_000;
+        let mut plain_table = Table::new("t".to_string());
+        let mut dict_table = Table::new("t".to_string());
+        let mut plain = StringColumn::new();
+        let mut dict = DictStringColumn::new();
+        let mut amounts = IntColumn::new();
+        for row in 0..rows {
+            let key = match row % 7 {
+                0 => Value::Null,
+                k => Value::String(format!("k{}", k % 4)),
+            };
+            plain.push_value(key.clone()).unwrap();
+            dict.push_value(key).unwrap();
+            amounts.push_value(Value::Int64(row as i64)).unwrap();
+        }
+        plain_table
+            .add_column("key".to_string(), Box::new(plain))
+            .unwrap();
+        plain_table
+            .add_column("amount".to_string(), Box::new(amounts.clone()))
+            .unwrap();
+        dict_table
+            .add_column("key".to_string(), Box::new(dict))
+            .unwrap();
+        dict_table
+            .add_column("amount".to_string(), Box::new(amounts))
+            .unwrap();
 
-        // Test greater than or equal with float
-        let pred = BinaryComparison::new(2, ComparisonOp::GreaterThanOrEqual, Value::Float64(35.0));
-        assert!(!pred.eval(&batch, 0).unwrap()); // row 0, age=25.0
-        assert!(!pred.eval(&batch, 1).unwrap()); // row 1, age=30.0
-        assert!(pred.eval(&batch, 2).unwrap()); // row 2, age=35.0
-        assert!(pred.eval(&batch, 3).unwrap()); // row 3, age=40.0
+        let grouped = |table: Table| {
+            let mut group_by = GroupBy::new(
+                Box::new(TableScan::new(table).with_batch_size(256)),
+                vec![0],
+                vec![1, 1],
+                vec![
+                    Box::new(crate::aggregates::CountAggregate::new(DataType::Int64)),
+                    Box::new(crate::aggregates::SumAggregate::new(DataType::Int64).unwrap()),
+                ],
+            );
+            drain_rows(&mut group_by)
+        };
 
-        scan.close().unwrap();
+        // Both paths list groups in first-seen order
+        let expected = grouped(plain_table);
+        assert_eq!(
+            expected
+                .iter()
+                .map(|row| row[0].to_string())
+                .collect::<Vec<_>>(),
+            vec!["NULL", "k1", "k2", "k3", "k0"]
+        );
+        assert_eq!(grouped(dict_table), expected);
     }
 
     #[test]
-    fn test_comparison_op_display() {
-        assert_eq!(format!("{}", ComparisonOp::Equal), "=");
-        assert_eq!(format!("{}", ComparisonOp::NotEqual), "!=");
-        assert_eq!(format!("{}", ComparisonOp::LessThan), "<");
-        assert_eq!(format!("{}", ComparisonOp::LessThanOrEqual), "<=");
-        assert_eq!(format!("{}", ComparisonOp::GreaterThan), ">");
-        assert_eq!(format!("{}", ComparisonOp::GreaterThanOrEqual), ">=");
+    fn test_group_key_equality_is_typed() {
+        let key = |values: &[Value]| GroupKey(values.iter().cloned().map(Some).collect());
+        let hash = |key: &GroupKey| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            key.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let int = key(&[Value::Int64(1)]);
+        assert_ne!(int, key(&[Value::String("1".to_string())]));
+        assert_ne!(int, key(&[Value::Float64(1.0)]));
+        assert_ne!(
+            key(&[Value::Boolean(true)]),
+            key(&[Value::String("true".to_string())])
+        );
+        assert_ne!(int, GroupKey(vec![None]));
+        assert_ne!(int, key(&[Value::Int64(1), Value::Int64(1)]));
+
+        // Equal keys hash alike, NaN included
+        for values in [
+            vec![Value::Int64(1), Value::String("a".to_string())],
+            vec![Value::Float64(f64::NAN), Value::Null],
+            vec![Value::Date(19_000), Value::Boolean(false)],
+        ] {
+            assert_eq!(key(&values), key(&values));
+            assert_eq!(hash(&key(&values)), hash(&key(&values)));
+        }
+
+        // Signed zeros and NaN payloads collapse to one key
+        let other_nan = f64::from_bits(f64::NAN.to_bits() | 1);
+        assert!(other_nan.is_nan());
+        for (a, b) in [(0.0, -0.0), (f64::NAN, other_nan), (f64::NAN, -f64::NAN)] {
+            let (a, b) = (key(&[Value::Float64(a)]), key(&[Value::Float64(b)]));
+            assert_eq!(a, b);
+            assert_eq!(hash(&a), hash(&b));
+        }
+        assert_ne!(
+            key(&[Value::Float64(f64::NAN)]),
+            key(&[Value::Float64(0.0)])
+        );
     }
 
     #[test]
-    fn test_and_predicate() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
+    fn test_group_by_float_signed_zero_and_nan() {
+        let other_nan = f64::from_bits(f64::NAN.to_bits() | 1);
+        let mut values = FloatColumn::new();
+        for v in [0.0, -0.0, f64::NAN, other_nan, 1.0] {
+            values.push_value(Value::Float64(v)).unwrap();
+        }
+        let mut table = Table::new("floats".to_string());
+        table.add_column("x".to_string(), Box::new(values)).unwrap();
+
+        // Repeated runs hash with fresh random state each time
+        for _ in 0..10 {
+            let mut group_by = GroupBy::new(
+                Box::new(TableScan::new(table.clone())),
+                vec![0],
+                vec![0],
+                vec![Box::new(CountAggregate::new(DataType::Float64))],
+            );
+            let rows = drain_rows(&mut group_by);
+            assert_eq!(rows.len(), 3);
+            assert_eq!(rows[0], vec![Value::Float64(0.0), Value::Int64(2)]);
+            assert!(matches!(rows[1][0], Value::Float64(v) if v.is_nan()));
+            assert_eq!(rows[1][1], Value::Int64(2));
+            assert_eq!(rows[2], vec![Value::Float64(1.0), Value::Int64(1)]);
+        }
+    }
 
-        scan.open().unwrap();
+    #[test]
+    fn test_count_distinct_float_signed_zero_and_nan() {
+        // The same values as GROUP BY keys and under COUNT(DISTINCT) agree
+        let other_nan = f64::from_bits(f64::NAN.to_bits() | 1);
+        let mut values = FloatColumn::new();
+        for v in [0.0, -0.0, f64::NAN, other_nan, 1.5] {
+            values.push_value(Value::Float64(v)).unwrap();
+        }
+        let mut table = Table::new("floats".to_string());
+        table.add_column("x".to_string(), Box::new(values)).unwrap();
 
-        let batch = scan.next_batch().unwrap().unwrap();
+        let mut group_by = GroupBy::new(
+            Box::new(TableScan::new(table.clone())),
+            vec![0],
+            vec![0],
+            vec![Box::new(CountAggregate::new(DataType::Float64))],
+        );
+        assert_eq!(drain_rows(&mut group_by).len(), 3);
 
-        // Test: id > 1 AND id < 4
-        let pred1 = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThan,
-            Value::Int64(1),
-        ));
-        let pred2 = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::LessThan,
-            Value::Int64(4),
-        ));
-        let and_pred = And::new(pred1, pred2);
+        let mut count_distinct = GroupBy::new(
+            Box::new(TableScan::new(table)),
+            vec![],
+            vec![0],
+            vec![Box::new(DistinctAdapter::new(Box::new(
+                CountAggregate::new(DataType::Float64),
+            )))],
+        );
+        assert_eq!(drain_rows(&mut count_distinct), vec![vec![Value::Int64(3)]]);
+    }
 
-        assert!(!and_pred.eval(&batch, 0).unwrap()); // id=1 (not > 1)
-        assert!(and_pred.eval(&batch, 1).unwrap()); // id=2 (1 < 2 < 4)
-        assert!(and_pred.eval(&batch, 2).unwrap()); // id=3 (1 < 3 < 4)
-        assert!(!and_pred.eval(&batch, 3).unwrap()); // id=4 (not < 4)
+    #[test]
+    fn test_group_by_keeps_int_and_string_keys_apart() {
+        // Both key columns print "1" on every row, but differ in type
+        let table = VirtualTable::new(
+            "mixed".to_string(),
+            vec![
+                ("id".to_string(), DataType::Int64),
+                ("code".to_string(), DataType::String),
+            ],
+            || {
+                Box::new(
+                    [(1, "1"), (1, "1"), (1, "01"), (10, "1")]
+                        .into_iter()
+                        .map(|(id, code)| vec![Value::Int64(id), Value::String(code.to_string())]),
+                )
+            },
+        )
+        .unwrap();
+        let mut group_by = GroupBy::new(
+            Box::new(VirtualScan::new(table)),
+            vec![0, 1],
+            vec![0],
+            vec![Box::new(crate::aggregates::CountAggregate::new(
+                DataType::Int64,
+            ))],
+        );
 
-        scan.close().unwrap();
+        assert_eq!(
+            drain_rows(&mut group_by),
+            vec![
+                vec![
+                    Value::Int64(1),
+                    Value::String("1".to_string()),
+                    Value::Int64(2)
+                ],
+                vec![
+                    Value::Int64(1),
+                    Value::String("01".to_string()),
+                    Value::Int64(1)
+                ],
+                vec![
+                    Value::Int64(10),
+                    Value::String("1".to_string()),
+                    Value::Int64(1)
+                ],
+            ]
+        );
     }
 
     #[test]
-    fn test_or_predicate() {
-        let table = create_test_table();
-        let mut scan = TableScan::new(table).with_batch_size(10);
+    fn test_group_by_million_rows() {
+        const ROWS: usize = 1_000_000;
+        let mut group_by = GroupBy::new(
+            Box::new(VirtualScan::new(scored_table(ROWS))),
+            vec![1],
+            vec![0, 2],
+            vec![
+                Box::new(crate::aggregates::CountAggregate::new(DataType::Int64)),
+                Box::new(crate::aggregates::SumAggregate::new(DataType::Float64).unwrap()),
+            ],
+        );
 
-        scan.open().unwrap();
+        let start = std::time::Instant::now();
+        let rows = drain_rows(&mut group_by);
+        let elapsed = start.elapsed();
 
-        let batch = scan.next_batch().unwrap().unwrap();
+        // Buckets 0..97 plus the NULL bucket for every 13th row
+        assert_eq!(rows.len(), 98);
+        let counted: i64 = rows
+            .iter()
+            .map(|row| match row[1] {
+                Value::Int64(count) => count,
+                _ => panic!("COUNT should be Int64"),
+            })
+            .sum();
+        assert_eq!(counted, ROWS as i64);
+        assert!(elapsed.as_secs() < 30, "grouping took {:?}", elapsed);
+    }
 
-        // Test: age < 30 OR age > 40
-        let pred1 = Arc::new(BinaryComparison::new(
-            2,
-            ComparisonOp::LessThan,
-            Value::Float64(30.0),
-        ));
-        let pred2 = Arc::new(BinaryComparison::new(
-            2,
-            ComparisonOp::GreaterThan,
-            Value::Float64(40.0),
+    #[test]
+    fn test_table_scan_batches_share_table_storage() {
+        for rows in [1_000, 2_500, 100_000] {
+            let table = scored_rows_table(rows);
+            let shared: Vec<Arc<dyn Column>> = table
+                .column_names()
+                .iter()
+                .map(|name| table.shared_column(name).unwrap())
+                .collect();
+            let mut scan = TableScan::new(table).with_batch_size(1000);
+            scan.open().unwrap();
+
+            // Every batch column is a window onto the table's own column, so
+            // a scan copies no values however many rows it reads
+            let mut scanned = 0;
+            while let Some(batch) = scan.next_batch().unwrap() {
+                for (column, table_column) in batch.columns().iter().zip(&shared) {
+                    let (base, range) = resolve_slice(column.as_ref());
+                    assert!(std::ptr::addr_eq(base, Arc::as_ptr(table_column)));
+                    assert_eq!(
+                        range,
+                        RowSelection::Range(scanned..scanned + batch.row_count())
+                    );
+                    if batch.row_count() < rows {
+                        assert_eq!(column.memory_size(), 0);
+                    }
+                }
+                scanned += batch.row_count();
+            }
+            assert_eq!(scanned, rows);
+        }
+    }
+
+    #[test]
+    fn test_skip_and_take_rows_share_storage() {
+        let batch = single_column_batch(
+            DataType::Int64,
+            &(0..10).map(Value::Int64).collect::<Vec<_>>(),
+        );
+        let window = batch.skip_rows(3).unwrap().take_rows(4).unwrap();
+
+        assert_eq!(
+            window.column(0).unwrap().slice(None),
+            (3..7).map(Value::Int64).collect::<Vec<_>>()
+        );
+        let column = window.column(0).unwrap();
+        let (base, range) = resolve_slice(column.as_ref());
+        assert!(std::ptr::addr_eq(
+            base,
+            Arc::as_ptr(&batch.column(0).unwrap())
         ));
-        let or_pred = Or::new(pred1, pred2);
+        assert_eq!(range, RowSelection::Range(3..7));
+    }
 
-        assert!(or_pred.eval(&batch, 0).unwrap()); // age=25.0 (< 30)
-        assert!(!or_pred.eval(&batch, 1).unwrap()); // age=30.0 (not < 30 and not > 40)
-        assert!(!or_pred.eval(&batch, 2).unwrap()); // age=35.0 (not < 30 and not > 40)
-        assert!(!or_pred.eval(&batch, 3).unwrap()); // age=40.0 (not < 30 and not > 40)
-        assert!(or_pred.eval(&batch, 4).unwrap()); // age=45.0 (> 40)
+    #[test]
+    fn test_skip_all_rows_gives_empty_batch() {
+        let batch = single_column_batch(
+            DataType::Int64,
+            &(0..4).map(Value::Int64).collect::<Vec<_>>(),
+        );
 
-        scan.close().unwrap();
+        let empty = batch.skip_rows(4).unwrap();
+        assert_eq!(empty.row_count(), 0);
+        assert_eq!(empty.column_count(), 1);
+        assert_eq!(empty.column(0).unwrap().data_type(), DataType::Int64);
+        assert!(batch.skip_rows(5).is_err());
     }
 
     #[test]
-    fn test_filter_simple() {
-        let table = create_test_table();
-        let scan = Box::new(TableScan::new(table).with_batch_size(10));
+    fn test_limit_offset_at_batch_boundaries() {
+        const ROWS: usize = 3000;
+        let table = scored_rows_table(ROWS);
+
+        for offset in [0, 1, 1023, 1024, 1025, 2047, 2048, 2999, 3000, 4000] {
+            for limit in [0, 1, 5, 1024, 2000, 5000] {
+                let mut plan = Limit::new(
+                    Box::new(TableScan::new(table.clone()).with_batch_size(1024)),
+                    Some(limit),
+                    offset,
+                );
 
-        // Filter: age > 30
-        let predicate = Arc::new(BinaryComparison::new(
-            2,
-            ComparisonOp::GreaterThan,
-            Value::Float64(30.0),
-        ));
+                let ids: Vec<Value> = drain_rows(&mut plan)
+                    .into_iter()
+                    .map(|row| row[0].clone())
+                    .collect();
+                let expected: Vec<Value> = (offset..ROWS.min(offset + limit))
+                    .map(|id| Value::Int64(id as i64))
+                    .collect();
+                assert_eq!(ids, expected, "LIMIT {} OFFSET {}", limit, offset);
+            }
+        }
+    }
 
-        let mut filter = Filter::new(scan, predicate);
+    /// Replays fixed batches of a single Int64 `id` column.
+    struct BatchSource {
+        batches: Vec<Batch>,
+        next: usize,
+        state: OperatorState,
+    }
 
-        filter.open().unwrap();
+    impl BatchSource {
+        fn new(batches: &[std::ops::Range<i64>]) -> Self {
+            BatchSource {
+                batches: batches
+                    .iter()
+                    .map(|ids| {
+                        single_column_batch(
+                            DataType::Int64,
+                            &ids.clone().map(Value::Int64).collect::<Vec<_>>(),
+                        )
+                    })
+                    .collect(),
+                next: 0,
+                state: OperatorState::NotOpen,
+            }
+        }
+    }
 
-        let batch = filter.next_batch().unwrap();
-        assert!(batch.is_some());
+    impl Operator for BatchSource {
+        fn open(&mut self) -> Result<()> {
+            self.next = 0;
+            self.state = OperatorState::Open;
+            Ok(())
+        }
 
-        let batch = batch.unwrap();
-        // Should only have rows with age > 30: rows 2, 3, 4 (ages 35, 40, 45)
-        assert_eq!(batch.row_count(), 3);
-        assert_eq!(batch.column_count(), 3);
+        fn next_batch(&mut self) -> Result<Option<Batch>> {
+            let batch = self.batches.get(self.next).cloned();
+            self.next += 1;
+            Ok(batch)
+        }
 
-        // Verify first row (id=3, name=Charlie, age=35.0)
-        let val = batch.get(0, 0).unwrap();
-        assert_eq!(val, Value::Int64(3));
+        fn close(&mut self) -> Result<()> {
+            self.state = OperatorState::Closed;
+            Ok(())
+        }
 
-        // Verify last row (id=5, name=Eve, age=45.0)
-        let val = batch.get(2, 0).unwrap();
-        assert_eq!(val, Value::Int64(5));
+        fn schema(&self) -> Result<Schema> {
+            Schema::new(vec![Field::new("id", DataType::Int64)])
+        }
 
-        filter.close().unwrap();
+        fn is_open(&self) -> bool {
+            self.state == OperatorState::Open
+        }
     }
 
-    #[test]
-    fn test_filter_with_and() {
-        let table = create_test_table();
-        let scan = Box::new(TableScan::new(table).with_batch_size(10));
-
-        // Filter: id >= 2 AND id <= 4
-        let pred1 = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThanOrEqual,
-            Value::Int64(2),
-        ));
-        let pred2 = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::LessThanOrEqual,
-            Value::Int64(4),
-        ));
-        let predicate = Arc::new(And::new(pred1, pred2));
+    /// A [`BatchSource`] that cancels `token` as it hands out batch number
+    /// `cancel_at`, counting the batches pulled from it
+    struct CancellingSource {
+        source: BatchSource,
+        token: CancellationToken,
+        cancel_at: usize,
+        pulled: std::rc::Rc<std::cell::Cell<usize>>,
+    }
 
-        let mut filter = Filter::new(scan, predicate);
+    impl Operator for CancellingSource {
+        fn open(&mut self) -> Result<()> {
+            self.source.open()
+        }
 
-        filter.open().unwrap();
+        fn next_batch(&mut self) -> Result<Option<Batch>> {
+            if self.pulled.get() == self.cancel_at {
+                self.token.cancel();
+            }
+            self.pulled.set(self.pulled.get() + 1);
+            self.source.next_batch()
+        }
 
-        let batch = filter.next_batch().unwrap().unwrap();
-        // Should have rows 1, 2, 3 (ids 2, 3, 4)
-        assert_eq!(batch.row_count(), 3);
+        fn close(&mut self) -> Result<()> {
+            self.source.close()
+        }
 
-        filter.close().unwrap();
+        fn schema(&self) -> Result<Schema> {
+            self.source.schema()
+        }
     }
 
-    #[test]
-    fn test_filter_with_or() {
-        let table = create_test_table();
-        let scan = Box::new(TableScan::new(table).with_batch_size(10));
+    /// Accepts every row, and cancels `token` as it evaluates batch number
+    /// `cancel_at`, counting the batches evaluated
+    #[derive(Debug)]
+    struct CancellingPredicate {
+        token: CancellationToken,
+        cancel_at: usize,
+        evaluated: Arc<AtomicUsize>,
+    }
 
-        // Filter: name = "Alice" OR name = "Eve"
-        let pred1 = Arc::new(BinaryComparison::new(
-            1,
-            ComparisonOp::Equal,
-            Value::String("Alice".to_string()),
-        ));
-        let pred2 = Arc::new(BinaryComparison::new(
-            1,
-            ComparisonOp::Equal,
-            Value::String("Eve".to_string()),
-        ));
-        let predicate = Arc::new(Or::new(pred1, pred2));
+    impl Predicate for CancellingPredicate {
+        fn eval(&self, _batch: &Batch, _row_index: usize) -> Result<bool> {
+            Ok(true)
+        }
 
-        let mut filter = Filter::new(scan, predicate);
+        fn bind(&self, _column_types: &[DataType]) -> Result<Arc<dyn Predicate>> {
+            Ok(Arc::new(CancellingPredicate {
+                token: self.token.clone(),
+                cancel_at: self.cancel_at,
+                evaluated: Arc::clone(&self.evaluated),
+            }))
+        }
 
-        filter.open().unwrap();
+        fn eval_batch(&self, batch: &Batch) -> Result<Vec<bool>> {
+            if self.evaluated.fetch_add(1, AtomicOrdering::SeqCst) == self.cancel_at {
+                self.token.cancel();
+            }
+            Ok(vec![true; batch.row_count()])
+        }
+    }
 
-        let batch = filter.next_batch().unwrap().unwrap();
-        // Should have rows 0 and 4 (Alice and Eve)
-        assert_eq!(batch.row_count(), 2);
+    #[test]
+    fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(token.check().is_ok());
 
-        let val = batch.get(0, 1).unwrap();
-        assert_eq!(val, Value::String("Alice".to_string()));
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert!(matches!(token.check(), Err(ExecutionError::Cancelled)));
+        assert!(!token.timed_out());
 
-        let val = batch.get(1, 1).unwrap();
-        assert_eq!(val, Value::String("Eve".to_string()));
+        token.reset();
+        assert!(clone.check().is_ok());
 
-        filter.close().unwrap();
+        let timed = CancellationToken::new().with_timeout(std::time::Duration::ZERO);
+        assert!(timed.timed_out());
+        assert!(matches!(timed.check(), Err(ExecutionError::Cancelled)));
+        let relaxed = CancellationToken::new().with_timeout(std::time::Duration::from_secs(3600));
+        assert!(relaxed.check().is_ok());
     }
 
     #[test]
-    fn test_filter_empty_result() {
-        let table = create_test_table();
-        let scan = Box::new(TableScan::new(table).with_batch_size(10));
+    fn test_operators_stop_within_a_batch_of_cancellation() {
+        type Build = fn(Box<dyn Operator>) -> Box<dyn Operator>;
+        let builds: [(&str, Build); 3] = [
+            ("Sort", |child| {
+                Box::new(Sort::new(child, vec![0], vec![SortDirection::Descending]))
+            }),
+            ("GroupBy", |child| {
+                Box::new(GroupBy::new(
+                    child,
+                    vec![0],
+                    vec![0],
+                    vec![Box::new(CountAggregate::new(DataType::Int64))],
+                ))
+            }),
+            ("Filter", |child| {
+                Box::new(Filter::new(
+                    child,
+                    Arc::new(BinaryComparison::new(
+                        0,
+                        ComparisonOp::GreaterThanOrEqual,
+                        Value::Int64(0),
+                    )),
+                ))
+            }),
+        ];
+        let ranges: Vec<std::ops::Range<i64>> = (0..100).map(|i| i * 10..i * 10 + 10).collect();
+
+        for (name, build) in builds {
+            let token = CancellationToken::new();
+            let pulled = std::rc::Rc::new(std::cell::Cell::new(0));
+            let source = CancellingSource {
+                source: BatchSource::new(&ranges),
+                token: token.clone(),
+                cancel_at: 10,
+                pulled: pulled.clone(),
+            };
+            let mut plan = build(Box::new(source));
+            plan.set_cancellation(&token);
 
-        // Filter: id > 100 (no rows match)
-        let predicate = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThan,
-            Value::Int64(100),
-        ));
+            let mut drain = || -> Result<()> {
+                plan.open()?;
+                while plan.next_batch()?.is_some() {}
+                Ok(())
+            };
+            let result = drain();
+            assert!(
+                matches!(result, Err(ExecutionError::Cancelled)),
+                "{}: {:?}",
+                name,
+                result
+            );
+            // The batch that triggered the cancellation is the last one read
+            assert_eq!(pulled.get(), 11, "{}", name);
+        }
+    }
 
-        let mut filter = Filter::new(scan, predicate);
+    #[test]
+    fn test_sort_cancellable() {
+        let mut values: Vec<i64> = (0..200_000).map(|i| (i * 7919) % 200_003).collect();
+        let mut expected = values.clone();
+        expected.sort();
+
+        sort_cancellable(&mut values, &CancellationToken::new(), i64::cmp).unwrap();
+        assert_eq!(values, expected);
+
+        // Ties keep their input order across chunks and merges
+        let mut pairs: Vec<(i64, usize)> = values.iter().map(|&v| (v % 7, 0)).collect();
+        for (index, pair) in pairs.iter_mut().enumerate() {
+            pair.1 = index;
+        }
+        let mut stable = pairs.clone();
+        stable.sort_by_key(|pair| pair.0);
+        sort_cancellable(&mut pairs, &CancellationToken::new(), |a, b| a.0.cmp(&b.0)).unwrap();
+        assert_eq!(pairs, stable);
+
+        // A cancellation while sorting a chunk stops before the next one,
+        // and one during the last merge within SORT_CANCEL_CHECK_INTERVAL
+        // rows
+        let shuffled: Vec<i64> = (0..200_000).map(|i| (i * 7919) % 200_003).collect();
+        let count_comparisons = |values: &mut [i64]| {
+            let mut comparisons = 0;
+            sort_cancellable(values, &CancellationToken::new(), |a, b| {
+                comparisons += 1;
+                b.cmp(a)
+            })
+            .unwrap();
+            comparisons
+        };
+        let chunk = count_comparisons(&mut shuffled[..SORT_CANCEL_CHECK_INTERVAL].to_vec());
+        let total = count_comparisons(&mut shuffled.clone());
+        for (cancel_at, bound) in [(10, chunk), (total - 100_000, SORT_CANCEL_CHECK_INTERVAL)] {
+            let mut values = shuffled.clone();
+            let token = CancellationToken::new();
+            let mut comparisons = 0;
+            let result = sort_cancellable(&mut values, &token, |a, b| {
+                comparisons += 1;
+                if comparisons == cancel_at {
+                    token.cancel();
+                }
+                b.cmp(a)
+            });
+            assert!(matches!(result, Err(ExecutionError::Cancelled)));
+            assert!(
+                comparisons <= cancel_at + bound,
+                "{} comparisons",
+                comparisons
+            );
+            // Every element is still there
+            values.sort();
+            assert_eq!(values, expected);
+        }
+    }
 
-        filter.open().unwrap();
+    #[test]
+    fn test_set_cancellation_reaches_table_scan() {
+        let token = CancellationToken::new();
+        let scan = TableScan::new(create_test_table()).with_batch_size(1);
+        let mut plan = Project::new(Box::new(Limit::new(Box::new(scan), Some(5), 0)), vec![0]);
+        plan.set_cancellation(&token);
 
-        let batch = filter.next_batch().unwrap();
-        // Should return None immediately since no rows match
-        assert!(batch.is_none());
+        plan.open().unwrap();
+        assert!(plan.next_batch().unwrap().is_some());
+        token.cancel();
+        assert!(matches!(plan.next_batch(), Err(ExecutionError::Cancelled)));
+    }
 
-        filter.close().unwrap();
+    #[test]
+    fn test_set_cancellation_stops_parallel_scan_workers() {
+        let token = CancellationToken::new();
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let predicate = Arc::new(CancellingPredicate {
+            token: token.clone(),
+            cancel_at: 10,
+            evaluated: Arc::clone(&evaluated),
+        });
+        let table = Arc::new(create_test_large_table(100_000));
+        let scan = ParallelScan::new(table, vec![0, 1], 4)
+            .with_batch_size(10)
+            .with_predicate(predicate);
+        let mut plan = Project::new(Box::new(scan), vec![1]);
+        plan.set_cancellation(&token);
+
+        plan.open().unwrap();
+        let result = loop {
+            match plan.next_batch() {
+                Ok(Some(_)) => {}
+                other => break other,
+            }
+        };
+        assert!(
+            matches!(result, Err(ExecutionError::Cancelled)),
+            "{:?}",
+            result
+        );
+        plan.close().unwrap();
+        // Each worker finishes the batch it holds, then claims no more of
+        // the 10,000
+        assert!(
+            evaluated.load(AtomicOrdering::SeqCst) < 10 + 4,
+            "{:?}",
+            evaluated
+        );
     }
 
     #[test]
-    fn test_filter_all_rows_pass() {
-        let table = create_test_table();
-        let scan = Box::new(TableScan::new(table).with_batch_size(10));
+    fn test_limit_continues_past_empty_child_batches() {
+        let batches = [0..3, 3..3, 3..6, 6..6, 6..9];
+
+        for (limit, offset, expected) in [(10, 0, 0..9), (4, 3, 3..7), (2, 6, 6..8), (5, 9, 9..9)] {
+            let mut plan = Limit::new(Box::new(BatchSource::new(&batches)), Some(limit), offset);
+            let ids: Vec<Value> = drain_rows(&mut plan)
+                .into_iter()
+                .map(|row| row[0].clone())
+                .collect();
+            assert_eq!(
+                ids,
+                expected.map(Value::Int64).collect::<Vec<_>>(),
+                "LIMIT {} OFFSET {}",
+                limit,
+                offset
+            );
+        }
+    }
 
-        // Filter: id >= 1 (all rows pass)
-        let predicate = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThanOrEqual,
-            Value::Int64(1),
-        ));
+    #[test]
+    fn test_operators_on_sliced_columns() {
+        // Small batches put every batch but the first at an offset into the
+        // table's columns, with NULLs and dictionary codes to map
+        let values: Vec<Option<&str>> = (0..40)
+            .map(|row| match row % 5 {
+                0 => None,
+                1 | 3 => Some("x"),
+                _ => Some("y"),
+            })
+            .collect();
+        let (plain, dict) = plain_and_dictionary_columns(&values);
+        let mut table = Table::new("t".to_string());
+        let mut ids = IntColumn::new();
+        for row in 0..40 {
+            ids.push_value(if row % 7 == 0 {
+                Value::Null
+            } else {
+                Value::Int64(row)
+            })
+            .unwrap();
+        }
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table.add_shared_column("plain".to_string(), plain).unwrap();
+        table.add_shared_column("dict".to_string(), dict).unwrap();
+
+        let expected: Vec<Vec<Value>> = (0..40)
+            .filter(|row| row % 7 != 0 && *row > 10 && values[*row as usize] == Some("x"))
+            .map(|row| vec![Value::String("x".into()), Value::Int64(row)])
+            .collect();
+        assert!(!expected.is_empty());
+
+        for string_column in [1, 2] {
+            let id_filter = Arc::new(BinaryComparison::new(
+                0,
+                ComparisonOp::GreaterThan,
+                Value::Int64(10),
+            ));
+            let string_filter = Arc::new(BinaryComparison::new(
+                string_column,
+                ComparisonOp::Equal,
+                Value::String("x".into()),
+            ));
+            let mut plan = Project::new(
+                Box::new(Filter::new(
+                    Box::new(Filter::new(
+                        Box::new(TableScan::new(table.clone()).with_batch_size(6)),
+                        id_filter,
+                    )),
+                    string_filter,
+                )),
+                vec![string_column, 0],
+            );
+            assert_eq!(drain_rows(&mut plan), expected, "column {}", string_column);
+        }
 
-        let mut filter = Filter::new(scan, predicate);
+        // Code comparison between two sliced dictionary columns
+        let batch = Batch::new(vec![
+            table.shared_column("dict").unwrap(),
+            table.shared_column("dict").unwrap(),
+        ])
+        .skip_rows(7)
+        .unwrap();
+        let predicate = ExpressionComparison::new(
+            ScalarExpression::Column(0),
+            ComparisonOp::Equal,
+            ScalarExpression::Column(1),
+        );
+        assert_eq!(
+            predicate.eval_batch(&batch).unwrap(),
+            eval_each_row(&predicate, &batch).unwrap()
+        );
+    }
 
-        filter.open().unwrap();
+    #[test]
+    fn test_batch_filter_rows() {
+        let batch = single_column_batch(
+            DataType::String,
+            &[
+                Value::String("a".into()),
+                Value::Null,
+                Value::String("c".into()),
+            ],
+        );
 
-        let batch = filter.next_batch().unwrap().unwrap();
-        // Should have all 5 rows
-        assert_eq!(batch.row_count(), 5);
+        let filtered = batch.filter_rows(&[false, true, true]).unwrap();
+        assert_eq!(filtered.row_count(), 2);
+        assert_eq!(filtered.get(0, 0).unwrap(), Value::Null);
+        assert_eq!(filtered.get(1, 0).unwrap(), Value::String("c".into()));
 
-        filter.close().unwrap();
+        assert!(batch.filter_rows(&[true]).is_err());
     }
 
     #[test]
-    fn test_filter_multiple_batches() {
-        let table = create_test_large_table(2500);
-        let scan = Box::new(TableScan::new(table).with_batch_size(1000));
+    fn test_batch_filter_rows_copies_runs_of_a_slice() {
+        let values: Vec<Value> = (0..40)
+            .map(|v| {
+                if v % 6 == 0 {
+                    Value::Null
+                } else {
+                    Value::Int64(v)
+                }
+            })
+            .collect();
+        let batch = single_column_batch(DataType::Int64, &values)
+            .slice_rows(5..35)
+            .unwrap();
 
-        // Filter: id >= 1000 (only second and third batches)
-        let predicate = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThanOrEqual,
-            Value::Int64(1000),
-        ));
+        for selection in [
+            vec![false; 30],
+            vec![true; 30],
+            (0..30).map(|row| row % 4 != 1).collect(),
+            (0..30).map(|row| (10..20).contains(&row)).collect(),
+        ] {
+            let filtered = batch.filter_rows(&selection).unwrap();
+            let expected: Vec<Value> = values[5..35]
+                .iter()
+                .zip(&selection)
+                .filter(|(_, &keep)| keep)
+                .map(|(value, _)| value.clone())
+                .collect();
+            assert_eq!(filtered.column(0).unwrap().slice(None), expected);
+        }
+    }
 
-        let mut filter = Filter::new(scan, predicate);
+    #[test]
+    fn test_in_list_matches_members() {
+        let batch = single_column_batch(
+            DataType::Int64,
+            &[Value::Int64(25), Value::Int64(26), Value::Int64(30)],
+        );
+        let members = vec![Value::Int64(25), Value::Float64(30.0)];
+
+        let unbound = InList::new(0, members.clone());
+        let bound = unbound.bind(&[DataType::Int64]).unwrap();
+        for predicate in [&unbound as &dyn Predicate, bound.as_ref()] {
+            assert!(predicate.eval(&batch, 0).unwrap());
+            assert!(!predicate.eval(&batch, 1).unwrap());
+            assert!(predicate.eval(&batch, 2).unwrap());
+        }
 
-        filter.open().unwrap();
+        let not_in = InList::new(0, members)
+            .negated()
+            .bind(&[DataType::Int64])
+            .unwrap();
+        assert!(!not_in.eval(&batch, 0).unwrap());
+        assert!(not_in.eval(&batch, 1).unwrap());
+    }
+
+    #[test]
+    fn test_is_null_predicate() {
+        let batch = single_column_batch(
+            DataType::String,
+            &[
+                Value::String("a".to_string()),
+                Value::Null,
+                Value::String("c".to_string()),
+                Value::Null,
+            ],
+        );
 
-        let mut total_rows = 0;
-        while let Some(batch) = filter.next_batch().unwrap() {
-            total_rows += batch.row_count();
+        let is_null = IsNullPredicate::new(0).bind(&[DataType::String]).unwrap();
+        let is_not_null = IsNullPredicate::new(0)
+            .negated()
+            .bind(&[DataType::String])
+            .unwrap();
+        assert_eq!(
+            is_null.eval_batch(&batch).unwrap(),
+            vec![false, true, false, true]
+        );
+        assert_eq!(
+            is_not_null.eval_batch(&batch).unwrap(),
+            vec![true, false, true, false]
+        );
+        for row in 0..4 {
+            assert_eq!(is_null.eval(&batch, row).unwrap(), row % 2 == 1);
+            assert_eq!(is_not_null.eval(&batch, row).unwrap(), row % 2 == 0);
         }
 
-        // Should have 1500 rows (ids 1000-2499)
-        assert_eq!(total_rows, 1500);
+        // A column without NULLs is answered without reading its rows
+        let full = single_column_batch(DataType::Int64, &[Value::Int64(1), Value::Int64(2)]);
+        assert_eq!(is_null.eval_batch(&full).unwrap(), vec![false, false]);
+        assert_eq!(is_not_null.eval_batch(&full).unwrap(), vec![true, true]);
 
-        filter.close().unwrap();
+        assert!(IsNullPredicate::new(1).bind(&[DataType::String]).is_err());
     }
 
     #[test]
-    fn test_filter_schema() {
-        let table = create_test_table();
-        let scan = Box::new(TableScan::new(table));
-
-        let predicate = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThan,
-            Value::Int64(0),
-        ));
-
-        let mut filter = Filter::new(scan, predicate);
+    fn test_in_list_coerces_int_members_for_float_column() {
+        let batch = single_column_batch(
+            DataType::Float64,
+            &[Value::Float64(2.0), Value::Float64(2.5)],
+        );
+        let bound = InList::new(0, vec![Value::Int64(2), Value::Int64(3)])
+            .bind(&[DataType::Float64])
+            .unwrap();
+        assert!(bound.eval(&batch, 0).unwrap());
+        assert!(!bound.eval(&batch, 1).unwrap());
+    }
 
-        // Schema not available before open
-        assert!(filter.schema().is_err());
+    #[test]
+    fn test_comparisons_against_null_are_false() {
+        let batch = single_column_batch(DataType::Int64, &[Value::Int64(5), Value::Null]);
 
-        filter.open().unwrap();
+        for op in [
+            ComparisonOp::Equal,
+            ComparisonOp::NotEqual,
+            ComparisonOp::LessThan,
+        ] {
+            let unbound = BinaryComparison::new(0, op, Value::Int64(7));
+            let bound = unbound.bind(&[DataType::Int64]).unwrap();
+            for predicate in [&unbound as &dyn Predicate, bound.as_ref()] {
+                assert!(!predicate.eval(&batch, 1).unwrap());
+            }
+        }
 
-        // Schema should match child's schema
-        let schema = filter.schema().unwrap();
-        assert_eq!(schema.len(), 3);
-        assert_eq!(schema.get("id"), Some(&DataType::Int64));
-        assert_eq!(schema.get("name"), Some(&DataType::String));
-        assert_eq!(schema.get("age"), Some(&DataType::Float64));
+        // A NULL literal matches nothing, not even a NULL row
+        let null_literal = BinaryComparison::new(0, ComparisonOp::Equal, Value::Null)
+            .bind(&[DataType::Int64])
+            .unwrap();
+        assert!(!null_literal.eval(&batch, 0).unwrap());
+        assert!(!null_literal.eval(&batch, 1).unwrap());
+
+        // NULL is neither IN nor NOT IN a list
+        let members = vec![Value::Int64(1)];
+        assert!(!InList::new(0, members.clone()).eval(&batch, 1).unwrap());
+        assert!(!InList::new(0, members).negated().eval(&batch, 1).unwrap());
+
+        // NOT IN a list containing NULL is never true
+        let not_in = InList::new(0, vec![Value::Int64(1), Value::Null])
+            .negated()
+            .bind(&[DataType::Int64])
+            .unwrap();
+        assert!(!not_in.eval(&batch, 0).unwrap());
+    }
 
-        filter.close().unwrap();
+    #[test]
+    fn test_in_list_rejects_mismatched_member_at_bind() {
+        let predicate = InList::new(0, vec![Value::String("a".to_string()), Value::Int64(1)]);
+        assert!(matches!(
+            predicate.bind(&[DataType::String]),
+            Err(ExecutionError::SchemaMismatch(_))
+        ));
+        assert!(matches!(
+            predicate.bind(&[]),
+            Err(ExecutionError::InvalidColumnIndex { .. })
+        ));
     }
 
     #[test]
-    fn test_filter_lifecycle() {
+    fn test_filter_rejects_unsupported_comparison_at_open() {
         let table = create_test_table();
         let scan = Box::new(TableScan::new(table));
 
-        let predicate = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThan,
-            Value::Int64(2),
+        // name (String) compared to an Int64 literal
+        let predicate = Arc::new(And::new(
+            Arc::new(BinaryComparison::new(
+                0,
+                ComparisonOp::GreaterThan,
+                Value::Int64(0),
+            )),
+            Arc::new(BinaryComparison::new(
+                1,
+                ComparisonOp::Equal,
+                Value::Int64(5),
+            )),
         ));
-
         let mut filter = Filter::new(scan, predicate);
 
-        assert!(!filter.is_open());
-
-        filter.open().unwrap();
-        assert!(filter.is_open());
-
-        // Get batches
-        while filter.next_batch().unwrap().is_some() {
-            // Keep consuming
+        let result = filter.open();
+        match result {
+            Err(ExecutionError::SchemaMismatch(msg)) => {
+                assert!(msg.contains("String column"), "{}", msg);
+                assert!(msg.contains("Int64 literal"), "{}", msg);
+            }
+            _ => panic!("expected a schema mismatch at open"),
         }
-
-        filter.close().unwrap();
-        assert!(!filter.is_open());
     }
 
     #[test]
-    fn test_filter_next_batch_not_open() {
+    fn test_filter_rejects_out_of_range_column_at_open() {
         let table = create_test_table();
         let scan = Box::new(TableScan::new(table));
-
         let predicate = Arc::new(BinaryComparison::new(
-            0,
-            ComparisonOp::GreaterThan,
-            Value::Int64(0),
+            42,
+            ComparisonOp::Equal,
+            Value::Int64(1),
         ));
-
         let mut filter = Filter::new(scan, predicate);
 
-        let result = filter.next_batch();
-        assert!(result.is_err());
-        assert!(matches!(result, Err(ExecutionError::OperatorNotOpen)));
+        assert!(matches!(
+            filter.open(),
+            Err(ExecutionError::InvalidColumnIndex { index: 42, .. })
+        ));
     }
     // ============================================================================
     // PROJECT TESTS
@@ -3226,9 +11155,13 @@ mod tests {
 
         // Verify column names
         let schema = project.schema().unwrap();
-        assert_eq!(schema.len(), 2);
-        assert!(schema.contains_key("id"));
-        assert!(schema.contains_key("name"));
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("id", DataType::Int64),
+                Field::new("name", DataType::String),
+            ]
+        );
 
         // Verify data
         let val = batch.get(0, 0).unwrap();
@@ -3257,11 +11190,13 @@ mod tests {
 
         // Verify schema has aliased names
         let schema = project.schema().unwrap();
-        assert_eq!(schema.len(), 2);
-        assert!(schema.contains_key("user_id"));
-        assert!(schema.contains_key("user_name"));
-        assert!(!schema.contains_key("id"));
-        assert!(!schema.contains_key("name"));
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("user_id", DataType::Int64),
+                Field::new("user_name", DataType::String),
+            ]
+        );
 
         let batch = project.next_batch().unwrap().unwrap();
         assert_eq!(batch.row_count(), 5);
@@ -3305,6 +11240,61 @@ mod tests {
         project.close().unwrap();
     }
 
+    #[test]
+    fn test_project_reordering_schema_matches_batches() {
+        // id > 1, reordered to (age, name, id), then sorted and limited
+        let scan = Box::new(TableScan::new(create_test_table()).with_batch_size(2));
+        let predicate = BinaryComparison::new(0, ComparisonOp::GreaterThan, Value::Int64(1));
+        let filter = Box::new(Filter::new(scan, Arc::new(predicate)));
+        let project = Box::new(Project::new(filter, vec![2, 1, 0]));
+        let sort = Box::new(Sort::new(project, vec![2], vec![SortDirection::Descending]));
+        let mut plan = Limit::new(sort, Some(3), 0);
+
+        plan.open().unwrap();
+        let schema = plan.schema().unwrap();
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("age", DataType::Float64),
+                Field::new("name", DataType::String),
+                Field::new("id", DataType::Int64),
+            ]
+        );
+
+        let mut ids = Vec::new();
+        while let Some(batch) = plan.next_batch().unwrap() {
+            assert_eq!(batch.schema(), Some(&schema));
+            for (field, column) in schema.fields().iter().zip(batch.columns()) {
+                assert_eq!(field.data_type, column.data_type());
+            }
+            let id = batch.column_by_name("id").unwrap();
+            ids.extend((0..batch.row_count()).map(|row| id.get(row).unwrap()));
+        }
+        assert_eq!(ids, vec![Value::Int64(5), Value::Int64(4), Value::Int64(3)]);
+        plan.close().unwrap();
+
+        // Every operator's batches carry the operator's own schema
+        let scan = Box::new(TableScan::new(create_test_table()).with_batch_size(2));
+        let project = Box::new(
+            Project::new(scan, vec![1, 0])
+                .with_aliases(vec!["label".to_string(), "key".to_string()]),
+        );
+        let mut group_by = GroupBy::new(
+            project,
+            vec![0],
+            vec![1],
+            vec![Box::new(CountAggregate::new(DataType::Int64))],
+        );
+        group_by.open().unwrap();
+        let batch = group_by.next_batch().unwrap().unwrap();
+        assert_eq!(
+            batch.schema().unwrap().column_names(),
+            vec!["label", "agg_0"]
+        );
+        assert_eq!(batch.schema(), Some(&group_by.schema().unwrap()));
+        group_by.close().unwrap();
+    }
+
     #[test]
     fn test_project_single_column() {
         let table = create_test_table();
@@ -3320,8 +11310,7 @@ mod tests {
         assert_eq!(batch.column_count(), 1);
 
         let schema = project.schema().unwrap();
-        assert_eq!(schema.len(), 1);
-        assert!(schema.contains_key("name"));
+        assert_eq!(schema.fields(), [Field::new("name", DataType::String),]);
 
         // Verify data
         for i in 0..5 {
@@ -3404,14 +11393,91 @@ mod tests {
         project.open().unwrap();
 
         let schema = project.schema().unwrap();
-        assert_eq!(schema.len(), 2);
-        assert_eq!(schema.get("id"), Some(&DataType::Int64));
-        assert_eq!(schema.get("age"), Some(&DataType::Float64));
-        assert_eq!(schema.get("name"), None);
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("id", DataType::Int64),
+                Field::new("age", DataType::Float64),
+            ]
+        );
+
+        project.close().unwrap();
+    }
+
+    #[test]
+    fn test_project_schema_and_column_names_agree() {
+        let table = create_test_table();
+        let scan = Box::new(TableScan::new(table));
+
+        // Aliases that swap the original names of two different columns
+        let mut project = Box::new(Project::new(scan, vec![2, 0, 1]).with_aliases(vec![
+            "id".to_string(),
+            "age".to_string(),
+            "label".to_string(),
+        ]));
+
+        // Neither accessor is available before open
+        assert!(project.schema().is_err());
+        assert!(project.column_names().is_err());
+
+        project.open().unwrap();
+
+        let names = project.column_names().unwrap();
+        assert_eq!(names, vec!["id", "age", "label"]);
+
+        // Every ordered name maps to the type of the column actually produced
+        let schema = project.schema().unwrap();
+        assert_eq!(schema.column_names(), names);
+        let batch = project.next_batch().unwrap().unwrap();
+        for (i, field) in schema.fields().iter().enumerate() {
+            assert_eq!(field.data_type, batch.column(i).unwrap().data_type());
+        }
+        assert_eq!(schema.data_type("id"), Some(DataType::Float64));
+        assert_eq!(schema.data_type("age"), Some(DataType::Int64));
+        assert_eq!(batch.get(0, 2).unwrap(), Value::String("Alice".to_string()));
 
         project.close().unwrap();
     }
 
+    #[test]
+    fn test_project_alias_count_must_match() {
+        // Some-but-not-all aliases is an error from open(), not a silent fallback
+        let scan = Box::new(TableScan::new(create_test_table()));
+        let mut project =
+            Project::new(scan, vec![0, 1, 2]).with_aliases(vec!["a".to_string(), "b".to_string()]);
+        assert!(matches!(project.open(), Err(ExecutionError::Custom(_))));
+        assert!(project.column_names().is_err());
+
+        // Extra aliases are rejected too
+        let scan = Box::new(TableScan::new(create_test_table()));
+        let mut project =
+            Project::new(scan, vec![0]).with_aliases(vec!["a".to_string(), "b".to_string()]);
+        assert!(matches!(project.open(), Err(ExecutionError::Custom(_))));
+    }
+
+    #[test]
+    fn test_project_alias_colliding_with_other_output_name() {
+        let table = create_test_table();
+        let scan = Box::new(TableScan::new(table));
+
+        // Two columns aliased to the same name
+        let mut project = Box::new(
+            Project::new(scan, vec![0, 1])
+                .with_aliases(vec!["name".to_string(), "name".to_string()]),
+        );
+        assert!(matches!(project.open(), Err(ExecutionError::Custom(_))));
+
+        // Reusing an original name of a column that is not projected is fine
+        let scan = Box::new(TableScan::new(create_test_table()));
+        let mut project = Project::new(scan, vec![0]).with_aliases(vec!["name".to_string()]);
+        project.open().unwrap();
+        assert_eq!(project.column_names().unwrap(), vec!["name"]);
+        assert_eq!(
+            project.schema().unwrap().data_type("name"),
+            Some(DataType::Int64)
+        );
+    }
+
     #[test]
     fn test_project_lifecycle() {
         let table = create_test_table();
@@ -3495,46 +11561,322 @@ mod tests {
             .add_column("id".to_string(), Box::new(id_col))
             .unwrap();
 
-        let mut name_col = StringColumn::new();
-        name_col
-            .push_value(Value::String("Single".to_string()))
-            .unwrap();
-        table
-            .add_column("name".to_string(), Box::new(name_col))
-            .unwrap();
+        let mut name_col = StringColumn::new();
+        name_col
+            .push_value(Value::String("Single".to_string()))
+            .unwrap();
+        table
+            .add_column("name".to_string(), Box::new(name_col))
+            .unwrap();
+
+        let scan = Box::new(TableScan::new(table));
+        let mut project = Box::new(Project::new(scan, vec![1, 0]));
+
+        project.open().unwrap();
+
+        let batch = project.next_batch().unwrap().unwrap();
+        assert_eq!(batch.row_count(), 1);
+        assert_eq!(batch.column_count(), 2);
+
+        let val = batch.get(0, 0).unwrap();
+        assert_eq!(val, Value::String("Single".to_string()));
+        let val = batch.get(0, 1).unwrap();
+        assert_eq!(val, Value::Int64(1));
+
+        assert!(project.next_batch().unwrap().is_none());
+
+        project.close().unwrap();
+    }
+
+    #[test]
+    fn test_project_double_open() {
+        let table = create_test_table();
+        let scan = Box::new(TableScan::new(table));
+
+        let mut project = Box::new(Project::new(scan, vec![0]));
+
+        project.open().unwrap();
+        let result = project.open();
+        assert!(result.is_err());
+        assert!(matches!(result, Err(ExecutionError::OperatorAlreadyOpen)));
+
+        project.close().unwrap();
+    }
+
+    // ============================================================================
+    // COMPUTE TESTS
+    // ============================================================================
+
+    fn arithmetic(
+        left: ScalarExpression,
+        op: ArithmeticOp,
+        right: ScalarExpression,
+    ) -> ExpressionEvaluator {
+        ExpressionEvaluator::new(ScalarExpression::Arithmetic {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+        })
+    }
+
+    #[test]
+    fn test_expression_evaluator_types_and_nulls() {
+        let ints = single_column_batch(
+            DataType::Int64,
+            &[Value::Int64(7), Value::Null, Value::Int64(-9)],
+        );
+        let column = ScalarExpression::Column;
+        let literal = ScalarExpression::Literal;
+
+        // Int64 / Int64 stays Int64 and truncates
+        let halved = arithmetic(column(0), ArithmeticOp::Divide, literal(Value::Int64(2)));
+        assert_eq!(
+            halved.data_type(&[DataType::Int64]).unwrap(),
+            DataType::Int64
+        );
+        assert_eq!(
+            halved.evaluate(&ints).unwrap().slice(None),
+            vec![Value::Int64(3), Value::Null, Value::Int64(-4)]
+        );
+
+        // A Float64 operand promotes the result
+        let scaled = arithmetic(
+            column(0),
+            ArithmeticOp::Multiply,
+            literal(Value::Float64(0.5)),
+        );
+        assert_eq!(
+            scaled.data_type(&[DataType::Int64]).unwrap(),
+            DataType::Float64
+        );
+        assert_eq!(
+            scaled.evaluate(&ints).unwrap().slice(None),
+            vec![Value::Float64(3.5), Value::Null, Value::Float64(-4.5)]
+        );
+
+        // Division by zero is NULL for both types
+        for zero in [Value::Int64(0), Value::Float64(0.0)] {
+            let divided = arithmetic(column(0), ArithmeticOp::Divide, literal(zero));
+            assert_eq!(divided.evaluate_row(&ints, 0).unwrap(), Value::Null);
+        }
+
+        let strings = single_column_batch(DataType::String, &[Value::String("a".into())]);
+        let concat = arithmetic(column(0), ArithmeticOp::Add, literal(Value::Int64(1)));
+        assert!(matches!(
+            concat.data_type(&[DataType::String]),
+            Err(ExecutionError::SchemaMismatch(_))
+        ));
+        assert!(concat.evaluate(&strings).is_err());
+    }
+
+    #[test]
+    fn test_string_functions() {
+        let text = |s: &str| Value::String(s.to_string());
+        let mut names = StringColumn::new();
+        for name in [Some("  Zoë "), None, Some("bob")] {
+            match name {
+                Some(name) => names.push_value(text(name)).unwrap(),
+                None => names.push_value(Value::Null).unwrap(),
+            }
+        }
+        let batch = Batch::new(vec![Arc::new(names)]);
+
+        let call = |function| {
+            ExpressionEvaluator::new(ScalarExpression::Function {
+                function,
+                args: vec![ScalarExpression::Column(0)],
+            })
+        };
+        let values = |function| call(function).evaluate(&batch).unwrap().slice(None);
+
+        assert_eq!(
+            values(ScalarFunction::Upper),
+            vec![text("  ZOË "), Value::Null, text("BOB")]
+        );
+        assert_eq!(
+            values(ScalarFunction::Lower),
+            vec![text("  zoë "), Value::Null, text("bob")]
+        );
+        assert_eq!(
+            values(ScalarFunction::Trim),
+            vec![text("Zoë"), Value::Null, text("bob")]
+        );
+        // Characters, not bytes
+        assert_eq!(
+            values(ScalarFunction::Length),
+            vec![Value::Int64(6), Value::Null, Value::Int64(3)]
+        );
+        assert_eq!(
+            call(ScalarFunction::Length)
+                .data_type(&[DataType::String])
+                .unwrap(),
+            DataType::Int64
+        );
+        assert!(matches!(
+            call(ScalarFunction::Upper).data_type(&[DataType::Int64]),
+            Err(ExecutionError::SchemaMismatch(_))
+        ));
+
+        assert_eq!(
+            ScalarFunction::from_name("trim"),
+            Some(ScalarFunction::Trim)
+        );
+        assert_eq!(ScalarFunction::from_name("reverse"), None);
+    }
+
+    #[test]
+    fn test_cast_value_conversions() {
+        let cast = |value: Value, target| cast_value(&value, target);
+
+        assert_eq!(
+            cast(Value::Int64(3), DataType::Float64).unwrap(),
+            Value::Float64(3.0)
+        );
+        assert_eq!(
+            cast(Value::Float64(2.5), DataType::Int64).unwrap(),
+            Value::Int64(3)
+        );
+        assert_eq!(
+            cast(Value::Float64(-2.4), DataType::Int64).unwrap(),
+            Value::Int64(-2)
+        );
+        assert_eq!(
+            cast(Value::Date(1), DataType::String).unwrap(),
+            Value::String("1970-01-02".to_string())
+        );
+        assert_eq!(
+            cast(Value::String(" 42 ".into()), DataType::Int64).unwrap(),
+            Value::Int64(42)
+        );
+        assert_eq!(
+            cast(Value::String("1e3".into()), DataType::Float64).unwrap(),
+            Value::Float64(1000.0)
+        );
+        assert_eq!(
+            cast(Value::String("TRUE".into()), DataType::Boolean).unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            cast(Value::String("2024-02-29".into()), DataType::Date).unwrap(),
+            Value::Date(19_782)
+        );
+        assert_eq!(cast(Value::Null, DataType::Int64).unwrap(), Value::Null);
+
+        // Values that do not convert are reported by value
+        for (value, target) in [
+            (Value::String("abc".into()), DataType::Int64),
+            (Value::String("1.5".into()), DataType::Int64),
+            (Value::Float64(f64::NAN), DataType::Int64),
+            (Value::Float64(1e19), DataType::Int64),
+        ] {
+            assert!(cast(value, target).is_err());
+        }
+        let err = cast(Value::String("abc".into()), DataType::Int64).unwrap_err();
+        assert!(err.to_string().contains("'abc'"), "{}", err);
+
+        // Types with no conversion are rejected before any row is read
+        let to_date = ExpressionEvaluator::new(ScalarExpression::Cast {
+            expr: Box::new(ScalarExpression::Column(0)),
+            target: DataType::Date,
+        });
+        assert!(matches!(
+            to_date.data_type(&[DataType::Int64]),
+            Err(ExecutionError::SchemaMismatch(_))
+        ));
+        assert_eq!(
+            to_date.data_type(&[DataType::String]).unwrap(),
+            DataType::Date
+        );
+    }
+
+    #[test]
+    fn test_expression_evaluator_checks_overflow() {
+        let batch = single_column_batch(DataType::Int64, &[Value::Int64(i64::MAX)]);
+        let column = || Box::new(ScalarExpression::Column(0));
+
+        let overflowing = [
+            arithmetic(
+                *column(),
+                ArithmeticOp::Add,
+                ScalarExpression::Literal(Value::Int64(1)),
+            ),
+            arithmetic(*column(), ArithmeticOp::Multiply, *column()),
+            ExpressionEvaluator::new(ScalarExpression::Arithmetic {
+                left: Box::new(ScalarExpression::Negate(column())),
+                op: ArithmeticOp::Subtract,
+                right: Box::new(ScalarExpression::Literal(Value::Int64(2))),
+            }),
+        ];
+        for evaluator in overflowing {
+            let err = evaluator.evaluate(&batch).err().unwrap();
+            assert!(err.to_string().contains("overflow"), "{}", err);
+        }
+    }
 
+    #[test]
+    fn test_compute_appends_columns() {
+        let table = create_test_table();
         let scan = Box::new(TableScan::new(table));
-        let mut project = Box::new(Project::new(scan, vec![1, 0]));
-
-        project.open().unwrap();
 
-        let batch = project.next_batch().unwrap().unwrap();
-        assert_eq!(batch.row_count(), 1);
-        assert_eq!(batch.column_count(), 2);
+        // age - id, with age Float64 and id Int64
+        let evaluator = arithmetic(
+            ScalarExpression::Column(2),
+            ArithmeticOp::Subtract,
+            ScalarExpression::Column(0),
+        );
+        let mut compute = Compute::new(scan, vec![("age - id".to_string(), evaluator)]);
+        compute.open().unwrap();
 
-        let val = batch.get(0, 0).unwrap();
-        assert_eq!(val, Value::String("Single".to_string()));
-        let val = batch.get(0, 1).unwrap();
-        assert_eq!(val, Value::Int64(1));
+        assert_eq!(
+            compute.column_names().unwrap(),
+            vec!["id", "name", "age", "age - id"]
+        );
+        assert_eq!(
+            compute.schema().unwrap().data_type("age - id"),
+            Some(DataType::Float64)
+        );
 
-        assert!(project.next_batch().unwrap().is_none());
+        let batch = compute.next_batch().unwrap().unwrap();
+        assert_eq!(batch.column_count(), 4);
+        assert_eq!(batch.get(0, 3).unwrap(), Value::Float64(24.0));
+        assert_eq!(batch.get(4, 3).unwrap(), Value::Float64(40.0));
 
-        project.close().unwrap();
+        compute.close().unwrap();
     }
 
     #[test]
-    fn test_project_double_open() {
+    fn test_filter_with_expression_comparison() {
         let table = create_test_table();
         let scan = Box::new(TableScan::new(table));
 
-        let mut project = Box::new(Project::new(scan, vec![0]));
+        // age > id * 10
+        let predicate = Arc::new(ExpressionComparison::new(
+            ScalarExpression::Column(2),
+            ComparisonOp::GreaterThan,
+            ScalarExpression::Arithmetic {
+                left: Box::new(ScalarExpression::Column(0)),
+                op: ArithmeticOp::Multiply,
+                right: Box::new(ScalarExpression::Literal(Value::Int64(10))),
+            },
+        ));
+        let mut filter = Filter::new(scan, predicate);
+        filter.open().unwrap();
 
-        project.open().unwrap();
-        let result = project.open();
-        assert!(result.is_err());
-        assert!(matches!(result, Err(ExecutionError::OperatorAlreadyOpen)));
+        let batch = filter.next_batch().unwrap().unwrap();
+        let ids: Vec<Value> = (0..batch.row_count())
+            .map(|row| batch.get(row, 0).unwrap())
+            .collect();
+        assert_eq!(ids, vec![Value::Int64(1), Value::Int64(2), Value::Int64(3)]);
+        filter.close().unwrap();
 
-        project.close().unwrap();
+        // Comparing a String with a number is rejected when the Filter opens
+        let scan = Box::new(TableScan::new(create_test_table()));
+        let predicate = Arc::new(ExpressionComparison::new(
+            ScalarExpression::Column(1),
+            ComparisonOp::Equal,
+            ScalarExpression::Column(0),
+        ));
+        assert!(Filter::new(scan, predicate).open().is_err());
     }
 
     // ============================================================================
@@ -3562,19 +11904,25 @@ mod tests {
 
         // Verify schema
         let schema = group_by.schema().unwrap();
-        assert_eq!(schema.len(), 2);
-        assert_eq!(schema.get("name"), Some(&DataType::String));
-        assert_eq!(schema.get("agg_0"), Some(&DataType::Int64));
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("name", DataType::String),
+                Field::new("agg_0", DataType::Int64),
+            ]
+        );
 
         // Verify column names
         let column_names = group_by.column_names().unwrap();
         assert_eq!(column_names, vec!["name".to_string(), "agg_0".to_string()]);
 
-        // Verify data - each name should have count 1
-        for i in 0..batch.row_count() {
-            let _name = batch.get(i, 0).unwrap();
-            let count = batch.get(i, 1).unwrap();
-            assert_eq!(count, Value::Int64(1));
+        // Groups come out in first-seen order, each name counted once
+        for (i, name) in ["Alice", "Bob", "Charlie", "David", "Eve"]
+            .into_iter()
+            .enumerate()
+        {
+            assert_eq!(batch.get(i, 0).unwrap(), Value::String(name.to_string()));
+            assert_eq!(batch.get(i, 1).unwrap(), Value::Int64(1));
         }
 
         // No more batches
@@ -3694,21 +12042,10 @@ mod tests {
         let batch = group_by.next_batch().unwrap().unwrap();
         assert_eq!(batch.row_count(), 2); // 2 groups
 
-        // Find each group and verify sum
-        let mut group1_found = false;
-        let mut group2_found = false;
-        for i in 0..batch.row_count() {
-            let group = batch.get(i, 0).unwrap();
-            let sum = batch.get(i, 1).unwrap();
-            if group == Value::Int64(1) {
-                assert_eq!(sum, Value::Int64(21)); // Sum of 1-6
-                group1_found = true;
-            } else if group == Value::Int64(2) {
-                assert_eq!(sum, Value::Int64(10)); // Sum of 1-4
-                group2_found = true;
-            }
-        }
-        assert!(group1_found && group2_found);
+        assert_eq!(batch.get(0, 0).unwrap(), Value::Int64(1));
+        assert_eq!(batch.get(0, 1).unwrap(), Value::Int64(21)); // Sum of 1-6
+        assert_eq!(batch.get(1, 0).unwrap(), Value::Int64(2));
+        assert_eq!(batch.get(1, 1).unwrap(), Value::Int64(10)); // Sum of 1-4
 
         group_by.close().unwrap();
     }
@@ -3759,23 +12096,110 @@ mod tests {
         assert_eq!(batch.row_count(), 2);
         assert_eq!(batch.column_count(), 3); // group + min + max
 
-        // Check group 1
-        for i in 0..batch.row_count() {
-            let group = batch.get(i, 0).unwrap();
-            let min = batch.get(i, 1).unwrap();
-            let max = batch.get(i, 2).unwrap();
-            if group == Value::Int64(1) {
-                assert_eq!(min, Value::Int64(5));
-                assert_eq!(max, Value::Int64(9));
-            } else if group == Value::Int64(2) {
-                assert_eq!(min, Value::Int64(1));
-                assert_eq!(max, Value::Int64(3));
+        // Group 1, then group 2
+        for (row, expected) in [[1, 5, 9], [2, 1, 3]].into_iter().enumerate() {
+            for (col, value) in expected.into_iter().enumerate() {
+                assert_eq!(batch.get(row, col).unwrap(), Value::Int64(value));
             }
         }
 
         group_by.close().unwrap();
     }
 
+    #[test]
+    fn test_group_by_min_max_strings() {
+        let mut table = Table::new("test".to_string());
+        let mut group_col = IntColumn::new();
+        let mut name_col = StringColumn::new();
+        for (group, name) in [
+            (1, Some("pear")),
+            (1, None),
+            (1, Some("apple")),
+            (2, None),
+            (1, Some("Zucchini")),
+        ] {
+            group_col.push_value(Value::Int64(group)).unwrap();
+            name_col
+                .push_value(name.map_or(Value::Null, |name| Value::String(name.to_string())))
+                .unwrap();
+        }
+        table
+            .add_column("group".to_string(), Box::new(group_col))
+            .unwrap();
+        table
+            .add_column("name".to_string(), Box::new(name_col))
+            .unwrap();
+
+        let mut group_by = GroupBy::new(
+            Box::new(TableScan::new(table)),
+            vec![0],
+            vec![1, 1],
+            vec![
+                Box::new(MinAggregate::new(DataType::String)),
+                Box::new(MaxAggregate::new(DataType::String)),
+            ],
+        );
+        group_by.open().unwrap();
+        let batch = group_by.next_batch().unwrap().unwrap();
+        assert_eq!(batch.row_count(), 2);
+        assert_eq!(
+            batch.get(0, 1).unwrap(),
+            Value::String("Zucchini".to_string())
+        );
+        assert_eq!(batch.get(0, 2).unwrap(), Value::String("pear".to_string()));
+
+        // A group of only NULLs has a NULL minimum, not an empty string
+        assert_eq!(batch.get(1, 1).unwrap(), Value::Null);
+        assert_eq!(batch.get(1, 2).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_group_by_rejects_result_of_wrong_type() {
+        /// Claims to produce strings but returns an integer
+        #[derive(Debug, Clone)]
+        struct Mislabeled;
+
+        impl AggregateFunction for Mislabeled {
+            fn update(&mut self, _value: Option<Value>) -> crate::error::Result<()> {
+                Ok(())
+            }
+            fn update_batch(&mut self, _values: &[Option<Value>]) -> crate::error::Result<()> {
+                Ok(())
+            }
+            fn result(&self) -> Option<Value> {
+                Some(Value::Int64(0))
+            }
+            fn reset(&mut self) {}
+            fn data_type(&self) -> DataType {
+                DataType::String
+            }
+            fn clone_box(&self) -> Box<dyn AggregateFunction> {
+                Box::new(self.clone())
+            }
+        }
+
+        let mut table = Table::new("test".to_string());
+        let mut col = IntColumn::new();
+        col.push_value(Value::Int64(1)).unwrap();
+        table.add_column("a".to_string(), Box::new(col)).unwrap();
+
+        let mut group_by = GroupBy::new(
+            Box::new(TableScan::new(table)),
+            vec![0],
+            vec![0],
+            vec![Box::new(Mislabeled)],
+        );
+        group_by.open().unwrap();
+        match group_by.next_batch() {
+            Err(ExecutionError::TypeMismatch {
+                expected: DataType::String,
+                actual: DataType::Int64,
+                column: Some(_),
+            }) => {}
+            other => panic!("Expected a type mismatch, got {:?}", other.map(|_| ())),
+        }
+    }
+
     #[test]
     fn test_group_by_avg_aggregate() {
         let mut table = Table::new("test".to_string());
@@ -3819,15 +12243,10 @@ mod tests {
         assert_eq!(batch.row_count(), 2);
 
         // Check averages: (10+20+30)/3=20, (40+50+60)/3=50
-        for i in 0..batch.row_count() {
-            let group = batch.get(i, 0).unwrap();
-            let avg = batch.get(i, 1).unwrap();
-            if group == Value::Int64(1) {
-                assert_eq!(avg, Value::Float64(20.0));
-            } else if group == Value::Int64(2) {
-                assert_eq!(avg, Value::Float64(50.0));
-            }
-        }
+        assert_eq!(batch.get(0, 0).unwrap(), Value::Int64(1));
+        assert_eq!(batch.get(0, 1).unwrap(), Value::Float64(20.0));
+        assert_eq!(batch.get(1, 0).unwrap(), Value::Int64(2));
+        assert_eq!(batch.get(1, 1).unwrap(), Value::Float64(50.0));
 
         group_by.close().unwrap();
     }
@@ -3885,6 +12304,16 @@ mod tests {
         let column_names = group_by.column_names().unwrap();
         assert_eq!(column_names, vec!["col1", "col2", "agg_0"]);
 
+        // Groups in first-seen order: values 1+3, 2+4, 5+7, 6+8
+        for (row, expected) in [[1, 0, 4], [1, 1, 6], [2, 0, 12], [2, 1, 14]]
+            .into_iter()
+            .enumerate()
+        {
+            for (col, value) in expected.into_iter().enumerate() {
+                assert_eq!(batch.get(row, col).unwrap(), Value::Int64(value));
+            }
+        }
+
         group_by.close().unwrap();
     }
 
@@ -3915,6 +12344,35 @@ mod tests {
         group_by.close().unwrap();
     }
 
+    #[test]
+    fn test_global_aggregate_of_empty_input() {
+        let mut table = Table::new("empty".to_string());
+        table
+            .add_column("value".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+
+        let mut group_by = GroupBy::new(
+            Box::new(TableScan::new(table)),
+            vec![],
+            vec![0, 0, 0],
+            vec![
+                Box::new(CountAggregate::new(DataType::Int64)),
+                Box::new(SumAggregate::new(DataType::Int64).unwrap()),
+                Box::new(MinAggregate::new(DataType::Int64)),
+            ],
+        );
+
+        // No GROUP BY columns: one row built from the empty aggregates
+        group_by.open().unwrap();
+        let batch = group_by.next_batch().unwrap().unwrap();
+        assert_eq!(batch.row_count(), 1);
+        assert_eq!(batch.get(0, 0).unwrap(), Value::Int64(0));
+        assert_eq!(batch.get(0, 1).unwrap(), Value::Int64(0));
+        assert_eq!(batch.get(0, 2).unwrap(), Value::Null);
+        assert!(group_by.next_batch().unwrap().is_none());
+        group_by.close().unwrap();
+    }
+
     #[test]
     fn test_group_by_single_group() {
         let mut table = Table::new("test".to_string());
@@ -3955,6 +12413,51 @@ mod tests {
         group_by.close().unwrap();
     }
 
+    #[test]
+    fn test_group_by_keeps_state_per_group_not_rows() {
+        // A million rows streamed lazily into ten groups
+        let rows = VirtualTable::new(
+            "numbers".to_string(),
+            vec![
+                ("group".to_string(), DataType::Int64),
+                ("n".to_string(), DataType::Int64),
+            ],
+            || Box::new((0..1_000_000).map(|n| vec![Value::Int64(n % 10), Value::Int64(n)])),
+        )
+        .unwrap();
+
+        let mut group_by = GroupBy::new(
+            Box::new(VirtualScan::new(rows)),
+            vec![0],
+            vec![1, 1],
+            vec![
+                Box::new(CountAggregate::new(DataType::Int64)),
+                Box::new(SumAggregate::new(DataType::Int64).unwrap()),
+            ],
+        );
+        group_by.open().unwrap();
+
+        // Only one set of aggregate states per group is retained
+        let groups = group_by.grouped_data.as_ref().unwrap();
+        assert_eq!(groups.iter().count(), 10);
+        assert!(groups.iter().all(|(_, states)| states.len() == 2));
+
+        let batch = group_by.next_batch().unwrap().unwrap();
+        assert_eq!(batch.row_count(), 10);
+        for row in 0..10 {
+            // Groups 0..10 in first-seen order
+            let group = row as i64;
+            assert_eq!(batch.get(row, 0).unwrap(), Value::Int64(group));
+            // Rows group, group + 10, ..., group + 999_990
+            assert_eq!(batch.get(row, 1).unwrap(), Value::Int64(100_000));
+            assert_eq!(
+                batch.get(row, 2).unwrap(),
+                Value::Int64(100_000 * group + 10 * (99_999 * 100_000 / 2))
+            );
+        }
+        group_by.close().unwrap();
+    }
+
     #[test]
     fn test_group_by_invalid_group_by_column_index() {
         let table = create_test_table();
@@ -4013,6 +12516,92 @@ mod tests {
         assert!(matches!(result, Err(ExecutionError::Custom(_))));
     }
 
+    #[test]
+    fn test_group_by_aggregate_error_names_its_column() {
+        // SUM set up for Int64 is fed the String name column
+        let mut group_by = GroupBy::new(
+            Box::new(TableScan::new(create_test_table())),
+            vec![0],
+            vec![1],
+            vec![Box::new(SumAggregate::new(DataType::Int64).unwrap())],
+        );
+
+        match group_by.open() {
+            Err(ExecutionError::AggregateError { column, source }) => {
+                assert_eq!(column, "name");
+                assert!(
+                    matches!(*source, crate::error::DatabaseError::TypeError(_)),
+                    "{:?}",
+                    source
+                );
+            }
+            other => panic!("expected an aggregate error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_blocking_operators_wrap_child_errors() {
+        let wrong_type = vec![vec![Value::String("x".to_string()), Value::Int64(1)]];
+        let mut sort = Sort::new(
+            Box::new(VirtualScan::new(virtual_pairs_table(wrong_type))),
+            vec![0],
+            vec![SortDirection::Ascending],
+        );
+
+        let err = sort.open().unwrap_err();
+        assert!(
+            matches!(err, ExecutionError::ChildOperatorError(_)),
+            "{:?}",
+            err
+        );
+        assert!(matches!(
+            err.root_cause(),
+            ExecutionError::TypeMismatch { column: Some(column), .. } if column == "pairs.n"
+        ));
+        // Wrapping does not change the message
+        assert_eq!(err.to_string(), err.root_cause().to_string());
+
+        // Cancellation belongs to the query, not the input, and is not wrapped
+        assert!(matches!(
+            ExecutionError::from_child(ExecutionError::Cancelled),
+            ExecutionError::Cancelled
+        ));
+    }
+
+    #[test]
+    fn test_database_error_conversions_keep_structure() {
+        use crate::error::DatabaseError;
+
+        // An execution error survives a round trip through DatabaseError
+        let err = DatabaseError::from(ExecutionError::InvalidColumnIndex { index: 4, count: 2 });
+        assert!(matches!(
+            ExecutionError::from(err),
+            ExecutionError::InvalidColumnIndex { index: 4, count: 2 }
+        ));
+
+        assert!(matches!(
+            ExecutionError::from(DatabaseError::column_error("Type mismatch")),
+            ExecutionError::ColumnConversion(msg) if msg == "Type mismatch"
+        ));
+        assert!(matches!(
+            ExecutionError::from(DatabaseError::parser_error("Unexpected token")),
+            ExecutionError::Custom(_)
+        ));
+
+        // A value of the wrong type pushed into a computed column
+        let mut column = IntColumn::new();
+        let err = ExecutionError::from(
+            column
+                .push_value(Value::String("x".to_string()))
+                .unwrap_err(),
+        );
+        assert!(
+            matches!(err, ExecutionError::ColumnConversion(_)),
+            "{:?}",
+            err
+        );
+    }
+
     #[test]
     fn test_group_by_lifecycle() {
         let table = create_test_table();
@@ -4071,10 +12660,14 @@ mod tests {
         group_by.open().unwrap();
 
         let schema = group_by.schema().unwrap();
-        assert_eq!(schema.len(), 3);
-        assert_eq!(schema.get("name"), Some(&DataType::String));
-        assert_eq!(schema.get("age"), Some(&DataType::Float64));
-        assert_eq!(schema.get("agg_0"), Some(&DataType::Int64));
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("name", DataType::String),
+                Field::new("age", DataType::Float64),
+                Field::new("agg_0", DataType::Int64),
+            ]
+        );
 
         group_by.close().unwrap();
     }
@@ -4128,6 +12721,169 @@ mod tests {
         group_by.close().unwrap();
     }
 
+    // HashJoin Operator Tests
+
+    /// Collects (name, amount) from a join of the test table with orders.
+    fn join_rows(join: &mut HashJoin) -> Vec<(String, f64)> {
+        let mut rows = Vec::new();
+        while let Some(batch) = join.next_batch().unwrap() {
+            for row in 0..batch.row_count() {
+                let name = batch.get(row, 1).unwrap().to_string();
+                let Value::Float64(amount) = batch.get(row, 4).unwrap() else {
+                    panic!("amount should be a float");
+                };
+                rows.push((name, amount));
+            }
+        }
+        rows.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        rows
+    }
+
+    #[test]
+    fn test_hash_join_matches_keys() {
+        let mut join = HashJoin::new(
+            Box::new(TableScan::new(create_test_table())),
+            Box::new(TableScan::new(create_orders_table())),
+            0,
+            0,
+        );
+        join.open().unwrap();
+
+        assert_eq!(
+            join.column_names().unwrap(),
+            vec!["id", "name", "age", "user_id", "amount"]
+        );
+        assert_eq!(
+            join.schema().unwrap().data_type("amount"),
+            Some(DataType::Float64)
+        );
+
+        // Bob has two orders, Eve none, and the NULL and unknown keys match no one
+        assert_eq!(
+            join_rows(&mut join),
+            vec![
+                ("Alice".to_string(), 10.0),
+                ("Bob".to_string(), 20.0),
+                ("Bob".to_string(), 25.0),
+                ("David".to_string(), 40.0),
+            ]
+        );
+        join.close().unwrap();
+        assert!(!join.is_open());
+    }
+
+    #[test]
+    fn test_hash_join_build_side_does_not_change_result() {
+        let mut build_right = HashJoin::new(
+            Box::new(TableScan::new(create_test_table())),
+            Box::new(TableScan::new(create_orders_table())),
+            0,
+            0,
+        );
+        let mut build_left = HashJoin::new(
+            Box::new(TableScan::new(create_test_table())),
+            Box::new(TableScan::new(create_orders_table())),
+            0,
+            0,
+        )
+        .with_build_side(JoinSide::Left);
+        build_right.open().unwrap();
+        build_left.open().unwrap();
+
+        assert_eq!(
+            build_left.column_names().unwrap(),
+            build_right.column_names().unwrap()
+        );
+        assert_eq!(join_rows(&mut build_left), join_rows(&mut build_right));
+    }
+
+    #[test]
+    fn test_hash_join_rejects_colliding_columns_and_key_types() {
+        let mut join = HashJoin::new(
+            Box::new(TableScan::new(create_test_table())),
+            Box::new(TableScan::new(create_test_table())),
+            0,
+            0,
+        );
+        assert!(matches!(
+            join.open(),
+            Err(ExecutionError::SchemaMismatch(msg)) if msg.contains("'id'")
+        ));
+
+        // name (String) against user_id (Int64)
+        let mut join = HashJoin::new(
+            Box::new(TableScan::new(create_test_table())),
+            Box::new(TableScan::new(create_orders_table())),
+            1,
+            0,
+        );
+        assert!(matches!(
+            join.open(),
+            Err(ExecutionError::SchemaMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_hash_join_int_key_matches_float_key() {
+        // user_id (Int64) against value (Float64, i * 1.5): only 9 == 9.0
+        let mut join = HashJoin::new(
+            Box::new(TableScan::new(create_orders_table())),
+            Box::new(TableScan::new(create_test_large_table(10))),
+            0,
+            1,
+        );
+        join.open().unwrap();
+
+        let batch = join.next_batch().unwrap().unwrap();
+        assert_eq!(batch.row_count(), 1);
+        assert_eq!(batch.get(0, 0).unwrap(), Value::Int64(9));
+        assert_eq!(batch.get(0, 2).unwrap(), Value::Int64(6));
+        assert!(join.next_batch().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_profiled_operator_counts_rows_per_operator() {
+        let scan = Box::new(TableScan::new(create_test_table()).with_batch_size(2));
+        let predicate = Arc::new(BinaryComparison::new(
+            2,
+            ComparisonOp::GreaterThan,
+            Value::Float64(30.0),
+        ));
+        let mut plan = ProfiledOperator::wrap_plan(Box::new(Filter::new(scan, predicate)));
+
+        plan.open().unwrap();
+        let mut ids = Vec::new();
+        while let Some(batch) = plan.next_batch().unwrap() {
+            for row in 0..batch.row_count() {
+                ids.push(batch.get(row, 0).unwrap());
+            }
+        }
+        plan.close().unwrap();
+
+        // Profiling doesn't change the rows
+        assert_eq!(ids, vec![Value::Int64(3), Value::Int64(4), Value::Int64(5)]);
+
+        let filter_stats = plan.stats().unwrap();
+        assert_eq!(filter_stats.rows, 3);
+        let scan_stats = plan.children()[0].stats().unwrap();
+        assert_eq!(scan_stats.rows, 5);
+        assert_eq!(scan_stats.batches, 3);
+
+        let profile = render_profile(plan.as_ref());
+        let lines: Vec<&str> = profile.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(
+            lines[0].starts_with("Filter(age > 30.0) rows=3 input=5 time="),
+            "{}",
+            profile
+        );
+        assert!(
+            lines[1].starts_with("  TableScan(test: id, name, age) rows=5 time="),
+            "{}",
+            profile
+        );
+    }
+
     // ============================================================================
     // HELPER FUNCTIONS
     // ============================================================================
@@ -4180,6 +12936,32 @@ mod tests {
         table
     }
 
+    // Helper function to create an orders table keyed by the test table's ids
+    fn create_orders_table() -> Table {
+        let mut table = Table::new("orders".to_string());
+
+        // Add user_id column: Bob twice, Eve never, one unknown user and one NULL
+        let mut user_id_col = IntColumn::new();
+        for id in [1, 2, 2, 4, 9] {
+            user_id_col.push_value(Value::Int64(id)).unwrap();
+        }
+        user_id_col.push_null();
+        table
+            .add_column("user_id".to_string(), Box::new(user_id_col))
+            .unwrap();
+
+        // Add amount column
+        let mut amount_col = FloatColumn::new();
+        for amount in [10.0, 20.0, 25.0, 40.0, 90.0, 60.0] {
+            amount_col.push_value(Value::Float64(amount)).unwrap();
+        }
+        table
+            .add_column("amount".to_string(), Box::new(amount_col))
+            .unwrap();
+
+        table
+    }
+
     // Helper function to create a large test table
     fn create_test_large_table(row_count: usize) -> Table {
         let mut table = Table::new("large_test".to_string());
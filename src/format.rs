@@ -0,0 +1,453 @@
+//! # Value Formatting Module
+//!
+//! This module turns values into display strings according to a column's
+//! display hint. Display hints are metadata kept alongside a column (see
+//! `Table::set_display_hint`); they never change how a value is stored, only
+//! how the REPL printer and opt-in formatted exports render it.
+//!
+//! All output is locale independent: `.` is always the decimal separator and
+//! `,` is always the thousands separator.
+//!
+//! Columns without a hint are rendered with [`FormatOptions`]: by default
+//! floats print as the shortest text that parses back to the same value, and
+//! the REPL's `SET float_precision = N` switches them to `N` fixed decimals.
+//!
+//! ## Hint Syntax
+//!
+//! - `money` - two decimals with thousands separators (`50,000.00`)
+//! - `percent` / `percent(1)` - the value is a 0-1 fraction (`0.125` -> `12.50%`)
+//! - `percent(100)` - the value is already 0-100 (`12.5` -> `12.50%`)
+//! - `thousands` - thousands separators, decimals left as-is (`1,234.5`)
+//! - a printf-like pattern with one `%[,][.N](f|d)` conversion, optional
+//!   literal text around it and `%%` for a literal percent sign
+//!   (`$%,.2f`, `%.1f%%`, `%,d items`)
+
+use crate::error::{DatabaseError, Result};
+use crate::types::Value;
+use std::fmt;
+
+/// How a percent hint interprets the stored value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PercentScale {
+    /// The value is a fraction where 1.0 means 100%
+    Fraction,
+    /// The value is already expressed out of 100
+    Hundred,
+}
+
+/// A column display hint, separate from the column's storage type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisplayHint {
+    /// Two decimals with thousands separators
+    Money,
+    /// Percentage with two decimals and a `%` suffix
+    Percent(PercentScale),
+    /// Thousands separators, decimals unchanged
+    Thousands,
+    /// printf-like pattern
+    Pattern(NumberPattern),
+}
+
+/// A parsed printf-like pattern: `prefix %[,][.N](f|d) suffix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberPattern {
+    /// The pattern as written, kept for display
+    source: String,
+    /// Literal text before the conversion (with `%%` unescaped)
+    prefix: String,
+    /// Literal text after the conversion (with `%%` unescaped)
+    suffix: String,
+    /// Whether to group the integer digits with `,`
+    grouping: bool,
+    /// Digits after the decimal point
+    decimals: usize,
+}
+
+impl DisplayHint {
+    /// Parses a hint from its textual form (see the module docs).
+    ///
+    /// # Arguments
+    ///
+    /// * `spec` - The hint text, e.g. `money` or `%,.2f`
+    ///
+    /// # Returns
+    ///
+    /// The parsed hint, or a parser error describing what is wrong with it
+    pub fn parse(spec: &str) -> Result<Self> {
+        let trimmed = spec.trim();
+        let lower = trimmed.to_lowercase();
+
+        match lower.as_str() {
+            "money" => return Ok(DisplayHint::Money),
+            "thousands" => return Ok(DisplayHint::Thousands),
+            "percent" | "percent(1)" => return Ok(DisplayHint::Percent(PercentScale::Fraction)),
+            "percent(100)" => return Ok(DisplayHint::Percent(PercentScale::Hundred)),
+            _ => {}
+        }
+
+        if lower.starts_with("percent(") {
+            return Err(DatabaseError::parser_error(format!(
+                "Invalid percent scale in '{}': use percent(1) for 0-1 fractions or percent(100) for 0-100 values",
+                trimmed
+            )));
+        }
+
+        if trimmed.contains('%') {
+            return NumberPattern::parse(trimmed).map(DisplayHint::Pattern);
+        }
+
+        Err(DatabaseError::parser_error(format!(
+            "Unknown display format '{}'. Use money, percent, percent(100), thousands, or a pattern such as '%,.2f'",
+            trimmed
+        )))
+    }
+
+    /// Formats a value according to this hint.
+    ///
+    /// Numeric values are formatted; strings, NaN and infinities are
+    /// rendered unchanged.
+    pub fn format(&self, value: &Value) -> String {
+        let number = match value {
+            Value::Int64(v) => Number::Int(*v),
+            Value::Float64(v) if v.is_finite() => Number::Float(*v),
+            other => return other.to_string(),
+        };
+
+        match self {
+            DisplayHint::Money => format_fixed(number, 2, true),
+            DisplayHint::Percent(scale) => {
+                let scaled = match (scale, number) {
+                    (PercentScale::Hundred, n) => n,
+                    (PercentScale::Fraction, Number::Int(v)) => Number::Float(v as f64 * 100.0),
+                    (PercentScale::Fraction, Number::Float(v)) => Number::Float(v * 100.0),
+                };
+                format!("{}%", format_fixed(scaled, 2, true))
+            }
+            DisplayHint::Thousands => match number {
+                Number::Int(v) => group_signed(v < 0, &v.unsigned_abs().to_string(), ""),
+                Number::Float(v) => {
+                    let text = v.abs().to_string();
+                    let (int_part, frac_part) = match text.split_once('.') {
+                        Some((int_part, frac)) => (int_part.to_string(), format!(".{}", frac)),
+                        None => (text, String::new()),
+                    };
+                    group_signed(v < 0.0, &int_part, &frac_part)
+                }
+            },
+            DisplayHint::Pattern(pattern) => pattern.format(number),
+        }
+    }
+}
+
+impl fmt::Display for DisplayHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayHint::Money => write!(f, "money"),
+            DisplayHint::Percent(PercentScale::Fraction) => write!(f, "percent(1)"),
+            DisplayHint::Percent(PercentScale::Hundred) => write!(f, "percent(100)"),
+            DisplayHint::Thousands => write!(f, "thousands"),
+            DisplayHint::Pattern(pattern) => write!(f, "{}", pattern.source),
+        }
+    }
+}
+
+impl NumberPattern {
+    fn parse(source: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            DatabaseError::parser_error(format!("Invalid format pattern '{}': {}", source, reason))
+        };
+
+        let mut prefix = String::new();
+        let mut suffix = String::new();
+        let mut spec: Option<(bool, usize)> = None;
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                if spec.is_some() {
+                    suffix.push(c);
+                } else {
+                    prefix.push(c);
+                }
+                continue;
+            }
+
+            if chars.peek() == Some(&'%') {
+                chars.next();
+                if spec.is_some() {
+                    suffix.push('%');
+                } else {
+                    prefix.push('%');
+                }
+                continue;
+            }
+
+            if spec.is_some() {
+                return Err(invalid("only one conversion is allowed"));
+            }
+
+            let grouping = chars.next_if_eq(&',').is_some();
+            let mut precision: Option<usize> = None;
+            if chars.next_if_eq(&'.').is_some() {
+                let mut digits = String::new();
+                while let Some(d) = chars.next_if(|c| c.is_ascii_digit()) {
+                    digits.push(d);
+                }
+                precision = Some(
+                    digits
+                        .parse()
+                        .ok()
+                        .filter(|&p| p <= 20)
+                        .ok_or_else(|| invalid("precision must be a number from 0 to 20"))?,
+                );
+            }
+
+            match chars.next() {
+                // printf's default precision for %f is 6
+                Some('f') => spec = Some((grouping, precision.unwrap_or(6))),
+                Some('d') if precision.is_none() => spec = Some((grouping, 0)),
+                Some('d') => return Err(invalid("%d does not take a precision")),
+                _ => return Err(invalid("expected a conversion like %f, %.2f, %,d")),
+            }
+        }
+
+        let (grouping, decimals) = spec.ok_or_else(|| invalid("no %f or %d conversion"))?;
+        Ok(Self {
+            source: source.to_string(),
+            prefix,
+            suffix,
+            grouping,
+            decimals,
+        })
+    }
+
+    fn format(&self, number: Number) -> String {
+        format!(
+            "{}{}{}",
+            self.prefix,
+            format_fixed(number, self.decimals, self.grouping),
+            self.suffix
+        )
+    }
+}
+
+// ============================================================================
+// NUMBER RENDERING
+// ============================================================================
+
+/// A finite numeric value, kept exact for integers.
+#[derive(Clone, Copy)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+/// Renders a number with a fixed number of decimals.
+fn format_fixed(number: Number, decimals: usize, grouping: bool) -> String {
+    let (negative, int_part, frac_part) = match number {
+        // Integers are rendered from their digits so large values stay exact
+        Number::Int(v) => {
+            let frac = if decimals > 0 {
+                format!(".{}", "0".repeat(decimals))
+            } else {
+                String::new()
+            };
+            (v < 0, v.unsigned_abs().to_string(), frac)
+        }
+        Number::Float(v) => {
+            let text = format!("{:.*}", decimals, v.abs());
+            let (int_part, frac) = match text.split_once('.') {
+                Some((int_part, frac)) => (int_part.to_string(), format!(".{}", frac)),
+                None => (text, String::new()),
+            };
+            (v < 0.0, int_part, frac)
+        }
+    };
+
+    // Values that round to zero print without a sign ("0.00", not "-0.00")
+    let is_zero = int_part
+        .chars()
+        .chain(frac_part.chars())
+        .all(|c| c == '0' || c == '.');
+    let negative = negative && !is_zero;
+
+    if grouping {
+        group_signed(negative, &int_part, &frac_part)
+    } else {
+        format!(
+            "{}{}{}",
+            if negative { "-" } else { "" },
+            int_part,
+            frac_part
+        )
+    }
+}
+
+/// Joins a sign, integer digits grouped in threes, and a fractional part.
+fn group_signed(negative: bool, int_digits: &str, frac_part: &str) -> String {
+    let mut grouped = String::with_capacity(int_digits.len() + int_digits.len() / 3 + 1);
+    for (i, digit) in int_digits.chars().enumerate() {
+        if i > 0 && (int_digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    format!(
+        "{}{}{}",
+        if negative { "-" } else { "" },
+        grouped,
+        frac_part
+    )
+}
+
+/// Options for rendering values that have no display hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+    /// Fixed number of decimals for floats; `None` prints the shortest text
+    /// that parses back to the same value
+    pub float_precision: Option<usize>,
+}
+
+/// Formats a value with an optional hint; without one the value is rendered
+/// with `options` (see [`Value::format`]).
+pub fn format_value(value: &Value, hint: Option<&DisplayHint>, options: &FormatOptions) -> String {
+    match hint {
+        Some(hint) => hint.format(value),
+        None => value.format(options),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hint(spec: &str) -> DisplayHint {
+        DisplayHint::parse(spec).unwrap()
+    }
+
+    #[test]
+    fn test_parse_named_hints() {
+        assert_eq!(hint("money"), DisplayHint::Money);
+        assert_eq!(hint(" MONEY "), DisplayHint::Money);
+        assert_eq!(hint("thousands"), DisplayHint::Thousands);
+        assert_eq!(
+            hint("percent"),
+            DisplayHint::Percent(PercentScale::Fraction)
+        );
+        assert_eq!(
+            hint("percent(1)"),
+            DisplayHint::Percent(PercentScale::Fraction)
+        );
+        assert_eq!(
+            hint("percent(100)"),
+            DisplayHint::Percent(PercentScale::Hundred)
+        );
+
+        // Display gives back a spec that parses to the same hint
+        for spec in ["money", "percent(1)", "percent(100)", "thousands", "$%,.2f"] {
+            assert_eq!(hint(&hint(spec).to_string()), hint(spec));
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_hints() {
+        assert!(DisplayHint::parse("currency").is_err());
+        assert!(DisplayHint::parse("percent(50)").is_err());
+        assert!(DisplayHint::parse("%x").is_err());
+        assert!(DisplayHint::parse("%.2d").is_err());
+        assert!(DisplayHint::parse("%d and %d").is_err());
+        assert!(DisplayHint::parse("100%%").is_err());
+    }
+
+    #[test]
+    fn test_money() {
+        let money = hint("money");
+        assert_eq!(money.format(&Value::Int64(50000)), "50,000.00");
+        assert_eq!(money.format(&Value::Float64(1234.567)), "1,234.57");
+        assert_eq!(money.format(&Value::Float64(-1234.5)), "-1,234.50");
+        assert_eq!(money.format(&Value::Int64(-999)), "-999.00");
+        assert_eq!(money.format(&Value::Int64(0)), "0.00");
+        assert_eq!(money.format(&Value::Float64(-0.0)), "0.00");
+        assert_eq!(money.format(&Value::Float64(-0.001)), "0.00");
+    }
+
+    #[test]
+    fn test_large_magnitudes() {
+        assert_eq!(
+            hint("money").format(&Value::Int64(i64::MAX)),
+            "9,223,372,036,854,775,807.00"
+        );
+        assert_eq!(
+            hint("thousands").format(&Value::Int64(i64::MIN)),
+            "-9,223,372,036,854,775,808"
+        );
+        assert_eq!(
+            hint("money").format(&Value::Float64(1e21)),
+            "1,000,000,000,000,000,000,000.00"
+        );
+    }
+
+    #[test]
+    fn test_percent_scales() {
+        let fraction = hint("percent(1)");
+        let hundred = hint("percent(100)");
+
+        assert_eq!(fraction.format(&Value::Float64(0.125)), "12.50%");
+        assert_eq!(hundred.format(&Value::Float64(12.5)), "12.50%");
+        assert_eq!(fraction.format(&Value::Int64(1)), "100.00%");
+        assert_eq!(hundred.format(&Value::Int64(1)), "1.00%");
+        assert_eq!(fraction.format(&Value::Float64(-0.05)), "-5.00%");
+        assert_eq!(fraction.format(&Value::Float64(0.0)), "0.00%");
+        assert_eq!(fraction.format(&Value::Float64(12.5)), "1,250.00%");
+    }
+
+    #[test]
+    fn test_thousands() {
+        let thousands = hint("thousands");
+        assert_eq!(thousands.format(&Value::Int64(1234567)), "1,234,567");
+        assert_eq!(thousands.format(&Value::Int64(999)), "999");
+        assert_eq!(thousands.format(&Value::Int64(-1000)), "-1,000");
+        assert_eq!(thousands.format(&Value::Float64(1234.5)), "1,234.5");
+        assert_eq!(thousands.format(&Value::Int64(0)), "0");
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(hint("$%,.2f").format(&Value::Int64(50000)), "$50,000.00");
+        assert_eq!(hint("%.1f%%").format(&Value::Float64(12.34)), "12.3%");
+        assert_eq!(
+            hint("%,d items").format(&Value::Float64(1234.6)),
+            "1,235 items"
+        );
+        assert_eq!(hint("%f").format(&Value::Float64(1.5)), "1.500000");
+        assert_eq!(hint("%.0f").format(&Value::Float64(-2.5)), "-2");
+    }
+
+    #[test]
+    fn test_non_numeric_values_pass_through() {
+        let money = hint("money");
+        assert_eq!(money.format(&Value::String("n/a".to_string())), "n/a");
+        assert_eq!(money.format(&Value::Float64(f64::NAN)), "NaN");
+        assert_eq!(
+            format_value(&Value::Int64(5), None, &FormatOptions::default()),
+            "5"
+        );
+    }
+
+    #[test]
+    fn test_hints_take_precedence_over_float_precision() {
+        let options = FormatOptions {
+            float_precision: Some(1),
+        };
+        assert_eq!(format_value(&Value::Float64(2.25), None, &options), "2.2");
+        assert_eq!(
+            format_value(&Value::Float64(2.25), Some(&hint("money")), &options),
+            "2.25"
+        );
+    }
+}
@@ -7,7 +7,16 @@
 //! - **CSV Parsing**: Reads CSV files and parses headers and data rows
 //! - **Type Inference**: Automatically detects column data types (Int64, Float64, String)
 //! - **Columnar Conversion**: Transforms row-based CSV data into columnar format
-//! - **Error Handling**: Graceful handling of malformed CSVs and type conversion errors
+//! - **Error Handling**: A bad row fails the load with an [`IngestError`]
+//!   naming its file, line, column, and text; `CsvOptions::max_errors` skips
+//!   up to that many bad rows instead and lists them in a [`LoadReport`]
+//! - **Dialects**: `CsvOptions` selects the delimiter, quote character, header
+//!   row, NULL token, and declared column types (`load_csv_with_options`)
+//! - **Header Repair**: repeated header names become `name_1`, `name_2`, ...
+//!   and empty ones `col_<index>`, listed in the [`LoadReport`]
+//! - **Compressed Input**: `.csv.gz` files are decompressed while reading (`gzip` feature)
+//! - **Incremental Loads**: `load_csv_append` adds a file's rows to an existing table
+//! - **CSV Export**: `write_batches_csv` writes query results back out as CSV
 //!
 //! ## Design Philosophy
 //!
@@ -15,8 +24,9 @@
 //! 1. Read and parse CSV file
 //! 2. Extract header row for column names
 //! 3. Analyze sample data to infer types for each column
-//! 4. Create appropriate column instances based on inferred types
-//! 5. Transpose row data into columns
+//! 4. Transpose row data into columns
+//! 5. Create appropriate column instances based on inferred types;
+//!    String columns with repeating values are dictionary-encoded
 //! 6. Build and return a Table
 //!
 //! ## Type Inference Strategy
@@ -26,9 +36,16 @@
 //! - **Float64**: If not integer, try to parse as float (with decimal point or scientific notation)
 //! - **String**: If neither numeric type works, treat as string
 //!
+//! A column takes the narrowest type that holds every value, widening along
+//! Int64 → Float64 → String (Boolean and Date columns widen straight to
+//! String). Every row is inspected unless `CsvOptions::sample_rows` limits
+//! inference to the first rows; a later value that does not fit then
+//! promotes its column while it is built, so a load never fails or truncates
+//! because of a value the sample did not cover.
+//!
 //! Empty values are handled specially:
 //! - They're ignored during type inference
-//! - When inserting, they're represented as appropriate "null" values for the column type
+//! - When inserting, they become NULLs (`Value::Null`)
 //!
 //! ## Usage Example
 //!
@@ -51,12 +68,15 @@
 //! ```
 
 use crate::catalog::Catalog;
-use crate::column::create_column;
-use crate::error::{DatabaseError, Result};
+use crate::column::{create_column, Column, DictStringColumn};
+use crate::error::{DatabaseError, IngestError, IngestErrorKind, Result};
+use crate::execution::Batch;
 use crate::table::Table;
-use crate::types::{DataType, Value};
+use crate::types::{parse_boolean, parse_date, DataType, Value};
+use std::collections::HashSet;
+use std::fmt;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 
 // ============================================================================
@@ -67,6 +87,9 @@ use std::path::Path;
 ///
 /// This function examines a sample of string values and determines the most
 /// appropriate data type. The inference follows these rules:
+/// - If every value is `true` or `false` (in any letter case), the type is
+///   Boolean
+/// - If every value is an ISO `YYYY-MM-DD` date, the type is Date
 /// - If any value cannot be parsed as a number, the type is String
 /// - If all numeric values can be parsed as integers without decimal points,
 ///   the type is Int64
@@ -103,6 +126,10 @@ pub fn infer_column_type(values: &[String]) -> DataType {
 
     let mut has_decimal = false;
     let mut has_non_numeric = false;
+    let mut has_boolean = false;
+    let mut has_non_boolean = false;
+    let mut has_date = false;
+    let mut has_non_date = false;
 
     for value in values {
         let trimmed = value.trim();
@@ -112,6 +139,18 @@ pub fn infer_column_type(values: &[String]) -> DataType {
             continue;
         }
 
+        if parse_boolean(trimmed).is_some() {
+            has_boolean = true;
+            continue;
+        }
+        has_non_boolean = true;
+
+        if parse_date(trimmed).is_some() {
+            has_date = true;
+            continue;
+        }
+        has_non_date = true;
+
         // Check if it's an integer (no decimal point, no scientific notation)
         if trimmed.parse::<i64>().is_ok() {
             continue;
@@ -128,8 +167,21 @@ pub fn infer_column_type(values: &[String]) -> DataType {
         break;
     }
 
-    // Determine the type based on our analysis
-    if has_non_numeric {
+    // Determine the type based on our analysis; booleans or dates mixed
+    // with anything else can only be kept as text
+    if has_boolean {
+        if has_non_boolean {
+            DataType::String
+        } else {
+            DataType::Boolean
+        }
+    } else if has_date {
+        if has_non_date {
+            DataType::String
+        } else {
+            DataType::Date
+        }
+    } else if has_non_numeric {
         DataType::String
     } else if has_decimal {
         DataType::Float64
@@ -138,10 +190,77 @@ pub fn infer_column_type(values: &[String]) -> DataType {
     }
 }
 
+/// Returns the narrowest type that holds both `a` and `b`.
+///
+/// Int64 widens to Float64, and any other pair of different types only fits
+/// in a String.
+fn widen_type(a: DataType, b: DataType) -> DataType {
+    match (a, b) {
+        _ if a == b => a,
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            DataType::Float64
+        }
+        _ => DataType::String,
+    }
+}
+
+/// Returns the type `value` would have on its own, as [`infer_column_type`]
+/// infers it.
+fn value_type(value: &str) -> DataType {
+    infer_column_type(&[value.to_string()])
+}
+
+/// Infers one DataType per header from the parsed data rows.
+///
+/// Each column's values are collected (rows that are too short simply don't
+/// contribute) and passed to [`infer_column_type`].
+fn infer_column_types(headers: &[String], rows: &[Vec<String>]) -> Vec<DataType> {
+    (0..headers.len())
+        .map(|col_idx| {
+            // Collect sample values for this column
+            let sample_values: Vec<String> = rows
+                .iter()
+                .filter_map(|row| row.get(col_idx).cloned())
+                .collect();
+
+            // Infer the type
+            infer_column_type(&sample_values)
+        })
+        .collect()
+}
+
+/// Infers the schema of a CSV file without building a table.
+///
+/// The file is parsed exactly as [`load_csv`] would parse it, so the reported
+/// types are the ones a subsequent load will produce.
+///
+/// # Arguments
+///
+/// * `path` - The path to the CSV file
+///
+/// # Returns
+///
+/// The column names paired with their inferred types, in header order
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::ingest::infer_schema;
+///
+/// for (name, data_type) in infer_schema("data/users.csv")? {
+///     println!("{}: {}", name, data_type);
+/// }
+/// ```
+pub fn infer_schema<P: AsRef<Path>>(path: P) -> Result<Vec<(String, DataType)>> {
+    let CsvFile { headers, rows, .. } = read_csv_file(path.as_ref(), &CsvOptions::default())?;
+    let column_types = infer_column_types(&headers, &rows);
+    Ok(headers.into_iter().zip(column_types).collect())
+}
+
 /// Parses a string value into the appropriate Value type.
 ///
-/// This function attempts to parse a string as the specified data type,
-/// falling back to string if the type conversion fails.
+/// This function attempts to parse a string as the specified data type.
+/// An empty (or all-whitespace) field is NULL, whatever the type.
 ///
 /// # Arguments
 ///
@@ -154,13 +273,9 @@ pub fn infer_column_type(values: &[String]) -> DataType {
 fn parse_value(value: &str, target_type: DataType) -> Result<Value> {
     let trimmed = value.trim();
 
-    // Handle empty values
+    // Empty fields are missing values
     if trimmed.is_empty() {
-        return match target_type {
-            DataType::Int64 => Ok(Value::Int64(0)),
-            DataType::Float64 => Ok(Value::Float64(0.0)),
-            DataType::String => Ok(Value::String(String::new())),
-        };
+        return Ok(Value::Null);
     }
 
     // Parse based on target type
@@ -172,6 +287,12 @@ fn parse_value(value: &str, target_type: DataType) -> Result<Value> {
             DatabaseError::type_error(format!("Failed to parse '{}' as Float64", trimmed))
         }),
         DataType::String => Ok(Value::String(trimmed.to_string())),
+        DataType::Boolean => parse_boolean(trimmed).map(Value::Boolean).ok_or_else(|| {
+            DatabaseError::type_error(format!("Failed to parse '{}' as Boolean", trimmed))
+        }),
+        DataType::Date => parse_date(trimmed).map(Value::Date).ok_or_else(|| {
+            DatabaseError::type_error(format!("Failed to parse '{}' as Date", trimmed))
+        }),
     }
 }
 
@@ -179,19 +300,192 @@ fn parse_value(value: &str, target_type: DataType) -> Result<Value> {
 // CSV PARSING
 // ============================================================================
 
+/// The first two bytes of every gzip stream (RFC 1952)
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Opens an input file for line-oriented reading.
+///
+/// Files ending in `.gz`, or starting with the gzip magic bytes, are wrapped
+/// in a streaming gzip decoder so the rest of the ingestion pipeline sees
+/// plain CSV text. Every loader goes through this helper, so they all accept
+/// compressed input.
+///
+/// # Returns
+///
+/// The reader, and whether the input is gzip-compressed
+fn open_input(path: &Path) -> Result<(Box<dyn BufRead>, bool)> {
+    let file = File::open(path).map_err(|e| {
+        DatabaseError::ingestion_error(format!("Failed to open file '{}': {}", path.display(), e))
+    })?;
+    let mut reader = BufReader::new(file);
+
+    let has_gz_extension = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"));
+    let has_gzip_magic = reader
+        .fill_buf()
+        .map(|buf| buf.starts_with(&GZIP_MAGIC))
+        .unwrap_or(false);
+
+    if !(has_gz_extension || has_gzip_magic) {
+        return Ok((Box::new(reader), false));
+    }
+
+    #[cfg(feature = "gzip")]
+    {
+        let decoder = flate2::bufread::MultiGzDecoder::new(reader);
+        Ok((Box::new(BufReader::new(decoder)), true))
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    {
+        Err(DatabaseError::ingestion_error(format!(
+            "'{}' is gzip-compressed, but this build was compiled without the `gzip` feature",
+            path.display()
+        )))
+    }
+}
+
+/// The dialect of a delimited text file.
+///
+/// The default is a comma-separated file with a header row and `"` quotes,
+/// in which only empty fields are NULL.
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::ingest::CsvOptions;
+///
+/// // A headerless, pipe-delimited export that writes NULL for missing values
+/// let options = CsvOptions {
+///     delimiter: b'|',
+///     has_header: false,
+///     null_token: Some("NULL".to_string()),
+///     ..CsvOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The byte separating fields, such as `b','` or `b'\t'`
+    pub delimiter: u8,
+    /// Whether the first record names the columns; if not, the columns are
+    /// named `col_0`, `col_1`, ...
+    pub has_header: bool,
+    /// The byte quoting fields that contain delimiters, quotes, or newlines
+    pub quote: u8,
+    /// A field that loads as NULL in addition to the empty field, such as
+    /// `NULL` or `\N`
+    pub null_token: Option<String>,
+    /// Number of leading rows used to infer column types, or None to
+    /// inspect every row. Later values that do not fit promote their column.
+    pub sample_rows: Option<usize>,
+    /// Types for named columns, used instead of inferring them. A field
+    /// that does not parse as its column's declared type makes its row bad.
+    pub column_types: Vec<(String, DataType)>,
+    /// Number of bad rows to skip before the load fails; with 0 the first
+    /// bad row fails it. Skipped rows are listed in the [`LoadReport`].
+    pub max_errors: usize,
+    /// Whether a header name that repeats is renamed (`name_1`, `name_2`,
+    /// ...) rather than failing the load. Renames are listed in the
+    /// [`LoadReport`].
+    pub rename_duplicate_headers: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            has_header: true,
+            quote: b'"',
+            null_token: None,
+            sample_rows: None,
+            column_types: Vec::new(),
+            max_errors: 0,
+            rename_duplicate_headers: true,
+        }
+    }
+}
+
+/// A header cell loaded under a different name, because it repeated an
+/// earlier name or was empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderRename {
+    /// The column's position in the file, from 0
+    pub index: usize,
+    /// The name in the header, possibly empty
+    pub original: String,
+    /// The name the column was loaded under
+    pub renamed: String,
+}
+
+impl fmt::Display for HeaderRename {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.original.trim().is_empty() {
+            write!(
+                f,
+                "column {} has no name in the header; loaded as '{}'",
+                self.index + 1,
+                self.renamed
+            )
+        } else {
+            write!(
+                f,
+                "column {} repeats the name '{}'; loaded as '{}'",
+                self.index + 1,
+                self.original,
+                self.renamed
+            )
+        }
+    }
+}
+
+/// The outcome of a load that succeeded, perhaps after skipping bad rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// Number of rows loaded into the table
+    pub rows_loaded: usize,
+    /// The rows that were skipped, in file order
+    pub errors: Vec<IngestError>,
+    /// The header cells loaded under a different name, in file order
+    pub renamed_columns: Vec<HeaderRename>,
+}
+
+impl LoadReport {
+    /// Number of rows skipped because they were bad.
+    pub fn rows_skipped(&self) -> usize {
+        self.errors.len()
+    }
+}
+
+/// The contents of a delimited file, as read by [`read_csv_file`].
+struct CsvFile {
+    /// The column names, unique and non-empty
+    headers: Vec<String>,
+    /// The header cells renamed to make them so
+    renames: Vec<HeaderRename>,
+    /// The fields of each data row
+    rows: Vec<Vec<String>>,
+    /// The 1-based line of the file on which each data row starts
+    lines: Vec<usize>,
+}
+
 /// Reads a CSV file and returns its headers and rows.
 ///
 /// This function reads the entire CSV file into memory, separating the header
-/// row from the data rows. It handles basic CSV parsing including quoted values.
+/// row from the data rows (or naming the columns `col_0`, `col_1`, ... when
+/// `options.has_header` is false). Header names are made unique as
+/// [`unique_headers`] describes. Fields matching `options.null_token` are
+/// returned empty, so they load as NULL. Gzip-compressed files are
+/// decompressed on the fly (see [`open_input`]).
 ///
 /// # Arguments
 ///
 /// * `path` - The path to the CSV file
+/// * `options` - The file's dialect
 ///
 /// # Returns
 ///
-/// A tuple of (headers, rows) where headers is a `Vec<String>` of column names
-/// and rows is a `Vec<Vec<String>>` of data rows
+/// The column names, the data rows, and the line on which each row starts
 ///
 /// # Errors
 ///
@@ -199,117 +493,231 @@ fn parse_value(value: &str, target_type: DataType) -> Result<Value> {
 /// - The file cannot be opened
 /// - The CSV cannot be parsed
 /// - The file is empty
-fn read_csv_file(path: &Path) -> Result<(Vec<String>, Vec<Vec<String>>)> {
-    // Open the file
-    let file = File::open(path).map_err(|e| {
-        DatabaseError::ingestion_error(format!("Failed to open file '{}': {}", path.display(), e))
-    })?;
-
-    let reader = BufReader::new(file);
-    let mut lines = reader.lines();
-
-    // Read the header row
-    let header_line = lines.next().ok_or_else(|| {
-        DatabaseError::ingestion_error(format!(
-            "CSV file '{}' is empty (no header row)",
-            path.display()
-        ))
-    })?;
-
-    let header_line = header_line.map_err(|e| {
-        DatabaseError::ingestion_error(format!(
-            "Failed to read header from '{}': {}",
-            path.display(),
-            e
-        ))
-    })?;
-
-    // Parse the header using csv crate for proper handling of quoted values
-    let headers = parse_csv_line(&header_line)?;
-
-    if headers.is_empty() {
-        return Err(DatabaseError::ingestion_error(format!(
-            "CSV file '{}' has empty header row",
-            path.display()
-        )));
-    }
-
-    // Read all data rows
-    let mut rows = Vec::new();
-    let mut row_num = 1; // Header is row 0, so data starts at row 1
-
-    for line_result in lines {
-        row_num += 1;
-        let line = line_result.map_err(|e| {
-            DatabaseError::ingestion_error(format!(
-                "Failed to read line {} from '{}': {}",
-                row_num,
+/// - A header name repeats and `options.rename_duplicate_headers` is false
+fn read_csv_file(path: &Path, options: &CsvOptions) -> Result<CsvFile> {
+    // Open the file, transparently decompressing gzip input
+    let (reader, compressed) = open_input(path)?;
+
+    let mut records = parse_csv_records(reader, options).map_err(|e| {
+        let line = e.position().map_or(0, |position| position.line());
+        match e.kind() {
+            // A read error on compressed input almost always means a damaged
+            // stream, which deserves a different message from a plain I/O
+            // failure
+            csv::ErrorKind::Io(_) if compressed => DatabaseError::ingestion_error(format!(
+                "Failed to decompress '{}': the gzip stream is corrupt or truncated: {}",
                 path.display(),
                 e
-            ))
-        })?;
-
-        // Skip empty lines
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        // Parse the row
-        let row = parse_csv_line(&line).map_err(|e| {
-            DatabaseError::ingestion_error(format!(
+            )),
+            csv::ErrorKind::Io(_) => DatabaseError::ingestion_error(format!(
+                "Failed to read '{}': {}",
+                path.display(),
+                e
+            )),
+            _ => DatabaseError::ingestion_error(format!(
                 "Failed to parse row {} in '{}': {}",
-                row_num,
+                line,
                 path.display(),
                 e
-            ))
-        })?;
+            )),
+        }
+    })?;
 
-        rows.push(row);
+    let (headers, renames) = if options.has_header {
+        if records.is_empty() {
+            return Err(DatabaseError::ingestion_error(format!(
+                "CSV file '{}' is empty (no header row)",
+                path.display()
+            )));
+        }
+        let headers = records.remove(0).1;
+        unique_headers(path, headers, options)?
+    } else {
+        let width = records
+            .iter()
+            .map(|(_, fields)| fields.len())
+            .max()
+            .unwrap_or(0);
+        (
+            (0..width).map(|i| format!("col_{}", i)).collect(),
+            Vec::new(),
+        )
+    };
+    let (lines, mut rows): (Vec<usize>, Vec<Vec<String>>) = records.into_iter().unzip();
+
+    if let Some(token) = &options.null_token {
+        for field in rows.iter_mut().flatten() {
+            if field.trim() == token {
+                field.clear();
+            }
+        }
     }
 
-    Ok((headers, rows))
+    Ok(CsvFile {
+        headers,
+        renames,
+        rows,
+        lines,
+    })
 }
 
-/// Parses a single CSV line into individual fields.
+/// Makes a file's header names unique and non-empty.
 ///
-/// This function uses the csv crate's ReaderBuilder to properly handle:
-/// - Quoted fields
-/// - Embedded commas in quoted fields
-/// - Embedded newlines (not supported in this simple version)
-/// - Different quote characters
+/// An empty (or blank) cell is named `col_<index>`, as the columns of a
+/// headerless file are. A name that repeats an earlier one gets the first
+/// free suffix `_1`, `_2`, ...; names that appear in the header are never
+/// taken as suffixed names, so `a,a,a_1` loads as `a`, `a_2`, `a_1`.
 ///
 /// # Arguments
 ///
-/// * `line` - The CSV line to parse
+/// * `path` - The file, for error messages
+/// * `headers` - The header row as read
+/// * `options` - Whether repeated names are renamed or fail the load
 ///
 /// # Returns
 ///
-/// A vector of field strings
+/// The names to load the columns under, and the cells that were renamed
+fn unique_headers(
+    path: &Path,
+    headers: Vec<String>,
+    options: &CsvOptions,
+) -> Result<(Vec<String>, Vec<HeaderRename>)> {
+    let reserved: HashSet<&str> = headers.iter().map(String::as_str).collect();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut names = Vec::with_capacity(headers.len());
+    let mut renames = Vec::new();
+
+    for (index, header) in headers.iter().enumerate() {
+        let free = |name: &str| !used.contains(name) && !reserved.contains(name);
+        let name = if header.trim().is_empty() {
+            let base = format!("col_{}", index);
+            if free(&base) {
+                base
+            } else {
+                (1..)
+                    .map(|n| format!("{}_{}", base, n))
+                    .find(|name| free(name))
+                    .unwrap()
+            }
+        } else if !used.contains(header) {
+            header.clone()
+        } else if options.rename_duplicate_headers {
+            (1..)
+                .map(|n| format!("{}_{}", header, n))
+                .find(|name| free(name))
+                .unwrap()
+        } else {
+            let first = headers.iter().position(|h| h == header).unwrap_or(index);
+            return Err(DatabaseError::ingestion_error(format!(
+                "Column name '{}' appears more than once in the header of '{}' (columns {} and {})",
+                header,
+                path.display(),
+                first + 1,
+                index + 1
+            )));
+        };
+
+        if name != *header {
+            renames.push(HeaderRename {
+                index,
+                original: header.clone(),
+                renamed: name.clone(),
+            });
+        }
+        used.insert(name.clone());
+        names.push(name);
+    }
+
+    Ok((names, renames))
+}
+
+/// Parses delimited text into records of fields.
+///
+/// The csv crate's reader is a state machine over the whole input rather
+/// than a line splitter, so quoted fields may contain the delimiter, doubled
+/// quotes, and newlines. Records may have differing numbers of fields, and
+/// blank lines are skipped.
 ///
-/// # Errors
+/// # Arguments
 ///
-/// Returns an error if the line cannot be parsed
-fn parse_csv_line(line: &str) -> Result<Vec<String>> {
-    // Create a CSV reader from the line
-    let mut rdr = csv::ReaderBuilder::new()
+/// * `input` - The text to parse
+/// * `options` - The delimiter and quote character to parse with
+///
+/// # Returns
+///
+/// The fields of each record, in order, with the 1-based line on which the
+/// record starts
+fn parse_csv_records<R: Read>(
+    input: R,
+    options: &CsvOptions,
+) -> csv::Result<Vec<(usize, Vec<String>)>> {
+    let mut reader = csv::ReaderBuilder::new()
         .has_headers(false)
-        .from_reader(line.as_bytes());
+        .flexible(true)
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .from_reader(LineIndex::new(input));
+
+    // The csv crate's own record positions count skipped blank lines and
+    // CRLF terminators inconsistently, so each record is located by the
+    // last byte it consumed instead, less the newlines quoted inside it
+    let mut records = Vec::new();
+    let mut ends = Vec::new();
+    let mut record = csv::StringRecord::new();
+    while reader.read_record(&mut record)? {
+        // Lines holding only whitespace are blank too
+        if record.len() == 1 && record[0].trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<String> = record.iter().map(str::to_string).collect();
+        let quoted_newlines = fields.iter().map(|field| field.matches('\n').count()).sum();
+        ends.push((reader.position().byte(), quoted_newlines));
+        records.push(fields);
+    }
+
+    let index = reader.into_inner();
+    Ok(ends
+        .into_iter()
+        .map(|(end, quoted_newlines): (u64, usize)| {
+            index.line_of(end.saturating_sub(1)) - quoted_newlines
+        })
+        .zip(records)
+        .collect())
+}
 
-    // Get the records iterator
-    let mut records = rdr.records();
+/// A reader that remembers the offset of every newline read through it, so
+/// byte positions can be turned into line numbers.
+struct LineIndex<R> {
+    inner: R,
+    /// Number of bytes read so far
+    offset: u64,
+    /// Byte offsets of the `\n`s read so far, in order
+    newlines: Vec<u64>,
+}
+
+impl<R: Read> LineIndex<R> {
+    fn new(inner: R) -> Self {
+        LineIndex {
+            inner,
+            offset: 0,
+            newlines: Vec::new(),
+        }
+    }
 
-    // Get the first (and should be only) record
-    let record = records
-        .next()
-        .transpose()
-        .map_err(|e| DatabaseError::ingestion_error(format!("CSV parsing error: {}", e)))?;
+    /// The 1-based line holding the byte at `offset`.
+    fn line_of(&self, offset: u64) -> usize {
+        1 + self.newlines.partition_point(|&newline| newline < offset)
+    }
+}
 
-    match record {
-        Some(record) => {
-            let fields: Vec<String> = record.iter().map(|s| s.to_string()).collect();
-            Ok(fields)
+impl<R: Read> Read for LineIndex<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        for (i, _) in buf[..read].iter().enumerate().filter(|(_, &b)| b == b'\n') {
+            self.newlines.push(self.offset + i as u64);
         }
-        None => Ok(Vec::new()),
+        self.offset += read as u64;
+        Ok(read)
     }
 }
 
@@ -340,8 +748,8 @@ fn parse_csv_line(line: &str) -> Result<Vec<String>> {
 /// Returns an error if:
 /// - The file cannot be read
 /// - The CSV is malformed
-/// - Type inference fails
-/// - Data conversion fails
+/// - A row has a different number of fields than the header
+///   ([`DatabaseError::IngestError`], naming the row's line)
 ///
 /// # Example
 ///
@@ -356,10 +764,57 @@ fn parse_csv_line(line: &str) -> Result<Vec<String>> {
 ///          table.row_count());
 /// ```
 pub fn load_csv<P: AsRef<Path>>(path: P, table_name: String) -> Result<Table> {
-    let path = path.as_ref();
+    load_csv_table(path.as_ref(), table_name, &CsvOptions::default()).map(|(table, _)| table)
+}
 
+/// Loads a delimited file in the given dialect without registering it,
+/// returning the table alongside a report of the rows it skipped.
+///
+/// # Arguments
+///
+/// * `path` - The path to the file
+/// * `table_name` - The name to give the created table
+/// * `options` - The file's dialect; `max_errors` sets how many bad rows may
+///   be skipped
+///
+/// # Returns
+///
+/// The table and its [`LoadReport`]. A bad row beyond `options.max_errors`
+/// fails the load with that row's [`DatabaseError::IngestError`].
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::ingest::{load_csv_with_report, CsvOptions};
+///
+/// let options = CsvOptions { max_errors: 100, ..CsvOptions::default() };
+/// let (table, report) = load_csv_with_report("events.csv", "events".to_string(), &options)?;
+/// for error in &report.errors {
+///     eprintln!("skipped {}", error);
+/// }
+/// ```
+pub fn load_csv_with_report<P: AsRef<Path>>(
+    path: P,
+    table_name: String,
+    options: &CsvOptions,
+) -> Result<(Table, LoadReport)> {
+    load_csv_table(path.as_ref(), table_name, options)
+}
+
+/// Builds a Table from a delimited file in the given dialect; the body of
+/// [`load_csv`].
+fn load_csv_table(
+    path: &Path,
+    table_name: String,
+    options: &CsvOptions,
+) -> Result<(Table, LoadReport)> {
     // Step 1: Read the CSV file
-    let (headers, rows) = read_csv_file(path)?;
+    let CsvFile {
+        headers,
+        renames,
+        rows,
+        lines,
+    } = read_csv_file(path, options)?;
 
     if rows.is_empty() {
         return Err(DatabaseError::ingestion_error(format!(
@@ -368,35 +823,38 @@ pub fn load_csv<P: AsRef<Path>>(path: P, table_name: String) -> Result<Table> {
         )));
     }
 
-    // Step 2: Infer column types
-    let column_types: Vec<DataType> = headers
-        .iter()
-        .enumerate()
-        .map(|(col_idx, _header)| {
-            // Collect sample values for this column
-            let sample_values: Vec<String> = rows
-                .iter()
-                .filter_map(|row| {
-                    if col_idx < row.len() {
-                        Some(row[col_idx].clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-
-            // Infer the type
-            infer_column_type(&sample_values)
-        })
-        .collect();
-
-    // Step 3: Create the table and add columns
-    let mut table = Table::new(table_name);
-
-    for (header, data_type) in headers.iter().zip(column_types.iter()) {
-        let column = create_column(*data_type);
-        table.add_column(header.clone(), column)?;
+    // Step 2: Set aside bad rows, failing once there are too many
+    let declared = declared_column_types(path, &headers, options)?;
+    let mut report = LoadReport {
+        renamed_columns: renames,
+        ..LoadReport::default()
+    };
+    let mut good_rows = Vec::with_capacity(rows.len());
+    for (row, line) in rows.into_iter().zip(lines) {
+        match check_row(&headers, &declared, &row) {
+            None => good_rows.push(row),
+            Some(kind) => {
+                let error = IngestError {
+                    path: path.to_path_buf(),
+                    line,
+                    kind,
+                };
+                if report.errors.len() == options.max_errors {
+                    return Err(DatabaseError::IngestError(error));
+                }
+                report.errors.push(error);
+            }
+        }
     }
+    let rows = good_rows;
+
+    // Step 3: Infer the undeclared column types from the sample
+    let sample = &rows[..options.sample_rows.unwrap_or(rows.len()).min(rows.len())];
+    let column_types: Vec<DataType> = infer_column_types(&headers, sample)
+        .into_iter()
+        .zip(&declared)
+        .map(|(inferred, declared)| declared.unwrap_or(inferred))
+        .collect();
 
     // Step 4: Transpose row data into columns
     // First, collect all values by column
@@ -410,21 +868,119 @@ pub fn load_csv<P: AsRef<Path>>(path: P, table_name: String) -> Result<Table> {
         }
     }
 
-    // Step 5: Insert values into columns
-    for (col_idx, header) in headers.iter().enumerate() {
-        let data_type = column_types[col_idx];
+    // Step 5: Build each column and add it to the table; declared columns
+    // were checked in step 2, so only inferred ones can be promoted
+    let mut table = Table::new(table_name);
+
+    for ((header, data_type), values) in headers.iter().zip(column_types).zip(&column_data) {
+        table.add_column(header.clone(), build_ingest_column(data_type, values)?)?;
+    }
+
+    report.rows_loaded = rows.len();
+    Ok((table, report))
+}
 
-        for value_str in &column_data[col_idx] {
-            // Parse the value
-            let value = parse_value(value_str, data_type)?;
+/// Looks up `options.column_types` in the header, giving each column its
+/// declared type, if any.
+fn declared_column_types(
+    path: &Path,
+    headers: &[String],
+    options: &CsvOptions,
+) -> Result<Vec<Option<DataType>>> {
+    let mut declared = vec![None; headers.len()];
+    for (name, data_type) in &options.column_types {
+        let index = headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| {
+                DatabaseError::ingestion_error(format!(
+                    "Column '{}' has a declared type but is not in '{}'",
+                    name,
+                    path.display()
+                ))
+            })?;
+        declared[index] = Some(*data_type);
+    }
+    Ok(declared)
+}
 
-            // Get the column and push the value
-            let column = table.get_column_mut(header)?;
-            column.push_value(value)?;
+/// Checks one data row, returning what makes it bad: a field count that
+/// differs from the header's, or a field that does not parse as its
+/// column's declared type.
+fn check_row(
+    headers: &[String],
+    declared: &[Option<DataType>],
+    row: &[String],
+) -> Option<IngestErrorKind> {
+    if row.len() != headers.len() {
+        return Some(IngestErrorKind::FieldCount {
+            expected: headers.len(),
+            found: row.len(),
+        });
+    }
+
+    headers
+        .iter()
+        .zip(declared)
+        .zip(row)
+        .find_map(|((header, declared), value)| {
+            let expected = (*declared)?;
+            parse_value(value, expected)
+                .is_err()
+                .then(|| IngestErrorKind::InvalidValue {
+                    column: header.clone(),
+                    value: value.clone(),
+                    expected,
+                })
+        })
+}
+
+/// Builds the column for one CSV field from its values, starting from the
+/// inferred type.
+///
+/// A value that does not parse as the current type (one past the inference
+/// sample) promotes the column to the narrowest type holding it as well, and
+/// the column is rebuilt; a String column holds any value, so this ends.
+fn build_ingest_column(mut data_type: DataType, values: &[String]) -> Result<Box<dyn Column>> {
+    'build: loop {
+        let mut column = create_ingest_column(data_type, values);
+        for value in values {
+            match parse_value(value, data_type) {
+                Ok(value) => column.push_value(value)?,
+                Err(_) => {
+                    let widened = widen_type(data_type, value_type(value.trim()));
+                    data_type = if widened == data_type {
+                        DataType::String
+                    } else {
+                        widened
+                    };
+                    continue 'build;
+                }
+            }
         }
+        return Ok(column);
     }
+}
 
-    Ok(table)
+/// A String column is dictionary-encoded on load when each distinct value
+/// appears at least this many times on average.
+pub const DICTIONARY_MIN_REPEATS: usize = 2;
+
+/// Creates the column for one CSV field, using a `DictStringColumn` for
+/// String fields whose values repeat (see [`DICTIONARY_MIN_REPEATS`]).
+fn create_ingest_column(data_type: DataType, values: &[String]) -> Box<dyn Column> {
+    if data_type == DataType::String {
+        // Empty fields load as NULL and never enter the dictionary
+        let distinct: HashSet<&str> = values
+            .iter()
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .collect();
+        if distinct.len() * DICTIONARY_MIN_REPEATS <= values.len() {
+            return Box::new(DictStringColumn::new());
+        }
+    }
+    create_column(data_type)
 }
 
 /// Loads a CSV file and directly registers it in the catalog.
@@ -456,11 +1012,245 @@ pub fn load_csv_into_catalog<P: AsRef<Path>>(
     table_name: String,
     catalog: &mut Catalog,
 ) -> Result<()> {
-    let table = load_csv(path, table_name.clone())?;
-    catalog.register_table(table)?;
+    load_csv_with_options(path, table_name, &CsvOptions::default(), catalog)?;
     Ok(())
 }
 
+/// Loads a delimited file in the given dialect and registers it in the
+/// catalog.
+///
+/// This is `load_csv_into_catalog` for files that are not comma-separated
+/// with a header row: tab- or pipe-delimited exports, headerless files
+/// (whose columns are named `col_0`, `col_1`, ...), and files that spell
+/// NULL with a token.
+///
+/// # Arguments
+///
+/// * `path` - The path to the file
+/// * `table_name` - The name to give the created table
+/// * `options` - The file's dialect
+/// * `catalog` - The catalog to register the table in
+///
+/// # Returns
+///
+/// The [`LoadReport`], listing any rows skipped under `options.max_errors`
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::ingest::{load_csv_with_options, CsvOptions};
+///
+/// let tsv = CsvOptions { delimiter: b'\t', ..CsvOptions::default() };
+/// load_csv_with_options("data/users.tsv", "users".to_string(), &tsv, &mut catalog)?;
+/// ```
+pub fn load_csv_with_options<P: AsRef<Path>>(
+    path: P,
+    table_name: String,
+    options: &CsvOptions,
+    catalog: &mut Catalog,
+) -> Result<LoadReport> {
+    let (table, report) = load_csv_table(path.as_ref(), table_name, options)?;
+    catalog.register_table(table)?;
+    Ok(report)
+}
+
+/// Loads a CSV file and appends its rows to an existing table.
+///
+/// The file is loaded and its types inferred as `load_csv` does, then its
+/// columns are matched to the table's by name, so they may come in any
+/// order. Every table column must appear in the file with the same type, and
+/// the file may have no other columns. Two differences are tolerated: an
+/// Int64 column may feed a Float64 one (a day whose amounts were all whole),
+/// and a column that is empty throughout may feed any type.
+///
+/// # Arguments
+///
+/// * `path` - The path to the CSV file
+/// * `table_name` - The table to append to, matched as
+///   `Catalog::resolve_table_name` does
+/// * `catalog` - The catalog holding the table
+///
+/// # Returns
+///
+/// The number of rows appended. If the schemas differ the error lists
+/// every differing column and the table is left unchanged.
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::ingest::{load_csv_append, load_csv_into_catalog};
+///
+/// load_csv_into_catalog("sales_0101.csv", "sales".to_string(), &mut catalog)?;
+/// load_csv_append("sales_0102.csv", "sales", &mut catalog)?;
+/// ```
+pub fn load_csv_append<P: AsRef<Path>>(
+    path: P,
+    table_name: &str,
+    catalog: &mut Catalog,
+) -> Result<usize> {
+    load_csv_append_with_options(path, table_name, &CsvOptions::default(), catalog)
+}
+
+/// Appends a delimited file in the given dialect to an existing table.
+///
+/// This is [`load_csv_append`] for files described by [`CsvOptions`]; a
+/// headerless file only matches a table whose columns are named `col_0`,
+/// `col_1`, ....
+pub fn load_csv_append_with_options<P: AsRef<Path>>(
+    path: P,
+    table_name: &str,
+    options: &CsvOptions,
+    catalog: &mut Catalog,
+) -> Result<usize> {
+    load_csv_append_with_report(path, table_name, options, catalog).map(|report| report.rows_loaded)
+}
+
+/// Appends a delimited file to an existing table, returning a report of the
+/// rows appended and skipped.
+///
+/// This is [`load_csv_append_with_options`] for callers that skip bad rows
+/// with `options.max_errors` and want to know which ones were skipped.
+pub fn load_csv_append_with_report<P: AsRef<Path>>(
+    path: P,
+    table_name: &str,
+    options: &CsvOptions,
+    catalog: &mut Catalog,
+) -> Result<LoadReport> {
+    let path = path.as_ref();
+    let table_name = catalog.resolve_table_name(table_name)?;
+    let (incoming, mut report) = load_csv_table(path, table_name.clone(), options)?;
+    let table = catalog.get_table_mut(&table_name)?;
+
+    let mismatches = schema_mismatches(table, &incoming);
+    if !mismatches.is_empty() {
+        return Err(DatabaseError::ingestion_error(format!(
+            "Cannot append '{}' to table '{}': {}",
+            path.display(),
+            table_name,
+            mismatches.join("; ")
+        )));
+    }
+
+    report.rows_loaded = table.append_table(&incoming)?;
+    Ok(report)
+}
+
+/// Describes each way an incoming table's columns differ from an existing
+/// table's, for `load_csv_append`.
+fn schema_mismatches(table: &Table, incoming: &Table) -> Vec<String> {
+    let mut mismatches = Vec::new();
+
+    for name in table.column_names() {
+        let expected = table.get_column(&name).map(|c| c.data_type());
+        match (expected, incoming.get_column(&name)) {
+            (Ok(expected), Ok(column)) => {
+                let found = column.data_type();
+                let compatible = found == expected
+                    || (found == DataType::Int64 && expected == DataType::Float64)
+                    || column.null_count() == column.len();
+                if !compatible {
+                    mismatches.push(format!(
+                        "column '{}' is {} in the table but {} in the file",
+                        name, expected, found
+                    ));
+                }
+            }
+            _ => mismatches.push(format!("column '{}' is missing from the file", name)),
+        }
+    }
+    for name in incoming.column_names() {
+        if table.get_column(&name).is_err() {
+            mismatches.push(format!("column '{}' is not in the table", name));
+        }
+    }
+
+    mismatches
+}
+
+// ============================================================================
+// CSV EXPORT
+// ============================================================================
+
+/// Renders a value as a CSV field that `load_csv` reads back unchanged.
+///
+/// NULL becomes an empty field. Floats use Rust's shortest round-trip
+/// formatting and always keep a decimal point, so `3.0` is not re-inferred
+/// as an integer.
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Float64(v) => {
+            let text = v.to_string();
+            if v.is_finite() && !text.contains('.') {
+                text + ".0"
+            } else {
+                text
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Writes query results to a CSV file with a header row.
+///
+/// Fields containing commas, quotes or line breaks are quoted as RFC 4180
+/// describes. Rows go through a buffered writer one batch at a time, so the
+/// file is never assembled in memory. Loading the file with `load_csv`
+/// gives back the same values, except that an empty string reads back as
+/// NULL, types are inferred again (a String column holding only digits
+/// comes back as Int64), surrounding spaces are trimmed, and `load_csv`
+/// cannot yet read a quoted field that spans lines.
+///
+/// # Arguments
+///
+/// * `batches` - The result batches, in output order
+/// * `column_names` - The header row, one name per column
+/// * `path` - The file to create or overwrite
+///
+/// # Returns
+///
+/// The number of data rows written
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::ingest::write_batches_csv;
+///
+/// let result = catalog.query("SELECT region, SUM(amount) FROM sales GROUP BY region")?;
+/// write_batches_csv(result.batches(), result.column_names(), Path::new("totals.csv"))?;
+/// ```
+pub fn write_batches_csv(batches: &[Batch], column_names: &[String], path: &Path) -> Result<usize> {
+    let csv_error = |e: csv::Error| {
+        DatabaseError::ingestion_error(format!("Cannot write '{}': {}", path.display(), e))
+    };
+
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    writer.write_record(column_names).map_err(csv_error)?;
+
+    let mut rows = 0;
+    for batch in batches {
+        if batch.column_count() != column_names.len() {
+            return Err(DatabaseError::ingestion_error(format!(
+                "Batch has {} columns but the header names {}",
+                batch.column_count(),
+                column_names.len()
+            )));
+        }
+        for row_idx in 0..batch.row_count() {
+            let mut record = Vec::with_capacity(batch.column_count());
+            for col_idx in 0..batch.column_count() {
+                let value = batch.get(row_idx, col_idx)?;
+                record.push(csv_field(&value));
+            }
+            writer.write_record(&record).map_err(csv_error)?;
+        }
+        rows += batch.row_count();
+    }
+
+    writer.flush()?;
+    Ok(rows)
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -543,6 +1333,34 @@ mod tests {
         assert_eq!(infer_column_type(&values), DataType::Float64);
     }
 
+    #[test]
+    fn test_widen_type() {
+        use DataType::*;
+        assert_eq!(widen_type(Int64, Int64), Int64);
+        assert_eq!(widen_type(Int64, Float64), Float64);
+        assert_eq!(widen_type(Float64, Int64), Float64);
+        assert_eq!(widen_type(Float64, String), String);
+        assert_eq!(widen_type(Boolean, Int64), String);
+        assert_eq!(widen_type(Date, Date), Date);
+        assert_eq!(widen_type(Date, Float64), String);
+    }
+
+    #[test]
+    fn test_build_ingest_column_promotes() {
+        let values = |values: &[&str]| -> Vec<String> {
+            values.iter().map(|value| value.to_string()).collect()
+        };
+
+        let column = build_ingest_column(DataType::Int64, &values(&["1", "", "3.5"])).unwrap();
+        assert_eq!(column.data_type(), DataType::Float64);
+        assert_eq!(column.get(0).unwrap(), Value::Float64(1.0));
+        assert!(column.is_null(1));
+
+        let column = build_ingest_column(DataType::Int64, &values(&["1", "3.5", "abc"])).unwrap();
+        assert_eq!(column.data_type(), DataType::String);
+        assert_eq!(column.get(1).unwrap(), Value::String("3.5".to_string()));
+    }
+
     // ------------------------------------------------------------------------
     // Value Parsing Tests
     // ------------------------------------------------------------------------
@@ -568,19 +1386,19 @@ mod tests {
     #[test]
     fn test_parse_value_empty_int() {
         let value = parse_value("", DataType::Int64).unwrap();
-        assert_eq!(value, Value::Int64(0));
+        assert_eq!(value, Value::Null);
     }
 
     #[test]
     fn test_parse_value_empty_float() {
         let value = parse_value("", DataType::Float64).unwrap();
-        assert_eq!(value, Value::Float64(0.0));
+        assert_eq!(value, Value::Null);
     }
 
     #[test]
     fn test_parse_value_empty_string() {
         let value = parse_value("", DataType::String).unwrap();
-        assert_eq!(value, Value::String(String::new()));
+        assert_eq!(value, Value::Null);
     }
 
     #[test]
@@ -611,34 +1429,178 @@ mod tests {
     // CSV Parsing Tests
     // ------------------------------------------------------------------------
 
+    fn parse_csv_line(line: &str) -> Vec<String> {
+        let mut records = parse_csv_records(line.as_bytes(), &CsvOptions::default()).unwrap();
+        assert!(records.len() <= 1, "{:?}", records);
+        records.pop().map(|(_, fields)| fields).unwrap_or_default()
+    }
+
     #[test]
     fn test_parse_csv_line_simple() {
         let line = "id,name,age";
-        let fields = parse_csv_line(line).unwrap();
+        let fields = parse_csv_line(line);
         assert_eq!(fields, vec!["id", "name", "age"]);
     }
 
     #[test]
     fn test_parse_csv_line_with_quotes() {
         let line = "1,\"John Doe\",30";
-        let fields = parse_csv_line(line).unwrap();
+        let fields = parse_csv_line(line);
         assert_eq!(fields, vec!["1", "John Doe", "30"]);
     }
 
     #[test]
     fn test_parse_csv_line_with_embedded_comma() {
         let line = "1,\"Doe, John\",30";
-        let fields = parse_csv_line(line).unwrap();
+        let fields = parse_csv_line(line);
         assert_eq!(fields, vec!["1", "Doe, John", "30"]);
     }
 
     #[test]
     fn test_parse_csv_line_empty() {
         let line = "";
-        let fields = parse_csv_line(line).unwrap();
+        let fields = parse_csv_line(line);
         assert_eq!(fields.len(), 0);
     }
 
+    #[test]
+    fn test_parse_csv_records_line_numbers() {
+        let lines = |text: &str| -> Vec<usize> {
+            parse_csv_records(text.as_bytes(), &CsvOptions::default())
+                .unwrap()
+                .into_iter()
+                .map(|(line, _)| line)
+                .collect()
+        };
+        assert_eq!(lines("a\n\n\nb\nc"), vec![1, 4, 5]);
+        assert_eq!(lines("a\r\n\r\nb\r\nc\r\n"), vec![1, 3, 4]);
+        assert_eq!(lines("a\n\"x\r\ny\nz\"\n  \nb\n"), vec![1, 2, 6]);
+    }
+
+    #[test]
+    fn test_parse_csv_records_embedded_newline() {
+        let text = "id,note\n1,\"two\nlines\"\n\n2,\"say \"\"hi\"\"\"\n";
+        let records = parse_csv_records(text.as_bytes(), &CsvOptions::default()).unwrap();
+        // Each record reports the line it starts on
+        assert_eq!(
+            records,
+            vec![
+                (1, vec!["id".to_string(), "note".to_string()]),
+                (2, vec!["1".to_string(), "two\nlines".to_string()]),
+                (5, vec!["2".to_string(), "say \"hi\"".to_string()]),
+            ]
+        );
+    }
+
+    // ------------------------------------------------------------------------
+    // CSV Dialect Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_load_pipe_delimited() {
+        let file = create_temp_csv("id|name|city\n1|Alice|\"Paris|Texas\"\n2|Bob|Oslo\n");
+        let options = CsvOptions {
+            delimiter: b'|',
+            ..CsvOptions::default()
+        };
+        let mut catalog = Catalog::new();
+        load_csv_with_options(file.path(), "people".to_string(), &options, &mut catalog).unwrap();
+
+        let table = catalog.get_table("people").unwrap();
+        assert_eq!(table.column_names(), vec!["id", "name", "city"]);
+        assert_eq!(table.get_column_type("id").unwrap(), DataType::Int64);
+        assert_eq!(
+            table.get_value("city", 0).unwrap(),
+            Value::String("Paris|Texas".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_tsv_with_quoted_tabs_and_newlines() {
+        let file = create_temp_csv(
+            "id\tcomment\tscore\n1\t\"tab\there\"\t1.5\n2\t\"spans\ntwo lines\"\t2.5\n3\tplain\t3\n",
+        );
+        let options = CsvOptions {
+            delimiter: b'\t',
+            ..CsvOptions::default()
+        };
+        let (table, _) = load_csv_table(file.path(), "notes".to_string(), &options).unwrap();
+
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.get_column_type("score").unwrap(), DataType::Float64);
+        assert_eq!(
+            table.get_value("comment", 0).unwrap(),
+            Value::String("tab\there".to_string())
+        );
+        assert_eq!(
+            table.get_value("comment", 1).unwrap(),
+            Value::String("spans\ntwo lines".to_string())
+        );
+        assert_eq!(table.get_value("score", 2).unwrap(), Value::Float64(3.0));
+    }
+
+    #[test]
+    fn test_load_headerless_file() {
+        let file = create_temp_csv("1,Alice,30\n2,Bob,25\n");
+        let options = CsvOptions {
+            has_header: false,
+            ..CsvOptions::default()
+        };
+        let (table, _) = load_csv_table(file.path(), "t".to_string(), &options).unwrap();
+
+        // The first record is data, not a header
+        assert_eq!(table.column_names(), vec!["col_0", "col_1", "col_2"]);
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.get_value("col_0", 0).unwrap(), Value::Int64(1));
+        assert_eq!(table.get_column_type("col_2").unwrap(), DataType::Int64);
+
+        let empty = create_temp_csv("");
+        assert!(load_csv_table(empty.path(), "t".to_string(), &options).is_err());
+    }
+
+    #[test]
+    fn test_load_with_null_token() {
+        let file = create_temp_csv("id,amount,name\n1,NULL,Alice\n2,2.5, NULL \n3,,Carol\n");
+        let options = CsvOptions {
+            null_token: Some("NULL".to_string()),
+            ..CsvOptions::default()
+        };
+        let (table, _) = load_csv_table(file.path(), "t".to_string(), &options).unwrap();
+
+        // The token is ignored during inference, so `amount` is still numeric
+        assert_eq!(table.get_column_type("amount").unwrap(), DataType::Float64);
+        assert_eq!(table.get_value("amount", 0).unwrap(), Value::Null);
+        assert_eq!(table.get_value("name", 1).unwrap(), Value::Null);
+        // Empty fields stay NULL
+        assert_eq!(table.get_value("amount", 2).unwrap(), Value::Null);
+
+        // Without the option the token is an ordinary string
+        let plain = load_csv(file.path(), "t".to_string()).unwrap();
+        assert_eq!(plain.get_column_type("amount").unwrap(), DataType::String);
+    }
+
+    #[test]
+    fn test_append_with_options() {
+        let mut catalog = Catalog::new();
+        let first = create_temp_csv("1|a\n2|b\n");
+        let second = create_temp_csv("3|c\n");
+        let options = CsvOptions {
+            delimiter: b'|',
+            has_header: false,
+            ..CsvOptions::default()
+        };
+        load_csv_with_options(first.path(), "t".to_string(), &options, &mut catalog).unwrap();
+        let rows =
+            load_csv_append_with_options(second.path(), "t", &options, &mut catalog).unwrap();
+
+        assert_eq!(rows, 1);
+        let table = catalog.get_table("t").unwrap();
+        assert_eq!(
+            table.get_value("col_1", 2).unwrap(),
+            Value::String("c".to_string())
+        );
+    }
+
     // ------------------------------------------------------------------------
     // Load CSV Tests
     // ------------------------------------------------------------------------
@@ -704,9 +1666,10 @@ mod tests {
         assert_eq!(table.column_count(), 3);
         assert_eq!(table.row_count(), 3);
 
-        // Check that empty email is handled correctly
+        // Check that empty email is loaded as NULL
         let email_value = table.get_value("email", 1).unwrap();
-        assert_eq!(email_value, Value::String(String::new()));
+        assert_eq!(email_value, Value::Null);
+        assert_eq!(table.get_column("email").unwrap().null_count(), 1);
     }
 
     #[test]
@@ -750,6 +1713,35 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_unique_headers_keeps_names_from_the_header() {
+        let headers = |names: &[&str]| {
+            let names = names.iter().map(|name| name.to_string()).collect();
+            unique_headers(Path::new("t.csv"), names, &CsvOptions::default())
+                .unwrap()
+                .0
+        };
+
+        assert_eq!(headers(&["a", "b", "a", "a"]), ["a", "b", "a_1", "a_2"]);
+        // `a_1` is a real column, so the repeated `a` skips past it
+        assert_eq!(headers(&["a", "a", "a_1"]), ["a", "a_2", "a_1"]);
+        assert_eq!(headers(&["", "col_0", " "]), ["col_0_1", "col_0", "col_2"]);
+
+        let strict = CsvOptions {
+            rename_duplicate_headers: false,
+            ..CsvOptions::default()
+        };
+        let names = vec!["x".to_string(), "y".to_string(), "x".to_string()];
+        let err = unique_headers(Path::new("t.csv"), names, &strict).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "Column name 'x' appears more than once in the header of 't.csv' (columns 1 and 3)"
+            ),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_load_csv_file_not_found() {
         let result = load_csv("/nonexistent/file.csv", "test".to_string());
@@ -931,4 +1923,188 @@ mod tests {
             panic!("Expected Float64");
         }
     }
+
+    #[test]
+    fn test_load_csv_infers_boolean() {
+        let csv_content = "id,active,code\n1,true,1\n2,FALSE,false\n3,,yes";
+
+        let file = create_temp_csv(csv_content);
+        let table = load_csv(file.path(), "flags".to_string()).unwrap();
+
+        assert_eq!(table.get_column_type("active").unwrap(), DataType::Boolean);
+        assert_eq!(table.get_value("active", 0).unwrap(), Value::Boolean(true));
+        assert_eq!(table.get_value("active", 1).unwrap(), Value::Boolean(false));
+        assert_eq!(table.get_value("active", 2).unwrap(), Value::Null);
+
+        // Booleans mixed with anything else fall back to String
+        assert_eq!(table.get_column_type("code").unwrap(), DataType::String);
+        assert_eq!(
+            table.get_value("code", 1).unwrap(),
+            Value::String("false".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_csv_infers_date() {
+        let csv_content =
+            "id,order_date,note\n1,2024-01-15,2024-01-15\n2,,x\n3,1999-12-31,1999-12-31";
+
+        let file = create_temp_csv(csv_content);
+        let table = load_csv(file.path(), "orders".to_string()).unwrap();
+
+        assert_eq!(table.get_column_type("order_date").unwrap(), DataType::Date);
+        assert_eq!(
+            table.get_value("order_date", 0).unwrap().to_string(),
+            "2024-01-15"
+        );
+        assert_eq!(table.get_value("order_date", 1).unwrap(), Value::Null);
+
+        // Dates mixed with anything else fall back to String
+        assert_eq!(table.get_column_type("note").unwrap(), DataType::String);
+    }
+
+    #[test]
+    fn test_load_csv_dictionary_encodes_repeated_strings() {
+        let csv_content = "id,status,note\n1,open,a\n2,closed,b\n3,open,c\n4,,d\n5,open,e";
+
+        let file = create_temp_csv(csv_content);
+        let table = load_csv(file.path(), "tickets".to_string()).unwrap();
+        let is_dictionary = |table: &Table, name: &str| {
+            table
+                .get_column(name)
+                .unwrap()
+                .as_any()
+                .downcast_ref::<DictStringColumn>()
+                .is_some()
+        };
+
+        // Three distinct statuses in five rows repeat; unique notes do not
+        assert!(is_dictionary(&table, "status"));
+        assert!(!is_dictionary(&table, "note"));
+        assert_eq!(table.get_column_type("status").unwrap(), DataType::String);
+        assert_eq!(
+            table.get_value("status", 2).unwrap(),
+            Value::String("open".to_string())
+        );
+        assert_eq!(table.get_value("status", 3).unwrap(), Value::Null);
+
+        // Cloning the table keeps the encoding
+        assert!(is_dictionary(&table.clone(), "status"));
+    }
+
+    // ------------------------------------------------------------------------
+    // Schema Inference Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_infer_schema_preserves_header_order() {
+        let csv_content = "name,id,score\nAlice,1,9.5\nBob,2,7.0";
+
+        let file = create_temp_csv(csv_content);
+        let schema = infer_schema(file.path()).unwrap();
+
+        assert_eq!(
+            schema,
+            vec![
+                ("name".to_string(), DataType::String),
+                ("id".to_string(), DataType::Int64),
+                ("score".to_string(), DataType::Float64),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_infer_schema_matches_load_csv() {
+        let csv_content = "a,b\n1,x\n2,3.5";
+
+        let file = create_temp_csv(csv_content);
+        let schema = infer_schema(file.path()).unwrap();
+        let table = load_csv(file.path(), "t".to_string()).unwrap();
+
+        for (name, data_type) in schema {
+            assert_eq!(table.get_column_type(&name).unwrap(), data_type);
+        }
+    }
+
+    #[test]
+    fn test_infer_schema_file_not_found() {
+        assert!(infer_schema("/nonexistent/file.csv").is_err());
+    }
+
+    // ------------------------------------------------------------------------
+    // Compressed Input Tests
+    // ------------------------------------------------------------------------
+
+    #[cfg(feature = "gzip")]
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data")
+            .join(name)
+    }
+
+    #[cfg(feature = "gzip")]
+    fn gzip_bytes(content: &str) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_load_gzip_matches_uncompressed_twin() {
+        let plain = load_csv(fixture("test_data.csv"), "plain".to_string()).unwrap();
+        let gzipped = load_csv(fixture("test_data.csv.gz"), "gzipped".to_string()).unwrap();
+
+        assert_eq!(gzipped.column_names(), plain.column_names());
+        assert_eq!(gzipped.row_count(), plain.row_count());
+        for name in plain.column_names() {
+            assert_eq!(
+                gzipped.get_column_type(&name).unwrap(),
+                plain.get_column_type(&name).unwrap()
+            );
+            for row in 0..plain.row_count() {
+                assert_eq!(
+                    gzipped.get_value(&name, row).unwrap(),
+                    plain.get_value(&name, row).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_load_gzip_detected_by_magic_bytes() {
+        // No .gz extension: detection falls back to the magic bytes
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&gzip_bytes("id,name\n1,Alice\n2,Bob\n"))
+            .unwrap();
+
+        let table = load_csv(file.path(), "users".to_string()).unwrap();
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(
+            table.get_value("name", 1).unwrap(),
+            Value::String("Bob".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_load_corrupt_gzip_reports_decompression_error() {
+        let content: String = (0..500).map(|i| format!("{},{}\n", i, i * 2)).collect();
+        let bytes = gzip_bytes(&format!("a,b\n{}", content));
+
+        // Truncate the stream halfway through
+        let mut file = tempfile::Builder::new()
+            .suffix(".csv.gz")
+            .tempfile()
+            .unwrap();
+        file.write_all(&bytes[..bytes.len() / 2]).unwrap();
+
+        let err = load_csv(file.path(), "broken".to_string())
+            .err()
+            .expect("truncated gzip must not load");
+        let msg = err.to_string();
+        assert!(msg.contains("decompress"), "{}", msg);
+        assert!(msg.contains(&file.path().display().to_string()), "{}", msg);
+    }
 }
@@ -13,10 +13,18 @@
 //! - [`mod@column`] - Columnar storage implementation
 //! - [`table`] - Table structure holding columns
 //! - [`catalog`] - Metadata management for tables
+//! - [`virtual_table`] - Tables backed by Rust closures
 //! - [`ingest`] - CSV data ingestion
+//! - [`datagen`] - Seeded generation of large tables for benchmarks
+//! - [`storage`] - Binary on-disk table format
+//! - `parquet` - Parquet export and import (`parquet` feature)
+//! - [`format`] - Display formatting of values (money, percent, ...)
 //! - [`parser`] - SQL query parsing
+//! - [`plan_builder`] - Building query plans from Rust code instead of SQL
 //! - [`execution`] - Query execution engine
 //! - [`aggregates`] - Aggregate functions
+//! - [`replay`] - Recording and replaying query workloads
+//! - `server` - Query results as JSON over HTTP (`http` feature)
 //!
 //! ## Key Features
 //!
@@ -57,11 +65,22 @@
 pub use catalog::Catalog;
 pub use column::{create_column, Column, FloatColumn, IntColumn, StringColumn};
 pub use error::{DatabaseError, Result};
-pub use ingest::{load_csv, load_csv_into_catalog};
-pub use parser::{Parser, Query, SelectStatement};
-pub use planner::{PlanResult, Planner, PlannerError, QueryPlanner};
+pub use ingest::{
+    infer_schema, load_csv, load_csv_append, load_csv_into_catalog, load_csv_with_options,
+    load_csv_with_report, CsvOptions, HeaderRename, LoadReport,
+};
+pub use parser::{
+    CreateTableAsStatement, CreateTableStatement, InsertStatement, Parser, Query, SelectStatement,
+};
+pub use planner::{
+    create_table_as, execute_create_table, execute_create_table_as, execute_insert, execute_query,
+    execute_query_with_cancellation, execute_query_with_options, execute_sql, explain_analyze,
+    PlanResult, Planner, PlannerError, PlannerOptions, QueryPlanner, QueryResult,
+};
+pub use prepared::{prepare, BoundStatement, PreparedStatement};
 pub use table::Table;
 pub use types::{DataType, Value};
+pub use virtual_table::VirtualTable;
 
 // ============================================================================
 // MODULE DECLARATIONS
@@ -74,12 +93,23 @@ pub mod error;
 pub mod aggregates;
 pub mod catalog;
 pub mod column;
+pub mod datagen;
 pub mod execution;
+pub mod format;
 pub mod ingest;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod parser;
+pub mod plan_builder;
 pub mod planner;
+pub mod prepared;
+pub mod replay;
+#[cfg(feature = "http")]
+pub mod server;
+pub mod storage;
 pub mod table;
 pub mod types;
+pub mod virtual_table;
 
 // ============================================================================
 // VERSION INFORMATION
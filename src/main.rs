@@ -2,21 +2,54 @@
 //!
 //! This is the main entry point for the Mini Rust OLAP database.
 //! It provides an interactive REPL (Read-Eval-Print Loop) for:
-//! - Loading CSV data
+//! - Loading CSV (and, with the `parquet` feature, Parquet) data
 //! - Executing SQL queries
 //! - Managing tables
 //! - Inspecting schemas
-
-use mini_rust_olap::catalog::Catalog;
-use mini_rust_olap::error::{DatabaseError, Result};
-use mini_rust_olap::execution::Batch;
-use mini_rust_olap::ingest::load_csv;
-use mini_rust_olap::parser::Parser;
-use mini_rust_olap::planner::Planner;
-use mini_rust_olap::table::Table;
+//! - Setting column display formats (money, percent, ...)
+//!
+//! When given a subcommand it instead runs one-shot, for shell scripting:
+//!
+//! ```text
+//! mini_rust_olap query --load sales=data/sales.csv "SELECT ..." --format csv
+//! mini_rust_olap load data.csv
+//! mini_rust_olap convert data.csv data.olap
+//! mini_rust_olap describe data.csv
+//! mini_rust_olap replay session.replay --load emp=data.csv
+//! ```
+//!
+//! Exit codes: 0 on success, 1 when a query or data error occurs, 2 on a
+//! usage error.
+
+use mini_rust_olap::catalog::{Catalog, IdentifierMatch, SharedCatalog};
+use mini_rust_olap::datagen::{generate_table, TableSpec};
+use mini_rust_olap::error::{DatabaseError, IngestError, Result};
+use mini_rust_olap::execution::{Batch, CancellationToken};
+use mini_rust_olap::format::{format_value, DisplayHint, FormatOptions};
+use mini_rust_olap::ingest::{
+    infer_schema, load_csv, load_csv_append_with_report, load_csv_with_options, write_batches_csv,
+    CsvOptions, LoadReport,
+};
+#[cfg(feature = "parquet")]
+use mini_rust_olap::parquet::{export_parquet, is_parquet_file, read_parquet};
+use mini_rust_olap::parser::{Expression, Parser, Query, SelectItem};
+use mini_rust_olap::planner::{
+    create_table_as, execute_create_table, execute_insert, execute_query_with_options, execute_sql,
+    explain_analyze, PlannerOptions, QueryResult,
+};
+use mini_rust_olap::replay::{replay, Recording};
+#[cfg(feature = "http")]
+use mini_rust_olap::server::QueryServer;
+use mini_rust_olap::storage::{is_table_file, load_table, save_table};
+use mini_rust_olap::table::{Table, TableStats};
+use mini_rust_olap::types::{DataType, Value};
 use rustyline::error::ReadlineError;
-use rustyline::{history::FileHistory, Editor};
-use std::time::Instant;
+use rustyline::history::{FileHistory, History};
+use rustyline::Editor;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // REPL STRUCTURE
@@ -28,19 +61,284 @@ pub struct Repl {
     catalog: Catalog,
     /// Readline editor for command history and editing
     editor: Editor<(), FileHistory>,
+    /// File the command history is loaded from and saved to
+    history_path: PathBuf,
     /// Whether to continue the REPL loop
     running: bool,
+    /// Active `.record` target: file path and the statements recorded so far
+    recording: Option<(String, Recording)>,
+    /// Cancelled by Ctrl+C while a query runs; reset before each query
+    interrupt: CancellationToken,
+    /// Session settings (`SET`, `SHOW SETTINGS`)
+    settings: Settings,
+    /// Rows skipped by the last LOAD (`LAST LOAD ERRORS`)
+    last_load_errors: Vec<IngestError>,
+    /// Copy of the catalog answering `SERVE` requests, refreshed after
+    /// every command
+    served_catalog: Option<SharedCatalog>,
+}
+
+/// Rows of a result printed before the rest are summarised, unless changed
+/// with `SET max_display_rows`
+const DEFAULT_MAX_DISPLAY_ROWS: usize = 50;
+
+/// Largest accepted `max_display_rows`
+const MAX_DISPLAY_ROWS_LIMIT: usize = 10_000;
+
+/// Largest accepted `float_precision`
+const MAX_FLOAT_PRECISION: usize = 20;
+
+/// Environment variable giving the terminal width, used as the default
+/// `max_width`
+const WIDTH_ENV_VAR: &str = "COLUMNS";
+
+/// `max_width` when `$COLUMNS` is unset
+const DEFAULT_MAX_WIDTH: usize = 120;
+
+/// Widest a result column is drawn; longer values are cut short with `…`
+const MAX_COLUMN_WIDTH: usize = 50;
+
+/// Fewest characters of each value shown in the vertical record layout,
+/// however narrow `max_width` is
+const MIN_RECORD_VALUE_WIDTH: usize = 10;
+
+/// Environment variable naming the command history file
+const HISTORY_ENV_VAR: &str = "OLAP_HISTORY";
+
+/// History file name, kept in the home directory unless `OLAP_HISTORY`
+/// names another file
+const HISTORY_FILE_NAME: &str = ".olap_history";
+
+/// History entries `.history` lists when not given a count
+const DEFAULT_HISTORY_ENTRIES: usize = 20;
+
+/// Address `SERVE` listens on; the server has no authentication, so it only
+/// accepts local connections
+#[cfg(feature = "http")]
+const SERVE_ADDRESS: &str = "127.0.0.1";
+
+/// Session settings, changed with `SET <name> = <value>` and listed by
+/// `SHOW SETTINGS`
+#[derive(Debug, Clone, PartialEq)]
+struct Settings {
+    /// Rows per batch read by scans and produced by sorts
+    batch_size: usize,
+    /// Rows of a result printed before the rest are summarised
+    max_display_rows: usize,
+    /// Whether to print how long each command took
+    timing: bool,
+    /// Whether to print the peak memory each query buffered
+    memstats: bool,
+    /// Most bytes a query's sorts, groupings and joins may buffer
+    memory_limit: Option<usize>,
+    /// Queries running longer than this are cancelled
+    timeout: Option<Duration>,
+    /// Fixed decimals for printed floats; `None` prints them exactly
+    float_precision: Option<usize>,
+    /// Widest a result table is drawn before each row is printed as its own
+    /// block of lines; `None` always draws a table
+    max_width: Option<usize>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            batch_size: PlannerOptions::default().batch_size,
+            max_display_rows: DEFAULT_MAX_DISPLAY_ROWS,
+            timing: true,
+            memstats: false,
+            memory_limit: None,
+            timeout: None,
+            float_precision: None,
+            max_width: Some(default_max_width()),
+        }
+    }
+}
+
+impl Settings {
+    /// Setting names, in the order SHOW SETTINGS lists them
+    const NAMES: [&'static str; 8] = [
+        "batch_size",
+        "max_display_rows",
+        "timing",
+        "memstats",
+        "memory_limit",
+        "timeout",
+        "float_precision",
+        "max_width",
+    ];
+
+    /// Changes one setting, returning a confirmation to print
+    ///
+    /// The value is validated before anything changes, so an error leaves
+    /// every setting as it was.
+    fn set(&mut self, name: &str, value: &str) -> Result<String> {
+        let invalid = |expected: &str| {
+            DatabaseError::settings_error(format!(
+                "Invalid value '{}' for {}: expected {}",
+                value,
+                name.to_lowercase(),
+                expected
+            ))
+        };
+        match name.to_lowercase().as_str() {
+            "batch_size" => {
+                self.batch_size = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&rows| rows > 0)
+                    .ok_or_else(|| invalid("a number of rows greater than 0"))?;
+            }
+            "max_display_rows" => {
+                self.max_display_rows = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|rows| (1..=MAX_DISPLAY_ROWS_LIMIT).contains(rows))
+                    .ok_or_else(|| {
+                        invalid(&format!("a number from 1 to {}", MAX_DISPLAY_ROWS_LIMIT))
+                    })?;
+            }
+            "timing" => {
+                self.timing = match value.to_uppercase().as_str() {
+                    "ON" | "TRUE" => true,
+                    "OFF" | "FALSE" => false,
+                    _ => return Err(invalid("ON or OFF")),
+                };
+            }
+            "memstats" => {
+                self.memstats = match value.to_uppercase().as_str() {
+                    "ON" | "TRUE" => true,
+                    "OFF" | "FALSE" => false,
+                    _ => return Err(invalid("ON or OFF")),
+                };
+            }
+            "memory_limit" => {
+                self.memory_limit = if value.eq_ignore_ascii_case("OFF") {
+                    None
+                } else {
+                    Some(
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|&bytes| bytes > 0)
+                            .ok_or_else(|| invalid("a number of bytes or OFF"))?,
+                    )
+                };
+            }
+            "timeout" => {
+                self.timeout = if value.eq_ignore_ascii_case("OFF") {
+                    None
+                } else {
+                    let ms = value
+                        .parse::<u64>()
+                        .map_err(|_| invalid("milliseconds or OFF"))?;
+                    (ms > 0).then(|| Duration::from_millis(ms))
+                };
+                return Ok(match self.timeout {
+                    Some(timeout) => {
+                        format!("Queries now time out after {} ms.", timeout.as_millis())
+                    }
+                    None => "Query timeout disabled.".to_string(),
+                });
+            }
+            "float_precision" => {
+                self.float_precision = if value.eq_ignore_ascii_case("OFF") {
+                    None
+                } else {
+                    Some(
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|&decimals| decimals <= MAX_FLOAT_PRECISION)
+                            .ok_or_else(|| {
+                                invalid(&format!(
+                                    "a number from 0 to {} or OFF",
+                                    MAX_FLOAT_PRECISION
+                                ))
+                            })?,
+                    )
+                };
+            }
+            "max_width" => {
+                self.max_width = if value.eq_ignore_ascii_case("OFF") {
+                    None
+                } else {
+                    Some(
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|&width| width > 0)
+                            .ok_or_else(|| invalid("a number of characters or OFF"))?,
+                    )
+                };
+            }
+            _ => {
+                return Err(DatabaseError::settings_error(format!(
+                    "Unknown setting '{}'. Settings are {}",
+                    name,
+                    Self::NAMES.join(", ")
+                )))
+            }
+        }
+        Ok(format!("{} = {}", name.to_lowercase(), self.value(name)))
+    }
+
+    /// The current value of a setting, as SHOW SETTINGS prints it
+    fn value(&self, name: &str) -> String {
+        match name.to_lowercase().as_str() {
+            "batch_size" => self.batch_size.to_string(),
+            "max_display_rows" => self.max_display_rows.to_string(),
+            "timing" => if self.timing { "ON" } else { "OFF" }.to_string(),
+            "memstats" => if self.memstats { "ON" } else { "OFF" }.to_string(),
+            "memory_limit" => self
+                .memory_limit
+                .map_or("OFF".to_string(), |bytes| format!("{} bytes", bytes)),
+            "timeout" => self.timeout.map_or("OFF".to_string(), |timeout| {
+                format!("{} ms", timeout.as_millis())
+            }),
+            "float_precision" => self
+                .float_precision
+                .map_or("OFF".to_string(), |decimals| decimals.to_string()),
+            "max_width" => self
+                .max_width
+                .map_or("OFF".to_string(), |width| width.to_string()),
+            _ => String::new(),
+        }
+    }
+
+    /// How the printer renders values without a display hint
+    fn format_options(&self) -> FormatOptions {
+        FormatOptions {
+            float_precision: self.float_precision,
+        }
+    }
+
+    /// Execution options for the planner
+    fn planner_options(&self) -> PlannerOptions {
+        PlannerOptions {
+            batch_size: self.batch_size,
+            memory_limit: self.memory_limit,
+            ..PlannerOptions::default()
+        }
+    }
 }
 
 impl Repl {
-    /// Creates a new REPL instance
+    /// Creates a new REPL instance whose history file is `$OLAP_HISTORY`,
+    /// or `.olap_history` in the home directory
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        Self::with_history_file(default_history_path())
+    }
+
+    /// Creates a new REPL instance that keeps its command history in `path`
+    pub fn with_history_file(path: impl Into<PathBuf>) -> Self {
+        let history_path = path.into();
         let mut editor =
             Editor::<(), FileHistory>::new().expect("Failed to initialize readline editor");
 
         // Load command history if available
-        if let Err(_e) = editor.load_history(".olap_history") {
+        if let Err(_e) = editor.load_history(&history_path) {
             // History file doesn't exist yet, that's okay
             println!("No previous history found. Starting fresh.");
         }
@@ -48,7 +346,13 @@ impl Repl {
         Self {
             catalog: Catalog::new(),
             editor,
+            history_path,
             running: true,
+            recording: None,
+            interrupt: CancellationToken::new(),
+            settings: Settings::default(),
+            last_load_errors: Vec::new(),
+            served_catalog: None,
         }
     }
 
@@ -61,29 +365,63 @@ impl Repl {
     pub fn run(&mut self) -> Result<()> {
         self.print_welcome();
 
+        // At the prompt the editor reads Ctrl+C itself; while a command runs
+        // the terminal delivers it as a signal, which cancels the query
+        let interrupt = self.interrupt.clone();
+        if let Err(e) = ctrlc::set_handler(move || interrupt.cancel()) {
+            eprintln!("Warning: Ctrl+C will not cancel queries: {}", e);
+        }
+
+        // A SQL statement may span several lines; they gather here until a
+        // semicolon or a blank line ends it
+        let mut buffer = String::new();
+
         while self.running {
-            let readline = self.editor.readline("olap> ");
+            let prompt = if buffer.is_empty() { "olap> " } else { "...> " };
+            let readline = self.editor.readline(prompt);
 
             match readline {
                 Ok(line) => {
-                    // Save to history
-                    let line = line.trim();
-                    if line.is_empty() {
+                    let blank = line.trim().is_empty();
+                    if buffer.is_empty() {
+                        if blank {
+                            continue;
+                        }
+                        buffer.push_str(line.trim());
+                    } else if !blank {
+                        buffer.push('\n');
+                        buffer.push_str(line.trim_end());
+                    }
+                    if !blank && needs_more_input(&buffer) {
                         continue;
                     }
-                    let _ = self.editor.add_history_entry(line);
+
+                    // Save to history as one entry, so up-arrow recalls the
+                    // whole statement; a re-run saves the query it expands to
+                    let input = std::mem::take(&mut buffer);
+                    if !is_rerun_command(&input) {
+                        let _ = self.editor.add_history_entry(input.as_str());
+                    }
 
                     // Process the command
-                    if let Err(e) = self.process_command(line) {
+                    if let Err(e) = self.process_command(&input) {
                         self.print_error(&e);
                     }
                 }
                 Err(ReadlineError::Interrupted) => {
-                    // Ctrl+C - continue running
-                    println!("Use EXIT or QUIT to exit.");
+                    // Ctrl+C - abandon a partial statement, or continue running
+                    if buffer.is_empty() {
+                        println!("Use EXIT or QUIT to exit.");
+                    } else {
+                        buffer.clear();
+                        println!("Statement abandoned.");
+                    }
                 }
                 Err(ReadlineError::Eof) => {
                     // Ctrl+D - exit
+                    if !buffer.is_empty() {
+                        println!("Unfinished statement discarded.");
+                    }
                     println!("Goodbye!");
                     self.running = false;
                 }
@@ -96,43 +434,136 @@ impl Repl {
         }
 
         // Save history before exiting
-        if let Err(e) = self.editor.save_history(".olap_history") {
+        if let Err(e) = self.editor.save_history(&self.history_path) {
             eprintln!("Warning: Failed to save history: {}", e);
         }
 
         Ok(())
     }
 
-    /// Processes a single command
+    /// Processes a line of input: one command, or several separated by
+    /// semicolons
+    ///
+    /// Statements run in order and stop at the first failure, whose error
+    /// names the statement by its position on the line.
     pub fn process_command(&mut self, input: &str) -> Result<()> {
-        let start = Instant::now();
+        let statements = split_statements(input);
+        if let [statement] = statements.as_slice() {
+            // The re-run query reports its own timing
+            if is_rerun_command(statement) {
+                return self.cmd_rerun(statement);
+            }
 
-        // Parse the command
-        let result = self.execute_command(input);
+            let start = Instant::now();
 
-        let elapsed = start.elapsed();
+            // Parse the command
+            let result = self.execute_command(statement);
 
-        // Print timing if command was successful
-        if result.is_ok() {
-            self.print_timing(elapsed);
+            let elapsed = start.elapsed();
+
+            // Print timing if command was successful
+            if result.is_ok() && self.settings.timing {
+                self.print_timing(elapsed);
+            }
+
+            return result;
         }
 
-        result
+        let total = Instant::now();
+        for (index, statement) in statements.iter().enumerate() {
+            let start = Instant::now();
+            self.execute_command(statement).map_err(|e| {
+                e.with_context(format!(
+                    "Statement {} of {} ({})",
+                    index + 1,
+                    statements.len(),
+                    statement
+                ))
+            })?;
+            if self.settings.timing {
+                println!(
+                    "⏱ Statement {} executed in {}",
+                    index + 1,
+                    format_elapsed(start.elapsed())
+                );
+            }
+
+            // EXIT stops the rest of the line too
+            if !self.running {
+                return Ok(());
+            }
+        }
+        if !statements.is_empty() && self.settings.timing {
+            println!(
+                "⏱ {} statements executed in {}",
+                statements.len(),
+                format_elapsed(total.elapsed())
+            );
+        }
+        Ok(())
     }
 
     /// Executes a parsed command
     pub fn execute_command(&mut self, input: &str) -> Result<()> {
-        let upper_input = input.to_uppercase();
+        let result = self.dispatch_command(input);
+        self.publish_catalog();
+        result
+    }
+
+    /// Runs a single command, choosing the handler by its leading keywords
+    fn dispatch_command(&mut self, input: &str) -> Result<()> {
+        // Line breaks from multi-line input separate keywords like spaces
+        let upper_input = input.to_uppercase().replace(['\n', '\r', '\t'], " ");
 
         // Identify command type
         if upper_input.starts_with("LOAD ") {
             self.cmd_load(input)
+        } else if upper_input.starts_with("GENERATE ") {
+            self.cmd_generate(input)
+        } else if upper_input.starts_with("SET ") {
+            self.cmd_set(input)
+        } else if upper_input.starts_with("EXPLAIN ANALYZE ") {
+            self.cmd_explain_analyze(input)
         } else if upper_input.starts_with("SELECT ") || upper_input.starts_with("WITH ") {
             self.cmd_select(input)
+        } else if upper_input.starts_with("INSERT ") {
+            self.cmd_insert(input)
+        } else if upper_input.starts_with("CREATE ") {
+            self.cmd_create_table(input)
+        } else if upper_input == "LAST LOAD ERRORS" {
+            self.cmd_last_load_errors()
+        } else if upper_input == "SHOW SETTINGS" {
+            self.cmd_show_settings()
         } else if upper_input == "SHOW TABLES" || upper_input == ".TABLES" {
             self.cmd_show_tables()
         } else if upper_input.starts_with("DESCRIBE ") || upper_input.starts_with(".SCHEMA ") {
             self.cmd_describe(input)
+        } else if upper_input.starts_with("STATS ") {
+            self.cmd_stats(input)
+        } else if upper_input.starts_with("COMPRESS ") {
+            self.cmd_compress(input)
+        } else if upper_input.starts_with("ALTER ") {
+            self.cmd_alter(input)
+        } else if upper_input.starts_with("DROP ") {
+            self.cmd_drop_table(input)
+        } else if upper_input.starts_with("EXPORT ") {
+            self.cmd_export(input)
+        } else if upper_input.starts_with("SERVE ") {
+            self.cmd_serve(input)
+        } else if upper_input.starts_with("SAVE ") {
+            self.cmd_save_database(input)
+        } else if upper_input.starts_with("OPEN ") {
+            self.cmd_open_database(input)
+        } else if upper_input == ".VERIFY" || upper_input.starts_with(".VERIFY ") {
+            self.cmd_verify(input)
+        } else if upper_input == ".RECORD" || upper_input.starts_with(".RECORD ") {
+            self.cmd_record(input)
+        } else if upper_input == ".HISTORY" || upper_input.starts_with(".HISTORY ") {
+            self.cmd_history(input)
+        } else if is_rerun_command(input) {
+            self.cmd_rerun(input)
+        } else if upper_input.starts_with(".SAVE-HISTORY ") {
+            self.cmd_save_history(input)
         } else if upper_input == "EXIT" || upper_input == "QUIT" || upper_input == ".EXIT" {
             self.cmd_exit()
         } else if upper_input == "HELP" || upper_input == ".HELP" || upper_input == "?" {
@@ -153,63 +584,337 @@ impl Repl {
 
     /// LOAD command: Load a CSV file into the catalog
     /// Syntax: LOAD <path> AS <table_name>
+    ///         LOAD <path> INTO <table_name>
+    /// The path may be quoted with ' or " to include spaces, and trailing
+    /// modifiers (DELIMITER, QUOTE, NULL, NOHEADER, SAMPLE, TYPE, MAX_ERRORS)
+    /// describe the file.
     pub fn cmd_load(&mut self, input: &str) -> Result<()> {
-        // Parse: LOAD <path> AS <table_name>
-        let parts: Vec<&str> = input.split_whitespace().collect();
-
-        if parts.len() != 4 || parts[2].to_uppercase() != "AS" {
+        let (path, append, table_name, options) = parse_load(input)?;
+        let path = resolve_load_path(&path)?;
+        let path = path.as_path();
+        let table_name = table_name.as_str();
+        self.last_load_errors.clear();
+
+        let parquet = is_parquet_file(path);
+        if parquet && (append || options != CsvOptions::default()) {
             return Err(DatabaseError::parser_error(
-                "Invalid LOAD syntax. Use: LOAD <path> AS <table_name>".to_string(),
+                "A Parquet file is loaded as a new table without CSV options. \
+                 Use: LOAD <path.parquet> AS <table_name>"
+                    .to_string(),
             ));
         }
 
-        let path = parts[1];
-        let table_name = parts[3];
+        if append {
+            println!(
+                "Appending CSV from '{}' to '{}'...",
+                path.display(),
+                table_name
+            );
+            let report =
+                load_csv_append_with_report(path, table_name, &options, &mut self.catalog)?;
+            print_header_renames(&report);
+            if report.errors.is_empty() {
+                println!(
+                    "✓ Appended {} rows to table '{}'.",
+                    report.rows_loaded, table_name
+                );
+            } else {
+                println!("✓ Appended {}.", skipped_rows_summary(&report));
+            }
+            self.last_load_errors = report.errors;
+            return Ok(());
+        }
 
         // Check if table already exists
         if self.catalog.table_exists(table_name) {
             return Err(DatabaseError::catalog_error(format!(
-                "Table '{}' already exists. Drop it first if you want to reload.",
-                table_name
+                "Table '{}' already exists. Use DROP TABLE {} first if you want to reload.",
+                table_name, table_name
             )));
         }
 
-        println!("Loading CSV from '{}' as '{}'...", path, table_name);
+        if parquet {
+            println!(
+                "Loading Parquet from '{}' as '{}'...",
+                path.display(),
+                table_name
+            );
+            let table = read_parquet(path, table_name.to_string())?;
+            let rows = table.row_count();
+            self.catalog.register_table(table)?;
+            println!(
+                "✓ Loaded table '{}' ({} row{}) from '{}' successfully.",
+                table_name,
+                rows,
+                if rows == 1 { "" } else { "s" },
+                path.display()
+            );
+            return Ok(());
+        }
+
+        println!(
+            "Loading CSV from '{}' as '{}'...",
+            path.display(),
+            table_name
+        );
 
         // Load the CSV
-        let table = load_csv(path, table_name.to_string())?;
+        let report =
+            load_csv_with_options(path, table_name.to_string(), &options, &mut self.catalog)?;
+        print_header_renames(&report);
+
+        if report.errors.is_empty() {
+            println!(
+                "✓ Loaded table '{}' from '{}' successfully.",
+                table_name,
+                path.display()
+            );
+        } else {
+            println!(
+                "✓ Loaded table '{}': {}.",
+                table_name,
+                skipped_rows_summary(&report)
+            );
+        }
+        self.last_load_errors = report.errors;
+        Ok(())
+    }
+
+    /// LAST LOAD ERRORS command: List the rows skipped by the last LOAD
+    /// Syntax: LAST LOAD ERRORS
+    pub fn cmd_last_load_errors(&self) -> Result<()> {
+        if self.last_load_errors.is_empty() {
+            println!("No rows were skipped by the last LOAD.");
+        }
+        for error in &self.last_load_errors {
+            println!("  {}", error);
+        }
+        Ok(())
+    }
+
+    /// GENERATE command: Create a table of seeded random data
+    /// Syntax: GENERATE <table_name> ROWS <n> [SEED <seed>]
+    pub fn cmd_generate(&mut self, input: &str) -> Result<()> {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+        let keyword = |index: usize, expected: &str| {
+            parts
+                .get(index)
+                .is_some_and(|part| part.eq_ignore_ascii_case(expected))
+        };
+        let number = |index: usize| parts.get(index).and_then(|part| part.parse::<u64>().ok());
+
+        let (rows, seed) = match parts.len() {
+            4 if keyword(2, "ROWS") => (number(3), Some(42)),
+            6 if keyword(2, "ROWS") && keyword(4, "SEED") => (number(3), number(5)),
+            _ => (None, None),
+        };
+        let (Some(rows), Some(seed)) = (rows, seed) else {
+            return Err(DatabaseError::parser_error(
+                "Invalid GENERATE syntax. Use: GENERATE <table_name> ROWS <n> [SEED <seed>]"
+                    .to_string(),
+            ));
+        };
+
+        let table_name = parts[1];
+        if self.catalog.table_exists(table_name) {
+            return Err(DatabaseError::catalog_error(format!(
+                "Table '{}' already exists. Use DROP TABLE {} first if you want to regenerate.",
+                table_name, table_name
+            )));
+        }
+
+        let start = Instant::now();
+        let table = generate_table(table_name, rows as usize, &TableSpec::benchmark(seed))?;
         self.catalog.register_table(table)?;
+        println!(
+            "✓ Generated table '{}' with {} rows in {}.",
+            table_name,
+            rows,
+            format_elapsed(start.elapsed())
+        );
+        Ok(())
+    }
+
+    /// SET command: Change a session setting
+    /// Syntax: SET <name> = <value> (or SET <name> <value>)
+    pub fn cmd_set(&mut self, input: &str) -> Result<()> {
+        let (name, value) = parse_set(input)?;
+        let message = self.settings.set(name, value)?;
+        println!("✓ {}", message);
+        Ok(())
+    }
 
-        println!("✓ Loaded table '{}' successfully.", table_name);
+    /// SHOW SETTINGS command: List the session settings
+    pub fn cmd_show_settings(&self) -> Result<()> {
+        println!();
+        for name in Settings::NAMES {
+            println!("  {:18} {}", name, self.settings.value(name));
+        }
+        println!();
         Ok(())
     }
 
+    /// Runs a query that Ctrl+C or the session timeout can cancel
+    fn run_query(&self, sql: &str) -> Result<QueryResult> {
+        let options = self.settings.planner_options();
+        self.run_cancellable(|token| execute_query_with_options(&self.catalog, sql, options, token))
+    }
+
+    /// Runs `run` with a token that Ctrl+C or the session timeout cancels
+    fn run_cancellable<T>(&self, run: impl FnOnce(&CancellationToken) -> Result<T>) -> Result<T> {
+        self.interrupt.reset();
+        let token = match self.settings.timeout {
+            Some(timeout) => self.interrupt.clone().with_timeout(timeout),
+            None => self.interrupt.clone(),
+        };
+
+        run(&token).map_err(|e| match self.settings.timeout {
+            Some(timeout) if token.timed_out() => DatabaseError::execution_error(format!(
+                "Query timed out after {} ms (change it with SET TIMEOUT)",
+                timeout.as_millis()
+            )),
+            _ if token.is_cancelled() => {
+                DatabaseError::execution_error("Query cancelled".to_string())
+            }
+            _ => e,
+        })
+    }
+
     /// SELECT command: Execute a SQL query
     /// Syntax: SELECT ...
     pub fn cmd_select(&mut self, input: &str) -> Result<()> {
-        // Parse the SQL query
-        let mut parser = Parser::new(input);
-        let query = parser.parse()?;
-
-        // Create a planner and plan the query
-        let planner = Planner::new(&self.catalog);
-        let mut plan = planner.plan(&query)?;
-
-        // Execute the query
-        plan.open()
-            .map_err(|e| DatabaseError::execution_error(e.to_string()))?;
-        let mut all_batches: Vec<Batch> = Vec::new();
-
-        while let Some(batch) = plan
-            .next_batch()
-            .map_err(|e| DatabaseError::execution_error(e.to_string()))?
-        {
-            all_batches.push(batch);
+        let start = Instant::now();
+        let result = self.run_query(input)?;
+        let elapsed = start.elapsed();
+
+        // Append to the active recording before printing, so it is saved even
+        // if the output is interrupted
+        if let Some((path, recording)) = &mut self.recording {
+            recording.record(input, result.batches(), elapsed)?;
+            recording.save(path.as_str())?;
+        }
+
+        // Display the results, formatted with the source columns' hints
+        let query = Parser::new(input).parse()?;
+        let hints = result_display_hints(&self.catalog, &query, result.column_names());
+        self.print_batches(result.batches(), result.column_names(), &hints);
+        if self.settings.memstats {
+            self.print_peak_memory(result.peak_memory());
+        }
+
+        Ok(())
+    }
+
+    /// EXPLAIN ANALYZE command: Run a query and show each operator's rows
+    /// and time instead of the results
+    /// Syntax: EXPLAIN ANALYZE <query>
+    pub fn cmd_explain_analyze(&mut self, input: &str) -> Result<()> {
+        let sql = input["EXPLAIN ANALYZE".len()..].trim();
+        let options = self.settings.planner_options();
+        let (result, plan) =
+            self.run_cancellable(|token| explain_analyze(&self.catalog, sql, options, token))?;
+
+        print!("{}", plan);
+        println!(
+            "({} row{})",
+            result.row_count(),
+            if result.row_count() == 1 { "" } else { "s" }
+        );
+        Ok(())
+    }
+
+    /// EXPORT command: Write a query's results to a CSV file, or a table to
+    /// a Parquet file
+    /// Syntax: EXPORT <query> TO '<path>'
+    ///         EXPORT <table> TO PARQUET '<path>'
+    pub fn cmd_export(&mut self, input: &str) -> Result<()> {
+        if let Some((table_name, path)) = parse_export_parquet(input)? {
+            let table_name = self.catalog.resolve_table_name(&table_name)?;
+            let table = self.catalog.get_table(&table_name)?;
+            export_parquet(&table, Path::new(&path))?;
+            let rows = table.row_count();
+            println!(
+                "✓ Exported {} row{} of '{}' to '{}'.",
+                rows,
+                if rows == 1 { "" } else { "s" },
+                table_name,
+                path
+            );
+            return Ok(());
         }
 
-        // Display the results
-        self.print_batches(&all_batches);
+        let (query, path) = parse_export(input)?;
+        let result = self.run_query(&query)?;
+        let rows = write_batches_csv(result.batches(), result.column_names(), Path::new(&path))?;
+
+        println!(
+            "✓ Exported {} row{} to '{}'.",
+            rows,
+            if rows == 1 { "" } else { "s" },
+            path
+        );
+        Ok(())
+    }
+
+    /// INSERT command: Append rows to a table
+    /// Syntax: INSERT INTO <table> VALUES (v1, v2, ...), (...)
+    pub fn cmd_insert(&mut self, input: &str) -> Result<()> {
+        let Query::Insert(stmt) = Parser::new(input).parse()? else {
+            return Err(DatabaseError::parser_error(
+                "Invalid INSERT syntax. Use: INSERT INTO <table> VALUES (v1, v2, ...)".to_string(),
+            ));
+        };
+
+        let inserted = execute_insert(&mut self.catalog, &stmt)?;
+        println!(
+            "✓ Inserted {} row{} into '{}'.",
+            inserted,
+            if inserted == 1 { "" } else { "s" },
+            stmt.table
+        );
+        Ok(())
+    }
 
+    /// CREATE TABLE command: Register an empty table with a declared schema,
+    /// or a table holding a query's result
+    /// Syntax: CREATE TABLE <table> (<column> <type>, ...)
+    ///         CREATE TABLE <table> AS SELECT ...
+    pub fn cmd_create_table(&mut self, input: &str) -> Result<()> {
+        match Parser::new(input).parse()? {
+            Query::CreateTable(stmt) => {
+                execute_create_table(&mut self.catalog, &stmt)?;
+                println!(
+                    "✓ Created table '{}' with {} column{}.",
+                    stmt.table,
+                    stmt.columns.len(),
+                    if stmt.columns.len() == 1 { "" } else { "s" }
+                );
+            }
+            Query::CreateTableAs(stmt) => {
+                // Run the query like a SELECT, so Ctrl+C and the timeout apply
+                let start = Instant::now();
+                let options = self.settings.planner_options();
+                let table = self.run_cancellable(|token| {
+                    create_table_as(&self.catalog, &stmt, options, token)
+                })?;
+                let rows = table.row_count();
+                self.catalog.register_table(table)?;
+                println!(
+                    "✓ Created table '{}' with {} row{} in {}.",
+                    stmt.table,
+                    rows,
+                    if rows == 1 { "" } else { "s" },
+                    format_elapsed(start.elapsed())
+                );
+            }
+            _ => {
+                return Err(DatabaseError::parser_error(
+                    "Invalid CREATE syntax. Use: CREATE TABLE <table> (<column> <type>, ...) \
+                     or CREATE TABLE <table> AS SELECT ..."
+                        .to_string(),
+                ))
+            }
+        }
         Ok(())
     }
 
@@ -240,10 +945,295 @@ impl Repl {
             ));
         }
 
-        let table_name = parts[1];
-        let table = self.catalog.get_table(table_name)?;
+        let table_name = self.catalog.resolve_table_name(parts[1])?;
+        let table = self.catalog.get_table(&table_name)?;
+
+        self.print_schema(&table_name, &table);
+        Ok(())
+    }
+
+    /// STATS command: Show per-column statistics for a table
+    /// Syntax: STATS <table_name>
+    pub fn cmd_stats(&self, input: &str) -> Result<()> {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+
+        if parts.len() != 2 {
+            return Err(DatabaseError::parser_error(
+                "Invalid STATS syntax. Use: STATS <table_name>".to_string(),
+            ));
+        }
+
+        let table_name = self.catalog.resolve_table_name(parts[1])?;
+        let stats = self.catalog.table_stats(&table_name)?;
+        self.print_stats(&stats);
+        Ok(())
+    }
+
+    /// SERVE command: Answer `POST /query` requests over HTTP on a background
+    /// thread, against the tables as they are after each command
+    /// Syntax: SERVE <port>
+    #[cfg(feature = "http")]
+    pub fn cmd_serve(&mut self, input: &str) -> Result<()> {
+        let port = parse_serve(input)?;
+        let catalog = self
+            .served_catalog
+            .get_or_insert_with(|| SharedCatalog::new(self.catalog.clone()))
+            .clone();
+        // The server keeps running until the REPL exits
+        let server = QueryServer::start((SERVE_ADDRESS, port), catalog)?;
+        println!(
+            "✓ Serving POST /query on http://{} (read-only)",
+            server.local_addr()
+        );
+        Ok(())
+    }
+
+    /// SERVE command, in a build without the `http` feature
+    #[cfg(not(feature = "http"))]
+    pub fn cmd_serve(&mut self, input: &str) -> Result<()> {
+        parse_serve(input)?;
+        Err(DatabaseError::execution_error(
+            "SERVE needs the http feature; rebuild with `cargo build --features http`".to_string(),
+        ))
+    }
+
+    /// Replaces the catalog `SERVE` answers from with the current one, so
+    /// served queries see loads and drops once their command completes
+    fn publish_catalog(&self) {
+        if let Some(served) = &self.served_catalog {
+            // Shares the tables; nothing is copied
+            *served.write() = self.catalog.clone();
+        }
+    }
+
+    /// COMPRESS command: Re-encode the columns of a table that compress well
+    /// Syntax: COMPRESS <table_name>
+    pub fn cmd_compress(&mut self, input: &str) -> Result<()> {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+
+        if parts.len() != 2 {
+            return Err(DatabaseError::parser_error(
+                "Invalid COMPRESS syntax. Use: COMPRESS <table_name>".to_string(),
+            ));
+        }
+
+        let table_name = self.catalog.resolve_table_name(parts[1])?;
+        let changes = self.catalog.get_table_mut(&table_name)?.compress()?;
+        if changes.is_empty() {
+            println!("No column of '{}' compresses well enough.", table_name);
+            return Ok(());
+        }
+
+        for change in &changes {
+            println!(
+                "  {:20} {:12} {} → {}",
+                change.column,
+                change.encoding.to_string(),
+                format_bytes(change.bytes_before),
+                format_bytes(change.bytes_after)
+            );
+        }
+        let before: usize = changes.iter().map(|change| change.bytes_before).sum();
+        let after: usize = changes.iter().map(|change| change.bytes_after).sum();
+        println!(
+            "✓ Compressed {} column{} of '{}' from {} to {}.",
+            changes.len(),
+            if changes.len() == 1 { "" } else { "s" },
+            table_name,
+            format_bytes(before),
+            format_bytes(after)
+        );
+        Ok(())
+    }
+
+    /// ALTER command: Set or clear a column's display format
+    /// Syntax: ALTER TABLE <table> ALTER COLUMN <column> SET FORMAT '<format>'
+    ///         ALTER TABLE <table> ALTER COLUMN <column> DROP FORMAT
+    pub fn cmd_alter(&mut self, input: &str) -> Result<()> {
+        let (table_name, column_name, hint) = parse_alter_format(input)?;
+        let table_name = self.catalog.resolve_table_name(&table_name)?;
+        let table = self.catalog.get_table_mut(&table_name)?;
+        table.set_display_hint(&column_name, hint.clone())?;
+
+        match hint {
+            Some(hint) => println!("✓ Column '{}' now displays as '{}'.", column_name, hint),
+            None => println!("✓ Column '{}' now displays raw values.", column_name),
+        }
+        Ok(())
+    }
+
+    /// DROP TABLE command: Remove a table from the catalog
+    /// Syntax: DROP TABLE [IF EXISTS] <table_name>
+    pub fn cmd_drop_table(&mut self, input: &str) -> Result<()> {
+        let (table_name, if_exists) = parse_drop_table(input)?;
+        if if_exists && self.catalog.match_table_name(&table_name) == IdentifierMatch::Missing {
+            println!("Table '{}' does not exist; nothing to drop.", table_name);
+            return Ok(());
+        }
+        let table_name = self.catalog.resolve_table_name(&table_name)?;
+
+        if self.catalog.virtual_table_exists(&table_name) {
+            self.catalog.drop_virtual_table(&table_name)?;
+            println!("✓ Dropped virtual table '{}'.", table_name);
+        } else {
+            let table = self.catalog.drop_table(&table_name)?;
+            println!(
+                "✓ Dropped table '{}' ({} rows freed).",
+                table_name,
+                table.row_count()
+            );
+        }
+        Ok(())
+    }
+
+    /// SAVE DATABASE command: Write all tables to a database directory
+    /// Syntax: SAVE DATABASE <dir>
+    pub fn cmd_save_database(&self, input: &str) -> Result<()> {
+        let dir = parse_database_dir(input, "SAVE")?;
+        self.catalog.save_to_dir(&dir)?;
+        println!(
+            "✓ Saved {} table(s) to '{}'.",
+            self.catalog.table_count(),
+            dir
+        );
+        Ok(())
+    }
+
+    /// OPEN DATABASE command: Replace the catalog with a saved database
+    /// Syntax: OPEN DATABASE <dir>
+    pub fn cmd_open_database(&mut self, input: &str) -> Result<()> {
+        let dir = parse_database_dir(input, "OPEN")?;
+        // Only replace the current tables once the whole database has loaded
+        self.catalog = Catalog::load_from_dir(&dir)?;
+        println!(
+            "✓ Opened '{}' with {} table(s).",
+            dir,
+            self.catalog.table_count()
+        );
+        Ok(())
+    }
+
+    /// .verify command: Check tables for internal inconsistencies
+    /// Syntax: .verify [table_name]
+    pub fn cmd_verify(&self, input: &str) -> Result<()> {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+
+        let issues = match parts.as_slice() {
+            [_] => self.catalog.verify(),
+            [_, table_name] => {
+                let table_name = self.catalog.resolve_table_name(table_name)?;
+                self.catalog.verify_table(&table_name)?
+            }
+            _ => {
+                return Err(DatabaseError::parser_error(
+                    "Invalid .verify syntax. Use: .verify [table_name]".to_string(),
+                ))
+            }
+        };
+
+        if issues.is_empty() {
+            println!("✓ No integrity issues found.");
+        } else {
+            println!("Found {} integrity issue(s):", issues.len());
+            for issue in &issues {
+                println!("  - {}", issue);
+            }
+        }
+        Ok(())
+    }
+
+    /// .record command: Record executed queries for a later `replay`
+    /// Syntax: .record <path> | .record off
+    pub fn cmd_record(&mut self, input: &str) -> Result<()> {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [_, arg] if arg.eq_ignore_ascii_case("off") => match self.recording.take() {
+                Some((path, recording)) => {
+                    println!(
+                        "✓ Stopped recording; {} statement(s) saved to '{}'.",
+                        recording.len(),
+                        path
+                    );
+                    Ok(())
+                }
+                None => Err(DatabaseError::parser_error("Not recording.".to_string())),
+            },
+            [_, path] => {
+                let recording = Recording::new();
+                recording.save(path)?;
+                println!("✓ Recording queries to '{}'.", path);
+                self.recording = Some((path.to_string(), recording));
+                Ok(())
+            }
+            _ => Err(DatabaseError::parser_error(
+                "Invalid .record syntax. Use: .record <path> | .record off".to_string(),
+            )),
+        }
+    }
+
+    /// .history command: list the last entries of the command history
+    /// Syntax: .history [n]
+    pub fn cmd_history(&self, input: &str) -> Result<()> {
+        let count = parse_history_count(input)?;
+        let history = self.editor.history();
+        let first = history.len().saturating_sub(count);
+        for (index, entry) in history.iter().enumerate().skip(first) {
+            // Continuation lines of a multi-line statement line up under its
+            // first line
+            let entry = entry.replace('\n', "\n       ");
+            println!("{:>5}  {}", index + 1, entry);
+        }
+        Ok(())
+    }
+
+    /// Re-run command: echo and execute a numbered history entry
+    /// Syntax: !<n>
+    ///         .rerun <n>
+    /// Only the re-run query is added to the history, not the command itself.
+    pub fn cmd_rerun(&mut self, input: &str) -> Result<()> {
+        let number = parse_rerun(input)?;
+        let history = self.editor.history();
+        let entry = number
+            .checked_sub(1)
+            .and_then(|index| history.iter().nth(index))
+            .cloned()
+            .ok_or_else(|| {
+                DatabaseError::parser_error(format!(
+                    "No history entry {} (there are {}). Use .history to list them.",
+                    number,
+                    history.len()
+                ))
+            })?;
+
+        // A re-run never re-runs another one, so history cannot loop
+        if split_statements(&entry)
+            .iter()
+            .any(|statement| is_rerun_command(statement))
+        {
+            return Err(DatabaseError::parser_error(format!(
+                "History entry {} is itself a re-run: {}",
+                number, entry
+            )));
+        }
+
+        println!("{}", entry);
+        let _ = self.editor.add_history_entry(entry.as_str());
+        self.process_command(&entry)
+    }
 
-        self.print_schema(table_name, table);
+    /// .save-history command: write the command history to a file
+    /// Syntax: .save-history <path>
+    pub fn cmd_save_history(&mut self, input: &str) -> Result<()> {
+        let path = parse_save_history(input)?;
+        self.editor.save_history(&path).map_err(|e| {
+            DatabaseError::ingestion_error(format!("Failed to save history to '{}': {}", path, e))
+        })?;
+        println!(
+            "✓ Saved {} history entries to '{}'",
+            self.editor.history().len(),
+            path
+        );
         Ok(())
     }
 
@@ -262,28 +1252,94 @@ impl Repl {
         println!();
         println!("Data Loading:");
         println!("  LOAD <path> AS <table_name>      Load a CSV file into the catalog");
+        println!("                                    (a .parquet file loads as Parquet)");
+        println!("                                    (quote paths with spaces: '...' or \"...\")");
+        println!("  LOAD <path> INTO <table_name>    Append a CSV file's rows to a table");
+        println!("    ... [DELIMITER '<c>'] [QUOTE '<c>'] [NULL '<token>'] [NOHEADER]");
+        println!("                                    Describe the file's dialect ('\\t' = tab)");
+        println!("    ... [SAMPLE <rows>]             Infer types from the first rows only");
+        println!(
+            "    ... [TYPE <column> <type>]      Load a column as a type instead of inferring it"
+        );
+        println!("    ... [MAX_ERRORS <n>]            Skip up to n bad rows instead of failing");
+        println!("    ... [STRICT_HEADER]             Fail on repeated column names instead of");
+        println!("                                    renaming them name_1, name_2, ...");
+        println!("  LAST LOAD ERRORS                  List the rows the last LOAD skipped");
+        println!("  GENERATE <table> ROWS <n> [SEED <s>]");
+        println!("                                    Create a table of seeded random data");
+        println!("                                    (id, value, price, category, customer)");
+        println!("  INSERT INTO <table> VALUES (...), (...)");
+        println!("                                    Append rows to a table");
         println!();
         println!("Querying:");
         println!("  SELECT <columns> FROM <table>    Execute a SQL SELECT query");
+        println!("  SELECT ... FROM '<path.csv>'     Query a CSV file without loading it");
+        println!("  <expr> AS <name>                 Name an output column");
+        println!("  JOIN <table> ON <a> = <b>        Inner join on equal columns");
         println!("  WHERE <condition>                Add filtering conditions");
-        println!("  GROUP BY <columns>               Group results");
-        println!("  ORDER BY <columns> [ASC|DESC]    Sort results");
+        println!("  <column> IS [NOT] NULL           Match missing (or present) values");
+        println!("  UPPER/LOWER/LENGTH/TRIM(<text>)  String functions in SELECT and WHERE");
+        println!("  CASE WHEN <c> THEN <v> ... END   Conditional value, NULL if no ELSE");
+        println!("  GROUP BY <columns|exprs|n>       Group results (n: n-th SELECT item)");
+        println!("  HAVING <condition>               Filter groups");
+        println!("  ORDER BY <columns|n> [ASC|DESC]  Sort results (n: n-th SELECT item)");
         println!("  LIMIT <n>                        Limit number of rows");
+        println!("  EXPORT <query> TO '<path>'       Write the results to a CSV file");
+        println!("  EXPORT <table> TO PARQUET '<path>'");
+        println!("                                    Write a table to a Parquet file");
+        println!("  EXPLAIN ANALYZE <query>          Show each operator's rows and time");
         println!();
         println!("Catalog Management:");
+        println!("  CREATE TABLE <t> (<col> <type>, ...)");
+        println!("                                    Create an empty table; types are");
+        println!("                                    INT, FLOAT, TEXT, BOOLEAN, and DATE");
+        println!("  CREATE TABLE <t> AS SELECT ...    Save a query's result as a new table");
         println!("  SHOW TABLES                       List all tables");
         println!("  DESCRIBE <table_name>             Show table schema");
+        println!("  STATS <table_name>                Show min/max, NULL and distinct counts");
+        println!("  COMPRESS <table_name>             Re-encode columns that compress well");
+        println!("  DROP TABLE [IF EXISTS] <name>     Remove a table from the catalog");
+        println!("  SAVE DATABASE <dir>               Save all tables to a directory");
+        println!("  OPEN DATABASE <dir>               Replace all tables with a saved database");
+        println!("  ALTER TABLE <t> ALTER COLUMN <c> SET FORMAT '<fmt>'");
+        println!("                                    Display a column as money, percent,");
+        println!("                                    percent(100), thousands, or '%,.2f'");
+        println!("  ALTER TABLE <t> ALTER COLUMN <c> DROP FORMAT");
+        println!("                                    Display raw values again");
+        println!("  .verify [table_name]              Check tables for corruption");
+        println!("  .record <path> | .record off      Record queries for `replay`");
+        println!();
+        println!("History:");
+        println!("  .history [n]                      List the last n commands (default 20)");
+        println!("  !<n> or .rerun <n>                Run command n from .history again");
+        println!("  .save-history <path>              Write the command history to a file");
+        println!("                                    (kept in $OLAP_HISTORY, or");
+        println!("                                    ~/.olap_history by default)");
         println!();
         println!("Utility:");
         println!("  HELP or ?                         Show this help message");
         println!("  CLEAR                             Clear screen");
         println!("  EXIT or QUIT                      Exit the REPL");
+        println!("  <cmd>; <cmd>; ...                 Run several commands in order");
+        println!("  SELECT ... (over several lines);  End with ; or a blank line");
+        println!("  SET TIMEOUT <ms> | OFF            Cancel queries that run longer");
+        println!("  SET <name> = <value>              Change batch_size, max_display_rows,");
+        println!("                                    timing (ON/OFF), memstats (ON/OFF),");
+        println!("                                    memory_limit (bytes/OFF), timeout,");
+        println!("                                    float_precision (decimals/OFF) or");
+        println!("                                    max_width (characters/OFF)");
+        println!("  SHOW SETTINGS                     List the session settings");
+        println!("  SERVE <port>                      Answer POST /query with JSON on");
+        println!("                                    127.0.0.1:<port> (http feature)");
+        println!("  Ctrl+C (while a query runs)       Cancel the query");
+        println!("  Ctrl+C (at the ...> prompt)       Abandon the unfinished statement");
         println!();
         println!("Features:");
         println!("  • Columnar storage for fast analytics");
         println!("  • SQL-like query language");
         println!("  • Automatic type inference from CSV");
-        println!("  • Aggregations: COUNT, SUM, AVG, MIN, MAX");
+        println!("  • Aggregations: COUNT, SUM, AVG, MIN, MAX, MEDIAN, PERCENTILE,");
+        println!("    APPROX_QUANTILE, STDDEV, VARIANCE (and _SAMP/_POP forms)");
         println!();
         Ok(())
     }
@@ -313,167 +1369,2579 @@ impl Repl {
         println!();
     }
 
-    /// Prints a table with ASCII formatting
-    pub fn print_batches(&self, batches: &[Batch]) {
-        let total_rows: usize = batches.iter().map(|b| b.row_count()).sum();
+    /// Prints a table with box drawing under the given column headers, or
+    /// one block per row if it is wider than the `max_width` setting
+    pub fn print_batches(
+        &self,
+        batches: &[Batch],
+        column_names: &[String],
+        hints: &[Option<DisplayHint>],
+    ) {
+        print_batches(
+            batches,
+            column_names,
+            hints,
+            self.settings.max_display_rows,
+            &self.settings.format_options(),
+            self.settings.max_width,
+        );
+    }
 
-        if total_rows == 0 {
-            println!("Empty result set.");
-            return;
-        }
+    /// Prints a table's schema
+    pub fn print_schema(&self, table_name: &str, table: &Table) {
+        let column_names: Vec<String> = table.column_names();
+        let row_count = table.row_count();
+
+        println!();
+        println!("Table: {}", table_name);
+        println!("┌────────────────────────┬──────────┬──────────────┬────────────────┐");
+        println!("│ Column Name            │ Type     │ Format       │ Description    │");
+        println!("├────────────────────────┼──────────┼──────────────┼────────────────┤");
 
-        // Collect all column names from the first batch
-        let first_batch = &batches[0];
-        let column_count = first_batch.column_count();
-        let mut column_names: Vec<String> = Vec::new();
-        for i in 0..column_count {
-            column_names.push(format!("col_{}", i));
+        for col_name in &column_names {
+            let col = table.get_column(col_name).unwrap();
+            let type_name = format!("{:?}", col.data_type());
+            let format_name = table
+                .display_hint(col_name)
+                .map(|hint| hint.to_string())
+                .unwrap_or_default();
+
+            println!(
+                "│ {:22} │ {:8} │ {:12} │ {:>9} rows │",
+                col_name,
+                type_name,
+                format_name,
+                col.len()
+            );
         }
 
-        let mut column_widths: Vec<usize> = column_names.iter().map(|s| s.len()).collect();
+        println!("└────────────────────────┴──────────┴──────────────┴────────────────┘");
+        println!("Total rows: {}", row_count);
+        println!();
+    }
 
-        // Calculate column widths based on data
-        let mut global_row_idx = 0;
-        for batch in batches {
-            for (col_idx, width) in column_widths
-                .iter_mut()
-                .enumerate()
-                .take(batch.column_count())
-            {
-                for row_idx in 0..batch.row_count() {
-                    if global_row_idx >= 100 {
-                        break;
-                    }
-                    if let Ok(value) = batch.get(row_idx, col_idx) {
-                        *width = (*width).max(value.to_string().len());
-                    }
-                    global_row_idx += 1;
+    /// Prints a table's column statistics
+    pub fn print_stats(&self, stats: &TableStats) {
+        let headers = ["Column", "Type", "Min", "Max", "Nulls", "Distinct", "Mean"];
+        let show = |value: &Option<Value>| value.as_ref().map_or("-".to_string(), Value::to_string);
+        let rows: Vec<[String; 7]> = stats
+            .columns
+            .iter()
+            .map(|column| {
+                [
+                    column.name.clone(),
+                    column.data_type.to_string(),
+                    show(&column.min),
+                    show(&column.max),
+                    column.null_count.to_string(),
+                    column.distinct_count.to_string(),
+                    column
+                        .mean
+                        .map_or("-".to_string(), |mean| format!("{:.2}", mean)),
+                ]
+            })
+            .collect();
+
+        let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let border = |left: &str, middle: &str, right: &str| {
+            let lines: Vec<String> = widths.iter().map(|&w| "─".repeat(w + 2)).collect();
+            format!("{}{}{}", left, lines.join(middle), right)
+        };
+        let print_row = |cells: &[String]| {
+            print!("│");
+            for (index, (cell, &width)) in cells.iter().zip(&widths).enumerate() {
+                // The count and mean columns line up on the right
+                if index >= 4 {
+                    print!(" {:>width$} │", cell, width = width);
+                } else {
+                    print!(" {:width$} │", cell, width = width);
                 }
             }
+            println!();
+        };
+
+        println!();
+        println!("Table: {} ({} rows)", stats.table, stats.row_count);
+        println!("{}", border("┌", "┬", "┐"));
+        print_row(&headers.map(str::to_string));
+        println!("{}", border("├", "┼", "┤"));
+        for row in &rows {
+            print_row(row);
+        }
+        println!("{}", border("└", "┴", "┘"));
+        println!();
+    }
+
+    /// Prints an error message
+    pub fn print_error(&self, error: &DatabaseError) {
+        println!();
+        println!("╔═════════════════════════════════════════════════════════╗");
+        println!("║ ❌ ERROR                                                  ║");
+        println!("╠═════════════════════════════════════════════════════════╣");
+        println!("║ {}", error);
+        println!("╚═════════════════════════════════════════════════════════╝");
+        println!();
+    }
+
+    /// Prints timing information
+    pub fn print_timing(&self, elapsed: std::time::Duration) {
+        println!("⏱ Executed in {}", format_elapsed(elapsed));
+    }
+
+    /// Prints the most memory a query's operators buffered at once
+    pub fn print_peak_memory(&self, bytes: usize) {
+        println!("💾 Peak memory: {}", format_bytes(bytes));
+    }
+}
+
+/// Formats a duration as milliseconds, or as seconds from one second up
+fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let millis = elapsed.as_secs_f64() * 1000.0;
+    if millis >= 1000.0 {
+        format!("{:.3}s", elapsed.as_secs_f64())
+    } else {
+        format!("{:.2}ms", millis)
+    }
+}
+
+/// Formats a byte count in B, KB, MB or GB (powers of 1024)
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Splits a line of input into statements at semicolons
+///
+/// Semicolons inside single-quoted strings or double-quoted identifiers
+/// do not split. Statements are trimmed, and empty ones (as between `;;`
+/// or after a trailing `;`) are dropped.
+fn split_statements(input: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, ';') => {
+                statements.push(input[start..i].trim());
+                start = i + 1;
+            }
+            (None, _) => {}
         }
+    }
+    statements.push(input[start..].trim());
+
+    statements.retain(|statement| !statement.is_empty());
+    statements
+}
 
-        // Cap width to prevent very wide tables
-        for width in &mut column_widths {
-            *width = (*width).min(50);
+/// Keywords starting the SQL statements that may span several lines
+const MULTILINE_KEYWORDS: [&str; 5] = ["SELECT", "WITH", "CREATE", "INSERT", "EXPLAIN"];
+
+/// Returns whether the REPL should keep reading lines into `buffer`
+///
+/// Only input starting with one of `MULTILINE_KEYWORDS` continues, until it
+/// ends with a semicolon outside quotes; everything else (LOAD, SET,
+/// dot-commands, ...) is complete on one line.
+fn needs_more_input(buffer: &str) -> bool {
+    let starts_statement = buffer.split_whitespace().next().is_some_and(|word| {
+        MULTILINE_KEYWORDS
+            .iter()
+            .any(|keyword| word.eq_ignore_ascii_case(keyword))
+    });
+    if !starts_statement {
+        return false;
+    }
+
+    let mut quote: Option<char> = None;
+    let mut last = None;
+    for c in buffer.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, c) if c.is_whitespace() => continue,
+            (None, _) => {}
         }
+        last = Some(c);
+    }
+    quote.is_some() || last != Some(';')
+}
+
+// ============================================================================
+// RESULT RENDERING
+// ============================================================================
 
-        // Calculate total width
-        let total_width: usize = column_widths.iter().map(|&w| w + 3).sum::<usize>() + 1;
+/// Prints query results as [`render_batches`] lays them out
+fn print_batches(
+    batches: &[Batch],
+    column_names: &[String],
+    hints: &[Option<DisplayHint>],
+    max_rows: usize,
+    options: &FormatOptions,
+    max_width: Option<usize>,
+) {
+    print!(
+        "{}",
+        render_batches(batches, column_names, hints, max_rows, options, max_width)
+    );
+}
+
+/// Lays out a table with box drawing under the plan's `column_names`,
+/// rendering column `i` with `hints[i]` and showing at most `max_rows` rows
+///
+/// Cells wider than [`MAX_COLUMN_WIDTH`] characters are cut short with `…`,
+/// and newlines, tabs and other control characters in them are escaped so
+/// every row stays on one line. A table wider than `max_width` is laid out
+/// as one block of `column │ value` lines per row instead.
+fn render_batches(
+    batches: &[Batch],
+    column_names: &[String],
+    hints: &[Option<DisplayHint>],
+    max_rows: usize,
+    options: &FormatOptions,
+    max_width: Option<usize>,
+) -> String {
+    let total_rows: usize = batches.iter().map(|b| b.row_count()).sum();
+
+    if total_rows == 0 {
+        return "Empty result set.\n".to_string();
+    }
 
-        // Print top border
-        println!("┌{}┐", "─".repeat(total_width - 2));
+    let names: Vec<String> = column_names.iter().map(|name| escape_cell(name)).collect();
+    let rows: Vec<Vec<String>> = batches
+        .iter()
+        .flat_map(|batch| (0..batch.row_count()).map(move |row_idx| (batch, row_idx)))
+        .take(max_rows)
+        .map(|(batch, row_idx)| {
+            (0..names.len())
+                .map(|col_idx| match batch.get(row_idx, col_idx) {
+                    Ok(value) => escape_cell(&format_value(
+                        &value,
+                        hints.get(col_idx).and_then(Option::as_ref),
+                        options,
+                    )),
+                    Err(_) => "NULL".to_string(),
+                })
+                .collect()
+        })
+        .collect();
+
+    // Widths of the rows shown, capped to keep long values from stretching
+    // the whole table
+    let column_widths: Vec<usize> = names
+        .iter()
+        .enumerate()
+        .map(|(col_idx, name)| {
+            rows.iter()
+                .map(|row| display_width(&row[col_idx]))
+                .fold(display_width(name), usize::max)
+                .min(MAX_COLUMN_WIDTH)
+        })
+        .collect();
+    let total_width: usize = column_widths.iter().map(|&w| w + 3).sum::<usize>() + 1;
+
+    let mut out = if max_width.is_some_and(|max_width| total_width > max_width) {
+        render_records(&names, &rows, max_width.unwrap_or(total_width))
+    } else {
+        render_table(&names, &rows, &column_widths, total_width)
+    };
+
+    if total_rows > max_rows {
+        out.push_str(&format!(
+            "({} rows total, showing first {})\n",
+            total_rows, max_rows
+        ));
+    } else {
+        out.push_str(&format!(
+            "({} row{})\n",
+            total_rows,
+            if total_rows == 1 { "" } else { "s" }
+        ));
+    }
+    out
+}
 
-        // Print header
-        print!("│");
-        for (col_name, &width) in column_names.iter().zip(column_widths.iter()) {
-            print!(" {:width$} │", col_name, width = width);
+/// Draws rows as a box-drawn table with one column per result column
+fn render_table(
+    names: &[String],
+    rows: &[Vec<String>],
+    column_widths: &[usize],
+    total_width: usize,
+) -> String {
+    let line = |cells: &[String]| {
+        let mut line = "│".to_string();
+        for (cell, &width) in cells.iter().zip(column_widths) {
+            line.push_str(&format!(" {:width$} │", truncate_cell(cell, width)));
         }
-        println!();
+        line.push('\n');
+        line
+    };
+
+    let mut out = format!("┌{}┐\n", "─".repeat(total_width - 2));
+    out.push_str(&line(names));
+    out.push_str(&format!("├{}┤\n", "─".repeat(total_width - 2)));
+    for row in rows {
+        out.push_str(&line(row));
+    }
+    out.push_str(&format!("└{}┘\n", "─".repeat(total_width - 2)));
+    out
+}
 
-        // Print separator
-        println!("├{}┤", "─".repeat(total_width - 2));
+/// Draws each row as a block of `column │ value` lines headed by its
+/// record number, for results too wide to show as a table in `max_width`
+fn render_records(names: &[String], rows: &[Vec<String>], max_width: usize) -> String {
+    let name_width = names
+        .iter()
+        .map(|name| display_width(name))
+        .max()
+        .unwrap_or(0)
+        .min(MAX_COLUMN_WIDTH);
+    // Values get what is left of the line, but never less than a column
+    let value_width = max_width
+        .saturating_sub(name_width + 3)
+        .max(MIN_RECORD_VALUE_WIDTH);
+
+    let mut out = String::new();
+    for (record, row) in rows.iter().enumerate() {
+        let header = format!("-[ RECORD {} ]", record + 1);
+        let rule_width = max_width.min(name_width + 3 + value_width);
+        out.push_str(&header);
+        out.push_str(&"-".repeat(rule_width.saturating_sub(display_width(&header))));
+        out.push('\n');
+        for (name, cell) in names.iter().zip(row) {
+            out.push_str(&format!(
+                "{:name_width$} │ {}\n",
+                truncate_cell(name, name_width),
+                truncate_cell(cell, value_width)
+            ));
+        }
+    }
+    out
+}
 
-        // Print data rows (limit to 50 rows)
-        let max_rows = 50;
-        let mut display_rows = 0;
+/// Width of a cell in characters
+fn display_width(text: &str) -> usize {
+    text.chars().count()
+}
 
-        for batch in batches {
-            let batch_row_count = batch.row_count();
-            let rows_to_show = (max_rows - display_rows).min(batch_row_count);
+/// Cuts `text` to at most `width` characters, ending it with `…` if any
+/// were dropped
+fn truncate_cell(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
 
-            for row_idx in 0..rows_to_show {
-                print!("│");
-                for (col_idx, width) in column_widths.iter().enumerate().take(batch.column_count())
-                {
-                    if let Ok(value) = batch.get(row_idx, col_idx) {
-                        print!(" {:width$} │", value.to_string(), width = width);
-                    } else {
-                        print!(" {:width$} │", "NULL", width = width);
+/// Escapes control characters such as newlines and tabs (as `\n`, `\t`),
+/// so a cell cannot break the line it is drawn on
+fn escape_cell(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_control() {
+                c.escape_default().collect()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Writes query results to stdout as CSV with a header row.
+///
+/// Values are written raw unless `hints` is given, so exports round-trip
+/// losslessly by default.
+fn write_csv(
+    column_names: &[String],
+    batches: &[Batch],
+    hints: Option<&[Option<DisplayHint>]>,
+) -> Result<()> {
+    let csv_error =
+        |e: csv::Error| DatabaseError::execution_error(format!("CSV output error: {}", e));
+
+    let mut writer = csv::Writer::from_writer(std::io::stdout().lock());
+    writer.write_record(column_names).map_err(csv_error)?;
+
+    for batch in batches {
+        for row_idx in 0..batch.row_count() {
+            let mut record = Vec::with_capacity(batch.column_count());
+            for col_idx in 0..batch.column_count() {
+                let value = batch.get(row_idx, col_idx)?;
+                let hint = hints.and_then(|hints| hints.get(col_idx)?.as_ref());
+                record.push(format_value(&value, hint, &FormatOptions::default()));
+            }
+            writer.write_record(&record).map_err(csv_error)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Looks up the display hint for each result column.
+///
+/// A result column inherits the hint of the source column with the same name,
+/// where a `table.column` name from a join is looked up in that table;
+/// computed columns such as aggregates, and every column of a query reading
+/// a derived table, are shown raw.
+fn result_display_hints(
+    catalog: &Catalog,
+    query: &Query,
+    column_names: &[String],
+) -> Vec<Option<DisplayHint>> {
+    let stmt = match query {
+        Query::Select(stmt) if stmt.from_subquery.is_none() => stmt,
+        _ => return vec![None; column_names.len()],
+    };
+
+    column_names
+        .iter()
+        .map(|name| {
+            // An aliased column keeps the display hint of the column it renames
+            let name = stmt
+                .select_items
+                .iter()
+                .find_map(|item| match item {
+                    SelectItem::Expression(Expression::Column(column), Some(alias))
+                        if alias == name =>
+                    {
+                        Some(column)
                     }
-                }
-                println!();
-                display_rows += 1;
+                    _ => None,
+                })
+                .unwrap_or(name);
+            let (table_name, column) = match name.split_once('.') {
+                Some((table_name, column)) if !stmt.joins.is_empty() => (table_name, column),
+                _ => (stmt.from_table.as_str(), name.as_str()),
+            };
+            catalog
+                .get_table(table_name)
+                .ok()?
+                .display_hint(column)
+                .cloned()
+        })
+        .collect()
+}
 
-                if display_rows >= max_rows {
-                    break;
+/// Parses `ALTER TABLE <t> ALTER COLUMN <c> SET FORMAT '<fmt>'` or
+/// `... DROP FORMAT` into (table, column, hint)
+fn parse_alter_format(input: &str) -> Result<(String, String, Option<DisplayHint>)> {
+    let syntax_error = || {
+        DatabaseError::parser_error(
+            "Invalid ALTER syntax. Use: ALTER TABLE <table> ALTER COLUMN <column> SET FORMAT '<format>' \
+             or ... DROP FORMAT"
+                .to_string(),
+        )
+    };
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let keyword = |index: usize, expected: &str| {
+        words
+            .get(index)
+            .is_some_and(|word| word.eq_ignore_ascii_case(expected))
+    };
+
+    if words.len() < 8
+        || !keyword(0, "ALTER")
+        || !keyword(1, "TABLE")
+        || !keyword(3, "ALTER")
+        || !keyword(4, "COLUMN")
+        || !keyword(7, "FORMAT")
+    {
+        return Err(syntax_error());
+    }
+    let table_name = words[2].to_string();
+    let column_name = words[5].to_string();
+
+    if keyword(6, "DROP") && words.len() == 8 {
+        return Ok((table_name, column_name, None));
+    }
+    if !keyword(6, "SET") {
+        return Err(syntax_error());
+    }
+
+    // The format is the quoted remainder, which may contain spaces
+    let rest = input.trim();
+    let start = rest.find('\'').ok_or_else(syntax_error)?;
+    let end = rest
+        .rfind('\'')
+        .filter(|&end| end > start)
+        .ok_or_else(syntax_error)?;
+    if !rest[end + 1..].trim().is_empty() {
+        return Err(syntax_error());
+    }
+
+    let hint = DisplayHint::parse(&rest[start + 1..end])?;
+    Ok((table_name, column_name, Some(hint)))
+}
+
+/// Parses `LOAD <path> AS|INTO <table> [modifiers]` into (path, append,
+/// table, options)
+///
+/// The path is either a single word or a string quoted with `'` or `"`, in
+/// which a doubled quote stands for one quote character. The modifiers
+/// `DELIMITER <c>`, `QUOTE <c>`, `NULL <token>`, `NOHEADER`, `STRICT_HEADER`,
+/// `SAMPLE <rows>`, `TYPE <column> <type>` (repeatable), and `MAX_ERRORS <n>`
+/// set the
+/// corresponding [`CsvOptions`]; `\t` spells a tab.
+fn parse_load(input: &str) -> Result<(String, bool, String, CsvOptions)> {
+    let syntax_error = || {
+        DatabaseError::parser_error(
+            "Invalid LOAD syntax. Use: LOAD <path> AS <table_name> or LOAD <path> INTO <table_name> \
+             [DELIMITER '<c>'] [QUOTE '<c>'] [NULL '<token>'] [NOHEADER] [STRICT_HEADER] \
+             [SAMPLE <rows>] [TYPE <column> <type>] [MAX_ERRORS <n>] (quote paths containing \
+             spaces)"
+                .to_string(),
+        )
+    };
+
+    let input = input.trim();
+    let mut rest = input
+        .get(.."LOAD".len())
+        .filter(|word| word.eq_ignore_ascii_case("LOAD"))
+        .map(|_| &input["LOAD".len()..])
+        .ok_or_else(syntax_error)?;
+
+    // Split the rest into (text, quoted) words
+    let mut words = Vec::new();
+    while !rest.trim_start().is_empty() {
+        // Words, quoted or not, are separated by whitespace
+        if !rest.starts_with(char::is_whitespace) {
+            return Err(syntax_error());
+        }
+        rest = rest.trim_start();
+        let word = match rest.chars().next() {
+            Some(quote @ ('\'' | '"')) => {
+                let (text, after) = scan_quoted(rest, quote).ok_or_else(|| {
+                    DatabaseError::parser_error(format!("Unterminated {} in LOAD command", quote))
+                })?;
+                rest = after;
+                (text, true)
+            }
+            _ => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let text = rest[..end].to_string();
+                rest = &rest[end..];
+                (text, false)
+            }
+        };
+        words.push(word);
+    }
+
+    let keyword =
+        |word: &(String, bool), expected: &str| !word.1 && word.0.eq_ignore_ascii_case(expected);
+    let (path, mode, table, modifiers) = match words.as_slice() {
+        [path, mode, (table, false), modifiers @ ..] if !path.0.is_empty() => {
+            (path.0.clone(), mode, table.clone(), modifiers)
+        }
+        _ => return Err(syntax_error()),
+    };
+    let append = if keyword(mode, "AS") {
+        false
+    } else if keyword(mode, "INTO") {
+        true
+    } else {
+        return Err(syntax_error());
+    };
+
+    let mut options = CsvOptions::default();
+    let mut modifiers = modifiers.iter();
+    while let Some(modifier) = modifiers.next() {
+        if keyword(modifier, "NOHEADER") {
+            options.has_header = false;
+            continue;
+        }
+        if keyword(modifier, "STRICT_HEADER") {
+            options.rename_duplicate_headers = false;
+            continue;
+        }
+        let mut value = || {
+            modifiers
+                .next()
+                .map(|value| value.0.as_str())
+                .ok_or_else(|| {
+                    DatabaseError::parser_error(format!("LOAD {} needs a value", modifier.0))
+                })
+        };
+        if keyword(modifier, "DELIMITER") {
+            options.delimiter = parse_csv_byte(value()?, "DELIMITER")?;
+        } else if keyword(modifier, "QUOTE") {
+            options.quote = parse_csv_byte(value()?, "QUOTE")?;
+        } else if keyword(modifier, "NULL") {
+            options.null_token = Some(value()?.to_string());
+        } else if keyword(modifier, "SAMPLE") {
+            let rows = value()?;
+            let rows = rows.parse().ok().filter(|&rows| rows > 0).ok_or_else(|| {
+                DatabaseError::parser_error(format!(
+                    "LOAD SAMPLE must be a positive number of rows, got '{}'",
+                    rows
+                ))
+            })?;
+            options.sample_rows = Some(rows);
+        } else if keyword(modifier, "TYPE") {
+            let column = value()?.to_string();
+            let data_type = value()?.parse::<DataType>()?;
+            options.column_types.push((column, data_type));
+        } else if keyword(modifier, "MAX_ERRORS") {
+            let count = value()?;
+            options.max_errors = count.parse().map_err(|_| {
+                DatabaseError::parser_error(format!(
+                    "LOAD MAX_ERRORS must be a number of rows, got '{}'",
+                    count
+                ))
+            })?;
+        } else {
+            return Err(DatabaseError::parser_error(format!(
+                "Unknown LOAD option '{}'. Expected DELIMITER, QUOTE, NULL, NOHEADER, \
+                 STRICT_HEADER, SAMPLE, TYPE, or MAX_ERRORS",
+                modifier.0
+            )));
+        }
+    }
+
+    Ok((path, append, table, options))
+}
+
+/// Warns about header cells a load renamed because they repeated an earlier
+/// name or were empty.
+fn print_header_renames(report: &LoadReport) {
+    for rename in &report.renamed_columns {
+        println!("Warning: {}", rename);
+    }
+}
+
+/// Describes a load that skipped rows, e.g. "loaded 499,987 rows, skipped 13
+/// (see details with LAST LOAD ERRORS)"
+fn skipped_rows_summary(report: &LoadReport) -> String {
+    let count = |n: usize| DisplayHint::Thousands.format(&Value::Int64(n as i64));
+    format!(
+        "loaded {} rows, skipped {} (see details with LAST LOAD ERRORS)",
+        count(report.rows_loaded),
+        count(report.rows_skipped())
+    )
+}
+
+/// Parses a LOAD DELIMITER or QUOTE value: one ASCII character, or `\t` for
+/// a tab
+fn parse_csv_byte(value: &str, option: &str) -> Result<u8> {
+    match value {
+        "\\t" => Ok(b'\t'),
+        _ if value.len() == 1 && value.is_ascii() => Ok(value.as_bytes()[0]),
+        _ => Err(DatabaseError::parser_error(format!(
+            "LOAD {} must be a single ASCII character or '\\t', got '{}'",
+            option, value
+        ))),
+    }
+}
+
+/// Reads the string quoted by `quote` at the start of `text`, where a doubled
+/// quote stands for one quote character
+///
+/// Returns the unescaped contents and the text after the closing quote, or
+/// None if the quote is never closed.
+fn scan_quoted(text: &str, quote: char) -> Option<(String, &str)> {
+    let mut contents = String::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != quote {
+            contents.push(c);
+        } else if chars.peek().is_some_and(|&(_, next)| next == quote) {
+            contents.push(quote);
+            chars.next();
+        } else {
+            return Some((contents, &text[index + c.len_utf8()..]));
+        }
+    }
+    None
+}
+
+/// Terminal width results are fitted to by default: `$COLUMNS` if it holds
+/// a positive number, otherwise [`DEFAULT_MAX_WIDTH`]
+fn default_max_width() -> usize {
+    std::env::var(WIDTH_ENV_VAR)
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&width| width > 0)
+        .unwrap_or(DEFAULT_MAX_WIDTH)
+}
+
+/// History file used by `Repl::new`: `$OLAP_HISTORY` if set, otherwise
+/// `.olap_history` in the home directory (or the current directory when
+/// there is no home)
+fn default_history_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(HISTORY_ENV_VAR).filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+    match std::env::var_os("HOME").filter(|home| !home.is_empty()) {
+        Some(home) => PathBuf::from(home).join(HISTORY_FILE_NAME),
+        None => PathBuf::from(HISTORY_FILE_NAME),
+    }
+}
+
+/// Whether a statement re-runs a history entry (`!<n>` or `.rerun <n>`)
+fn is_rerun_command(input: &str) -> bool {
+    let input = input.trim();
+    input.starts_with('!')
+        || input
+            .split_whitespace()
+            .next()
+            .is_some_and(|word| word.eq_ignore_ascii_case(".rerun"))
+}
+
+/// Parses `.history [n]` into the number of entries to list
+fn parse_history_count(input: &str) -> Result<usize> {
+    match input.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [_] => Ok(DEFAULT_HISTORY_ENTRIES),
+        [_, count] => count.parse().map_err(|_| {
+            DatabaseError::parser_error(format!(
+                "Invalid .history count '{}'. Use: .history [n]",
+                count
+            ))
+        }),
+        _ => Err(DatabaseError::parser_error(
+            "Invalid .history syntax. Use: .history [n]".to_string(),
+        )),
+    }
+}
+
+/// Parses `!<n>` or `.rerun <n>` into the 1-based history entry number
+fn parse_rerun(input: &str) -> Result<usize> {
+    let input = input.trim();
+    let number = match input.strip_prefix('!') {
+        Some(number) => number.trim(),
+        None => input.get(".rerun".len()..).unwrap_or("").trim(),
+    };
+    number
+        .parse()
+        .ok()
+        .filter(|&number| number > 0)
+        .ok_or_else(|| {
+            DatabaseError::parser_error(format!(
+                "Invalid history entry number '{}'. Use: !<n> or .rerun <n>",
+                number
+            ))
+        })
+}
+
+/// Parses `.save-history <path>` into the path, which may be quoted with
+/// ' or " to include spaces
+fn parse_save_history(input: &str) -> Result<String> {
+    let syntax_error = || {
+        DatabaseError::parser_error(
+            "Invalid .save-history syntax. Use: .save-history <path>".to_string(),
+        )
+    };
+
+    let path = input
+        .trim()
+        .get(".save-history".len()..)
+        .ok_or_else(syntax_error)?
+        .trim();
+    let path = match path.chars().next() {
+        Some(quote @ ('\'' | '"')) => match scan_quoted(path, quote) {
+            Some((path, rest)) if rest.trim().is_empty() => path,
+            _ => return Err(syntax_error()),
+        },
+        _ => path.to_string(),
+    };
+    if path.is_empty() {
+        return Err(syntax_error());
+    }
+    Ok(path)
+}
+
+/// Resolves a LOAD path against the current directory and checks that it
+/// names a file
+fn resolve_load_path(path: &str) -> Result<std::path::PathBuf> {
+    let absolute = std::path::absolute(path).map_err(|e| {
+        DatabaseError::ingestion_error(format!("Cannot resolve path '{}': {}", path, e))
+    })?;
+    if absolute.is_dir() {
+        return Err(DatabaseError::ingestion_error(format!(
+            "'{}' is a directory, not a CSV file",
+            absolute.display()
+        )));
+    }
+    if !absolute.exists() {
+        return Err(DatabaseError::ingestion_error(format!(
+            "File not found: '{}'",
+            absolute.display()
+        )));
+    }
+    Ok(absolute)
+}
+
+/// Parses `SERVE <port>` into the port
+fn parse_serve(input: &str) -> Result<u16> {
+    match input.split_whitespace().collect::<Vec<_>>()[..] {
+        [_, port] => port.parse().map_err(|_| {
+            DatabaseError::parser_error(format!(
+                "Invalid port '{}': expected a number from 0 to 65535",
+                port
+            ))
+        }),
+        _ => Err(DatabaseError::parser_error(
+            "Invalid SERVE syntax. Use: SERVE <port>".to_string(),
+        )),
+    }
+}
+
+/// Parses `SET <name> = <value>` or `SET <name> <value>` into (name, value)
+fn parse_set(input: &str) -> Result<(&str, &str)> {
+    let assignment = input.get(3..).unwrap_or("").trim();
+    let parts = match assignment.split_once('=') {
+        Some((name, value)) => Some((name.trim(), value.trim())),
+        None => assignment
+            .split_once(char::is_whitespace)
+            .map(|(name, value)| (name, value.trim())),
+    };
+    match parts {
+        Some((name, value))
+            if !name.is_empty() && !value.is_empty() && !name.contains(char::is_whitespace) =>
+        {
+            Ok((name, value))
+        }
+        _ => Err(DatabaseError::parser_error(
+            "Invalid SET syntax. Use: SET <name> = <value> (SHOW SETTINGS lists them)".to_string(),
+        )),
+    }
+}
+
+/// Parses `DROP TABLE [IF EXISTS] <table>` into (table, if_exists)
+fn parse_drop_table(input: &str) -> Result<(String, bool)> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let keywords: Vec<String> = words.iter().map(|word| word.to_uppercase()).collect();
+    let keywords: Vec<&str> = keywords.iter().map(String::as_str).collect();
+
+    match keywords.as_slice() {
+        ["DROP", "TABLE", _] => Ok((words[2].to_string(), false)),
+        ["DROP", "TABLE", "IF", "EXISTS", _] => Ok((words[4].to_string(), true)),
+        _ => Err(DatabaseError::parser_error(
+            "Invalid DROP syntax. Use: DROP TABLE [IF EXISTS] <table_name>".to_string(),
+        )),
+    }
+}
+
+/// Parses `<verb> DATABASE <dir>` into the directory, which may contain spaces
+fn parse_database_dir(input: &str, verb: &str) -> Result<String> {
+    let syntax_error = || {
+        DatabaseError::parser_error(format!(
+            "Invalid {} syntax. Use: {} DATABASE <dir>",
+            verb, verb
+        ))
+    };
+
+    let rest = input.trim();
+    let rest = rest
+        .get(..verb.len())
+        .filter(|word| word.eq_ignore_ascii_case(verb))
+        .map(|_| rest[verb.len()..].trim_start())
+        .ok_or_else(syntax_error)?;
+    let dir = rest
+        .get(.."DATABASE".len())
+        .filter(|word| word.eq_ignore_ascii_case("DATABASE"))
+        .map(|_| &rest["DATABASE".len()..])
+        .filter(|dir| dir.starts_with(char::is_whitespace))
+        .map(str::trim)
+        .ok_or_else(syntax_error)?;
+
+    if dir.is_empty() {
+        return Err(syntax_error());
+    }
+    Ok(dir.to_string())
+}
+
+/// Parses `EXPORT <table> TO PARQUET '<path>'` into the table and the path,
+/// or `None` for any other EXPORT command
+fn parse_export_parquet(input: &str) -> Result<Option<(String, String)>> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let keyword = |index: usize, expected: &str| {
+        words
+            .get(index)
+            .is_some_and(|w| w.eq_ignore_ascii_case(expected))
+    };
+    if !(keyword(0, "EXPORT") && keyword(2, "TO") && keyword(3, "PARQUET")) {
+        return Ok(None);
+    }
+
+    // The path is the single-quoted rest after the fourth word
+    let mut rest = input.trim();
+    for _ in 0..4 {
+        rest = rest.trim_start();
+        rest = &rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..];
+    }
+    let path = rest
+        .trim()
+        .strip_prefix('\'')
+        .and_then(|path| path.strip_suffix('\''))
+        .filter(|path| !path.is_empty() && !path.contains('\''))
+        .ok_or_else(|| {
+            DatabaseError::parser_error(
+                "Invalid EXPORT syntax. Use: EXPORT <table> TO PARQUET '<path>'".to_string(),
+            )
+        })?;
+    Ok(Some((words[1].to_string(), path.to_string())))
+}
+
+/// Parses `EXPORT <query> TO '<path>'` into the query and the path
+fn parse_export(input: &str) -> Result<(String, String)> {
+    let syntax_error = || {
+        DatabaseError::parser_error(
+            "Invalid EXPORT syntax. Use: EXPORT <query> TO '<path>' \
+             or EXPORT <table> TO PARQUET '<path>'"
+                .to_string(),
+        )
+    };
+
+    let input = input.trim();
+    let rest = input
+        .get(.."EXPORT".len())
+        .filter(|word| word.eq_ignore_ascii_case("EXPORT"))
+        .map(|_| &input["EXPORT".len()..])
+        .ok_or_else(syntax_error)?;
+
+    // The path is the quoted string at the very end
+    let quoted = rest.strip_suffix('\'').ok_or_else(syntax_error)?;
+    let open_quote = quoted.rfind('\'').ok_or_else(syntax_error)?;
+    let path = &quoted[open_quote + 1..];
+
+    // ...preceded by the TO keyword
+    let before = quoted[..open_quote].trim_end();
+    let query = before
+        .len()
+        .checked_sub("TO".len())
+        .and_then(|split| Some((before.get(..split)?, before.get(split..)?)))
+        .filter(|(query, to)| to.eq_ignore_ascii_case("TO") && query.ends_with(char::is_whitespace))
+        .map(|(query, _)| query.trim())
+        .ok_or_else(syntax_error)?;
+
+    if query.is_empty() || path.is_empty() {
+        return Err(syntax_error());
+    }
+    Ok((query.to_string(), path.to_string()))
+}
+
+// ============================================================================
+// COMMAND-LINE SUBCOMMANDS
+// ============================================================================
+
+const USAGE: &str = "\
+Usage:
+  mini_rust_olap                                   Start the interactive REPL
+  mini_rust_olap query [--load [name=]<path>]... [--format table|csv] [--formatted] <sql>
+                                                   Run one query and print the result
+  mini_rust_olap load <path> [--name <table>]      Load a file and report its size
+  mini_rust_olap convert <input.csv> <output.olap> Convert a CSV file to the binary format
+  mini_rust_olap describe <path>                   Print the (inferred) schema of a file
+  mini_rust_olap replay <recording> [--load [name=]<path>]...
+                                                   Re-run recorded queries and compare results
+  mini_rust_olap help | --help | -h                Show this message
+  mini_rust_olap --version | -V                    Show the version
+
+Files ending in .olap are read as binary tables, .parquet as Parquet (with the
+parquet feature), anything else as CSV.
+CSV output is raw unless --formatted applies the columns' display formats.
+
+A replay whose results differ from the recording exits with code 1.
+
+Exit codes: 0 success, 1 query or data error, 2 usage error";
+
+/// Output format for the `query` subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Box-drawn table, the same as the REPL prints
+    Table,
+    /// RFC 4180 CSV with a header row
+    Csv,
+}
+
+/// A parsed command line
+#[derive(Debug, PartialEq)]
+enum Command {
+    Repl,
+    Help,
+    Version,
+    Query {
+        /// (table name, path) pairs to load before running the query
+        loads: Vec<(String, String)>,
+        sql: String,
+        format: OutputFormat,
+        /// Apply display hints to CSV output too
+        formatted: bool,
+    },
+    Load {
+        path: String,
+        table_name: Option<String>,
+    },
+    Convert {
+        input: String,
+        output: String,
+    },
+    Describe {
+        path: String,
+    },
+    Replay {
+        /// The recording written by the REPL's `.record`
+        recording: String,
+        /// (table name, path) pairs to load before replaying
+        loads: Vec<(String, String)>,
+    },
+}
+
+/// A problem with the command line itself (exit code 2)
+#[derive(Debug, PartialEq)]
+struct UsageError(String);
+
+/// Parses the arguments that follow the program name.
+fn parse_args(args: &[String]) -> std::result::Result<Command, UsageError> {
+    let Some((subcommand, rest)) = args.split_first() else {
+        return Ok(Command::Repl);
+    };
+
+    match subcommand.as_str() {
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        "--version" | "-V" => Ok(Command::Version),
+        "query" => parse_query_args(rest),
+        "load" => {
+            let mut path = None;
+            let mut table_name = None;
+            let mut iter = rest.iter();
+            while let Some(arg) = iter.next() {
+                if arg == "--name" {
+                    table_name = Some(option_value(&mut iter, "--name")?);
+                } else if arg.starts_with("--") {
+                    return Err(UsageError(format!("Unknown option '{}' for load", arg)));
+                } else if path.replace(arg.clone()).is_some() {
+                    return Err(UsageError("load takes exactly one path".to_string()));
                 }
             }
+            let path = path.ok_or_else(|| UsageError("load requires a path".to_string()))?;
+            Ok(Command::Load { path, table_name })
+        }
+        "convert" => match rest {
+            [input, output] => Ok(Command::Convert {
+                input: input.clone(),
+                output: output.clone(),
+            }),
+            _ => Err(UsageError(
+                "convert requires an input and an output path".to_string(),
+            )),
+        },
+        "describe" => match rest {
+            [path] => Ok(Command::Describe { path: path.clone() }),
+            _ => Err(UsageError("describe requires exactly one path".to_string())),
+        },
+        "replay" => {
+            let mut recording = None;
+            let mut loads = Vec::new();
+            let mut iter = rest.iter();
+            while let Some(arg) = iter.next() {
+                if arg == "--load" {
+                    loads.push(parse_load_spec(&option_value(&mut iter, "--load")?)?);
+                } else if arg.starts_with("--") {
+                    return Err(UsageError(format!("Unknown option '{}' for replay", arg)));
+                } else if recording.replace(arg.clone()).is_some() {
+                    return Err(UsageError("replay takes exactly one recording".to_string()));
+                }
+            }
+            let recording =
+                recording.ok_or_else(|| UsageError("replay requires a recording".to_string()))?;
+            Ok(Command::Replay { recording, loads })
+        }
+        other => Err(UsageError(format!("Unknown subcommand '{}'", other))),
+    }
+}
 
-            if display_rows >= max_rows {
-                break;
+fn parse_query_args(args: &[String]) -> std::result::Result<Command, UsageError> {
+    let mut loads = Vec::new();
+    let mut sql = None;
+    let mut format = OutputFormat::Table;
+    let mut formatted = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--load" => {
+                let spec = option_value(&mut iter, "--load")?;
+                loads.push(parse_load_spec(&spec)?);
+            }
+            "--format" => {
+                format = match option_value(&mut iter, "--format")?.as_str() {
+                    "table" => OutputFormat::Table,
+                    "csv" => OutputFormat::Csv,
+                    other => {
+                        return Err(UsageError(format!(
+                            "Unknown format '{}' (expected table or csv)",
+                            other
+                        )))
+                    }
+                };
+            }
+            "--formatted" => formatted = true,
+            option if option.starts_with("--") => {
+                return Err(UsageError(format!("Unknown option '{}' for query", option)));
+            }
+            _ => {
+                if sql.replace(arg.clone()).is_some() {
+                    return Err(UsageError(
+                        "query takes a single SQL argument (quote it)".to_string(),
+                    ));
+                }
             }
         }
+    }
 
-        // Print bottom border
-        println!("└{}┘", "─".repeat(total_width - 2));
+    let sql = sql.ok_or_else(|| UsageError("query requires a SQL argument".to_string()))?;
+    Ok(Command::Query {
+        loads,
+        sql,
+        format,
+        formatted,
+    })
+}
 
-        // Print row count info
-        if total_rows > max_rows {
-            println!("({} rows total, showing first {})", total_rows, max_rows);
-        } else {
-            println!(
-                "({} row{})",
-                total_rows,
-                if total_rows == 1 { "" } else { "s" }
-            );
+/// Parses `name=path`, or a bare path whose file stem becomes the table name
+fn parse_load_spec(spec: &str) -> std::result::Result<(String, String), UsageError> {
+    if let Some((name, path)) = spec.split_once('=') {
+        if name.is_empty() || path.is_empty() {
+            return Err(UsageError(format!(
+                "Invalid --load value '{}' (expected name=path)",
+                spec
+            )));
         }
+        return Ok((name.to_string(), path.to_string()));
     }
 
-    /// Prints a table's schema
-    pub fn print_schema(&self, table_name: &str, table: &Table) {
-        let column_names: Vec<String> = table.column_names();
-        let row_count = table.row_count();
+    Ok((default_table_name(spec)?, spec.to_string()))
+}
 
-        println!();
-        println!("Table: {}", table_name);
-        println!("┌────────────────────────┬──────────┬────────────────┐");
-        println!("│ Column Name            │ Type     │ Description    │");
-        println!("├────────────────────────┼──────────┼────────────────┤");
+fn default_table_name(path: &str) -> std::result::Result<String, UsageError> {
+    Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| !stem.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| UsageError(format!("Cannot derive a table name from '{}'", path)))
+}
 
-        for col_name in &column_names {
-            let col = table.get_column(col_name).unwrap();
-            let type_name = format!("{:?}", col.data_type());
+fn option_value<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+) -> std::result::Result<String, UsageError> {
+    iter.next()
+        .cloned()
+        .ok_or_else(|| UsageError(format!("{} requires a value", option)))
+}
+
+/// Returns true if the path names a Parquet file, in a build without the
+/// `parquet` feature (LOAD and EXPORT then explain how to enable it)
+#[cfg(not(feature = "parquet"))]
+fn is_parquet_file<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref()
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_disabled() -> DatabaseError {
+    DatabaseError::ingestion_error(
+        "Parquet files need the parquet feature; rebuild with `cargo build --features parquet`",
+    )
+}
+
+/// Parquet import, in a build without the `parquet` feature
+#[cfg(not(feature = "parquet"))]
+fn read_parquet<P: AsRef<Path>>(_path: P, _table_name: String) -> Result<Table> {
+    Err(parquet_disabled())
+}
+
+/// Parquet export, in a build without the `parquet` feature
+#[cfg(not(feature = "parquet"))]
+fn export_parquet<P: AsRef<Path>>(_table: &Table, _path: P) -> Result<()> {
+    Err(parquet_disabled())
+}
+
+/// Loads a CSV, Parquet or binary table file, naming the table `table_name`
+fn load_file(path: &str, table_name: String) -> Result<Table> {
+    if is_table_file(path) {
+        let mut table = load_table(path)?;
+        table.set_name(table_name);
+        Ok(table)
+    } else if is_parquet_file(path) {
+        read_parquet(path, table_name)
+    } else {
+        load_csv(path, table_name)
+    }
+}
 
+/// Runs a non-REPL subcommand
+fn run_command(command: Command) -> Result<()> {
+    match command {
+        Command::Repl | Command::Help | Command::Version => Ok(()),
+        Command::Query {
+            loads,
+            sql,
+            format,
+            formatted,
+        } => {
+            let mut catalog = Catalog::new();
+            for (table_name, path) in loads {
+                catalog.register_table(load_file(&path, table_name)?)?;
+            }
+
+            let (column_names, batches) = execute_sql(&catalog, &sql)?;
+            let query = Parser::new(&sql).parse()?;
+            let hints = result_display_hints(&catalog, &query, &column_names);
+            match format {
+                OutputFormat::Table => print_batches(
+                    &batches,
+                    &column_names,
+                    &hints,
+                    DEFAULT_MAX_DISPLAY_ROWS,
+                    &FormatOptions::default(),
+                    Some(default_max_width()),
+                ),
+                OutputFormat::Csv => {
+                    write_csv(&column_names, &batches, formatted.then_some(&hints[..]))?
+                }
+            }
+            Ok(())
+        }
+        Command::Load { path, table_name } => {
+            let table_name = match table_name {
+                Some(name) => name,
+                None => default_table_name(&path)
+                    .map_err(|UsageError(msg)| DatabaseError::ingestion_error(msg))?,
+            };
+            let table = load_file(&path, table_name)?;
             println!(
-                "│ {:22} │ {:8} │ {:>12} rows │",
-                col_name,
-                type_name,
-                col.len()
+                "Loaded table '{}': {} rows, {} columns",
+                table.name(),
+                table.row_count(),
+                table.column_count()
+            );
+            Ok(())
+        }
+        Command::Convert { input, output } => {
+            let table_name = default_table_name(&input)
+                .map_err(|UsageError(msg)| DatabaseError::ingestion_error(msg))?;
+            let table = load_csv(&input, table_name)?;
+            save_table(&table, &output)?;
+            println!(
+                "Converted '{}' to '{}' ({} rows)",
+                input,
+                output,
+                table.row_count()
             );
+            Ok(())
+        }
+        Command::Describe { path } => {
+            let schema: Vec<(String, DataType, Option<DisplayHint>)> =
+                if is_table_file(&path) || is_parquet_file(&path) {
+                    let table = load_file(&path, String::new())?;
+                    table
+                        .column_names()
+                        .into_iter()
+                        .map(|name| {
+                            let data_type = table.get_column_type(&name)?;
+                            let hint = table.display_hint(&name).cloned();
+                            Ok((name, data_type, hint))
+                        })
+                        .collect::<Result<_>>()?
+                } else {
+                    infer_schema(&path)?
+                        .into_iter()
+                        .map(|(name, data_type)| (name, data_type, None))
+                        .collect()
+                };
+
+            let mut stdout = std::io::stdout().lock();
+            for (name, data_type, hint) in schema {
+                match hint {
+                    Some(hint) => writeln!(stdout, "{}\t{}\t{}", name, data_type, hint)?,
+                    None => writeln!(stdout, "{}\t{}", name, data_type)?,
+                }
+            }
+            Ok(())
         }
+        Command::Replay { recording, loads } => {
+            let mut catalog = Catalog::new();
+            for (table_name, path) in loads {
+                catalog.register_table(load_file(&path, table_name)?)?;
+            }
 
-        println!("└────────────────────────┴──────────┴────────────────┘");
-        println!("Total rows: {}", row_count);
-        println!();
-    }
+            let report = replay(&catalog, &Recording::load(&recording)?);
+            println!("{}", report);
 
-    /// Prints an error message
-    pub fn print_error(&self, error: &DatabaseError) {
-        println!();
-        println!("╔═════════════════════════════════════════════════════════╗");
-        println!("║ ❌ ERROR                                                  ║");
-        println!("╠═════════════════════════════════════════════════════════╣");
-        println!("║ {}", error);
-        println!("╚═════════════════════════════════════════════════════════╝");
-        println!();
+            let mismatches = report.mismatches().len();
+            if mismatches > 0 {
+                return Err(DatabaseError::execution_error(format!(
+                    "{} of {} replayed statement(s) did not match the recording",
+                    mismatches,
+                    report.outcomes.len()
+                )));
+            }
+            Ok(())
+        }
     }
+}
 
-    /// Prints timing information
-    pub fn print_timing(&self, elapsed: std::time::Duration) {
-        let millis = elapsed.as_secs_f64() * 1000.0;
-        if millis >= 1000.0 {
-            println!("⏱ Executed in {:.3}s", elapsed.as_secs_f64());
-        } else {
-            println!("⏱ Executed in {:.2}ms", millis);
+// ============================================================================
+// MAIN ENTRY POINT
+// ============================================================================
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(UsageError(msg)) => {
+            eprintln!("error: {}", msg);
+            eprintln!();
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Repl => {
+            let mut repl = Repl::new();
+            match repl.run() {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    ExitCode::from(1)
+                }
+            }
+        }
+        Command::Help => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
         }
+        Command::Version => {
+            println!("mini_rust_olap {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        command => match run_command(command) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                ExitCode::from(1)
+            }
+        },
     }
 }
 
 // ============================================================================
-// MAIN ENTRY POINT
+// TESTS
 // ============================================================================
 
-fn main() -> Result<()> {
-    let mut repl = Repl::new();
-    repl.run()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mini_rust_olap::column::{Column, IntColumn, StringColumn};
+    use std::sync::Arc;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_arguments_starts_repl() {
+        assert_eq!(parse_args(&[]), Ok(Command::Repl));
+    }
+
+    #[test]
+    fn test_parse_query_with_loads_and_format() {
+        let command = parse_args(&args(&[
+            "query",
+            "--load",
+            "sales=data/sales.csv",
+            "SELECT * FROM sales",
+            "--format",
+            "csv",
+            "--load",
+            "data/users.olap",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            command,
+            Command::Query {
+                loads: vec![
+                    ("sales".to_string(), "data/sales.csv".to_string()),
+                    ("users".to_string(), "data/users.olap".to_string()),
+                ],
+                sql: "SELECT * FROM sales".to_string(),
+                format: OutputFormat::Csv,
+                formatted: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_query_usage_errors() {
+        assert!(parse_args(&args(&["query"])).is_err());
+        assert!(parse_args(&args(&["query", "SELECT 1", "SELECT 2"])).is_err());
+        assert!(parse_args(&args(&["query", "--format", "json", "SELECT"])).is_err());
+        assert!(parse_args(&args(&["query", "--load", "=x.csv", "SELECT"])).is_err());
+        assert!(parse_args(&args(&["query", "SELECT", "--load"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_other_subcommands() {
+        assert_eq!(
+            parse_args(&args(&["convert", "a.csv", "a.olap"])),
+            Ok(Command::Convert {
+                input: "a.csv".to_string(),
+                output: "a.olap".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["describe", "a.csv"])),
+            Ok(Command::Describe {
+                path: "a.csv".to_string()
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["load", "a.csv", "--name", "t"])),
+            Ok(Command::Load {
+                path: "a.csv".to_string(),
+                table_name: Some("t".to_string()),
+            })
+        );
+        assert!(parse_args(&args(&["convert", "a.csv"])).is_err());
+        assert!(parse_args(&args(&["frobnicate"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_replay() {
+        assert_eq!(
+            parse_args(&args(&["replay", "s.replay", "--load", "emp=e.csv"])),
+            Ok(Command::Replay {
+                recording: "s.replay".to_string(),
+                loads: vec![("emp".to_string(), "e.csv".to_string())],
+            })
+        );
+        assert!(parse_args(&args(&["replay"])).is_err());
+        assert!(parse_args(&args(&["replay", "a", "b"])).is_err());
+        assert!(parse_args(&args(&["replay", "a", "--fast"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_query_formatted_flag() {
+        let command = parse_args(&args(&["query", "--formatted", "SELECT 1"])).unwrap();
+        assert!(matches!(
+            command,
+            Command::Query {
+                formatted: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_alter_format() {
+        let (table, column, hint) =
+            parse_alter_format("ALTER TABLE emp ALTER COLUMN salary SET FORMAT 'money'").unwrap();
+        assert_eq!(table, "emp");
+        assert_eq!(column, "salary");
+        assert_eq!(hint, Some(DisplayHint::Money));
+
+        // Patterns may contain spaces and keywords are case-insensitive
+        let (_, _, hint) =
+            parse_alter_format("alter table emp alter column qty set format '%,d items'").unwrap();
+        assert_eq!(hint.unwrap().to_string(), "%,d items");
+
+        let (_, _, hint) =
+            parse_alter_format("ALTER TABLE emp ALTER COLUMN salary DROP FORMAT").unwrap();
+        assert_eq!(hint, None);
+    }
+
+    #[test]
+    fn test_parse_alter_format_errors() {
+        assert!(parse_alter_format("ALTER TABLE emp").is_err());
+        assert!(
+            parse_alter_format("ALTER TABLE emp ALTER COLUMN salary SET FORMAT money").is_err()
+        );
+        assert!(
+            parse_alter_format("ALTER TABLE emp ALTER COLUMN salary SET FORMAT 'bogus'").is_err()
+        );
+        assert!(
+            parse_alter_format("ALTER TABLE emp ALTER COLUMN salary SET FORMAT 'money' x").is_err()
+        );
+        assert!(parse_alter_format("ALTER TABLE emp DROP COLUMN salary FORMAT").is_err());
+    }
+
+    #[test]
+    fn test_parse_load() {
+        let load = |input: &str| {
+            let (path, append, table, options) = parse_load(input).unwrap();
+            assert_eq!(options, CsvOptions::default());
+            (path, append, table)
+        };
+        let expected =
+            |path: &str, append: bool, table: &str| (path.to_string(), append, table.to_string());
+
+        // The unquoted form is unchanged
+        assert_eq!(
+            load("LOAD data/sales.csv AS sales"),
+            expected("data/sales.csv", false, "sales")
+        );
+        assert_eq!(
+            load("load a.csv into Sales"),
+            expected("a.csv", true, "Sales")
+        );
+
+        assert_eq!(
+            load("LOAD '/data/My Files/sales 2024.csv' AS sales"),
+            expected("/data/My Files/sales 2024.csv", false, "sales")
+        );
+        assert_eq!(
+            load("LOAD \"/data/My Files/sales 2024.csv\"  INTO  sales "),
+            expected("/data/My Files/sales 2024.csv", true, "sales")
+        );
+        // A doubled quote stands for one; the other quote needs no escaping
+        assert_eq!(
+            load("LOAD 'it''s \"here\".csv' AS t"),
+            expected("it's \"here\".csv", false, "t")
+        );
+        assert_eq!(
+            load("LOAD \"say \"\"hi\"\".csv\" AS t"),
+            expected("say \"hi\".csv", false, "t")
+        );
+
+        for invalid in [
+            "LOAD",
+            "LOAD a.csv",
+            "LOAD a.csv AS",
+            "LOAD a.csv ONTO t",
+            "LOAD a.csv AS t extra",
+            "LOAD my file.csv AS t",
+            "LOAD 'a.csv'AS t",
+            "LOAD '' AS t",
+            "LOADa.csv AS t",
+        ] {
+            assert!(parse_load(invalid).is_err(), "{}", invalid);
+        }
+        let err = parse_load("LOAD 'a.csv AS t").unwrap_err();
+        assert!(err.to_string().contains("Unterminated '"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_load_options() {
+        let options = |input: &str| parse_load(input).unwrap().3;
+
+        assert_eq!(
+            options("LOAD a.tsv AS t DELIMITER '\\t' NOHEADER"),
+            CsvOptions {
+                delimiter: b'\t',
+                has_header: false,
+                ..CsvOptions::default()
+            }
+        );
+        assert!(!options("LOAD a.csv AS t strict_header").rename_duplicate_headers);
+        assert_eq!(
+            options("LOAD a.txt INTO t delimiter | quote \"'\" null 'NULL'"),
+            CsvOptions {
+                delimiter: b'|',
+                quote: b'\'',
+                null_token: Some("NULL".to_string()),
+                ..CsvOptions::default()
+            }
+        );
+        assert_eq!(options("LOAD a.csv AS t SAMPLE 100").sample_rows, Some(100));
+        assert_eq!(
+            options("LOAD a.csv AS t TYPE id INT type amount float MAX_ERRORS 10"),
+            CsvOptions {
+                column_types: vec![
+                    ("id".to_string(), DataType::Int64),
+                    ("amount".to_string(), DataType::Float64),
+                ],
+                max_errors: 10,
+                ..CsvOptions::default()
+            }
+        );
+
+        for invalid in [
+            "LOAD a.csv AS t SAMPLE",
+            "LOAD a.csv AS t SAMPLE 0",
+            "LOAD a.csv AS t SAMPLE many",
+            "LOAD a.csv AS t TYPE id",
+            "LOAD a.csv AS t TYPE id NUMBER",
+            "LOAD a.csv AS t MAX_ERRORS -1",
+            "LOAD a.csv AS t DELIMITER",
+            "LOAD a.csv AS t DELIMITER '||'",
+            "LOAD a.csv AS t HEADERLESS",
+            "LOAD a.csv AS t 'NOHEADER'",
+        ] {
+            assert!(parse_load(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_load_with_delimiter_and_noheader() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("events.tsv");
+        std::fs::write(&file, "1\t\"a\tb\"\tNULL\n2\tc\t2.5\n").unwrap();
+
+        let mut repl = Repl::new();
+        repl.execute_command(&format!(
+            "LOAD '{}' AS events DELIMITER '\\t' NOHEADER NULL 'NULL'",
+            file.display()
+        ))
+        .unwrap();
+
+        let table = repl.catalog.get_table("events").unwrap();
+        assert_eq!(table.column_names(), vec!["col_0", "col_1", "col_2"]);
+        assert_eq!(
+            table.get_value("col_1", 0).unwrap(),
+            Value::String("a\tb".to_string())
+        );
+        assert_eq!(table.get_value("col_2", 0).unwrap(), Value::Null);
+        assert_eq!(table.get_value("col_2", 1).unwrap(), Value::Float64(2.5));
+    }
+
+    #[test]
+    fn test_load_quoted_path_with_spaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("My Files");
+        std::fs::create_dir(&folder).unwrap();
+        let file = folder.join("sales 2024.csv");
+        std::fs::copy(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv"),
+            &file,
+        )
+        .unwrap();
+
+        let mut repl = Repl::new();
+        repl.execute_command(&format!("LOAD '{}' AS sales", file.display()))
+            .unwrap();
+        repl.execute_command(&format!("LOAD \"{}\" INTO sales", file.display()))
+            .unwrap();
+        assert_eq!(repl.catalog.get_table("sales").unwrap().row_count(), 20);
+
+        // Errors name the path that was tried
+        let err = repl
+            .execute_command(&format!("LOAD '{}' AS dir", folder.display()))
+            .unwrap_err();
+        assert!(err.to_string().contains("is a directory"), "{}", err);
+        let missing = folder.join("missing.csv");
+        let err = repl
+            .execute_command(&format!("LOAD '{}' AS missing", missing.display()))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(&format!("File not found: '{}'", missing.display())),
+            "{}",
+            err
+        );
+
+        // A relative path is resolved against the current directory
+        let err = repl
+            .execute_command("LOAD no_such_file.csv AS t")
+            .unwrap_err();
+        let absolute = std::env::current_dir().unwrap().join("no_such_file.csv");
+        assert!(
+            err.to_string().contains(&absolute.display().to_string()),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_drop_table() {
+        assert_eq!(
+            parse_drop_table("DROP TABLE sales").unwrap(),
+            ("sales".to_string(), false)
+        );
+        assert_eq!(
+            parse_drop_table("drop table if exists Sales").unwrap(),
+            ("Sales".to_string(), true)
+        );
+
+        assert!(parse_drop_table("DROP TABLE").is_err());
+        assert!(parse_drop_table("DROP sales").is_err());
+        assert!(parse_drop_table("DROP TABLE IF sales").is_err());
+        assert!(parse_drop_table("DROP TABLE a b").is_err());
+    }
+
+    #[test]
+    fn test_parse_database_dir() {
+        assert_eq!(
+            parse_database_dir("SAVE DATABASE data/shop", "SAVE").unwrap(),
+            "data/shop"
+        );
+        assert_eq!(
+            parse_database_dir("open database  my dbs/shop ", "OPEN").unwrap(),
+            "my dbs/shop"
+        );
+
+        assert!(parse_database_dir("SAVE DATABASE", "SAVE").is_err());
+        assert!(parse_database_dir("SAVE DATABASEdir", "SAVE").is_err());
+        assert!(parse_database_dir("SAVE TABLE dir", "SAVE").is_err());
+    }
+
+    #[test]
+    fn test_parse_export() {
+        assert_eq!(
+            parse_export("EXPORT SELECT a, b FROM t TO 'out/result.csv'").unwrap(),
+            (
+                "SELECT a, b FROM t".to_string(),
+                "out/result.csv".to_string()
+            )
+        );
+        assert_eq!(
+            parse_export("export select * from t where name = 'to'  to  'my file.csv' ").unwrap(),
+            (
+                "select * from t where name = 'to'".to_string(),
+                "my file.csv".to_string()
+            )
+        );
+
+        assert!(parse_export("EXPORT SELECT * FROM t").is_err());
+        assert!(parse_export("EXPORT SELECT * FROM t TO out.csv").is_err());
+        assert!(parse_export("EXPORT SELECT * FROM t INTO 'out.csv'").is_err());
+        assert!(parse_export("EXPORT TO 'out.csv'").is_err());
+        assert!(parse_export("EXPORT SELECT * FROM t TO ''").is_err());
+    }
+
+    #[test]
+    fn test_parse_export_parquet() {
+        assert_eq!(
+            parse_export_parquet("export sales  to parquet  'out/my sales.parquet' ").unwrap(),
+            Some(("sales".to_string(), "out/my sales.parquet".to_string()))
+        );
+        // Anything else is a CSV export
+        assert_eq!(
+            parse_export_parquet("EXPORT SELECT * FROM t TO 'parquet'").unwrap(),
+            None
+        );
+
+        assert!(parse_export_parquet("EXPORT t TO PARQUET").is_err());
+        assert!(parse_export_parquet("EXPORT t TO PARQUET out.parquet").is_err());
+        assert!(parse_export_parquet("EXPORT t TO PARQUET ''").is_err());
+    }
+
+    #[test]
+    fn test_parse_history_commands() {
+        assert_eq!(parse_history_count(".history").unwrap(), 20);
+        assert_eq!(parse_history_count(".HISTORY 5").unwrap(), 5);
+        assert!(parse_history_count(".history five").is_err());
+        assert!(parse_history_count(".history 1 2").is_err());
+
+        assert!(is_rerun_command("!3"));
+        assert!(is_rerun_command("  .RERUN 3"));
+        assert!(!is_rerun_command(".rerunner"));
+        assert!(!is_rerun_command("SELECT '!' FROM t"));
+        assert_eq!(parse_rerun("!3").unwrap(), 3);
+        assert_eq!(parse_rerun(".rerun 12").unwrap(), 12);
+        assert!(parse_rerun("!0").is_err());
+        assert!(parse_rerun("!x").is_err());
+        assert!(parse_rerun(".rerun").is_err());
+
+        assert_eq!(
+            parse_save_history(".save-history out.txt").unwrap(),
+            "out.txt"
+        );
+        assert_eq!(
+            parse_save_history(".save-history 'my history.txt'").unwrap(),
+            "my history.txt"
+        );
+        assert!(parse_save_history(".save-history").is_err());
+        assert!(parse_save_history(".save-history 'a' b").is_err());
+    }
+
+    #[test]
+    fn test_rerun_history_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut repl = Repl::with_history_file(dir.path().join("history"));
+        repl.execute_command("CREATE TABLE t (a INT)").unwrap();
+        for entry in ["INSERT INTO t VALUES (1)", "!1", "SELECT 1; !1"] {
+            repl.editor.add_history_entry(entry).unwrap();
+        }
+
+        repl.process_command("!1").unwrap();
+        repl.process_command(".rerun 1").unwrap();
+        assert_eq!(repl.catalog.get_table("t").unwrap().row_count(), 2);
+        // Only the expanded query was added, and a repeat of it just once
+        assert_eq!(repl.editor.history().len(), 4);
+
+        // Entries that re-run other entries are refused, so nothing loops
+        assert!(repl.process_command("!2").is_err());
+        assert!(repl.process_command("!3").is_err());
+        assert!(repl.process_command("!99").is_err());
+        repl.execute_command(".history 2").unwrap();
+
+        let saved = dir.path().join("saved history");
+        repl.execute_command(&format!(".save-history '{}'", saved.display()))
+            .unwrap();
+        let text = std::fs::read_to_string(&saved).unwrap();
+        assert!(text.contains("INSERT INTO t VALUES (1)"));
+    }
+
+    #[test]
+    fn test_split_statements() {
+        assert_eq!(
+            split_statements("LOAD a.csv AS a; LOAD b.csv AS b; SELECT COUNT(*) FROM a;"),
+            vec![
+                "LOAD a.csv AS a",
+                "LOAD b.csv AS b",
+                "SELECT COUNT(*) FROM a"
+            ]
+        );
+        assert_eq!(
+            split_statements(" ;; SHOW TABLES ;  ; "),
+            vec!["SHOW TABLES"]
+        );
+        assert_eq!(
+            split_statements("SELECT * FROM t WHERE s = 'a;b' OR \"x;y\" = 'it''s;'; HELP"),
+            vec![
+                "SELECT * FROM t WHERE s = 'a;b' OR \"x;y\" = 'it''s;'",
+                "HELP"
+            ]
+        );
+        assert!(split_statements(" ; ").is_empty());
+    }
+
+    #[test]
+    fn test_needs_more_input() {
+        // SQL statements continue until a semicolon ends them
+        assert!(needs_more_input("SELECT name"));
+        assert!(needs_more_input("select name\nFROM t"));
+        assert!(needs_more_input("WITH x AS (SELECT 1)"));
+        assert!(needs_more_input("EXPLAIN ANALYZE SELECT *"));
+        assert!(!needs_more_input("SELECT name\nFROM t;"));
+        assert!(!needs_more_input("INSERT INTO t VALUES (1) ;  "));
+        assert!(!needs_more_input("CREATE TABLE t (a INT);"));
+
+        // Semicolons inside quotes do not end the statement
+        assert!(needs_more_input("SELECT * FROM t WHERE s = 'a;"));
+        assert!(needs_more_input("SELECT * FROM t WHERE s = 'it''s;'"));
+        assert!(needs_more_input("SELECT \"x;\" FROM t"));
+        assert!(!needs_more_input("SELECT * FROM t WHERE s = 'a;b';"));
+
+        // Commands are single-line, with or without a semicolon
+        assert!(!needs_more_input("LOAD data.csv AS t"));
+        assert!(!needs_more_input(".tables"));
+        assert!(!needs_more_input("SET timing ON"));
+        assert!(!needs_more_input("SELECTION"));
+    }
+
+    #[test]
+    fn test_multi_line_statement_executes() {
+        let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+        let mut repl = Repl::new();
+        repl.process_command(&format!("LOAD {} AS t", data))
+            .unwrap();
+
+        repl.process_command("SELECT COUNT(*)\nFROM t\n\tWHERE id > 1;")
+            .unwrap();
+        repl.process_command("EXPLAIN\nANALYZE SELECT * FROM t")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_multiple_statements_per_line() {
+        let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+        let mut repl = Repl::new();
+        repl.process_command(&format!(
+            "LOAD {} AS a; LOAD {} AS b;; SELECT COUNT(*) FROM a;",
+            data, data
+        ))
+        .unwrap();
+        assert!(repl.catalog.table_exists("a"));
+        assert!(repl.catalog.table_exists("b"));
+
+        // A trailing semicolon on a single statement is accepted
+        assert!(repl.process_command("SELECT COUNT(*) FROM b;").is_ok());
+        assert!(repl.process_command(";").is_ok());
+
+        // The failing statement is reported by position, and later ones don't run
+        let err = repl
+            .process_command("DROP TABLE a; SELECT * FROM missing; DROP TABLE b")
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Statement 2 of 3"),
+            "unexpected error: {}",
+            err
+        );
+        assert!(!repl.catalog.table_exists("a"));
+        assert!(repl.catalog.table_exists("b"));
+    }
+
+    #[test]
+    fn test_export_command_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("seniors.csv");
+        let mut repl = Repl::new();
+        repl.execute_command(&format!(
+            "LOAD {} AS emp",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv")
+        ))
+        .unwrap();
+
+        let sql = "SELECT name, age FROM emp WHERE age > 30 ORDER BY name";
+        repl.execute_command(&format!("EXPORT {} TO '{}'", sql, path.display()))
+            .unwrap();
+        repl.execute_command(&format!("LOAD {} AS seniors", path.display()))
+            .unwrap();
+
+        let expected: Vec<Vec<Value>> = repl.catalog.query(sql).unwrap().rows().collect();
+        let exported: Vec<Vec<Value>> = repl
+            .catalog
+            .query("SELECT name, age FROM seniors")
+            .unwrap()
+            .rows()
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(exported, expected);
+    }
+
+    #[test]
+    fn test_load_into_appends_rows() {
+        let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+        let mut repl = Repl::new();
+        repl.execute_command(&format!("LOAD {} AS emp", data))
+            .unwrap();
+        repl.execute_command(&format!("load {} into emp", data))
+            .unwrap();
+        assert_eq!(repl.catalog.get_table("emp").unwrap().row_count(), 20);
+
+        assert!(repl
+            .execute_command(&format!("LOAD {} INTO missing", data))
+            .is_err());
+        assert!(repl
+            .execute_command(&format!("LOAD {} ONTO emp", data))
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_load_gzip_file() {
+        let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+        let mut repl = Repl::new();
+        repl.execute_command(&format!("LOAD {}.gz AS packed", data))
+            .unwrap();
+        repl.execute_command(&format!("LOAD {} AS plain", data))
+            .unwrap();
+        // Appending compressed rows works the same way
+        repl.execute_command(&format!("LOAD {}.gz INTO plain", data))
+            .unwrap();
+
+        let packed = repl.catalog.get_table("packed").unwrap();
+        let plain = repl.catalog.get_table("plain").unwrap();
+        assert_eq!(packed.column_names(), plain.column_names());
+        assert_eq!(packed.row_count() * 2, plain.row_count());
+    }
+
+    #[test]
+    #[cfg(feature = "parquet")]
+    fn test_export_and_load_parquet() {
+        let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("data.parquet");
+        let mut repl = Repl::new();
+        repl.execute_command(&format!("LOAD {} AS plain", data))
+            .unwrap();
+        repl.execute_command(&format!("EXPORT plain TO PARQUET '{}'", file.display()))
+            .unwrap();
+        repl.execute_command(&format!("LOAD '{}' AS columnar", file.display()))
+            .unwrap();
+
+        let plain = repl.catalog.get_table("plain").unwrap();
+        let columnar = repl.catalog.get_table("columnar").unwrap();
+        assert_eq!(columnar.column_names(), plain.column_names());
+        assert_eq!(columnar.row_count(), plain.row_count());
+
+        // Parquet files are loaded whole, without CSV options
+        let err = repl
+            .execute_command(&format!("LOAD '{}' INTO plain", file.display()))
+            .unwrap_err();
+        assert!(err.to_string().contains("Parquet"), "{}", err);
+        let err = repl
+            .execute_command(&format!("LOAD '{}' AS other NOHEADER", file.display()))
+            .unwrap_err();
+        assert!(err.to_string().contains("Parquet"), "{}", err);
+        assert!(repl
+            .execute_command(&format!("EXPORT missing TO PARQUET '{}'", file.display()))
+            .is_err());
+
+        // The table name matches regardless of case, as in SELECT
+        let mixed = dir.path().join("mixed.parquet");
+        repl.execute_command(&format!("LOAD {} AS Mixed", data))
+            .unwrap();
+        repl.execute_command(&format!("EXPORT mixed TO PARQUET '{}'", mixed.display()))
+            .unwrap();
+        repl.execute_command(&format!("LOAD '{}' AS reloaded", mixed.display()))
+            .unwrap();
+        let reloaded = repl.catalog.get_table("reloaded").unwrap();
+        assert_eq!(reloaded.row_count(), plain.row_count());
+    }
+
+    #[test]
+    #[cfg(not(feature = "parquet"))]
+    fn test_parquet_needs_the_feature() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("data.parquet");
+        std::fs::write(&file, "").unwrap();
+        let mut repl = Repl::new();
+        let err = repl
+            .execute_command(&format!("LOAD '{}' AS t", file.display()))
+            .unwrap_err();
+        assert!(err.to_string().contains("--features parquet"), "{}", err);
+    }
+
+    #[test]
+    fn test_load_skips_bad_rows_up_to_max_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("sales.csv");
+        std::fs::write(&file, "id,amount\n1,2.5\n2,oops\n3\n4,1.5\n").unwrap();
+        let load = |modifiers: &str| format!("LOAD '{}' AS sales {}", file.display(), modifiers);
+
+        let mut repl = Repl::new();
+        let err = repl
+            .execute_command(&load("TYPE amount FLOAT"))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DatabaseError::IngestError(IngestError { line: 3, .. })
+        ));
+        assert!(!repl.catalog.table_exists("sales"));
+
+        repl.execute_command(&load("TYPE amount FLOAT MAX_ERRORS 2"))
+            .unwrap();
+        assert_eq!(repl.catalog.get_table("sales").unwrap().row_count(), 2);
+        let lines: Vec<usize> = repl.last_load_errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 4]);
+        repl.execute_command("last load errors").unwrap();
+
+        assert_eq!(
+            skipped_rows_summary(&LoadReport {
+                rows_loaded: 499_987,
+                errors: vec![repl.last_load_errors[0].clone(); 13],
+                ..LoadReport::default()
+            }),
+            "loaded 499,987 rows, skipped 13 (see details with LAST LOAD ERRORS)"
+        );
+    }
+
+    #[test]
+    fn test_generate_command() {
+        let mut repl = Repl::new();
+        repl.execute_command("GENERATE bench ROWS 1000").unwrap();
+        repl.execute_command("generate seeded rows 1000 seed 42")
+            .unwrap();
+
+        let bench = repl.catalog.get_table("bench").unwrap();
+        assert_eq!(bench.row_count(), 1000);
+        assert_eq!(
+            bench.column_names(),
+            vec!["id", "value", "price", "category", "customer"]
+        );
+        // The default seed is 42
+        let seeded = repl.catalog.get_table("seeded").unwrap();
+        assert_eq!(
+            bench.get_value("price", 999).unwrap(),
+            seeded.get_value("price", 999).unwrap()
+        );
+
+        for invalid in [
+            "GENERATE bench ROWS 10",
+            "GENERATE other ROWS many",
+            "GENERATE other 10",
+            "GENERATE other ROWS 10 SEED",
+        ] {
+            assert!(repl.execute_command(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_query_cancellation_and_timeout() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let mut repl = Repl::new();
+        repl.execute_command("GENERATE big ROWS 300000").unwrap();
+        let sort = "SELECT id, price FROM big ORDER BY customer, price";
+
+        repl.execute_command("SET TIMEOUT 1").unwrap();
+        assert_eq!(repl.settings.timeout, Some(Duration::from_millis(1)));
+        let err = repl.execute_command(sort).unwrap_err();
+        assert!(err.to_string().contains("timed out after 1 ms"), "{}", err);
+
+        // The REPL is still usable, and a cancellation from an earlier
+        // query does not leak into the next one
+        repl.execute_command("SET timeout off").unwrap();
+        assert_eq!(repl.settings.timeout, None);
+        repl.interrupt.cancel();
+        repl.execute_command("SELECT COUNT(*) FROM big").unwrap();
+
+        // Ctrl+C cancels through the shared token; keep pressing it until
+        // the query gives up, as it is reset when the query starts
+        let interrupt = repl.interrupt.clone();
+        let done = Arc::new(AtomicBool::new(false));
+        let canceller = {
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    interrupt.cancel();
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            })
+        };
+        let result = repl.run_query(sort);
+        done.store(true, Ordering::Relaxed);
+        canceller.join().unwrap();
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("Query cancelled"), "{}", err);
+
+        for invalid in ["SET TIMEOUT", "SET TIMEOUT soon", "SET PARALLELISM 4"] {
+            assert!(repl.execute_command(invalid).is_err(), "{}", invalid);
+        }
+        repl.execute_command("SET TIMEOUT 0").unwrap();
+        assert_eq!(repl.settings.timeout, None);
+    }
+
+    #[test]
+    fn test_set_and_show_settings() {
+        let mut repl = Repl::new();
+        repl.execute_command("GENERATE t ROWS 100").unwrap();
+        assert_eq!(repl.run_query("SELECT id FROM t").unwrap().batch_count(), 1);
+
+        repl.execute_command("SET batch_size = 10").unwrap();
+        let result = repl.run_query("SELECT id FROM t").unwrap();
+        assert_eq!(result.batch_count(), 10);
+        assert_eq!(result.row_count(), 100);
+
+        repl.execute_command("SET MAX_DISPLAY_ROWS=5").unwrap();
+        repl.execute_command("set timing off").unwrap();
+        repl.execute_command("SET timeout = 2000").unwrap();
+        repl.execute_command("SET float_precision = 2").unwrap();
+        repl.execute_command("SET max_width = 100").unwrap();
+        repl.execute_command("SET memstats ON").unwrap();
+        repl.execute_command("SET memory_limit = 1048576").unwrap();
+        let expected = Settings {
+            batch_size: 10,
+            max_display_rows: 5,
+            timing: false,
+            memstats: true,
+            memory_limit: Some(1 << 20),
+            timeout: Some(Duration::from_millis(2000)),
+            float_precision: Some(2),
+            max_width: Some(100),
+        };
+        assert_eq!(repl.settings, expected);
+        assert_eq!(
+            Value::Float64(2.0 / 3.0).format(&repl.settings.format_options()),
+            "0.67"
+        );
+        assert_eq!(repl.settings, expected);
+        repl.execute_command("SHOW SETTINGS").unwrap();
+        repl.execute_command("SELECT id FROM t").unwrap();
+
+        // Rejected values leave every setting unchanged
+        for invalid in [
+            "SET batch_size = 0",
+            "SET batch_size = -1",
+            "SET max_display_rows = 0",
+            "SET max_display_rows = 1000000",
+            "SET timing = maybe",
+            "SET memstats = sometimes",
+            "SET memory_limit = 0",
+            "SET memory_limit = lots",
+            "SET timeout = soon",
+            "SET float_precision = 21",
+            "SET float_precision = -1",
+            "SET max_width = 0",
+            "SET max_width = wide",
+            "SET colour = blue",
+        ] {
+            let err = repl.execute_command(invalid).unwrap_err();
+            assert!(matches!(err, DatabaseError::SettingsError(_)), "{}", err);
+            assert_eq!(repl.settings, expected, "{}", invalid);
+        }
+
+        // Malformed SET commands are syntax errors instead
+        for malformed in ["SET batch_size =", "SET = 10"] {
+            let err = repl.execute_command(malformed).unwrap_err();
+            assert!(matches!(err, DatabaseError::ParserError(_)), "{}", err);
+            assert_eq!(repl.settings, expected, "{}", malformed);
+        }
+
+        repl.execute_command("SET float_precision = off").unwrap();
+        assert_eq!(repl.settings.float_precision, None);
+        repl.execute_command("SET max_width = OFF").unwrap();
+        assert_eq!(repl.settings.max_width, None);
+    }
+
+    /// A batch with an Int64 `id` column and a String `note` column
+    fn notes_batch(notes: &[&str]) -> Batch {
+        let mut ids = IntColumn::new();
+        let mut text = StringColumn::new();
+        for (id, note) in notes.iter().enumerate() {
+            ids.push_value(Value::Int64(id as i64 + 1)).unwrap();
+            text.push_value(Value::String(note.to_string())).unwrap();
+        }
+        Batch::new(vec![Arc::new(ids), Arc::new(text)])
+    }
+
+    #[test]
+    fn test_render_batches_truncates_and_escapes_cells() {
+        let long = "x".repeat(80);
+        let batch = notes_batch(&["first\nline\tend", &long, "short"]);
+        let names = vec!["id".to_string(), "note".to_string()];
+        let rendered = render_batches(
+            &[batch],
+            &names,
+            &[None, None],
+            DEFAULT_MAX_DISPLAY_ROWS,
+            &FormatOptions::default(),
+            None,
+        );
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        // Borders, header and one line per row, all the same width
+        assert_eq!(lines.len(), 8, "{}", rendered);
+        let table_width = display_width(lines[0]);
+        assert_eq!(table_width, (2 + 3) + (MAX_COLUMN_WIDTH + 3) + 1);
+        for line in &lines[..7] {
+            assert_eq!(display_width(line), table_width, "{}", line);
+        }
+        assert!(lines[3].contains("first\\nline\\tend"), "{}", lines[3]);
+        assert!(lines[4].contains(&format!("{}…", "x".repeat(MAX_COLUMN_WIDTH - 1))));
+        assert_eq!(lines[7], "(3 rows)");
+    }
+
+    #[test]
+    fn test_render_batches_switches_to_records_when_too_wide() {
+        let batch = notes_batch(&["a note", "another"]);
+        let names = vec!["id".to_string(), "note".to_string()];
+        let render = |max_rows, max_width| {
+            render_batches(
+                std::slice::from_ref(&batch),
+                &names,
+                &[None, None],
+                max_rows,
+                &FormatOptions::default(),
+                max_width,
+            )
+        };
+
+        assert!(render(10, Some(80)).starts_with('┌'));
+        assert_eq!(
+            render(10, Some(12)),
+            "-[ RECORD 1 ]\n\
+             id   │ 1\n\
+             note │ a note\n\
+             -[ RECORD 2 ]\n\
+             id   │ 2\n\
+             note │ another\n\
+             (2 rows)\n"
+        );
+        assert_eq!(
+            render(1, Some(14)),
+            "-[ RECORD 1 ]-\n\
+             id   │ 1\n\
+             note │ a note\n\
+             (2 rows total, showing first 1)\n"
+        );
+        assert_eq!(
+            render_batches(&[], &names, &[], 10, &FormatOptions::default(), None),
+            "Empty result set.\n"
+        );
+    }
+
+    #[test]
+    fn test_stats_command() {
+        let mut repl = Repl::new();
+        repl.execute_command(&format!(
+            "LOAD {} AS emp",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv")
+        ))
+        .unwrap();
+
+        assert!(repl.execute_command("STATS emp").is_ok());
+        assert!(repl.execute_command("stats emp").is_ok());
+        assert!(repl.execute_command("STATS missing").is_err());
+        assert!(repl.execute_command("STATS emp extra").is_err());
+    }
+
+    #[test]
+    fn test_aggregate_of_empty_table_shows_one_row() {
+        let mut repl = Repl::new();
+        repl.execute_command("CREATE TABLE t (a INT, s TEXT)")
+            .unwrap();
+
+        let result = repl.run_query("SELECT COUNT(*), MAX(s) FROM t").unwrap();
+        assert_eq!(
+            result.rows().collect::<Vec<_>>(),
+            vec![vec![Value::Int64(0), Value::Null]]
+        );
+        let result = repl
+            .run_query("SELECT a, COUNT(*) FROM t GROUP BY a")
+            .unwrap();
+        assert_eq!(result.row_count(), 0);
+    }
+
+    #[test]
+    fn test_compress_command() {
+        let mut repl = Repl::new();
+        repl.execute_command("GENERATE bench ROWS 5000 SEED 3")
+            .unwrap();
+        let before = repl.run_query("SELECT * FROM bench ORDER BY id").unwrap();
+
+        repl.execute_command("COMPRESS bench").unwrap();
+        let table = repl.catalog.get_table("bench").unwrap();
+        assert!(table
+            .get_column("id")
+            .unwrap()
+            .as_any()
+            .is::<mini_rust_olap::column::DeltaColumn>());
+        let after = repl.run_query("SELECT * FROM bench ORDER BY id").unwrap();
+        assert_eq!(
+            after.rows().collect::<Vec<_>>(),
+            before.rows().collect::<Vec<_>>()
+        );
+
+        // Compressing again changes nothing
+        repl.execute_command("compress bench").unwrap();
+        assert!(repl.execute_command("COMPRESS missing").is_err());
+        assert!(repl.execute_command("COMPRESS bench extra").is_err());
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn test_save_and_open_database_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("db");
+        let mut repl = Repl::new();
+        repl.catalog
+            .register_table(
+                load_csv(
+                    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv"),
+                    "emp".to_string(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        repl.execute_command(&format!("SAVE DATABASE {}", db.display()))
+            .unwrap();
+        repl.execute_command("DROP TABLE emp").unwrap();
+        repl.execute_command(&format!("OPEN DATABASE {}", db.display()))
+            .unwrap();
+        assert_eq!(repl.catalog.get_table("emp").unwrap().row_count(), 10);
+
+        // A failed OPEN keeps the current tables
+        let missing = dir.path().join("missing");
+        assert!(repl
+            .execute_command(&format!("OPEN DATABASE {}", missing.display()))
+            .is_err());
+        assert!(repl.catalog.table_exists("emp"));
+    }
+
+    #[test]
+    fn test_insert_command() {
+        let mut repl = Repl::new();
+        repl.catalog
+            .register_table(
+                load_csv(
+                    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv"),
+                    "emp".to_string(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        repl.execute_command(
+            "insert into emp values (11, 'Kate', 30, 50000, 'Sales', '2021-01-01')",
+        )
+        .unwrap();
+        assert_eq!(repl.catalog.get_table("emp").unwrap().row_count(), 11);
+
+        assert!(repl.execute_command("INSERT INTO emp VALUES (1)").is_err());
+        assert_eq!(repl.catalog.get_table("emp").unwrap().row_count(), 11);
+    }
+
+    #[test]
+    fn test_create_table_command() {
+        let mut repl = Repl::new();
+
+        repl.execute_command("CREATE TABLE notes (id INT, body TEXT)")
+            .unwrap();
+        repl.execute_command("DESCRIBE notes").unwrap();
+        let table = repl.catalog.get_table("notes").unwrap();
+        assert_eq!(table.column_names(), vec!["id", "body"]);
+        assert_eq!(table.row_count(), 0);
+
+        let err = repl
+            .execute_command("create table notes (x INT)")
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_serve() {
+        assert_eq!(parse_serve("SERVE 8080").unwrap(), 8080);
+        assert_eq!(parse_serve("serve  0").unwrap(), 0);
+        assert!(parse_serve("SERVE").is_err());
+        assert!(parse_serve("SERVE 70000").is_err());
+        assert!(parse_serve("SERVE 80 81").is_err());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_serve_command_publishes_catalog_changes() {
+        let mut repl = Repl::new();
+        repl.execute_command("SERVE 0").unwrap();
+        let served = repl.served_catalog.clone().unwrap();
+        assert!(!served.read().table_exists("notes"));
+
+        repl.execute_command("CREATE TABLE notes (id INT)").unwrap();
+        repl.execute_command("INSERT INTO notes VALUES (1)")
+            .unwrap();
+        assert_eq!(served.get_table("notes").unwrap().row_count(), 1);
+    }
+
+    #[test]
+    fn test_create_table_as_command() {
+        let mut repl = Repl::new();
+        repl.execute_command("CREATE TABLE notes (id INT, body TEXT)")
+            .unwrap();
+        repl.execute_command("INSERT INTO notes VALUES (1, 'a'), (2, 'b'), (3, 'c')")
+            .unwrap();
+
+        repl.execute_command(
+            "CREATE TABLE\nfilled AS\nSELECT id AS note_id, body FROM notes WHERE id > 1",
+        )
+        .unwrap();
+        let table = repl.catalog.get_table("filled").unwrap();
+        assert_eq!(table.column_names(), vec!["note_id", "body"]);
+        assert_eq!(table.row_count(), 2);
+
+        let err = repl
+            .execute_command("CREATE TABLE notes AS SELECT id FROM notes")
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+    }
+
+    #[test]
+    fn test_tokenizer_error_reaches_repl() {
+        let mut repl = Repl::new();
+        repl.execute_command("CREATE TABLE emp (id INT)").unwrap();
+
+        let err = repl
+            .execute_command("SELECT id FROM emp WHERE id # 1")
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unexpected character '#' at line 1, column 29"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_drop_table_command() {
+        let mut repl = Repl::new();
+        repl.catalog
+            .register_table(
+                load_csv(
+                    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv"),
+                    "emp".to_string(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        repl.execute_command("DROP TABLE emp").unwrap();
+        assert!(!repl.catalog.table_exists("emp"));
+
+        let err = repl.execute_command("DROP TABLE emp").unwrap_err();
+        assert!(err.to_string().contains("not found"), "{}", err);
+        repl.execute_command("DROP TABLE IF EXISTS emp").unwrap();
+    }
+
+    #[test]
+    fn test_table_commands_ignore_name_case() {
+        let mut repl = Repl::new();
+        repl.execute_command("GENERATE Zed ROWS 10").unwrap();
+        assert!(repl.catalog.table_exists("Zed"));
+
+        repl.execute_command("ALTER TABLE zed ALTER COLUMN price SET FORMAT 'money'")
+            .unwrap();
+        let table = repl.catalog.get_table("Zed").unwrap();
+        assert_eq!(table.display_hint("price"), Some(&DisplayHint::Money));
+        repl.execute_command(".verify ZED").unwrap();
+
+        repl.execute_command("DROP TABLE IF EXISTS zed").unwrap();
+        assert!(!repl.catalog.table_exists("Zed"));
+
+        repl.execute_command("GENERATE Zed ROWS 10").unwrap();
+        repl.execute_command("DROP TABLE zed").unwrap();
+        assert!(!repl.catalog.table_exists("Zed"));
+
+        // Names that differ only by case still need the exact spelling
+        repl.execute_command("GENERATE Zed ROWS 10").unwrap();
+        repl.execute_command("GENERATE ZED ROWS 10").unwrap();
+        let err = repl
+            .execute_command("DROP TABLE IF EXISTS zed")
+            .unwrap_err();
+        assert!(err.to_string().contains("ambiguous"), "{}", err);
+        assert!(repl.catalog.table_exists("Zed") && repl.catalog.table_exists("ZED"));
+    }
+
+    #[test]
+    fn test_result_display_hints_follow_source_columns() {
+        let mut catalog = Catalog::new();
+        let mut table = load_csv(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv"),
+            "emp".to_string(),
+        )
+        .unwrap();
+        table
+            .set_display_hint("salary", Some(DisplayHint::Money))
+            .unwrap();
+        catalog.register_table(table).unwrap();
+
+        let query = Parser::new("SELECT name, salary FROM emp").parse().unwrap();
+        let hints = result_display_hints(
+            &catalog,
+            &query,
+            &["name".to_string(), "salary".to_string()],
+        );
+        assert_eq!(hints, vec![None, Some(DisplayHint::Money)]);
+
+        let query = Parser::new("SELECT name AS employee, salary AS pay FROM emp")
+            .parse()
+            .unwrap();
+        let hints = result_display_hints(
+            &catalog,
+            &query,
+            &["employee".to_string(), "pay".to_string()],
+        );
+        assert_eq!(hints, vec![None, Some(DisplayHint::Money)]);
+    }
 }
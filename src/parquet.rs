@@ -0,0 +1,539 @@
+//! # Parquet Module
+//!
+//! This module exports tables to and loads them from Apache Parquet files
+//! (`parquet` feature). CSV loses its types on the way out and has to infer
+//! them again on the way in; Parquet stores typed, compressed columns and is
+//! read by most other data tools.
+//!
+//! ## Type Mapping
+//!
+//! | Column type | Parquet physical type | Logical type |
+//! |-------------|-----------------------|--------------|
+//! | Int64       | INT64                 |              |
+//! | Float64     | DOUBLE                |              |
+//! | String      | BYTE_ARRAY            | UTF8         |
+//! | Boolean     | BOOLEAN               |              |
+//! | Date        | INT32                 | DATE         |
+//!
+//! NULLs are stored as Parquet nulls and display hints in the column's
+//! metadata, so a table written by [`export_parquet`] loads back with the
+//! same schema, hints and values.
+//!
+//! Files from other tools may use narrower types: smaller integers load as
+//! Int64, 32-bit floats as Float64 and large strings as String. Any other
+//! column type (timestamps, binary, decimals, nested types, ...) fails the
+//! load with an ingestion error naming each such column and its type,
+//! before any data is read.
+
+use crate::catalog::Catalog;
+use crate::column::create_column;
+use crate::error::{DatabaseError, Result};
+use crate::format::DisplayHint;
+use crate::table::Table;
+use crate::types::{DataType, Value};
+use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::basic::Compression;
+use ::parquet::file::properties::WriterProperties;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type,
+    UInt32Type, UInt8Type,
+};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Date32Array, Float64Array, Int64Array, RecordBatch, StringArray,
+};
+use arrow_schema::{DataType as ArrowType, Field, Schema};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Conventional file extension for Parquet files
+pub const FILE_EXTENSION: &str = "parquet";
+
+/// Rows written per row group (and read per batch)
+const ROW_GROUP_SIZE: usize = 64 * 1024;
+
+/// Column metadata key holding the column's display hint
+const DISPLAY_HINT_KEY: &str = "mini_rust_olap.display_hint";
+
+/// Returns true if the path has the Parquet file extension.
+pub fn is_parquet_file<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref()
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(FILE_EXTENSION))
+}
+
+// ============================================================================
+// EXPORT
+// ============================================================================
+
+/// Writes a table to a Parquet file.
+///
+/// # Arguments
+///
+/// * `table` - The table to export
+/// * `path` - Destination file (created or truncated)
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::parquet::export_parquet;
+///
+/// export_parquet(catalog.get_table("sales")?, "sales.parquet")?;
+/// ```
+pub fn export_parquet<P: AsRef<Path>>(table: &Table, path: P) -> Result<()> {
+    let path = path.as_ref();
+    let write_error = |e: &dyn std::fmt::Display| {
+        DatabaseError::ingestion_error(format!("Cannot write '{}': {}", path.display(), e))
+    };
+
+    let column_names = table.column_names();
+    let mut fields = Vec::with_capacity(column_names.len());
+    for name in &column_names {
+        let mut field = Field::new(name, arrow_type(table.get_column_type(name)?), true);
+        if let Some(hint) = table.display_hint(name) {
+            field = field.with_metadata(HashMap::from([(
+                DISPLAY_HINT_KEY.to_string(),
+                hint.to_string(),
+            )]));
+        }
+        fields.push(field);
+    }
+    let schema = Arc::new(Schema::new(fields));
+
+    let file = File::create(path).map_err(|e| {
+        DatabaseError::ingestion_error(format!("Failed to create file '{}': {}", path.display(), e))
+    })?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_max_row_group_size(ROW_GROUP_SIZE)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, Arc::clone(&schema), Some(properties))
+        .map_err(|e| write_error(&e))?;
+
+    // A record batch needs at least one column; a table without any is
+    // written as a bare schema
+    let row_count = if column_names.is_empty() {
+        0
+    } else {
+        table.row_count()
+    };
+    for start in (0..row_count).step_by(ROW_GROUP_SIZE) {
+        let rows = start..row_count.min(start + ROW_GROUP_SIZE);
+        let mut arrays = Vec::with_capacity(column_names.len());
+        for name in &column_names {
+            let column = table.get_column(name)?;
+            arrays.push(arrow_array(
+                column.data_type(),
+                column.slice(Some(rows.clone())),
+            ));
+        }
+        let batch =
+            RecordBatch::try_new(Arc::clone(&schema), arrays).map_err(|e| write_error(&e))?;
+        writer.write(&batch).map_err(|e| write_error(&e))?;
+    }
+
+    writer.close().map_err(|e| write_error(&e))?;
+    Ok(())
+}
+
+/// The Arrow type a column of `data_type` is written as
+fn arrow_type(data_type: DataType) -> ArrowType {
+    match data_type {
+        DataType::Int64 => ArrowType::Int64,
+        DataType::Float64 => ArrowType::Float64,
+        DataType::String => ArrowType::Utf8,
+        DataType::Boolean => ArrowType::Boolean,
+        DataType::Date => ArrowType::Date32,
+    }
+}
+
+/// Builds the Arrow array of a column's values; NULLs become Arrow nulls
+fn arrow_array(data_type: DataType, values: Vec<Value>) -> ArrayRef {
+    let values = values.into_iter();
+    match data_type {
+        DataType::Int64 => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::Int64(v) => Some(v),
+                    _ => None,
+                })
+                .collect::<Int64Array>(),
+        ),
+        DataType::Float64 => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::Float64(v) => Some(v),
+                    _ => None,
+                })
+                .collect::<Float64Array>(),
+        ),
+        DataType::String => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::String(s) => Some(s),
+                    _ => None,
+                })
+                .collect::<StringArray>(),
+        ),
+        DataType::Boolean => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::Boolean(b) => Some(b),
+                    _ => None,
+                })
+                .collect::<BooleanArray>(),
+        ),
+        DataType::Date => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::Date(days) => Some(days),
+                    _ => None,
+                })
+                .collect::<Date32Array>(),
+        ),
+    }
+}
+
+// ============================================================================
+// IMPORT
+// ============================================================================
+
+/// Reads a Parquet file into a table named `table_name`.
+///
+/// # Errors
+///
+/// Returns an ingestion error if the file cannot be read as Parquet, or if
+/// any column has a type without a column equivalent; the error lists every
+/// such column with its type.
+pub fn read_parquet<P: AsRef<Path>>(path: P, table_name: String) -> Result<Table> {
+    let path = path.as_ref();
+    let read_error = |e: &dyn std::fmt::Display| {
+        DatabaseError::ingestion_error(format!("Cannot read '{}': {}", path.display(), e))
+    };
+
+    let file = File::open(path).map_err(|e| {
+        DatabaseError::ingestion_error(format!("Failed to open file '{}': {}", path.display(), e))
+    })?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| read_error(&e))?;
+    let schema = Arc::clone(builder.schema());
+
+    // Check every column's type before reading any data
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    let mut unsupported = Vec::new();
+    for field in schema.fields() {
+        match column_type(field.data_type()) {
+            Some(data_type) => columns.push((field, data_type, create_column(data_type))),
+            None => unsupported.push(format!("'{}' ({})", field.name(), field.data_type())),
+        }
+    }
+    if !unsupported.is_empty() {
+        return Err(DatabaseError::ingestion_error(format!(
+            "Cannot load '{}': unsupported Parquet column type{} {}",
+            path.display(),
+            if unsupported.len() == 1 { "" } else { "s" },
+            unsupported.join(", ")
+        )));
+    }
+
+    let reader = builder
+        .with_batch_size(ROW_GROUP_SIZE)
+        .build()
+        .map_err(|e| read_error(&e))?;
+    for batch in reader {
+        let batch = batch.map_err(|e| read_error(&e))?;
+        for (index, (_, _, column)) in columns.iter_mut().enumerate() {
+            let array = batch.column(index);
+            for row in 0..array.len() {
+                match array_value(array.as_ref(), row) {
+                    Some(value) => column.push_value(value)?,
+                    None => column.push_null(),
+                }
+            }
+        }
+    }
+
+    let mut table = Table::new(table_name);
+    for (field, _, column) in columns {
+        table.add_column(field.name().clone(), column)?;
+        if let Some(spec) = field.metadata().get(DISPLAY_HINT_KEY) {
+            let hint = DisplayHint::parse(spec).map_err(|e| {
+                DatabaseError::ingestion_error(format!(
+                    "Invalid display hint '{}' on column '{}' in '{}': {}",
+                    spec,
+                    field.name(),
+                    path.display(),
+                    e
+                ))
+            })?;
+            table.set_display_hint(field.name(), Some(hint))?;
+        }
+    }
+    Ok(table)
+}
+
+/// Loads a Parquet file and registers it in the catalog as `table_name`.
+///
+/// # Arguments
+///
+/// * `path` - The Parquet file to read
+/// * `table_name` - The name to give the created table
+/// * `catalog` - The catalog to register the table in
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::catalog::Catalog;
+/// use mini_rust_olap::parquet::load_parquet;
+///
+/// let mut catalog = Catalog::new();
+/// load_parquet("sales.parquet", "sales".to_string(), &mut catalog)?;
+/// ```
+pub fn load_parquet<P: AsRef<Path>>(
+    path: P,
+    table_name: String,
+    catalog: &mut Catalog,
+) -> Result<()> {
+    catalog.register_table(read_parquet(path, table_name)?)
+}
+
+/// The column type an Arrow type loads as, if it has one
+fn column_type(arrow_type: &ArrowType) -> Option<DataType> {
+    match arrow_type {
+        ArrowType::Int8
+        | ArrowType::Int16
+        | ArrowType::Int32
+        | ArrowType::Int64
+        | ArrowType::UInt8
+        | ArrowType::UInt16
+        | ArrowType::UInt32 => Some(DataType::Int64),
+        ArrowType::Float32 | ArrowType::Float64 => Some(DataType::Float64),
+        ArrowType::Utf8 | ArrowType::LargeUtf8 => Some(DataType::String),
+        ArrowType::Boolean => Some(DataType::Boolean),
+        ArrowType::Date32 => Some(DataType::Date),
+        _ => None,
+    }
+}
+
+/// The value at `row` of an array whose type [`column_type`] accepts, or
+/// `None` for a null
+fn array_value(array: &dyn Array, row: usize) -> Option<Value> {
+    if array.is_null(row) {
+        return None;
+    }
+    let value = match array.data_type() {
+        ArrowType::Int8 => Value::Int64(array.as_primitive::<Int8Type>().value(row).into()),
+        ArrowType::Int16 => Value::Int64(array.as_primitive::<Int16Type>().value(row).into()),
+        ArrowType::Int32 => Value::Int64(array.as_primitive::<Int32Type>().value(row).into()),
+        ArrowType::Int64 => Value::Int64(array.as_primitive::<Int64Type>().value(row)),
+        ArrowType::UInt8 => Value::Int64(array.as_primitive::<UInt8Type>().value(row).into()),
+        ArrowType::UInt16 => Value::Int64(array.as_primitive::<UInt16Type>().value(row).into()),
+        ArrowType::UInt32 => Value::Int64(array.as_primitive::<UInt32Type>().value(row).into()),
+        ArrowType::Float32 => Value::Float64(array.as_primitive::<Float32Type>().value(row).into()),
+        ArrowType::Float64 => Value::Float64(array.as_primitive::<Float64Type>().value(row)),
+        ArrowType::Utf8 => Value::String(array.as_string::<i32>().value(row).to_string()),
+        ArrowType::LargeUtf8 => Value::String(array.as_string::<i64>().value(row).to_string()),
+        ArrowType::Boolean => Value::Boolean(array.as_boolean().value(row)),
+        ArrowType::Date32 => Value::Date(array.as_primitive::<Date32Type>().value(row)),
+        other => unreachable!("column_type rejects {}", other),
+    };
+    Some(value)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::{BoolColumn, Column, DateColumn, FloatColumn, IntColumn, StringColumn};
+    use arrow_array::{BinaryArray, Int32Array, TimestampMillisecondArray};
+
+    fn sample_table() -> Table {
+        let mut ids = IntColumn::new();
+        let mut prices = FloatColumn::new();
+        let mut names = StringColumn::new();
+        let mut flags = BoolColumn::new();
+        let mut dates = DateColumn::new();
+        for i in 0..5i64 {
+            ids.push_value(Value::Int64(i * 1000)).unwrap();
+            prices.push_value(Value::Float64(i as f64 / 4.0)).unwrap();
+            names
+                .push_value(Value::String(format!("name {}", i)))
+                .unwrap();
+            flags.push_value(Value::Boolean(i % 2 == 0)).unwrap();
+            dates.push_value(Value::Date(19_000 + i as i32)).unwrap();
+        }
+        ids.push_null();
+        prices.push_value(Value::Float64(f64::NAN)).unwrap();
+        names.push_null();
+        flags.push_null();
+        dates.push_null();
+
+        let mut table = Table::new("sample".to_string());
+        let columns: [(&str, Box<dyn Column>); 5] = [
+            ("id", Box::new(ids)),
+            ("price", Box::new(prices)),
+            ("name", Box::new(names)),
+            ("flag", Box::new(flags)),
+            ("day", Box::new(dates)),
+        ];
+        for (name, column) in columns {
+            table.add_column(name.to_string(), column).unwrap();
+        }
+        table
+            .set_display_hint("price", Some(DisplayHint::parse("money").unwrap()))
+            .unwrap();
+        table
+    }
+
+    fn column_values(table: &Table, name: &str) -> Vec<Value> {
+        table.get_column(name).unwrap().slice(None)
+    }
+
+    /// Writes a file with the given Arrow columns, as another tool would
+    fn write_arrow_file(path: &Path, columns: Vec<(&str, ArrayRef)>) {
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let mut writer =
+            ArrowWriter::try_new(File::create(path).unwrap(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_preserves_types_nulls_and_hints() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.parquet");
+        let table = sample_table();
+        export_parquet(&table, &path).unwrap();
+
+        let loaded = read_parquet(&path, "copy".to_string()).unwrap();
+        assert_eq!(loaded.name(), "copy");
+        assert_eq!(loaded.column_names(), table.column_names());
+        assert_eq!(loaded.row_count(), 6);
+        for name in table.column_names() {
+            assert_eq!(
+                loaded.get_column_type(&name).unwrap(),
+                table.get_column_type(&name).unwrap()
+            );
+        }
+        for name in ["id", "name", "flag", "day"] {
+            assert_eq!(column_values(&loaded, name), column_values(&table, name));
+        }
+        let prices = column_values(&loaded, "price");
+        assert_eq!(prices[..5], column_values(&table, "price")[..5]);
+        assert!(matches!(prices[5], Value::Float64(v) if v.is_nan()));
+        assert_eq!(
+            loaded.display_hint("price").map(ToString::to_string),
+            Some("money".to_string())
+        );
+        assert!(loaded.display_hint("id").is_none());
+    }
+
+    #[test]
+    fn test_round_trip_empty_tables() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut no_rows = Table::new("no_rows".to_string());
+        no_rows
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        let path = dir.path().join("no_rows.parquet");
+        export_parquet(&no_rows, &path).unwrap();
+        let loaded = read_parquet(&path, "no_rows".to_string()).unwrap();
+        assert_eq!(loaded.column_names(), vec!["id"]);
+        assert_eq!(loaded.row_count(), 0);
+
+        let path = dir.path().join("no_columns.parquet");
+        export_parquet(&Table::new("none".to_string()), &path).unwrap();
+        assert_eq!(
+            read_parquet(&path, "none".to_string())
+                .unwrap()
+                .column_count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_narrow_types_are_widened() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("narrow.parquet");
+        write_arrow_file(
+            &path,
+            vec![
+                ("small", Arc::new(Int32Array::from(vec![Some(7), None]))),
+                (
+                    "ratio",
+                    Arc::new(arrow_array::Float32Array::from(vec![0.5, 1.25])),
+                ),
+            ],
+        );
+
+        let table = read_parquet(&path, "narrow".to_string()).unwrap();
+        assert_eq!(table.get_column_type("small").unwrap(), DataType::Int64);
+        assert_eq!(
+            column_values(&table, "small"),
+            vec![Value::Int64(7), Value::Null]
+        );
+        assert_eq!(
+            column_values(&table, "ratio"),
+            vec![Value::Float64(0.5), Value::Float64(1.25)]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_types_are_listed_per_column() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.parquet");
+        write_arrow_file(
+            &path,
+            vec![
+                ("id", Arc::new(Int64Array::from(vec![1]))),
+                ("at", Arc::new(TimestampMillisecondArray::from(vec![0]))),
+                ("blob", Arc::new(BinaryArray::from(vec![&b"x"[..]]))),
+            ],
+        );
+
+        let Err(err) = read_parquet(&path, "events".to_string()) else {
+            panic!("a timestamp column should not load");
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("unsupported Parquet column types"), "{}", msg);
+        assert!(
+            msg.contains("'at' (Timestamp(Millisecond, None))"),
+            "{}",
+            msg
+        );
+        assert!(msg.contains("'blob' (Binary)"), "{}", msg);
+        assert!(!msg.contains("'id'"), "{}", msg);
+    }
+
+    #[test]
+    fn test_load_parquet_registers_the_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.parquet");
+        export_parquet(&sample_table(), &path).unwrap();
+
+        let mut catalog = Catalog::new();
+        load_parquet(&path, "imported".to_string(), &mut catalog).unwrap();
+        assert!(catalog.table_exists("imported"));
+        assert!(load_parquet(&path, "imported".to_string(), &mut catalog).is_err());
+    }
+
+    #[test]
+    fn test_not_a_parquet_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fake.parquet");
+        std::fs::write(&path, "id,name\n1,a\n").unwrap();
+        let Err(err) = read_parquet(&path, "fake".to_string()) else {
+            panic!("a CSV file should not load as Parquet");
+        };
+        assert!(err.to_string().contains("fake.parquet"), "{}", err);
+
+        assert!(is_parquet_file("data/sales.PARQUET"));
+        assert!(!is_parquet_file("data/sales.csv"));
+    }
+}
@@ -19,12 +19,23 @@
 //! ## Supported SQL Features
 //!
 //! - SELECT statement with column selection
-//! - FROM clause with table names
-//! - WHERE clause with comparison and logical operators
-//! - GROUP BY clause
-//! - Aggregate functions: COUNT, SUM, AVG, MIN, MAX
+//! - FROM clause with table names, and inner `JOIN ... ON a.col = b.col`
+//! - Derived tables: `FROM (SELECT ...) [AS] alias`
+//! - Qualified column names (`table.column`)
+//! - WHERE clause with comparison and logical operators, and [NOT] IN lists
+//! - GROUP BY clause, with an optional HAVING condition on groups
+//! - Aggregate functions: COUNT, SUM, AVG, MIN, MAX, with an optional DISTINCT
+//! - Arithmetic (+, -, *, /) in SELECT items and WHERE comparisons
+//! - TRUE and FALSE literals
+//! - INSERT INTO ... VALUES with one or more rows
+//! - CREATE TABLE with explicitly typed columns, or AS SELECT to fill a
+//!   table from a query
 //! - Wildcard (*) in SELECT
 //!
+//! With the `serde` feature, the AST types ([`Query`] and everything it
+//! contains) implement `Serialize` and `Deserialize`, so a parsed query can
+//! be stored or sent elsewhere and read back unchanged.
+//!
 //! ## Example Usage
 //!
 //! ```ignore
@@ -38,7 +49,8 @@
 //! ```
 
 use crate::error::{DatabaseError, Result};
-use crate::types::SortDirection;
+use crate::types::{DataType, SortDirection};
+use std::fmt;
 
 // ============================================================================
 // TOKEN DEFINITIONS
@@ -53,14 +65,38 @@ pub enum TokenType {
     Where,
     Group,
     By,
+    Having,
     And,
     Or,
     Not,
+    In,
+    Between,
+    Is,
+    Null,
+    Cast,
+    Case,
+    When,
+    Then,
+    Else,
+    End,
     Order,
     Limit,
     Offset,
     Asc,
     Desc,
+    Distinct,
+    Join,
+    Inner,
+    On,
+    True,
+    False,
+    Insert,
+    Into,
+    Values,
+    Create,
+    Table,
+    As,
+    With,
 
     // Aggregate functions
     Count,
@@ -68,6 +104,15 @@ pub enum TokenType {
     Avg,
     Min,
     Max,
+    Median,
+    Percentile,
+    ApproxQuantile,
+    Stddev,
+    StddevSamp,
+    StddevPop,
+    Variance,
+    VarSamp,
+    VarPop,
 
     // Operators
     Equal,        // =
@@ -85,11 +130,15 @@ pub enum TokenType {
     RightParen, // )
     Comma,      // ,
     Asterisk,   // *
+    Dot,        // .
+    Semicolon,  // ;
 
     // Literals and identifiers
     Identifier(String),
     StringLiteral(String),
     NumberLiteral(String),
+    /// Parameter placeholder: `?` (None) or `$n`
+    Parameter(Option<usize>),
 
     // Special tokens
     EOF,
@@ -119,11 +168,22 @@ impl Token {
             TokenType::Identifier(s) => s.clone(),
             TokenType::StringLiteral(s) => s.clone(),
             TokenType::NumberLiteral(s) => s.clone(),
+            TokenType::Parameter(None) => "?".to_string(),
+            TokenType::Parameter(Some(n)) => format!("${}", n),
             TokenType::Count => "COUNT".to_string(),
             TokenType::Sum => "SUM".to_string(),
             TokenType::Avg => "AVG".to_string(),
             TokenType::Min => "MIN".to_string(),
             TokenType::Max => "MAX".to_string(),
+            TokenType::Median => "MEDIAN".to_string(),
+            TokenType::Percentile => "PERCENTILE".to_string(),
+            TokenType::ApproxQuantile => "APPROX_QUANTILE".to_string(),
+            TokenType::Stddev => "STDDEV".to_string(),
+            TokenType::StddevSamp => "STDDEV_SAMP".to_string(),
+            TokenType::StddevPop => "STDDEV_POP".to_string(),
+            TokenType::Variance => "VARIANCE".to_string(),
+            TokenType::VarSamp => "VAR_SAMP".to_string(),
+            TokenType::VarPop => "VAR_POP".to_string(),
             _ => format!("{:?}", self.token_type),
         }
     }
@@ -201,6 +261,14 @@ impl Tokenizer {
                 self.advance();
                 Ok(Token::new(TokenType::Asterisk, line, column))
             }
+            '.' => {
+                self.advance();
+                Ok(Token::new(TokenType::Dot, line, column))
+            }
+            ';' => {
+                self.advance();
+                Ok(Token::new(TokenType::Semicolon, line, column))
+            }
             '+' => {
                 self.advance();
                 Ok(Token::new(TokenType::Plus, line, column))
@@ -247,9 +315,19 @@ impl Tokenizer {
                 }
             }
 
+            // Parameter placeholders
+            '?' => {
+                self.advance();
+                Ok(Token::new(TokenType::Parameter(None), line, column))
+            }
+            '$' => self.numbered_parameter(line, column),
+
             // String literals
             '\'' => self.string_literal(line, column),
 
+            // Quoted identifiers
+            '"' => self.quoted_identifier(line, column),
+
             // Numbers
             '0'..='9' => self.number_literal(line, column),
 
@@ -278,10 +356,68 @@ impl Tokenizer {
             self.advance();
         }
 
-        Err(DatabaseError::parser_error("Unterminated string literal"))
+        Err(DatabaseError::parser_error(format!(
+            "Unterminated string literal starting at line {}, column {}",
+            line, column
+        )))
+    }
+
+    /// Parses an identifier enclosed in double quotes, e.g. `"User ID"`.
+    ///
+    /// Unlike an unquoted identifier the name keeps its case and may contain
+    /// any character; a doubled quote (`""`) stands for one `"`. A quoted
+    /// name is never a keyword, so `"count"` names a column.
+    fn quoted_identifier(&mut self, line: usize, column: usize) -> Result<Token> {
+        self.advance(); // Skip opening quote
+        let mut value = String::new();
+
+        while let Some(&c) = self.peek() {
+            self.advance();
+            if c == '"' {
+                if self.match_char('"') {
+                    value.push('"');
+                    continue;
+                }
+                if value.is_empty() {
+                    return Err(DatabaseError::parser_error(format!(
+                        "Empty quoted identifier at line {}, column {}",
+                        line, column
+                    )));
+                }
+                return Ok(Token::new(TokenType::Identifier(value), line, column));
+            }
+            value.push(c);
+        }
+
+        Err(DatabaseError::parser_error(format!(
+            "Unterminated quoted identifier starting at line {}, column {}",
+            line, column
+        )))
+    }
+
+    /// Parses a numbered parameter placeholder, e.g. `$1`.
+    fn numbered_parameter(&mut self, line: usize, column: usize) -> Result<Token> {
+        self.advance(); // Skip '$'
+        let mut digits = String::new();
+        while let Some(&c) = self.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            digits.push(c);
+            self.advance();
+        }
+
+        match digits.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(Token::new(TokenType::Parameter(Some(n)), line, column)),
+            _ => Err(DatabaseError::parser_error(format!(
+                "Expected a parameter number from 1 after '$' at line {}, column {}",
+                line, column
+            ))),
+        }
     }
 
-    /// Parses a numeric literal (integer or float).
+    /// Parses a numeric literal (integer or float, with an optional exponent
+    /// such as `1e6` or `2.5E-3`).
     fn number_literal(&mut self, line: usize, column: usize) -> Result<Token> {
         let mut value = String::new();
 
@@ -298,6 +434,26 @@ impl Tokenizer {
             return Err(DatabaseError::parser_error("Expected number"));
         }
 
+        // An exponent: 'e' or 'E', an optional sign, then at least one digit
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let signed = matches!(self.input.get(self.position + 1), Some('+' | '-'));
+            let first_digit = self.position + 1 + usize::from(signed);
+            if self
+                .input
+                .get(first_digit)
+                .is_some_and(char::is_ascii_digit)
+            {
+                while self.position < first_digit {
+                    value.push(self.input[self.position]);
+                    self.advance();
+                }
+                while let Some(&c) = self.peek().filter(|c| c.is_ascii_digit()) {
+                    value.push(c);
+                    self.advance();
+                }
+            }
+        }
+
         Ok(Token::new(TokenType::NumberLiteral(value), line, column))
     }
 
@@ -321,19 +477,52 @@ impl Tokenizer {
             "WHERE" => TokenType::Where,
             "GROUP" => TokenType::Group,
             "BY" => TokenType::By,
+            "HAVING" => TokenType::Having,
             "AND" => TokenType::And,
             "OR" => TokenType::Or,
             "NOT" => TokenType::Not,
+            "IN" => TokenType::In,
+            "BETWEEN" => TokenType::Between,
+            "IS" => TokenType::Is,
+            "NULL" => TokenType::Null,
+            "CAST" => TokenType::Cast,
+            "CASE" => TokenType::Case,
+            "WHEN" => TokenType::When,
+            "THEN" => TokenType::Then,
+            "ELSE" => TokenType::Else,
+            "END" => TokenType::End,
             "ORDER" => TokenType::Order,
             "LIMIT" => TokenType::Limit,
             "OFFSET" => TokenType::Offset,
             "ASC" => TokenType::Asc,
             "DESC" => TokenType::Desc,
+            "DISTINCT" => TokenType::Distinct,
+            "JOIN" => TokenType::Join,
+            "INNER" => TokenType::Inner,
+            "ON" => TokenType::On,
+            "TRUE" => TokenType::True,
+            "FALSE" => TokenType::False,
+            "INSERT" => TokenType::Insert,
+            "INTO" => TokenType::Into,
+            "VALUES" => TokenType::Values,
+            "CREATE" => TokenType::Create,
+            "TABLE" => TokenType::Table,
+            "AS" => TokenType::As,
+            "WITH" => TokenType::With,
             "COUNT" => TokenType::Count,
             "SUM" => TokenType::Sum,
             "AVG" => TokenType::Avg,
             "MIN" => TokenType::Min,
             "MAX" => TokenType::Max,
+            "MEDIAN" => TokenType::Median,
+            "PERCENTILE" => TokenType::Percentile,
+            "APPROX_QUANTILE" => TokenType::ApproxQuantile,
+            "STDDEV" => TokenType::Stddev,
+            "STDDEV_SAMP" => TokenType::StddevSamp,
+            "STDDEV_POP" => TokenType::StddevPop,
+            "VARIANCE" => TokenType::Variance,
+            "VAR_SAMP" => TokenType::VarSamp,
+            "VAR_POP" => TokenType::VarPop,
             _ => TokenType::Identifier(value.to_lowercase()),
         };
 
@@ -344,13 +533,12 @@ impl Tokenizer {
     fn skip_whitespace(&mut self) {
         while let Some(&c) = self.peek() {
             if c.is_whitespace() {
+                // advance() moves the column; a newline then starts it over
+                self.advance();
                 if c == '\n' {
                     self.line += 1;
                     self.column = 1;
-                } else {
-                    self.column += 1;
                 }
-                self.advance();
             } else {
                 break;
             }
@@ -393,23 +581,87 @@ impl Tokenizer {
 // ============================================================================
 
 /// Represents a complete SQL query.
+// One Query exists per statement, so the size gap between variants is not
+// worth boxing the SELECT for
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Query {
     /// SELECT query
     Select(SelectStatement),
+    /// INSERT INTO ... VALUES statement
+    Insert(InsertStatement),
+    /// CREATE TABLE statement
+    CreateTable(CreateTableStatement),
+    /// CREATE TABLE ... AS SELECT statement
+    CreateTableAs(CreateTableAsStatement),
+}
+
+/// Represents an `INSERT INTO table VALUES (...), (...)` statement.
+///
+/// Values are kept as expressions; the executor evaluates them and checks
+/// them against the table's columns, which are filled in table order.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsertStatement {
+    /// The table receiving the rows
+    pub table: String,
+    /// One list of values per inserted row
+    pub rows: Vec<Vec<Expression>>,
+}
+
+/// Represents a `CREATE TABLE name (column TYPE, ...)` statement.
+///
+/// Type names are those accepted by `DataType::from_str` (e.g. INT, FLOAT,
+/// TEXT, BOOLEAN). Column names are unique; the parser rejects duplicates.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateTableStatement {
+    /// The new table's name
+    pub table: String,
+    /// The columns in declaration order
+    pub columns: Vec<(String, DataType)>,
+}
+
+/// Represents a `CREATE TABLE name AS SELECT ...` statement.
+///
+/// The new table takes its columns, names and types from the query's
+/// output, and its rows from the query's result.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateTableAsStatement {
+    /// The new table's name
+    pub table: String,
+    /// The query whose result fills the table
+    pub query: SelectStatement,
 }
 
 /// Represents a SELECT statement with all its clauses.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SelectStatement {
+    /// Common table expressions from a leading WITH clause, in order
+    pub ctes: Vec<CommonTableExpression>,
     /// Columns or expressions to select
     pub select_items: Vec<SelectItem>,
-    /// Table name in the FROM clause
+    /// Table name in the FROM clause, a CSV file's path when `from_file`
+    /// is set, or the alias of `from_subquery` (empty when it has none)
     pub from_table: String,
+    /// Whether the FROM clause names a CSV file by a quoted path
+    /// (`FROM 'data/sales.csv'`) rather than a table
+    pub from_file: bool,
+    /// A derived table: the parenthesized query of
+    /// `FROM (SELECT ...) [AS] alias`, whose result is read as the table
+    pub from_subquery: Option<Box<SelectStatement>>,
+    /// Tables joined to the FROM table, in order
+    pub joins: Vec<JoinClause>,
     /// Optional WHERE clause condition
     pub where_clause: Option<Expression>,
-    /// Optional GROUP BY columns
-    pub group_by: Option<Vec<String>>,
+    /// Optional GROUP BY keys: columns, expressions, or 1-based positions
+    /// in the SELECT list written as number literals
+    pub group_by: Option<Vec<Expression>>,
+    /// Optional HAVING condition, evaluated on each group
+    pub having: Option<Expression>,
     /// Optional ORDER BY clause
     pub order_by: Option<Vec<OrderByItem>>,
     /// Optional LIMIT clause
@@ -418,39 +670,127 @@ pub struct SelectStatement {
     pub offset: Option<usize>,
 }
 
+/// One `name AS (SELECT ...)` entry of a WITH clause.
+///
+/// The name is visible as a table to later entries and to the main query,
+/// where it hides any catalog table of the same name.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommonTableExpression {
+    /// The name the query's result is referred to by
+    pub name: String,
+    /// The query defining the table
+    pub query: SelectStatement,
+}
+
+impl SelectStatement {
+    /// Every expression of the statement, including those of its CTEs and
+    /// its derived table.
+    fn expressions(&self) -> Vec<&Expression> {
+        let mut expressions: Vec<&Expression> = self
+            .ctes
+            .iter()
+            .flat_map(|cte| cte.query.expressions())
+            .collect();
+        if let Some(subquery) = &self.from_subquery {
+            expressions.extend(subquery.expressions());
+        }
+        expressions.extend(self.select_items.iter().filter_map(|item| match item {
+            SelectItem::Expression(expr, _) => Some(expr),
+            SelectItem::Wildcard => None,
+        }));
+        expressions.extend(&self.where_clause);
+        expressions.extend(&self.having);
+        expressions.extend(self.order_by.iter().flatten().map(|item| &item.expression));
+        expressions
+    }
+
+    /// Mutable access to the expressions listed by `expressions`.
+    fn expressions_mut(&mut self) -> Vec<&mut Expression> {
+        let mut expressions: Vec<&mut Expression> = self
+            .ctes
+            .iter_mut()
+            .flat_map(|cte| cte.query.expressions_mut())
+            .collect();
+        if let Some(subquery) = &mut self.from_subquery {
+            expressions.extend(subquery.expressions_mut());
+        }
+        expressions.extend(self.select_items.iter_mut().filter_map(|item| match item {
+            SelectItem::Expression(expr, _) => Some(expr),
+            SelectItem::Wildcard => None,
+        }));
+        expressions.extend(&mut self.where_clause);
+        expressions.extend(&mut self.having);
+        expressions.extend(
+            self.order_by
+                .iter_mut()
+                .flatten()
+                .map(|item| &mut item.expression),
+        );
+        expressions
+    }
+}
+
+/// Represents an inner `JOIN table ON left = right` clause.
+///
+/// The ON columns are kept as written, qualified or not; the planner decides
+/// which side of the join each one belongs to.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoinClause {
+    /// The joined table
+    pub table: String,
+    /// Column on the left of the `=` in the ON condition
+    pub left_column: String,
+    /// Column on the right of the `=` in the ON condition
+    pub right_column: String,
+}
+
 /// Represents an item in the SELECT clause.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectItem {
     /// Wildcard (*)
     Wildcard,
-    /// An expression (column reference, aggregate function, etc.)
-    Expression(Expression),
+    /// An expression (column reference, aggregate function, etc.) with the
+    /// output column name given by `AS`, if any
+    Expression(Expression, Option<String>),
 }
 
 /// Represents an item in the ORDER BY clause.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderByItem {
-    /// Column name to sort by
-    pub column: String,
+    /// What to sort by: a column, an aggregate such as `SUM(amount)`, or a
+    /// 1-based position in the SELECT list written as a number literal
+    pub expression: Expression,
     /// Sort direction (ASC or DESC)
     pub direction: SortDirection,
 }
 
 /// Represents an expression in a SQL query.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression {
-    /// Column reference
+    /// Column reference, either `column` or `table.column`
     Column(String),
     /// String literal
     StringLiteral(String),
     /// Number literal (integer or float)
     NumberLiteral(String),
+    /// Boolean literal (TRUE or FALSE)
+    BooleanLiteral(bool),
     /// Aggregate function call
     AggregateFunction {
-        /// Function name (COUNT, SUM, AVG, MIN, MAX)
+        /// Function name (COUNT, SUM, AVG, MIN, MAX, MEDIAN, PERCENTILE,
+        /// APPROX_QUANTILE, STDDEV, VARIANCE and their _SAMP/_POP forms)
         function: String,
         /// The argument to the aggregate function
         argument: Box<Expression>,
+        /// Any further arguments, e.g. the quantile in `PERCENTILE(x, 0.95)`
+        parameters: Vec<Expression>,
+        /// Whether the argument was prefixed with DISTINCT, e.g. `SUM(DISTINCT x)`
+        distinct: bool,
     },
     /// Binary operation (e.g., age > 25)
     BinaryOp {
@@ -463,10 +803,51 @@ pub enum Expression {
         operator: UnaryOperator,
         operand: Box<Expression>,
     },
+    /// List membership (e.g., category IN ('Laptop', 'Phone'))
+    InList {
+        /// The value tested for membership
+        expr: Box<Expression>,
+        /// The candidate values
+        list: Vec<Expression>,
+        /// True for NOT IN
+        negated: bool,
+    },
+    /// NULL test (e.g., email IS NULL, email IS NOT NULL)
+    IsNull {
+        /// The value tested
+        expr: Box<Expression>,
+        /// True for IS NOT NULL
+        negated: bool,
+    },
+    /// Scalar function call, evaluated per row (e.g., UPPER(name))
+    ScalarFunction {
+        /// Function name as written
+        name: String,
+        /// The arguments, in order
+        args: Vec<Expression>,
+    },
+    /// Explicit type conversion (e.g., CAST(zip AS TEXT))
+    Cast {
+        /// The value to convert
+        expr: Box<Expression>,
+        /// The type to convert it to
+        target: DataType,
+    },
+    /// Parameter placeholder, numbered from 1 (`$1`, or the first `?`),
+    /// replaced by a value when a prepared statement is bound
+    Parameter(usize),
+    /// Conditional value (e.g., CASE WHEN age < 30 THEN 'young' ELSE 'old' END)
+    Case {
+        /// `WHEN condition THEN result` pairs, tried in order
+        branches: Vec<(Expression, Expression)>,
+        /// The result when no condition holds; NULL if absent
+        else_expr: Option<Box<Expression>>,
+    },
 }
 
 /// Represents binary operators.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryOperator {
     Equal,
     NotEqual,
@@ -484,11 +865,320 @@ pub enum BinaryOperator {
 
 /// Represents unary operators.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOperator {
     Not,
     Minus,
 }
 
+impl BinaryOperator {
+    /// Binding strength, higher binds tighter; mirrors the parser's
+    /// precedence levels.
+    fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => 1,
+            BinaryOperator::And => 2,
+            BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::Less
+            | BinaryOperator::Greater
+            | BinaryOperator::LessEqual
+            | BinaryOperator::GreaterEqual => 3,
+            BinaryOperator::Plus | BinaryOperator::Minus => 4,
+            BinaryOperator::Multiply | BinaryOperator::Divide => 5,
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            BinaryOperator::Equal => "=",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::Less => "<",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::LessEqual => "<=",
+            BinaryOperator::GreaterEqual => ">=",
+            BinaryOperator::And => "AND",
+            BinaryOperator::Or => "OR",
+            BinaryOperator::Plus => "+",
+            BinaryOperator::Minus => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Formats an expression back as SQL, e.g. `salary / 12`.
+///
+/// Parentheses are written only where precedence requires them, so the
+/// output is how the planner names computed columns.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Column(name) => write!(f, "{}", name),
+            Expression::StringLiteral(s) => write!(f, "'{}'", s),
+            Expression::NumberLiteral(n) => write!(f, "{}", n),
+            Expression::BooleanLiteral(b) => write!(f, "{}", if *b { "TRUE" } else { "FALSE" }),
+            Expression::Parameter(n) => write!(f, "${}", n),
+            Expression::AggregateFunction {
+                function,
+                argument,
+                parameters,
+                distinct,
+            } => {
+                write!(f, "{}(", function.to_uppercase())?;
+                if *distinct {
+                    write!(f, "DISTINCT ")?;
+                }
+                write!(f, "{}", argument)?;
+                for parameter in parameters {
+                    write!(f, ", {}", parameter)?;
+                }
+                write!(f, ")")
+            }
+            Expression::BinaryOp {
+                left,
+                operator,
+                right,
+            } => {
+                // Operators are left-associative, so an operand of equal
+                // precedence only needs parentheses on the right
+                let needs_parens = |operand: &Expression, on_right: bool| match operand {
+                    Expression::BinaryOp {
+                        operator: inner, ..
+                    } => {
+                        inner.precedence() < operator.precedence()
+                            || (on_right && inner.precedence() == operator.precedence())
+                    }
+                    // NOT binds looser than comparisons and arithmetic
+                    Expression::UnaryOp {
+                        operator: UnaryOperator::Not,
+                        ..
+                    } => operator.precedence() > BinaryOperator::And.precedence(),
+                    _ => false,
+                };
+                write_operand(f, left, needs_parens(left, false))?;
+                write!(f, " {} ", operator)?;
+                write_operand(f, right, needs_parens(right, true))
+            }
+            Expression::UnaryOp { operator, operand } => {
+                let needs_parens = matches!(**operand, Expression::BinaryOp { .. });
+                match operator {
+                    UnaryOperator::Not => write!(f, "NOT ")?,
+                    UnaryOperator::Minus => write!(f, "-")?,
+                }
+                write_operand(f, operand, needs_parens)
+            }
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                write!(f, "{} {}IN (", expr, if *negated { "NOT " } else { "" })?;
+                for (i, item) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+            Expression::IsNull { expr, negated } => {
+                let needs_parens = match &**expr {
+                    Expression::BinaryOp { operator, .. } => {
+                        operator.precedence() <= BinaryOperator::Equal.precedence()
+                    }
+                    Expression::UnaryOp { operator, .. } => *operator == UnaryOperator::Not,
+                    _ => false,
+                };
+                write_operand(f, expr, needs_parens)?;
+                write!(f, " IS {}NULL", if *negated { "NOT " } else { "" })
+            }
+            Expression::ScalarFunction { name, args } => {
+                write!(f, "{}(", name.to_uppercase())?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+            Expression::Cast { expr, target } => write!(f, "CAST({} AS {})", expr, target),
+            Expression::Case {
+                branches,
+                else_expr,
+            } => {
+                write!(f, "CASE")?;
+                for (condition, result) in branches {
+                    write!(f, " WHEN {} THEN {}", condition, result)?;
+                }
+                if let Some(else_expr) = else_expr {
+                    write!(f, " ELSE {}", else_expr)?;
+                }
+                write!(f, " END")
+            }
+        }
+    }
+}
+
+fn write_operand(f: &mut fmt::Formatter<'_>, operand: &Expression, parens: bool) -> fmt::Result {
+    if parens {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+impl Expression {
+    /// Calls `visit` on this expression and then on every expression nested
+    /// in it, outermost first.
+    pub fn walk(&self, visit: &mut impl FnMut(&Expression)) {
+        visit(self);
+        match self {
+            Expression::AggregateFunction {
+                argument,
+                parameters,
+                ..
+            } => {
+                argument.walk(visit);
+                for parameter in parameters {
+                    parameter.walk(visit);
+                }
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                left.walk(visit);
+                right.walk(visit);
+            }
+            Expression::UnaryOp { operand, .. } => operand.walk(visit),
+            Expression::InList { expr, list, .. } => {
+                expr.walk(visit);
+                for item in list {
+                    item.walk(visit);
+                }
+            }
+            Expression::ScalarFunction { args, .. } => {
+                for arg in args {
+                    arg.walk(visit);
+                }
+            }
+            Expression::IsNull { expr, .. } | Expression::Cast { expr, .. } => expr.walk(visit),
+            Expression::Case {
+                branches,
+                else_expr,
+            } => {
+                for (condition, result) in branches {
+                    condition.walk(visit);
+                    result.walk(visit);
+                }
+                if let Some(else_expr) = else_expr {
+                    else_expr.walk(visit);
+                }
+            }
+            Expression::Column(_)
+            | Expression::StringLiteral(_)
+            | Expression::NumberLiteral(_)
+            | Expression::BooleanLiteral(_)
+            | Expression::Parameter(_) => {}
+        }
+    }
+
+    /// Like [`Expression::walk`], but `visit` may replace each expression
+    /// before the ones nested in it are visited.
+    pub fn walk_mut(&mut self, visit: &mut impl FnMut(&mut Expression)) {
+        visit(self);
+        match self {
+            Expression::AggregateFunction {
+                argument,
+                parameters,
+                ..
+            } => {
+                argument.walk_mut(visit);
+                for parameter in parameters {
+                    parameter.walk_mut(visit);
+                }
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                left.walk_mut(visit);
+                right.walk_mut(visit);
+            }
+            Expression::UnaryOp { operand, .. } => operand.walk_mut(visit),
+            Expression::InList { expr, list, .. } => {
+                expr.walk_mut(visit);
+                for item in list {
+                    item.walk_mut(visit);
+                }
+            }
+            Expression::ScalarFunction { args, .. } => {
+                for arg in args {
+                    arg.walk_mut(visit);
+                }
+            }
+            Expression::IsNull { expr, .. } | Expression::Cast { expr, .. } => expr.walk_mut(visit),
+            Expression::Case {
+                branches,
+                else_expr,
+            } => {
+                for (condition, result) in branches {
+                    condition.walk_mut(visit);
+                    result.walk_mut(visit);
+                }
+                if let Some(else_expr) = else_expr {
+                    else_expr.walk_mut(visit);
+                }
+            }
+            Expression::Column(_)
+            | Expression::StringLiteral(_)
+            | Expression::NumberLiteral(_)
+            | Expression::BooleanLiteral(_)
+            | Expression::Parameter(_) => {}
+        }
+    }
+}
+
+impl Query {
+    /// The statement's top-level expressions: SELECT items, WHERE, HAVING,
+    /// and ORDER BY of a query, or the values of an INSERT.
+    pub fn expressions(&self) -> Vec<&Expression> {
+        match self {
+            Query::Select(stmt)
+            | Query::CreateTableAs(CreateTableAsStatement { query: stmt, .. }) => {
+                stmt.expressions()
+            }
+            Query::Insert(stmt) => stmt.rows.iter().flatten().collect(),
+            Query::CreateTable(_) => Vec::new(),
+        }
+    }
+
+    /// Mutable access to the expressions listed by [`Query::expressions`].
+    pub fn expressions_mut(&mut self) -> Vec<&mut Expression> {
+        match self {
+            Query::Select(stmt)
+            | Query::CreateTableAs(CreateTableAsStatement { query: stmt, .. }) => {
+                stmt.expressions_mut()
+            }
+            Query::Insert(stmt) => stmt.rows.iter_mut().flatten().collect(),
+            Query::CreateTable(_) => Vec::new(),
+        }
+    }
+
+    /// The number of values the statement takes: the highest `$n`, or the
+    /// number of `?` placeholders. Zero for a statement without parameters.
+    pub fn parameter_count(&self) -> usize {
+        let mut count = 0;
+        for expr in self.expressions() {
+            expr.walk(&mut |expr| {
+                if let Expression::Parameter(n) = expr {
+                    count = count.max(*n);
+                }
+            });
+        }
+        count
+    }
+}
+
 // ============================================================================
 // PARSER
 // ============================================================================
@@ -498,54 +1188,120 @@ pub enum UnaryOperator {
 /// This is a recursive descent parser where each grammar rule is
 /// implemented as a separate method.
 pub struct Parser {
+    sql: String,
     tokens: Vec<Token>,
     position: usize,
+    /// Number of `?` placeholders parsed so far
+    positional_parameters: usize,
+    /// Whether a `$n` placeholder has been parsed
+    numbered_parameters: bool,
 }
 
 impl Parser {
     /// Creates a new parser for the given SQL input.
+    ///
+    /// Tokenization is deferred to [`Parser::parse`], so an illegal
+    /// character is reported from there along with its line and column.
     pub fn new(sql: &str) -> Self {
-        let mut tokenizer = Tokenizer::new(sql);
-        let tokens = tokenizer.tokenize().unwrap_or_else(|e| {
-            eprintln!("Tokenizer error: {}", e);
-            Vec::new()
-        });
-
         Parser {
-            tokens,
+            sql: sql.to_string(),
+            tokens: Vec::new(),
             position: 0,
+            positional_parameters: 0,
+            numbered_parameters: false,
         }
     }
 
     /// Parses the SQL input into a Query AST.
+    ///
+    /// # Returns
+    ///
+    /// The parsed query, or a `ParserError` naming the offending token's
+    /// line and column. Tokenizer errors are returned unchanged.
     pub fn parse(&mut self) -> Result<Query> {
+        self.tokens = Tokenizer::new(&self.sql).tokenize()?;
+        self.position = 0;
+        self.positional_parameters = 0;
+        self.numbered_parameters = false;
         self.parse_query()
     }
 
-    /// Parses a complete query (currently only SELECT is supported).
+    /// Parses a complete statement (SELECT, optionally after a WITH clause,
+    /// INSERT, CREATE TABLE, or CREATE TABLE ... AS SELECT).
     fn parse_query(&mut self) -> Result<Query> {
         match self.peek_token_type() {
-            Some(TokenType::Select) => {
+            Some(TokenType::Select) | Some(TokenType::With) => {
                 let select_statement = self.parse_select_statement()?;
                 Ok(Query::Select(select_statement))
             }
-            Some(token_type) => Err(DatabaseError::parser_error(format!(
-                "Expected SELECT, found {:?}",
+            Some(TokenType::Insert) => {
+                let insert_statement = self.parse_insert_statement()?;
+                Ok(Query::Insert(insert_statement))
+            }
+            Some(TokenType::Create) => self.parse_create_table_statement(),
+            Some(token_type) => Err(self.error_at_current(format!(
+                "Expected SELECT, INSERT, or CREATE, found {:?}",
                 token_type
             ))),
             None => Err(DatabaseError::parser_error("Unexpected end of input")),
         }
     }
 
-    /// Parses a SELECT statement.
+    /// Parses a SELECT statement that makes up the rest of the input.
     fn parse_select_statement(&mut self) -> Result<SelectStatement> {
+        let statement = self.parse_select_query()?;
+
+        // Should be at EOF now
+        self.consume_end_of_statement()?;
+        Ok(statement)
+    }
+
+    /// Parses a SELECT query, with an optional leading WITH clause, up to
+    /// the first token that cannot continue it.
+    fn parse_select_query(&mut self) -> Result<SelectStatement> {
+        let ctes = if self.peek_token_type() == Some(TokenType::With) {
+            self.parse_with_clause()?
+        } else {
+            Vec::new()
+        };
+
         // Parse SELECT clause
         self.consume_token(TokenType::Select, "Expected SELECT")?;
         let select_items = self.parse_select_items()?;
 
         // Parse FROM clause
         self.consume_token(TokenType::From, "Expected FROM")?;
-        let from_table = self.parse_identifier()?;
+        let mut from_subquery = None;
+        let (from_table, from_file) = match self.peek_token_type() {
+            Some(TokenType::StringLiteral(path)) => {
+                self.advance();
+                (path, true)
+            }
+            Some(TokenType::LeftParen) => {
+                self.advance();
+                from_subquery = Some(Box::new(self.parse_select_query()?));
+                self.consume_token(TokenType::RightParen, "Expected ')' after the FROM query")?;
+                let alias = if self.match_token(TokenType::As) {
+                    self.parse_identifier()?
+                } else if let Some(TokenType::Identifier(name)) = self.peek_token_type() {
+                    self.advance();
+                    name
+                } else {
+                    String::new()
+                };
+                (alias, false)
+            }
+            _ => (self.parse_identifier()?, false),
+        };
+
+        // Parse optional JOIN clauses
+        let mut joins = Vec::new();
+        while matches!(
+            self.peek_token_type(),
+            Some(TokenType::Join) | Some(TokenType::Inner)
+        ) {
+            joins.push(self.parse_join_clause()?);
+        }
 
         // Parse optional WHERE clause
         let where_clause = if self.match_token(TokenType::Where) {
@@ -557,7 +1313,14 @@ impl Parser {
         // Parse optional GROUP BY clause
         let group_by = if self.match_token(TokenType::Group) {
             self.consume_token(TokenType::By, "Expected BY after GROUP")?;
-            Some(self.parse_group_by_columns()?)
+            Some(self.parse_group_by_items()?)
+        } else {
+            None
+        };
+
+        // Parse optional HAVING clause
+        let having = if self.match_token(TokenType::Having) {
+            Some(self.parse_expression()?)
         } else {
             None
         };
@@ -584,20 +1347,142 @@ impl Parser {
             None
         };
 
-        // Should be at EOF now
-        self.consume_token(TokenType::EOF, "Expected end of statement")?;
-
         Ok(SelectStatement {
+            ctes,
             select_items,
             from_table,
+            from_file,
+            from_subquery,
+            joins,
             where_clause,
             group_by,
+            having,
             order_by,
             limit,
             offset,
         })
     }
 
+    /// Parses `WITH name AS (SELECT ...), name2 AS (SELECT ...)`.
+    fn parse_with_clause(&mut self) -> Result<Vec<CommonTableExpression>> {
+        self.consume_token(TokenType::With, "Expected WITH")?;
+
+        let mut ctes: Vec<CommonTableExpression> = Vec::new();
+        loop {
+            let name = self.parse_identifier()?;
+            if ctes.iter().any(|cte| cte.name == name) {
+                return Err(DatabaseError::parser_error(format!(
+                    "WITH defines '{}' more than once",
+                    name
+                )));
+            }
+            self.consume_token(TokenType::As, "Expected AS after the WITH name")?;
+            self.consume_token(TokenType::LeftParen, "Expected '(' before the WITH query")?;
+            let query = self.parse_select_query()?;
+            self.consume_token(TokenType::RightParen, "Expected ')' after the WITH query")?;
+            ctes.push(CommonTableExpression { name, query });
+
+            if !self.match_token(TokenType::Comma) {
+                break;
+            }
+        }
+        Ok(ctes)
+    }
+
+    /// Parses `INSERT INTO table VALUES (v1, v2, ...), (...)`.
+    fn parse_insert_statement(&mut self) -> Result<InsertStatement> {
+        self.consume_token(TokenType::Insert, "Expected INSERT")?;
+        self.consume_token(TokenType::Into, "Expected INTO after INSERT")?;
+        let table = self.parse_identifier()?;
+        self.consume_token(TokenType::Values, "Expected VALUES after table name")?;
+
+        let mut rows = vec![self.parse_value_row()?];
+        while self.match_token(TokenType::Comma) {
+            rows.push(self.parse_value_row()?);
+        }
+
+        // Should be at EOF now
+        self.consume_end_of_statement()?;
+
+        Ok(InsertStatement { table, rows })
+    }
+
+    /// Parses `CREATE TABLE name (column TYPE, ...)` or
+    /// `CREATE TABLE name AS SELECT ...`.
+    fn parse_create_table_statement(&mut self) -> Result<Query> {
+        self.consume_token(TokenType::Create, "Expected CREATE")?;
+        self.consume_token(TokenType::Table, "Expected TABLE after CREATE")?;
+        let table = self.parse_identifier()?;
+        if self.match_token(TokenType::As) {
+            let query = self.parse_select_statement()?;
+            return Ok(Query::CreateTableAs(CreateTableAsStatement {
+                table,
+                query,
+            }));
+        }
+        self.consume_token(
+            TokenType::LeftParen,
+            "Expected '(' or AS after the table name",
+        )?;
+
+        let mut columns: Vec<(String, DataType)> = Vec::new();
+        loop {
+            let name = self.parse_identifier()?;
+            if columns.iter().any(|(existing, _)| *existing == name) {
+                return Err(DatabaseError::parser_error(format!(
+                    "Column '{}' is declared more than once in CREATE TABLE {}",
+                    name, table
+                )));
+            }
+            let data_type = self.parse_identifier()?.parse::<DataType>()?;
+            columns.push((name, data_type));
+
+            if !self.match_token(TokenType::Comma) {
+                break;
+            }
+        }
+
+        self.consume_token(
+            TokenType::RightParen,
+            "Expected ')' after column definitions",
+        )?;
+        self.consume_end_of_statement()?;
+
+        Ok(Query::CreateTable(CreateTableStatement { table, columns }))
+    }
+
+    /// Parses one parenthesized row of an INSERT: `(expr, expr, ...)`.
+    fn parse_value_row(&mut self) -> Result<Vec<Expression>> {
+        self.consume_token(TokenType::LeftParen, "Expected '(' before row values")?;
+        let mut values = vec![self.parse_expression()?];
+        while self.match_token(TokenType::Comma) {
+            values.push(self.parse_expression()?);
+        }
+        self.consume_token(TokenType::RightParen, "Expected ')' after row values")?;
+        Ok(values)
+    }
+
+    /// Parses `[INNER] JOIN table ON column = column`.
+    fn parse_join_clause(&mut self) -> Result<JoinClause> {
+        self.match_token(TokenType::Inner);
+        self.consume_token(TokenType::Join, "Expected JOIN")?;
+        let table = self.parse_identifier()?;
+
+        self.consume_token(TokenType::On, "Expected ON after JOIN table")?;
+        let left_column = self.parse_column_reference()?;
+        self.consume_token(
+            TokenType::Equal,
+            "Expected '=' in JOIN condition (only equi-joins are supported)",
+        )?;
+        let right_column = self.parse_column_reference()?;
+
+        Ok(JoinClause {
+            table,
+            left_column,
+            right_column,
+        })
+    }
+
     /// Parses the SELECT clause items.
     fn parse_select_items(&mut self) -> Result<Vec<SelectItem>> {
         let mut items = Vec::new();
@@ -613,19 +1498,24 @@ impl Parser {
         Ok(items)
     }
 
-    /// Parses a single SELECT item.
+    /// Parses a single SELECT item, with an optional `AS alias`.
     fn parse_select_item(&mut self) -> Result<SelectItem> {
         if self.match_token(TokenType::Asterisk) {
             Ok(SelectItem::Wildcard)
         } else {
             let expr = self.parse_expression()?;
-            Ok(SelectItem::Expression(expr))
+            let alias = if self.match_token(TokenType::As) {
+                Some(self.parse_identifier()?)
+            } else {
+                None
+            };
+            Ok(SelectItem::Expression(expr, alias))
         }
     }
 
     /// Parses an expression.
     ///
-    /// Expressions follow the precedence: OR > AND > comparisons > arithmetic > unary > primary.
+    /// Expressions follow the precedence: OR > AND > NOT > comparisons > arithmetic > unary > primary.
     fn parse_expression(&mut self) -> Result<Expression> {
         self.parse_or_expression()
     }
@@ -649,11 +1539,11 @@ impl Parser {
 
     /// Parses AND expressions.
     fn parse_and_expression(&mut self) -> Result<Expression> {
-        let mut left = self.parse_comparison_expression()?;
+        let mut left = self.parse_not_expression()?;
 
         while self.match_token(TokenType::And) {
             let operator = BinaryOperator::And;
-            let right = self.parse_comparison_expression()?;
+            let right = self.parse_not_expression()?;
             left = Expression::BinaryOp {
                 left: Box::new(left),
                 operator,
@@ -664,10 +1554,54 @@ impl Parser {
         Ok(left)
     }
 
-    /// Parses comparison expressions (=, !=, <, >, <=, >=).
+    /// Parses logical NOT, which binds looser than comparisons, as in SQL:
+    /// `NOT a = b` is `NOT (a = b)`.
+    fn parse_not_expression(&mut self) -> Result<Expression> {
+        if self.match_token(TokenType::Not) {
+            let operand = self.parse_not_expression()?;
+            return Ok(Expression::UnaryOp {
+                operator: UnaryOperator::Not,
+                operand: Box::new(operand),
+            });
+        }
+        self.parse_comparison_expression()
+    }
+
+    /// Parses comparison expressions (=, !=, <, >, <=, >=, [NOT] IN,
+    /// [NOT] BETWEEN, IS [NOT] NULL).
     fn parse_comparison_expression(&mut self) -> Result<Expression> {
         let mut left = self.parse_additive_expression()?;
 
+        if self.match_token(TokenType::Is) {
+            let negated = self.match_token(TokenType::Not);
+            self.consume_token(TokenType::Null, "Expected NULL after IS")?;
+            return Ok(Expression::IsNull {
+                expr: Box::new(left),
+                negated,
+            });
+        }
+
+        // [NOT] IN (...) and [NOT] BETWEEN bind like a comparison and do not chain
+        let negated = self.peek_token_type() == Some(TokenType::Not)
+            && matches!(
+                self.peek_token_type_at(1),
+                Some(TokenType::In) | Some(TokenType::Between)
+            );
+        if negated {
+            self.advance();
+        }
+        if self.match_token(TokenType::In) {
+            let list = self.parse_in_list()?;
+            return Ok(Expression::InList {
+                expr: Box::new(left),
+                list,
+                negated,
+            });
+        }
+        if self.match_token(TokenType::Between) {
+            return self.parse_between(left, negated);
+        }
+
         while let Some(token_type) = self.peek_token_type() {
             let operator = match token_type {
                 TokenType::Equal => {
@@ -708,6 +1642,61 @@ impl Parser {
         Ok(left)
     }
 
+    /// Parses the `low AND high` bounds after BETWEEN and desugars the range.
+    ///
+    /// `x BETWEEN low AND high` becomes `x >= low AND x <= high`, and the
+    /// negated form becomes `x < low OR x > high`, so the planner only ever
+    /// sees ordinary comparisons. The bounds are parsed as additive
+    /// expressions, which is what keeps the AND between them from being read
+    /// as a logical AND.
+    fn parse_between(&mut self, expr: Expression, negated: bool) -> Result<Expression> {
+        let low = self.parse_additive_expression()?;
+        self.consume_token(TokenType::And, "Expected AND between BETWEEN bounds")?;
+        let high = self.parse_additive_expression()?;
+
+        let (low_op, join_op, high_op) = if negated {
+            (
+                BinaryOperator::Less,
+                BinaryOperator::Or,
+                BinaryOperator::Greater,
+            )
+        } else {
+            (
+                BinaryOperator::GreaterEqual,
+                BinaryOperator::And,
+                BinaryOperator::LessEqual,
+            )
+        };
+        let compare = |operator, bound| Expression::BinaryOp {
+            left: Box::new(expr.clone()),
+            operator,
+            right: Box::new(bound),
+        };
+
+        Ok(Expression::BinaryOp {
+            left: Box::new(compare(low_op, low)),
+            operator: join_op,
+            right: Box::new(compare(high_op, high)),
+        })
+    }
+
+    /// Parses the parenthesized, non-empty value list after IN.
+    fn parse_in_list(&mut self) -> Result<Vec<Expression>> {
+        self.consume_token(TokenType::LeftParen, "Expected '(' after IN")?;
+
+        if self.peek_token_type() == Some(TokenType::RightParen) {
+            return Err(self.error_at_current("IN list must contain at least one value"));
+        }
+
+        let mut list = vec![self.parse_additive_expression()?];
+        while self.match_token(TokenType::Comma) {
+            list.push(self.parse_additive_expression()?);
+        }
+
+        self.consume_token(TokenType::RightParen, "Expected ')' after IN list")?;
+        Ok(list)
+    }
+
     /// Parses additive expressions (+, -).
     fn parse_additive_expression(&mut self) -> Result<Expression> {
         let mut left = self.parse_multiplicative_expression()?;
@@ -768,26 +1757,12 @@ impl Parser {
 
     /// Parses unary expressions (NOT, -).
     fn parse_unary_expression(&mut self) -> Result<Expression> {
-        if let Some(token_type) = self.peek_token_type() {
-            match token_type {
-                TokenType::Not => {
-                    self.advance();
-                    let operand = self.parse_unary_expression()?;
-                    return Ok(Expression::UnaryOp {
-                        operator: UnaryOperator::Not,
-                        operand: Box::new(operand),
-                    });
-                }
-                TokenType::Minus => {
-                    self.advance();
-                    let operand = self.parse_unary_expression()?;
-                    return Ok(Expression::UnaryOp {
-                        operator: UnaryOperator::Minus,
-                        operand: Box::new(operand),
-                    });
-                }
-                _ => {}
-            }
+        if self.match_token(TokenType::Minus) {
+            let operand = self.parse_unary_expression()?;
+            return Ok(Expression::UnaryOp {
+                operator: UnaryOperator::Minus,
+                operand: Box::new(operand),
+            });
         }
 
         self.parse_primary_expression()
@@ -801,9 +1776,13 @@ impl Parser {
             Some(TokenType::Identifier(name)) => {
                 let name = name.clone();
                 self.advance();
-                // Check if this is an aggregate function call
+                // Aggregates are keywords, so any other name followed by
+                // '(' calls a scalar function
                 if self.match_token(TokenType::LeftParen) {
-                    self.parse_aggregate_function(name)
+                    self.parse_scalar_function(name)
+                } else if self.match_token(TokenType::Dot) {
+                    let column = self.parse_identifier()?;
+                    Ok(Expression::Column(format!("{}.{}", name, column)))
                 } else {
                     Ok(Expression::Column(name))
                 }
@@ -818,11 +1797,50 @@ impl Parser {
                 self.advance();
                 Ok(Expression::NumberLiteral(value))
             }
+            Some(TokenType::Parameter(number)) => {
+                let mixed = match number {
+                    Some(_) => self.positional_parameters > 0,
+                    None => self.numbered_parameters,
+                };
+                if mixed {
+                    return Err(self
+                        .error_at_current("Cannot mix '?' and '$n' parameters in one statement"));
+                }
+                self.advance();
+                let number = match number {
+                    Some(n) => {
+                        self.numbered_parameters = true;
+                        n
+                    }
+                    None => {
+                        self.positional_parameters += 1;
+                        self.positional_parameters
+                    }
+                };
+                Ok(Expression::Parameter(number))
+            }
+            Some(TokenType::True) => {
+                self.advance();
+                Ok(Expression::BooleanLiteral(true))
+            }
+            Some(TokenType::False) => {
+                self.advance();
+                Ok(Expression::BooleanLiteral(false))
+            }
             Some(TokenType::Count)
             | Some(TokenType::Sum)
             | Some(TokenType::Avg)
             | Some(TokenType::Min)
-            | Some(TokenType::Max) => {
+            | Some(TokenType::Max)
+            | Some(TokenType::Median)
+            | Some(TokenType::Percentile)
+            | Some(TokenType::ApproxQuantile)
+            | Some(TokenType::Stddev)
+            | Some(TokenType::StddevSamp)
+            | Some(TokenType::StddevPop)
+            | Some(TokenType::Variance)
+            | Some(TokenType::VarSamp)
+            | Some(TokenType::VarPop) => {
                 let function_name = self.peek_token().unwrap().value();
                 self.advance();
                 self.consume_token(
@@ -837,21 +1855,45 @@ impl Parser {
                 self.consume_token(TokenType::RightParen, "Expected ')' after expression")?;
                 Ok(expr)
             }
+            Some(TokenType::Cast) => {
+                // CAST(expr AS type), with a type name as in CREATE TABLE
+                self.advance();
+                self.consume_token(TokenType::LeftParen, "Expected '(' after CAST")?;
+                let expr = self.parse_expression()?;
+                self.consume_token(TokenType::As, "Expected AS in CAST")?;
+                let target = self.parse_identifier()?.parse::<DataType>()?;
+                self.consume_token(TokenType::RightParen, "Expected ')' after CAST type")?;
+                Ok(Expression::Cast {
+                    expr: Box::new(expr),
+                    target,
+                })
+            }
+            Some(TokenType::Case) => {
+                self.advance();
+                self.parse_case()
+            }
             Some(TokenType::Asterisk) => {
                 self.advance();
                 Ok(Expression::Column("*".to_string()))
             }
-            Some(token_type) => Err(DatabaseError::parser_error(format!(
-                "Unexpected token: {:?}",
-                token_type
-            ))),
+            Some(token_type) => {
+                Err(self.error_at_current(format!("Unexpected token: {:?}", token_type)))
+            }
             None => Err(DatabaseError::parser_error("Unexpected end of input")),
         }
     }
 
     /// Parses an aggregate function call.
     fn parse_aggregate_function(&mut self, function: String) -> Result<Expression> {
+        let distinct = self.match_token(TokenType::Distinct);
         let argument = self.parse_expression()?;
+
+        // Additional arguments, e.g. PERCENTILE(latency, 0.95)
+        let mut parameters = Vec::new();
+        while self.match_token(TokenType::Comma) {
+            parameters.push(self.parse_expression()?);
+        }
+
         self.consume_token(
             TokenType::RightParen,
             "Expected ')' after aggregate function argument",
@@ -860,9 +1902,53 @@ impl Parser {
         Ok(Expression::AggregateFunction {
             function,
             argument: Box::new(argument),
+            parameters,
+            distinct,
         })
     }
 
+    /// Parses the branches of a CASE expression, after the CASE keyword.
+    fn parse_case(&mut self) -> Result<Expression> {
+        let mut branches = Vec::new();
+        while self.match_token(TokenType::When) {
+            let condition = self.parse_expression()?;
+            self.consume_token(TokenType::Then, "Expected THEN after WHEN condition")?;
+            branches.push((condition, self.parse_expression()?));
+        }
+        if branches.is_empty() {
+            return Err(self.error_at_current("Expected WHEN after CASE"));
+        }
+
+        let else_expr = if self.match_token(TokenType::Else) {
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+        self.consume_token(TokenType::End, "Expected END to close CASE")?;
+
+        Ok(Expression::Case {
+            branches,
+            else_expr,
+        })
+    }
+
+    /// Parses the arguments of a scalar function call, after the '('.
+    fn parse_scalar_function(&mut self, name: String) -> Result<Expression> {
+        let mut args = Vec::new();
+        if !self.match_token(TokenType::RightParen) {
+            args.push(self.parse_expression()?);
+            while self.match_token(TokenType::Comma) {
+                args.push(self.parse_expression()?);
+            }
+            self.consume_token(
+                TokenType::RightParen,
+                "Expected ')' after function arguments",
+            )?;
+        }
+
+        Ok(Expression::ScalarFunction { name, args })
+    }
+
     /// Parses a column name (identifier).
     fn parse_identifier(&mut self) -> Result<String> {
         match self.peek_token() {
@@ -872,7 +1958,7 @@ impl Parser {
                     self.advance();
                     Ok(name)
                 }
-                _ => Err(DatabaseError::parser_error(format!(
+                _ => Err(self.error_at_current(format!(
                     "Expected identifier, found {:?}",
                     token.token_type
                 ))),
@@ -883,17 +1969,28 @@ impl Parser {
         }
     }
 
-    /// Parses GROUP BY column list.
-    fn parse_group_by_columns(&mut self) -> Result<Vec<String>> {
-        let mut columns = Vec::new();
+    /// Parses a column name, optionally qualified as `table.column`.
+    fn parse_column_reference(&mut self) -> Result<String> {
+        let name = self.parse_identifier()?;
+        if self.match_token(TokenType::Dot) {
+            let column = self.parse_identifier()?;
+            Ok(format!("{}.{}", name, column))
+        } else {
+            Ok(name)
+        }
+    }
 
-        columns.push(self.parse_identifier()?);
+    /// Parses GROUP BY key list.
+    fn parse_group_by_items(&mut self) -> Result<Vec<Expression>> {
+        let mut items = Vec::new();
+
+        items.push(self.parse_expression()?);
 
         while self.match_token(TokenType::Comma) {
-            columns.push(self.parse_identifier()?);
+            items.push(self.parse_expression()?);
         }
 
-        Ok(columns)
+        Ok(items)
     }
 
     /// Parses ORDER BY item list.
@@ -909,9 +2006,11 @@ impl Parser {
         Ok(items)
     }
 
-    /// Parses a single ORDER BY item (column with optional direction).
+    /// Parses a single ORDER BY item (column, aggregate, or SELECT list
+    /// position, with optional direction).
     fn parse_order_by_item(&mut self) -> Result<OrderByItem> {
-        let column = self.parse_identifier()?;
+        // A signed position such as `-1` parses, for the planner to reject
+        let expression = self.parse_unary_expression()?;
 
         // Check for optional ASC or DESC
         let direction = if self.match_token(TokenType::Desc) {
@@ -923,7 +2022,10 @@ impl Parser {
             SortDirection::Ascending
         };
 
-        Ok(OrderByItem { column, direction })
+        Ok(OrderByItem {
+            expression,
+            direction,
+        })
     }
 
     /// Parses a number literal (for LIMIT and OFFSET).
@@ -932,7 +2034,7 @@ impl Parser {
             Some(token) => match &token.token_type {
                 TokenType::NumberLiteral(num_str) => {
                     let value = num_str.parse::<usize>().map_err(|_| {
-                        DatabaseError::parser_error(format!(
+                        self.error_at_current(format!(
                             "Invalid number literal '{}': must be a positive integer",
                             num_str
                         ))
@@ -940,7 +2042,7 @@ impl Parser {
                     self.advance();
                     Ok(value)
                 }
-                _ => Err(DatabaseError::parser_error(format!(
+                _ => Err(self.error_at_current(format!(
                     "Expected number literal, found {:?}",
                     token.token_type
                 ))),
@@ -965,6 +2067,13 @@ impl Parser {
         self.peek_token().map(|t| t.token_type.clone())
     }
 
+    /// Returns the type of the token `offset` positions ahead of the current one.
+    fn peek_token_type_at(&self, offset: usize) -> Option<TokenType> {
+        self.tokens
+            .get(self.position + offset)
+            .map(|t| t.token_type.clone())
+    }
+
     /// Advances to the next token.
     fn advance(&mut self) {
         if self.position < self.tokens.len() {
@@ -984,6 +2093,25 @@ impl Parser {
         false
     }
 
+    /// Builds a parser error that points at the current token.
+    fn error_at_current(&self, message: impl Into<String>) -> DatabaseError {
+        let message = message.into();
+        match self.peek_token() {
+            Some(token) => DatabaseError::parser_error(format!(
+                "{} at line {}, column {}",
+                message, token.line, token.column
+            )),
+            None => DatabaseError::parser_error(message),
+        }
+    }
+
+    /// Consumes the end of a statement: any semicolons, then EOF.
+    fn consume_end_of_statement(&mut self) -> Result<()> {
+        // A statement may end with semicolons, e.g. `SELECT * FROM t;`
+        while self.match_token(TokenType::Semicolon) {}
+        self.consume_token(TokenType::EOF, "Expected end of statement")
+    }
+
     /// Consumes the current token if it matches the expected type,
     /// otherwise returns an error.
     fn consume_token(&mut self, expected: TokenType, error_message: &str) -> Result<()> {
@@ -993,10 +2121,7 @@ impl Parser {
                 return Ok(());
             }
 
-            return Err(DatabaseError::parser_error(format!(
-                "{} at line {}, column {}",
-                error_message, token.line, token.column
-            )));
+            return Err(self.error_at_current(error_message));
         }
 
         Err(DatabaseError::parser_error(format!(
@@ -1031,6 +2156,7 @@ mod tests {
                 assert!(select_stmt.where_clause.is_none());
                 assert!(select_stmt.group_by.is_none());
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
@@ -1047,7 +2173,7 @@ mod tests {
             Query::Select(select_stmt) => {
                 assert_eq!(select_stmt.select_items.len(), 3);
 
-                if let SelectItem::Expression(Expression::Column(name)) =
+                if let SelectItem::Expression(Expression::Column(name), _) =
                     &select_stmt.select_items[0]
                 {
                     assert_eq!(name, "name");
@@ -1055,7 +2181,7 @@ mod tests {
                     panic!("Expected column name");
                 }
 
-                if let SelectItem::Expression(Expression::Column(age)) =
+                if let SelectItem::Expression(Expression::Column(age), _) =
                     &select_stmt.select_items[1]
                 {
                     assert_eq!(age, "age");
@@ -1063,7 +2189,7 @@ mod tests {
                     panic!("Expected column age");
                 }
 
-                if let SelectItem::Expression(Expression::Column(city)) =
+                if let SelectItem::Expression(Expression::Column(city), _) =
                     &select_stmt.select_items[2]
                 {
                     assert_eq!(city, "city");
@@ -1073,6 +2199,7 @@ mod tests {
 
                 assert_eq!(select_stmt.from_table, "users");
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
@@ -1112,6 +2239,7 @@ mod tests {
                     panic!("Expected binary operation");
                 }
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
@@ -1131,11 +2259,125 @@ mod tests {
 
                 let group_by = select_stmt.group_by.unwrap();
                 assert_eq!(group_by.len(), 1);
-                assert_eq!(group_by[0], "city");
+                assert_eq!(group_by[0], Expression::Column("city".to_string()));
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
+    /// Test GROUP BY with HAVING
+    #[test]
+    fn test_select_group_by_having() {
+        let mut parser = Parser::new(
+            "SELECT product, SUM(amount) FROM sales GROUP BY product HAVING SUM(amount) > 1000 LIMIT 5",
+        );
+        let Query::Select(select_stmt) = parser.parse().unwrap() else {
+            panic!("Expected SELECT query");
+        };
+
+        assert_eq!(
+            select_stmt.group_by,
+            Some(vec![Expression::Column("product".to_string())])
+        );
+        assert_eq!(select_stmt.limit, Some(5));
+        match select_stmt.having {
+            Some(Expression::BinaryOp {
+                left,
+                operator: BinaryOperator::Greater,
+                right,
+            }) => {
+                assert!(matches!(
+                    *left,
+                    Expression::AggregateFunction { ref function, .. } if function == "SUM"
+                ));
+                assert_eq!(*right, Expression::NumberLiteral("1000".to_string()));
+            }
+            other => panic!("Expected a comparison, got {:?}", other),
+        }
+
+        assert!(Parser::new("SELECT a FROM t GROUP BY a HAVING")
+            .parse()
+            .is_err());
+    }
+
+    /// Test formatting expressions back as SQL
+    #[test]
+    fn test_expression_display() {
+        let expression_text = |sql: &str| {
+            let Query::Select(stmt) = Parser::new(&format!("SELECT {} FROM t", sql))
+                .parse()
+                .unwrap()
+            else {
+                panic!("Expected SELECT query");
+            };
+            match &stmt.select_items[0] {
+                SelectItem::Expression(expr, _) => expr.to_string(),
+                SelectItem::Wildcard => panic!("Expected an expression"),
+            }
+        };
+
+        assert_eq!(expression_text("salary / 12"), "salary / 12");
+        assert_eq!(expression_text("a + b * c"), "a + b * c");
+        assert_eq!(expression_text("(a + b) * c"), "(a + b) * c");
+        assert_eq!(expression_text("a - (b - c)"), "a - (b - c)");
+        assert_eq!(expression_text("a - b - c"), "a - b - c");
+        assert_eq!(expression_text("-(a + 1)"), "-(a + 1)");
+        assert_eq!(expression_text("sum(DISTINCT x)"), "SUM(DISTINCT x)");
+        assert_eq!(expression_text("t.a * 1.5"), "t.a * 1.5");
+        assert_eq!(
+            expression_text("case when a > 1 then 'x' else b end"),
+            "CASE WHEN a > 1 THEN 'x' ELSE b END"
+        );
+    }
+
+    /// Test ORDER BY on an aggregate
+    #[test]
+    fn test_order_by_aggregate() {
+        let mut parser = Parser::new(
+            "SELECT product, SUM(amount) FROM sales GROUP BY product ORDER BY SUM(amount) DESC, product",
+        );
+        let Query::Select(select_stmt) = parser.parse().unwrap() else {
+            panic!("Expected SELECT query");
+        };
+
+        let order_by = select_stmt.order_by.unwrap();
+        assert_eq!(order_by.len(), 2);
+        assert!(matches!(
+            &order_by[0].expression,
+            Expression::AggregateFunction { function, .. } if function == "SUM"
+        ));
+        assert_eq!(order_by[0].direction, SortDirection::Descending);
+        assert_eq!(
+            order_by[1].expression,
+            Expression::Column("product".to_string())
+        );
+        assert_eq!(order_by[1].direction, SortDirection::Ascending);
+    }
+
+    /// Test ORDER BY on SELECT list positions
+    #[test]
+    fn test_order_by_position() {
+        let mut parser = Parser::new("SELECT a, b FROM t ORDER BY 2 DESC, -1");
+        let Query::Select(select_stmt) = parser.parse().unwrap() else {
+            panic!("Expected SELECT query");
+        };
+
+        let order_by = select_stmt.order_by.unwrap();
+        assert_eq!(
+            order_by[0].expression,
+            Expression::NumberLiteral("2".to_string())
+        );
+        assert_eq!(order_by[0].direction, SortDirection::Descending);
+        // Negative positions parse; the planner rejects them
+        assert!(matches!(
+            order_by[1].expression,
+            Expression::UnaryOp {
+                operator: UnaryOperator::Minus,
+                ..
+            }
+        ));
+    }
+
     /// Test aggregate functions
     #[test]
     fn test_aggregate_functions() {
@@ -1151,10 +2393,12 @@ mod tests {
                 assert_eq!(select_stmt.select_items.len(), 5);
 
                 // Check COUNT(*)
-                if let SelectItem::Expression(Expression::AggregateFunction {
-                    function,
-                    argument,
-                }) = &select_stmt.select_items[0]
+                if let SelectItem::Expression(
+                    Expression::AggregateFunction {
+                        function, argument, ..
+                    },
+                    _,
+                ) = &select_stmt.select_items[0]
                 {
                     assert_eq!(function, "COUNT");
                     if let Expression::Column(c) = &**argument {
@@ -1167,10 +2411,12 @@ mod tests {
                 }
 
                 // Check SUM(age)
-                if let SelectItem::Expression(Expression::AggregateFunction {
-                    function,
-                    argument,
-                }) = &select_stmt.select_items[1]
+                if let SelectItem::Expression(
+                    Expression::AggregateFunction {
+                        function, argument, ..
+                    },
+                    _,
+                ) = &select_stmt.select_items[1]
                 {
                     assert_eq!(function, "SUM");
                     if let Expression::Column(col) = &**argument {
@@ -1183,10 +2429,12 @@ mod tests {
                 }
 
                 // Check AVG(salary)
-                if let SelectItem::Expression(Expression::AggregateFunction {
-                    function,
-                    argument,
-                }) = &select_stmt.select_items[2]
+                if let SelectItem::Expression(
+                    Expression::AggregateFunction {
+                        function, argument, ..
+                    },
+                    _,
+                ) = &select_stmt.select_items[2]
                 {
                     assert_eq!(function, "AVG");
                     if let Expression::Column(col) = &**argument {
@@ -1199,10 +2447,12 @@ mod tests {
                 }
 
                 // Check MIN(age)
-                if let SelectItem::Expression(Expression::AggregateFunction {
-                    function,
-                    argument,
-                }) = &select_stmt.select_items[3]
+                if let SelectItem::Expression(
+                    Expression::AggregateFunction {
+                        function, argument, ..
+                    },
+                    _,
+                ) = &select_stmt.select_items[3]
                 {
                     assert_eq!(function, "MIN");
                     if let Expression::Column(col) = &**argument {
@@ -1215,10 +2465,12 @@ mod tests {
                 }
 
                 // Check MAX(age)
-                if let SelectItem::Expression(Expression::AggregateFunction {
-                    function,
-                    argument,
-                }) = &select_stmt.select_items[4]
+                if let SelectItem::Expression(
+                    Expression::AggregateFunction {
+                        function, argument, ..
+                    },
+                    _,
+                ) = &select_stmt.select_items[4]
                 {
                     assert_eq!(function, "MAX");
                     if let Expression::Column(col) = &**argument {
@@ -1230,6 +2482,344 @@ mod tests {
                     panic!("Expected MAX(age)");
                 }
             }
+            _ => panic!("Expected SELECT query"),
+        }
+    }
+
+    /// Test aggregate functions with a second argument
+    #[test]
+    fn test_quantile_aggregate_arguments() {
+        let mut parser = Parser::new(
+            "SELECT PERCENTILE(latency, 0.5), APPROX_QUANTILE(latency, 0.95) FROM requests",
+        );
+        let query = parser.parse().unwrap();
+
+        match query {
+            Query::Select(select_stmt) => {
+                let expected = [("PERCENTILE", "0.5"), ("APPROX_QUANTILE", "0.95")];
+                for (item, (name, quantile)) in select_stmt.select_items.iter().zip(expected) {
+                    if let SelectItem::Expression(
+                        Expression::AggregateFunction {
+                            function,
+                            argument,
+                            parameters,
+                            ..
+                        },
+                        _,
+                    ) = item
+                    {
+                        assert_eq!(function, name);
+                        assert_eq!(**argument, Expression::Column("latency".to_string()));
+                        assert_eq!(
+                            parameters,
+                            &vec![Expression::NumberLiteral(quantile.to_string())]
+                        );
+                    } else {
+                        panic!("Expected {} aggregate", name);
+                    }
+                }
+            }
+            _ => panic!("Expected SELECT query"),
+        }
+    }
+
+    /// Test the DISTINCT modifier inside aggregate calls
+    #[test]
+    fn test_aggregate_distinct_flag() {
+        let mut parser = Parser::new("SELECT SUM(DISTINCT amount), AVG(amount) FROM sales");
+        let query = parser.parse().unwrap();
+
+        let Query::Select(select_stmt) = query else {
+            panic!("Expected SELECT query");
+        };
+        let flags: Vec<bool> = select_stmt
+            .select_items
+            .iter()
+            .map(|item| match item {
+                SelectItem::Expression(
+                    Expression::AggregateFunction {
+                        argument, distinct, ..
+                    },
+                    _,
+                ) => {
+                    assert_eq!(**argument, Expression::Column("amount".to_string()));
+                    *distinct
+                }
+                _ => panic!("Expected aggregate"),
+            })
+            .collect();
+        assert_eq!(flags, vec![true, false]);
+
+        assert!(Parser::new("SELECT SUM(DISTINCT) FROM sales")
+            .parse()
+            .is_err());
+    }
+
+    /// Test JOIN clauses and qualified column names
+    #[test]
+    fn test_join() {
+        let mut parser = Parser::new(
+            "SELECT users.name, orders.amount FROM users JOIN orders ON users.id = orders.user_id \
+             INNER JOIN items ON item_id = items.id ORDER BY orders.amount",
+        );
+        let Query::Select(select_stmt) = parser.parse().unwrap() else {
+            panic!("Expected SELECT query");
+        };
+
+        assert_eq!(select_stmt.from_table, "users");
+        assert_eq!(
+            select_stmt.select_items[0],
+            SelectItem::Expression(Expression::Column("users.name".to_string()), None)
+        );
+        assert_eq!(
+            select_stmt.joins,
+            vec![
+                JoinClause {
+                    table: "orders".to_string(),
+                    left_column: "users.id".to_string(),
+                    right_column: "orders.user_id".to_string(),
+                },
+                JoinClause {
+                    table: "items".to_string(),
+                    left_column: "item_id".to_string(),
+                    right_column: "items.id".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            select_stmt.order_by.unwrap()[0].expression,
+            Expression::Column("orders.amount".to_string())
+        );
+
+        for sql in [
+            "SELECT * FROM a JOIN b",
+            "SELECT * FROM a JOIN b ON a.id",
+            "SELECT * FROM a JOIN b ON a.id > b.id",
+            "SELECT * FROM a JOIN ON a.id = b.id",
+            "SELECT a. FROM a",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{} should fail", sql);
+        }
+    }
+
+    /// Test IN and NOT IN lists
+    #[test]
+    fn test_in_list() {
+        let mut parser =
+            Parser::new("SELECT name FROM users WHERE age IN (25, 30) AND city NOT IN ('Paris')");
+        let Query::Select(select_stmt) = parser.parse().unwrap() else {
+            panic!("Expected SELECT query");
+        };
+
+        let expected = Expression::BinaryOp {
+            left: Box::new(Expression::InList {
+                expr: Box::new(Expression::Column("age".to_string())),
+                list: vec![
+                    Expression::NumberLiteral("25".to_string()),
+                    Expression::NumberLiteral("30".to_string()),
+                ],
+                negated: false,
+            }),
+            operator: BinaryOperator::And,
+            right: Box::new(Expression::InList {
+                expr: Box::new(Expression::Column("city".to_string())),
+                list: vec![Expression::StringLiteral("Paris".to_string())],
+                negated: true,
+            }),
+        };
+        assert_eq!(select_stmt.where_clause, Some(expected));
+
+        for sql in [
+            "SELECT name FROM users WHERE age IN ()",
+            "SELECT name FROM users WHERE age IN 25",
+            "SELECT name FROM users WHERE age IN (25",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{} should fail", sql);
+        }
+    }
+
+    /// Test IS NULL and IS NOT NULL, alone and under NOT and OR
+    #[test]
+    fn test_is_null() {
+        let where_clause = |sql: &str| {
+            let Query::Select(select_stmt) = Parser::new(sql).parse().unwrap() else {
+                panic!("Expected SELECT query");
+            };
+            select_stmt.where_clause.unwrap()
+        };
+        let is_null = |column: &str, negated| Expression::IsNull {
+            expr: Box::new(Expression::Column(column.to_string())),
+            negated,
+        };
+
+        assert_eq!(
+            where_clause("SELECT * FROM users WHERE email IS NULL"),
+            is_null("email", false)
+        );
+        assert_eq!(
+            where_clause("SELECT * FROM users WHERE NOT email is not null OR age IS NULL"),
+            Expression::BinaryOp {
+                left: Box::new(Expression::UnaryOp {
+                    operator: UnaryOperator::Not,
+                    operand: Box::new(is_null("email", true)),
+                }),
+                operator: BinaryOperator::Or,
+                right: Box::new(is_null("age", false)),
+            }
+        );
+        assert_eq!(is_null("email", true).to_string(), "email IS NOT NULL");
+
+        for sql in [
+            "SELECT * FROM users WHERE email IS",
+            "SELECT * FROM users WHERE email IS NOT",
+            "SELECT * FROM users WHERE email IS 5",
+            "SELECT * FROM users WHERE email NOT NULL",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{} should fail", sql);
+        }
+    }
+
+    /// Test `?` and `$n` parameter placeholders
+    #[test]
+    fn test_scalar_function_calls() {
+        let query = Parser::new(
+            "SELECT upper(name) AS shout, TRIM(city) FROM users WHERE LENGTH(name) > 5",
+        )
+        .parse()
+        .unwrap();
+        let Query::Select(select_stmt) = &query else {
+            panic!("Expected SELECT query");
+        };
+        assert_eq!(
+            select_stmt.select_items[0],
+            SelectItem::Expression(
+                Expression::ScalarFunction {
+                    name: "upper".to_string(),
+                    args: vec![Expression::Column("name".to_string())],
+                },
+                Some("shout".to_string())
+            )
+        );
+        assert_eq!(
+            select_stmt.where_clause.as_ref().unwrap().to_string(),
+            "LENGTH(name) > 5"
+        );
+
+        // Any number of arguments parses; the planner checks the count
+        let query = Parser::new("SELECT f(), g(a, b + 1) FROM t")
+            .parse()
+            .unwrap();
+        let Query::Select(select_stmt) = &query else {
+            panic!("Expected SELECT query");
+        };
+        let names: Vec<String> = select_stmt
+            .select_items
+            .iter()
+            .map(|item| match item {
+                SelectItem::Expression(expr, _) => expr.to_string(),
+                SelectItem::Wildcard => panic!("Expected an expression"),
+            })
+            .collect();
+        assert_eq!(names, vec!["F()", "G(a, b + 1)"]);
+
+        assert!(Parser::new("SELECT UPPER(name FROM users").parse().is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let query = Parser::new("SELECT name FROM users WHERE age > ? AND name IN (?, ?)")
+            .parse()
+            .unwrap();
+        assert_eq!(query.parameter_count(), 3);
+        let Query::Select(select_stmt) = &query else {
+            panic!("Expected SELECT query");
+        };
+        assert_eq!(
+            select_stmt.where_clause.as_ref().unwrap().to_string(),
+            "age > $1 AND name IN ($2, $3)"
+        );
+
+        let query = Parser::new("SELECT name FROM users WHERE age > $2 OR age < $2")
+            .parse()
+            .unwrap();
+        assert_eq!(query.parameter_count(), 2);
+        assert_eq!(
+            Parser::new("SELECT name FROM users")
+                .parse()
+                .unwrap()
+                .parameter_count(),
+            0
+        );
+
+        for sql in [
+            "SELECT name FROM users WHERE age > ? AND age < $2",
+            "SELECT name FROM users WHERE age > $1 AND age < ?",
+            "SELECT name FROM users WHERE age > $0",
+            "SELECT name FROM users WHERE age > $",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{} should fail", sql);
+        }
+    }
+
+    /// Test BETWEEN desugaring and its precedence against AND/OR
+    #[test]
+    fn test_between() {
+        let where_clause = |sql: &str| {
+            let Query::Select(select_stmt) = Parser::new(sql).parse().unwrap() else {
+                panic!("Expected SELECT query");
+            };
+            select_stmt.where_clause.unwrap()
+        };
+        let compare = |column: &str, operator, value: &str| Expression::BinaryOp {
+            left: Box::new(Expression::Column(column.to_string())),
+            operator,
+            right: Box::new(Expression::NumberLiteral(value.to_string())),
+        };
+        let binary = |left, operator, right| Expression::BinaryOp {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        };
+
+        let age_range = binary(
+            compare("age", BinaryOperator::GreaterEqual, "25"),
+            BinaryOperator::And,
+            compare("age", BinaryOperator::LessEqual, "40"),
+        );
+        assert_eq!(
+            where_clause("SELECT * FROM users WHERE age BETWEEN 25 AND 40"),
+            age_range
+        );
+
+        // The AND inside BETWEEN is consumed first; the next AND is logical
+        assert_eq!(
+            where_clause("SELECT * FROM users WHERE age BETWEEN 25 AND 40 AND id = 1"),
+            binary(
+                age_range,
+                BinaryOperator::And,
+                compare("id", BinaryOperator::Equal, "1")
+            )
+        );
+
+        assert_eq!(
+            where_clause("SELECT * FROM users WHERE id = 1 OR age NOT BETWEEN 25 AND 40"),
+            binary(
+                compare("id", BinaryOperator::Equal, "1"),
+                BinaryOperator::Or,
+                binary(
+                    compare("age", BinaryOperator::Less, "25"),
+                    BinaryOperator::Or,
+                    compare("age", BinaryOperator::Greater, "40"),
+                )
+            )
+        );
+
+        for sql in [
+            "SELECT * FROM users WHERE age BETWEEN 25",
+            "SELECT * FROM users WHERE age BETWEEN 25 OR 40",
+            "SELECT * FROM users WHERE age BETWEEN AND 40",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{} should fail", sql);
         }
     }
 
@@ -1289,6 +2879,7 @@ mod tests {
                     panic!("Expected binary operation");
                 }
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
@@ -1305,7 +2896,7 @@ mod tests {
             Query::Select(select_stmt) => {
                 assert_eq!(select_stmt.select_items.len(), 2);
 
-                if let SelectItem::Expression(Expression::StringLiteral(s)) =
+                if let SelectItem::Expression(Expression::StringLiteral(s), _) =
                     &select_stmt.select_items[0]
                 {
                     assert_eq!(s, "hello");
@@ -1313,7 +2904,7 @@ mod tests {
                     panic!("Expected string literal");
                 }
 
-                if let SelectItem::Expression(Expression::StringLiteral(s)) =
+                if let SelectItem::Expression(Expression::StringLiteral(s), _) =
                     &select_stmt.select_items[1]
                 {
                     assert_eq!(s, "world");
@@ -1321,6 +2912,7 @@ mod tests {
                     panic!("Expected string literal");
                 }
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
@@ -1345,7 +2937,9 @@ mod tests {
             assert!(result.is_ok(), "Failed for operator {}", op_str);
 
             let query = result.unwrap();
-            let Query::Select(select_stmt) = query;
+            let Query::Select(select_stmt) = query else {
+                panic!("Expected SELECT query");
+            };
             if let Some(Expression::BinaryOp { operator, .. }) = select_stmt.where_clause {
                 assert_eq!(operator, expected_op, "Operator mismatch for {}", op_str);
             } else {
@@ -1381,6 +2975,7 @@ mod tests {
                     panic!("Expected unary operation");
                 }
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
@@ -1430,6 +3025,26 @@ mod tests {
         assert!(matches!(&tokens[1].token_type, TokenType::NumberLiteral(s) if s == "45.67"));
     }
 
+    #[test]
+    fn test_tokenizer_number_exponents() {
+        let tokens = Tokenizer::new("1e6 2.5E-3 7e+2 3e x").tokenize().unwrap();
+        let types: Vec<TokenType> = tokens.into_iter().map(|t| t.token_type).collect();
+
+        assert_eq!(
+            types,
+            vec![
+                TokenType::NumberLiteral("1e6".to_string()),
+                TokenType::NumberLiteral("2.5E-3".to_string()),
+                TokenType::NumberLiteral("7e+2".to_string()),
+                // Without digits after it, the 'e' is not an exponent
+                TokenType::NumberLiteral("3".to_string()),
+                TokenType::Identifier("e".to_string()),
+                TokenType::Identifier("x".to_string()),
+                TokenType::EOF,
+            ]
+        );
+    }
+
     /// Test tokenizer with strings
     #[test]
     fn test_tokenizer_strings() {
@@ -1476,7 +3091,9 @@ mod tests {
             assert!(result.is_ok(), "Failed for SQL: {}", sql);
 
             let query = result.unwrap();
-            let Query::Select(select_stmt) = query;
+            let Query::Select(select_stmt) = query else {
+                panic!("Expected SELECT query");
+            };
             assert_eq!(select_stmt.from_table, "users");
         }
     }
@@ -1500,8 +3117,9 @@ mod tests {
 
                 let group_by = select_stmt.group_by.unwrap();
                 assert_eq!(group_by.len(), 1);
-                assert_eq!(group_by[0], "city");
+                assert_eq!(group_by[0], Expression::Column("city".to_string()));
             }
+            _ => panic!("Expected SELECT query"),
         }
     }
 
@@ -1538,9 +3156,342 @@ mod tests {
                     panic!("Expected binary operation");
                 }
             }
+            _ => panic!("Expected SELECT query"),
+        }
+    }
+
+    /// Test INSERT INTO ... VALUES
+    #[test]
+    fn test_parse_errors_report_position() {
+        // Tokenizer errors surface from parse() instead of an empty token stream
+        let err = Parser::new("SELECT price #5 FROM t").parse().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Unexpected character '#' at line 1, column 14"
+        );
+
+        let err = Parser::new("SELECT a, b users").parse().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Expected FROM at line 1, column 13"
+        );
+
+        let err = Parser::new("SELECT a\nFROM t\nLIMIT x")
+            .parse()
+            .unwrap_err();
+        assert!(err.to_string().ends_with("at line 3, column 7"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_select_aliases() {
+        let query = Parser::new("SELECT salary AS pay, name, COUNT(*) AS n FROM users")
+            .parse()
+            .unwrap();
+        let Query::Select(select_stmt) = query else {
+            panic!("Expected SELECT query");
+        };
+        assert_eq!(
+            select_stmt.select_items,
+            vec![
+                SelectItem::Expression(
+                    Expression::Column("salary".to_string()),
+                    Some("pay".to_string())
+                ),
+                SelectItem::Expression(Expression::Column("name".to_string()), None),
+                SelectItem::Expression(
+                    Expression::AggregateFunction {
+                        function: "COUNT".to_string(),
+                        argument: Box::new(Expression::Column("*".to_string())),
+                        parameters: vec![],
+                        distinct: false,
+                    },
+                    Some("n".to_string())
+                ),
+            ]
+        );
+
+        assert!(Parser::new("SELECT salary AS FROM users").parse().is_err());
+    }
+
+    #[test]
+    fn test_parse_insert() {
+        let mut parser =
+            Parser::new("INSERT INTO users VALUES (1, 'Alice', -2.5), (2, 'Bob', TRUE)");
+        let query = parser.parse().unwrap();
+
+        let Query::Insert(stmt) = query else {
+            panic!("Expected INSERT");
+        };
+        assert_eq!(stmt.table, "users");
+        assert_eq!(stmt.rows.len(), 2);
+        assert_eq!(
+            stmt.rows[0][1],
+            Expression::StringLiteral("Alice".to_string())
+        );
+        assert_eq!(stmt.rows[0][2].to_string(), "-2.5");
+        assert_eq!(stmt.rows[1][2], Expression::BooleanLiteral(true));
+
+        for sql in [
+            "INSERT users VALUES (1)",
+            "INSERT INTO users (1)",
+            "INSERT INTO users VALUES 1, 2",
+            "INSERT INTO users VALUES (1,)",
+            "INSERT INTO users VALUES (1) extra",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{}", sql);
+        }
+    }
+
+    /// Test CREATE TABLE
+    #[test]
+    fn test_parse_create_table() {
+        let query =
+            Parser::new("CREATE TABLE events (id INT, score float, label TEXT, ok BOOLEAN)")
+                .parse()
+                .unwrap();
+
+        let Query::CreateTable(stmt) = query else {
+            panic!("Expected CREATE TABLE");
+        };
+        assert_eq!(stmt.table, "events");
+        assert_eq!(
+            stmt.columns,
+            vec![
+                ("id".to_string(), DataType::Int64),
+                ("score".to_string(), DataType::Float64),
+                ("label".to_string(), DataType::String),
+                ("ok".to_string(), DataType::Boolean),
+            ]
+        );
+
+        let err = Parser::new("CREATE TABLE t (a INT, a TEXT)")
+            .parse()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("declared more than once"),
+            "{}",
+            err
+        );
+        for sql in [
+            "CREATE TABLE t ()",
+            "CREATE TABLE t (a DECIMAL)",
+            "CREATE TABLE t (a)",
+            "CREATE t (a INT)",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{}", sql);
+        }
+    }
+
+    /// Test CREATE TABLE ... AS SELECT
+    #[test]
+    fn test_parse_create_table_as() {
+        let query = Parser::new(
+            "CREATE TABLE summary AS SELECT dept, AVG(salary) AS avg_salary FROM employees GROUP BY dept;",
+        )
+        .parse()
+        .unwrap();
+
+        let Query::CreateTableAs(stmt) = query else {
+            panic!("Expected CREATE TABLE AS");
+        };
+        assert_eq!(stmt.table, "summary");
+        assert_eq!(stmt.query.from_table, "employees");
+        assert_eq!(stmt.query.select_items.len(), 2);
+        assert!(stmt.query.group_by.is_some());
+
+        // Placeholders in the query count as the statement's parameters
+        let query = Parser::new("CREATE TABLE t AS SELECT id FROM users WHERE id > ?")
+            .parse()
+            .unwrap();
+        assert_eq!(query.parameter_count(), 1);
+
+        for sql in [
+            "CREATE TABLE t AS",
+            "CREATE TABLE t AS INSERT INTO u VALUES (1)",
+            "CREATE TABLE t AS SELECT id FROM users extra",
+            "CREATE TABLE AS SELECT id FROM users",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_parse_from_file_path() {
+        let query = Parser::new("SELECT COUNT(*) FROM 'data/sales.csv' WHERE amount > 100")
+            .parse()
+            .unwrap();
+        let Query::Select(stmt) = query else {
+            panic!("Expected SELECT query");
+        };
+        assert_eq!(stmt.from_table, "data/sales.csv");
+        assert!(stmt.from_file);
+
+        let Query::Select(stmt) = Parser::new("SELECT * FROM sales").parse().unwrap() else {
+            panic!("Expected SELECT query");
+        };
+        assert!(!stmt.from_file);
+    }
+
+    #[test]
+    fn test_parse_derived_table() {
+        let query = Parser::new(
+            "SELECT d.dept, COUNT(*) FROM (SELECT department AS dept FROM employees \
+             WHERE salary > ?) AS d WHERE dept != ? GROUP BY d.dept",
+        )
+        .parse()
+        .unwrap();
+        let Query::Select(stmt) = query else {
+            panic!("Expected SELECT query");
+        };
+        assert_eq!(stmt.from_table, "d");
+        assert!(!stmt.from_file);
+        let inner = stmt.from_subquery.as_deref().unwrap();
+        assert_eq!(inner.from_table, "employees");
+        // Parameters are numbered across both levels, inner query first
+        assert_eq!(
+            inner.where_clause.as_ref().unwrap().to_string(),
+            "salary > $1"
+        );
+        assert_eq!(
+            stmt.where_clause.as_ref().unwrap().to_string(),
+            "dept != $2"
+        );
+
+        // The alias is optional, and AS may be left out
+        for (sql, alias) in [
+            ("SELECT * FROM (SELECT id FROM users)", ""),
+            ("SELECT * FROM (SELECT id FROM users) u", "u"),
+            (
+                "SELECT * FROM (SELECT * FROM (SELECT id FROM users) a) b",
+                "b",
+            ),
+        ] {
+            let Query::Select(stmt) = Parser::new(sql).parse().unwrap() else {
+                panic!("Expected SELECT query");
+            };
+            assert_eq!(stmt.from_table, alias, "{}", sql);
+            assert!(stmt.from_subquery.is_some(), "{}", sql);
+        }
+
+        for sql in [
+            "SELECT * FROM (SELECT id FROM users",
+            "SELECT * FROM (users)",
+            "SELECT * FROM (SELECT id FROM users) AS",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_parse_with_clause() {
+        let query = Parser::new(
+            "WITH olds AS (SELECT * FROM users WHERE age > ?), \
+             cities AS (SELECT city, COUNT(*) AS n FROM olds GROUP BY city) \
+             SELECT city FROM cities WHERE n > ?;",
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(query.parameter_count(), 2);
+
+        let Query::Select(stmt) = query else {
+            panic!("Expected SELECT query");
+        };
+        let names: Vec<&str> = stmt.ctes.iter().map(|cte| cte.name.as_str()).collect();
+        assert_eq!(names, ["olds", "cities"]);
+        assert_eq!(stmt.ctes[1].query.from_table, "olds");
+        assert_eq!(stmt.from_table, "cities");
+
+        for (sql, message) in [
+            (
+                "WITH a AS (SELECT id FROM t), a AS (SELECT id FROM u) SELECT id FROM a",
+                "WITH defines 'a' more than once",
+            ),
+            ("WITH a (SELECT id FROM t) SELECT id FROM a", "AS"),
+            ("WITH a AS SELECT id FROM t SELECT id FROM a", "("),
+            ("WITH a AS (SELECT id FROM t)", "SELECT"),
+        ] {
+            let err = Parser::new(sql).parse().unwrap_err();
+            assert!(err.to_string().contains(message), "{}: {}", sql, err);
+        }
+    }
+
+    /// Test TRUE/FALSE literals
+    #[test]
+    fn test_boolean_literals() {
+        let mut parser = Parser::new("SELECT id FROM t WHERE active = true AND flagged != FALSE");
+        let query = parser.parse().unwrap();
+
+        let where_clause = match query {
+            Query::Select(select_stmt) => select_stmt.where_clause.unwrap(),
+            _ => panic!("Expected SELECT query"),
+        };
+        assert_eq!(
+            where_clause.to_string(),
+            "active = TRUE AND flagged != FALSE"
+        );
+
+        if let Expression::BinaryOp { left, .. } = where_clause {
+            if let Expression::BinaryOp { right, .. } = &*left {
+                assert_eq!(**right, Expression::BooleanLiteral(true));
+            } else {
+                panic!("Expected comparison");
+            }
+        } else {
+            panic!("Expected binary operation");
         }
     }
 
+    /// Test statements ending in semicolons
+    #[test]
+    fn test_trailing_semicolon() {
+        let with = Parser::new("SELECT id FROM t WHERE id > 1;")
+            .parse()
+            .unwrap();
+        let without = Parser::new("SELECT id FROM t WHERE id > 1")
+            .parse()
+            .unwrap();
+        assert_eq!(with, without);
+
+        assert!(Parser::new("INSERT INTO t VALUES (1);;").parse().is_ok());
+        assert!(Parser::new("CREATE TABLE t (id INT);").parse().is_ok());
+        assert!(Parser::new("SELECT id FROM t; SELECT id FROM t")
+            .parse()
+            .is_err());
+    }
+
+    /// Test double-quoted identifiers
+    #[test]
+    fn test_quoted_identifiers() {
+        let mut parser =
+            Parser::new("SELECT \"User ID\", \"say \"\"hi\"\"\", \"count\", Name FROM \"MyTable\"");
+        let stmt = match parser.parse().unwrap() {
+            Query::Select(stmt) => stmt,
+            _ => panic!("Expected SELECT query"),
+        };
+
+        let columns: Vec<Expression> = stmt
+            .select_items
+            .into_iter()
+            .map(|item| match item {
+                SelectItem::Expression(expr, None) => expr,
+                other => panic!("Expected a plain expression, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                Expression::Column("User ID".to_string()),
+                Expression::Column("say \"hi\"".to_string()),
+                Expression::Column("count".to_string()),
+                Expression::Column("name".to_string()),
+            ]
+        );
+        assert_eq!(stmt.from_table, "MyTable");
+
+        assert!(Parser::new("SELECT \"\" FROM t").parse().is_err());
+        assert!(Parser::new("SELECT \"open FROM t").parse().is_err());
+    }
+
     /// Test arithmetic operators
     #[test]
     fn test_arithmetic_operators() {
@@ -1554,11 +3505,14 @@ mod tests {
             Query::Select(select_stmt) => {
                 assert_eq!(select_stmt.select_items.len(), 1);
 
-                if let SelectItem::Expression(Expression::BinaryOp {
-                    left,
-                    operator,
-                    right,
-                }) = &select_stmt.select_items[0]
+                if let SelectItem::Expression(
+                    Expression::BinaryOp {
+                        left,
+                        operator,
+                        right,
+                    },
+                    _,
+                ) = &select_stmt.select_items[0]
                 {
                     assert_eq!(*operator, BinaryOperator::Plus);
                     if let Expression::Column(c) = &**left {
@@ -1575,6 +3529,83 @@ mod tests {
                     panic!("Expected binary operation");
                 }
             }
+            _ => panic!("Expected SELECT query"),
+        }
+    }
+
+    /// Test CAST expressions
+    #[test]
+    fn test_cast_expression() {
+        let query = Parser::new("SELECT CAST(zip AS TEXT), cast(price * 2 AS int) FROM t")
+            .parse()
+            .unwrap();
+        let Query::Select(select_stmt) = query else {
+            panic!("Expected SELECT query");
+        };
+
+        assert_eq!(
+            select_stmt.select_items[0],
+            SelectItem::Expression(
+                Expression::Cast {
+                    expr: Box::new(Expression::Column("zip".to_string())),
+                    target: DataType::String,
+                },
+                None
+            )
+        );
+        if let SelectItem::Expression(expr, _) = &select_stmt.select_items[1] {
+            assert_eq!(expr.to_string(), "CAST(price * 2 AS Int64)");
+        } else {
+            panic!("Expected an expression");
+        }
+
+        assert!(Parser::new("SELECT CAST(zip AS blob) FROM t")
+            .parse()
+            .is_err());
+        assert!(Parser::new("SELECT CAST(zip TEXT) FROM t").parse().is_err());
+        assert!(Parser::new("SELECT CAST zip AS TEXT FROM t")
+            .parse()
+            .is_err());
+    }
+
+    /// Test CASE expressions and GROUP BY keys other than columns
+    #[test]
+    fn test_case_expression() {
+        let query = Parser::new(
+            "SELECT case when age < 30 then 'young' when age < 50 then 'mid' end, COUNT(*) \
+             FROM users GROUP BY 1, CASE WHEN x THEN 1 ELSE y + 1 END",
+        )
+        .parse()
+        .unwrap();
+        let Query::Select(select_stmt) = query else {
+            panic!("Expected SELECT query");
+        };
+
+        let SelectItem::Expression(
+            Expression::Case {
+                branches,
+                else_expr,
+            },
+            None,
+        ) = &select_stmt.select_items[0]
+        else {
+            panic!("Expected a CASE expression");
+        };
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[1].1, Expression::StringLiteral("mid".to_string()));
+        assert!(else_expr.is_none());
+
+        let group_by = select_stmt.group_by.unwrap();
+        assert_eq!(group_by[0], Expression::NumberLiteral("1".to_string()));
+        assert_eq!(group_by[1].to_string(), "CASE WHEN x THEN 1 ELSE y + 1 END");
+
+        for sql in [
+            "SELECT CASE END FROM t",
+            "SELECT CASE WHEN a THEN 1 FROM t",
+            "SELECT CASE WHEN a 1 END FROM t",
+            "SELECT CASE WHEN a THEN 1 ELSE END FROM t",
+        ] {
+            assert!(Parser::new(sql).parse().is_err(), "{}", sql);
         }
     }
 }
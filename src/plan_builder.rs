@@ -0,0 +1,678 @@
+//! # Plan Builder
+//!
+//! Builds query plans from Rust code, without writing SQL. Each step wraps
+//! the plan so far in one more operator, resolving the column names it is
+//! given against the columns the plan produces at that point:
+//!
+//! - [`PlanBuilder::scan`] reads every column of a table
+//! - [`PlanBuilder::filter`] keeps the rows matching an [`Expr`]
+//! - [`PlanBuilder::project`] keeps and reorders columns
+//! - [`PlanBuilder::group_by`] groups rows and computes [`agg`] aggregates
+//! - [`PlanBuilder::order_by`] and [`PlanBuilder::limit`] sort and trim
+//!
+//! Predicates and aggregates are built by the same [`Planner`] code that
+//! plans SQL, so a built plan returns what the equivalent query returns.
+//! Unknown columns are a [`PlannerError::ColumnNotFound`] and comparisons
+//! between incompatible types a [`PlannerError::TypeMismatch`], both
+//! reported by the step that names them rather than when the plan runs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mini_rust_olap::catalog::Catalog;
+//! use mini_rust_olap::column::{Column, IntColumn, StringColumn};
+//! use mini_rust_olap::plan_builder::{agg, col, lit, PlanBuilder};
+//! use mini_rust_olap::table::Table;
+//! use mini_rust_olap::types::{SortDirection, Value};
+//!
+//! let mut names = StringColumn::new();
+//! let mut ages = IntColumn::new();
+//! let mut salaries = IntColumn::new();
+//! for (name, age, salary) in [("Ann", 41, 100), ("Bob", 25, 70), ("Ann", 35, 90), ("Cy", 52, 80)] {
+//!     names.push_value(Value::from(name))?;
+//!     ages.push_value(Value::Int64(age))?;
+//!     salaries.push_value(Value::Int64(salary))?;
+//! }
+//! let mut table = Table::new("users".to_string());
+//! table.add_column("name".to_string(), Box::new(names))?;
+//! table.add_column("age".to_string(), Box::new(ages))?;
+//! table.add_column("salary".to_string(), Box::new(salaries))?;
+//! let mut catalog = Catalog::new();
+//! catalog.register_table(table)?;
+//!
+//! // SELECT name, SUM(salary) FROM users WHERE age > 30
+//! // GROUP BY name ORDER BY name LIMIT 10
+//! let result = PlanBuilder::scan(&catalog, "users")?
+//!     .filter(col("age").gt(lit(30)))?
+//!     .project(&["name", "salary"])?
+//!     .group_by(&["name"], &[agg::sum("salary")])?
+//!     .order_by("name", SortDirection::Ascending)?
+//!     .limit(10)
+//!     .execute()?;
+//!
+//! assert_eq!(result.column_names(), ["name", "SUM_salary"]);
+//! assert_eq!(
+//!     result.rows().collect::<Vec<_>>(),
+//!     vec![
+//!         vec![Value::from("Ann"), Value::Int64(190)],
+//!         vec![Value::from("Cy"), Value::Int64(80)],
+//!     ]
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::catalog::{match_identifier, quote_candidates, Catalog, IdentifierMatch};
+use crate::error::Result;
+use crate::execution::{CancellationToken, Filter, GroupBy, Limit, Operator, Project};
+use crate::parser::{BinaryOperator, Expression, UnaryOperator};
+use crate::planner::{run_plan, PlanResult, Planner, PlannerError, QueryResult, TOP_N_MAX_LIMIT};
+use crate::types::{DataType, SortDirection, Value};
+use std::collections::HashMap;
+use std::fmt;
+
+// ============================================================================
+// EXPRESSIONS
+// ============================================================================
+
+/// A condition or value computed from a row, for [`PlanBuilder::filter`].
+///
+/// Built with [`col`] and [`lit`] and combined with the comparison methods,
+/// [`Expr::and`], [`Expr::or`], `!` and the arithmetic operators:
+///
+/// ```rust
+/// use mini_rust_olap::plan_builder::{col, lit};
+///
+/// let condition = col("age").gt_eq(lit(18)).and(!col("email").is_null());
+/// assert_eq!(condition.to_string(), "age >= 18 AND NOT email IS NULL");
+/// let total = (col("price") * col("quantity")).lt(lit(100.5));
+/// assert_eq!(total.to_string(), "price * quantity < 100.5");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A column of the plan, by name
+    Column(String),
+    /// A constant
+    Literal(Value),
+    /// Two expressions joined by an operator
+    Binary {
+        left: Box<Expr>,
+        operator: BinaryOperator,
+        right: Box<Expr>,
+    },
+    /// The negation of a condition
+    Not(Box<Expr>),
+    /// A NULL test
+    IsNull {
+        /// The value tested
+        expr: Box<Expr>,
+        /// True for IS NOT NULL
+        negated: bool,
+    },
+}
+
+/// A reference to the column named `name`.
+pub fn col(name: &str) -> Expr {
+    Expr::Column(name.to_string())
+}
+
+/// A constant, such as `lit(30)`, `lit(2.5)` or `lit("Paris")`.
+pub fn lit(value: impl Into<Value>) -> Expr {
+    Expr::Literal(value.into())
+}
+
+impl Expr {
+    fn binary(self, operator: BinaryOperator, right: Expr) -> Expr {
+        Expr::Binary {
+            left: Box::new(self),
+            operator,
+            right: Box::new(right),
+        }
+    }
+
+    /// `self = other`
+    pub fn eq(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Equal, other)
+    }
+
+    /// `self != other`
+    pub fn not_eq(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::NotEqual, other)
+    }
+
+    /// `self < other`
+    pub fn lt(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Less, other)
+    }
+
+    /// `self <= other`
+    pub fn lt_eq(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::LessEqual, other)
+    }
+
+    /// `self > other`
+    pub fn gt(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Greater, other)
+    }
+
+    /// `self >= other`
+    pub fn gt_eq(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::GreaterEqual, other)
+    }
+
+    /// `self AND other`
+    pub fn and(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::And, other)
+    }
+
+    /// `self OR other`
+    pub fn or(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Or, other)
+    }
+
+    /// `self IS NULL`
+    pub fn is_null(self) -> Expr {
+        Expr::IsNull {
+            expr: Box::new(self),
+            negated: false,
+        }
+    }
+
+    /// `self IS NOT NULL`
+    pub fn is_not_null(self) -> Expr {
+        Expr::IsNull {
+            expr: Box::new(self),
+            negated: true,
+        }
+    }
+
+    /// The same expression in the parser's AST, which the planner builds
+    /// predicates from.
+    fn to_expression(&self) -> PlanResult<Expression> {
+        Ok(match self {
+            Expr::Column(name) => Expression::Column(name.clone()),
+            Expr::Literal(value) => match value {
+                Value::Int64(v) => Expression::NumberLiteral(v.to_string()),
+                Value::Float64(v) if v.is_finite() => {
+                    // A literal without a '.' would be read back as an integer
+                    let mut text = v.to_string();
+                    if !text.contains('.') {
+                        text.push_str(".0");
+                    }
+                    Expression::NumberLiteral(text)
+                }
+                Value::String(s) => Expression::StringLiteral(s.clone()),
+                Value::Boolean(b) => Expression::BooleanLiteral(*b),
+                Value::Date(_) => Expression::StringLiteral(value.to_string()),
+                Value::Float64(_) | Value::Null => {
+                    return Err(PlannerError::Custom(format!(
+                        "{} cannot be used as a literal; use is_null() to test for NULL",
+                        value
+                    )))
+                }
+            },
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => Expression::BinaryOp {
+                left: Box::new(left.to_expression()?),
+                operator: *operator,
+                right: Box::new(right.to_expression()?),
+            },
+            Expr::Not(operand) => Expression::UnaryOp {
+                operator: UnaryOperator::Not,
+                operand: Box::new(operand.to_expression()?),
+            },
+            Expr::IsNull { expr, negated } => Expression::IsNull {
+                expr: Box::new(expr.to_expression()?),
+                negated: *negated,
+            },
+        })
+    }
+}
+
+impl std::ops::Not for Expr {
+    type Output = Expr;
+
+    /// `NOT self`
+    fn not(self) -> Expr {
+        Expr::Not(Box::new(self))
+    }
+}
+
+impl std::ops::Add for Expr {
+    type Output = Expr;
+
+    fn add(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Plus, other)
+    }
+}
+
+impl std::ops::Sub for Expr {
+    type Output = Expr;
+
+    fn sub(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Minus, other)
+    }
+}
+
+impl std::ops::Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Multiply, other)
+    }
+}
+
+impl std::ops::Div for Expr {
+    type Output = Expr;
+
+    fn div(self, other: Expr) -> Expr {
+        self.binary(BinaryOperator::Divide, other)
+    }
+}
+
+impl fmt::Display for Expr {
+    /// Formats the expression as SQL.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_expression() {
+            Ok(expression) => write!(f, "{}", expression),
+            Err(_) => write!(f, "{:?}", self),
+        }
+    }
+}
+
+// ============================================================================
+// AGGREGATES
+// ============================================================================
+
+/// An aggregate call for [`PlanBuilder::group_by`], made by the functions
+/// in [`agg`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    /// The function name, e.g. `SUM`
+    function: String,
+    /// The column it reads, or None for `COUNT(*)`
+    column: Option<String>,
+    /// Whether only distinct values are aggregated
+    distinct: bool,
+    /// The output column name, if not the default
+    alias: Option<String>,
+}
+
+impl Aggregate {
+    fn new(function: &str, column: &str) -> Aggregate {
+        Aggregate {
+            function: function.to_string(),
+            column: Some(column.to_string()),
+            distinct: false,
+            alias: None,
+        }
+    }
+
+    /// Aggregate only the distinct values of the column, as
+    /// `COUNT(DISTINCT x)`.
+    pub fn distinct(mut self) -> Aggregate {
+        self.distinct = true;
+        self
+    }
+
+    /// Name the output column, as `AS name`.
+    pub fn alias(mut self, name: &str) -> Aggregate {
+        self.alias = Some(name.to_string());
+        self
+    }
+
+    /// The output column name: the alias, or the name SQL gives the same
+    /// call, e.g. `SUM_salary` or `COUNT(*)`.
+    pub fn output_name(&self) -> String {
+        if let Some(alias) = &self.alias {
+            return alias.clone();
+        }
+        match (&self.column, self.distinct) {
+            (None, _) => format!("{}(*)", self.function),
+            (Some(column), true) => format!("{}_DISTINCT_{}", self.function, column),
+            (Some(column), false) => format!("{}_{}", self.function, column),
+        }
+    }
+}
+
+/// Aggregate calls for [`PlanBuilder::group_by`].
+pub mod agg {
+    use super::Aggregate;
+
+    /// `COUNT(*)`: the number of rows
+    pub fn count_star() -> Aggregate {
+        Aggregate {
+            function: "COUNT".to_string(),
+            column: None,
+            distinct: false,
+            alias: None,
+        }
+    }
+
+    /// `COUNT(column)`: the number of non-NULL values
+    pub fn count(column: &str) -> Aggregate {
+        Aggregate::new("COUNT", column)
+    }
+
+    /// `SUM(column)`
+    pub fn sum(column: &str) -> Aggregate {
+        Aggregate::new("SUM", column)
+    }
+
+    /// `AVG(column)`
+    pub fn avg(column: &str) -> Aggregate {
+        Aggregate::new("AVG", column)
+    }
+
+    /// `MIN(column)`
+    pub fn min(column: &str) -> Aggregate {
+        Aggregate::new("MIN", column)
+    }
+
+    /// `MAX(column)`
+    pub fn max(column: &str) -> Aggregate {
+        Aggregate::new("MAX", column)
+    }
+}
+
+// ============================================================================
+// PLAN BUILDER
+// ============================================================================
+
+/// Builds a plan one operator at a time; see the [module docs](self).
+pub struct PlanBuilder<'a> {
+    /// Builds the predicates, aggregates and sorts
+    planner: Planner<'a>,
+    /// The plan so far
+    plan: Box<dyn Operator>,
+    /// The names and types of the columns `plan` produces
+    columns: Vec<(String, DataType)>,
+    /// Sort keys from `order_by` calls not yet applied, so consecutive
+    /// calls sort by several columns
+    sort_keys: Vec<(usize, SortDirection)>,
+}
+
+impl<'a> PlanBuilder<'a> {
+    /// Start a plan that reads every column of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `catalog` - The catalog holding the table
+    /// * `table` - The table's name, matched as in SQL
+    ///
+    /// # Returns
+    ///
+    /// The builder, or [`PlannerError::TableNotFound`]
+    pub fn scan(catalog: &'a Catalog, table: &str) -> PlanResult<Self> {
+        let planner = Planner::new(catalog);
+        let (plan, columns) = planner.scan_table(table)?;
+        Ok(PlanBuilder {
+            planner,
+            plan,
+            columns,
+            sort_keys: Vec::new(),
+        })
+    }
+
+    /// The names and types of the columns the plan produces so far.
+    pub fn columns(&self) -> &[(String, DataType)] {
+        &self.columns
+    }
+
+    /// Keep the rows for which `condition` is true.
+    ///
+    /// Comparisons take a column and a constant or two expressions, as in
+    /// WHERE; both sides must be numbers, or both of the same type (a
+    /// string may also be compared with a date).
+    pub fn filter(self, condition: Expr) -> PlanResult<Self> {
+        let mut builder = self.finish_sort(None);
+        builder.check_condition(&condition)?;
+
+        let column_names: HashMap<String, usize> = builder
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| (name.clone(), index))
+            .collect();
+        let identity: Vec<usize> = (0..builder.columns.len()).collect();
+        let expression = builder.resolve(&condition)?.to_expression()?;
+        let predicate = builder
+            .planner
+            .build_predicate(&expression, &column_names, &identity)?;
+
+        builder.plan = Box::new(Filter::new(builder.plan, predicate));
+        Ok(builder)
+    }
+
+    /// Keep only the named columns, in the order given.
+    pub fn project(self, columns: &[&str]) -> PlanResult<Self> {
+        let mut builder = self.finish_sort(None);
+        let indices = columns
+            .iter()
+            .map(|name| builder.column_index(name))
+            .collect::<PlanResult<Vec<_>>>()?;
+
+        builder.columns = indices
+            .iter()
+            .map(|&i| builder.columns[i].clone())
+            .collect();
+        builder.plan = Box::new(Project::new(builder.plan, indices));
+        Ok(builder)
+    }
+
+    /// Group rows by the `keys` columns and compute `aggregates` for each
+    /// group; with no keys the whole input is one group.
+    ///
+    /// The output has the key columns, then one column per aggregate named
+    /// by [`Aggregate::output_name`].
+    pub fn group_by(self, keys: &[&str], aggregates: &[Aggregate]) -> PlanResult<Self> {
+        let mut builder = self.finish_sort(None);
+        if builder.columns.is_empty() {
+            return Err(PlannerError::Custom(
+                "Cannot use aggregate functions on empty table".to_string(),
+            ));
+        }
+
+        let key_indices = keys
+            .iter()
+            .map(|name| builder.column_index(name))
+            .collect::<PlanResult<Vec<_>>>()?;
+        let mut output: Vec<(String, DataType)> = key_indices
+            .iter()
+            .map(|&i| builder.columns[i].clone())
+            .collect();
+
+        let mut aggregate_columns = Vec::new();
+        let mut functions = Vec::new();
+        for aggregate in aggregates {
+            // COUNT(*) counts rows, so any column will do as its input
+            let column = match &aggregate.column {
+                Some(name) => builder.column_index(name)?,
+                None => 0,
+            };
+            let function = builder.planner.build_aggregate(
+                &aggregate.function,
+                &builder.columns[column].1,
+                &[],
+                aggregate.distinct,
+                aggregate.column.is_none(),
+            )?;
+            output.push((aggregate.output_name(), function.data_type()));
+            aggregate_columns.push(column);
+            functions.push(function);
+        }
+
+        // GroupBy names its aggregates agg_0, agg_1, ...; rename them
+        let group_by = GroupBy::new(builder.plan, key_indices, aggregate_columns, functions);
+        let names = output.iter().map(|(name, _)| name.clone()).collect();
+        builder.plan = Box::new(
+            Project::new(Box::new(group_by), (0..output.len()).collect()).with_aliases(names),
+        );
+        builder.columns = output;
+        Ok(builder)
+    }
+
+    /// Sort by a column. Consecutive calls sort by several columns, the
+    /// first call's column first.
+    pub fn order_by(mut self, column: &str, direction: SortDirection) -> PlanResult<Self> {
+        let index = self.column_index(column)?;
+        self.sort_keys.push((index, direction));
+        Ok(self)
+    }
+
+    /// Keep only the first `count` rows. After `order_by` with a small
+    /// count, only the top rows are kept while sorting, as in SQL.
+    pub fn limit(self, count: usize) -> Self {
+        if !self.sort_keys.is_empty() && count <= TOP_N_MAX_LIMIT {
+            return self.finish_sort(Some(count));
+        }
+        let mut builder = self.finish_sort(None);
+        builder.plan = Box::new(Limit::new(builder.plan, Some(count), 0));
+        builder
+    }
+
+    /// Finish the plan.
+    pub fn build(self) -> Box<dyn Operator> {
+        self.finish_sort(None).plan
+    }
+
+    /// Finish the plan and run it to completion.
+    pub fn execute(self) -> Result<QueryResult> {
+        run_plan(self.build().as_mut(), &CancellationToken::new())
+    }
+
+    /// Apply the pending `order_by` keys, keeping the first `top_n` rows
+    /// if given.
+    fn finish_sort(mut self, top_n: Option<usize>) -> Self {
+        if !self.sort_keys.is_empty() {
+            let (columns, directions) = std::mem::take(&mut self.sort_keys).into_iter().unzip();
+            self.plan = self
+                .planner
+                .plan_sort(self.plan, columns, directions, top_n);
+        }
+        self
+    }
+
+    /// The position of a column in the plan's output, matched as in SQL.
+    fn column_index(&self, name: &str) -> PlanResult<usize> {
+        let names = self.columns.iter().map(|(name, _)| name);
+        match match_identifier(name, names) {
+            IdentifierMatch::Found(found) => Ok(self
+                .columns
+                .iter()
+                .position(|(name, _)| name == found)
+                .unwrap_or_default()),
+            IdentifierMatch::Missing => Err(PlannerError::ColumnNotFound(name.to_string())),
+            IdentifierMatch::Ambiguous(candidates) => Err(PlannerError::Custom(format!(
+                "Column '{}' is ambiguous: it matches {}; quote the name to choose one",
+                name,
+                quote_candidates(&candidates)
+            ))),
+        }
+    }
+
+    /// `expr` with every column named as the plan names it.
+    fn resolve(&self, expr: &Expr) -> PlanResult<Expr> {
+        Ok(match expr {
+            Expr::Column(name) => Expr::Column(self.columns[self.column_index(name)?].0.clone()),
+            Expr::Literal(_) => expr.clone(),
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => Expr::Binary {
+                left: Box::new(self.resolve(left)?),
+                operator: *operator,
+                right: Box::new(self.resolve(right)?),
+            },
+            Expr::Not(operand) => Expr::Not(Box::new(self.resolve(operand)?)),
+            Expr::IsNull { expr, negated } => Expr::IsNull {
+                expr: Box::new(self.resolve(expr)?),
+                negated: *negated,
+            },
+        })
+    }
+
+    /// Check that every comparison and arithmetic operation in a condition
+    /// combines compatible types.
+    fn check_condition(&self, condition: &Expr) -> PlanResult<()> {
+        match condition {
+            Expr::Binary {
+                left,
+                operator: BinaryOperator::And | BinaryOperator::Or,
+                right,
+            } => {
+                self.check_condition(left)?;
+                self.check_condition(right)
+            }
+            Expr::Not(operand) => self.check_condition(operand),
+            _ => self.value_type(condition).map(|_| ()),
+        }
+    }
+
+    /// The type of the value an expression computes, or None for a NULL
+    /// constant.
+    fn value_type(&self, expr: &Expr) -> PlanResult<Option<DataType>> {
+        match expr {
+            Expr::Column(name) => Ok(Some(self.columns[self.column_index(name)?].1)),
+            Expr::Literal(value) => Ok(value.data_type()),
+            Expr::Not(operand) => {
+                self.check_condition(operand)?;
+                Ok(Some(DataType::Boolean))
+            }
+            Expr::IsNull { expr, .. } => {
+                self.value_type(expr)?;
+                Ok(Some(DataType::Boolean))
+            }
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let (Some(left_type), Some(right_type)) =
+                    (self.value_type(left)?, self.value_type(right)?)
+                else {
+                    return Ok(None);
+                };
+                let mismatch = || PlannerError::TypeMismatch {
+                    expression: expr.to_string(),
+                    left: left_type,
+                    right: right_type,
+                };
+                match operator {
+                    BinaryOperator::Plus
+                    | BinaryOperator::Minus
+                    | BinaryOperator::Multiply
+                    | BinaryOperator::Divide => {
+                        if !left_type.is_numeric() || !right_type.is_numeric() {
+                            Err(mismatch())
+                        } else if left_type == DataType::Float64 || right_type == DataType::Float64
+                        {
+                            Ok(Some(DataType::Float64))
+                        } else {
+                            Ok(Some(DataType::Int64))
+                        }
+                    }
+                    BinaryOperator::And | BinaryOperator::Or => {
+                        self.check_condition(expr)?;
+                        Ok(Some(DataType::Boolean))
+                    }
+                    _ if comparable(left_type, right_type) => Ok(Some(DataType::Boolean)),
+                    _ => Err(mismatch()),
+                }
+            }
+        }
+    }
+}
+
+/// Whether values of two types can be compared.
+fn comparable(left: DataType, right: DataType) -> bool {
+    left == right
+        || (left.is_numeric() && right.is_numeric())
+        || matches!(
+            (left, right),
+            (DataType::String, DataType::Date) | (DataType::Date, DataType::String)
+        )
+}
@@ -0,0 +1,431 @@
+//! # Prepared Statements
+//!
+//! A prepared statement is a SELECT parsed once with parameter placeholders
+//! where its constants go, written `?` (numbered in order of appearance) or
+//! `$1`, `$2`, ... (which may repeat). Binding values to it checks each one
+//! against the column it is compared with and substitutes it as a literal,
+//! so the same statement runs with different constants without reparsing,
+//! and without formatting values into SQL text.
+//!
+//! ## Type Checking
+//!
+//! When the statement is prepared, each parameter that is a side of a
+//! comparison with a column (`age > ?`), or a member of an IN list on a
+//! column, takes that column's type. Binding accepts a value of the same
+//! type, any number for a numeric column, and a `'YYYY-MM-DD'` string for a
+//! Date column; anything else is an error before the query runs. NULL is
+//! never accepted, since a comparison with NULL matches nothing; use
+//! `IS NULL` instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mini_rust_olap::catalog::Catalog;
+//! use mini_rust_olap::column::{Column, IntColumn, StringColumn};
+//! use mini_rust_olap::table::Table;
+//! use mini_rust_olap::types::Value;
+//!
+//! let mut names = StringColumn::new();
+//! let mut ages = IntColumn::new();
+//! for (name, age) in [("Alice", 30), ("Bob", 25), ("Carol", 41)] {
+//!     names.push_value(Value::String(name.to_string()))?;
+//!     ages.push_value(Value::Int64(age))?;
+//! }
+//! let mut table = Table::new("people".to_string());
+//! table.add_column("name".to_string(), Box::new(names))?;
+//! table.add_column("age".to_string(), Box::new(ages))?;
+//! let mut catalog = Catalog::new();
+//! catalog.register_table(table)?;
+//!
+//! // Parsed once, run with different constants
+//! let statement = catalog.prepare("SELECT name FROM people WHERE age > ? ORDER BY name")?;
+//! let older_than = |age: i64| -> mini_rust_olap::Result<Vec<Value>> {
+//!     statement.bind(&[Value::Int64(age)])?.execute(&catalog)?.column("name")
+//! };
+//! assert_eq!(older_than(28)?, vec![Value::from("Alice"), Value::from("Carol")]);
+//! assert_eq!(older_than(40)?, vec![Value::from("Carol")]);
+//!
+//! // A value of the wrong type is rejected before the query runs
+//! assert!(statement.bind(&[Value::from("forty")]).is_err());
+//! # Ok::<(), mini_rust_olap::error::DatabaseError>(())
+//! ```
+
+use crate::catalog::Catalog;
+use crate::error::{DatabaseError, Result};
+use crate::execution::CancellationToken;
+use crate::parser::{Expression, Parser, Query};
+use crate::planner::{run_query, Planner, PlannerOptions, QueryResult};
+use crate::types::{DataType, Value};
+
+// ============================================================================
+// PREPARED STATEMENT
+// ============================================================================
+
+/// A parsed SELECT with parameter placeholders, ready to be bound.
+///
+/// Created by [`prepare`] or [`Catalog::prepare`].
+#[derive(Debug, Clone)]
+pub struct PreparedStatement {
+    /// The parsed statement, with `Expression::Parameter` placeholders
+    query: Query,
+
+    /// For each parameter, the column it is compared with, if any
+    parameter_columns: Vec<Option<(String, DataType)>>,
+}
+
+/// Parses a SELECT with parameter placeholders for repeated execution.
+///
+/// # Arguments
+///
+/// * `catalog` - The catalog holding the tables the query refers to
+/// * `sql` - The SQL text, with `?` or `$n` where values go
+///
+/// # Returns
+///
+/// The prepared statement, or an error if the SQL does not parse, is not a
+/// SELECT, refers to unknown tables or columns, or skips a parameter number
+/// (e.g. uses `$1` and `$3` but not `$2`)
+pub fn prepare(catalog: &Catalog, sql: &str) -> Result<PreparedStatement> {
+    let query = Parser::new(sql).parse()?;
+    let Query::Select(stmt) = &query else {
+        return Err(DatabaseError::planning_error(
+            "Only SELECT statements can be prepared",
+        ));
+    };
+
+    let count = query.parameter_count();
+    let mut used = vec![false; count];
+    for expr in query.expressions() {
+        expr.walk(&mut |expr| {
+            if let Expression::Parameter(n) = expr {
+                used[n - 1] = true;
+            }
+        });
+    }
+    if let Some(unused) = used.iter().position(|used| !used) {
+        return Err(DatabaseError::planning_error(format!(
+            "Parameter ${} is never used; number parameters from $1 without gaps",
+            unused + 1
+        )));
+    }
+
+    let parameter_columns = Planner::new(catalog).parameter_columns(stmt, count)?;
+    Ok(PreparedStatement {
+        query,
+        parameter_columns,
+    })
+}
+
+impl PreparedStatement {
+    /// The number of values [`PreparedStatement::bind`] expects.
+    pub fn parameter_count(&self) -> usize {
+        self.parameter_columns.len()
+    }
+
+    /// The type of the column each parameter is compared with, indexed
+    /// from `$1`; None for parameters that accept any value.
+    pub fn parameter_types(&self) -> Vec<Option<DataType>> {
+        self.parameter_columns
+            .iter()
+            .map(|column| column.as_ref().map(|(_, data_type)| *data_type))
+            .collect()
+    }
+
+    /// The parsed statement, placeholders included.
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Substitutes values for the statement's parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - One value per parameter, `values[0]` for `$1`
+    ///
+    /// # Returns
+    ///
+    /// The statement with every placeholder replaced by a literal, or an
+    /// error if the number of values is wrong, or a value is NULL, not a
+    /// finite number, or of a type its column cannot be compared with
+    pub fn bind(&self, values: &[Value]) -> Result<BoundStatement> {
+        if values.len() != self.parameter_count() {
+            return Err(DatabaseError::planning_error(format!(
+                "Statement takes {} parameter{}, {} given",
+                self.parameter_count(),
+                if self.parameter_count() == 1 { "" } else { "s" },
+                values.len()
+            )));
+        }
+
+        let literals = values
+            .iter()
+            .zip(&self.parameter_columns)
+            .enumerate()
+            .map(|(i, (value, column))| {
+                if let Some((name, data_type)) = column {
+                    if !accepts(*data_type, value) {
+                        return Err(DatabaseError::type_error(format!(
+                            "Parameter ${} is compared with column '{}' ({}) but was given {} ({})",
+                            i + 1,
+                            name,
+                            data_type,
+                            value,
+                            value.type_name()
+                        )));
+                    }
+                }
+                literal(i + 1, value)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut query = self.query.clone();
+        for expr in query.expressions_mut() {
+            expr.walk_mut(&mut |expr| {
+                if let Expression::Parameter(n) = expr {
+                    *expr = literals[*n - 1].clone();
+                }
+            });
+        }
+        Ok(BoundStatement { query })
+    }
+}
+
+/// Whether a value may be compared with a column of the given type.
+fn accepts(column_type: DataType, value: &Value) -> bool {
+    match value.data_type() {
+        Some(value_type) => {
+            value_type == column_type
+                || (value_type.is_numeric() && column_type.is_numeric())
+                || (value_type == DataType::String && column_type == DataType::Date)
+        }
+        None => false,
+    }
+}
+
+/// The literal expression standing for parameter `$number`'s value.
+fn literal(number: usize, value: &Value) -> Result<Expression> {
+    match value {
+        Value::Int64(v) => Ok(Expression::NumberLiteral(v.to_string())),
+        Value::Float64(v) if v.is_finite() => {
+            // A literal without a '.' would be read back as an integer
+            let mut text = v.to_string();
+            if !text.contains('.') {
+                text.push_str(".0");
+            }
+            Ok(Expression::NumberLiteral(text))
+        }
+        Value::Float64(v) => Err(DatabaseError::type_error(format!(
+            "Parameter ${} is {}; only finite numbers can be bound",
+            number, v
+        ))),
+        Value::String(s) => Ok(Expression::StringLiteral(s.clone())),
+        Value::Boolean(b) => Ok(Expression::BooleanLiteral(*b)),
+        Value::Date(_) => Ok(Expression::StringLiteral(value.to_string())),
+        Value::Null => Err(DatabaseError::type_error(format!(
+            "Parameter ${} is NULL, which no comparison matches; use IS NULL instead",
+            number
+        ))),
+    }
+}
+
+// ============================================================================
+// BOUND STATEMENT
+// ============================================================================
+
+/// A prepared statement with a value for every parameter.
+#[derive(Debug, Clone)]
+pub struct BoundStatement {
+    /// The statement with each placeholder replaced by a literal
+    query: Query,
+}
+
+impl BoundStatement {
+    /// The statement as it will run.
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Plans and runs the statement against `catalog`.
+    pub fn execute(&self, catalog: &Catalog) -> Result<QueryResult> {
+        self.execute_with_cancellation(catalog, &CancellationToken::new())
+    }
+
+    /// Plans and runs the statement, stopping once `token` is cancelled.
+    pub fn execute_with_cancellation(
+        &self,
+        catalog: &Catalog,
+        token: &CancellationToken,
+    ) -> Result<QueryResult> {
+        run_query(catalog, &self.query, PlannerOptions::default(), token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::{Column, FloatColumn, IntColumn, StringColumn};
+    use crate::planner::execute_query;
+    use crate::table::Table;
+
+    fn create_catalog() -> Catalog {
+        let mut names = StringColumn::new();
+        let mut ages = IntColumn::new();
+        let mut scores = FloatColumn::new();
+        for (name, age, score) in [
+            ("Alice", 30, 88.5),
+            ("Bob", 25, 72.0),
+            ("Carol", 41, 95.25),
+            ("O'Brien", 35, 60.0),
+        ] {
+            names.push_value(Value::String(name.to_string())).unwrap();
+            ages.push_value(Value::Int64(age)).unwrap();
+            scores.push_value(Value::Float64(score)).unwrap();
+        }
+        let mut table = Table::new("people".to_string());
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+        table.add_column("age".to_string(), Box::new(ages)).unwrap();
+        table
+            .add_column("score".to_string(), Box::new(scores))
+            .unwrap();
+        let mut catalog = Catalog::new();
+        catalog.register_table(table).unwrap();
+        catalog
+    }
+
+    fn names(result: QueryResult) -> Vec<String> {
+        result
+            .column("name")
+            .unwrap()
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_bind_runs_with_different_values() {
+        let catalog = create_catalog();
+        let statement = prepare(
+            &catalog,
+            "SELECT name FROM people WHERE age >= ? AND score < ? ORDER BY name",
+        )
+        .unwrap();
+        assert_eq!(statement.parameter_count(), 2);
+        assert_eq!(
+            statement.parameter_types(),
+            vec![Some(DataType::Int64), Some(DataType::Float64)]
+        );
+
+        let run = |age: i64, score: Value| {
+            names(
+                statement
+                    .bind(&[Value::Int64(age), score])
+                    .unwrap()
+                    .execute(&catalog)
+                    .unwrap(),
+            )
+        };
+        assert_eq!(run(30, Value::Float64(90.0)), vec!["Alice", "O'Brien"]);
+        assert_eq!(run(40, Value::Float64(100.0)), vec!["Carol"]);
+        // An integer compares with a Float64 column
+        assert_eq!(run(0, Value::Int64(70)), vec!["O'Brien"]);
+    }
+
+    #[test]
+    fn test_numbered_parameters_and_in_lists() {
+        let catalog = create_catalog();
+        let statement = prepare(
+            &catalog,
+            "SELECT name FROM people WHERE name IN ($2, $3) OR age = $1 ORDER BY name",
+        )
+        .unwrap();
+        assert_eq!(
+            statement.parameter_types(),
+            vec![
+                Some(DataType::Int64),
+                Some(DataType::String),
+                Some(DataType::String)
+            ]
+        );
+
+        let bound = statement
+            .bind(&[
+                Value::Int64(25),
+                Value::String("O'Brien".to_string()),
+                Value::String("x' OR '1' = '1".to_string()),
+            ])
+            .unwrap();
+        // Values are literals, never SQL text
+        assert_eq!(
+            names(bound.execute(&catalog).unwrap()),
+            vec!["Bob", "O'Brien"]
+        );
+
+        // A parameter may be used more than once
+        let statement = prepare(
+            &catalog,
+            "SELECT name FROM people WHERE age > $1 AND score > $1 ORDER BY name",
+        )
+        .unwrap();
+        let bound = statement.bind(&[Value::Int64(33)]).unwrap();
+        assert_eq!(
+            names(bound.execute(&catalog).unwrap()),
+            vec!["Carol", "O'Brien"]
+        );
+    }
+
+    #[test]
+    fn test_bind_rejects_wrong_values() {
+        let catalog = create_catalog();
+        let statement = prepare(&catalog, "SELECT name FROM people WHERE age > ?").unwrap();
+
+        let err = statement.bind(&[]).unwrap_err();
+        assert!(err.to_string().contains("takes 1 parameter, 0 given"));
+        assert!(statement.bind(&[Value::Int64(1), Value::Int64(2)]).is_err());
+
+        let err = statement
+            .bind(&[Value::String("thirty".to_string())])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Type error: Parameter $1 is compared with column 'age' (Int64) but was given thirty (String)"
+        );
+        assert!(statement.bind(&[Value::Null]).is_err());
+        assert!(statement.bind(&[Value::Float64(f64::NAN)]).is_err());
+        assert!(statement.bind(&[Value::Float64(29.5)]).is_ok());
+    }
+
+    #[test]
+    fn test_prepare_errors() {
+        let catalog = create_catalog();
+        assert!(prepare(
+            &catalog,
+            "SELECT name FROM people WHERE age > $1 AND age < $3"
+        )
+        .is_err());
+        assert!(prepare(
+            &catalog,
+            "SELECT name FROM people WHERE age > ? AND age < $2"
+        )
+        .is_err());
+        assert!(prepare(&catalog, "SELECT name FROM missing WHERE age > ?").is_err());
+        assert!(prepare(&catalog, "INSERT INTO people VALUES (?, 1, 2.0)").is_err());
+
+        // Without binding, a placeholder cannot run
+        let err = execute_query(&catalog, "SELECT name FROM people WHERE age > ?").unwrap_err();
+        assert!(err.to_string().contains("Parameter $1 has no value"));
+    }
+
+    #[test]
+    fn test_parameters_without_a_column_take_any_value() {
+        let catalog = create_catalog();
+        let statement = prepare(&catalog, "SELECT name FROM people WHERE age + ? > 40").unwrap();
+        assert_eq!(statement.parameter_types(), vec![None]);
+        let bound = statement.bind(&[Value::Float64(10.5)]).unwrap();
+        assert_eq!(
+            names(bound.execute(&catalog).unwrap()),
+            vec!["Alice", "Carol", "O'Brien"]
+        );
+    }
+}
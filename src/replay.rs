@@ -0,0 +1,524 @@
+//! # Replay Module
+//!
+//! This module records the queries of a session together with a fingerprint
+//! of their results, and replays a recording against a catalog to check that
+//! a new build still returns the same answers, and how fast.
+//!
+//! ## Result Fingerprints
+//!
+//! Every recorded statement stores its row count and a checksum of the result.
+//! The checksum is order-insensitive: each row is serialized canonically
+//! (type tag plus the deterministic `format_value` text of every cell), hashed
+//! with 64-bit FNV-1a, and the row hashes are summed. Two results with the
+//! same multiset of rows therefore have the same checksum, whatever order the
+//! rows come out in, which matters for GROUP BY queries whose group order is
+//! not specified.
+//!
+//! FNV-1a is used instead of `std`'s hasher because its output is fixed, so a
+//! recording made by one build can be checked by another.
+//!
+//! ## File Layout
+//!
+//! A recording is a text file with a header line and one line per statement:
+//!
+//! ```text
+//! # mini_rust_olap recording v1
+//! <row count>\t<checksum, 16 hex digits>\t<elapsed microseconds>\t<sql>
+//! ```
+//!
+//! Backslashes, tabs, and newlines in the SQL are escaped as `\\`, `\t`,
+//! and `\n`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use mini_rust_olap::replay::{replay, Recording};
+//!
+//! let mut recording = Recording::new();
+//! recording.execute(&catalog, "SELECT COUNT(*) FROM users")?;
+//! recording.save("session.replay")?;
+//!
+//! // Later, against a new build:
+//! let report = replay(&catalog, &Recording::load("session.replay")?);
+//! assert!(report.all_match());
+//! ```
+
+use crate::catalog::Catalog;
+use crate::error::{DatabaseError, Result};
+use crate::execution::Batch;
+use crate::format::{format_value, FormatOptions};
+use crate::planner::execute_sql;
+use crate::types::Value;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// First line of every recording file
+const HEADER: &str = "# mini_rust_olap recording v1";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// ============================================================================
+// RESULT CHECKSUMS
+// ============================================================================
+
+/// Hashes bytes with 64-bit FNV-1a.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Appends the canonical serialization of one cell to `out`.
+///
+/// Cells use the default formatting, whatever the session's float precision,
+/// and the type tag keeps `Int64(1)` and `Float64(1.0)` apart; the unit
+/// separator keeps cell boundaries unambiguous.
+fn write_canonical_cell(value: &Value, out: &mut String) {
+    out.push_str(value.type_name());
+    out.push(':');
+    out.push_str(&format_value(value, None, &FormatOptions::default()));
+    out.push('\u{1f}');
+}
+
+/// Computes the order-insensitive checksum of a query result.
+///
+/// # Arguments
+///
+/// * `batches` - The batches the query produced
+///
+/// # Returns
+///
+/// The sum of the FNV-1a hashes of every row's canonical serialization, or
+/// an error if a cell cannot be read
+pub fn result_checksum(batches: &[Batch]) -> Result<u64> {
+    let mut checksum = 0u64;
+    let mut row_text = String::new();
+
+    for batch in batches {
+        for row in 0..batch.row_count() {
+            row_text.clear();
+            for column in 0..batch.column_count() {
+                let value = batch.get(row, column)?;
+                write_canonical_cell(&value, &mut row_text);
+            }
+            checksum = checksum.wrapping_add(fnv1a(row_text.as_bytes()));
+        }
+    }
+
+    Ok(checksum)
+}
+
+// ============================================================================
+// RECORDING
+// ============================================================================
+
+/// One executed statement and the fingerprint of its result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedStatement {
+    /// The SQL text as executed
+    pub sql: String,
+    /// Number of rows the statement returned
+    pub row_count: usize,
+    /// Order-insensitive checksum of the result (see [`result_checksum`])
+    pub checksum: u64,
+    /// How long the statement took when it was recorded
+    pub elapsed: Duration,
+}
+
+/// An ordered list of recorded statements.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    statements: Vec<RecordedStatement>,
+}
+
+impl Recording {
+    /// Creates an empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded statements in execution order.
+    pub fn statements(&self) -> &[RecordedStatement] {
+        &self.statements
+    }
+
+    /// Returns the number of recorded statements.
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    /// Returns true if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    /// Records a statement whose result was produced elsewhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `sql` - The SQL text as executed
+    /// * `batches` - The batches the statement produced
+    /// * `elapsed` - How long the statement took
+    pub fn record(&mut self, sql: &str, batches: &[Batch], elapsed: Duration) -> Result<()> {
+        self.statements.push(RecordedStatement {
+            sql: sql.to_string(),
+            row_count: batches.iter().map(|b| b.row_count()).sum(),
+            checksum: result_checksum(batches)?,
+            elapsed,
+        });
+        Ok(())
+    }
+
+    /// Executes a statement against `catalog` and records it.
+    ///
+    /// # Returns
+    ///
+    /// The output column names and batches, as [`execute_sql`] returns them.
+    /// Failed statements are not recorded.
+    pub fn execute(&mut self, catalog: &Catalog, sql: &str) -> Result<(Vec<String>, Vec<Batch>)> {
+        let start = Instant::now();
+        let (column_names, batches) = execute_sql(catalog, sql)?;
+        self.record(sql, &batches, start.elapsed())?;
+        Ok((column_names, batches))
+    }
+
+    /// Writes the recording to a file, replacing any existing content.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut text = String::from(HEADER);
+        text.push('\n');
+        for statement in &self.statements {
+            text.push_str(&format!(
+                "{}\t{:016x}\t{}\t{}\n",
+                statement.row_count,
+                statement.checksum,
+                statement.elapsed.as_micros(),
+                escape_sql(&statement.sql)
+            ));
+        }
+
+        fs::write(path.as_ref(), text).map_err(|e| {
+            DatabaseError::ingestion_error(format!(
+                "Failed to write recording '{}': {}",
+                path.as_ref().display(),
+                e
+            ))
+        })
+    }
+
+    /// Reads a recording written by [`Recording::save`].
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| {
+            DatabaseError::ingestion_error(format!(
+                "Failed to read recording '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        Self::parse(&text).map_err(|msg| {
+            DatabaseError::ingestion_error(format!(
+                "Invalid recording '{}': {}",
+                path.display(),
+                msg
+            ))
+        })
+    }
+
+    /// Parses the text form of a recording.
+    fn parse(text: &str) -> std::result::Result<Self, String> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            _ => return Err(format!("missing '{}' header", HEADER)),
+        }
+
+        let mut statements = Vec::new();
+        for (index, line) in lines {
+            if line.is_empty() {
+                continue;
+            }
+            let line_number = index + 1;
+            let fields: Vec<&str> = line.splitn(4, '\t').collect();
+            let [row_count, checksum, micros, sql] = fields[..] else {
+                return Err(format!(
+                    "line {}: expected 4 tab-separated fields",
+                    line_number
+                ));
+            };
+
+            statements.push(RecordedStatement {
+                row_count: row_count
+                    .parse()
+                    .map_err(|_| format!("line {}: invalid row count", line_number))?,
+                checksum: u64::from_str_radix(checksum, 16)
+                    .map_err(|_| format!("line {}: invalid checksum", line_number))?,
+                elapsed: Duration::from_micros(
+                    micros
+                        .parse()
+                        .map_err(|_| format!("line {}: invalid duration", line_number))?,
+                ),
+                sql: unescape_sql(sql),
+            });
+        }
+
+        Ok(Recording { statements })
+    }
+}
+
+/// Escapes the characters that would break the line-based file layout.
+fn escape_sql(sql: &str) -> String {
+    let mut escaped = String::with_capacity(sql.len());
+    for c in sql.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverses [`escape_sql`].
+fn unescape_sql(text: &str) -> String {
+    let mut sql = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            sql.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => sql.push('\t'),
+            Some('n') => sql.push('\n'),
+            Some('r') => sql.push('\r'),
+            Some(other) => sql.push(other),
+            None => sql.push('\\'),
+        }
+    }
+    sql
+}
+
+// ============================================================================
+// REPLAY
+// ============================================================================
+
+/// The result of re-running one recorded statement.
+#[derive(Debug, Clone)]
+pub struct ReplayOutcome {
+    /// The statement as recorded
+    pub recorded: RecordedStatement,
+    /// Row count of the replayed result, if the statement succeeded
+    pub row_count: Option<usize>,
+    /// Checksum of the replayed result, if the statement succeeded
+    pub checksum: Option<u64>,
+    /// How long the replayed statement took
+    pub elapsed: Duration,
+    /// The error message, if the statement failed
+    pub error: Option<String>,
+}
+
+impl ReplayOutcome {
+    /// Returns true if the statement succeeded with the recorded result.
+    pub fn matches(&self) -> bool {
+        self.error.is_none()
+            && self.row_count == Some(self.recorded.row_count)
+            && self.checksum == Some(self.recorded.checksum)
+    }
+
+    /// Returns how many times faster the replay was than the recording
+    /// (below 1.0 means slower).
+    pub fn speedup(&self) -> f64 {
+        let replayed = self.elapsed.as_secs_f64();
+        if replayed == 0.0 {
+            return 1.0;
+        }
+        self.recorded.elapsed.as_secs_f64() / replayed
+    }
+}
+
+/// The outcome of replaying a whole recording.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    /// One outcome per recorded statement, in recording order
+    pub outcomes: Vec<ReplayOutcome>,
+}
+
+impl ReplayReport {
+    /// Returns true if every statement reproduced its recorded result.
+    pub fn all_match(&self) -> bool {
+        self.outcomes.iter().all(ReplayOutcome::matches)
+    }
+
+    /// Returns the outcomes whose result differed or that failed.
+    pub fn mismatches(&self) -> Vec<&ReplayOutcome> {
+        self.outcomes.iter().filter(|o| !o.matches()).collect()
+    }
+
+    /// Returns the total recorded and replayed time.
+    pub fn total_elapsed(&self) -> (Duration, Duration) {
+        self.outcomes.iter().fold(
+            (Duration::ZERO, Duration::ZERO),
+            |(recorded, replayed), o| (recorded + o.recorded.elapsed, replayed + o.elapsed),
+        )
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, outcome) in self.outcomes.iter().enumerate() {
+            let status = match (&outcome.error, outcome.matches()) {
+                (Some(_), _) => "ERROR",
+                (None, true) => "ok",
+                (None, false) => "MISMATCH",
+            };
+            writeln!(
+                f,
+                "#{:<3} {:<8} {:>10.3} ms -> {:>10.3} ms ({:.2}x)  {}",
+                index + 1,
+                status,
+                outcome.recorded.elapsed.as_secs_f64() * 1000.0,
+                outcome.elapsed.as_secs_f64() * 1000.0,
+                outcome.speedup(),
+                outcome.recorded.sql
+            )?;
+            if let Some(error) = &outcome.error {
+                writeln!(f, "      error: {}", error)?;
+            } else if !outcome.matches() {
+                writeln!(
+                    f,
+                    "      expected {} rows (checksum {:016x}), got {} rows (checksum {:016x})",
+                    outcome.recorded.row_count,
+                    outcome.recorded.checksum,
+                    outcome.row_count.unwrap_or(0),
+                    outcome.checksum.unwrap_or(0)
+                )?;
+            }
+        }
+
+        let (recorded, replayed) = self.total_elapsed();
+        write!(
+            f,
+            "{} of {} statements matched; total {:.3} ms -> {:.3} ms",
+            self.outcomes.len() - self.mismatches().len(),
+            self.outcomes.len(),
+            recorded.as_secs_f64() * 1000.0,
+            replayed.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/// Re-executes every statement of a recording and compares the results.
+///
+/// A failing statement is reported in its outcome rather than aborting the
+/// replay, so one broken query does not hide the state of the others.
+///
+/// # Arguments
+///
+/// * `catalog` - The tables to run the statements against
+/// * `recording` - The statements and their expected results
+///
+/// # Returns
+///
+/// A report with one outcome per statement
+pub fn replay(catalog: &Catalog, recording: &Recording) -> ReplayReport {
+    let outcomes = recording
+        .statements()
+        .iter()
+        .map(|recorded| {
+            let start = Instant::now();
+            let result = execute_sql(catalog, &recorded.sql).and_then(|(_, batches)| {
+                let row_count = batches.iter().map(|b| b.row_count()).sum();
+                Ok((row_count, result_checksum(&batches)?))
+            });
+            let elapsed = start.elapsed();
+
+            match result {
+                Ok((row_count, checksum)) => ReplayOutcome {
+                    recorded: recorded.clone(),
+                    row_count: Some(row_count),
+                    checksum: Some(checksum),
+                    elapsed,
+                    error: None,
+                },
+                Err(e) => ReplayOutcome {
+                    recorded: recorded.clone(),
+                    row_count: None,
+                    checksum: None,
+                    elapsed,
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .collect();
+
+    ReplayReport { outcomes }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::{Column, FloatColumn, IntColumn};
+    use std::sync::Arc;
+
+    fn batch(ints: &[i64], floats: &[f64]) -> Batch {
+        let mut int_col = IntColumn::new();
+        let mut float_col = FloatColumn::new();
+        for (&i, &f) in ints.iter().zip(floats) {
+            int_col.push_value(Value::Int64(i)).unwrap();
+            float_col.push_value(Value::Float64(f)).unwrap();
+        }
+        Batch::new(vec![Arc::new(int_col), Arc::new(float_col)])
+    }
+
+    #[test]
+    fn test_checksum_ignores_row_order_and_batching() {
+        let one = [batch(&[1, 2, 3], &[0.5, 1.5, 2.5])];
+        let shuffled = [batch(&[3], &[2.5]), batch(&[1, 2], &[0.5, 1.5])];
+        assert_eq!(
+            result_checksum(&one).unwrap(),
+            result_checksum(&shuffled).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_checksum_sees_values_duplicates_and_pairing() {
+        let base = result_checksum(&[batch(&[1, 2], &[0.5, 1.5])]).unwrap();
+        let changed = result_checksum(&[batch(&[1, 2], &[0.5, 1.25])]).unwrap();
+        let duplicated = result_checksum(&[batch(&[1, 1, 2], &[0.5, 0.5, 1.5])]).unwrap();
+        let swapped = result_checksum(&[batch(&[1, 2], &[1.5, 0.5])]).unwrap();
+
+        assert_ne!(base, changed);
+        assert_ne!(base, duplicated);
+        assert_ne!(base, swapped);
+        assert_eq!(result_checksum(&[]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_sql_escaping_round_trips() {
+        for sql in ["SELECT a FROM t", "a\tb\nc\\d", "trailing\\"] {
+            assert_eq!(unescape_sql(&escape_sql(sql)), sql);
+            assert!(!escape_sql(sql).contains(['\t', '\n']));
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_recordings() {
+        assert!(Recording::parse("").is_err());
+        assert!(Recording::parse("SELECT 1\n").is_err());
+        assert!(Recording::parse(&format!("{}\n1\t2\n", HEADER)).is_err());
+        assert!(Recording::parse(&format!("{}\nx\t00\t1\tSELECT\n", HEADER)).is_err());
+        assert!(Recording::parse(&format!("{}\n", HEADER))
+            .unwrap()
+            .is_empty());
+    }
+}
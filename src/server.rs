@@ -0,0 +1,430 @@
+//! # HTTP Query Server
+//!
+//! This module serves query results as JSON over HTTP, so lightweight
+//! dashboards can be pointed at a catalog. It is built with the `http`
+//! feature (`cargo build --features http`) and answers a single route:
+//!
+//! ```text
+//! POST /query    {"sql": "SELECT region, SUM(amount) FROM sales GROUP BY region"}
+//!
+//! 200 OK         {"columns": ["region", "SUM_amount"], "rows": [["east", 40], ["west", 25]]}
+//! 400            {"error": "Column not found: ..."}
+//! ```
+//!
+//! Int64 and Float64 values are JSON numbers, strings and dates (as
+//! `YYYY-MM-DD`) are JSON strings, booleans are JSON booleans, and NULL,
+//! NaN and infinities are `null`.
+//!
+//! ## Read-Only Snapshots
+//!
+//! The server holds a [`SharedCatalog`]. Each request runs against a
+//! snapshot of it, which shares the tables instead of copying them, so a
+//! query never sees a half-made change and nothing a request sends can
+//! modify the catalog. Tables registered through the `SharedCatalog` while
+//! the server runs are visible to the next request.
+//!
+//! ## Protocol
+//!
+//! The server is a hand-rolled HTTP/1.1 subset on `std::net`: one request
+//! per connection, each on its own thread, with the body length taken from
+//! `Content-Length` and capped at [`MAX_BODY_SIZE`]. It has no TLS or
+//! authentication; bind it to a loopback address.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use mini_rust_olap::catalog::SharedCatalog;
+//! use mini_rust_olap::server::QueryServer;
+//!
+//! let server = QueryServer::start("127.0.0.1:8080", SharedCatalog::new(catalog))?;
+//! println!("Serving on http://{}", server.local_addr());
+//! ```
+
+use crate::catalog::SharedCatalog;
+use crate::error::Result;
+use crate::planner::QueryResult;
+use crate::types::Value;
+use serde_json::{json, Value as Json};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Largest request body accepted, in bytes
+pub const MAX_BODY_SIZE: usize = 1 << 20;
+
+/// How long a client may take to send its request before it is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// SERVER
+// ============================================================================
+
+/// A running HTTP query server
+///
+/// Dropping the handle leaves the server running until the process exits;
+/// call [`QueryServer::shutdown`] to stop it.
+pub struct QueryServer {
+    /// Address the server accepts connections on
+    local_addr: SocketAddr,
+    /// Set to make the accept loop return
+    stopping: Arc<AtomicBool>,
+    /// The accept loop
+    thread: JoinHandle<()>,
+}
+
+impl QueryServer {
+    /// Binds `addr` and starts answering requests on a background thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address to listen on; port 0 picks a free port
+    /// * `catalog` - The catalog queries run against
+    ///
+    /// # Returns
+    ///
+    /// The running server, or an error if the address cannot be bound
+    pub fn start<A: ToSocketAddrs>(addr: A, catalog: SharedCatalog) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let stopping = Arc::new(AtomicBool::new(false));
+
+        let stop = Arc::clone(&stopping);
+        let thread = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                // A failed accept only loses that connection
+                let Ok(stream) = stream else { continue };
+                let catalog = catalog.clone();
+                thread::spawn(move || {
+                    // The client is gone if its response cannot be written
+                    let _ = handle_connection(stream, &catalog);
+                });
+            }
+        });
+
+        Ok(Self {
+            local_addr,
+            stopping,
+            thread,
+        })
+    }
+
+    /// Returns the address the server accepts connections on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stops accepting connections and waits for the accept loop to end.
+    ///
+    /// Requests already being answered run to completion.
+    pub fn shutdown(self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.local_addr);
+        let _ = self.thread.join();
+    }
+}
+
+// ============================================================================
+// REQUEST HANDLING
+// ============================================================================
+
+/// The parts of an HTTP request the server looks at
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// A response: a status code and a JSON body
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: Json,
+}
+
+impl Response {
+    fn ok(body: Json) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Reads one request from the connection and writes its response.
+fn handle_connection(stream: TcpStream, catalog: &SharedCatalog) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => route(&request, catalog),
+        Err(response) => response,
+    };
+    write_response(&mut &stream, &response)
+}
+
+/// Parses the request line, headers and body, or returns the error
+/// response to send instead.
+fn read_request(reader: &mut impl BufRead) -> std::result::Result<Request, Response> {
+    let malformed = |_| Response::error(400, "Malformed HTTP request");
+
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(malformed)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Response::error(400, "Malformed HTTP request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(malformed)? == 0 {
+            return Err(Response::error(400, "Request ended inside its headers"));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| Response::error(400, "Invalid Content-Length header"))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(Response::error(
+            413,
+            format!("Request body exceeds {} bytes", MAX_BODY_SIZE),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(malformed)?;
+
+    Ok(Request { method, path, body })
+}
+
+/// Answers a parsed request.
+fn route(request: &Request, catalog: &SharedCatalog) -> Response {
+    // A query string does not change the route
+    let path = request.path.split('?').next().unwrap_or_default();
+    match (request.method.as_str(), path) {
+        ("POST", "/query") => run_query(&request.body, catalog),
+        (_, "/query") => Response::error(405, "Use POST /query"),
+        _ => Response::error(404, format!("No route for {}", path)),
+    }
+}
+
+/// Runs the query in a `{"sql": "..."}` body against a catalog snapshot.
+fn run_query(body: &[u8], catalog: &SharedCatalog) -> Response {
+    let request: Json = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return Response::error(400, format!("Invalid JSON body: {}", e)),
+    };
+    let Some(sql) = request.get("sql").and_then(Json::as_str) else {
+        return Response::error(400, "Request body must be an object with an \"sql\" string");
+    };
+
+    match catalog.query(sql) {
+        Ok(result) => Response::ok(result_to_json(&result)),
+        Err(e) => Response::error(400, e.to_string()),
+    }
+}
+
+/// Writes a response with a JSON body and closes the exchange.
+fn write_response(writer: &mut impl Write, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        body.len(),
+        body
+    )?;
+    writer.flush()
+}
+
+/// The standard reason phrase of the status codes the server sends
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "",
+    }
+}
+
+// ============================================================================
+// JSON ENCODING
+// ============================================================================
+
+/// Encodes a query result as `{"columns": [...], "rows": [[...], ...]}`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::catalog::Catalog;
+/// use mini_rust_olap::column::IntColumn;
+/// use mini_rust_olap::server::result_to_json;
+/// use mini_rust_olap::table::Table;
+/// use mini_rust_olap::types::Value;
+///
+/// let mut table = Table::new("t".to_string());
+/// table.add_column("n".to_string(), Box::new(IntColumn::new())).unwrap();
+/// table.insert_rows(vec![vec![Value::Int64(3)]]).unwrap();
+/// let mut catalog = Catalog::new();
+/// catalog.register_table(table).unwrap();
+///
+/// let result = catalog.query("SELECT n FROM t").unwrap();
+/// assert_eq!(
+///     result_to_json(&result).to_string(),
+///     r#"{"columns":["n"],"rows":[[3]]}"#
+/// );
+/// ```
+pub fn result_to_json(result: &QueryResult) -> Json {
+    json!({
+        "columns": result.column_names(),
+        "rows": result
+            .rows()
+            .map(|row| row.iter().map(value_to_json).collect::<Vec<_>>())
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Encodes a value as the JSON type closest to it.
+///
+/// Floats that JSON cannot represent (NaN and infinities) become `null`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::server::value_to_json;
+/// use mini_rust_olap::types::Value;
+///
+/// assert_eq!(value_to_json(&Value::Int64(7)).to_string(), "7");
+/// assert_eq!(value_to_json(&Value::Date(0)).to_string(), "\"1970-01-01\"");
+/// assert_eq!(value_to_json(&Value::Float64(f64::NAN)).to_string(), "null");
+/// ```
+pub fn value_to_json(value: &Value) -> Json {
+    value.to_json()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::column::IntColumn;
+    use crate::table::Table;
+
+    fn request(raw: &str) -> std::result::Result<Request, Response> {
+        read_request(&mut raw.as_bytes())
+    }
+
+    fn numbers_catalog() -> SharedCatalog {
+        let mut table = Table::new("numbers".to_string());
+        table
+            .add_column("n".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        table
+            .insert_rows(vec![vec![Value::Int64(1)], vec![Value::Null]])
+            .unwrap();
+        let mut catalog = Catalog::new();
+        catalog.register_table(table).unwrap();
+        SharedCatalog::new(catalog)
+    }
+
+    #[test]
+    fn test_read_request() {
+        let parsed =
+            request("POST /query HTTP/1.1\r\nHost: x\r\ncontent-length: 4\r\n\r\nbodyEXTRA")
+                .unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.path, "/query");
+        assert_eq!(parsed.body, b"body");
+
+        assert_eq!(request("garbage\r\n\r\n").unwrap_err().status, 400);
+        assert_eq!(
+            request("POST /query HTTP/1.1\r\nContent-Length: x\r\n\r\n")
+                .unwrap_err()
+                .status,
+            400
+        );
+        assert_eq!(
+            request("POST /query HTTP/1.1\r\nContent-Length: 9\r\n\r\nshort")
+                .unwrap_err()
+                .status,
+            400
+        );
+        let too_big = format!(
+            "POST /query HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_SIZE + 1
+        );
+        assert_eq!(request(&too_big).unwrap_err().status, 413);
+    }
+
+    #[test]
+    fn test_route() {
+        let catalog = numbers_catalog();
+        let post = |path: &str, body: &str| {
+            route(
+                &Request {
+                    method: "POST".to_string(),
+                    path: path.to_string(),
+                    body: body.as_bytes().to_vec(),
+                },
+                &catalog,
+            )
+        };
+
+        assert_eq!(
+            post("/query?pretty", r#"{"sql": "SELECT n FROM numbers"}"#),
+            Response::ok(json!({ "columns": ["n"], "rows": [[1], [null]] }))
+        );
+
+        let error = post("/query", r#"{"sql": "SELECT missing FROM numbers"}"#);
+        assert_eq!(error.status, 400);
+        assert!(error.body["error"].as_str().unwrap().contains("missing"));
+        assert_eq!(post("/query", "not json").status, 400);
+        assert_eq!(post("/query", r#"{"query": "SELECT 1"}"#).status, 400);
+        assert_eq!(post("/other", "").status, 404);
+
+        let get = Request {
+            method: "GET".to_string(),
+            path: "/query".to_string(),
+            body: Vec::new(),
+        };
+        assert_eq!(route(&get, &catalog).status, 405);
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut out = Vec::new();
+        write_response(&mut out, &Response::error(404, "No route for /")).unwrap();
+        let body = r#"{"error":"No route for /"}"#;
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        );
+    }
+}
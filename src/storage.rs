@@ -0,0 +1,861 @@
+//! # Storage Module
+//!
+//! This module implements a simple binary on-disk format for tables. Loading a
+//! CSV means re-parsing text and re-running type inference every time; the
+//! binary format stores the already-typed columns so a table can be restored
+//! with a straight read.
+//!
+//! ## File Layout
+//!
+//! All integers are little-endian.
+//!
+//! ```text
+//! magic            8 bytes   b"MROLAP\0\0"
+//! version          u32       FORMAT_VERSION
+//! table name       string
+//! column count     u32
+//! row count        u64
+//! column headers   column count x (name: string, type tag: u8, display hint: string)
+//! column data      column count x (null count: u64, null bitmap, row count values)
+//! ```
+//!
+//! The display hint is the hint's text form (e.g. `money`), or an empty string
+//! for none. Version 1 files have no display hint in the column headers and
+//! are still readable.
+//!
+//! Each column's data starts with its NULL count. When that is non-zero it is
+//! followed by a bitmap of `ceil(row count / 8)` bytes with bit `i % 8` of
+//! byte `i / 8` set for NULL rows; columns without NULLs have no bitmap.
+//! NULL rows still store a placeholder value (zero or the empty string) so
+//! every row has the same layout. Files before version 3 have neither field
+//! and contain no NULLs.
+//!
+//! Strings are stored as a `u32` byte length followed by UTF-8 bytes. Column
+//! data is laid out column by column (this is a column store after all):
+//! - **Int64**: 8 bytes per value (`i64`)
+//! - **Float64**: 8 bytes per value (`f64` bit pattern, so NaN payloads survive)
+//! - **String**: one length-prefixed string per value
+//! - **Boolean**: 1 byte per value, `0` or `1`
+//! - **Date**: 4 bytes per value (`i32` days since 1970-01-01)
+//!
+//! ## Database Directories
+//!
+//! A whole catalog is saved as a directory holding one `<table>.olap` file per
+//! table plus a manifest listing the tables:
+//!
+//! ```text
+//! magic            8 bytes   b"MRCATLG\0"
+//! version          u32       CATALOG_FORMAT_VERSION
+//! table count      u32
+//! table names      table count x string
+//! ```
+//!
+//! Virtual tables are computed on the fly and are not saved.
+//!
+//! ## Error Handling
+//!
+//! A file with the wrong magic bytes, an unknown version, an unknown type tag,
+//! invalid UTF-8, or a truncated body is reported as an ingestion error rather
+//! than a panic.
+
+use crate::catalog::Catalog;
+use crate::column::create_column;
+use crate::error::{DatabaseError, Result};
+use crate::format::DisplayHint;
+use crate::table::Table;
+use crate::types::{DataType, Value};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes identifying a Mini Rust OLAP table file
+pub const MAGIC: &[u8; 8] = b"MROLAP\0\0";
+
+/// Version of the on-disk format written by this build
+pub const FORMAT_VERSION: u32 = 3;
+
+/// Oldest on-disk format version this build can still read
+const MIN_READ_VERSION: u32 = 1;
+
+/// Conventional file extension for table files
+pub const FILE_EXTENSION: &str = "olap";
+
+/// Magic bytes identifying a database directory manifest
+pub const CATALOG_MAGIC: &[u8; 8] = b"MRCATLG\0";
+
+/// Version of the database manifest written by this build
+pub const CATALOG_FORMAT_VERSION: u32 = 1;
+
+/// Name of the manifest file inside a database directory
+pub const MANIFEST_FILE: &str = "MANIFEST";
+
+// ============================================================================
+// TYPE TAGS
+// ============================================================================
+
+fn type_tag(data_type: DataType) -> u8 {
+    match data_type {
+        DataType::Int64 => 0,
+        DataType::Float64 => 1,
+        DataType::String => 2,
+        DataType::Boolean => 3,
+        DataType::Date => 4,
+    }
+}
+
+fn type_from_tag(tag: u8) -> Result<DataType> {
+    match tag {
+        0 => Ok(DataType::Int64),
+        1 => Ok(DataType::Float64),
+        2 => Ok(DataType::String),
+        3 => Ok(DataType::Boolean),
+        4 => Ok(DataType::Date),
+        other => Err(corrupt(format!("unknown column type tag {}", other))),
+    }
+}
+
+fn corrupt(msg: impl std::fmt::Display) -> DatabaseError {
+    DatabaseError::ingestion_error(format!("Corrupt table file: {}", msg))
+}
+
+// ============================================================================
+// WRITING
+// ============================================================================
+
+/// Writes a table to any byte sink in the binary table format.
+///
+/// # Arguments
+///
+/// * `table` - The table to serialize
+/// * `writer` - Destination for the encoded bytes
+pub fn write_table<W: Write>(table: &Table, writer: &mut W) -> Result<()> {
+    let column_names = table.column_names();
+    let row_count = table.row_count();
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+    write_string(writer, table.name())?;
+    writer.write_all(&(column_names.len() as u32).to_le_bytes())?;
+    writer.write_all(&(row_count as u64).to_le_bytes())?;
+
+    for name in &column_names {
+        write_string(writer, name)?;
+        writer.write_all(&[type_tag(table.get_column_type(name)?)])?;
+        let hint = table
+            .display_hint(name)
+            .map(|hint| hint.to_string())
+            .unwrap_or_default();
+        write_string(writer, &hint)?;
+    }
+
+    for name in &column_names {
+        let column = table.get_column(name)?;
+        let values = column.slice(None);
+
+        let null_count = values.iter().filter(|v| v.is_null()).count();
+        writer.write_all(&(null_count as u64).to_le_bytes())?;
+        if null_count > 0 {
+            let mut bitmap = vec![0u8; row_count.div_ceil(8)];
+            for (row, value) in values.iter().enumerate() {
+                if value.is_null() {
+                    bitmap[row / 8] |= 1 << (row % 8);
+                }
+            }
+            writer.write_all(&bitmap)?;
+        }
+
+        let data_type = column.data_type();
+        for value in values {
+            match (value, data_type) {
+                (Value::Int64(v), _) => writer.write_all(&v.to_le_bytes())?,
+                (Value::Float64(v), _) => writer.write_all(&v.to_bits().to_le_bytes())?,
+                (Value::String(s), _) => write_string(writer, &s)?,
+                (Value::Boolean(b), _) => writer.write_all(&[b as u8])?,
+                (Value::Date(days), _) => writer.write_all(&days.to_le_bytes())?,
+                (Value::Null, DataType::String) => write_string(writer, "")?,
+                (Value::Null, DataType::Boolean) => writer.write_all(&[0])?,
+                (Value::Null, DataType::Date) => writer.write_all(&0i32.to_le_bytes())?,
+                (Value::Null, _) => writer.write_all(&0u64.to_le_bytes())?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Saves a table to a file in the binary table format.
+///
+/// # Arguments
+///
+/// * `table` - The table to save
+/// * `path` - Destination file (created or truncated)
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::ingest::load_csv;
+/// use mini_rust_olap::storage::save_table;
+///
+/// let table = load_csv("data/users.csv", "users".to_string())?;
+/// save_table(&table, "data/users.olap")?;
+/// ```
+pub fn save_table<P: AsRef<Path>>(table: &Table, path: P) -> Result<()> {
+    let path = path.as_ref();
+    let file = File::create(path).map_err(|e| {
+        DatabaseError::ingestion_error(format!("Failed to create file '{}': {}", path.display(), e))
+    })?;
+
+    let mut writer = BufWriter::new(file);
+    write_table(table, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_string<W: Write>(writer: &mut W, s: &str) -> Result<()> {
+    let len = u32::try_from(s.len())
+        .map_err(|_| DatabaseError::ingestion_error("String too long to store (over 4 GiB)"))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(s.as_bytes())?;
+    Ok(())
+}
+
+// ============================================================================
+// READING
+// ============================================================================
+
+/// Reads a table from any byte source in the binary table format.
+///
+/// # Arguments
+///
+/// * `reader` - Source of the encoded bytes
+///
+/// # Errors
+///
+/// Returns an ingestion error if the data is not a table file, was written by
+/// an unsupported format version, or is truncated or otherwise corrupt.
+pub fn read_table<R: Read>(reader: &mut R) -> Result<Table> {
+    let mut magic = [0u8; 8];
+    read_exact(reader, &mut magic)?;
+    if &magic != MAGIC {
+        return Err(corrupt("not a Mini Rust OLAP table file (bad magic bytes)"));
+    }
+
+    let version = read_u32(reader)?;
+    if !(MIN_READ_VERSION..=FORMAT_VERSION).contains(&version) {
+        return Err(DatabaseError::ingestion_error(format!(
+            "Unsupported table file version {} (expected {} to {})",
+            version, MIN_READ_VERSION, FORMAT_VERSION
+        )));
+    }
+
+    let table_name = read_string(reader)?;
+    let column_count = read_u32(reader)? as usize;
+    let row_count = read_u64(reader)?;
+
+    // Read the headers before any data so an invalid type tag is caught early
+    let mut headers = Vec::new();
+    for _ in 0..column_count {
+        let name = read_string(reader)?;
+        let mut tag = [0u8; 1];
+        read_exact(reader, &mut tag)?;
+        let hint = if version >= 2 {
+            let spec = read_string(reader)?;
+            if spec.is_empty() {
+                None
+            } else {
+                Some(DisplayHint::parse(&spec).map_err(|_| {
+                    corrupt(format!(
+                        "invalid display hint '{}' on column '{}'",
+                        spec, name
+                    ))
+                })?)
+            }
+        } else {
+            None
+        };
+        headers.push((name, type_from_tag(tag[0])?, hint));
+    }
+
+    let mut table = Table::new(table_name);
+    for (name, data_type, hint) in headers {
+        let null_bitmap = if version >= 3 && read_u64(reader)? > 0 {
+            // Read through `take` so a corrupt row count can't force a huge
+            // allocation
+            let len = row_count.div_ceil(8);
+            let mut bitmap = Vec::new();
+            reader.by_ref().take(len).read_to_end(&mut bitmap)?;
+            if bitmap.len() as u64 != len {
+                return Err(corrupt("unexpected end of file"));
+            }
+            Some(bitmap)
+        } else {
+            None
+        };
+
+        let mut column = create_column(data_type);
+        for row in 0..row_count as usize {
+            let value = match data_type {
+                DataType::Int64 => Value::Int64(read_u64(reader)? as i64),
+                DataType::Float64 => Value::Float64(f64::from_bits(read_u64(reader)?)),
+                DataType::String => Value::String(read_string(reader)?),
+                DataType::Boolean => {
+                    let mut byte = [0u8; 1];
+                    read_exact(reader, &mut byte)?;
+                    match byte[0] {
+                        0 => Value::Boolean(false),
+                        1 => Value::Boolean(true),
+                        other => return Err(corrupt(format!("invalid boolean byte {}", other))),
+                    }
+                }
+                DataType::Date => Value::Date(read_u32(reader)? as i32),
+            };
+            match &null_bitmap {
+                Some(bitmap) if bitmap[row / 8] & (1 << (row % 8)) != 0 => column.push_null(),
+                _ => column.push_value(value)?,
+            }
+        }
+        table.add_column(name.clone(), column)?;
+        table
+            .set_display_hint(&name, hint)
+            .map_err(|e| corrupt(e.to_string()))?;
+    }
+
+    Ok(table)
+}
+
+/// Loads a table from a file written by [`save_table`].
+///
+/// # Arguments
+///
+/// * `path` - The table file to read
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::storage::load_table;
+///
+/// let table = load_table("data/users.olap")?;
+/// println!("Restored {} rows", table.row_count());
+/// ```
+pub fn load_table<P: AsRef<Path>>(path: P) -> Result<Table> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| {
+        DatabaseError::ingestion_error(format!("Failed to open file '{}': {}", path.display(), e))
+    })?;
+
+    let table = read_table(&mut BufReader::new(file)).map_err(|e| match e {
+        DatabaseError::IngestionError(msg) => {
+            DatabaseError::ingestion_error(format!("{} ('{}')", msg, path.display()))
+        }
+        other => other,
+    })?;
+
+    // Cheap structural check; `Table::verify` does the deep one on request
+    if let Some(issue) = table.verify_metadata().first() {
+        return Err(DatabaseError::ingestion_error(format!(
+            "Corrupt table file: {} ('{}')",
+            issue,
+            path.display()
+        )));
+    }
+
+    Ok(table)
+}
+
+/// Returns true if the path has the table file extension.
+pub fn is_table_file<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref()
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(FILE_EXTENSION))
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<()> {
+    reader.read_exact(buf).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            corrupt("unexpected end of file")
+        } else {
+            DatabaseError::IoError(e)
+        }
+    })
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    read_exact(reader, &mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    read_exact(reader, &mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_string<R: Read>(reader: &mut R) -> Result<String> {
+    let len = read_u32(reader)? as u64;
+
+    // Read through `take` so a corrupt length can't force a huge allocation
+    let mut bytes = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(corrupt("unexpected end of file"));
+    }
+
+    String::from_utf8(bytes).map_err(|_| corrupt("string is not valid UTF-8"))
+}
+
+// ============================================================================
+// DATABASE DIRECTORIES
+// ============================================================================
+
+/// Returns the file a table is stored in inside a database directory.
+///
+/// Table names come from user input, so anything that could escape the
+/// directory is rejected.
+fn table_file(dir: &Path, name: &str) -> Result<std::path::PathBuf> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\', '\0']) {
+        return Err(DatabaseError::ingestion_error(format!(
+            "Table name '{}' cannot be used as a file name",
+            name
+        )));
+    }
+    Ok(dir.join(format!("{}.{}", name, FILE_EXTENSION)))
+}
+
+/// Saves every regular table of a catalog into a database directory.
+///
+/// The directory is created if needed. Table files are written before the
+/// manifest, so an interrupted save leaves the previous manifest in charge.
+///
+/// # Arguments
+///
+/// * `catalog` - The catalog to save
+/// * `dir` - The database directory
+///
+/// # Example
+///
+/// ```ignore
+/// use mini_rust_olap::storage::save_catalog;
+///
+/// save_catalog(&catalog, "data/shop.db")?;
+/// ```
+pub fn save_catalog<P: AsRef<Path>>(catalog: &Catalog, dir: P) -> Result<()> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).map_err(|e| {
+        DatabaseError::ingestion_error(format!(
+            "Failed to create directory '{}': {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    let names = catalog.list_tables_sorted();
+    for name in &names {
+        let table = catalog.get_table(name)?;
+        save_table(&table, table_file(dir, name)?)?;
+    }
+
+    let mut manifest = Vec::new();
+    manifest.extend_from_slice(CATALOG_MAGIC);
+    manifest.extend_from_slice(&CATALOG_FORMAT_VERSION.to_le_bytes());
+    let count = u32::try_from(names.len())
+        .map_err(|_| DatabaseError::ingestion_error("Too many tables to store"))?;
+    manifest.extend_from_slice(&count.to_le_bytes());
+    for name in &names {
+        write_string(&mut manifest, name)?;
+    }
+    std::fs::write(dir.join(MANIFEST_FILE), manifest)?;
+    Ok(())
+}
+
+/// Loads a catalog from a database directory written by [`save_catalog`].
+///
+/// # Arguments
+///
+/// * `dir` - The database directory
+///
+/// # Errors
+///
+/// Returns an ingestion error if the manifest is missing, has the wrong
+/// magic bytes or version, or is corrupt, or if any listed table file is
+/// missing, corrupt, or holds a different table than its name says.
+pub fn load_catalog<P: AsRef<Path>>(dir: P) -> Result<Catalog> {
+    let dir = dir.as_ref();
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let manifest = std::fs::read(&manifest_path).map_err(|e| {
+        DatabaseError::ingestion_error(format!(
+            "Failed to open database '{}': {}",
+            dir.display(),
+            e
+        ))
+    })?;
+    let names = read_manifest(&mut manifest.as_slice()).map_err(|e| match e {
+        DatabaseError::IngestionError(msg) => {
+            DatabaseError::ingestion_error(format!("{} ('{}')", msg, manifest_path.display()))
+        }
+        other => other,
+    })?;
+
+    let mut catalog = Catalog::new();
+    for name in names {
+        let path = table_file(dir, &name)?;
+        let table = load_table(&path)?;
+        if table.name() != name {
+            return Err(corrupt(format!(
+                "file holds table '{}', expected '{}' ('{}')",
+                table.name(),
+                name,
+                path.display()
+            )));
+        }
+        catalog.register_table(table)?;
+    }
+    Ok(catalog)
+}
+
+/// Reads the table names from a database manifest.
+fn read_manifest<R: Read>(reader: &mut R) -> Result<Vec<String>> {
+    let mut magic = [0u8; 8];
+    read_exact(reader, &mut magic)?;
+    if &magic != CATALOG_MAGIC {
+        return Err(corrupt(
+            "not a Mini Rust OLAP database manifest (bad magic bytes)",
+        ));
+    }
+
+    let version = read_u32(reader)?;
+    if version != CATALOG_FORMAT_VERSION {
+        return Err(DatabaseError::ingestion_error(format!(
+            "Unsupported database manifest version {} (expected {})",
+            version, CATALOG_FORMAT_VERSION
+        )));
+    }
+
+    let count = read_u32(reader)?;
+    let mut names = Vec::new();
+    for _ in 0..count {
+        names.push(read_string(reader)?);
+    }
+
+    let mut rest = [0u8; 1];
+    if reader.read(&mut rest)? != 0 {
+        return Err(corrupt("trailing bytes after the table list"));
+    }
+    Ok(names)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::{BoolColumn, Column, DateColumn, FloatColumn, IntColumn, StringColumn};
+
+    fn create_test_table() -> Table {
+        let mut table = Table::new("mixed".to_string());
+
+        let mut ids = IntColumn::new();
+        let mut scores = FloatColumn::new();
+        let mut names = StringColumn::new();
+        for (id, score, name) in [(1, 9.5, "Alice"), (-2, f64::NAN, ""), (3, -0.0, "Ünïcode")] {
+            ids.push_value(Value::Int64(id)).unwrap();
+            scores.push_value(Value::Float64(score)).unwrap();
+            names.push_value(Value::String(name.to_string())).unwrap();
+        }
+
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table
+            .add_column("score".to_string(), Box::new(scores))
+            .unwrap();
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+        table
+    }
+
+    fn encode(table: &Table) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_table(table, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_round_trip_preserves_schema_and_values() {
+        let table = create_test_table();
+        let restored = read_table(&mut encode(&table).as_slice()).unwrap();
+
+        assert_eq!(restored.name(), "mixed");
+        assert_eq!(restored.column_names(), table.column_names());
+        assert_eq!(restored.row_count(), 3);
+        assert_eq!(restored.get_value("id", 1).unwrap(), Value::Int64(-2));
+        assert_eq!(
+            restored.get_value("name", 2).unwrap(),
+            Value::String("Ünïcode".to_string())
+        );
+
+        // Floats are stored as raw bits, so NaN and -0.0 survive exactly
+        match restored.get_value("score", 1).unwrap() {
+            Value::Float64(f) => assert!(f.is_nan()),
+            other => panic!("Expected Float64, got {:?}", other),
+        }
+        match restored.get_value("score", 2).unwrap() {
+            Value::Float64(f) => assert!(f == 0.0 && f.is_sign_negative()),
+            other => panic!("Expected Float64, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_empty_table() {
+        let mut table = Table::new("empty".to_string());
+        table
+            .add_column("x".to_string(), create_column(DataType::Float64))
+            .unwrap();
+
+        let restored = read_table(&mut encode(&table).as_slice()).unwrap();
+        assert_eq!(restored.row_count(), 0);
+        assert_eq!(restored.get_column_type("x").unwrap(), DataType::Float64);
+    }
+
+    #[test]
+    fn test_round_trip_preserves_display_hints() {
+        let mut table = create_test_table();
+        table
+            .set_display_hint("score", Some(DisplayHint::parse("percent(100)").unwrap()))
+            .unwrap();
+
+        let restored = read_table(&mut encode(&table).as_slice()).unwrap();
+        assert_eq!(
+            restored.display_hint("score").map(|hint| hint.to_string()),
+            Some("percent(100)".to_string())
+        );
+        assert!(restored.display_hint("id").is_none());
+    }
+
+    #[test]
+    fn test_version_1_files_are_readable() {
+        // Hand-built version 1 file: one Int64 column, no display hints
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        write_string(&mut bytes, "old").unwrap();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&2u64.to_le_bytes());
+        write_string(&mut bytes, "n").unwrap();
+        bytes.push(type_tag(DataType::Int64));
+        bytes.extend_from_slice(&7i64.to_le_bytes());
+        bytes.extend_from_slice(&8i64.to_le_bytes());
+
+        let table = read_table(&mut bytes.as_slice()).unwrap();
+        assert_eq!(table.name(), "old");
+        assert_eq!(table.get_value("n", 1).unwrap(), Value::Int64(8));
+        assert!(table.display_hint("n").is_none());
+    }
+
+    #[test]
+    fn test_round_trip_preserves_nulls() {
+        let mut table = Table::new("sparse".to_string());
+        let mut ids = IntColumn::new();
+        let mut names = StringColumn::new();
+        for row in 0..10 {
+            if row % 3 == 0 {
+                ids.push_null();
+                names
+                    .push_value(Value::String(format!("n{}", row)))
+                    .unwrap();
+            } else {
+                ids.push_value(Value::Int64(row)).unwrap();
+                names.push_null();
+            }
+        }
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+
+        let restored = read_table(&mut encode(&table).as_slice()).unwrap();
+        for row in 0..10 {
+            for column in ["id", "name"] {
+                assert_eq!(
+                    restored.get_value(column, row).unwrap(),
+                    table.get_value(column, row).unwrap()
+                );
+            }
+        }
+        assert_eq!(restored.get_column("id").unwrap().null_count(), 4);
+        assert_eq!(
+            restored.get_value("name", 0).unwrap(),
+            Value::String("n0".into())
+        );
+    }
+
+    #[test]
+    fn test_round_trip_date_column() {
+        let mut table = Table::new("orders".to_string());
+        let mut dates = DateColumn::new();
+        dates.push_value(Value::Date(19_723)).unwrap();
+        dates.push_null();
+        dates.push_value(Value::Date(-36_524)).unwrap();
+        table
+            .add_column("order_date".to_string(), Box::new(dates))
+            .unwrap();
+
+        let restored = read_table(&mut encode(&table).as_slice()).unwrap();
+        assert_eq!(
+            restored.get_column_type("order_date").unwrap(),
+            DataType::Date
+        );
+        for row in 0..3 {
+            assert_eq!(
+                restored.get_value("order_date", row).unwrap(),
+                table.get_value("order_date", row).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip_boolean_column() {
+        let mut table = Table::new("flags".to_string());
+        let mut flags = BoolColumn::new();
+        for row in 0..70 {
+            if row % 10 == 0 {
+                flags.push_null();
+            } else {
+                flags.push_value(Value::Boolean(row % 2 == 0)).unwrap();
+            }
+        }
+        table
+            .add_column("active".to_string(), Box::new(flags))
+            .unwrap();
+
+        let restored = read_table(&mut encode(&table).as_slice()).unwrap();
+        assert_eq!(
+            restored.get_column_type("active").unwrap(),
+            DataType::Boolean
+        );
+        for row in 0..70 {
+            assert_eq!(
+                restored.get_value("active", row).unwrap(),
+                table.get_value("active", row).unwrap()
+            );
+        }
+        assert_eq!(restored.get_column("active").unwrap().null_count(), 7);
+    }
+
+    #[test]
+    fn test_bad_magic_is_rejected() {
+        let err = read_table(&mut &b"NOTATABLEFILE"[..]).err().unwrap();
+        assert!(err.to_string().contains("bad magic"));
+    }
+
+    #[test]
+    fn test_unsupported_version_is_rejected() {
+        let mut bytes = encode(&create_test_table());
+        bytes[8..12].copy_from_slice(&99u32.to_le_bytes());
+
+        let err = read_table(&mut bytes.as_slice()).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("Unsupported table file version 99"));
+    }
+
+    #[test]
+    fn test_truncated_file_is_rejected() {
+        let bytes = encode(&create_test_table());
+
+        // Every proper prefix must fail cleanly rather than panic
+        for len in 0..bytes.len() {
+            assert!(read_table(&mut &bytes[..len]).is_err(), "prefix {}", len);
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mixed.olap");
+
+        save_table(&create_test_table(), &path).unwrap();
+        let restored = load_table(&path).unwrap();
+        assert_eq!(restored.row_count(), 3);
+        assert!(is_table_file(&path));
+        assert!(!is_table_file("data.csv"));
+    }
+
+    fn create_test_catalog() -> Catalog {
+        let mut other = create_test_table();
+        other.set_name("other".to_string());
+
+        let mut catalog = Catalog::new();
+        catalog.register_table(create_test_table()).unwrap();
+        catalog.register_table(other).unwrap();
+        catalog
+    }
+
+    #[test]
+    fn test_catalog_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        save_catalog(&create_test_catalog(), dir.path()).unwrap();
+
+        let restored = load_catalog(dir.path()).unwrap();
+        assert_eq!(restored.list_tables_sorted(), vec!["mixed", "other"]);
+        assert_eq!(restored.get_table("other").unwrap().name(), "other");
+
+        // Re-encoding gives the same bytes, NaN payloads and -0.0 included
+        let table = restored.get_table("mixed").unwrap();
+        assert_eq!(encode(&table), encode(&create_test_table()));
+    }
+
+    #[test]
+    fn test_catalog_rejects_bad_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_catalog(dir.path())
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Failed to open database"));
+
+        save_catalog(&create_test_catalog(), dir.path()).unwrap();
+        let manifest_path = dir.path().join(MANIFEST_FILE);
+        let manifest = std::fs::read(&manifest_path).unwrap();
+
+        // Every proper prefix must fail cleanly rather than panic
+        for len in 0..manifest.len() {
+            std::fs::write(&manifest_path, &manifest[..len]).unwrap();
+            assert!(load_catalog(dir.path()).is_err(), "prefix {}", len);
+        }
+
+        let mut bad_version = manifest.clone();
+        bad_version[8..12].copy_from_slice(&7u32.to_le_bytes());
+        std::fs::write(&manifest_path, bad_version).unwrap();
+        assert!(load_catalog(dir.path())
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Unsupported database manifest version 7"));
+    }
+
+    #[test]
+    fn test_catalog_rejects_missing_or_mismatched_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        save_catalog(&create_test_catalog(), dir.path()).unwrap();
+
+        // A table file holding a different table
+        std::fs::copy(dir.path().join("mixed.olap"), dir.path().join("other.olap")).unwrap();
+        let err = load_catalog(dir.path()).err().unwrap();
+        assert!(err.to_string().contains("expected 'other'"), "{}", err);
+
+        std::fs::remove_file(dir.path().join("other.olap")).unwrap();
+        assert!(load_catalog(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_table_names_cannot_escape_the_directory() {
+        let mut catalog = Catalog::new();
+        catalog
+            .register_table(Table::new("../evil".to_string()))
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(save_catalog(&catalog, dir.path()).is_err());
+    }
+}
@@ -5,10 +5,14 @@
 //! columnar format, storing each column independently to enable efficient
 //! analytical queries.
 
-use crate::column::{create_column, Column};
+use crate::column::{compress_column, Column, ColumnIssue, Encoding, ZoneMap};
 use crate::error::{DatabaseError, Result};
-use crate::types::{DataType, Value};
-use std::collections::HashMap;
+use crate::format::DisplayHint;
+use crate::types::{canonical_float_bits, parse_boolean, parse_date, DataType, Value};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 
 /// Represents a table in the database with a name, schema, and columnar data.
 ///
@@ -17,6 +21,21 @@ use std::collections::HashMap;
 /// This organization enables efficient analytical queries that only need to scan
 /// a subset of columns.
 ///
+/// Columns are reference-counted: cloning a table or scanning it shares the
+/// column data, and a write to a shared column copies it first, so clones
+/// and running scans never see each other's changes.
+///
+/// The columns live in an immutable [`ColumnSet`] behind an `Arc`. A write
+/// to a table whose set is shared (with a clone, or a scan that took a
+/// [`Table::snapshot`]) first replaces the set with a copy, which shares
+/// every column but the ones being written, so a snapshot keeps the same
+/// columns and row count however the table changes afterwards.
+///
+/// Int64, Float64, and Date columns carry a [`ZoneMap`], built when the
+/// column is added and kept up to date as rows are appended. Writing through
+/// [`Table::get_column_mut`] drops the column's zone map, since the change
+/// may be anywhere; [`Table::build_zone_maps`] restores it.
+///
 /// # Example
 ///
 /// ```ignore
@@ -41,34 +60,70 @@ pub struct Table {
     /// Mapping from column name to column index for fast lookup
     column_index: HashMap<String, usize>,
 
-    /// The actual column data, stored as boxed Column trait objects
-    columns: Vec<Box<dyn Column>>,
+    /// The column data and zone maps, shared with clones and scans of the
+    /// table and copied before a write while shared
+    data: Arc<ColumnSet>,
 
     /// The schema mapping column names to their data types
     schema: HashMap<String, DataType>,
+
+    /// Display hints (money, percent, ...) for columns that have one
+    display_hints: HashMap<String, DisplayHint>,
 }
 
 impl Clone for Table {
+    /// Clones the table's metadata and shares its column data; see
+    /// [`Table::get_column_mut`] for what happens on a later write.
     fn clone(&self) -> Self {
-        let mut new_table = Table::new(self.name.clone());
+        Table {
+            name: self.name.clone(),
+            column_index: self.column_index.clone(),
+            data: Arc::clone(&self.data),
+            schema: self.schema.clone(),
+            display_hints: self.display_hints.clone(),
+        }
+    }
+}
 
-        // Iterate over column names in insertion order, not schema (HashMap order is non-deterministic)
-        for name in self.column_names() {
-            let data_type = self.schema.get(&name).unwrap();
-            let index = self.column_index.get(&name).unwrap();
-            let original_column = &self.columns[*index];
-            let cloned_column = original_column.slice(Some(0..original_column.len()));
-
-            let mut column = create_column(*data_type);
-            for i in 0..cloned_column.len() {
-                let value = cloned_column.get(i).unwrap();
-                let _ = column.as_mut().push_value(value.clone());
-            }
+/// The columns of a table at one point in time.
+///
+/// A table never changes a set that anything else holds: it copies the set
+/// before writing, so every column of a snapshot keeps the row count it had
+/// when the snapshot was taken. Copying a set shares its column data.
+#[derive(Clone, Default)]
+pub struct ColumnSet {
+    /// The column data, in table order
+    columns: Vec<Arc<dyn Column>>,
+
+    /// Zone map of each column, by column index; None for column types
+    /// without zone maps and for columns changed in place
+    zone_maps: Vec<Option<Arc<ZoneMap>>>,
+}
 
-            new_table.add_column(name.clone(), column).unwrap();
-        }
+impl ColumnSet {
+    /// Returns the number of rows, which every column has.
+    pub fn row_count(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.len())
+    }
+
+    /// Returns the number of columns.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns whether the set has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns the column at `index`, in table order.
+    pub fn column(&self, index: usize) -> Option<&Arc<dyn Column>> {
+        self.columns.get(index)
+    }
 
-        new_table
+    /// Returns the zone map of the column at `index`, if it has one.
+    pub fn zone_map(&self, index: usize) -> Option<&ZoneMap> {
+        self.zone_maps.get(index)?.as_deref()
     }
 }
 
@@ -86,8 +141,9 @@ impl Table {
         Self {
             name,
             column_index: HashMap::new(),
-            columns: Vec::new(),
+            data: Arc::new(ColumnSet::default()),
             schema: HashMap::new(),
+            display_hints: HashMap::new(),
         }
     }
 
@@ -105,6 +161,35 @@ impl Table {
     ///
     /// Returns an error if the column name already exists or if row counts don't match
     pub fn add_column(&mut self, name: String, column: Box<dyn Column>) -> Result<()> {
+        self.add_shared_column(name, Arc::from(column))
+    }
+
+    /// Adds a column whose data may be shared with other tables or batches.
+    ///
+    /// Works like [`Table::add_column`] without copying the data; the table
+    /// copies the column the first time it writes to it while it is shared.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column
+    /// * `column` - The column to add
+    ///
+    /// # Returns
+    ///
+    /// Returns an error if the column name already exists or if row counts don't match
+    pub fn add_shared_column(&mut self, name: String, column: Arc<dyn Column>) -> Result<()> {
+        let zone_map = ZoneMap::supports(column.data_type())
+            .then(|| Arc::new(ZoneMap::build(column.as_ref())));
+        self.insert_column(name, column, zone_map)
+    }
+
+    /// Adds a column with an already computed zone map.
+    fn insert_column(
+        &mut self,
+        name: String,
+        column: Arc<dyn Column>,
+        zone_map: Option<Arc<ZoneMap>>,
+    ) -> Result<()> {
         // Check for duplicate column names
         if self.schema.contains_key(&name) {
             return Err(DatabaseError::column_error(format!(
@@ -114,8 +199,8 @@ impl Table {
         }
 
         // Check that the new column has the same number of rows as existing columns
-        if !self.columns.is_empty() {
-            let existing_row_count = self.columns[0].len();
+        if !self.data.columns.is_empty() {
+            let existing_row_count = self.data.columns[0].len();
             let new_row_count = column.len();
 
             if existing_row_count != new_row_count {
@@ -130,11 +215,13 @@ impl Table {
         let data_type = column.data_type();
 
         // Track the column index for fast lookup
-        let index = self.columns.len();
+        let index = self.data.columns.len();
         self.column_index.insert(name.clone(), index);
 
         // Store the column and update the schema
-        self.columns.push(column);
+        let data = self.data_mut();
+        data.columns.push(column);
+        data.zone_maps.push(zone_map);
         self.schema.insert(name, data_type);
 
         Ok(())
@@ -157,11 +244,39 @@ impl Table {
             ))
         })?;
 
-        Ok(self.columns[*index].as_ref())
+        Ok(self.data.columns[*index].as_ref())
+    }
+
+    /// Gets a shared handle to a column by name, without copying its data.
+    ///
+    /// Scans use this to hand out slices of the table's columns (see
+    /// `ColumnSlice`). While a handle is alive, the next write to the
+    /// column through the table copies it first.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column to retrieve
+    ///
+    /// # Returns
+    ///
+    /// The shared column, or an error if not found
+    pub fn shared_column(&self, name: &str) -> Result<Arc<dyn Column>> {
+        let index = self.column_index.get(name).ok_or_else(|| {
+            DatabaseError::column_error(format!(
+                "Column '{}' not found in table '{}'",
+                name, self.name
+            ))
+        })?;
+
+        Ok(Arc::clone(&self.data.columns[*index]))
     }
 
     /// Gets a mutable reference to a column by name.
     ///
+    /// If the column's data is shared with a clone of the table or a batch
+    /// that is still alive, it is copied first, so the write is only seen
+    /// through this table.
+    ///
     /// # Arguments
     ///
     /// * `name` - The name of the column to retrieve
@@ -177,7 +292,51 @@ impl Table {
             ))
         })?;
 
-        Ok(self.columns[index].as_mut())
+        // The caller may change any row, so the zone map can no longer be trusted
+        self.data_mut().zone_maps[index] = None;
+        Ok(self.column_mut_at(index))
+    }
+
+    /// Returns the column at `index` for writing, copying it first if its
+    /// data is shared.
+    fn column_mut_at(&mut self, index: usize) -> &mut dyn Column {
+        let column = &mut self.data_mut().columns[index];
+        if Arc::get_mut(column).is_none() {
+            // Keeps the representation, e.g. a dictionary-encoded column
+            *column = Arc::from(column.clone_column());
+        }
+        Arc::get_mut(column).expect("column is unshared after copying")
+    }
+
+    /// Returns the column set for writing, first replacing it with a copy
+    /// if a snapshot or clone of the table still holds it.
+    fn data_mut(&mut self) -> &mut ColumnSet {
+        Arc::make_mut(&mut self.data)
+    }
+
+    /// Returns the table's columns as they are now.
+    ///
+    /// The snapshot is unaffected by later writes to the table, so a reader
+    /// holding it sees the same row count in every column. Taking one does
+    /// not copy any data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_rust_olap::column::IntColumn;
+    /// # use mini_rust_olap::table::Table;
+    /// # use mini_rust_olap::types::Value;
+    /// let mut table = Table::new("numbers".to_string());
+    /// table.add_column("n".to_string(), Box::new(IntColumn::new())).unwrap();
+    /// table.insert_rows(vec![vec![Value::Int64(1)]]).unwrap();
+    ///
+    /// let snapshot = table.snapshot();
+    /// table.insert_rows(vec![vec![Value::Int64(2)]]).unwrap();
+    /// assert_eq!(snapshot.row_count(), 1);
+    /// assert_eq!(table.row_count(), 2);
+    /// ```
+    pub fn snapshot(&self) -> Arc<ColumnSet> {
+        Arc::clone(&self.data)
     }
 
     /// Gets the value at a specific row and column.
@@ -195,20 +354,43 @@ impl Table {
         column.get(row_index)
     }
 
+    /// Iterates over the table's rows, each as its values in column order.
+    ///
+    /// This is a convenience for small tables and tests; reading a column
+    /// through its typed accessors (such as `IntColumn::values`) avoids
+    /// building a `Value` for every cell.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for row in table.iter_rows() {
+    ///     println!("{:?}", row);
+    /// }
+    /// ```
+    pub fn iter_rows(&self) -> impl Iterator<Item = Vec<Value>> + '_ {
+        (0..self.row_count()).map(move |row| {
+            self.data
+                .columns
+                .iter()
+                .map(|column| column.get(row).expect("every column has row_count rows"))
+                .collect()
+        })
+    }
+
     /// Returns the number of rows in the table.
     ///
     /// If the table has no columns, returns 0.
     pub fn row_count(&self) -> usize {
-        if self.columns.is_empty() {
+        if self.data.columns.is_empty() {
             0
         } else {
-            self.columns[0].len()
+            self.data.columns[0].len()
         }
     }
 
     /// Returns the number of columns in the table.
     pub fn column_count(&self) -> usize {
-        self.columns.len()
+        self.data.columns.len()
     }
 
     /// Returns the name of the table.
@@ -216,6 +398,11 @@ impl Table {
         &self.name
     }
 
+    /// Changes the name of the table.
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
     /// Returns a slice of all column names in insertion order.
     pub fn column_names(&self) -> Vec<String> {
         let mut name_index_pairs: Vec<(String, usize)> = self
@@ -223,7 +410,7 @@ impl Table {
             .iter()
             .map(|(name, &index)| (name.clone(), index))
             .collect();
-        name_index_pairs.sort_by(|a, b| a.1.cmp(&b.1));
+        name_index_pairs.sort_by_key(|a| a.1);
         name_index_pairs.into_iter().map(|(name, _)| name).collect()
     }
 
@@ -250,7 +437,7 @@ impl Table {
         }
 
         for (index, value) in values.iter().enumerate() {
-            let column = self.columns[index].as_mut();
+            let column = self.column_mut_at(index);
 
             // Parse the string value according to column type
             let parsed_value = match column.data_type() {
@@ -261,14 +448,255 @@ impl Table {
                     DatabaseError::column_error(format!("Invalid float value: '{}'", value))
                 })?,
                 DataType::String => Value::String(value.clone()),
+                DataType::Boolean => parse_boolean(value).map(Value::Boolean).ok_or_else(|| {
+                    DatabaseError::column_error(format!("Invalid boolean value: '{}'", value))
+                })?,
+                DataType::Date => parse_date(value).map(Value::Date).ok_or_else(|| {
+                    DatabaseError::column_error(format!("Invalid date value: '{}'", value))
+                })?,
             };
 
             let _ = column.push_value(parsed_value);
         }
 
+        self.extend_zone_maps();
         Ok(())
     }
 
+    /// Appends rows of typed values, all or nothing.
+    ///
+    /// Each row must have one value per column, in column order. A value
+    /// must match its column's type or be NULL; the coercions applied are
+    /// Int64 into a Float64 column and an ISO `YYYY-MM-DD` string into a
+    /// Date column. Every row is checked before any column
+    /// is touched, so a rejected insert leaves the table unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The rows to append
+    ///
+    /// # Returns
+    ///
+    /// The number of rows appended, or an error naming the first offending
+    /// row and column
+    pub fn insert_rows(&mut self, rows: Vec<Vec<Value>>) -> Result<usize> {
+        let names = self.column_names();
+        let mut coerced_rows = Vec::with_capacity(rows.len());
+
+        for (row_number, row) in rows.into_iter().enumerate() {
+            if row.len() != self.column_count() {
+                return Err(DatabaseError::table_error(format!(
+                    "Row {} has {} values but table '{}' has {} columns",
+                    row_number + 1,
+                    row.len(),
+                    self.name,
+                    self.column_count()
+                )));
+            }
+
+            let mut coerced = Vec::with_capacity(row.len());
+            for ((value, column), name) in row.into_iter().zip(&self.data.columns).zip(&names) {
+                let data_type = column.data_type();
+                coerced.push(match (value, data_type) {
+                    (Value::Int64(v), DataType::Float64) => Value::Float64(v as f64),
+                    (Value::String(v), DataType::Date) => match parse_date(&v) {
+                        Some(days) => Value::Date(days),
+                        None => {
+                            return Err(DatabaseError::type_error(format!(
+                            "Row {}: cannot insert '{}' into Date column '{}': expected YYYY-MM-DD",
+                            row_number + 1,
+                            v,
+                            name
+                        )))
+                        }
+                    },
+                    (value, _) if value.is_null() || value.data_type() == Some(data_type) => value,
+                    (value, _) => {
+                        return Err(DatabaseError::type_error(format!(
+                            "Row {}: cannot insert {} value {} into {} column '{}'",
+                            row_number + 1,
+                            value.type_name(),
+                            value,
+                            data_type,
+                            name
+                        )))
+                    }
+                });
+            }
+            coerced_rows.push(coerced);
+        }
+
+        let inserted = coerced_rows.len();
+        for row in coerced_rows {
+            for (index, value) in row.into_iter().enumerate() {
+                self.column_mut_at(index).push_value(value)?;
+            }
+        }
+        self.extend_zone_maps();
+        Ok(inserted)
+    }
+
+    /// Appends every row of another table, all or nothing.
+    ///
+    /// Columns are matched by name, and `other` must have exactly this
+    /// table's columns. Each must have the same type, be Int64 for a Float64
+    /// column, or hold only NULLs. Columns of the same type are appended with
+    /// [`Column::append_column`], which shares the chunks of integer, float
+    /// and string columns instead of copying them.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The table whose rows to append
+    ///
+    /// # Returns
+    ///
+    /// The number of rows appended, or an error naming the first column that
+    /// does not fit
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_rust_olap::column::IntColumn;
+    /// # use mini_rust_olap::table::Table;
+    /// # use mini_rust_olap::types::Value;
+    /// let mut table = Table::new("numbers".to_string());
+    /// table.add_column("n".to_string(), Box::new(IntColumn::new())).unwrap();
+    /// table.insert_rows(vec![vec![Value::Int64(1)]]).unwrap();
+    ///
+    /// let more = table.clone();
+    /// assert_eq!(table.append_table(&more).unwrap(), 1);
+    /// assert_eq!(table.row_count(), 2);
+    /// ```
+    pub fn append_table(&mut self, other: &Table) -> Result<usize> {
+        let names = self.column_names();
+        let mut sources = Vec::with_capacity(names.len());
+        for name in &names {
+            let expected = self.get_column_type(name)?;
+            let source = other.shared_column(name).map_err(|_| {
+                DatabaseError::table_error(format!(
+                    "Cannot append table '{}' to '{}': column '{}' is missing",
+                    other.name, self.name, name
+                ))
+            })?;
+            let found = source.data_type();
+            let compatible = found == expected
+                || (found == DataType::Int64 && expected == DataType::Float64)
+                || source.null_count() == source.len();
+            if !compatible {
+                return Err(DatabaseError::type_error(format!(
+                    "Cannot append {} column '{}' to {} column '{}' of table '{}'",
+                    found, name, expected, name, self.name
+                )));
+            }
+            sources.push(source);
+        }
+        if let Some(extra) = other
+            .column_names()
+            .into_iter()
+            .find(|name| !self.has_column(name))
+        {
+            return Err(DatabaseError::table_error(format!(
+                "Cannot append table '{}' to '{}': column '{}' is not in '{}'",
+                other.name, self.name, extra, self.name
+            )));
+        }
+
+        for (index, source) in sources.iter().enumerate() {
+            let column = self.column_mut_at(index);
+            if source.data_type() == column.data_type() {
+                column.append_column(source.as_ref())?;
+                continue;
+            }
+            for value in source.iter_range(0..source.len()) {
+                column.push_value(match value {
+                    Value::Int64(v) => Value::Float64(v as f64),
+                    _ => Value::Null,
+                })?;
+            }
+        }
+        self.extend_zone_maps();
+        Ok(other.row_count())
+    }
+
+    /// Folds appended rows into the zone maps.
+    fn extend_zone_maps(&mut self) {
+        let data = self.data_mut();
+        for (zone_map, column) in data.zone_maps.iter_mut().zip(&data.columns) {
+            if let Some(zone_map) = zone_map {
+                Arc::make_mut(zone_map).extend(column.as_ref());
+            }
+        }
+    }
+
+    /// Returns the zone map of a column.
+    ///
+    /// # Returns
+    ///
+    /// The zone map, or None if the column does not exist, its type has no
+    /// zone maps, or it was changed through [`Table::get_column_mut`] since
+    /// zone maps were last built
+    pub fn zone_map(&self, name: &str) -> Option<&ZoneMap> {
+        let index = *self.column_index.get(name)?;
+        self.data.zone_maps[index].as_deref()
+    }
+
+    /// Rebuilds the zone map of every Int64, Float64, and Date column.
+    pub fn build_zone_maps(&mut self) {
+        let data = self.data_mut();
+        for (zone_map, column) in data.zone_maps.iter_mut().zip(&data.columns) {
+            *zone_map = ZoneMap::supports(column.data_type())
+                .then(|| Arc::new(ZoneMap::build(column.as_ref())));
+        }
+    }
+
+    /// Re-encodes every column that a compressed representation shrinks by
+    /// enough, as chosen by [`compress_column`].
+    ///
+    /// Values, NULLs and zone maps are unchanged, so queries return the same
+    /// results; reads decode on the fly. Snapshots taken earlier keep the
+    /// uncompressed columns. Rows appended later are encoded as they come.
+    ///
+    /// # Returns
+    ///
+    /// One entry per column that was re-encoded, in table order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use mini_rust_olap::column::{Encoding, IntColumn};
+    /// # use mini_rust_olap::table::Table;
+    /// # use mini_rust_olap::types::Value;
+    /// let mut table = Table::new("events".to_string());
+    /// table.add_column("id".to_string(), Box::new(IntColumn::new())).unwrap();
+    /// table
+    ///     .insert_rows((0..10_000).map(|id| vec![Value::Int64(id)]).collect())
+    ///     .unwrap();
+    ///
+    /// let changes = table.compress().unwrap();
+    /// assert_eq!(changes[0].encoding, Encoding::Delta);
+    /// assert!(changes[0].bytes_after < changes[0].bytes_before);
+    /// assert_eq!(table.get_value("id", 9_999).unwrap(), Value::Int64(9_999));
+    /// ```
+    pub fn compress(&mut self) -> Result<Vec<ColumnCompression>> {
+        let mut changes = Vec::new();
+        for name in self.column_names() {
+            let index = self.column_index[&name];
+            let column = &self.data.columns[index];
+            let Some((encoding, compressed)) = compress_column(column.as_ref())? else {
+                continue;
+            };
+
+            changes.push(ColumnCompression {
+                column: name,
+                encoding,
+                bytes_before: column.memory_size(),
+                bytes_after: compressed.memory_size(),
+            });
+            self.data_mut().columns[index] = Arc::from(compressed);
+        }
+        Ok(changes)
+    }
+
     /// Returns a reference to the schema (column name to data type mapping).
     pub fn schema(&self) -> &HashMap<String, DataType> {
         &self.schema
@@ -305,6 +733,44 @@ impl Table {
         })
     }
 
+    /// Sets or clears the display hint of a numeric column.
+    ///
+    /// Hints only affect how values are rendered; the stored data is unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The column name
+    /// * `hint` - The hint to set, or `None` to go back to raw output
+    ///
+    /// # Returns
+    ///
+    /// An error if the column doesn't exist or is not numeric
+    pub fn set_display_hint(&mut self, name: &str, hint: Option<DisplayHint>) -> Result<()> {
+        let data_type = self.get_column_type(name)?;
+
+        match hint {
+            Some(hint) => {
+                if !data_type.is_numeric() {
+                    return Err(DatabaseError::column_error(format!(
+                        "Cannot set format '{}' on {} column '{}': display formats apply to numeric columns",
+                        hint, data_type, name
+                    )));
+                }
+                self.display_hints.insert(name.to_string(), hint);
+            }
+            None => {
+                self.display_hints.remove(name);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the display hint of a column, if one is set.
+    pub fn display_hint(&self, name: &str) -> Option<&DisplayHint> {
+        self.display_hints.get(name)
+    }
+
     /// Creates a new table containing only the specified columns.
     ///
     /// # Arguments
@@ -325,23 +791,15 @@ impl Table {
                 ))
             })?;
 
-            // Clone the column
-            let original_column = &self.columns[*index];
-            let cloned_column = original_column.slice(Some(0..original_column.len()));
-
-            // Get the data type
-            let data_type = self.schema.get(name).unwrap();
-
-            // Add to the new table
-            let mut column = create_column(*data_type);
-            for i in 0..cloned_column.len() {
-                let value = cloned_column.get(i).ok_or_else(|| {
-                    DatabaseError::column_error(format!("Failed to get value at index {}", i))
-                })?;
-                let _ = column.as_mut().push_value(value.clone());
+            // Share the column; either table copies it when writing to it
+            new_table.insert_column(
+                name.clone(),
+                Arc::clone(&self.data.columns[*index]),
+                self.data.zone_maps[*index].clone(),
+            )?;
+            if let Some(hint) = self.display_hints.get(name) {
+                new_table.display_hints.insert(name.clone(), hint.clone());
             }
-
-            new_table.add_column(name.clone(), column)?;
         }
 
         Ok(new_table)
@@ -356,12 +814,15 @@ impl Table {
     ///
     /// true if all columns have the same length, false otherwise
     pub fn validate_schema(&self) -> bool {
-        if self.columns.is_empty() {
+        if self.data.columns.is_empty() {
             return true;
         }
 
-        let first_length = self.columns[0].len();
-        self.columns.iter().all(|col| col.len() == first_length)
+        let first_length = self.data.columns[0].len();
+        self.data
+            .columns
+            .iter()
+            .all(|col| col.len() == first_length)
     }
 
     /// Drops a column from the table.
@@ -381,28 +842,495 @@ impl Table {
             ))
         })?;
 
-        self.columns.remove(index);
-        self.schema.remove(name);
+        let data = self.data_mut();
+        data.columns.remove(index);
+        data.zone_maps.remove(index);
+        self.schema.remove(name);
+        self.display_hints.remove(name);
+
+        // Shift the columns that came after the removed one down by one
+        for column_index in self.column_index.values_mut() {
+            if *column_index > index {
+                *column_index -= 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // STATISTICS
+    // ========================================================================
+
+    /// Computes summary statistics for every column in one pass over the data.
+    ///
+    /// Statistics are not cached; call this again after the table changes.
+    /// Distinct counts are exact up to [`DISTINCT_COUNT_CAP`] values, after
+    /// which counting stops and the count is reported as a lower bound.
+    ///
+    /// # Returns
+    ///
+    /// The table's statistics, with columns in table order
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let stats = table.compute_stats();
+    /// let age = stats.column("age").unwrap();
+    /// println!("age ranges from {:?} to {:?}", age.min, age.max);
+    /// ```
+    pub fn compute_stats(&self) -> TableStats {
+        let columns = self
+            .column_names()
+            .into_iter()
+            .map(|name| {
+                let column = self.get_column(&name).unwrap();
+                ColumnStats::compute(name, column)
+            })
+            .collect();
+
+        TableStats {
+            table: self.name.clone(),
+            row_count: self.row_count(),
+            columns,
+        }
+    }
+
+    // ========================================================================
+    // INTEGRITY CHECKS
+    // ========================================================================
+
+    /// Checks the table's metadata for consistency.
+    ///
+    /// This is the cheap subset of [`Table::verify`]: it checks that the
+    /// name index, schema, and column list agree with each other and that
+    /// all columns have the same length, without reading any values.
+    ///
+    /// # Returns
+    ///
+    /// The issues found, in a stable order; empty if the table is consistent
+    pub fn verify_metadata(&self) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+        let issue = |column: Option<&str>, kind: IssueKind| IntegrityIssue {
+            table: self.name.clone(),
+            column: column.map(str::to_string),
+            row: None,
+            kind,
+        };
+
+        // Every indexed name must point at its own, existing column of the
+        // schema's type
+        let mut owners: BTreeMap<usize, &str> = BTreeMap::new();
+        let by_position: BTreeMap<usize, &str> = self
+            .column_index
+            .iter()
+            .map(|(name, &index)| (index, name.as_str()))
+            .collect();
+        let mut names: Vec<(&str, usize)> = self
+            .column_index
+            .iter()
+            .map(|(name, &index)| (name.as_str(), index))
+            .collect();
+        names.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+
+        for (name, index) in names {
+            let Some(column) = self.data.columns.get(index) else {
+                issues.push(issue(
+                    Some(name),
+                    IssueKind::DanglingIndex {
+                        index,
+                        column_count: self.data.columns.len(),
+                    },
+                ));
+                continue;
+            };
+
+            if let Some(other) = owners.insert(index, name) {
+                issues.push(issue(
+                    Some(name),
+                    IssueKind::SharedIndex {
+                        other: other.to_string(),
+                        index,
+                    },
+                ));
+            }
+
+            match self.schema.get(name) {
+                None => issues.push(issue(Some(name), IssueKind::MissingSchema)),
+                Some(&schema_type) if schema_type != column.data_type() => issues.push(issue(
+                    Some(name),
+                    IssueKind::TypeMismatch {
+                        schema: schema_type,
+                        actual: column.data_type(),
+                    },
+                )),
+                Some(_) => {}
+            }
+        }
+
+        let mut unindexed: Vec<&String> = self
+            .schema
+            .keys()
+            .filter(|name| !self.column_index.contains_key(*name))
+            .collect();
+        unindexed.sort();
+        for name in unindexed {
+            issues.push(issue(Some(name), IssueKind::MissingIndex));
+        }
+
+        for index in 0..self.data.columns.len() {
+            if !by_position.contains_key(&index) {
+                issues.push(issue(None, IssueKind::OrphanColumn { index }));
+            }
+        }
+
+        // All columns must have the same number of rows as the first
+        if let Some(first) = self.data.columns.first() {
+            let expected = first.len();
+            for (index, column) in self.data.columns.iter().enumerate().skip(1) {
+                if column.len() != expected {
+                    issues.push(issue(
+                        by_position.get(&index).copied(),
+                        IssueKind::RowCountMismatch {
+                            expected,
+                            actual: column.len(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Performs a deep integrity check of the table.
+    ///
+    /// Runs [`Table::verify_metadata`] and then asks every column to check
+    /// its own data (see [`Column::verify`]). Problems are reported with
+    /// table, column, and row context instead of panicking.
+    ///
+    /// # Returns
+    ///
+    /// The issues found, in a stable order; empty if the table is consistent
+    pub fn verify(&self) -> Vec<IntegrityIssue> {
+        let mut issues = self.verify_metadata();
+
+        let mut by_position: Vec<(usize, &String)> = self
+            .column_index
+            .iter()
+            .map(|(name, &index)| (index, name))
+            .collect();
+        by_position.sort();
+        by_position.dedup_by_key(|(index, _)| *index);
+
+        for (index, name) in by_position {
+            let Some(column) = self.data.columns.get(index) else {
+                continue;
+            };
+            for ColumnIssue { row, detail } in column.verify() {
+                issues.push(IntegrityIssue {
+                    table: self.name.clone(),
+                    column: Some(name.clone()),
+                    row,
+                    kind: IssueKind::ColumnData { detail },
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// Builds a table from raw parts without any validation, so tests can
+    /// construct deliberately inconsistent tables.
+    #[cfg(test)]
+    pub(crate) fn from_parts_unchecked(
+        name: &str,
+        columns: Vec<(&str, usize, DataType)>,
+        data: Vec<Box<dyn Column>>,
+    ) -> Self {
+        let mut table = Table::new(name.to_string());
+        for (column_name, index, data_type) in columns {
+            table.column_index.insert(column_name.to_string(), index);
+            table.schema.insert(column_name.to_string(), data_type);
+        }
+        table.data_mut().columns = data.into_iter().map(Arc::from).collect();
+        table
+    }
+}
+
+/// What kind of inconsistency an [`IntegrityIssue`] describes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    /// The column has a different number of rows than the table's first column
+    RowCountMismatch { expected: usize, actual: usize },
+    /// The schema's type for the column differs from the column data's type
+    TypeMismatch { schema: DataType, actual: DataType },
+    /// The column's index points past the end of the column list
+    DanglingIndex { index: usize, column_count: usize },
+    /// Two column names point at the same column data
+    SharedIndex { other: String, index: usize },
+    /// The column is in the name index but not in the schema
+    MissingSchema,
+    /// The column is in the schema but not in the name index
+    MissingIndex,
+    /// Column data exists that no column name refers to
+    OrphanColumn { index: usize },
+    /// The column's own data is inconsistent
+    ColumnData { detail: String },
+    /// The catalog has the table registered under a different name
+    NameMismatch { registered_as: String },
+}
+
+/// A single problem found by an integrity check, with its location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegrityIssue {
+    /// The table the problem was found in
+    pub table: String,
+    /// The affected column, if the problem is specific to one
+    pub column: Option<String>,
+    /// The affected row, if the problem is specific to one
+    pub row: Option<usize>,
+    /// What is wrong
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "table '{}'", self.table)?;
+        if let Some(column) = &self.column {
+            write!(f, ", column '{}'", column)?;
+        }
+        if let Some(row) = self.row {
+            write!(f, ", row {}", row)?;
+        }
+        write!(f, ": ")?;
+
+        match &self.kind {
+            IssueKind::RowCountMismatch { expected, actual } => {
+                write!(f, "has {} rows, expected {}", actual, expected)
+            }
+            IssueKind::TypeMismatch { schema, actual } => {
+                write!(f, "schema says {} but the data is {}", schema, actual)
+            }
+            IssueKind::DanglingIndex {
+                index,
+                column_count,
+            } => write!(
+                f,
+                "points at column {} but the table only has {} columns",
+                index, column_count
+            ),
+            IssueKind::SharedIndex { other, index } => {
+                write!(f, "shares column data {} with column '{}'", index, other)
+            }
+            IssueKind::MissingSchema => write!(f, "has no schema entry"),
+            IssueKind::MissingIndex => write!(f, "is in the schema but has no column data"),
+            IssueKind::OrphanColumn { index } => {
+                write!(
+                    f,
+                    "column data {} is not referenced by any column name",
+                    index
+                )
+            }
+            IssueKind::ColumnData { detail } => write!(f, "{}", detail),
+            IssueKind::NameMismatch { registered_as } => {
+                write!(f, "is registered in the catalog as '{}'", registered_as)
+            }
+        }
+    }
+}
+
+/// A column re-encoded by [`Table::compress`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnCompression {
+    /// The column name
+    pub column: String,
+    /// The encoding the column now uses
+    pub encoding: Encoding,
+    /// Memory held by the column before, in bytes
+    pub bytes_before: usize,
+    /// Memory held by the column after, in bytes
+    pub bytes_after: usize,
+}
+
+// ============================================================================
+// STATISTICS
+// ============================================================================
+
+/// Distinct counts stop growing at this many values, so profiling a
+/// high-cardinality column does not hold every value in memory.
+pub const DISTINCT_COUNT_CAP: usize = 100_000;
+
+/// Summary statistics for a table, from [`Table::compute_stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableStats {
+    /// The table the statistics describe
+    pub table: String,
+    /// The number of rows in the table
+    pub row_count: usize,
+    /// One entry per column, in table order
+    pub columns: Vec<ColumnStats>,
+}
+
+impl TableStats {
+    /// Returns the statistics for the named column, if the table has it.
+    pub fn column(&self, name: &str) -> Option<&ColumnStats> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
+
+/// Summary statistics for a single column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    /// The column name
+    pub name: String,
+    /// The column's data type
+    pub data_type: DataType,
+    /// The smallest non-NULL value, or `None` if there is none
+    pub min: Option<Value>,
+    /// The largest non-NULL value, or `None` if there is none
+    pub max: Option<Value>,
+    /// The number of NULL values
+    pub null_count: usize,
+    /// The number of distinct non-NULL values
+    pub distinct_count: DistinctCount,
+    /// The mean of the non-NULL values, for Int64 and Float64 columns
+    pub mean: Option<f64>,
+}
+
+/// A distinct-value count that may have hit [`DISTINCT_COUNT_CAP`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistinctCount {
+    /// The exact number of distinct values
+    Exact(usize),
+    /// Counting stopped; there are at least this many distinct values
+    AtLeast(usize),
+}
 
-        // Rebuild column_index to fix indices
-        self.rebuild_column_index();
+impl DistinctCount {
+    /// Returns the counted number, which is a lower bound for `AtLeast`.
+    pub fn count(&self) -> usize {
+        match self {
+            DistinctCount::Exact(count) | DistinctCount::AtLeast(count) => *count,
+        }
+    }
+}
 
-        Ok(())
+impl fmt::Display for DistinctCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistinctCount::Exact(count) => write!(f, "{}", count),
+            DistinctCount::AtLeast(count) => write!(f, "≥{}", count),
+        }
+    }
+}
+
+/// A hashable form of a non-NULL value for distinct counting.
+#[derive(PartialEq, Eq, Hash)]
+enum DistinctKey {
+    Int(i64),
+    /// Floats are keyed by their canonical bits, as GROUP BY and
+    /// COUNT(DISTINCT) key them, so 0.0 and -0.0 count once, as do all NaNs
+    Float(u64),
+    String(String),
+    Boolean(bool),
+    Date(i32),
+}
+
+impl DistinctKey {
+    fn new(value: &Value) -> Option<Self> {
+        match value {
+            Value::Int64(v) => Some(DistinctKey::Int(*v)),
+            Value::Float64(v) => Some(DistinctKey::Float(canonical_float_bits(*v))),
+            Value::String(v) => Some(DistinctKey::String(v.clone())),
+            Value::Boolean(v) => Some(DistinctKey::Boolean(*v)),
+            Value::Date(v) => Some(DistinctKey::Date(*v)),
+            Value::Null => None,
+        }
+    }
+}
+
+/// Orders two non-NULL values of the same type; NaN and mixed types are unordered.
+fn compare_stat_values(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Int64(a), Value::Int64(b)) => Some(a.cmp(b)),
+        (Value::Float64(a), Value::Float64(b)) => a.partial_cmp(b),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+        (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+        _ => None,
     }
+}
+
+impl ColumnStats {
+    fn compute(name: String, column: &dyn Column) -> Self {
+        let mut min: Option<Value> = None;
+        let mut max: Option<Value> = None;
+        let mut null_count = 0;
+        let mut distinct = HashSet::new();
+        let mut capped = false;
+        let mut sum = 0.0;
+        let mut numeric_count = 0usize;
+
+        for row in 0..column.len() {
+            let value = column.get(row).unwrap_or(Value::Null);
+            let Some(key) = DistinctKey::new(&value) else {
+                null_count += 1;
+                continue;
+            };
 
-    /// Rebuilds the column index map after removing a column.
-    fn rebuild_column_index(&mut self) {
-        self.column_index.clear();
-        for (name, data_type) in self.schema.iter() {
-            // We need to find the index by searching columns
-            for (idx, col) in self.columns.iter().enumerate() {
-                if col.data_type() == *data_type {
-                    // This is a heuristic - in reality we'd need a better way to match
-                    self.column_index.insert(name.clone(), idx);
-                    break;
+            if !capped {
+                distinct.insert(key);
+                capped = distinct.len() >= DISTINCT_COUNT_CAP;
+            }
+
+            match value {
+                Value::Int64(v) => {
+                    sum += v as f64;
+                    numeric_count += 1;
                 }
+                Value::Float64(v) => {
+                    sum += v;
+                    numeric_count += 1;
+                }
+                _ => {}
+            }
+
+            // NaN is unordered even against itself and is left out of MIN/MAX
+            if compare_stat_values(&value, &value).is_none() {
+                continue;
+            }
+            let beats = |current: &Option<Value>, wanted: Ordering| {
+                current
+                    .as_ref()
+                    .is_none_or(|current| compare_stat_values(&value, current) == Some(wanted))
+            };
+            if beats(&min, Ordering::Less) {
+                min = Some(value.clone());
+            }
+            if beats(&max, Ordering::Greater) {
+                max = Some(value);
             }
         }
+
+        let data_type = column.data_type();
+        let mean =
+            (data_type.is_numeric() && numeric_count > 0).then(|| sum / numeric_count as f64);
+        let distinct_count = if capped {
+            DistinctCount::AtLeast(distinct.len())
+        } else {
+            DistinctCount::Exact(distinct.len())
+        };
+
+        ColumnStats {
+            name,
+            data_type,
+            min,
+            max,
+            null_count,
+            distinct_count,
+            mean,
+        }
     }
 }
 
@@ -451,7 +1379,7 @@ impl std::fmt::Display for Table {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::column::{FloatColumn, IntColumn, StringColumn};
+    use crate::column::{create_column, FloatColumn, IntColumn, StringColumn, Zone};
 
     /// Test creating a new table
     #[test]
@@ -654,6 +1582,33 @@ mod tests {
         assert_eq!(val2.unwrap(), Value::Int64(100));
     }
 
+    /// Test iter_rows yields each row in column order
+    #[test]
+    fn test_iter_rows() {
+        let mut table = Table::new("test".to_string());
+        assert_eq!(table.iter_rows().count(), 0);
+
+        let mut ids = IntColumn::new();
+        ids.push_value(Value::Int64(1)).unwrap();
+        ids.push_null();
+        let mut names = StringColumn::new();
+        names.push_value(Value::String("a".to_string())).unwrap();
+        names.push_value(Value::String("b".to_string())).unwrap();
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+
+        let rows: Vec<Vec<Value>> = table.iter_rows().collect();
+        assert_eq!(
+            rows,
+            vec![
+                vec![Value::Int64(1), Value::String("a".to_string())],
+                vec![Value::Null, Value::String("b".to_string())],
+            ]
+        );
+    }
+
     /// Test get_value with invalid column
     #[test]
     fn test_get_value_invalid_column() {
@@ -739,6 +1694,131 @@ mod tests {
         assert!(error_msg.contains("Invalid integer value"));
     }
 
+    /// Test insert_rows coercion and NULLs
+    #[test]
+    fn test_insert_rows() {
+        let mut table = Table::new("test".to_string());
+        table
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        table
+            .add_column("score".to_string(), Box::new(FloatColumn::new()))
+            .unwrap();
+
+        let inserted = table
+            .insert_rows(vec![
+                vec![Value::Int64(1), Value::Int64(7)],
+                vec![Value::Null, Value::Float64(2.5)],
+            ])
+            .unwrap();
+
+        assert_eq!(inserted, 2);
+        assert_eq!(table.get_value("score", 0).unwrap(), Value::Float64(7.0));
+        assert_eq!(table.get_value("id", 1).unwrap(), Value::Null);
+    }
+
+    /// Test that a rejected insert_rows leaves every column untouched
+    #[test]
+    fn test_insert_rows_is_atomic() {
+        let mut table = Table::new("test".to_string());
+        table
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        table
+            .add_column("name".to_string(), Box::new(StringColumn::new()))
+            .unwrap();
+
+        let err = table
+            .insert_rows(vec![
+                vec![Value::Int64(1), Value::String("a".to_string())],
+                vec![Value::Int64(2)],
+            ])
+            .unwrap_err();
+        assert!(err.to_string().contains("Row 2 has 1 values"), "{}", err);
+
+        let err = table
+            .insert_rows(vec![
+                vec![Value::Int64(1), Value::String("a".to_string())],
+                vec![Value::Int64(2), Value::Int64(3)],
+            ])
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("cannot insert Int64 value 3 into String column 'name'"),
+            "{}",
+            err
+        );
+
+        assert_eq!(table.row_count(), 0);
+        assert!(table.verify().is_empty());
+    }
+
+    /// Test appending a table, with coercion and columns in another order
+    #[test]
+    fn test_append_table() {
+        let mut table = Table::new("test".to_string());
+        table
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        table
+            .add_column("score".to_string(), Box::new(FloatColumn::new()))
+            .unwrap();
+        table
+            .insert_rows(vec![vec![Value::Int64(1), Value::Float64(0.5)]])
+            .unwrap();
+        table.build_zone_maps();
+
+        let mut incoming = Table::new("incoming".to_string());
+        incoming
+            .add_column("score".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        incoming
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        incoming
+            .insert_rows(vec![
+                vec![Value::Int64(7), Value::Int64(2)],
+                vec![Value::Null, Value::Int64(3)],
+            ])
+            .unwrap();
+
+        assert_eq!(table.append_table(&incoming).unwrap(), 2);
+        assert_eq!(
+            table.iter_rows().collect::<Vec<_>>(),
+            vec![
+                vec![Value::Int64(1), Value::Float64(0.5)],
+                vec![Value::Int64(2), Value::Float64(7.0)],
+                vec![Value::Int64(3), Value::Null],
+            ]
+        );
+        assert_eq!(
+            table.zone_map("id"),
+            Some(&ZoneMap::build(table.get_column("id").unwrap()))
+        );
+        assert!(table.verify().is_empty());
+
+        // A column that does not fit rejects the whole append
+        let mut mismatched = Table::new("mismatched".to_string());
+        mismatched
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        mismatched
+            .add_column("score".to_string(), Box::new(StringColumn::new()))
+            .unwrap();
+        mismatched
+            .insert_rows(vec![vec![Value::Int64(4), Value::String("x".to_string())]])
+            .unwrap();
+        let err = table.append_table(&mismatched).unwrap_err();
+        assert!(err.to_string().contains("column 'score'"), "{}", err);
+
+        let mut missing = Table::new("missing".to_string());
+        missing
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        assert!(table.append_table(&missing).is_err());
+        assert_eq!(table.row_count(), 3);
+    }
+
     /// Test schema retrieval
     #[test]
     fn test_schema() {
@@ -1040,4 +2120,530 @@ mod tests {
         assert!(display.contains("Rows: 0"));
         assert!(display.contains("Columns: 0"));
     }
+
+    /// Test display hints are validated, cloned and dropped with the column
+    #[test]
+    fn test_display_hints() {
+        let mut table = Table::new("test".to_string());
+        table
+            .add_column("id".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        table
+            .add_column("name".to_string(), Box::new(StringColumn::new()))
+            .unwrap();
+        let money = DisplayHint::parse("money").unwrap();
+
+        table.set_display_hint("id", Some(money.clone())).unwrap();
+        assert_eq!(table.display_hint("id"), Some(&money));
+        assert_eq!(table.clone().display_hint("id"), Some(&money));
+        assert_eq!(
+            table
+                .select_columns(&["id".to_string()])
+                .unwrap()
+                .display_hint("id"),
+            Some(&money)
+        );
+
+        // Strings can't be formatted and unknown columns are errors
+        assert!(table.set_display_hint("name", Some(money.clone())).is_err());
+        assert!(table.set_display_hint("missing", None).is_err());
+
+        table.set_display_hint("id", None).unwrap();
+        assert_eq!(table.display_hint("id"), None);
+
+        table.set_display_hint("id", Some(money)).unwrap();
+        table.drop_column("id").unwrap();
+        assert!(table.display_hint("id").is_none());
+    }
+
+    // ------------------------------------------------------------------------
+    // Integrity Check Tests
+    // ------------------------------------------------------------------------
+
+    fn int_column(values: &[i64]) -> Box<dyn Column> {
+        let mut column = IntColumn::new();
+        for &v in values {
+            column.push_value(Value::Int64(v)).unwrap();
+        }
+        Box::new(column)
+    }
+
+    /// A column whose stored data disagrees with its declared type
+    struct CorruptColumn;
+
+    impl Column for CorruptColumn {
+        fn data_type(&self) -> DataType {
+            DataType::Int64
+        }
+        fn len(&self) -> usize {
+            3
+        }
+        fn push_value(&mut self, _value: Value) -> Result<()> {
+            Ok(())
+        }
+        fn push_null(&mut self) {}
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+        fn get(&self, index: usize) -> Result<Value> {
+            match index {
+                0 => Ok(Value::Int64(1)),
+                1 => Ok(Value::String("oops".to_string())),
+                _ => Err(DatabaseError::column_error("buffer too short")),
+            }
+        }
+        fn slice(&self, _range: Option<std::ops::Range<usize>>) -> Vec<Value> {
+            Vec::new()
+        }
+        fn clear(&mut self) {}
+        fn memory_size(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn test_verify_consistent_table() {
+        let mut table = Table::new("ok".to_string());
+        table
+            .add_column("a".to_string(), int_column(&[1, 2]))
+            .unwrap();
+        table
+            .add_column("b".to_string(), int_column(&[3, 4]))
+            .unwrap();
+        assert!(table.verify().is_empty());
+        assert!(Table::new("empty".to_string()).verify().is_empty());
+    }
+
+    #[test]
+    fn test_verify_detects_ragged_columns() {
+        let table = Table::from_parts_unchecked(
+            "t",
+            vec![("a", 0, DataType::Int64), ("b", 1, DataType::Int64)],
+            vec![int_column(&[1, 2, 3]), int_column(&[1])],
+        );
+
+        let issues = table.verify();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].table, "t");
+        assert_eq!(issues[0].column.as_deref(), Some("b"));
+        assert_eq!(
+            issues[0].kind,
+            IssueKind::RowCountMismatch {
+                expected: 3,
+                actual: 1
+            }
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "table 't', column 'b': has 1 rows, expected 3"
+        );
+    }
+
+    #[test]
+    fn test_verify_detects_type_mismatch() {
+        let table = Table::from_parts_unchecked(
+            "t",
+            vec![("a", 0, DataType::String)],
+            vec![int_column(&[1])],
+        );
+
+        assert_eq!(
+            table.verify()[0].kind,
+            IssueKind::TypeMismatch {
+                schema: DataType::String,
+                actual: DataType::Int64
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_detects_index_problems() {
+        let table = Table::from_parts_unchecked(
+            "t",
+            vec![
+                ("a", 0, DataType::Int64),
+                ("b", 0, DataType::Int64),
+                ("c", 7, DataType::Int64),
+            ],
+            vec![int_column(&[1]), int_column(&[2])],
+        );
+
+        let kinds: Vec<IssueKind> = table.verify().into_iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                IssueKind::SharedIndex {
+                    other: "a".to_string(),
+                    index: 0
+                },
+                IssueKind::DanglingIndex {
+                    index: 7,
+                    column_count: 2
+                },
+                IssueKind::OrphanColumn { index: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_detects_schema_index_disagreement() {
+        let mut table = Table::from_parts_unchecked(
+            "t",
+            vec![("a", 0, DataType::Int64), ("ghost", 1, DataType::Int64)],
+            vec![int_column(&[1]), int_column(&[1])],
+        );
+        table.schema.remove("a");
+        table.column_index.remove("ghost");
+
+        let issues = table.verify();
+        let located: Vec<(Option<&str>, &IssueKind)> = issues
+            .iter()
+            .map(|i| (i.column.as_deref(), &i.kind))
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                (Some("a"), &IssueKind::MissingSchema),
+                (Some("ghost"), &IssueKind::MissingIndex),
+                (None, &IssueKind::OrphanColumn { index: 1 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_reports_column_data_with_row() {
+        let table = Table::from_parts_unchecked(
+            "t",
+            vec![("bad", 0, DataType::Int64)],
+            vec![Box::new(CorruptColumn)],
+        );
+
+        // Metadata is fine; only the deep check reads the values
+        assert!(table.verify_metadata().is_empty());
+
+        let issues = table.verify();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].row, Some(1));
+        assert_eq!(
+            issues[0].to_string(),
+            "table 't', column 'bad', row 1: holds a String value but the column is Int64"
+        );
+        assert_eq!(issues[1].row, Some(2));
+        assert!(matches!(issues[1].kind, IssueKind::ColumnData { .. }));
+    }
+
+    #[test]
+    fn test_drop_column_keeps_table_consistent() {
+        // Two columns of the same type after the dropped one
+        let mut table = Table::new("t".to_string());
+        table.add_column("x".to_string(), int_column(&[1])).unwrap();
+        table.add_column("y".to_string(), int_column(&[2])).unwrap();
+        table.add_column("z".to_string(), int_column(&[3])).unwrap();
+
+        table.drop_column("x").unwrap();
+        assert!(table.verify().is_empty());
+        assert_eq!(table.column_names(), vec!["y", "z"]);
+        assert_eq!(table.get_value("z", 0).unwrap(), Value::Int64(3));
+    }
+
+    #[test]
+    fn test_clone_shares_columns_until_written() {
+        let mut table = Table::new("t".to_string());
+        table
+            .add_column("n".to_string(), int_column(&[1, 2]))
+            .unwrap();
+
+        let mut copy = table.clone();
+        assert!(Arc::ptr_eq(
+            &table.shared_column("n").unwrap(),
+            &copy.shared_column("n").unwrap()
+        ));
+
+        copy.insert_rows(vec![vec![Value::Int64(3)]]).unwrap();
+        assert_eq!(copy.row_count(), 3);
+        assert_eq!(table.row_count(), 2);
+        assert!(!Arc::ptr_eq(
+            &table.shared_column("n").unwrap(),
+            &copy.shared_column("n").unwrap()
+        ));
+
+        // A handle held elsewhere keeps seeing the data it was given
+        let handle = table.shared_column("n").unwrap();
+        table.add_row(vec!["4".to_string()]).unwrap();
+        assert_eq!(handle.len(), 2);
+        assert_eq!(table.get_value("n", 2).unwrap(), Value::Int64(4));
+
+        // Unshared columns are written in place
+        let before = table.shared_column("n").unwrap();
+        let before_ptr = Arc::as_ptr(&before);
+        drop((handle, before));
+        table.get_column_mut("n").unwrap().push_null();
+        assert_eq!(
+            Arc::as_ptr(&table.shared_column("n").unwrap()) as *const (),
+            before_ptr as *const ()
+        );
+        assert!(table.shared_column("missing").is_err());
+    }
+
+    #[test]
+    fn test_select_columns_shares_data() {
+        let mut table = Table::new("t".to_string());
+        table.add_column("a".to_string(), int_column(&[1])).unwrap();
+        table.add_column("b".to_string(), int_column(&[2])).unwrap();
+
+        let selected = table.select_columns(&["b".to_string()]).unwrap();
+        assert_eq!(selected.column_names(), vec!["b"]);
+        assert!(Arc::ptr_eq(
+            &selected.shared_column("b").unwrap(),
+            &table.shared_column("b").unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let mut table = Table::new("t".to_string());
+        let mut prices = FloatColumn::new();
+        let mut names = StringColumn::new();
+        let mut days = create_column(DataType::Date);
+        for (price, name, day) in [
+            (Value::Float64(2.5), "b", Value::Date(10)),
+            (Value::Null, "a", Value::Date(3)),
+            (Value::Float64(f64::NAN), "b", Value::Null),
+            (Value::Float64(-1.5), "c", Value::Date(3)),
+        ] {
+            prices.push_value(price).unwrap();
+            names.push_value(Value::String(name.to_string())).unwrap();
+            days.push_value(day).unwrap();
+        }
+        table
+            .add_column("price".to_string(), Box::new(prices))
+            .unwrap();
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+        table.add_column("day".to_string(), days).unwrap();
+
+        let stats = table.compute_stats();
+        assert_eq!(stats.table, "t");
+        assert_eq!(stats.row_count, 4);
+        let columns: Vec<&str> = stats.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(columns, vec!["price", "name", "day"]);
+
+        // NaN counts as a distinct value but stays out of MIN/MAX
+        let price = stats.column("price").unwrap();
+        assert_eq!(price.min, Some(Value::Float64(-1.5)));
+        assert_eq!(price.max, Some(Value::Float64(2.5)));
+        assert_eq!(price.null_count, 1);
+        assert_eq!(price.distinct_count, DistinctCount::Exact(3));
+        assert!(price.mean.unwrap().is_nan());
+
+        let name = stats.column("name").unwrap();
+        assert_eq!(name.min, Some(Value::String("a".to_string())));
+        assert_eq!(name.max, Some(Value::String("c".to_string())));
+        assert_eq!(name.distinct_count, DistinctCount::Exact(3));
+        assert_eq!(name.mean, None);
+
+        let day = stats.column("day").unwrap();
+        assert_eq!(day.min, Some(Value::Date(3)));
+        assert_eq!(day.max, Some(Value::Date(10)));
+        assert_eq!(day.null_count, 1);
+        assert_eq!(day.distinct_count, DistinctCount::Exact(2));
+        assert!(stats.column("missing").is_none());
+    }
+
+    #[test]
+    fn test_compute_stats_mean_and_empty_columns() {
+        let mut table = Table::new("t".to_string());
+        table
+            .add_column("n".to_string(), int_column(&[1, 2, 3, 6]))
+            .unwrap();
+        let n = table.compute_stats().columns[0].clone();
+        assert_eq!(n.mean, Some(3.0));
+        assert_eq!(n.null_count, 0);
+
+        let mut empty = Table::new("empty".to_string());
+        empty
+            .add_column("n".to_string(), create_column(DataType::Int64))
+            .unwrap();
+        let n = empty.compute_stats().columns[0].clone();
+        assert_eq!((n.min, n.max, n.mean), (None, None, None));
+        assert_eq!(n.distinct_count, DistinctCount::Exact(0));
+    }
+
+    #[test]
+    fn test_compute_stats_distinct_floats_match_group_by() {
+        let mut values = FloatColumn::new();
+        for v in [0.0, -0.0, 1.5, f64::NAN, -f64::NAN, 1.5] {
+            values.push_value(Value::Float64(v)).unwrap();
+        }
+        let mut table = Table::new("t".to_string());
+        table.add_column("v".to_string(), Box::new(values)).unwrap();
+
+        // 0.0 and -0.0 are one value, and so are the NaNs
+        let v = table.compute_stats().columns[0].clone();
+        assert_eq!(v.distinct_count, DistinctCount::Exact(3));
+    }
+
+    #[test]
+    fn test_zone_maps_follow_table_changes() {
+        let values: Vec<i64> = (0..3000).collect();
+        let mut table = Table::new("t".to_string());
+        table
+            .add_column("n".to_string(), int_column(&values))
+            .unwrap();
+        let zone_count = |table: &Table| table.zone_map("n").map(|zones| zones.zones().len());
+        assert_eq!(zone_count(&table), Some(3));
+
+        // Appends keep the zone map current, and clones share it
+        let before_insert = table.clone();
+        table
+            .insert_rows(vec![vec![Value::Int64(-5)], vec![Value::Int64(9000)]])
+            .unwrap();
+        table.add_row(vec!["4000".to_string()]).unwrap();
+        let zones = table.zone_map("n").unwrap();
+        assert_eq!(zones.row_count(), 3003);
+        assert_eq!(
+            zones.zones()[2],
+            Zone::Range {
+                min: Value::Int64(-5),
+                max: Value::Int64(9000)
+            }
+        );
+        assert_eq!(zones, &ZoneMap::build(table.get_column("n").unwrap()));
+        assert_eq!(before_insert.zone_map("n").unwrap().row_count(), 3000);
+
+        // A selection keeps the zone map
+        let selected = table.select_columns(&["n".to_string()]).unwrap();
+        assert_eq!(selected.zone_map("n"), table.zone_map("n"));
+
+        // An in-place write drops it until it is rebuilt
+        table.get_column_mut("n").unwrap();
+        assert!(table.zone_map("n").is_none());
+        table.build_zone_maps();
+        assert_eq!(zone_count(&table), Some(3));
+
+        // Only numeric and date columns have zone maps
+        let mut names = StringColumn::new();
+        for _ in 0..3003 {
+            names.push_value(Value::String("x".to_string())).unwrap();
+        }
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+        assert!(table.zone_map("name").is_none());
+
+        table.drop_column("n").unwrap();
+        assert!(table.zone_map("n").is_none());
+        assert!(table.zone_map("name").is_none());
+    }
+
+    #[test]
+    fn test_compress_keeps_values_zone_maps_and_snapshots() {
+        let mut table = Table::new("t".to_string());
+        let mut ids = IntColumn::new();
+        let mut names = StringColumn::new();
+        for i in 0..5_000 {
+            ids.push_value(Value::Int64(i)).unwrap();
+            names
+                .push_value(Value::String(format!("name {}", i * 7919 % 5_000)))
+                .unwrap();
+        }
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+        let rows: Vec<_> = table.iter_rows().collect();
+        let zones = table.zone_map("id").unwrap().zones().to_vec();
+        let before = table.snapshot();
+
+        // Only the id column shrinks enough
+        let changes = table.compress().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].column, "id");
+        assert_eq!(changes[0].encoding, Encoding::Delta);
+        assert!(changes[0].bytes_after < changes[0].bytes_before);
+        assert!(table.compress().unwrap().is_empty());
+
+        assert_eq!(table.iter_rows().collect::<Vec<_>>(), rows);
+        assert_eq!(table.zone_map("id").unwrap().zones(), zones);
+        assert!(before.column(0).unwrap().as_any().is::<IntColumn>());
+
+        // Appends go through the compressed column
+        table
+            .insert_rows(vec![vec![Value::Int64(5_000), Value::Null]])
+            .unwrap();
+        assert_eq!(table.get_value("id", 5_000).unwrap(), Value::Int64(5_000));
+        assert_eq!(table.row_count(), 5_001);
+        assert!(table.verify().is_empty());
+    }
+
+    #[test]
+    fn test_snapshots_are_unaffected_by_writes() {
+        let mut table = Table::new("t".to_string());
+        let mut ids = IntColumn::new();
+        let mut names = StringColumn::new();
+        for i in 0..3 {
+            ids.push_value(Value::Int64(i)).unwrap();
+            names.push_value(Value::String(format!("n{}", i))).unwrap();
+        }
+        table.add_column("id".to_string(), Box::new(ids)).unwrap();
+        table
+            .add_column("name".to_string(), Box::new(names))
+            .unwrap();
+
+        // Taking a snapshot copies nothing
+        let before = table.snapshot();
+        assert!(Arc::ptr_eq(&before, &table.snapshot()));
+
+        table
+            .add_row(vec!["3".to_string(), "n3".to_string()])
+            .unwrap();
+        table
+            .insert_rows(vec![vec![Value::Int64(4), Value::Null]])
+            .unwrap();
+        table.drop_column("name").unwrap();
+
+        assert_eq!((before.len(), before.row_count()), (2, 3));
+        assert_eq!(before.column(1).unwrap().len(), 3);
+        assert_eq!(before.zone_map(0).unwrap().zones().len(), 1);
+        assert_eq!((table.column_count(), table.row_count()), (1, 5));
+
+        // A write to one column leaves the snapshot's other columns shared
+        let mut table = Table::new("t".to_string());
+        table
+            .add_column("a".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        table
+            .add_column("b".to_string(), Box::new(IntColumn::new()))
+            .unwrap();
+        let before = table.snapshot();
+        table.get_column_mut("a").unwrap();
+        let after = table.snapshot();
+        assert!(!Arc::ptr_eq(
+            before.column(0).unwrap(),
+            after.column(0).unwrap()
+        ));
+        assert!(Arc::ptr_eq(
+            before.column(1).unwrap(),
+            after.column(1).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_compute_stats_caps_distinct_count() {
+        let values: Vec<i64> = (0..DISTINCT_COUNT_CAP as i64 + 10).collect();
+        let mut table = Table::new("t".to_string());
+        table
+            .add_column("n".to_string(), int_column(&values))
+            .unwrap();
+
+        let n = table.compute_stats().columns[0].clone();
+        assert_eq!(n.distinct_count, DistinctCount::AtLeast(DISTINCT_COUNT_CAP));
+        assert_eq!(n.distinct_count.to_string(), "≥100000");
+        // The cap only limits the distinct count
+        assert_eq!(n.max, Some(Value::Int64(DISTINCT_COUNT_CAP as i64 + 9)));
+    }
 }
@@ -22,6 +22,15 @@
 //! - **String handling**: Strings are heap-allocated and use `String` type
 //! - **NULL handling**: `Option<Value>` represents nullable values
 //!
+//! ## Serialization
+//!
+//! With the `serde` feature, `DataType`, `Value` and `SortDirection`
+//! implement `Serialize` and `Deserialize`. Values are externally tagged
+//! (`{"Int64":1}`, `{"Float64":1.0}`, `"Null"`), so an integer and a float
+//! with the same magnitude stay distinct after a round trip. JSON has no
+//! NaN or infinities, so a non-finite `Float64` is written as one of the
+//! strings `"NaN"`, `"inf"` or `"-inf"` and read back from them.
+//!
 //! ## Usage Example
 //!
 //! ```no_run
@@ -40,6 +49,7 @@ use std::fmt;
 use std::str::FromStr;
 
 use crate::error::{DatabaseError, Result};
+use crate::format::FormatOptions;
 
 /// Represents data type of a column in the database schema
 ///
@@ -51,6 +61,8 @@ use crate::error::{DatabaseError, Result};
 /// - **Int64**: 64-bit signed integer, fixed 8-byte size
 /// - **Float64**: 64-bit floating point number, fixed 8-byte size (IEEE 754)
 /// - **String**: Variable-length UTF-8 encoded string, heap-allocated
+/// - **Boolean**: TRUE or FALSE, stored one bit per value in columns
+/// - **Date**: Calendar date, stored as days since 1970-01-01 in 4 bytes
 ///
 /// ## Type Safety
 ///
@@ -66,6 +78,7 @@ use crate::error::{DatabaseError, Result};
 /// assert_eq!(column_type.name(), "Int64");
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataType {
     /// 64-bit signed integer
     /// Range: -9,223,372,036,854,775,808 to 9,223,372,036,854,775,807
@@ -78,6 +91,14 @@ pub enum DataType {
     /// UTF-8 encoded string
     /// Variable length, stored on the heap
     String,
+
+    /// Boolean (TRUE or FALSE)
+    /// Stored packed, one bit per value
+    Boolean,
+
+    /// Calendar date without a time of day
+    /// Stored as a signed count of days since 1970-01-01
+    Date,
 }
 
 impl DataType {
@@ -96,6 +117,8 @@ impl DataType {
             DataType::Int64 => "Int64",
             DataType::Float64 => "Float64",
             DataType::String => "String",
+            DataType::Boolean => "Boolean",
+            DataType::Date => "Date",
         }
     }
 
@@ -117,6 +140,8 @@ impl DataType {
             DataType::Int64 => std::mem::size_of::<i64>(),
             DataType::Float64 => std::mem::size_of::<f64>(),
             DataType::String => std::mem::size_of::<String>(),
+            DataType::Boolean => std::mem::size_of::<bool>(),
+            DataType::Date => std::mem::size_of::<i32>(),
         }
     }
 
@@ -182,8 +207,9 @@ impl fmt::Display for DataType {
 ///
 /// ## Null Values
 ///
-/// To represent NULL/missing values, use `Option<Value>`. This is the idiomatic Rust way.
-/// A value that must be present is `Value`, an optional value is `Option<Value>`.
+/// `Value::Null` is a SQL NULL: a missing value of no particular type. It is
+/// what columns return for rows loaded from empty CSV fields, compares false
+/// against everything in predicates, and is skipped by aggregates.
 ///
 /// # Example
 /// ```rust
@@ -194,21 +220,33 @@ impl fmt::Display for DataType {
 /// let name = Value::String("Alice".to_string());
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     /// 64-bit signed integer value
     Int64(i64),
 
     /// 64-bit floating point value
+    #[cfg_attr(feature = "serde", serde(with = "float_repr"))]
     Float64(f64),
 
     /// String value (UTF-8 encoded)
     String(String),
+
+    /// Boolean value
+    Boolean(bool),
+
+    /// Date value, as days since 1970-01-01
+    Date(i32),
+
+    /// SQL NULL (a missing value)
+    Null,
 }
 
 /// Sort direction for ORDER BY clause.
 ///
 /// Represents the direction in which values should be sorted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SortDirection {
     /// Ascending order (ASC)
     Ascending,
@@ -219,23 +257,49 @@ pub enum SortDirection {
 impl Value {
     /// Returns the DataType of this value
     ///
-    /// This is useful for type checking and validation.
+    /// This is useful for type checking and validation. NULL has no type
+    /// of its own, so `Value::Null` returns `None`.
     ///
     /// # Example
     /// ```rust
     /// use mini_rust_olap::types::{Value, DataType};
     ///
     /// let v = Value::Int64(42);
-    /// assert_eq!(v.data_type(), DataType::Int64);
+    /// assert_eq!(v.data_type(), Some(DataType::Int64));
+    /// assert_eq!(Value::Null.data_type(), None);
     /// ```
-    pub fn data_type(&self) -> DataType {
+    pub fn data_type(&self) -> Option<DataType> {
         match self {
-            Value::Int64(_) => DataType::Int64,
-            Value::Float64(_) => DataType::Float64,
-            Value::String(_) => DataType::String,
+            Value::Int64(_) => Some(DataType::Int64),
+            Value::Float64(_) => Some(DataType::Float64),
+            Value::String(_) => Some(DataType::String),
+            Value::Boolean(_) => Some(DataType::Boolean),
+            Value::Date(_) => Some(DataType::Date),
+            Value::Null => None,
         }
     }
 
+    /// Returns the name of this value's type, or `"NULL"` for NULL
+    ///
+    /// Intended for error messages.
+    pub fn type_name(&self) -> &'static str {
+        self.data_type()
+            .map_or("NULL", |data_type| data_type.name())
+    }
+
+    /// Returns true if this value is NULL
+    ///
+    /// # Example
+    /// ```rust
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// assert!(Value::Null.is_null());
+    /// assert!(!Value::Int64(0).is_null());
+    /// ```
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
     /// Attempts to convert this value to another DataType
     ///
     /// Returns the converted value if the conversion is valid, otherwise an error.
@@ -243,6 +307,8 @@ impl Value {
     /// Conversion rules:
     /// - Same type: Returns the value as-is
     /// - Int64 → Float64: Converts with possible loss of precision
+    /// - String → Date: Parses an ISO `YYYY-MM-DD` date
+    /// - NULL: Stays NULL, whatever the target type
     /// - Other conversions: Return an error
     ///
     /// # Example
@@ -255,10 +321,23 @@ impl Value {
     /// ```
     pub fn cast_to(&self, target: DataType) -> Result<Value> {
         match (self, target) {
+            // NULL casts to NULL of any type
+            (Value::Null, _) => Ok(Value::Null),
+
             // Same type - no conversion needed
             (Value::Int64(v), DataType::Int64) => Ok(Value::Int64(*v)),
             (Value::Float64(v), DataType::Float64) => Ok(Value::Float64(*v)),
             (Value::String(v), DataType::String) => Ok(Value::String(v.clone())),
+            (Value::Boolean(v), DataType::Boolean) => Ok(Value::Boolean(*v)),
+            (Value::Date(v), DataType::Date) => Ok(Value::Date(*v)),
+
+            // String → Date (ISO YYYY-MM-DD only)
+            (Value::String(v), DataType::Date) => parse_date(v).map(Value::Date).ok_or_else(|| {
+                DatabaseError::type_error(format!(
+                    "Cannot cast '{}' to Date: expected YYYY-MM-DD",
+                    v
+                ))
+            }),
 
             // Int64 → Float64 (promoting integer to float)
             (Value::Int64(v), DataType::Float64) => Ok(Value::Float64(*v as f64)),
@@ -280,6 +359,15 @@ impl Value {
             (Value::Float64(_), DataType::String) => {
                 Err(DatabaseError::type_error("Cannot cast Float64 to String"))
             }
+            // Booleans neither convert to nor from other types, and dates
+            // only convert from strings
+            (Value::Boolean(_) | Value::Date(_), _) | (_, DataType::Boolean | DataType::Date) => {
+                Err(DatabaseError::type_error(format!(
+                    "Cannot cast {} to {}",
+                    self.type_name(),
+                    target
+                )))
+            }
         }
     }
 
@@ -301,6 +389,7 @@ impl Value {
     ///
     /// This is similar to `PartialEq` but provides type checking.
     /// Returns an error if trying to compare incompatible types.
+    /// Following SQL, NULL is never equal to anything, including NULL.
     ///
     /// # Example
     /// ```rust
@@ -311,23 +400,136 @@ impl Value {
     /// assert_eq!(v1.equals(&v2).unwrap(), true);
     /// ```
     pub fn equals(&self, other: &Value) -> Result<bool> {
+        if self.is_null() || other.is_null() {
+            return Ok(false);
+        }
         if self.data_type() != other.data_type() {
             return Err(DatabaseError::type_error(format!(
                 "Cannot compare {} with {}",
-                self.data_type(),
-                other.data_type()
+                self.type_name(),
+                other.type_name()
             )));
         }
         Ok(self == other)
     }
 }
 
+impl Value {
+    /// Renders the value as text for display
+    ///
+    /// Floats print as the shortest text that parses back to exactly the
+    /// same value, so `0.1` stays `0.1` and `1e-7` keeps every digit it
+    /// needs. Integer-valued floats keep a trailing `.0` (`648000.0`) so they
+    /// can't be mistaken for `Int64`s, and very large or small magnitudes use
+    /// exponent notation (`1e20`). With `float_precision` set, floats print
+    /// with that many fixed decimals instead. Every other type prints as its
+    /// `Display` output.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - How to render floats
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mini_rust_olap::format::FormatOptions;
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// let default = FormatOptions::default();
+    /// assert_eq!(Value::Float64(0.1).format(&default), "0.1");
+    /// assert_eq!(Value::Float64(3.0).format(&default), "3.0");
+    ///
+    /// let fixed = FormatOptions { float_precision: Some(2) };
+    /// assert_eq!(Value::Float64(2.0 / 3.0).format(&fixed), "0.67");
+    /// assert_eq!(Value::Int64(7).format(&fixed), "7");
+    /// ```
+    pub fn format(&self, options: &FormatOptions) -> String {
+        match (self, options.float_precision) {
+            (Value::Float64(v), Some(precision)) => format!("{:.*}", precision, v),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Encodes the value as the plain JSON type closest to it.
+    ///
+    /// Unlike the tagged `Serialize` form, this loses the distinction
+    /// between `Int64(1)` and `Float64(1.0)`: it is for result payloads
+    /// meant for people and dashboards, not for round trips. Dates become
+    /// `YYYY-MM-DD` strings, and NaN and infinities become `null`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_rust_olap::types::Value;
+    ///
+    /// assert_eq!(Value::Int64(7).to_json().to_string(), "7");
+    /// assert_eq!(Value::Date(0).to_json().to_string(), "\"1970-01-01\"");
+    /// assert_eq!(Value::Float64(f64::NAN).to_json().to_string(), "null");
+    /// ```
+    #[cfg(any(feature = "http", feature = "serde"))]
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::{json, Value as Json};
+        match self {
+            Value::Int64(v) => json!(v),
+            Value::Float64(v) => serde_json::Number::from_f64(*v).map_or(Json::Null, Json::Number),
+            Value::String(v) => json!(v),
+            Value::Boolean(v) => json!(v),
+            Value::Date(_) => json!(self.to_string()),
+            Value::Null => Json::Null,
+        }
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Value::Int64(v) => write!(f, "{}", v),
-            Value::Float64(v) => write!(f, "{}", v),
+            // Debug is f64's shortest round-trip form, keeping ".0"
+            Value::Float64(v) => write!(f, "{:?}", v),
             Value::String(v) => write!(f, "{}", v),
+            Value::Boolean(v) => write!(f, "{}", v),
+            Value::Date(days) => write!(f, "{}", format_date(*days)),
+            Value::Null => write!(f, "NULL"),
+        }
+    }
+}
+
+/// Serde representation of `Value::Float64`: finite floats are numbers,
+/// and NaN and the infinities are the strings `"NaN"`, `"inf"` and
+/// `"-inf"`, which formats like JSON cannot hold as numbers.
+#[cfg(feature = "serde")]
+mod float_repr {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Number(f64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_nan() {
+            serializer.serialize_str("NaN")
+        } else if value.is_infinite() {
+            serializer.serialize_str(if *value > 0.0 { "inf" } else { "-inf" })
+        } else {
+            serializer.serialize_f64(*value)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(value) => Ok(value),
+            Repr::Text(text) => match text.as_str() {
+                "NaN" => Ok(f64::NAN),
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                _ => Err(de::Error::invalid_value(
+                    de::Unexpected::Str(&text),
+                    &"a number, \"NaN\", \"inf\" or \"-inf\"",
+                )),
+            },
         }
     }
 }
@@ -357,6 +559,13 @@ impl From<String> for Value {
     }
 }
 
+/// Allow creating Value from bool
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Boolean(value)
+    }
+}
+
 /// Allow creating Value from &str
 impl From<&str> for Value {
     fn from(value: &str) -> Self {
@@ -364,10 +573,135 @@ impl From<&str> for Value {
     }
 }
 
+// ============================================================================
+// FLOAT KEYS
+// ============================================================================
+
+/// The bits a float is keyed by when grouping or counting distinct values:
+/// `-0.0` becomes `0.0` and every NaN becomes `f64::NAN`, so values that SQL
+/// treats as one value hash and compare alike.
+pub(crate) fn canonical_float_bits(value: f64) -> u64 {
+    if value.is_nan() {
+        f64::NAN.to_bits()
+    } else if value == 0.0 {
+        0.0f64.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
 // ============================================================================
 // PARSING STRING REPRESENTATIONS
 // ============================================================================
 
+/// Parse a boolean written as `true` or `false`, in any letter case
+///
+/// # Example
+/// ```rust
+/// use mini_rust_olap::types::parse_boolean;
+///
+/// assert_eq!(parse_boolean("TRUE"), Some(true));
+/// assert_eq!(parse_boolean("false"), Some(false));
+/// assert_eq!(parse_boolean("yes"), None);
+/// ```
+pub fn parse_boolean(text: &str) -> Option<bool> {
+    if text.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if text.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Days from 1970-01-01 to the given proleptic Gregorian date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // Count years from March, so the leap day ends the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The proleptic Gregorian (year, month, day) of a count of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Parse an ISO `YYYY-MM-DD` date into days since 1970-01-01
+///
+/// Returns `None` unless the text is exactly four year digits, two month
+/// digits and two day digits naming a real calendar day.
+///
+/// # Example
+/// ```rust
+/// use mini_rust_olap::types::parse_date;
+///
+/// assert_eq!(parse_date("1970-01-02"), Some(1));
+/// assert_eq!(parse_date("2024-02-29"), Some(19_782));
+/// assert_eq!(parse_date("2023-02-29"), None);
+/// assert_eq!(parse_date("2024-1-5"), None);
+/// ```
+pub fn parse_date(text: &str) -> Option<i32> {
+    let bytes = text.as_bytes();
+    let digits = |range: std::ops::Range<usize>| -> Option<u32> {
+        let part = text.get(range)?;
+        if part.bytes().all(|b| b.is_ascii_digit()) {
+            part.parse().ok()
+        } else {
+            None
+        }
+    };
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let (year, month, day) = (digits(0..4)?, digits(5..7)?, digits(8..10)?);
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if day == 0 || day > days_in_month {
+        return None;
+    }
+    i32::try_from(days_from_civil(year as i64, month, day)).ok()
+}
+
+/// Render days since 1970-01-01 as an ISO `YYYY-MM-DD` date
+///
+/// # Example
+/// ```rust
+/// use mini_rust_olap::types::format_date;
+///
+/// assert_eq!(format_date(0), "1970-01-01");
+/// assert_eq!(format_date(-1), "1969-12-31");
+/// ```
+pub fn format_date(days: i32) -> String {
+    let (year, month, day) = civil_from_days(days as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
 /// Parse a DataType from its string representation
 ///
 /// # Example
@@ -382,9 +716,11 @@ impl FromStr for DataType {
 
     fn from_str(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
-            "int64" | "int" => Ok(DataType::Int64),
+            "int64" | "int" | "integer" => Ok(DataType::Int64),
             "float64" | "float" | "double" => Ok(DataType::Float64),
             "string" | "text" | "varchar" => Ok(DataType::String),
+            "boolean" | "bool" => Ok(DataType::Boolean),
+            "date" => Ok(DataType::Date),
             _ => Err(DatabaseError::type_error(format!(
                 "Unknown data type: {}",
                 s
@@ -398,6 +734,7 @@ impl FromStr for DataType {
 /// This attempts to infer the type from the string content:
 /// - If it looks like an integer, parse as Int64
 /// - If it looks like a float, parse as Float64
+/// - If it is an ISO `YYYY-MM-DD` date, parse as Date
 /// - Otherwise, treat as String
 ///
 /// # Example
@@ -423,6 +760,11 @@ impl FromStr for Value {
             return Ok(Value::Float64(f));
         }
 
+        // Try to parse as an ISO date
+        if let Some(days) = parse_date(s) {
+            return Ok(Value::Date(days));
+        }
+
         // Default to string
         Ok(Value::String(s.to_string()))
     }
@@ -512,12 +854,13 @@ mod tests {
 
     #[test]
     fn test_value_data_type() {
-        assert_eq!(Value::Int64(42).data_type(), DataType::Int64);
-        assert_eq!(Value::Float64(3.5).data_type(), DataType::Float64);
+        assert_eq!(Value::Int64(42).data_type(), Some(DataType::Int64));
+        assert_eq!(Value::Float64(3.5).data_type(), Some(DataType::Float64));
         assert_eq!(
             Value::String("test".to_string()).data_type(),
-            DataType::String
+            Some(DataType::String)
         );
+        assert_eq!(Value::Null.data_type(), None);
     }
 
     #[test]
@@ -532,6 +875,62 @@ mod tests {
         assert_eq!(format!("{}", Value::Int64(42)), "42");
         assert_eq!(format!("{}", Value::Float64(3.5)), "3.5");
         assert_eq!(format!("{}", Value::String("hello".to_string())), "hello");
+        assert_eq!(format!("{}", Value::Null), "NULL");
+    }
+
+    #[test]
+    fn test_float_format_round_trips() {
+        let default = FormatOptions::default();
+        for v in [
+            1e-7,
+            1e20,
+            0.1,
+            -0.0,
+            648000.0,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ] {
+            let text = Value::Float64(v).format(&default);
+            let parsed: f64 = text.parse().unwrap();
+            assert_eq!(parsed.to_bits(), v.to_bits(), "{}", text);
+        }
+        let nan = Value::Float64(f64::NAN).format(&default);
+        assert!(nan.parse::<f64>().unwrap().is_nan(), "{}", nan);
+
+        // Integer-valued floats keep their ".0"
+        assert_eq!(Value::Float64(648000.0).format(&default), "648000.0");
+        assert_eq!(Value::Float64(-0.0).format(&default), "-0.0");
+        assert_eq!(
+            Value::Float64(0.1 + 0.2).format(&default),
+            "0.30000000000000004"
+        );
+    }
+
+    #[test]
+    fn test_float_format_with_fixed_precision() {
+        let fixed = |decimals| FormatOptions {
+            float_precision: Some(decimals),
+        };
+        assert_eq!(Value::Float64(0.1 + 0.2).format(&fixed(2)), "0.30");
+        assert_eq!(
+            Value::Float64(1e20).format(&fixed(1)),
+            "100000000000000000000.0"
+        );
+        assert_eq!(Value::Float64(2.5).format(&fixed(0)), "2");
+        assert_eq!(Value::Float64(f64::NAN).format(&fixed(2)), "NaN");
+        // Only floats are affected
+        assert_eq!(Value::Int64(3).format(&fixed(2)), "3");
+        assert_eq!(Value::Null.format(&fixed(2)), "NULL");
+    }
+
+    #[test]
+    fn test_value_null() {
+        assert!(Value::Null.is_null());
+        assert_eq!(Value::Null.type_name(), "NULL");
+        assert_eq!(Value::Int64(1).type_name(), "Int64");
+        assert_eq!(Value::Null.cast_to(DataType::Int64).unwrap(), Value::Null);
+        assert!(!Value::Null.equals(&Value::Null).unwrap());
+        assert!(!Value::Null.equals(&Value::Int64(1)).unwrap());
     }
 
     #[test]
@@ -725,7 +1124,7 @@ mod tests {
     #[test]
     fn test_empty_string() {
         let v = Value::String("".to_string());
-        assert_eq!(v.data_type(), DataType::String);
+        assert_eq!(v.data_type(), Some(DataType::String));
         assert_eq!(format!("{}", v), "");
     }
 
@@ -737,6 +1136,58 @@ mod tests {
         assert_eq!(v2, Value::Float64(0.0));
     }
 
+    #[test]
+    fn test_boolean_values() {
+        let v = Value::from(true);
+        assert_eq!(v.data_type(), Some(DataType::Boolean));
+        assert_eq!(v.to_string(), "true");
+        assert_eq!(v.cast_to(DataType::Boolean).unwrap(), v);
+        assert!(v.cast_to(DataType::Int64).is_err());
+        assert!(Value::Int64(1).cast_to(DataType::Boolean).is_err());
+
+        assert_eq!("BOOL".parse::<DataType>().unwrap(), DataType::Boolean);
+        assert_eq!("Integer".parse::<DataType>().unwrap(), DataType::Int64);
+        assert_eq!(parse_boolean("TRUE"), Some(true));
+        assert_eq!(parse_boolean("false"), Some(false));
+        assert_eq!(parse_boolean("yes"), None);
+    }
+
+    #[test]
+    fn test_date_values() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("1969-12-31"), Some(-1));
+        assert_eq!(parse_date("2000-03-01"), Some(11017));
+        assert_eq!(
+            parse_date("2024-02-29").map(format_date).unwrap(),
+            "2024-02-29"
+        );
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("1900-02-29"), None);
+        assert_eq!(parse_date("2024-1-05"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("20240105"), None);
+
+        for days in [-719_468, -1, 0, 59, 10_957, 19_723, 2_932_896] {
+            assert_eq!(parse_date(&format_date(days)), Some(days));
+        }
+
+        let v = Value::Date(parse_date("2024-01-15").unwrap());
+        assert_eq!(v.data_type(), Some(DataType::Date));
+        assert_eq!(v.to_string(), "2024-01-15");
+        assert_eq!(
+            Value::String("2024-01-15".to_string())
+                .cast_to(DataType::Date)
+                .unwrap(),
+            v
+        );
+        assert!(Value::String("Jan 15".to_string())
+            .cast_to(DataType::Date)
+            .is_err());
+        assert!(v.cast_to(DataType::Int64).is_err());
+        assert_eq!(Value::from_str("2024-01-15").unwrap(), v);
+        assert_eq!("DATE".parse::<DataType>().unwrap(), DataType::Date);
+    }
+
     // ============================================================================
     // TYPE INFERENCE TESTS
     /// }
@@ -0,0 +1,207 @@
+//! # Virtual Table Module
+//!
+//! This module provides the VirtualTable structure, which exposes rows produced
+//! by Rust code (an in-memory `Vec` of structs, a metrics registry, ...) as a
+//! queryable table without copying them into Column storage up front.
+//!
+//! A virtual table is a schema plus a factory closure. Every scan calls the
+//! factory to get a fresh row iterator, so each query sees a new snapshot of
+//! the underlying data. Because rows are only produced while scanning, the row
+//! count of a virtual table is unknown at planning time.
+
+use crate::error::{DatabaseError, Result};
+use crate::types::{DataType, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+/// Iterator over the rows of one snapshot of a virtual table.
+///
+/// Each row holds one value per column, in schema order.
+pub type RowIterator = Box<dyn Iterator<Item = Vec<Value>>>;
+
+/// Factory called once per scan to produce a fresh snapshot of the rows.
+pub type RowFactory = Arc<dyn Fn() -> RowIterator + Send + Sync>;
+
+/// A table whose rows are produced on demand by a Rust closure.
+///
+/// Queries over a virtual table are planned like any other table; the scan
+/// pulls rows from the factory's iterator and assembles batches lazily, so
+/// filters, projections, aggregates, and sorts all compose on top of it.
+///
+/// The number of rows is not known until the iterator is exhausted, so
+/// optimizations that rely on `Table::row_count` do not apply.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_rust_olap::catalog::Catalog;
+/// use mini_rust_olap::types::{DataType, Value};
+/// use mini_rust_olap::virtual_table::VirtualTable;
+/// use std::sync::{Arc, Mutex};
+///
+/// let readings = Arc::new(Mutex::new(vec![("cpu", 0.5), ("mem", 0.75)]));
+/// let source = Arc::clone(&readings);
+///
+/// let table = VirtualTable::new(
+///     "metrics".to_string(),
+///     vec![
+///         ("name".to_string(), DataType::String),
+///         ("value".to_string(), DataType::Float64),
+///     ],
+///     move || {
+///         let rows: Vec<Vec<Value>> = source
+///             .lock()
+///             .unwrap()
+///             .iter()
+///             .map(|(name, value)| {
+///                 vec![Value::String(name.to_string()), Value::Float64(*value)]
+///             })
+///             .collect();
+///         Box::new(rows.into_iter())
+///     },
+/// )
+/// .unwrap();
+///
+/// let mut catalog = Catalog::new();
+/// catalog.register_virtual_table(table).unwrap();
+/// assert!(catalog.virtual_table_exists("metrics"));
+/// ```
+#[derive(Clone)]
+pub struct VirtualTable {
+    /// The name of this table
+    name: String,
+
+    /// Column names and types, in output order
+    columns: Vec<(String, DataType)>,
+
+    /// Produces a fresh row iterator for each scan
+    factory: RowFactory,
+}
+
+impl VirtualTable {
+    /// Creates a new virtual table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The table name
+    /// * `columns` - Column names and types, in the order rows provide values
+    /// * `factory` - Closure returning a fresh iterator over the current rows
+    ///
+    /// # Returns
+    ///
+    /// The virtual table, or an error if the column list is empty or has
+    /// duplicate names
+    pub fn new<F>(name: String, columns: Vec<(String, DataType)>, factory: F) -> Result<Self>
+    where
+        F: Fn() -> RowIterator + Send + Sync + 'static,
+    {
+        if columns.is_empty() {
+            return Err(DatabaseError::table_error(format!(
+                "Virtual table '{}' must have at least one column",
+                name
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        for (column_name, _) in &columns {
+            if !seen.insert(column_name.as_str()) {
+                return Err(DatabaseError::column_error(format!(
+                    "Column '{}' appears more than once in virtual table '{}'",
+                    column_name, name
+                )));
+            }
+        }
+
+        Ok(Self {
+            name,
+            columns,
+            factory: Arc::new(factory),
+        })
+    }
+
+    /// Returns the name of the table.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the column names and types in output order.
+    pub fn columns(&self) -> &[(String, DataType)] {
+        &self.columns
+    }
+
+    /// Returns the column names in output order.
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Returns the number of columns.
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the schema as a map from column name to type.
+    pub fn schema(&self) -> HashMap<String, DataType> {
+        self.columns.iter().cloned().collect()
+    }
+
+    /// Calls the factory to start a new snapshot of the rows.
+    pub fn rows(&self) -> RowIterator {
+        (self.factory)()
+    }
+}
+
+impl fmt::Debug for VirtualTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VirtualTable")
+            .field("name", &self.name)
+            .field("columns", &self.columns)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers_table() -> VirtualTable {
+        VirtualTable::new(
+            "numbers".to_string(),
+            vec![("n".to_string(), DataType::Int64)],
+            || Box::new((1..=3).map(|n| vec![Value::Int64(n)])),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_virtual_table_metadata() {
+        let table = numbers_table();
+        assert_eq!(table.name(), "numbers");
+        assert_eq!(table.column_names(), vec!["n".to_string()]);
+        assert_eq!(table.column_count(), 1);
+        assert_eq!(table.schema().get("n"), Some(&DataType::Int64));
+    }
+
+    #[test]
+    fn test_virtual_table_rows_are_fresh_per_call() {
+        let table = numbers_table();
+        assert_eq!(table.rows().count(), 3);
+        assert_eq!(table.rows().count(), 3);
+    }
+
+    #[test]
+    fn test_virtual_table_rejects_bad_schemas() {
+        let empty = VirtualTable::new("empty".to_string(), vec![], || Box::new(std::iter::empty()));
+        assert!(empty.is_err());
+
+        let duplicate = VirtualTable::new(
+            "dup".to_string(),
+            vec![
+                ("a".to_string(), DataType::Int64),
+                ("a".to_string(), DataType::String),
+            ],
+            || Box::new(std::iter::empty()),
+        );
+        assert!(duplicate.is_err());
+    }
+}
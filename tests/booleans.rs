@@ -0,0 +1,93 @@
+//! # Integration Tests for Boolean Columns
+//!
+//! These tests load a CSV whose `active` column holds true/false values and
+//! check that it is inferred as Boolean and can be filtered, grouped and
+//! sorted with TRUE/FALSE literals in SQL.
+
+use mini_rust_olap::{catalog::Catalog, execute_sql, types::DataType, types::Value};
+
+mod common;
+use common::catalog_from_csv;
+
+/// Five accounts; Erin's `active` field is missing.
+const CSV: &str = "\
+name,active,balance
+Alice,true,100
+Bob,FALSE,250
+Carol,True,75
+Dave,false,10
+Erin,,40
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn catalog_with_accounts() -> Catalog {
+    catalog_from_csv(&[("accounts", CSV)])
+}
+
+/// Runs a query and returns its rows.
+fn query(catalog: &Catalog, sql: &str) -> Vec<Vec<Value>> {
+    let (_, batches) = execute_sql(catalog, sql).unwrap();
+    let mut rows = Vec::new();
+    for batch in &batches {
+        for row in 0..batch.row_count() {
+            rows.push(
+                (0..batch.column_count())
+                    .map(|col| batch.get(row, col).unwrap())
+                    .collect(),
+            );
+        }
+    }
+    rows
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_boolean_column_is_inferred() {
+    let catalog = catalog_with_accounts();
+    let table = catalog.get_table("accounts").unwrap();
+    assert_eq!(table.get_column_type("active").unwrap(), DataType::Boolean);
+}
+
+#[test]
+fn test_count_where_boolean_literal() {
+    let catalog = catalog_with_accounts();
+
+    assert_eq!(
+        query(
+            &catalog,
+            "SELECT COUNT(*) FROM accounts WHERE active = TRUE"
+        ),
+        vec![vec![Value::Int64(2)]]
+    );
+    assert_eq!(
+        query(
+            &catalog,
+            "SELECT COUNT(*) FROM accounts WHERE active != true"
+        ),
+        vec![vec![Value::Int64(2)]]
+    );
+}
+
+#[test]
+fn test_group_and_order_by_boolean() {
+    let catalog = catalog_with_accounts();
+
+    let rows = query(
+        &catalog,
+        "SELECT active, SUM(balance) FROM accounts WHERE active = TRUE OR active = FALSE \
+         GROUP BY active ORDER BY active",
+    );
+    assert_eq!(
+        rows,
+        vec![
+            vec![Value::Boolean(false), Value::Int64(260)],
+            vec![Value::Boolean(true), Value::Int64(175)],
+        ]
+    );
+}
@@ -0,0 +1,242 @@
+//! # Integration Tests for CASE Expressions
+//!
+//! These tests bucket a small users table with `CASE WHEN ... END`, in the
+//! SELECT list and as a GROUP BY key given by position or written out,
+//! and check the branch order, the NULL of a CASE without ELSE, the
+//! Int64-to-Float64 widening of mixed results and the error for results
+//! with no common type.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    types::{DataType, Value},
+};
+
+mod common;
+use common::{catalog_from_csv, text};
+
+const USERS: &str = "\
+id,name,age,score
+1,Alice,25,88.5
+2,Bob,35,72.0
+3,Carol,42,
+4,Dave,58,91.25
+5,Eve,19,64.0
+6,Frank,47,80.0
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn users_catalog() -> Catalog {
+    catalog_from_csv(&[("users", USERS)])
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_case_buckets_grouped_by_position() {
+    let catalog = users_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT CASE WHEN age < 30 THEN 'young' WHEN age < 50 THEN 'mid' ELSE 'old' END, COUNT(*) \
+         FROM users GROUP BY 1",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column_names(),
+        [
+            "CASE WHEN age < 30 THEN 'young' WHEN age < 50 THEN 'mid' ELSE 'old' END",
+            "COUNT(*)"
+        ]
+    );
+    assert_eq!(result.column_types(), [DataType::String, DataType::Int64]);
+    let mut rows: Vec<Vec<Value>> = result.rows().collect();
+    rows.sort_by_key(|row| row[0].to_string());
+    assert_eq!(
+        rows,
+        vec![
+            vec![text("mid"), Value::Int64(3)],
+            vec![text("old"), Value::Int64(1)],
+            vec![text("young"), Value::Int64(2)],
+        ]
+    );
+
+    // Groups sort by the bucket through its alias
+    let result = execute_query(
+        &catalog,
+        "SELECT CASE WHEN age < 30 THEN 'young' WHEN age < 50 THEN 'mid' ELSE 'old' END AS bucket, \
+         COUNT(*) AS n FROM users GROUP BY 1 ORDER BY bucket",
+    )
+    .unwrap();
+    assert_eq!(result.rows().collect::<Vec<_>>(), rows);
+}
+
+#[test]
+fn test_case_grouped_by_expression_and_alias() {
+    let catalog = users_catalog();
+
+    // Writing the expression out in GROUP BY matches the SELECT item, and
+    // ORDER BY may use the item's alias
+    let result = execute_query(
+        &catalog,
+        "SELECT CASE WHEN age >= 40 THEN 'over 40' ELSE 'under 40' END AS band, AVG(score) \
+         FROM users GROUP BY CASE WHEN age >= 40 THEN 'over 40' ELSE 'under 40' END \
+         ORDER BY band DESC",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["band", "AVG_score"]);
+    assert_eq!(
+        result.rows().collect::<Vec<_>>(),
+        vec![
+            vec![text("under 40"), Value::Float64((88.5 + 72.0 + 64.0) / 3.0)],
+            vec![text("over 40"), Value::Float64((91.25 + 80.0) / 2.0)],
+        ]
+    );
+
+    // A plain column and a position can be mixed
+    let result = execute_query(
+        &catalog,
+        "SELECT name, CASE WHEN score IS NULL THEN 'missing' ELSE 'scored' END AS status, COUNT(*) \
+         FROM users GROUP BY name, 2 ORDER BY name LIMIT 3",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column("status").unwrap(),
+        vec![text("scored"), text("scored"), text("missing")]
+    );
+}
+
+#[test]
+fn test_case_without_else_is_null() {
+    let catalog = users_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT id, CASE WHEN age > 40 THEN name END AS senior FROM users",
+    )
+    .unwrap();
+    assert_eq!(result.column_types()[1], DataType::String);
+    assert_eq!(
+        result.column("senior").unwrap(),
+        vec![
+            Value::Null,
+            Value::Null,
+            text("Carol"),
+            text("Dave"),
+            Value::Null,
+            text("Frank")
+        ]
+    );
+}
+
+#[test]
+fn test_first_matching_branch_wins() {
+    let catalog = users_catalog();
+
+    // Every row over 20 matches the second branch too, but takes the first
+    // that holds; a NULL score never satisfies a comparison
+    let result = execute_query(
+        &catalog,
+        "SELECT CASE WHEN score > 85 THEN 'top' WHEN age > 20 THEN 'adult' WHEN age > 10 THEN 'teen' END AS label \
+         FROM users",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column("label").unwrap(),
+        vec![
+            text("top"),
+            text("adult"),
+            text("adult"),
+            text("top"),
+            text("teen"),
+            text("adult")
+        ]
+    );
+}
+
+#[test]
+fn test_mixed_numeric_results_widen_to_float() {
+    let catalog = users_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT CASE WHEN score IS NULL THEN 0 ELSE score END AS filled FROM users WHERE id < 4",
+    )
+    .unwrap();
+    assert_eq!(result.column_types(), [DataType::Float64]);
+    assert_eq!(
+        result.column("filled").unwrap(),
+        vec![
+            Value::Float64(88.5),
+            Value::Float64(72.0),
+            Value::Float64(0.0)
+        ]
+    );
+}
+
+#[test]
+fn test_case_errors() {
+    let catalog = users_catalog();
+
+    let err = execute_query(
+        &catalog,
+        "SELECT CASE WHEN age < 30 THEN 'young' ELSE age END FROM users",
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("CASE branches return String and Int64, which have no common type"),
+        "{}",
+        err
+    );
+
+    let err = execute_query(
+        &catalog,
+        "SELECT CASE WHEN age < 30 THEN 'young' END, COUNT(*) FROM users GROUP BY 3",
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("GROUP BY position 3 is not in the SELECT list; positions run from 1 to 2"),
+        "{}",
+        err
+    );
+
+    let err = execute_query(
+        &catalog,
+        "SELECT CASE WHEN age < 30 THEN 'young' END, COUNT(*) FROM users GROUP BY 2",
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("GROUP BY cannot use the aggregate 'COUNT(*)'"),
+        "{}",
+        err
+    );
+
+    // Selecting a bucket without grouping by it has no single value per group
+    let err = execute_query(
+        &catalog,
+        "SELECT CASE WHEN age < 30 THEN 'young' END, COUNT(*) FROM users",
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Expressions cannot yet be combined with GROUP BY or aggregates"),
+        "{}",
+        err
+    );
+
+    for sql in [
+        "SELECT CASE age WHEN 1 THEN 'a' END FROM users",
+        "SELECT CASE WHEN age < 30 'young' END FROM users",
+        "SELECT CASE WHEN age < 30 THEN 'young' FROM users",
+    ] {
+        assert!(execute_query(&catalog, sql).is_err(), "{}", sql);
+    }
+}
@@ -0,0 +1,120 @@
+//! # Command-Line Interface Tests
+//!
+//! These tests spawn the compiled binary with one-shot subcommands and check
+//! its stdout and exit code, using the fixture in `tests/data`.
+
+use std::process::{Command, Output};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_mini_rust_olap"))
+        .args(args)
+        .output()
+        .expect("Failed to run binary")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_query_csv_output() {
+    let load = format!("emp={}", FIXTURE);
+    let output = run(&[
+        "query",
+        "--load",
+        &load,
+        "SELECT name, age FROM emp WHERE age > 40",
+        "--format",
+        "csv",
+    ]);
+
+    assert_eq!(output.status.code(), Some(0));
+    let text = stdout(&output);
+    let mut lines = text.lines();
+    assert_eq!(lines.next(), Some("name,age"));
+    assert!(lines.all(|line| line.split(',').nth(1).unwrap().parse::<i64>().unwrap() > 40));
+}
+
+#[test]
+fn test_describe_csv() {
+    let output = run(&["describe", FIXTURE]);
+
+    assert_eq!(output.status.code(), Some(0));
+    let text = stdout(&output);
+    assert!(text.starts_with("id\tInt64\nname\tString\nage\tInt64\nsalary\tFloat64\n"));
+}
+
+#[test]
+fn test_convert_then_query_binary_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let olap_path = dir.path().join("emp.olap");
+    let olap_path = olap_path.to_str().unwrap();
+
+    let output = run(&["convert", FIXTURE, olap_path]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let from_csv = run(&[
+        "query",
+        "--load",
+        &format!("emp={}", FIXTURE),
+        "SELECT name, salary FROM emp ORDER BY salary DESC",
+        "--format",
+        "csv",
+    ]);
+    let from_olap = run(&[
+        "query",
+        "--load",
+        &format!("emp={}", olap_path),
+        "SELECT name, salary FROM emp ORDER BY salary DESC",
+        "--format",
+        "csv",
+    ]);
+    assert_eq!(from_olap.status.code(), Some(0));
+    assert_eq!(stdout(&from_csv), stdout(&from_olap));
+}
+
+#[test]
+fn test_exit_codes() {
+    // Query errors exit with 1
+    let load = format!("emp={}", FIXTURE);
+    let output = run(&["query", "--load", &load, "SELECT nope FROM missing"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    // Missing input files are data errors, not usage errors
+    let output = run(&["describe", "/nonexistent/file.csv"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    // Usage errors exit with 2
+    let output = run(&["query"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&["frobnicate"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_replay_exit_code_reflects_mismatches() {
+    let dir = tempfile::tempdir().unwrap();
+    let recording = dir.path().join("session.replay");
+    let recording = recording.to_str().unwrap();
+    let load = format!("emp={}", FIXTURE);
+
+    // One statement whose checksum cannot match, one that will
+    std::fs::write(
+        recording,
+        "# mini_rust_olap recording v1\n\
+         10\t0000000000000000\t100\tSELECT id FROM emp\n\
+         1\t0000000000000000\t100\tSELECT COUNT(*) FROM emp WHERE id = 0\n",
+    )
+    .unwrap();
+
+    let output = run(&["replay", recording, "--load", &load]);
+    assert_eq!(output.status.code(), Some(1));
+    let text = stdout(&output);
+    assert!(text.contains("MISMATCH"));
+    assert!(text.contains("SELECT id FROM emp"));
+}
@@ -0,0 +1,42 @@
+//! # Shared Helpers for the Integration Tests
+//!
+//! Each file in `tests/` is compiled as its own crate and pulls these in
+//! with `mod common;`. Not every file uses every helper.
+
+#![allow(dead_code)]
+
+use mini_rust_olap::{catalog::Catalog, ingest::load_csv, types::Value};
+use std::fs;
+
+/// Builds a catalog with one table per `(name, csv)` pair.
+///
+/// Each CSV text is written to a temporary file and loaded with `load_csv`,
+/// so column types are inferred exactly as LOAD infers them. The tables are
+/// held in memory, so the files are removed once the catalog is built.
+pub fn catalog_from_csv(tables: &[(&str, &str)]) -> Catalog {
+    let dir = tempfile::tempdir().unwrap();
+    let mut catalog = Catalog::new();
+    for (name, csv) in tables {
+        let path = dir.path().join(format!("{}.csv", name));
+        fs::write(&path, csv).unwrap();
+        catalog
+            .register_table(load_csv(&path, name.to_string()).unwrap())
+            .unwrap();
+    }
+    catalog
+}
+
+/// A String value
+pub fn text(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+/// An Int64 value
+pub fn int(n: i64) -> Value {
+    Value::Int64(n)
+}
+
+/// String values, in order
+pub fn strings(values: &[&str]) -> Vec<Value> {
+    values.iter().map(|value| text(value)).collect()
+}
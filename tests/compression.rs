@@ -0,0 +1,164 @@
+//! # Integration Tests for Column Compression
+//!
+//! These tests load the same CSV into two catalogs, compress the tables of
+//! one with `Table::compress`, and check that filters, aggregations and
+//! sorts return exactly the same results from both, and that a compressed
+//! table still accepts new rows.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    column::{DeltaColumn, Encoding, RleColumn},
+    execute_query,
+    types::Value,
+};
+
+mod common;
+use common::catalog_from_csv;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Events sorted by region, with increasing ids and timestamps, a
+/// low-cardinality status in long runs, scattered amounts and some NULLs.
+fn events_csv() -> String {
+    let regions = ["east", "north", "south", "west"];
+    let mut csv = String::from("id,ts,region,status,amount,score\n");
+    for row in 0..6_000i64 {
+        let region = regions[(row / 1_500) as usize];
+        let status = if row % 1_000 < 700 { "done" } else { "open" };
+        let amount = (row * 7_919 % 10_007) as f64 / 100.0;
+        let score = if row % 13 == 0 {
+            String::new()
+        } else {
+            (row % 50).to_string()
+        };
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            row + 1,
+            1_700_000_000 + row * 60 + row % 7,
+            region,
+            status,
+            amount,
+            score
+        ));
+    }
+    csv
+}
+
+fn catalog_pair() -> (Catalog, Catalog) {
+    let csv = events_csv();
+    (
+        catalog_from_csv(&[("events", &csv)]),
+        catalog_from_csv(&[("events", &csv)]),
+    )
+}
+
+fn assert_same_results(plain: &Catalog, compressed: &Catalog, sql: &str) {
+    let expected = execute_query(plain, sql).unwrap();
+    let actual = execute_query(compressed, sql).unwrap();
+    assert_eq!(actual.column_names(), expected.column_names(), "{}", sql);
+    assert_eq!(actual.column_types(), expected.column_types(), "{}", sql);
+    assert_eq!(
+        actual.rows().collect::<Vec<_>>(),
+        expected.rows().collect::<Vec<_>>(),
+        "{}",
+        sql
+    );
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_compress_chooses_encodings_by_column() {
+    let (_, mut compressed) = catalog_pair();
+    let table = compressed.get_table_mut("events").unwrap();
+
+    let changes = table.compress().unwrap();
+    let encoding = |name: &str| {
+        changes
+            .iter()
+            .find(|change| change.column == name)
+            .map(|change| change.encoding)
+    };
+    assert_eq!(encoding("id"), Some(Encoding::Delta));
+    assert_eq!(encoding("ts"), Some(Encoding::Delta));
+    assert_eq!(encoding("region"), Some(Encoding::RunLength));
+    assert_eq!(encoding("amount"), None);
+    for change in &changes {
+        assert!(
+            change.bytes_after * 4 <= change.bytes_before * 3,
+            "{:?}",
+            change
+        );
+    }
+
+    let column = table.get_column("ts").unwrap();
+    assert!(column.as_any().is::<DeltaColumn>());
+    let column = table.get_column("region").unwrap();
+    assert!(column.as_any().is::<RleColumn>());
+}
+
+#[test]
+fn test_queries_over_compressed_tables_match() {
+    let (plain, mut compressed) = catalog_pair();
+    let changes = compressed
+        .get_table_mut("events")
+        .unwrap()
+        .compress()
+        .unwrap();
+    assert!(changes.len() >= 3, "{:?}", changes);
+
+    for sql in [
+        // Filters, including ones pushed into the scan
+        "SELECT * FROM events",
+        "SELECT id, region FROM events WHERE id > 2990 AND id <= 3010",
+        "SELECT id FROM events WHERE ts >= 1700180000 AND region = 'west'",
+        "SELECT id, score FROM events WHERE score IS NULL OR status = 'open'",
+        "SELECT id FROM events WHERE region IN ('north', 'south') AND amount < 5",
+        // Aggregations
+        "SELECT region, COUNT(*), MIN(ts), MAX(id), AVG(score) FROM events GROUP BY region ORDER BY region",
+        "SELECT status, region, SUM(amount), COUNT(score) FROM events GROUP BY status, region ORDER BY status, region",
+        "SELECT COUNT(DISTINCT region), SUM(id), MAX(score) FROM events",
+        // Sorts
+        "SELECT id, amount FROM events ORDER BY amount DESC, id LIMIT 25",
+        "SELECT id, region FROM events ORDER BY region DESC, ts DESC",
+        "SELECT ts FROM events ORDER BY score, id LIMIT 100 OFFSET 40",
+    ] {
+        assert_same_results(&plain, &compressed, sql);
+    }
+}
+
+#[test]
+fn test_compressed_table_accepts_new_rows() {
+    let (_, mut compressed) = catalog_pair();
+    let table = compressed.get_table_mut("events").unwrap();
+    table.compress().unwrap();
+
+    table
+        .insert_rows(vec![vec![
+            Value::Int64(6_001),
+            Value::Int64(1_700_360_001),
+            Value::String("west".to_string()),
+            Value::String("done".to_string()),
+            Value::Float64(1.5),
+            Value::Null,
+        ]])
+        .unwrap();
+
+    let result = execute_query(
+        &compressed,
+        "SELECT COUNT(*), MAX(id), MAX(ts) FROM events WHERE region = 'west'",
+    )
+    .unwrap();
+    assert_eq!(
+        result.rows().next().unwrap(),
+        vec![
+            Value::Int64(1_501),
+            Value::Int64(6_001),
+            Value::Int64(1_700_360_001)
+        ]
+    );
+}
@@ -0,0 +1,154 @@
+//! # Integration Tests for CREATE TABLE ... AS SELECT
+//!
+//! These tests materialize query results into new tables and query them
+//! again, checking that the new table takes its column names (aliases
+//! included) and types from the query, keeps NULLs and empty results, and
+//! that an existing name is rejected before the query runs.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_create_table_as, execute_query,
+    parser::{CreateTableAsStatement, Parser, Query},
+    types::{DataType, Value},
+    DatabaseError,
+};
+
+mod common;
+use common::catalog_from_csv;
+
+const EMPLOYEES: &str = "\
+name,department,salary,bonus
+Alice,eng,120.0,10
+Bob,eng,100.0,
+Carol,sales,80.0,5
+Dave,sales,70.0,
+Eve,ops,60.0,3
+Frank,eng,110.0,8
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn employees_catalog() -> Catalog {
+    catalog_from_csv(&[("employees", EMPLOYEES)])
+}
+
+fn parse_ctas(sql: &str) -> CreateTableAsStatement {
+    let Ok(Query::CreateTableAs(stmt)) = Parser::new(sql).parse() else {
+        panic!("expected a CREATE TABLE AS: {}", sql);
+    };
+    stmt
+}
+
+fn create(catalog: &mut Catalog, sql: &str) -> Result<usize, DatabaseError> {
+    execute_create_table_as(catalog, &parse_ctas(sql))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_materialized_aggregate_can_be_aggregated_again() {
+    let mut catalog = employees_catalog();
+
+    let rows = create(
+        &mut catalog,
+        "CREATE TABLE dept_summary AS \
+         SELECT department, AVG(salary) AS avg_salary, COUNT(*) AS headcount \
+         FROM employees GROUP BY department",
+    )
+    .unwrap();
+    assert_eq!(rows, 3);
+
+    let table = catalog.get_table("dept_summary").unwrap();
+    assert_eq!(
+        table.column_names(),
+        vec!["department", "avg_salary", "headcount"]
+    );
+
+    // A second aggregation over the materialized table
+    let result = execute_query(
+        &catalog,
+        "SELECT COUNT(*), SUM(headcount), MAX(avg_salary) FROM dept_summary WHERE headcount > 1",
+    )
+    .unwrap();
+    assert_eq!(
+        result.rows().collect::<Vec<_>>(),
+        vec![vec![
+            Value::Int64(2),
+            Value::Int64(5),
+            Value::Float64(110.0)
+        ]]
+    );
+
+    let result = execute_query(
+        &catalog,
+        "SELECT department FROM dept_summary ORDER BY avg_salary DESC",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column("department").unwrap(),
+        ["eng", "sales", "ops"]
+            .map(|name| Value::String(name.to_string()))
+            .to_vec()
+    );
+}
+
+#[test]
+fn test_new_table_keeps_types_and_nulls() {
+    let mut catalog = employees_catalog();
+
+    create(
+        &mut catalog,
+        "CREATE TABLE eng AS SELECT name, salary * 2 AS doubled, bonus \
+         FROM employees WHERE department = 'eng' ORDER BY name",
+    )
+    .unwrap();
+
+    let result = execute_query(&catalog, "SELECT * FROM eng").unwrap();
+    assert_eq!(result.column_names(), ["name", "doubled", "bonus"]);
+    assert_eq!(
+        result.column_types(),
+        [DataType::String, DataType::Float64, DataType::Int64]
+    );
+    assert_eq!(
+        result.column("bonus").unwrap(),
+        vec![Value::Int64(10), Value::Null, Value::Int64(8)]
+    );
+
+    // An empty result still gives a table with the query's columns
+    assert_eq!(
+        create(
+            &mut catalog,
+            "CREATE TABLE nobody AS SELECT name, salary FROM employees WHERE salary > 1000",
+        )
+        .unwrap(),
+        0
+    );
+    let result = execute_query(&catalog, "SELECT * FROM nobody").unwrap();
+    assert_eq!(result.column_types(), [DataType::String, DataType::Float64]);
+    assert_eq!(result.row_count(), 0);
+}
+
+#[test]
+fn test_existing_name_and_bad_queries_are_errors() {
+    let mut catalog = employees_catalog();
+
+    let err = create(
+        &mut catalog,
+        "CREATE TABLE employees AS SELECT name FROM employees",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("already exists"), "{}", err);
+    assert_eq!(catalog.get_table("employees").unwrap().row_count(), 6);
+
+    // A query that fails registers nothing
+    assert!(create(
+        &mut catalog,
+        "CREATE TABLE broken AS SELECT height FROM employees"
+    )
+    .is_err());
+    assert!(!catalog.table_exists("broken"));
+}
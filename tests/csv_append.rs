@@ -0,0 +1,143 @@
+//! # Integration Tests for Incremental CSV Loads
+//!
+//! These tests load one CSV file as a table, append further files to it with
+//! `load_csv_append`, and check that queries see the rows of every file.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    ingest::{load_csv_append, load_csv_into_catalog},
+    planner::execute_create_table,
+    types::Value,
+    Parser, Query,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Writes a daily sales extract of `rows` rows, numbering ids from `first_id`.
+fn write_extract(dir: &Path, file: &str, first_id: i64, rows: i64) -> PathBuf {
+    let mut csv = String::from("id,region,amount\n");
+    for id in first_id..first_id + rows {
+        let region = ["north", "south", "east"][(id % 3) as usize];
+        csv.push_str(&format!("{},{},{}.5\n", id, region, id));
+    }
+    let path = dir.join(file);
+    fs::write(&path, csv).unwrap();
+    path
+}
+
+fn single_value(catalog: &Catalog, sql: &str) -> Value {
+    execute_query(catalog, sql).unwrap().get(0, 0).unwrap()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_appends_accumulate_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut catalog = Catalog::new();
+    let Query::CreateTable(stmt) =
+        Parser::new("CREATE TABLE sales (id INT, region TEXT, amount FLOAT)")
+            .parse()
+            .unwrap()
+    else {
+        panic!("Expected CREATE TABLE");
+    };
+    execute_create_table(&mut catalog, &stmt).unwrap();
+
+    let first = write_extract(dir.path(), "day1.csv", 0, 1000);
+    let second = write_extract(dir.path(), "day2.csv", 1000, 1000);
+    assert_eq!(
+        load_csv_append(&first, "sales", &mut catalog).unwrap(),
+        1000
+    );
+    assert_eq!(
+        load_csv_append(&second, "sales", &mut catalog).unwrap(),
+        1000
+    );
+
+    assert_eq!(
+        single_value(&catalog, "SELECT COUNT(*) FROM sales"),
+        Value::Int64(2000)
+    );
+    assert_eq!(
+        single_value(&catalog, "SELECT MAX(id) FROM sales"),
+        Value::Int64(1999)
+    );
+    // Sum of id + 0.5 over ids 0..2000
+    assert_eq!(
+        single_value(&catalog, "SELECT SUM(amount) FROM sales"),
+        Value::Float64(1999.0 * 2000.0 / 2.0 + 1000.0)
+    );
+}
+
+#[test]
+fn test_append_matches_columns_by_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut catalog = Catalog::new();
+    load_csv_into_catalog(
+        write_extract(dir.path(), "day1.csv", 0, 3),
+        "sales".to_string(),
+        &mut catalog,
+    )
+    .unwrap();
+
+    let reordered = dir.path().join("day2.csv");
+    fs::write(&reordered, "amount,id,region\n7.25,3,west\n8.5,4,west\n").unwrap();
+    assert_eq!(
+        load_csv_append(&reordered, "SALES", &mut catalog).unwrap(),
+        2
+    );
+
+    let result = execute_query(
+        &catalog,
+        "SELECT id, region, amount FROM sales WHERE id > 2",
+    )
+    .unwrap();
+    let rows: Vec<Vec<Value>> = result.rows().collect();
+    assert_eq!(
+        rows,
+        vec![
+            vec![
+                Value::Int64(3),
+                Value::String("west".to_string()),
+                Value::Float64(7.25)
+            ],
+            vec![
+                Value::Int64(4),
+                Value::String("west".to_string()),
+                Value::Float64(8.5)
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_schema_mismatch_loads_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut catalog = Catalog::new();
+    load_csv_into_catalog(
+        write_extract(dir.path(), "day1.csv", 0, 3),
+        "sales".to_string(),
+        &mut catalog,
+    )
+    .unwrap();
+
+    let changed = dir.path().join("day2.csv");
+    fs::write(&changed, "id,amount,channel\nx1,1.5,web\nx2,2.5,store\n").unwrap();
+    let err = load_csv_append(&changed, "sales", &mut catalog)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("column 'id' is Int64 in the table but String in the file"));
+    assert!(err.contains("column 'region' is missing from the file"));
+    assert!(err.contains("column 'channel' is not in the table"));
+    assert!(!err.contains("'amount'"));
+
+    assert_eq!(catalog.get_table("sales").unwrap().row_count(), 3);
+}
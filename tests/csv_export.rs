@@ -0,0 +1,129 @@
+//! # Integration Tests for CSV Export
+//!
+//! These tests write query results with `write_batches_csv`, load the file
+//! again with `load_csv`, and check that queries over the copy return the
+//! same rows as queries over the original.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    column::{Column, FloatColumn, IntColumn, StringColumn},
+    execute_query,
+    ingest::{load_csv, write_batches_csv},
+    table::Table,
+    types::{DataType, Value},
+};
+use std::fs;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Rows with quoting hazards, awkward floats and NULLs.
+fn tricky_table() -> Table {
+    let names = [
+        Some("plain"),
+        Some("comma, inside"),
+        Some("say \"hi\""),
+        None,
+        Some("'single'"),
+    ];
+    let prices = [Some(0.1), Some(3.0), Some(-1.5e-7), Some(f64::MAX), None];
+
+    let mut ids = IntColumn::new();
+    let mut name_col = StringColumn::new();
+    let mut price_col = FloatColumn::new();
+    for (id, (name, price)) in names.iter().zip(prices).enumerate() {
+        ids.push_value(Value::Int64(id as i64)).unwrap();
+        name_col
+            .push_value(name.map_or(Value::Null, |n| Value::String(n.to_string())))
+            .unwrap();
+        price_col
+            .push_value(price.map_or(Value::Null, Value::Float64))
+            .unwrap();
+    }
+
+    let mut table = Table::new("items".to_string());
+    table.add_column("id".to_string(), Box::new(ids)).unwrap();
+    table
+        .add_column("name".to_string(), Box::new(name_col))
+        .unwrap();
+    table
+        .add_column("price".to_string(), Box::new(price_col))
+        .unwrap();
+    table
+}
+
+fn rows(catalog: &Catalog, sql: &str) -> Vec<Vec<Value>> {
+    execute_query(catalog, sql).unwrap().rows().collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_export_and_reload_gives_identical_results() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("items.csv");
+    let mut catalog = Catalog::new();
+    catalog.register_table(tricky_table()).unwrap();
+
+    let result = execute_query(&catalog, "SELECT * FROM items").unwrap();
+    let written = write_batches_csv(result.batches(), result.column_names(), &path).unwrap();
+    assert_eq!(written, 5);
+
+    let mut reloaded = Catalog::new();
+    let copy = load_csv(&path, "items".to_string()).unwrap();
+    assert_eq!(copy.get_column_type("id").unwrap(), DataType::Int64);
+    assert_eq!(copy.get_column_type("name").unwrap(), DataType::String);
+    assert_eq!(copy.get_column_type("price").unwrap(), DataType::Float64);
+    reloaded.register_table(copy).unwrap();
+
+    for sql in [
+        "SELECT id, name, price FROM items ORDER BY id",
+        "SELECT COUNT(name), SUM(price), MAX(price) FROM items",
+        "SELECT name FROM items WHERE price > 1",
+    ] {
+        assert_eq!(rows(&reloaded, sql), rows(&catalog, sql), "{}", sql);
+    }
+}
+
+#[test]
+fn test_export_uses_output_names_and_quotes_fields() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.csv");
+    let mut catalog = Catalog::new();
+    catalog.register_table(tricky_table()).unwrap();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT name AS label, price * 2 FROM items WHERE id < 3 ORDER BY id",
+    )
+    .unwrap();
+    write_batches_csv(result.batches(), result.column_names(), &path).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "label,price * 2\n\
+         plain,0.2\n\
+         \"comma, inside\",6.0\n\
+         \"say \"\"hi\"\"\",-0.0000003\n"
+    );
+}
+
+#[test]
+fn test_export_quotes_line_breaks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.csv");
+    let mut notes = StringColumn::new();
+    notes
+        .push_value(Value::String("line one\nline two".to_string()))
+        .unwrap();
+    let batch = mini_rust_olap::execution::Batch::new(vec![std::sync::Arc::new(notes)]);
+
+    write_batches_csv(&[batch], &["note".to_string()], &path).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "note\n\"line one\nline two\"\n"
+    );
+}
@@ -0,0 +1,224 @@
+//! # Integration Tests for WITH (Common Table Expressions)
+//!
+//! These tests run queries whose FROM and JOIN clauses name CTEs, checking
+//! that later CTEs can read earlier ones, that a CTE shadows a table of the
+//! same name for one statement only, and that parameters inside a CTE body
+//! bind like any others.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    types::{DataType, Value},
+};
+
+mod common;
+use common::{catalog_from_csv, strings};
+
+const USERS: &str = "\
+id,name,age,city
+1,Alice,25,Paris
+2,Bob,45,Berlin
+3,Carol,42,Paris
+4,Dave,31,Rome
+5,Eve,58,Berlin
+6,Frank,55,Paris
+";
+
+const ORDERS: &str = "\
+order_id,user_id,amount
+100,1,20.5
+101,2,12.0
+102,2,7.25
+103,4,99.0
+104,6,15.0
+105,6,3.5
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn shop_catalog() -> Catalog {
+    catalog_from_csv(&[("users", USERS), ("orders", ORDERS)])
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_query_reads_a_cte() {
+    let catalog = shop_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "WITH olds AS (SELECT * FROM users WHERE age > 40) SELECT COUNT(*) FROM olds",
+    )
+    .unwrap();
+    assert_eq!(result.rows().next().unwrap(), vec![Value::Int64(4)]);
+
+    // A CTE can be joined with a real table
+    let result = execute_query(
+        &catalog,
+        "WITH big AS (SELECT user_id, amount FROM orders WHERE amount > 10) \
+         SELECT users.name, big.amount FROM users JOIN big ON users.id = big.user_id \
+         ORDER BY big.amount",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["users.name", "big.amount"]);
+    assert_eq!(
+        result.column("users.name").unwrap(),
+        strings(&["Bob", "Frank", "Alice", "Dave"])
+    );
+}
+
+#[test]
+fn test_later_ctes_read_earlier_ones() {
+    let catalog = shop_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "WITH olds AS (SELECT name, city FROM users WHERE age > 40), \
+         per_city AS (SELECT city, COUNT(*) AS people FROM olds GROUP BY city) \
+         SELECT city FROM per_city WHERE people > 1 ORDER BY city",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["city"]);
+    assert_eq!(
+        result.column("city").unwrap(),
+        strings(&["Berlin", "Paris"])
+    );
+}
+
+#[test]
+fn test_cte_shadows_a_table_for_one_statement() {
+    let catalog = shop_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "WITH users AS (SELECT name FROM users WHERE city = 'Rome') SELECT * FROM users",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["name"]);
+    assert_eq!(result.column("name").unwrap(), strings(&["Dave"]));
+
+    // The catalog is untouched afterwards
+    assert_eq!(catalog.get_table("users").unwrap().row_count(), 6);
+    assert!(!catalog.table_exists("olds"));
+    execute_query(
+        &catalog,
+        "WITH olds AS (SELECT * FROM users) SELECT id FROM olds",
+    )
+    .unwrap();
+    assert!(!catalog.table_exists("olds"));
+}
+
+#[test]
+fn test_unknown_names_are_errors() {
+    let catalog = shop_catalog();
+
+    // A CTE cannot read one defined after it
+    let err = execute_query(
+        &catalog,
+        "WITH a AS (SELECT * FROM b), b AS (SELECT * FROM users) SELECT * FROM a",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("'b'"), "{}", err);
+
+    let err = execute_query(
+        &catalog,
+        "WITH a AS (SELECT height FROM users) SELECT * FROM a",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("height"), "{}", err);
+}
+
+#[test]
+fn test_prepared_statement_with_parameters_in_a_cte() {
+    let catalog = shop_catalog();
+
+    let statement = catalog
+        .prepare(
+            "WITH olds AS (SELECT name, age FROM users WHERE age > ?) \
+             SELECT name FROM olds WHERE age < ? ORDER BY name",
+        )
+        .unwrap();
+    assert_eq!(statement.parameter_count(), 2);
+    // The first parameter is compared with users.age; the second reads the
+    // CTE, whose columns are not known until it runs
+    assert_eq!(
+        statement.parameter_types(),
+        vec![Some(DataType::Int64), None]
+    );
+
+    let result = statement
+        .bind(&[Value::Int64(40), Value::Int64(50)])
+        .unwrap()
+        .execute(&catalog)
+        .unwrap();
+    assert_eq!(result.column("name").unwrap(), strings(&["Bob", "Carol"]));
+
+    assert!(statement
+        .bind(&[Value::String("forty".to_string()), Value::Int64(50)])
+        .is_err());
+}
+
+#[test]
+fn test_where_on_aliased_cte_columns() {
+    let catalog = shop_catalog();
+
+    // The main query's WHERE reads the CTE's aliases, while the CTE itself
+    // filters on a column it does not return
+    let result = execute_query(
+        &catalog,
+        "WITH people AS (SELECT name AS who, age AS years FROM users WHERE city = 'Paris') \
+         SELECT who FROM people WHERE years > 30 ORDER BY who",
+    )
+    .unwrap();
+    assert_eq!(result.column("who").unwrap(), strings(&["Carol", "Frank"]));
+
+    // Aliases that swap column names
+    let result = execute_query(
+        &catalog,
+        "WITH swapped AS (SELECT name AS city, city AS name FROM users) \
+         SELECT city FROM swapped WHERE name = 'Berlin' ORDER BY city",
+    )
+    .unwrap();
+    assert_eq!(result.column("city").unwrap(), strings(&["Bob", "Eve"]));
+
+    // The same results when the condition is grouped over afterwards
+    let result = execute_query(
+        &catalog,
+        "WITH people AS (SELECT city AS town, age FROM users) \
+         SELECT town, COUNT(*) FROM people WHERE age >= 42 GROUP BY town ORDER BY town",
+    )
+    .unwrap();
+    assert_eq!(
+        result.rows().collect::<Vec<_>>(),
+        vec![
+            vec![Value::String("Berlin".to_string()), Value::Int64(2)],
+            vec![Value::String("Paris".to_string()), Value::Int64(2)],
+        ]
+    );
+}
+
+#[test]
+fn test_where_on_column_the_cte_dropped_is_a_planning_error() {
+    let catalog = shop_catalog();
+
+    // `age` exists in users, but the CTE does not return it
+    let err = execute_query(
+        &catalog,
+        "WITH people AS (SELECT name AS who FROM users) SELECT who FROM people WHERE age > 30",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("'age'"), "{}", err);
+
+    // Nor can the original name of an aliased column be used
+    let err = execute_query(
+        &catalog,
+        "WITH people AS (SELECT name AS who FROM users) SELECT who FROM people WHERE name = 'Bob'",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("'name'"), "{}", err);
+}
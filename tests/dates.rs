@@ -0,0 +1,156 @@
+//! # Integration Tests for Date Columns
+//!
+//! These tests load a CSV whose `order_date` column holds ISO dates and
+//! check that it is inferred as Date and can be filtered with date literals,
+//! aggregated with MIN/MAX, grouped and sorted.
+
+use mini_rust_olap::{
+    catalog::Catalog, execute_create_table, execute_insert, execute_sql, types::DataType,
+    types::Value, Parser, Query,
+};
+
+mod common;
+use common::catalog_from_csv;
+
+/// Six orders; the last one has no date yet.
+const CSV: &str = "\
+id,order_date,amount
+1,2023-12-30,100
+2,2024-01-01,250
+3,2024-02-29,75
+4,2023-12-30,10
+5,2024-03-15,40
+6,,5
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn catalog_with_orders() -> Catalog {
+    catalog_from_csv(&[("orders", CSV)])
+}
+
+/// Runs a query and returns its rows rendered as strings.
+fn query(catalog: &Catalog, sql: &str) -> Vec<Vec<String>> {
+    let (_, batches) = execute_sql(catalog, sql).unwrap();
+    let mut rows = Vec::new();
+    for batch in &batches {
+        for row in 0..batch.row_count() {
+            rows.push(
+                (0..batch.column_count())
+                    .map(|col| batch.get(row, col).unwrap().to_string())
+                    .collect(),
+            );
+        }
+    }
+    rows
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_date_column_is_inferred() {
+    let catalog = catalog_with_orders();
+    let table = catalog.get_table("orders").unwrap();
+    assert_eq!(table.get_column_type("order_date").unwrap(), DataType::Date);
+    assert!(matches!(
+        table.get_value("order_date", 0).unwrap(),
+        Value::Date(_)
+    ));
+}
+
+#[test]
+fn test_where_date_literal() {
+    let catalog = catalog_with_orders();
+
+    assert_eq!(
+        query(
+            &catalog,
+            "SELECT id FROM orders WHERE order_date >= '2024-01-01' ORDER BY id"
+        ),
+        vec![vec!["2"], vec!["3"], vec!["5"]]
+    );
+    assert_eq!(
+        query(
+            &catalog,
+            "SELECT COUNT(*) FROM orders WHERE order_date = '2023-12-30'"
+        ),
+        vec![vec!["2"]]
+    );
+
+    let err = execute_sql(&catalog, "SELECT id FROM orders WHERE order_date > 'soon'")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("YYYY-MM-DD"), "{}", err);
+}
+
+#[test]
+fn test_min_max_dates() {
+    let catalog = catalog_with_orders();
+
+    assert_eq!(
+        query(
+            &catalog,
+            "SELECT MIN(order_date), MAX(order_date) FROM orders"
+        ),
+        vec![vec!["2023-12-30", "2024-03-15"]]
+    );
+    assert!(execute_sql(&catalog, "SELECT SUM(order_date) FROM orders").is_err());
+}
+
+#[test]
+fn test_group_and_order_by_date() {
+    let catalog = catalog_with_orders();
+
+    assert_eq!(
+        query(
+            &catalog,
+            "SELECT order_date, SUM(amount) FROM orders \
+             WHERE order_date < '2024-03-01' GROUP BY order_date ORDER BY order_date"
+        ),
+        vec![
+            vec!["2023-12-30", "110"],
+            vec!["2024-01-01", "250"],
+            vec!["2024-02-29", "75"],
+        ]
+    );
+    assert_eq!(
+        query(
+            &catalog,
+            "SELECT id, order_date FROM orders ORDER BY order_date DESC LIMIT 2"
+        ),
+        vec![vec!["5", "2024-03-15"], vec!["3", "2024-02-29"]]
+    );
+}
+
+#[test]
+fn test_insert_into_date_column() {
+    let mut catalog = Catalog::new();
+    let Ok(Query::CreateTable(create)) =
+        Parser::new("CREATE TABLE events (id INT, happened DATE)").parse()
+    else {
+        panic!("expected CREATE TABLE");
+    };
+    execute_create_table(&mut catalog, &create).unwrap();
+
+    let insert = |catalog: &mut Catalog, sql: &str| {
+        let Ok(Query::Insert(stmt)) = Parser::new(sql).parse() else {
+            panic!("expected INSERT");
+        };
+        execute_insert(catalog, &stmt)
+    };
+    insert(
+        &mut catalog,
+        "INSERT INTO events VALUES (1, '2024-05-01'), (2, '2024-05-02')",
+    )
+    .unwrap();
+    assert!(insert(&mut catalog, "INSERT INTO events VALUES (3, '05/01/2024')").is_err());
+
+    assert_eq!(
+        query(&catalog, "SELECT happened FROM events WHERE id = 1"),
+        vec![vec!["2024-05-01"]]
+    );
+}
@@ -0,0 +1,248 @@
+//! # Integration Tests for Derived Tables (Subqueries in FROM)
+//!
+//! These tests run queries reading `FROM (SELECT ...) alias`, checking that
+//! the outer query sees the inner query's columns under their aliases, that
+//! WHERE, GROUP BY and ORDER BY work at either level, and that a column the
+//! inner query does not produce is reported by name.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    execution::CancellationToken,
+    explain_analyze,
+    types::{DataType, Value},
+    PlannerOptions,
+};
+
+mod common;
+use common::{catalog_from_csv, strings};
+
+const EMPLOYEES: &str = "\
+id,name,department,salary
+1,Alice,Engineering,85000
+2,Bob,Sales,48000
+3,Carol,Engineering,95000
+4,Dave,Sales,62000
+5,Eve,Marketing,51000
+6,Frank,Engineering,45000
+7,Grace,Sales,70000
+";
+
+const DEPARTMENTS: &str = "\
+name,floor
+Engineering,3
+Sales,1
+Marketing,2
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn company_catalog() -> Catalog {
+    catalog_from_csv(&[("employees", EMPLOYEES), ("departments", DEPARTMENTS)])
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_select_from_derived_table() {
+    let catalog = company_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT * FROM (SELECT name, salary FROM employees WHERE salary > 60000)",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["name", "salary"]);
+    assert_eq!(
+        result.column("name").unwrap(),
+        strings(&["Alice", "Carol", "Dave", "Grace"])
+    );
+
+    // The alias qualifies the inner query's columns
+    let result = execute_query(
+        &catalog,
+        "SELECT e.name FROM (SELECT name, salary FROM employees) AS e \
+         WHERE e.salary < 50000 ORDER BY e.name",
+    )
+    .unwrap();
+    assert_eq!(result.column("name").unwrap(), strings(&["Bob", "Frank"]));
+}
+
+#[test]
+fn test_outer_query_reads_inner_aliases() {
+    let catalog = company_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT who, pay * 2 AS doubled FROM \
+         (SELECT name AS who, salary AS pay FROM employees WHERE department = 'Sales') staff \
+         WHERE pay > 50000 ORDER BY who",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["who", "doubled"]);
+    assert_eq!(result.column("who").unwrap(), strings(&["Dave", "Grace"]));
+    assert_eq!(
+        result.column("doubled").unwrap(),
+        vec![Value::Int64(124000), Value::Int64(140000)]
+    );
+}
+
+#[test]
+fn test_group_by_over_derived_table() {
+    let catalog = company_catalog();
+
+    // WHERE inside, GROUP BY outside
+    let result = execute_query(
+        &catalog,
+        "SELECT dept, COUNT(*) AS n, AVG(pay) AS average FROM \
+         (SELECT department AS dept, salary AS pay FROM employees WHERE salary > 50000) \
+         GROUP BY dept ORDER BY dept",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column("dept").unwrap(),
+        strings(&["Engineering", "Marketing", "Sales"])
+    );
+    assert_eq!(
+        result.column("n").unwrap(),
+        vec![Value::Int64(2), Value::Int64(1), Value::Int64(2)]
+    );
+    assert_eq!(
+        result.column("average").unwrap(),
+        vec![
+            Value::Float64(90000.0),
+            Value::Float64(51000.0),
+            Value::Float64(66000.0)
+        ]
+    );
+
+    // GROUP BY inside, WHERE on an aggregate's alias outside
+    let result = execute_query(
+        &catalog,
+        "SELECT t.department, t.total FROM \
+         (SELECT department, SUM(salary) AS total FROM employees GROUP BY department) AS t \
+         WHERE t.total > 100000 ORDER BY t.total DESC",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column("department").unwrap(),
+        strings(&["Engineering", "Sales"])
+    );
+    assert_eq!(
+        result.column("total").unwrap(),
+        vec![Value::Int64(225000), Value::Int64(180000)]
+    );
+}
+
+#[test]
+fn test_nested_derived_tables() {
+    let catalog = company_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT COUNT(*) AS rich FROM \
+         (SELECT who FROM (SELECT name AS who, salary FROM employees) a WHERE salary > 60000) b",
+    )
+    .unwrap();
+    assert_eq!(result.rows().next().unwrap(), vec![Value::Int64(4)]);
+}
+
+#[test]
+fn test_derived_table_in_a_join() {
+    let catalog = company_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT d.dept, departments.floor FROM \
+         (SELECT department AS dept, COUNT(*) AS n FROM employees GROUP BY department) AS d \
+         JOIN departments ON d.dept = departments.name WHERE d.n > 2 ORDER BY d.dept",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column("d.dept").unwrap(),
+        strings(&["Engineering", "Sales"])
+    );
+    assert_eq!(
+        result.column("departments.floor").unwrap(),
+        vec![Value::Int64(3), Value::Int64(1)]
+    );
+
+    let err = execute_query(
+        &catalog,
+        "SELECT * FROM (SELECT department FROM employees) \
+         JOIN departments ON department = departments.name",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("alias"), "{}", err);
+}
+
+#[test]
+fn test_column_the_inner_query_does_not_expose_is_an_error() {
+    let catalog = company_catalog();
+
+    // `salary` exists in the table, but the inner query renames it
+    let err = execute_query(
+        &catalog,
+        "SELECT salary FROM (SELECT name, salary AS pay FROM employees) AS e",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("salary"), "{}", err);
+
+    let err = execute_query(
+        &catalog,
+        "SELECT name FROM (SELECT name FROM employees) AS e WHERE department = 'Sales'",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("department"), "{}", err);
+
+    let err = execute_query(&catalog, "SELECT x FROM (SELECT missing FROM employees)").unwrap_err();
+    assert!(err.to_string().contains("missing"), "{}", err);
+}
+
+#[test]
+fn test_prepared_statement_with_parameters_in_a_derived_table() {
+    let catalog = company_catalog();
+
+    let statement = catalog
+        .prepare(
+            "SELECT name FROM (SELECT name, salary FROM employees WHERE salary > ?) e \
+             WHERE salary < ? ORDER BY name",
+        )
+        .unwrap();
+    assert_eq!(statement.parameter_count(), 2);
+    // The outer query's columns are not known until the inner one runs
+    assert_eq!(
+        statement.parameter_types(),
+        vec![Some(DataType::Int64), None]
+    );
+
+    let result = statement
+        .bind(&[Value::Int64(50000), Value::Int64(90000)])
+        .unwrap()
+        .execute(&catalog)
+        .unwrap();
+    assert_eq!(
+        result.column("name").unwrap(),
+        strings(&["Alice", "Dave", "Eve", "Grace"])
+    );
+}
+
+#[test]
+fn test_explain_analyze_shows_the_inner_plan() {
+    let catalog = company_catalog();
+
+    let (result, plan) = explain_analyze(
+        &catalog,
+        "SELECT COUNT(*) FROM (SELECT name FROM employees WHERE salary > 60000) AS e",
+        PlannerOptions::default(),
+        &CancellationToken::new(),
+    )
+    .unwrap();
+    assert_eq!(result.rows().next().unwrap(), vec![Value::Int64(4)]);
+    assert!(plan.contains("DerivedTable(e) rows=4"), "{}", plan);
+    assert!(plan.contains("Filter(salary > 60000) rows=4"), "{}", plan);
+}
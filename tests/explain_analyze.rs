@@ -0,0 +1,154 @@
+//! # Integration Tests for EXPLAIN ANALYZE
+//!
+//! Profiling wraps every operator of a plan to count its rows and time it.
+//! These tests check the wrapping is transparent, so queries return the same
+//! rows with and without it, and that the rendered tree reports the rows each
+//! operator emitted and consumed.
+
+use mini_rust_olap::{
+    catalog::Catalog, execute_query, execute_query_with_options, execution::CancellationToken,
+    explain_analyze, planner::PlannerOptions,
+};
+
+mod common;
+use common::catalog_from_csv;
+
+const USERS: &str = "\
+id,name,age,city
+1,Alice,25,Paris
+2,Bob,35,Berlin
+3,Carol,42,Paris
+4,Dave,31,Rome
+5,Eve,28,Berlin
+6,Frank,55,Paris
+";
+
+const ORDERS: &str = "\
+order_id,user_id,amount
+100,1,20.5
+101,2,12.0
+102,2,7.25
+103,4,99.0
+104,6,15.0
+105,6,3.5
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn shop_catalog() -> Catalog {
+    catalog_from_csv(&[("users", USERS), ("orders", ORDERS)])
+}
+
+fn profile(catalog: &Catalog, sql: &str) -> String {
+    let (_, plan) = explain_analyze(
+        catalog,
+        sql,
+        PlannerOptions::default(),
+        &CancellationToken::new(),
+    )
+    .unwrap();
+    plan
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_profiling_does_not_change_results() {
+    let catalog = shop_catalog();
+    let profiled = PlannerOptions {
+        batch_size: 2,
+        profile: true,
+        ..PlannerOptions::default()
+    };
+
+    for sql in [
+        "SELECT name, age FROM users WHERE age > 30",
+        "SELECT city, COUNT(*), AVG(age) FROM users GROUP BY city",
+        "SELECT name FROM users ORDER BY age DESC",
+        "SELECT name FROM users ORDER BY name LIMIT 2",
+        "SELECT id FROM users LIMIT 3 OFFSET 2",
+        "SELECT name, amount FROM users JOIN orders ON id = user_id WHERE amount > 10",
+        "SELECT UPPER(name) AS shout, age + 1 FROM users",
+    ] {
+        let plain = execute_query(&catalog, sql).unwrap();
+        let result =
+            execute_query_with_options(&catalog, sql, profiled, &CancellationToken::new()).unwrap();
+        assert_eq!(result.column_names(), plain.column_names(), "{}", sql);
+        assert_eq!(
+            result.rows().collect::<Vec<_>>(),
+            plain.rows().collect::<Vec<_>>(),
+            "{}",
+            sql
+        );
+
+        let (analyzed, _) = explain_analyze(
+            &catalog,
+            sql,
+            PlannerOptions::default(),
+            &CancellationToken::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            analyzed.rows().collect::<Vec<_>>(),
+            plain.rows().collect::<Vec<_>>(),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_profile_reports_emitted_and_consumed_rows() {
+    let catalog = shop_catalog();
+
+    let plan = profile(&catalog, "SELECT name FROM users WHERE age > 30");
+    let lines: Vec<&str> = plan.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", plan);
+    assert!(lines[0].starts_with("Project(name) rows=4 input=4 time="));
+    assert!(lines[1].starts_with("  Filter(age > 30) rows=4 input=6 time="));
+    assert!(lines[2].starts_with("    TableScan(users: "));
+    assert!(lines[2].contains(" rows=6 time="), "{}", plan);
+    assert!(!lines[2].contains("input="), "{}", plan);
+}
+
+#[test]
+fn test_profile_of_a_join_shows_both_inputs() {
+    let catalog = shop_catalog();
+
+    let plan = profile(
+        &catalog,
+        "SELECT name, amount FROM users JOIN orders ON id = user_id",
+    );
+    let join = plan
+        .lines()
+        .find(|line| line.trim_start().starts_with("HashJoin("))
+        .unwrap_or_else(|| panic!("no join in\n{}", plan));
+    // Six users and six orders go in, six matches come out
+    assert!(join.contains(" rows=6 input=12 time="), "{}", plan);
+    assert!(plan.contains("TableScan(users: "), "{}", plan);
+    assert!(plan.contains("TableScan(orders: "), "{}", plan);
+}
+
+#[test]
+fn test_profile_of_a_grouped_and_sorted_query() {
+    let catalog = shop_catalog();
+
+    let plan = profile(
+        &catalog,
+        "SELECT city, COUNT(*) FROM users GROUP BY city ORDER BY city LIMIT 2",
+    );
+    assert!(
+        plan.contains("TopN(2; city) rows=2 input=3 time="),
+        "{}",
+        plan
+    );
+    assert!(
+        plan.contains("GroupBy(city; 1 aggregate) rows=3 input=6 time="),
+        "{}",
+        plan
+    );
+}
@@ -0,0 +1,94 @@
+//! # Integration Tests for Querying CSV Files by Path
+//!
+//! These tests run queries whose FROM clause is a quoted file path, checking
+//! that the file is loaded without being registered, that repeated queries
+//! reuse the loaded table until the file changes, and that a missing or
+//! malformed file fails with an error naming it.
+
+use mini_rust_olap::{catalog::Catalog, execute_query, types::Value};
+use std::fs;
+use std::sync::Arc;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_aggregate_over_file_path() {
+    let catalog = Catalog::new();
+
+    let result = execute_query(
+        &catalog,
+        &format!(
+            "SELECT department, COUNT(*), MAX(age) FROM '{}' \
+             WHERE salary > 70000 GROUP BY department ORDER BY department",
+            FIXTURE
+        ),
+    )
+    .unwrap();
+    assert_eq!(
+        result.rows().collect::<Vec<_>>(),
+        vec![
+            vec![
+                Value::String("Engineering".to_string()),
+                Value::Int64(4),
+                Value::Int64(45)
+            ],
+            vec![
+                Value::String("Sales".to_string()),
+                Value::Int64(3),
+                Value::Int64(38)
+            ],
+        ]
+    );
+
+    // The file was never registered
+    assert_eq!(catalog.table_count(), 0);
+}
+
+#[test]
+fn test_file_table_is_cached_until_the_file_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sales.csv");
+    fs::write(&path, "region,amount\nnorth,10\nsouth,20\n").unwrap();
+    let path = path.to_str().unwrap();
+
+    let catalog = Catalog::new();
+    let sql = format!("SELECT SUM(amount) FROM '{}'", path);
+    let result = execute_query(&catalog, &sql).unwrap();
+    assert_eq!(result.rows().next().unwrap(), vec![Value::Int64(30)]);
+
+    // Unchanged, the file is loaded once, also for clones of the catalog
+    let first = catalog.file_table(path).unwrap();
+    assert!(Arc::ptr_eq(
+        &first,
+        &catalog.clone().file_table(path).unwrap()
+    ));
+
+    fs::write(path, "region,amount\nnorth,10\nsouth,20\neast,300\n").unwrap();
+    let result = execute_query(&catalog, &sql).unwrap();
+    assert_eq!(result.rows().next().unwrap(), vec![Value::Int64(330)]);
+    assert!(!Arc::ptr_eq(&first, &catalog.file_table(path).unwrap()));
+}
+
+#[test]
+fn test_file_query_errors_name_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let catalog = Catalog::new();
+
+    let missing = dir.path().join("missing.csv");
+    let err =
+        execute_query(&catalog, &format!("SELECT * FROM '{}'", missing.display())).unwrap_err();
+    assert!(err.to_string().contains("missing.csv"), "{}", err);
+
+    let malformed = dir.path().join("malformed.csv");
+    fs::write(&malformed, "a,b\n1,2\n3\n").unwrap();
+    let err = execute_query(
+        &catalog,
+        &format!("SELECT * FROM '{}'", malformed.display()),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("malformed.csv"), "{}", err);
+}
@@ -0,0 +1,139 @@
+//! # Integration Tests for Repeated and Empty Header Names
+//!
+//! These tests load files whose headers repeat a name, leave cells empty,
+//! or consist only of delimiters, and check the columns load under unique
+//! names that queries can use, and that the renames are reported.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    ingest::{infer_schema, load_csv_with_options, CsvOptions, HeaderRename, LoadReport},
+    types::{DataType, Value},
+};
+
+const DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn load(file: &str) -> (Catalog, LoadReport) {
+    let mut catalog = Catalog::new();
+    let report = load_csv_with_options(
+        format!("{}/{}", DATA_DIR, file),
+        "t".to_string(),
+        &CsvOptions::default(),
+        &mut catalog,
+    )
+    .unwrap();
+    (catalog, report)
+}
+
+fn rename(index: usize, original: &str, renamed: &str) -> HeaderRename {
+    HeaderRename {
+        index,
+        original: original.to_string(),
+        renamed: renamed.to_string(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_duplicate_names_are_renamed() {
+    let (catalog, report) = load("duplicate_headers.csv");
+
+    let table = catalog.get_table("t").unwrap();
+    assert_eq!(table.column_names(), ["id", "name", "name_1", "total"]);
+    assert_eq!(report.renamed_columns, vec![rename(2, "name", "name_1")]);
+    assert_eq!(
+        report.renamed_columns[0].to_string(),
+        "column 3 repeats the name 'name'; loaded as 'name_1'"
+    );
+
+    let result = execute_query(&catalog, "SELECT name, name_1 FROM t WHERE total > 8").unwrap();
+    assert_eq!(
+        result.rows().collect::<Vec<_>>(),
+        vec![
+            vec![
+                Value::String("Alice".to_string()),
+                Value::String("Smith".to_string())
+            ],
+            vec![
+                Value::String("Bob".to_string()),
+                Value::String("Jones".to_string())
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_names_fail_a_strict_load() {
+    let options = CsvOptions {
+        rename_duplicate_headers: false,
+        ..CsvOptions::default()
+    };
+    let mut catalog = Catalog::new();
+    let err = load_csv_with_options(
+        format!("{}/duplicate_headers.csv", DATA_DIR),
+        "t".to_string(),
+        &options,
+        &mut catalog,
+    )
+    .unwrap_err();
+
+    let message = err.to_string();
+    assert!(
+        message.contains("Column name 'name' appears more than once"),
+        "{}",
+        message
+    );
+    assert!(message.contains("duplicate_headers.csv"), "{}", message);
+    assert!(message.contains("(columns 2 and 3)"), "{}", message);
+    assert!(!catalog.table_exists("t"));
+}
+
+#[test]
+fn test_empty_names_are_synthesized() {
+    let (catalog, report) = load("empty_headers.csv");
+
+    let table = catalog.get_table("t").unwrap();
+    assert_eq!(table.column_names(), ["id", "col_1", "amount", "col_3"]);
+    assert_eq!(
+        report.renamed_columns,
+        vec![rename(1, "", "col_1"), rename(3, "", "col_3")]
+    );
+    assert_eq!(
+        report.renamed_columns[0].to_string(),
+        "column 2 has no name in the header; loaded as 'col_1'"
+    );
+
+    let result = execute_query(&catalog, "SELECT col_1 FROM t WHERE amount = 20").unwrap();
+    assert_eq!(
+        result.column("col_1").unwrap(),
+        vec![Value::String("south".to_string())]
+    );
+}
+
+#[test]
+fn test_header_of_only_commas() {
+    let (catalog, report) = load("commas_header.csv");
+
+    let table = catalog.get_table("t").unwrap();
+    assert_eq!(table.column_names(), ["col_0", "col_1", "col_2"]);
+    assert_eq!(report.renamed_columns.len(), 3);
+    assert_eq!(table.get_column_type("col_2").unwrap(), DataType::Boolean);
+
+    // Inferring the schema without loading names the columns the same way
+    let schema = infer_schema(format!("{}/commas_header.csv", DATA_DIR)).unwrap();
+    assert_eq!(
+        schema,
+        vec![
+            ("col_0".to_string(), DataType::Int64),
+            ("col_1".to_string(), DataType::String),
+            ("col_2".to_string(), DataType::Boolean),
+        ]
+    );
+}
@@ -0,0 +1,162 @@
+//! # Integration Tests for the HTTP Query Server
+//!
+//! These tests start a server on an ephemeral port, send it real HTTP
+//! requests over TCP, and check the status line and JSON payload. They need
+//! the `http` feature: `cargo test --features http`.
+
+#![cfg(feature = "http")]
+
+use mini_rust_olap::catalog::{Catalog, SharedCatalog};
+use mini_rust_olap::column::{Column, FloatColumn, IntColumn, StringColumn};
+use mini_rust_olap::server::QueryServer;
+use mini_rust_olap::table::Table;
+use mini_rust_olap::types::Value;
+use serde_json::{json, Value as Json};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// A `sales` table with a string, an integer and a float column
+fn sales_catalog() -> SharedCatalog {
+    let mut region = StringColumn::new();
+    let mut units = IntColumn::new();
+    let mut price = FloatColumn::new();
+    for (r, u, p) in [("east", 3, 2.5), ("west", 1, 10.0), ("east", 2, 4.0)] {
+        region.push_value(Value::String(r.to_string())).unwrap();
+        units.push_value(Value::Int64(u)).unwrap();
+        price.push_value(Value::Float64(p)).unwrap();
+    }
+    price.push_null();
+    region
+        .push_value(Value::String("north".to_string()))
+        .unwrap();
+    units.push_null();
+
+    let mut table = Table::new("sales".to_string());
+    table
+        .add_column("region".to_string(), Box::new(region))
+        .unwrap();
+    table
+        .add_column("units".to_string(), Box::new(units))
+        .unwrap();
+    table
+        .add_column("price".to_string(), Box::new(price))
+        .unwrap();
+
+    let mut catalog = Catalog::new();
+    catalog.register_table(table).unwrap();
+    SharedCatalog::new(catalog)
+}
+
+/// Sends one request and returns the status code and parsed JSON body
+fn send(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Json) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    assert!(head.contains("Content-Type: application/json"), "{}", head);
+    (status, serde_json::from_str(body).unwrap())
+}
+
+fn post_query(addr: SocketAddr, sql: &str) -> (u16, Json) {
+    send(addr, "POST", "/query", &json!({ "sql": sql }).to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_post_query_returns_typed_json() {
+    let server = QueryServer::start("127.0.0.1:0", sales_catalog()).unwrap();
+    let addr = server.local_addr();
+
+    let (status, body) = post_query(addr, "SELECT region, units, price FROM sales");
+    assert_eq!(status, 200);
+    assert_eq!(
+        body,
+        json!({
+            "columns": ["region", "units", "price"],
+            "rows": [
+                ["east", 3, 2.5],
+                ["west", 1, 10.0],
+                ["east", 2, 4.0],
+                ["north", null, null],
+            ],
+        })
+    );
+
+    let (status, body) = post_query(
+        addr,
+        "SELECT region, COUNT(*) AS total FROM sales GROUP BY region ORDER BY region",
+    );
+    assert_eq!(status, 200);
+    assert_eq!(body["columns"], json!(["region", "total"]));
+    assert_eq!(
+        body["rows"],
+        json!([["east", 2], ["north", 1], ["west", 1]])
+    );
+
+    server.shutdown();
+}
+
+#[test]
+fn test_errors_return_400_with_message() {
+    let server = QueryServer::start("127.0.0.1:0", sales_catalog()).unwrap();
+    let addr = server.local_addr();
+
+    let (status, body) = post_query(addr, "SELECT missing FROM sales");
+    assert_eq!(status, 400);
+    assert!(
+        body["error"].as_str().unwrap().contains("missing"),
+        "{}",
+        body
+    );
+
+    let (status, _) = send(addr, "POST", "/query", "{not json");
+    assert_eq!(status, 400);
+    let (status, _) = send(addr, "GET", "/query", "");
+    assert_eq!(status, 405);
+    let (status, _) = send(addr, "POST", "/tables", "");
+    assert_eq!(status, 404);
+
+    server.shutdown();
+}
+
+#[test]
+fn test_server_sees_tables_registered_later_but_cannot_change_them() {
+    let catalog = SharedCatalog::default();
+    let server = QueryServer::start("127.0.0.1:0", catalog.clone()).unwrap();
+    let addr = server.local_addr();
+
+    let (status, _) = post_query(addr, "SELECT COUNT(*) FROM sales");
+    assert_eq!(status, 400);
+
+    catalog
+        .write()
+        .register_table(sales_catalog().get_table("sales").unwrap().as_ref().clone())
+        .unwrap();
+    let (status, body) = post_query(addr, "SELECT COUNT(*) FROM sales");
+    assert_eq!(status, 200);
+    assert_eq!(body["rows"], json!([[4]]));
+
+    // Statements run against a snapshot, so they cannot modify the catalog
+    post_query(addr, "INSERT INTO sales VALUES ('south', 1, 1.0)");
+    assert_eq!(catalog.get_table("sales").unwrap().row_count(), 4);
+
+    server.shutdown();
+}
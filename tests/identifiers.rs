@@ -0,0 +1,86 @@
+//! # Integration Tests for Identifier Resolution
+//!
+//! These tests load CSV files whose headers use mixed case and spaces, and
+//! check that unquoted names match them ignoring case, that quoted names
+//! match them exactly, and that results keep the names the file used.
+
+use mini_rust_olap::{catalog::Catalog, execute_query, types::Value};
+
+mod common;
+use common::catalog_from_csv;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn players() -> Catalog {
+    catalog_from_csv(&[(
+        "Players",
+        "UserID,User Name,Score\n1,alice,10\n2,bob,25\n3,carol,40\n",
+    )])
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_unquoted_names_match_mixed_case_headers() {
+    let catalog = players();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT userid, Score FROM players WHERE SCORE > 20",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["UserID", "Score"]);
+    let rows: Vec<Vec<Value>> = result.rows().collect();
+    assert_eq!(
+        rows,
+        vec![
+            vec![Value::Int64(2), Value::Int64(25)],
+            vec![Value::Int64(3), Value::Int64(40)],
+        ]
+    );
+
+    let result = execute_query(&catalog, "SELECT * FROM PLAYERS").unwrap();
+    assert_eq!(result.column_names(), ["UserID", "User Name", "Score"]);
+}
+
+#[test]
+fn test_quoted_names_with_spaces() {
+    let catalog = players();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT \"User Name\", \"UserID\" FROM \"Players\" WHERE \"User Name\" = 'bob'",
+    )
+    .unwrap();
+    assert_eq!(result.column_names(), ["User Name", "UserID"]);
+    let rows: Vec<Vec<Value>> = result.rows().collect();
+    assert_eq!(
+        rows,
+        vec![vec![Value::String("bob".to_string()), Value::Int64(2)]]
+    );
+
+    // Without quotes the name ends at the space
+    assert!(execute_query(&catalog, "SELECT User Name FROM players").is_err());
+}
+
+#[test]
+fn test_names_differing_only_by_case_are_ambiguous() {
+    let catalog = catalog_from_csv(&[("t", "Id,ID,value\n1,2,x\n")]);
+
+    let err = execute_query(&catalog, "SELECT id FROM t").unwrap_err();
+    assert!(
+        err.to_string().contains("ambiguous"),
+        "unexpected error: {}",
+        err
+    );
+
+    // Quoting picks one of them exactly
+    let result = execute_query(&catalog, "SELECT \"ID\", \"Id\" FROM t").unwrap();
+    assert_eq!(result.column_names(), ["ID", "Id"]);
+    let rows: Vec<Vec<Value>> = result.rows().collect();
+    assert_eq!(rows, vec![vec![Value::Int64(2), Value::Int64(1)]]);
+}
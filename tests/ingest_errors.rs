@@ -0,0 +1,151 @@
+//! # Integration Tests for Ingest Errors
+//!
+//! These tests load a fixture whose bad rows sit on known lines, and check
+//! that a strict load reports the first of them in full while a permissive
+//! load skips them and lists where each one was.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    error::{DatabaseError, IngestError, IngestErrorKind, Result},
+    execute_query,
+    ingest::{load_csv, load_csv_with_options, load_csv_with_report, CsvOptions},
+    types::{DataType, Value},
+};
+use std::path::Path;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/bad_rows.csv");
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Declares `id` as Int64 and `amount` as Float64, allowing `max_errors` bad rows.
+fn typed_options(max_errors: usize) -> CsvOptions {
+    CsvOptions {
+        column_types: vec![
+            ("id".to_string(), DataType::Int64),
+            ("amount".to_string(), DataType::Float64),
+        ],
+        max_errors,
+        ..CsvOptions::default()
+    }
+}
+
+fn invalid_amount(line: usize, value: &str) -> IngestError {
+    IngestError {
+        path: Path::new(FIXTURE).to_path_buf(),
+        line,
+        kind: IngestErrorKind::InvalidValue {
+            column: "amount".to_string(),
+            value: value.to_string(),
+            expected: DataType::Float64,
+        },
+    }
+}
+
+fn extra_field(line: usize) -> IngestError {
+    IngestError {
+        path: Path::new(FIXTURE).to_path_buf(),
+        line,
+        kind: IngestErrorKind::FieldCount {
+            expected: 3,
+            found: 4,
+        },
+    }
+}
+
+/// The row error a failed load returned.
+fn ingest_error<T>(result: Result<T>) -> IngestError {
+    match result {
+        Err(DatabaseError::IngestError(err)) => err,
+        Err(other) => panic!("Expected IngestError, got {:?}", other),
+        Ok(_) => panic!("Expected the load to fail"),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_strict_load_reports_the_first_bad_cell() {
+    let err = ingest_error(load_csv_with_report(
+        FIXTURE,
+        "sales".to_string(),
+        &typed_options(0),
+    ));
+    assert_eq!(
+        DatabaseError::IngestError(err.clone()).to_string(),
+        format!(
+            "Ingestion error: '{}' line 3: column 'amount' expects Float64, found 'abc'",
+            FIXTURE
+        )
+    );
+    assert_eq!(err, invalid_amount(3, "abc"));
+}
+
+#[test]
+fn test_field_count_is_checked_without_declared_types() {
+    // `abc` only promotes `amount` to String, but the extra field is an error
+    let err = ingest_error(load_csv(FIXTURE, "sales".to_string()));
+    assert_eq!(err, extra_field(5));
+}
+
+#[test]
+fn test_permissive_load_skips_and_lists_bad_rows() {
+    let mut catalog = Catalog::new();
+    let report = load_csv_with_options(
+        FIXTURE,
+        "sales".to_string(),
+        &typed_options(3),
+        &mut catalog,
+    )
+    .unwrap();
+
+    assert_eq!(report.rows_loaded, 4);
+    assert_eq!(report.rows_skipped(), 3);
+    assert_eq!(
+        report.errors,
+        vec![
+            invalid_amount(3, "abc"),
+            extra_field(5),
+            invalid_amount(7, "n/a"),
+        ]
+    );
+
+    let table = catalog.get_table("sales").unwrap();
+    assert_eq!(table.get_column_type("amount").unwrap(), DataType::Float64);
+    let result = execute_query(&catalog, "SELECT SUM(amount), MAX(id) FROM sales").unwrap();
+    assert_eq!(
+        result.rows().collect::<Vec<_>>(),
+        vec![vec![Value::Float64(32.75), Value::Int64(7)]]
+    );
+}
+
+#[test]
+fn test_too_many_bad_rows_fail_at_the_first_over_the_limit() {
+    let mut catalog = Catalog::new();
+    let result = load_csv_with_options(
+        FIXTURE,
+        "sales".to_string(),
+        &typed_options(2),
+        &mut catalog,
+    );
+    assert_eq!(ingest_error(result), invalid_amount(7, "n/a"));
+    assert!(!catalog.table_exists("sales"));
+}
+
+#[test]
+fn test_declared_type_for_a_missing_column() {
+    let options = CsvOptions {
+        column_types: vec![("price".to_string(), DataType::Float64)],
+        max_errors: 10,
+        ..CsvOptions::default()
+    };
+    let Err(err) = load_csv_with_report(FIXTURE, "sales".to_string(), &options) else {
+        panic!("Expected the load to fail");
+    };
+    assert!(err
+        .to_string()
+        .contains("Column 'price' has a declared type but is not in"));
+}
@@ -20,7 +20,9 @@
 use mini_rust_olap::{
     aggregates::{AvgAggregate, CountAggregate, MaxAggregate, MinAggregate, SumAggregate},
     column::{Column, FloatColumn, IntColumn, StringColumn},
-    execution::{BinaryComparison, ComparisonOp, Filter, GroupBy, Operator, Project, TableScan},
+    execution::{
+        BinaryComparison, ComparisonOp, Field, Filter, GroupBy, Operator, Project, TableScan,
+    },
     table::Table,
     types::{DataType, Value},
 };
@@ -249,9 +251,14 @@ fn test_scan_filter_project_basic() {
 
         // Verify schema
         let schema = project.schema().unwrap();
-        assert_eq!(schema.len(), 2);
-        assert_eq!(schema.get("product"), Some(&DataType::String));
-        assert_eq!(schema.get("quantity"), Some(&DataType::Int64));
+        assert_eq!(
+            schema.fields(),
+            [
+                Field::new("product", DataType::String),
+                Field::new("quantity", DataType::Int64)
+            ]
+        );
+        assert_eq!(batch.schema(), Some(&schema));
 
         // Verify all quantities > 2
         for i in 0..batch.row_count() {
@@ -317,9 +324,13 @@ fn test_scan_filter_project_with_aliases() {
 
     // Verify schema
     let schema = project.schema().unwrap();
-    assert_eq!(schema.len(), 2);
-    assert_eq!(schema.get("employee_name"), Some(&DataType::String));
-    assert_eq!(schema.get("annual_salary"), Some(&DataType::Float64));
+    assert_eq!(
+        schema.fields(),
+        [
+            Field::new("employee_name", DataType::String),
+            Field::new("annual_salary", DataType::Float64)
+        ]
+    );
 
     // Verify all rows are Engineering
     for i in 0..batch.row_count() {
@@ -369,9 +380,13 @@ fn test_scan_group_by_count() {
 
     // Verify schema
     let schema = group_by.schema().unwrap();
-    assert_eq!(schema.len(), 2);
-    assert_eq!(schema.get("product"), Some(&DataType::String));
-    assert_eq!(schema.get("agg_0"), Some(&DataType::Int64));
+    assert_eq!(
+        schema.fields(),
+        [
+            Field::new("product", DataType::String),
+            Field::new("agg_0", DataType::Int64)
+        ]
+    );
 
     // Verify counts
     let mut counts = std::collections::HashMap::new();
@@ -529,9 +544,13 @@ fn test_scan_filter_groupby_project_complex() {
 
     // Verify schema and column names
     let schema = project.schema().unwrap();
-    assert_eq!(schema.len(), 2);
-    assert_eq!(schema.get("product_name"), Some(&DataType::String));
-    assert_eq!(schema.get("total_quantity"), Some(&DataType::Int64));
+    assert_eq!(
+        schema.fields(),
+        [
+            Field::new("product_name", DataType::String),
+            Field::new("total_quantity", DataType::Int64)
+        ]
+    );
 
     let column_names = project.column_names().unwrap();
     assert_eq!(
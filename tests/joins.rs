@@ -0,0 +1,145 @@
+//! # Integration Tests for Joins
+//!
+//! These tests load users, orders and products from CSV and join them with
+//! SQL, checking inner join semantics (unmatched and NULL keys drop out),
+//! table-qualified output names, and that filters, grouping and column
+//! pruning work on top of a join.
+
+use mini_rust_olap::{catalog::Catalog, execute_sql, execution::Batch, types::Value};
+
+mod common;
+use common::catalog_from_csv;
+
+const USERS: &str = "\
+id,name
+1,Alice
+2,Bob
+3,Carol
+";
+
+/// Bob has two orders, Carol none; one order has no user and one an unknown user.
+const ORDERS: &str = "\
+id,user_id,product_id,amount
+10,1,100,25.0
+11,2,100,40.0
+12,2,101,5.5
+13,,101,99.0
+14,7,100,1.0
+";
+
+const PRODUCTS: &str = "\
+id,title
+100,Lamp
+101,Mug
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn shop_catalog() -> Catalog {
+    catalog_from_csv(&[("users", USERS), ("orders", ORDERS), ("products", PRODUCTS)])
+}
+
+fn rows(batches: &[Batch]) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    for batch in batches {
+        for row in 0..batch.row_count() {
+            rows.push(
+                (0..batch.column_count())
+                    .map(|col| batch.get(row, col).unwrap().to_string())
+                    .collect(),
+            );
+        }
+    }
+    rows.sort();
+    rows
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_join_users_with_orders() {
+    let catalog = shop_catalog();
+
+    let (columns, batches) = execute_sql(
+        &catalog,
+        "SELECT users.name, orders.amount FROM users JOIN orders ON users.id = orders.user_id",
+    )
+    .unwrap();
+    assert_eq!(columns, vec!["users.name", "orders.amount"]);
+    assert_eq!(
+        rows(&batches),
+        vec![
+            vec!["Alice", "25.0"],
+            vec!["Bob", "40.0"],
+            vec!["Bob", "5.5"],
+        ]
+    );
+}
+
+#[test]
+fn test_join_output_keeps_both_id_columns() {
+    let catalog = shop_catalog();
+
+    let (columns, batches) = execute_sql(
+        &catalog,
+        "SELECT * FROM users JOIN orders ON users.id = orders.user_id WHERE amount > 30",
+    )
+    .unwrap();
+    assert_eq!(
+        columns,
+        vec![
+            "users.id",
+            "users.name",
+            "orders.id",
+            "orders.user_id",
+            "orders.product_id",
+            "orders.amount"
+        ]
+    );
+    assert_eq!(
+        rows(&batches),
+        vec![vec!["2", "Bob", "11", "2", "100", "40.0"]]
+    );
+}
+
+#[test]
+fn test_three_way_join_with_group_by() {
+    let catalog = shop_catalog();
+
+    let (columns, batches) = execute_sql(
+        &catalog,
+        "SELECT title, SUM(amount) FROM orders \
+         JOIN users ON orders.user_id = users.id \
+         JOIN products ON products.id = orders.product_id \
+         GROUP BY title",
+    )
+    .unwrap();
+    assert_eq!(columns, vec!["products.title", "SUM_orders.amount"]);
+    assert_eq!(
+        rows(&batches),
+        vec![vec!["Lamp", "65.0"], vec!["Mug", "5.5"]]
+    );
+
+    let (_, batches) = execute_sql(
+        &catalog,
+        "SELECT COUNT(*) FROM orders JOIN products ON product_id = products.id",
+    )
+    .unwrap();
+    assert_eq!(batches[0].get(0, 0).unwrap(), Value::Int64(5));
+}
+
+#[test]
+fn test_unqualified_shared_column_is_ambiguous() {
+    let catalog = shop_catalog();
+
+    let err = execute_sql(
+        &catalog,
+        "SELECT id FROM users JOIN orders ON users.id = orders.user_id",
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("ambiguous"), "{}", err);
+}
@@ -0,0 +1,153 @@
+//! # Integration Tests for Memory Accounting
+//!
+//! These tests run queries over generated tables with and without a memory
+//! limit, checking that a query over the limit fails with an error naming
+//! the operator, and that a query within it reports a plausible peak.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    datagen::{generate_table, ColumnSpec, TableSpec},
+    error::DatabaseError,
+    execute_query_with_options,
+    execution::{CancellationToken, ExecutionError},
+    PlannerOptions, QueryResult,
+};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// A `sales` table of 50,000 rows with about 10,000 customers
+fn sales_catalog() -> Catalog {
+    let spec = TableSpec::new(11)
+        .column("id", ColumnSpec::Sequence { start: 1 })
+        .column(
+            "customer",
+            ColumnSpec::Categorical {
+                cardinality: 10_000,
+                zipf_exponent: None,
+            },
+        )
+        .column(
+            "amount",
+            ColumnSpec::UniformFloat {
+                min: 0.0,
+                max: 100.0,
+            },
+        );
+    let mut catalog = Catalog::new();
+    catalog
+        .register_table(generate_table("sales", 50_000, &spec).unwrap())
+        .unwrap();
+    catalog
+}
+
+fn run(
+    catalog: &Catalog,
+    sql: &str,
+    memory_limit: Option<usize>,
+) -> mini_rust_olap::Result<QueryResult> {
+    let options = PlannerOptions {
+        memory_limit,
+        ..PlannerOptions::default()
+    };
+    execute_query_with_options(catalog, sql, options, &CancellationToken::new())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_group_by_over_the_limit_fails() {
+    let catalog = sales_catalog();
+
+    let err = run(
+        &catalog,
+        "SELECT customer, SUM(amount) FROM sales GROUP BY customer",
+        Some(64 * 1024),
+    )
+    .unwrap_err();
+    match &err {
+        DatabaseError::Execution(ExecutionError::ResourceExhausted {
+            operator, limit, ..
+        }) => {
+            assert!(operator.starts_with("GroupBy(customer"), "{}", operator);
+            assert_eq!(*limit, 64 * 1024);
+        }
+        other => panic!("expected ResourceExhausted, got {:?}", other),
+    }
+    assert!(err.to_string().contains("memory limit"), "{}", err);
+}
+
+#[test]
+fn test_group_by_within_the_limit_reports_its_peak() {
+    let catalog = sales_catalog();
+
+    let result = run(
+        &catalog,
+        "SELECT customer, SUM(amount) FROM sales GROUP BY customer",
+        Some(256 * 1024 * 1024),
+    )
+    .unwrap();
+    assert!(result.row_count() > 9_000);
+    // Each group holds at least its string key and an aggregate state
+    let peak = result.peak_memory();
+    assert!(peak > result.row_count() * 64, "{}", peak);
+    assert!(peak < 64 * 1024 * 1024, "{}", peak);
+
+    // Without a limit the peak is still tracked
+    let unlimited = run(
+        &catalog,
+        "SELECT customer, SUM(amount) FROM sales GROUP BY customer",
+        None,
+    )
+    .unwrap();
+    assert_eq!(unlimited.peak_memory(), peak);
+}
+
+#[test]
+fn test_streaming_queries_buffer_nothing() {
+    let catalog = sales_catalog();
+
+    let result = run(
+        &catalog,
+        "SELECT id, amount FROM sales WHERE amount > 50",
+        Some(1),
+    )
+    .unwrap();
+    assert!(result.row_count() > 0);
+    assert_eq!(result.peak_memory(), 0);
+}
+
+#[test]
+fn test_sort_and_derived_tables_count_towards_the_peak() {
+    let catalog = sales_catalog();
+
+    let sorted = run(&catalog, "SELECT id FROM sales ORDER BY amount", None).unwrap();
+    assert!(sorted.peak_memory() > 50_000, "{}", sorted.peak_memory());
+    assert!(run(&catalog, "SELECT id FROM sales ORDER BY amount", Some(1024)).is_err());
+
+    // The inner grouping runs while the query is planned, under the same
+    // tracker
+    let nested = run(
+        &catalog,
+        "SELECT COUNT(*) FROM (SELECT customer, SUM(amount) AS total FROM sales \
+         GROUP BY customer) AS t WHERE total > 100",
+        None,
+    )
+    .unwrap();
+    assert!(
+        nested.peak_memory() > 9_000 * 64,
+        "{}",
+        nested.peak_memory()
+    );
+    let err = run(
+        &catalog,
+        "SELECT COUNT(*) FROM (SELECT customer, SUM(amount) AS total FROM sales \
+         GROUP BY customer) AS t",
+        Some(64 * 1024),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("GroupBy(customer"), "{}", err);
+}
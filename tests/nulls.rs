@@ -0,0 +1,230 @@
+//! # Integration Tests for NULL Handling
+//!
+//! These tests load a CSV with missing fields and check that the empty fields
+//! come back as NULLs and that aggregates and WHERE filtering treat them the
+//! way SQL does: aggregates skip them, COUNT(*) still counts their rows, no
+//! comparison against NULL is true, and only IS [NOT] NULL tests for them.
+
+use mini_rust_olap::{catalog::Catalog, execute_sql, execution::Batch, types::Value};
+
+mod common;
+use common::catalog_from_csv;
+
+/// Four employees; Bob has no salary and Dave has neither age nor department.
+const CSV: &str = "\
+name,age,department,salary
+Alice,30,Engineering,100.0
+Bob,40,Engineering,
+Carol,50,Sales,50.0
+Dave,,,70.0
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn catalog_with_missing_values() -> Catalog {
+    catalog_from_csv(&[("emp", CSV)])
+}
+
+/// Runs a query and returns its rows.
+fn query(catalog: &Catalog, sql: &str) -> Vec<Vec<Value>> {
+    let (_, batches) = execute_sql(catalog, sql).unwrap();
+    rows(&batches)
+}
+
+fn rows(batches: &[Batch]) -> Vec<Vec<Value>> {
+    let mut rows = Vec::new();
+    for batch in batches {
+        for row in 0..batch.row_count() {
+            rows.push(
+                (0..batch.column_count())
+                    .map(|col| batch.get(row, col).unwrap())
+                    .collect(),
+            );
+        }
+    }
+    rows
+}
+
+fn names(catalog: &Catalog, sql: &str) -> Vec<String> {
+    let mut names: Vec<String> = query(catalog, sql)
+        .into_iter()
+        .map(|row| row[0].to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_empty_fields_load_as_null() {
+    let catalog = catalog_with_missing_values();
+    let table = catalog.get_table("emp").unwrap();
+
+    assert_eq!(table.get_value("salary", 1).unwrap(), Value::Null);
+    assert_eq!(table.get_value("age", 3).unwrap(), Value::Null);
+    assert_eq!(table.get_value("department", 3).unwrap(), Value::Null);
+    assert_eq!(table.get_column("salary").unwrap().null_count(), 1);
+    assert!(table.verify().is_empty());
+}
+
+#[test]
+fn test_aggregates_skip_nulls() {
+    let catalog = catalog_with_missing_values();
+
+    let result = query(
+        &catalog,
+        "SELECT SUM(salary), AVG(salary), COUNT(salary), COUNT(*) FROM emp",
+    );
+    assert_eq!(
+        result,
+        vec![vec![
+            Value::Float64(220.0),
+            // Divided by the three non-NULL salaries, not four rows
+            Value::Float64(220.0 / 3.0),
+            Value::Int64(3),
+            Value::Int64(4),
+        ]]
+    );
+
+    let result = query(&catalog, "SELECT AVG(age), MIN(age), MAX(age) FROM emp");
+    assert_eq!(
+        result,
+        vec![vec![
+            Value::Float64(40.0),
+            Value::Int64(30),
+            Value::Int64(50)
+        ]]
+    );
+}
+
+#[test]
+fn test_null_group_key_forms_its_own_group() {
+    let catalog = catalog_with_missing_values();
+
+    let mut result = query(
+        &catalog,
+        "SELECT department, COUNT(*), SUM(salary) FROM emp GROUP BY department",
+    );
+    result.sort_by_key(|row| row[0].to_string());
+    assert_eq!(
+        result,
+        vec![
+            vec![
+                Value::String("Engineering".into()),
+                Value::Int64(2),
+                Value::Float64(100.0)
+            ],
+            vec![Value::Null, Value::Int64(1), Value::Float64(70.0)],
+            vec![
+                Value::String("Sales".into()),
+                Value::Int64(1),
+                Value::Float64(50.0)
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_where_comparisons_against_null_are_false() {
+    let catalog = catalog_with_missing_values();
+
+    assert_eq!(
+        names(&catalog, "SELECT name FROM emp WHERE salary > 60"),
+        vec!["Alice", "Dave"]
+    );
+    assert_eq!(
+        names(&catalog, "SELECT name FROM emp WHERE salary <= 60"),
+        vec!["Carol"]
+    );
+    assert_eq!(
+        names(&catalog, "SELECT name FROM emp WHERE age != 40"),
+        vec!["Alice", "Carol"]
+    );
+    assert_eq!(
+        names(
+            &catalog,
+            "SELECT name FROM emp WHERE department NOT IN ('Sales')"
+        ),
+        vec!["Alice", "Bob"]
+    );
+}
+
+#[test]
+fn test_is_null_and_is_not_null() {
+    let catalog = catalog_with_missing_values();
+
+    assert_eq!(
+        names(&catalog, "SELECT name FROM emp WHERE salary IS NULL"),
+        vec!["Bob"]
+    );
+    assert_eq!(
+        names(&catalog, "SELECT name FROM emp WHERE age IS NOT NULL"),
+        vec!["Alice", "Bob", "Carol"]
+    );
+    assert_eq!(
+        names(
+            &catalog,
+            "SELECT name FROM emp WHERE salary IS NULL OR department IS NULL"
+        ),
+        vec!["Bob", "Dave"]
+    );
+    assert_eq!(
+        names(
+            &catalog,
+            "SELECT name FROM emp WHERE NOT (salary IS NULL) AND age IS NOT NULL"
+        ),
+        vec!["Alice", "Carol"]
+    );
+    // NOT of a comparison is still false for NULL
+    assert_eq!(
+        names(&catalog, "SELECT name FROM emp WHERE NOT (age > 40)"),
+        vec!["Alice", "Bob"]
+    );
+    assert_eq!(
+        names(
+            &catalog,
+            "SELECT name FROM emp WHERE NOT (age > 40 OR department IS NULL)"
+        ),
+        vec!["Alice", "Bob"]
+    );
+}
+
+#[test]
+fn test_is_null_across_batch_boundaries() {
+    // Every seventh email is missing, over three 1024-row batches
+    let mut csv = String::from("id,email\n");
+    for id in 0..3000 {
+        if id % 7 == 0 {
+            csv.push_str(&format!("{},\n", id));
+        } else {
+            csv.push_str(&format!("{},user{}@example.com\n", id, id));
+        }
+    }
+    let catalog = catalog_from_csv(&[("users", &csv)]);
+
+    let missing = query(&catalog, "SELECT id FROM users WHERE email IS NULL");
+    let expected: Vec<Vec<Value>> = (0..3000)
+        .filter(|id| id % 7 == 0)
+        .map(|id| vec![Value::Int64(id)])
+        .collect();
+    assert_eq!(missing, expected);
+
+    let count = |sql: &str| query(&catalog, sql)[0][0].clone();
+    assert_eq!(
+        count("SELECT COUNT(*) FROM users WHERE email IS NOT NULL"),
+        Value::Int64(3000 - 429)
+    );
+    assert_eq!(
+        count("SELECT COUNT(*) FROM users WHERE NOT (email IS NOT NULL)"),
+        Value::Int64(429)
+    );
+    assert_eq!(
+        count("SELECT COUNT(*) FROM users WHERE email IS NULL AND id >= 1024 AND id < 2048"),
+        Value::Int64(146)
+    );
+}
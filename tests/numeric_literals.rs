@@ -0,0 +1,178 @@
+//! # Integration Tests for Numeric Literals
+//!
+//! These tests load a CSV file written partly in scientific notation and
+//! query it with negative and exponent literals on either side of
+//! comparisons, in IN lists and in SELECT expressions.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    ingest::load_csv,
+    types::{DataType, Value},
+};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/scientific.csv");
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn readings_catalog() -> Catalog {
+    let mut catalog = Catalog::new();
+    catalog
+        .register_table(load_csv(FIXTURE, "readings".to_string()).unwrap())
+        .unwrap();
+    catalog
+}
+
+fn ids(catalog: &Catalog, sql: &str) -> Vec<i64> {
+    execute_query(catalog, sql)
+        .unwrap()
+        .column("id")
+        .unwrap()
+        .into_iter()
+        .map(|value| match value {
+            Value::Int64(id) => id,
+            other => panic!("unexpected id {:?} for {}", other, sql),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_scientific_notation_loads_as_float64() {
+    let catalog = readings_catalog();
+    let table = catalog.get_table("readings").unwrap();
+
+    assert_eq!(table.get_column_type("reading").unwrap(), DataType::Float64);
+    assert_eq!(table.get_column_type("delta").unwrap(), DataType::Int64);
+    assert_eq!(
+        table.get_value("reading", 0).unwrap(),
+        Value::Float64(1.5e10)
+    );
+    assert_eq!(
+        table.get_value("reading", 1).unwrap(),
+        Value::Float64(-3.2e-4)
+    );
+    assert_eq!(table.get_value("reading", 4).unwrap(), Value::Float64(-7.0));
+    assert_eq!(table.get_value("delta", 2).unwrap(), Value::Int64(-12));
+}
+
+#[test]
+fn test_negative_literals_in_where() {
+    let catalog = readings_catalog();
+
+    assert_eq!(
+        ids(&catalog, "SELECT id FROM readings WHERE delta > -5"),
+        [2, 4, 5]
+    );
+    assert_eq!(
+        ids(&catalog, "SELECT id FROM readings WHERE -5 >= delta"),
+        [1, 3]
+    );
+    assert_eq!(
+        ids(&catalog, "SELECT id FROM readings WHERE reading < -0.5"),
+        [5]
+    );
+    assert_eq!(
+        ids(&catalog, "SELECT id FROM readings WHERE -0.0001 > reading"),
+        [2, 5]
+    );
+    assert_eq!(
+        ids(
+            &catalog,
+            "SELECT id FROM readings WHERE delta IN (-12, -5, 99) ORDER BY id"
+        ),
+        [1, 3]
+    );
+    assert_eq!(
+        ids(
+            &catalog,
+            "SELECT id FROM readings WHERE delta BETWEEN -12 AND -1"
+        ),
+        [1, 3]
+    );
+    assert_eq!(
+        ids(
+            &catalog,
+            "SELECT id FROM readings WHERE delta > -9223372036854775808"
+        ),
+        [1, 2, 3, 4, 5]
+    );
+}
+
+#[test]
+fn test_exponent_literals() {
+    let catalog = readings_catalog();
+
+    assert_eq!(
+        ids(&catalog, "SELECT id FROM readings WHERE reading > 1e3"),
+        [1, 3]
+    );
+    assert_eq!(
+        ids(
+            &catalog,
+            "SELECT id FROM readings WHERE reading <= 2.5E+3 AND reading > 1E-3"
+        ),
+        [3]
+    );
+
+    // An exponent makes a literal Float64 even without a decimal point
+    let result = execute_query(
+        &catalog,
+        "SELECT delta * 1e2 AS scaled, -2e-1 + delta FROM readings WHERE id = 3",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column_types(),
+        [DataType::Float64, DataType::Float64]
+    );
+    assert_eq!(
+        result.rows().next().unwrap(),
+        vec![Value::Float64(-1200.0), Value::Float64(-12.2)]
+    );
+}
+
+#[test]
+fn test_i64_min_literal_in_expressions() {
+    let catalog = readings_catalog();
+
+    // The minus belongs to the literal, so i64::MIN is in range
+    let result = execute_query(
+        &catalog,
+        "SELECT -9223372036854775808 AS lowest, delta + -9223372036854775808 FROM readings WHERE id = 2",
+    )
+    .unwrap();
+    assert_eq!(result.column_types(), [DataType::Int64, DataType::Int64]);
+    assert_eq!(
+        result.rows().next().unwrap(),
+        vec![Value::Int64(i64::MIN), Value::Int64(i64::MIN + 3)]
+    );
+
+    // Its magnitude alone still does not fit
+    assert!(execute_query(&catalog, "SELECT 9223372036854775808 FROM readings").is_err());
+}
+
+#[test]
+fn test_negative_aggregate_parameter() {
+    let catalog = readings_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT PERCENTILE(delta, -0) AS low, PERCENTILE(delta, 0) FROM readings",
+    )
+    .unwrap();
+    assert_eq!(
+        result.rows().next().unwrap(),
+        vec![Value::Float64(-12.0), Value::Float64(-12.0)]
+    );
+
+    // A negative quantile is out of range rather than a non-literal
+    let error = execute_query(&catalog, "SELECT PERCENTILE(delta, -0.5) FROM readings")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("[0, 1]"), "{}", error);
+}
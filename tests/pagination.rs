@@ -0,0 +1,172 @@
+//! # Integration Tests for LIMIT/OFFSET Pagination
+//!
+//! LIMIT and OFFSET over a plain table scan become the range of rows the
+//! scan reads. These tests page through a table larger than several scan
+//! batches and check the pages line up with the unpaginated result,
+//! including at batch boundaries and past the end of the table.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    column::{Column, IntColumn, StringColumn},
+    execute_query,
+    table::Table,
+    types::Value,
+};
+
+/// Rows in the test table: two full scan batches of 1024 and a partial one
+const ROWS: usize = 2500;
+
+/// Rows per batch of a table scan
+const BATCH_SIZE: usize = 1024;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn numbers_catalog() -> Catalog {
+    let mut id = IntColumn::new();
+    let mut label = StringColumn::new();
+    for n in 0..ROWS {
+        id.push_value(Value::Int64(n as i64)).unwrap();
+        label
+            .push_value(Value::String(format!("row {}", n)))
+            .unwrap();
+    }
+
+    let mut table = Table::new("numbers".to_string());
+    table.add_column("id".to_string(), Box::new(id)).unwrap();
+    table
+        .add_column("label".to_string(), Box::new(label))
+        .unwrap();
+
+    let mut catalog = Catalog::new();
+    catalog.register_table(table).unwrap();
+    catalog
+}
+
+fn query_rows(catalog: &Catalog, sql: &str) -> Vec<Vec<Value>> {
+    execute_query(catalog, sql).unwrap().rows().collect()
+}
+
+fn ids(rows: &[Vec<Value>]) -> Vec<i64> {
+    rows.iter()
+        .map(|row| match row[0] {
+            Value::Int64(id) => id,
+            ref other => panic!("expected an id, got {:?}", other),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_pages_concatenate_to_the_full_result() {
+    let catalog = numbers_catalog();
+    let full = query_rows(&catalog, "SELECT id, label FROM numbers");
+    assert_eq!(full.len(), ROWS);
+
+    // Pages aligned with the scan batches, and pages straddling them
+    for page_size in [BATCH_SIZE, 1000, 7] {
+        let mut pages = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = query_rows(
+                &catalog,
+                &format!(
+                    "SELECT id, label FROM numbers LIMIT {} OFFSET {}",
+                    page_size, offset
+                ),
+            );
+            if page.is_empty() {
+                break;
+            }
+            assert!(page.len() <= page_size);
+            offset += page.len();
+            pages.extend(page);
+        }
+        assert_eq!(pages, full, "pages of {} rows", page_size);
+    }
+}
+
+#[test]
+fn test_page_boundaries_at_batch_size_multiples() {
+    let catalog = numbers_catalog();
+
+    let page = query_rows(
+        &catalog,
+        &format!(
+            "SELECT id FROM numbers LIMIT {} OFFSET {}",
+            BATCH_SIZE, BATCH_SIZE
+        ),
+    );
+    assert_eq!(
+        ids(&page),
+        (BATCH_SIZE as i64..2 * BATCH_SIZE as i64).collect::<Vec<_>>()
+    );
+
+    // The last page holds what is left after two full batches
+    let page = query_rows(
+        &catalog,
+        &format!(
+            "SELECT id FROM numbers LIMIT {} OFFSET {}",
+            BATCH_SIZE,
+            2 * BATCH_SIZE
+        ),
+    );
+    assert_eq!(
+        ids(&page),
+        (2 * BATCH_SIZE as i64..ROWS as i64).collect::<Vec<_>>()
+    );
+
+    // One row either side of a batch boundary
+    let page = query_rows(
+        &catalog,
+        &format!("SELECT id FROM numbers LIMIT 2 OFFSET {}", BATCH_SIZE - 1),
+    );
+    assert_eq!(ids(&page), vec![BATCH_SIZE as i64 - 1, BATCH_SIZE as i64]);
+}
+
+#[test]
+fn test_offset_at_or_past_the_end_is_empty() {
+    let catalog = numbers_catalog();
+
+    for offset in [ROWS, ROWS + 1, 10 * ROWS] {
+        let result = execute_query(
+            &catalog,
+            &format!("SELECT id, label FROM numbers LIMIT 10 OFFSET {}", offset),
+        )
+        .unwrap();
+        assert_eq!(result.row_count(), 0);
+        assert_eq!(result.column_names(), ["id", "label"]);
+    }
+
+    let result =
+        execute_query(&catalog, &format!("SELECT * FROM numbers OFFSET {}", ROWS)).unwrap();
+    assert_eq!(result.row_count(), 0);
+}
+
+#[test]
+fn test_offset_without_limit_reads_to_the_end() {
+    let catalog = numbers_catalog();
+
+    let rows = query_rows(&catalog, "SELECT label, id FROM numbers OFFSET 2400");
+    assert_eq!(rows.len(), ROWS - 2400);
+    assert_eq!(
+        rows[0],
+        vec![Value::String("row 2400".to_string()), Value::Int64(2400)]
+    );
+}
+
+#[test]
+fn test_filtered_pages_still_skip_matching_rows() {
+    let catalog = numbers_catalog();
+
+    // WHERE keeps the Limit above the filter, so OFFSET counts matches
+    let page = query_rows(
+        &catalog,
+        "SELECT id FROM numbers WHERE id >= 2000 LIMIT 3 OFFSET 5",
+    );
+    assert_eq!(ids(&page), vec![2005, 2006, 2007]);
+}
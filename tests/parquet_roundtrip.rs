@@ -0,0 +1,148 @@
+//! # Integration Tests for Parquet Export and Import
+//!
+//! These tests export tables with `export_parquet`, load the file again
+//! under a new name with `load_parquet`, and check that the copy has the
+//! same schema and that queries over it return the same rows as queries
+//! over the original. They need the `parquet` feature:
+//! `cargo test --features parquet`.
+
+#![cfg(feature = "parquet")]
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    column::{BoolColumn, Column, DateColumn, FloatColumn, IntColumn, StringColumn},
+    datagen::{generate_table, TableSpec},
+    execute_query,
+    parquet::{export_parquet, load_parquet},
+    table::Table,
+    types::{DataType, Value},
+};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Exports `table` and loads the file back as `copy`
+fn round_trip(catalog: &mut Catalog, table: &str, copy: &str) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(format!("{}.parquet", table));
+    export_parquet(&catalog.get_table(table).unwrap(), &path).unwrap();
+    load_parquet(&path, copy.to_string(), catalog).unwrap();
+}
+
+fn schema(catalog: &Catalog, table: &str) -> Vec<(String, DataType)> {
+    let table = catalog.get_table(table).unwrap();
+    table
+        .column_names()
+        .into_iter()
+        .map(|name| {
+            let data_type = table.get_column_type(&name).unwrap();
+            (name, data_type)
+        })
+        .collect()
+}
+
+/// The rows of `sql` with `{}` replaced by the table name
+fn rows(catalog: &Catalog, sql: &str, table: &str) -> Vec<Vec<Value>> {
+    execute_query(catalog, &sql.replace("{}", table))
+        .unwrap()
+        .rows()
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_generated_table_round_trips() {
+    let mut catalog = Catalog::new();
+    // More rows than one row group holds
+    catalog
+        .register_table(generate_table("sales", 150_000, &TableSpec::benchmark(7)).unwrap())
+        .unwrap();
+    round_trip(&mut catalog, "sales", "imported");
+
+    assert_eq!(schema(&catalog, "imported"), schema(&catalog, "sales"));
+    for sql in [
+        "SELECT * FROM {} ORDER BY id",
+        "SELECT category, COUNT(*), SUM(value), MIN(price), MAX(price) FROM {} \
+         GROUP BY category ORDER BY category",
+        "SELECT customer, price FROM {} WHERE value < 10 ORDER BY id LIMIT 50",
+    ] {
+        let original = rows(&catalog, sql, "sales");
+        assert!(!original.is_empty(), "{}", sql);
+        assert_eq!(rows(&catalog, sql, "imported"), original, "{}", sql);
+    }
+}
+
+#[test]
+fn test_every_column_type_and_nulls_round_trip() {
+    let mut catalog = Catalog::new();
+    let text = |s: &str| Value::String(s.to_string());
+    let records = [
+        [
+            Value::Int64(1),
+            Value::Float64(0.5),
+            text("first"),
+            Value::Boolean(true),
+            Value::Date(19_753),
+        ],
+        [
+            Value::Int64(2),
+            Value::Null,
+            text("ünïcode, \"quoted\""),
+            Value::Boolean(false),
+            Value::Null,
+        ],
+        [
+            Value::Null,
+            Value::Float64(-1e300),
+            Value::Null,
+            Value::Null,
+            Value::Date(-1),
+        ],
+    ];
+    let mut columns: [(&str, Box<dyn Column>); 5] = [
+        ("id", Box::new(IntColumn::new())),
+        ("score", Box::new(FloatColumn::new())),
+        ("label", Box::new(StringColumn::new())),
+        ("active", Box::new(BoolColumn::new())),
+        ("seen", Box::new(DateColumn::new())),
+    ];
+    for record in records {
+        for ((_, column), value) in columns.iter_mut().zip(record) {
+            column.push_value(value).unwrap();
+        }
+    }
+    let mut events = Table::new("events".to_string());
+    for (name, column) in columns {
+        events.add_column(name.to_string(), column).unwrap();
+    }
+    catalog.register_table(events).unwrap();
+    round_trip(&mut catalog, "events", "events_copy");
+
+    assert_eq!(
+        schema(&catalog, "events_copy"),
+        vec![
+            ("id".to_string(), DataType::Int64),
+            ("score".to_string(), DataType::Float64),
+            ("label".to_string(), DataType::String),
+            ("active".to_string(), DataType::Boolean),
+            ("seen".to_string(), DataType::Date),
+        ]
+    );
+    let sql = "SELECT * FROM {}";
+    assert_eq!(
+        rows(&catalog, sql, "events_copy"),
+        rows(&catalog, sql, "events")
+    );
+    assert_eq!(
+        rows(
+            &catalog,
+            "SELECT COUNT(*) FROM {} WHERE label IS NULL",
+            "events_copy"
+        ),
+        vec![vec![Value::Int64(1)]]
+    );
+}
@@ -0,0 +1,252 @@
+//! # Integration Tests for the Plan Builder
+//!
+//! These tests build plans with `PlanBuilder` and check that each returns
+//! exactly what the equivalent SQL returns through the planner, and that
+//! unknown names and mismatched types are reported by the step naming them.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    plan_builder::{agg, col, lit, PlanBuilder},
+    planner::{PlannerError, QueryResult},
+    types::{DataType, SortDirection, Value},
+};
+
+mod common;
+use common::catalog_from_csv;
+
+const EMPLOYEES: &str = "\
+name,dept,age,salary,email
+Alice,eng,34,120.5,alice@x.io
+Bob,eng,45,99.0,
+Carol,sales,29,75.25,carol@x.io
+Dave,sales,52,80.0,dave@x.io
+Eve,eng,23,101.0,
+Frank,ops,41,66.5,frank@x.io
+Grace,ops,38,70.0,grace@x.io
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn employees_catalog() -> Catalog {
+    catalog_from_csv(&[("employees", EMPLOYEES)])
+}
+
+fn assert_same(built: QueryResult, sql: &str, catalog: &Catalog) {
+    let expected = execute_query(catalog, sql).unwrap();
+    assert_eq!(built.column_names(), expected.column_names(), "{}", sql);
+    assert_eq!(built.column_types(), expected.column_types(), "{}", sql);
+    assert_eq!(
+        built.rows().collect::<Vec<_>>(),
+        expected.rows().collect::<Vec<_>>(),
+        "{}",
+        sql
+    );
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_filter_and_project_match_sql() {
+    let catalog = employees_catalog();
+
+    let built = PlanBuilder::scan(&catalog, "employees")
+        .unwrap()
+        .filter(
+            col("age")
+                .gt(lit(30))
+                .and(col("dept").not_eq(lit("ops")).or(col("email").is_null())),
+        )
+        .unwrap()
+        .project(&["salary", "name"])
+        .unwrap()
+        .execute()
+        .unwrap();
+    assert_same(
+        built,
+        "SELECT salary, name FROM employees WHERE age > 30 AND (dept != 'ops' OR email IS NULL)",
+        &catalog,
+    );
+
+    let built = PlanBuilder::scan(&catalog, "employees")
+        .unwrap()
+        .filter(!(col("salary") * lit(2)).lt_eq(lit(160.5)))
+        .unwrap()
+        .project(&["name"])
+        .unwrap()
+        .execute()
+        .unwrap();
+    assert_same(
+        built,
+        "SELECT name FROM employees WHERE NOT salary * 2 <= 160.5",
+        &catalog,
+    );
+}
+
+#[test]
+fn test_aggregation_matches_sql() {
+    let catalog = employees_catalog();
+
+    let built = PlanBuilder::scan(&catalog, "employees")
+        .unwrap()
+        .filter(col("age").gt(lit(25)))
+        .unwrap()
+        .group_by(
+            &["dept"],
+            &[
+                agg::count_star(),
+                agg::sum("salary"),
+                agg::avg("age"),
+                agg::max("name"),
+                agg::count("email"),
+            ],
+        )
+        .unwrap()
+        .order_by("dept", SortDirection::Descending)
+        .unwrap()
+        .execute()
+        .unwrap();
+    assert_same(
+        built,
+        "SELECT dept, COUNT(*), SUM(salary), AVG(age), MAX(name), COUNT(email) \
+         FROM employees WHERE age > 25 GROUP BY dept ORDER BY dept DESC",
+        &catalog,
+    );
+
+    // No keys: one group for the whole table
+    let built = PlanBuilder::scan(&catalog, "employees")
+        .unwrap()
+        .group_by(&[], &[agg::count("dept").distinct(), agg::min("age")])
+        .unwrap()
+        .execute()
+        .unwrap();
+    assert_same(
+        built,
+        "SELECT COUNT(DISTINCT dept), MIN(age) FROM employees",
+        &catalog,
+    );
+}
+
+#[test]
+fn test_order_by_and_limit_match_sql() {
+    let catalog = employees_catalog();
+
+    // Consecutive order_by calls sort by several columns
+    let built = PlanBuilder::scan(&catalog, "employees")
+        .unwrap()
+        .order_by("dept", SortDirection::Ascending)
+        .unwrap()
+        .order_by("salary", SortDirection::Descending)
+        .unwrap()
+        .limit(4)
+        .project(&["dept", "name", "salary"])
+        .unwrap()
+        .execute()
+        .unwrap();
+    assert_same(
+        built,
+        "SELECT dept, name, salary FROM employees ORDER BY dept, salary DESC LIMIT 4",
+        &catalog,
+    );
+
+    let built = PlanBuilder::scan(&catalog, "employees")
+        .unwrap()
+        .limit(2)
+        .execute()
+        .unwrap();
+    assert_same(built, "SELECT * FROM employees LIMIT 2", &catalog);
+}
+
+#[test]
+fn test_aliases_and_columns() {
+    let catalog = employees_catalog();
+
+    let builder = PlanBuilder::scan(&catalog, "employees")
+        .unwrap()
+        .group_by(&["dept"], &[agg::avg("salary").alias("average")])
+        .unwrap();
+    assert_eq!(
+        builder.columns(),
+        [
+            ("dept".to_string(), DataType::String),
+            ("average".to_string(), DataType::Float64)
+        ]
+    );
+
+    // Later steps refer to the new names
+    let result = builder
+        .filter(col("average").gt(lit(100)))
+        .unwrap()
+        .execute()
+        .unwrap();
+    assert_eq!(
+        result.column("dept").unwrap(),
+        vec![Value::String("eng".to_string())]
+    );
+}
+
+#[test]
+fn test_unknown_names_are_reported_by_their_step() {
+    let catalog = employees_catalog();
+    let scan = || PlanBuilder::scan(&catalog, "employees").unwrap();
+
+    assert!(matches!(
+        PlanBuilder::scan(&catalog, "people").err(),
+        Some(PlannerError::TableNotFound(name)) if name == "people"
+    ));
+    assert!(matches!(
+        scan().filter(col("height").gt(lit(2))).err(),
+        Some(PlannerError::ColumnNotFound(name)) if name == "height"
+    ));
+    assert!(matches!(
+        scan().project(&["name", "salery"]).err(),
+        Some(PlannerError::ColumnNotFound(name)) if name == "salery"
+    ));
+
+    // A projected-away column is gone for later steps
+    let projected = scan().project(&["name"]).unwrap();
+    assert!(matches!(
+        projected.order_by("age", SortDirection::Ascending).err(),
+        Some(PlannerError::ColumnNotFound(name)) if name == "age"
+    ));
+    assert!(matches!(
+        scan().group_by(&["dept"], &[agg::sum("bonus")]).err(),
+        Some(PlannerError::ColumnNotFound(name)) if name == "bonus"
+    ));
+}
+
+#[test]
+fn test_type_mismatches_are_errors() {
+    let catalog = employees_catalog();
+    let scan = || PlanBuilder::scan(&catalog, "employees").unwrap();
+
+    match scan().filter(col("age").gt(lit("thirty"))).err() {
+        Some(PlannerError::TypeMismatch {
+            expression,
+            left,
+            right,
+        }) => {
+            assert_eq!(expression, "age > 'thirty'");
+            assert_eq!((left, right), (DataType::Int64, DataType::String));
+        }
+        other => panic!("expected a type mismatch, got {:?}", other),
+    }
+    assert!(matches!(
+        scan().filter((col("name") + lit(1)).eq(lit(2))).err(),
+        Some(PlannerError::TypeMismatch { .. })
+    ));
+
+    let err = scan()
+        .group_by(&["dept"], &[agg::sum("name")])
+        .err()
+        .unwrap();
+    assert!(
+        err.to_string().contains("SUM cannot be applied to String"),
+        "{}",
+        err
+    );
+}
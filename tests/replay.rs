@@ -0,0 +1,111 @@
+//! # Integration Tests for Workload Replay
+//!
+//! These tests record a short session against the CSV fixture in
+//! `tests/data`, save it to disk, and replay it: first against the same data,
+//! where every statement must match, then against a copy with one salary
+//! changed, where exactly the statements that read it must be reported.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    ingest::load_csv,
+    replay::{replay, Recording},
+};
+use std::fs;
+use std::path::Path;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/test_data.csv");
+
+/// Statements of the recorded session; only the last two read `salary`.
+const SESSION: &[&str] = &[
+    "SELECT name, age FROM emp WHERE age > 30",
+    "SELECT department, COUNT(*) FROM emp GROUP BY department",
+    "SELECT name, age FROM emp ORDER BY age DESC LIMIT 3",
+    "SELECT department, SUM(salary) FROM emp GROUP BY department",
+    "SELECT name, salary FROM emp WHERE salary > 80000",
+];
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn catalog_from(path: &Path) -> Catalog {
+    let mut catalog = Catalog::new();
+    catalog
+        .register_table(load_csv(path.to_str().unwrap(), "emp".to_string()).unwrap())
+        .unwrap();
+    catalog
+}
+
+fn record_session(catalog: &Catalog, path: &Path) {
+    let mut recording = Recording::new();
+    for sql in SESSION {
+        recording.execute(catalog, sql).unwrap();
+    }
+    recording.save(path).unwrap();
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_replay_against_same_data_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    let recording_path = dir.path().join("session.replay");
+    let catalog = catalog_from(Path::new(FIXTURE));
+    record_session(&catalog, &recording_path);
+
+    let recording = Recording::load(&recording_path).unwrap();
+    assert_eq!(recording.len(), SESSION.len());
+    assert_eq!(recording.statements()[0].sql, SESSION[0]);
+    assert_eq!(recording.statements()[1].row_count, 3);
+
+    let report = replay(&catalog_from(Path::new(FIXTURE)), &recording);
+    assert!(report.all_match(), "unexpected mismatches:\n{}", report);
+    assert_eq!(report.outcomes.len(), SESSION.len());
+    assert!(report.to_string().contains("5 of 5 statements matched"));
+}
+
+#[test]
+fn test_replay_against_mutated_data_lists_mismatches() {
+    let dir = tempfile::tempdir().unwrap();
+    let recording_path = dir.path().join("session.replay");
+    record_session(&catalog_from(Path::new(FIXTURE)), &recording_path);
+
+    // Henry's salary changes, but stays above the WHERE threshold
+    let mutated_path = dir.path().join("mutated.csv");
+    let mutated = fs::read_to_string(FIXTURE)
+        .unwrap()
+        .replace("Henry,45,105000.00", "Henry,45,99000.00");
+    fs::write(&mutated_path, mutated).unwrap();
+
+    let report = replay(
+        &catalog_from(&mutated_path),
+        &Recording::load(&recording_path).unwrap(),
+    );
+
+    let mismatched: Vec<&str> = report
+        .mismatches()
+        .iter()
+        .map(|o| o.recorded.sql.as_str())
+        .collect();
+    assert_eq!(mismatched, &SESSION[3..]);
+
+    // Same row counts, different contents
+    for outcome in report.mismatches() {
+        assert_eq!(outcome.row_count, Some(outcome.recorded.row_count));
+        assert!(outcome.error.is_none());
+    }
+    assert!(report.to_string().contains("MISMATCH"));
+}
+
+#[test]
+fn test_replay_reports_failing_statements() {
+    let dir = tempfile::tempdir().unwrap();
+    let recording_path = dir.path().join("session.replay");
+    record_session(&catalog_from(Path::new(FIXTURE)), &recording_path);
+
+    let report = replay(&Catalog::new(), &Recording::load(&recording_path).unwrap());
+    assert_eq!(report.mismatches().len(), SESSION.len());
+    assert!(report.outcomes.iter().all(|o| o.error.is_some()));
+}
@@ -0,0 +1,213 @@
+//! # Integration Tests for Serde Support
+//!
+//! These tests serialize values, types and parsed queries to JSON and read
+//! them back, checking that nothing is lost on the way. They need the
+//! `serde` feature: `cargo test --features serde`.
+
+#![cfg(feature = "serde")]
+
+use mini_rust_olap::catalog::Catalog;
+use mini_rust_olap::column::{Column, FloatColumn, IntColumn, StringColumn};
+use mini_rust_olap::execution::Batch;
+use mini_rust_olap::parser::{Parser, Query};
+use mini_rust_olap::planner::{execute_create_table, execute_insert};
+use mini_rust_olap::types::{DataType, SortDirection, Value};
+use serde_json::json;
+use std::sync::Arc;
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn round_trip(value: &Value) -> Value {
+    let text = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&text).unwrap()
+}
+
+fn parse(sql: &str) -> Query {
+    Parser::new(sql).parse().unwrap()
+}
+
+// ============================================================================
+// Values and Types
+// ============================================================================
+
+#[test]
+fn test_every_value_variant_round_trips() {
+    let values = [
+        Value::Int64(-42),
+        Value::Int64(i64::MAX),
+        Value::Float64(0.1),
+        Value::Float64(-1.5e300),
+        Value::String("héllo, \"world\"".to_string()),
+        Value::Boolean(true),
+        Value::Boolean(false),
+        Value::Date(19_000),
+        Value::Null,
+    ];
+    for value in &values {
+        assert_eq!(&round_trip(value), value);
+    }
+}
+
+#[test]
+fn test_int_and_float_stay_distinct() {
+    assert_eq!(
+        serde_json::to_value(Value::Int64(1)).unwrap(),
+        json!({ "Int64": 1 })
+    );
+    assert_eq!(
+        serde_json::to_value(Value::Float64(1.0)).unwrap(),
+        json!({ "Float64": 1.0 })
+    );
+    assert_eq!(serde_json::to_value(Value::Null).unwrap(), json!("Null"));
+
+    assert_eq!(round_trip(&Value::Int64(1)), Value::Int64(1));
+    assert_eq!(round_trip(&Value::Float64(1.0)), Value::Float64(1.0));
+    // A float written without a fraction still reads back as a float
+    let value: Value = serde_json::from_str(r#"{"Float64":1}"#).unwrap();
+    assert_eq!(value, Value::Float64(1.0));
+}
+
+#[test]
+fn test_non_finite_floats_are_strings() {
+    assert_eq!(
+        serde_json::to_value(Value::Float64(f64::INFINITY)).unwrap(),
+        json!({ "Float64": "inf" })
+    );
+    assert_eq!(
+        serde_json::to_value(Value::Float64(f64::NEG_INFINITY)).unwrap(),
+        json!({ "Float64": "-inf" })
+    );
+    assert_eq!(
+        serde_json::to_value(Value::Float64(f64::NAN)).unwrap(),
+        json!({ "Float64": "NaN" })
+    );
+
+    assert_eq!(
+        round_trip(&Value::Float64(f64::INFINITY)),
+        Value::Float64(f64::INFINITY)
+    );
+    assert_eq!(
+        round_trip(&Value::Float64(f64::NEG_INFINITY)),
+        Value::Float64(f64::NEG_INFINITY)
+    );
+    match round_trip(&Value::Float64(f64::NAN)) {
+        Value::Float64(v) => assert!(v.is_nan()),
+        other => panic!("expected NaN, got {:?}", other),
+    }
+
+    let err = serde_json::from_str::<Value>(r#"{"Float64":"lots"}"#).unwrap_err();
+    assert!(err.to_string().contains("lots"), "{}", err);
+}
+
+#[test]
+fn test_data_type_and_sort_direction_round_trip() {
+    for data_type in [
+        DataType::Int64,
+        DataType::Float64,
+        DataType::String,
+        DataType::Boolean,
+        DataType::Date,
+    ] {
+        let text = serde_json::to_string(&data_type).unwrap();
+        assert_eq!(serde_json::from_str::<DataType>(&text).unwrap(), data_type);
+    }
+    for direction in [SortDirection::Ascending, SortDirection::Descending] {
+        let text = serde_json::to_string(&direction).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SortDirection>(&text).unwrap(),
+            direction
+        );
+    }
+}
+
+// ============================================================================
+// Parsed Queries
+// ============================================================================
+
+#[test]
+fn test_parsed_queries_round_trip() {
+    let statements = [
+        "WITH big AS (SELECT dept, salary FROM staff WHERE salary > 1000) \
+         SELECT dept, COUNT(DISTINCT salary) AS n, AVG(salary) FROM big \
+         JOIN depts ON big.dept = depts.name \
+         WHERE NOT (dept IN ('a', 'b')) AND salary IS NOT NULL \
+         GROUP BY dept HAVING COUNT(*) > 1 ORDER BY n DESC, 1 LIMIT 5 OFFSET 2",
+        "SELECT CASE WHEN -age < 30 THEN 'young' ELSE 'old' END, \
+         CAST(zip AS TEXT), UPPER(name) FROM people WHERE id = $1 OR active = TRUE",
+        "INSERT INTO t VALUES (1, 'x', 2.5), (2, 'y', 3.5)",
+        "CREATE TABLE t (id INT, name TEXT, seen DATE)",
+        "CREATE TABLE t2 AS SELECT * FROM t",
+    ];
+    for sql in statements {
+        let query = parse(sql);
+        let text = serde_json::to_string(&query).unwrap();
+        let back: Query = serde_json::from_str(&text).unwrap();
+        assert_eq!(back, query, "{}", sql);
+    }
+}
+
+// ============================================================================
+// Batch Rows
+// ============================================================================
+
+#[test]
+fn test_batch_to_json_rows() {
+    let mut region = StringColumn::new();
+    let mut units = IntColumn::new();
+    let mut price = FloatColumn::new();
+    for (r, u, p) in [("east", 3, 2.5), ("west", 1, f64::NAN)] {
+        region.push_value(Value::String(r.to_string())).unwrap();
+        units.push_value(Value::Int64(u)).unwrap();
+        price.push_value(Value::Float64(p)).unwrap();
+    }
+    units.push_null();
+    region.push_null();
+    price.push_value(Value::Float64(1.0)).unwrap();
+
+    let batch = Batch::new(vec![Arc::new(region), Arc::new(units), Arc::new(price)]);
+    let names = ["region", "units", "price"].map(String::from);
+    assert_eq!(
+        batch.to_json_rows(&names),
+        json!([
+            { "region": "east", "units": 3, "price": 2.5 },
+            { "region": "west", "units": 1, "price": null },
+            { "region": null, "units": null, "price": 1.0 },
+        ])
+    );
+}
+
+#[test]
+fn test_query_batches_to_json_rows() {
+    let mut catalog = Catalog::new();
+    let Query::CreateTable(create) = parse("CREATE TABLE t (id INT, seen DATE)") else {
+        unreachable!()
+    };
+    execute_create_table(&mut catalog, &create).unwrap();
+    let Query::Insert(insert) = parse("INSERT INTO t VALUES (1, '2024-03-01')") else {
+        unreachable!()
+    };
+    execute_insert(&mut catalog, &insert).unwrap();
+    let result = catalog.query("SELECT id, seen FROM t").unwrap();
+    let rows: Vec<_> = result
+        .batches()
+        .iter()
+        .flat_map(|batch| {
+            batch
+                .to_json_rows(result.column_names())
+                .as_array()
+                .unwrap()
+                .clone()
+        })
+        .collect();
+    assert_eq!(rows, vec![json!({ "id": 1, "seen": "2024-03-01" })]);
+}
+
+#[test]
+#[should_panic(expected = "one name per column")]
+fn test_to_json_rows_needs_a_name_per_column() {
+    let mut ids = IntColumn::new();
+    ids.push_value(Value::Int64(1)).unwrap();
+    Batch::new(vec![Arc::new(ids)]).to_json_rows(&[]);
+}
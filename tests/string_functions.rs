@@ -0,0 +1,139 @@
+//! # Integration Tests for String Functions
+//!
+//! These tests load a small users table from CSV and call UPPER, LOWER,
+//! LENGTH and TRIM in the SELECT list and in WHERE, checking the values and
+//! types they produce and the errors for unknown functions and non-String
+//! arguments.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    types::{DataType, Value},
+};
+
+mod common;
+use common::{catalog_from_csv, text};
+
+const USERS: &str = "\
+id,name,city
+1,Alice,\"  Paris \"
+2,Bob,Berlin
+3,Charlotte,
+4,Maximilian,\" Rome\"
+";
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn users_catalog() -> Catalog {
+    catalog_from_csv(&[("users", USERS)])
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_function_in_where() {
+    let catalog = users_catalog();
+
+    let result = execute_query(&catalog, "SELECT name FROM users WHERE LENGTH(name) > 5").unwrap();
+    assert_eq!(
+        result.column("name").unwrap(),
+        vec![text("Charlotte"), text("Maximilian")]
+    );
+
+    let result = execute_query(&catalog, "SELECT id FROM users WHERE UPPER(name) = 'BOB'").unwrap();
+    assert_eq!(result.column("id").unwrap(), vec![Value::Int64(2)]);
+
+    // A NULL argument gives NULL, which no comparison matches
+    let result = execute_query(
+        &catalog,
+        "SELECT id FROM users WHERE TRIM(city) != 'Berlin'",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column("id").unwrap(),
+        vec![Value::Int64(1), Value::Int64(4)]
+    );
+}
+
+#[test]
+fn test_function_in_select_with_alias() {
+    let catalog = users_catalog();
+
+    let result = execute_query(
+        &catalog,
+        "SELECT UPPER(name) AS shout, lower(name), LENGTH(name) AS len, TRIM(city) AS city FROM users",
+    )
+    .unwrap();
+    assert_eq!(
+        result.column_names(),
+        ["shout", "LOWER(name)", "len", "city"]
+    );
+    assert_eq!(
+        result.column_types(),
+        [
+            DataType::String,
+            DataType::String,
+            DataType::Int64,
+            DataType::String
+        ]
+    );
+    assert_eq!(
+        result.rows().next().unwrap(),
+        vec![text("ALICE"), text("alice"), Value::Int64(5), text("Paris")]
+    );
+    assert_eq!(
+        result.column("city").unwrap(),
+        vec![text("Paris"), text("Berlin"), Value::Null, text("Rome")]
+    );
+}
+
+#[test]
+fn test_function_on_non_string_column_is_an_error() {
+    let catalog = users_catalog();
+
+    let err = execute_query(&catalog, "SELECT UPPER(id) FROM users").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("UPPER expects a String argument, got Int64"),
+        "{}",
+        err
+    );
+
+    let err = execute_query(&catalog, "SELECT name FROM users WHERE LENGTH(id) > 1").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("LENGTH expects a String argument, got Int64"),
+        "{}",
+        err
+    );
+
+    let err = execute_query(&catalog, "SELECT TRIM(name, city) FROM users").unwrap_err();
+    assert!(
+        err.to_string().contains("TRIM takes 1 argument, 2 given"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_unknown_function_lists_supported_ones() {
+    let catalog = users_catalog();
+
+    for sql in [
+        "SELECT REVERSE(name) FROM users",
+        "SELECT name FROM users WHERE REVERSE(name) = 'boB'",
+    ] {
+        let err = execute_query(&catalog, sql).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "Unknown function 'REVERSE'; supported functions are UPPER, LOWER, LENGTH, TRIM"
+            ),
+            "{}",
+            err
+        );
+    }
+}
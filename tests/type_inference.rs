@@ -0,0 +1,136 @@
+//! # Integration Tests for CSV Type Inference
+//!
+//! These tests load files whose later rows contradict the types suggested by
+//! their first rows, and check that each column takes the narrowest type
+//! holding every value, whether inference reads the whole file or a sample.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_query,
+    ingest::{load_csv, load_csv_with_options, CsvOptions},
+    table::Table,
+    types::{DataType, Value},
+};
+use std::fs;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/mixed_types.csv");
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn column_types(table: &Table) -> Vec<(String, DataType)> {
+    table
+        .column_names()
+        .into_iter()
+        .map(|name| {
+            let data_type = table.get_column_type(&name).unwrap();
+            (name, data_type)
+        })
+        .collect()
+}
+
+fn expected_types() -> Vec<(String, DataType)> {
+    [
+        ("id", DataType::Int64),
+        ("ratio", DataType::Float64),
+        ("code", DataType::String),
+        ("score", DataType::String),
+        ("active", DataType::Boolean),
+    ]
+    .into_iter()
+    .map(|(name, data_type)| (name.to_string(), data_type))
+    .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_mixed_columns_take_the_widest_type() {
+    let table = load_csv(FIXTURE, "mixed".to_string()).unwrap();
+    assert_eq!(column_types(&table), expected_types());
+
+    // `1,2,3.5` is Float64, so whole numbers load as floats
+    assert_eq!(table.get_value("ratio", 0).unwrap(), Value::Float64(1.0));
+    assert_eq!(table.get_value("ratio", 2).unwrap(), Value::Float64(3.5));
+
+    // `1,2,3.5,abc` and `10,20,N/A` keep every value as text
+    assert_eq!(
+        table.get_value("code", 0).unwrap(),
+        Value::String("1".to_string())
+    );
+    assert_eq!(
+        table.get_value("code", 3).unwrap(),
+        Value::String("abc".to_string())
+    );
+    assert_eq!(
+        table.get_value("score", 2).unwrap(),
+        Value::String("N/A".to_string())
+    );
+}
+
+#[test]
+fn test_values_past_the_sample_promote_their_column() {
+    // The first two rows look like Int64 columns throughout
+    let options = CsvOptions {
+        sample_rows: Some(2),
+        ..CsvOptions::default()
+    };
+    let mut catalog = Catalog::new();
+    load_csv_with_options(FIXTURE, "mixed".to_string(), &options, &mut catalog).unwrap();
+
+    let table = catalog.get_table("mixed").unwrap();
+    assert_eq!(column_types(&table), expected_types());
+    assert_eq!(table.row_count(), 4);
+
+    let result = execute_query(&catalog, "SELECT SUM(ratio) FROM mixed").unwrap();
+    assert_eq!(
+        result.rows().collect::<Vec<_>>(),
+        vec![vec![Value::Float64(10.5)]]
+    );
+}
+
+#[test]
+fn test_promotion_past_a_long_sample() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("late.csv");
+    let mut csv = String::from("n,flag\n");
+    for n in 0..1000 {
+        csv.push_str(&format!("{},true\n", n));
+    }
+    csv.push_str("1e3,2\n");
+    fs::write(&path, csv).unwrap();
+
+    let options = CsvOptions {
+        sample_rows: Some(10),
+        ..CsvOptions::default()
+    };
+    let mut catalog = Catalog::new();
+    load_csv_with_options(&path, "late".to_string(), &options, &mut catalog).unwrap();
+
+    let table = catalog.get_table("late").unwrap();
+    assert_eq!(table.get_column_type("n").unwrap(), DataType::Float64);
+    assert_eq!(table.get_value("n", 1000).unwrap(), Value::Float64(1000.0));
+    assert_eq!(table.get_column_type("flag").unwrap(), DataType::String);
+    assert_eq!(
+        table.get_value("flag", 1000).unwrap(),
+        Value::String("2".to_string())
+    );
+}
+
+#[test]
+fn test_failed_load_registers_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("header_only.csv");
+    fs::write(&path, "id,name\n").unwrap();
+
+    let mut catalog = Catalog::new();
+    assert!(
+        load_csv_with_options(&path, "t".to_string(), &CsvOptions::default(), &mut catalog)
+            .is_err()
+    );
+    assert!(!catalog.table_exists("t"));
+    assert_eq!(catalog.table_count(), 0);
+}
@@ -0,0 +1,209 @@
+//! # Integration Tests for Virtual Tables
+//!
+//! These tests register closure-backed virtual tables on a Catalog and query
+//! them through the SQL front end, checking that the regular operators
+//! compose on top of `VirtualScan` and that every query takes a fresh
+//! snapshot from the factory.
+
+use mini_rust_olap::{
+    catalog::Catalog,
+    execute_sql,
+    execution::Batch,
+    types::{DataType, Value},
+    virtual_table::VirtualTable,
+};
+use std::sync::{Arc, Mutex};
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// An application-side record exposed through a virtual table.
+struct Order {
+    id: i64,
+    region: &'static str,
+    amount: f64,
+}
+
+/// Registers `orders` as a virtual table over a shared Vec of orders.
+fn register_orders(catalog: &mut Catalog, orders: Arc<Mutex<Vec<Order>>>) {
+    let table = VirtualTable::new(
+        "orders".to_string(),
+        vec![
+            ("id".to_string(), DataType::Int64),
+            ("region".to_string(), DataType::String),
+            ("amount".to_string(), DataType::Float64),
+        ],
+        move || {
+            let rows: Vec<Vec<Value>> = orders
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|order| {
+                    vec![
+                        Value::Int64(order.id),
+                        Value::String(order.region.to_string()),
+                        Value::Float64(order.amount),
+                    ]
+                })
+                .collect();
+            Box::new(rows.into_iter())
+        },
+    )
+    .unwrap();
+    catalog.register_virtual_table(table).unwrap();
+}
+
+fn sample_orders() -> Vec<Order> {
+    vec![
+        Order {
+            id: 1,
+            region: "North",
+            amount: 10.0,
+        },
+        Order {
+            id: 2,
+            region: "South",
+            amount: 25.0,
+        },
+        Order {
+            id: 3,
+            region: "North",
+            amount: 40.0,
+        },
+        Order {
+            id: 4,
+            region: "South",
+            amount: 5.0,
+        },
+        Order {
+            id: 5,
+            region: "East",
+            amount: 60.0,
+        },
+    ]
+}
+
+/// Flattens batches into rows for order-insensitive comparisons.
+fn rows_of(batches: &[Batch]) -> Vec<Vec<Value>> {
+    let mut rows = Vec::new();
+    for batch in batches {
+        for row in 0..batch.row_count() {
+            rows.push(
+                (0..batch.column_count())
+                    .map(|col| batch.get(row, col).unwrap())
+                    .collect(),
+            );
+        }
+    }
+    rows
+}
+
+/// Collects `region -> value` pairs from a two-column result.
+fn region_totals(batches: &[Batch]) -> Vec<(String, Value)> {
+    let mut totals: Vec<(String, Value)> = rows_of(batches)
+        .into_iter()
+        .map(|row| (row[0].to_string(), row[1].clone()))
+        .collect();
+    totals.sort_by(|a, b| a.0.cmp(&b.0));
+    totals
+}
+
+// ============================================================================
+// Query Tests
+// ============================================================================
+
+#[test]
+fn test_virtual_table_where_and_group_by() {
+    let orders = Arc::new(Mutex::new(sample_orders()));
+    let mut catalog = Catalog::new();
+    register_orders(&mut catalog, Arc::clone(&orders));
+
+    let (names, batches) = execute_sql(
+        &catalog,
+        "SELECT region, SUM(amount) FROM orders WHERE amount > 8.0 GROUP BY region",
+    )
+    .unwrap();
+
+    assert_eq!(names.len(), 2);
+    assert_eq!(
+        region_totals(&batches),
+        vec![
+            ("East".to_string(), Value::Float64(60.0)),
+            ("North".to_string(), Value::Float64(50.0)),
+            ("South".to_string(), Value::Float64(25.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_virtual_table_projection_and_order_by() {
+    let orders = Arc::new(Mutex::new(sample_orders()));
+    let mut catalog = Catalog::new();
+    register_orders(&mut catalog, orders);
+
+    let (names, batches) = execute_sql(
+        &catalog,
+        "SELECT id FROM orders WHERE region = 'North' ORDER BY id DESC",
+    )
+    .unwrap();
+
+    assert_eq!(names, vec!["id".to_string()]);
+    assert_eq!(
+        rows_of(&batches),
+        vec![vec![Value::Int64(3)], vec![Value::Int64(1)]]
+    );
+}
+
+#[test]
+fn test_virtual_table_requery_sees_updated_data() {
+    let orders = Arc::new(Mutex::new(sample_orders()));
+    let mut catalog = Catalog::new();
+    register_orders(&mut catalog, Arc::clone(&orders));
+
+    let sql = "SELECT region, COUNT(*) FROM orders GROUP BY region";
+    let (_, before) = execute_sql(&catalog, sql).unwrap();
+    assert_eq!(
+        region_totals(&before),
+        vec![
+            ("East".to_string(), Value::Int64(1)),
+            ("North".to_string(), Value::Int64(2)),
+            ("South".to_string(), Value::Int64(2)),
+        ]
+    );
+
+    // The application mutates its data; nothing is re-registered
+    {
+        let mut orders = orders.lock().unwrap();
+        orders.retain(|order| order.region != "East");
+        orders.push(Order {
+            id: 6,
+            region: "West",
+            amount: 12.5,
+        });
+        orders.push(Order {
+            id: 7,
+            region: "North",
+            amount: 3.0,
+        });
+    }
+
+    let (_, after) = execute_sql(&catalog, sql).unwrap();
+    assert_eq!(
+        region_totals(&after),
+        vec![
+            ("North".to_string(), Value::Int64(3)),
+            ("South".to_string(), Value::Int64(2)),
+            ("West".to_string(), Value::Int64(1)),
+        ]
+    );
+}
+
+#[test]
+fn test_virtual_table_unknown_column_is_a_planning_error() {
+    let orders = Arc::new(Mutex::new(sample_orders()));
+    let mut catalog = Catalog::new();
+    register_orders(&mut catalog, orders);
+
+    assert!(execute_sql(&catalog, "SELECT missing FROM orders").is_err());
+}